@@ -0,0 +1,164 @@
+use super::{anvil::normalize_url, providers::Rpc};
+use alloy::{
+    consensus::{Transaction as ConsensusTransaction, Typed2718},
+    eips::{BlockId, eip2930::AccessList},
+    network::{TransactionBuilder, TransactionResponse},
+    primitives::{Address, B256},
+    providers::{DynProvider, Provider},
+    rpc::types::{Transaction, TransactionRequest},
+};
+
+/// Storage keys each contract in an access list warms, in list order.
+pub type AccessEntries = Vec<(Address, Vec<B256>)>;
+
+fn entries(list: &AccessList) -> AccessEntries {
+    list.iter()
+        .map(|item| (item.address, item.storage_keys.clone()))
+        .collect()
+}
+
+/// The access list a transaction was sent with; legacy transactions have none.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeclaredAccessList {
+    pub transaction_type: u8,
+    pub entries: Option<AccessEntries>,
+}
+
+/// Result of `eth_createAccessList` for the transaction replayed as a request
+/// against the state before its block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedAccessList {
+    pub block: BlockId,
+    pub entries: AccessEntries,
+    pub gas_with: u64,
+    /// `eth_estimateGas` for the same request without a list.
+    pub gas_without: Result<u64, String>,
+}
+
+impl GeneratedAccessList {
+    /// Gas saved by sending the list; negative when it costs more than it warms.
+    pub fn savings(&self) -> Option<i128> {
+        let without = *self.gas_without.as_ref().ok()?;
+        Some(i128::from(without) - i128::from(self.gas_with))
+    }
+}
+
+/// State of the Access List tab for the selected transaction.
+#[derive(Debug, Default)]
+pub struct AccessListView {
+    /// Transaction the tab was loaded for.
+    pub hash: Option<String>,
+    pub loading: bool,
+    pub declared: Option<Result<DeclaredAccessList, String>>,
+    pub generating: bool,
+    pub generated: Option<Result<GeneratedAccessList, String>>,
+}
+
+impl AccessListView {
+    pub fn needs_load(&self, hash: &str) -> bool {
+        self.hash.as_deref() != Some(hash)
+    }
+}
+
+async fn load_transaction(
+    rpc: &Rpc,
+    rpc_url: &str,
+    hash: &str,
+) -> Result<(DynProvider, Transaction), String> {
+    let tx_hash: B256 = hash
+        .parse()
+        .map_err(|_| "invalid transaction hash".to_string())?;
+    let provider = rpc
+        .connect(&normalize_url(rpc, rpc_url))
+        .await
+        .map_err(|err| format!("{err:#}"))?;
+    rpc.stats().record_api_call("rpc");
+    let transaction = provider
+        .get_transaction_by_hash(tx_hash)
+        .await
+        .map_err(|err| format!("failed to load transaction: {err}"))?
+        .ok_or("transaction not found on this RPC")?;
+    Ok((provider, transaction))
+}
+
+pub async fn fetch_declared(
+    rpc: Rpc,
+    rpc_url: String,
+    hash: String,
+) -> Result<DeclaredAccessList, String> {
+    let (_, transaction) = load_transaction(&rpc, &rpc_url, &hash).await?;
+    Ok(DeclaredAccessList {
+        transaction_type: transaction.ty(),
+        entries: transaction.access_list().map(entries),
+    })
+}
+
+/// Replay the transaction's call (sender, target, calldata, value) through
+/// `eth_createAccessList` and `eth_estimateGas` at the parent block, so the
+/// list reflects the state the transaction actually ran against.
+pub async fn generate(
+    rpc: Rpc,
+    rpc_url: String,
+    hash: String,
+) -> Result<GeneratedAccessList, String> {
+    let (provider, transaction) = load_transaction(&rpc, &rpc_url, &hash).await?;
+    let block = match transaction.block_number {
+        Some(number) => BlockId::number(number.saturating_sub(1)),
+        None => BlockId::latest(),
+    };
+    let mut request = TransactionRequest::default()
+        .with_from(transaction.from())
+        .with_input(transaction.input().clone())
+        .with_value(transaction.value());
+    if let Some(to) = transaction.to() {
+        request = request.with_to(to);
+    }
+
+    rpc.stats().record_api_call("rpc");
+    let created = provider
+        .create_access_list(&request)
+        .block_id(block)
+        .await
+        .map_err(|err| format!("eth_createAccessList failed: {err}"))?
+        .ensure_ok()
+        .map_err(|err| format!("eth_createAccessList: {err}"))?;
+    rpc.stats().record_api_call("rpc");
+    let gas_without = provider
+        .estimate_gas(request)
+        .block(block)
+        .await
+        .map_err(|err| format!("eth_estimateGas failed: {err}"));
+    Ok(GeneratedAccessList {
+        block,
+        entries: entries(&created.access_list),
+        gas_with: created.gas_used.saturating_to(),
+        gas_without,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::eips::eip2930::AccessListItem;
+
+    #[test]
+    fn flattens_list_and_compares_gas() {
+        let list = AccessList(vec![AccessListItem {
+            address: Address::repeat_byte(0xaa),
+            storage_keys: vec![B256::repeat_byte(1), B256::repeat_byte(2)],
+        }]);
+        let entries = entries(&list);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].1.len(), 2);
+
+        let mut generated = GeneratedAccessList {
+            block: BlockId::latest(),
+            entries,
+            gas_with: 50_000,
+            gas_without: Ok(52_100),
+        };
+        assert_eq!(generated.savings(), Some(2_100));
+        generated.gas_without = Err("reverted".into());
+        assert_eq!(generated.savings(), None);
+    }
+}
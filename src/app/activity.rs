@@ -0,0 +1,152 @@
+use super::{AddressTransactionRow, TransactionDirection, TransactionStatus};
+use alloy::primitives::I256;
+
+const DAY: u64 = 24 * 60 * 60;
+
+/// Most buckets the chart keeps; older ones are dropped.
+pub const MAX_BUCKETS: usize = 60;
+
+/// Histories spanning more than this many days are charted per week.
+const DAILY_SPAN_DAYS: u64 = 31;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BucketSize {
+    Day,
+    Week,
+}
+
+impl BucketSize {
+    pub fn seconds(self) -> u64 {
+        match self {
+            BucketSize::Day => DAY,
+            BucketSize::Week => 7 * DAY,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BucketSize::Day => "day",
+            BucketSize::Week => "week",
+        }
+    }
+}
+
+/// Transactions and net ETH moved in one day or week.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActivityBucket {
+    /// Unix seconds the bucket starts at.
+    pub start: u64,
+    pub transactions: u64,
+    /// ETH received minus ETH sent, excluding gas.
+    pub net_wei: I256,
+}
+
+/// The fetched history bucketed by time, oldest first, with empty buckets
+/// kept so gaps show.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Activity {
+    pub size: BucketSize,
+    pub buckets: Vec<ActivityBucket>,
+    /// Rows left out because their source reported no timestamp.
+    pub undated: usize,
+}
+
+fn net_flow(row: &AddressTransactionRow) -> I256 {
+    if row.status == TransactionStatus::Failed {
+        return I256::ZERO;
+    }
+    let value = I256::try_from(row.value_wei).unwrap_or(I256::MAX);
+    match row.direction {
+        TransactionDirection::Incoming => value,
+        TransactionDirection::Outgoing => -value,
+        TransactionDirection::SelfTransfer | TransactionDirection::Interaction => I256::ZERO,
+    }
+}
+
+/// Bucket `rows` per day, or per week when they span more than a month.
+/// `None` when no row carries a timestamp.
+pub fn bucket_activity(rows: &[AddressTransactionRow]) -> Option<Activity> {
+    let dated: Vec<(u64, &AddressTransactionRow)> = rows
+        .iter()
+        .filter_map(|row| Some((row.timestamp?, row)))
+        .collect();
+    let first = dated.iter().map(|(timestamp, _)| *timestamp).min()?;
+    let last = dated.iter().map(|(timestamp, _)| *timestamp).max()?;
+    let size = if last - first > DAILY_SPAN_DAYS * DAY {
+        BucketSize::Week
+    } else {
+        BucketSize::Day
+    };
+    let width = size.seconds();
+    let first_bucket = (first / width).max((last / width).saturating_sub(MAX_BUCKETS as u64 - 1));
+    let mut buckets: Vec<ActivityBucket> = (first_bucket..=last / width)
+        .map(|index| ActivityBucket {
+            start: index * width,
+            transactions: 0,
+            net_wei: I256::ZERO,
+        })
+        .collect();
+    for (timestamp, row) in dated {
+        let Some(offset) = (timestamp / width).checked_sub(first_bucket) else {
+            continue;
+        };
+        let bucket = &mut buckets[offset as usize];
+        bucket.transactions += 1;
+        bucket.net_wei += net_flow(row);
+    }
+    Some(Activity {
+        size,
+        buckets,
+        undated: rows.len() - rows.iter().filter(|row| row.timestamp.is_some()).count(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::U256;
+
+    fn row(day: u64, direction: TransactionDirection, value: u64) -> AddressTransactionRow {
+        AddressTransactionRow {
+            hash: format!("0x{day:02x}"),
+            from: "0xaa".into(),
+            to: None,
+            value_wei: U256::from(value),
+            block_number: None,
+            direction,
+            counterparty: String::new(),
+            value_display: String::new(),
+            status: TransactionStatus::Success,
+            calldata: None,
+            timestamp: Some(day * DAY + 60),
+        }
+    }
+
+    #[test]
+    fn buckets_per_day_with_gaps_and_net_flow() {
+        let mut undated = row(0, TransactionDirection::Incoming, 1);
+        undated.timestamp = None;
+        let rows = vec![
+            row(12, TransactionDirection::Incoming, 10),
+            row(12, TransactionDirection::Outgoing, 4),
+            row(10, TransactionDirection::Interaction, 7),
+            undated,
+        ];
+        let activity = bucket_activity(&rows).unwrap();
+        assert_eq!(activity.size, BucketSize::Day);
+        assert_eq!(activity.undated, 1);
+        let counts: Vec<u64> = activity.buckets.iter().map(|b| b.transactions).collect();
+        assert_eq!(counts, vec![1, 0, 2]);
+        assert_eq!(activity.buckets[2].net_wei, I256::try_from(6).unwrap());
+        assert_eq!(activity.buckets[0].net_wei, I256::ZERO);
+
+        let spread = vec![
+            row(0, TransactionDirection::Incoming, 1),
+            row(700, TransactionDirection::Incoming, 1),
+        ];
+        let weekly = bucket_activity(&spread).unwrap();
+        assert_eq!(weekly.size, BucketSize::Week);
+        assert_eq!(weekly.buckets.len(), MAX_BUCKETS);
+        assert_eq!(weekly.buckets.last().unwrap().transactions, 1);
+    }
+}
@@ -1,36 +1,206 @@
+use super::{
+    explorer::AddressTransaction,
+    nonces,
+    providers::{BoxFuture, Rpc, RpcConnector},
+    retry::{self, TimedOut},
+    ws::{self, WsConnections},
+};
 use alloy::{
-    eips::{BlockId, BlockNumberOrTag},
-    primitives::{Address, U256},
-    providers::{Provider, ProviderBuilder},
+    consensus::Transaction as _,
+    eips::{BlockId, BlockNumberOrTag, eip7702::constants::EIP7702_DELEGATION_DESIGNATOR},
+    network::TransactionResponse,
+    primitives::{Address, B256, Bytes, U256},
+    providers::{DynProvider, Provider, ProviderBuilder},
+};
+use color_eyre::{
+    Result,
+    eyre::{WrapErr, eyre},
+};
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, HashSet},
+    future::Future,
+    sync::Mutex,
+    time::{Duration, Instant},
 };
-use color_eyre::{Result, eyre::WrapErr};
+use tokio::time::timeout;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Budget for one endpoint before failing over to the next configured one.
+const ATTEMPT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Chain ids reported by endpoints this session, keyed by URL. Validating an
+/// RPC URL on save fills this, so hydration can compare without another call.
+static CHAIN_IDS: Mutex<BTreeMap<String, u64>> = Mutex::new(BTreeMap::new());
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct AccountOverview {
     pub latest_block: u64,
     pub balance_wei: U256,
     pub transaction_count: u64,
+    /// Nonce including transactions the node holds as pending.
+    pub pending_transaction_count: u64,
+    /// Nonces queued behind a gap, when the node exposes its txpool.
+    pub queued_nonces: Option<Vec<u64>>,
     pub is_contract: bool,
+    /// EIP-7702 delegate when the account is an EOA with a delegation designator.
+    pub delegate: Option<Address>,
+    /// Deployed code, kept for matching against local artifacts.
+    #[serde(skip)]
+    pub code: Bytes,
+}
+
+/// EIP-4844 details of a type-3 transaction. Receipt fields are `None` while
+/// pending or when the node predates Cancun.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BlobDetails {
+    pub versioned_hashes: Vec<B256>,
+    pub max_fee_per_blob_gas: Option<u128>,
+    pub blob_gas_used: Option<u64>,
+    /// Blob base fee the transaction paid per unit of blob gas.
+    pub blob_gas_price: Option<u128>,
+}
+
+impl BlobDetails {
+    /// Total paid for blob gas, in wei.
+    pub fn fee_wei(&self) -> Option<U256> {
+        Some(U256::from(self.blob_gas_used?) * U256::from(self.blob_gas_price?))
+    }
+}
+
+/// Header fields and transaction hashes of one block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockSummary {
+    pub number: u64,
+    pub hash: B256,
+    pub parent_hash: B256,
+    pub timestamp: u64,
+    pub miner: Address,
+    pub gas_used: u64,
+    pub gas_limit: u64,
+    pub base_fee_per_gas: Option<u64>,
+    pub transactions: Vec<B256>,
+}
+
+/// Where a mined transaction sits in its block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BlockContext {
+    pub block_number: u64,
+    pub timestamp: u64,
+    pub index: usize,
+    /// Every transaction hash of the block, in order.
+    pub hashes: Vec<B256>,
+}
+
+impl BlockContext {
+    /// Hash of the transaction before (or after) this one in the block.
+    pub fn neighbor(&self, forward: bool) -> Option<B256> {
+        let index = if forward {
+            self.index.checked_add(1)?
+        } else {
+            self.index.checked_sub(1)?
+        };
+        self.hashes.get(index).copied()
+    }
+}
+
+/// The address an EIP-7702 delegation designator (`0xef0100 ‖ address`)
+/// points at, or `None` for regular code.
+pub fn delegation_target(code: &[u8]) -> Option<Address> {
+    let target = code.strip_prefix(&EIP7702_DELEGATION_DESIGNATOR)?;
+    (target.len() == Address::len_bytes()).then(|| Address::from_slice(target))
 }
 
-fn normalize_url(rpc_url: &str) -> String {
-    if rpc_url == "MOCK" {
-        std::env::var("EVM_TUI_TEST_RPC").unwrap_or_else(|_| rpc_url.to_string())
+fn resolve_mock(url: &str) -> String {
+    if url == "MOCK" {
+        std::env::var("EVM_TUI_TEST_RPC").unwrap_or_else(|_| url.to_string())
     } else {
-        rpc_url.to_string()
+        url.to_string()
     }
 }
 
-async fn connect_provider(rpc_url: &str) -> Result<impl Provider> {
-    ProviderBuilder::new()
-        .connect(rpc_url)
+/// The endpoint to hold a long-lived provider on: the first configured URL
+/// that did not fail its last request.
+pub(super) fn normalize_url(rpc: &Rpc, rpc_urls: &str) -> String {
+    let preferred = rpc
+        .health()
+        .attempt_order(rpc_urls)
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| rpc_urls.trim().to_string());
+    resolve_mock(&preferred)
+}
+
+/// Run `request` against each configured endpoint until one succeeds,
+/// recording latency and errors per endpoint. Any error or a timeout moves on
+/// to the next URL; when all of them fail on a transient error the round is
+/// retried under [`retry::NETWORK`], else the last error is returned.
+pub(super) async fn with_failover<T, F, Fut>(rpc: &Rpc, rpc_urls: &str, request: F) -> Result<T>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    retry::NETWORK
+        .run(|| failover_round(rpc, rpc_urls, &request))
         .await
-        .wrap_err_with(|| format!("failed to connect to RPC provider at {rpc_url}"))
 }
 
-pub async fn fetch_account_overview(rpc_url: &str, target: Address) -> Result<AccountOverview> {
-    let url = normalize_url(rpc_url);
-    let provider = connect_provider(&url).await?;
+async fn failover_round<T, F, Fut>(rpc: &Rpc, rpc_urls: &str, request: &F) -> Result<T>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let health = rpc.health();
+    let mut last_error = None;
+    for url in health.attempt_order(rpc_urls) {
+        let started = Instant::now();
+        let resolved = resolve_mock(&url);
+        let error = match timeout(ATTEMPT_TIMEOUT, request(resolved.clone())).await {
+            Ok(Ok(value)) => {
+                health.record_success(&url, started.elapsed());
+                return Ok(value);
+            }
+            Ok(Err(err)) => err,
+            Err(_) => TimedOut(format!("RPC request to {url} timed out")).into(),
+        };
+        health.record_failure(&url, format!("{error:#}"));
+        if ws::is_ws(&resolved) {
+            rpc.ws().invalidate(&resolved, format!("{error:#}"));
+        }
+        last_error = Some(error);
+    }
+    Err(last_error.unwrap_or_else(|| eyre!("no RPC endpoint configured")))
+}
+
+/// Dials the configured nodes. WebSocket endpoints reuse the session's
+/// shared connection; HTTP ones are cheap to open per request.
+pub struct NodeConnector(pub WsConnections);
+
+impl RpcConnector for NodeConnector {
+    fn connect<'a>(&'a self, rpc_url: &'a str) -> BoxFuture<'a, Result<DynProvider>> {
+        Box::pin(async move {
+            if ws::is_ws(rpc_url) {
+                return self.0.provider(rpc_url).await;
+            }
+            ProviderBuilder::new()
+                .connect(rpc_url)
+                .await
+                .map(Provider::erased)
+                .wrap_err_with(|| format!("failed to connect to RPC provider at {rpc_url}"))
+        })
+    }
+}
+
+pub async fn fetch_account_overview(
+    rpc: &Rpc,
+    rpc_url: &str,
+    target: Address,
+) -> Result<AccountOverview> {
+    with_failover(rpc, rpc_url, |url| account_overview(rpc, url, target)).await
+}
+
+async fn account_overview(rpc: &Rpc, url: String, target: Address) -> Result<AccountOverview> {
+    rpc.stats().record_api_call("rpc");
+    let provider = rpc.connect(&url).await?;
 
     let latest_block = provider
         .get_block_number()
@@ -49,27 +219,339 @@ pub async fn fetch_account_overview(rpc_url: &str, target: Address) -> Result<Ac
         .await
         .wrap_err("failed to query transaction count")?;
 
+    let pending_transaction_count = provider
+        .get_transaction_count(target)
+        .block_id(BlockId::Number(BlockNumberOrTag::Pending))
+        .await
+        .wrap_err("failed to query pending transaction count")?;
+
     let code = provider
         .get_code_at(target)
         .block_id(BlockId::Number(BlockNumberOrTag::Latest))
         .await
         .wrap_err("failed to query account code")?;
 
+    let delegate = delegation_target(&code);
+    let queued_nonces = if code.is_empty() || delegate.is_some() {
+        nonces::queued_nonces(rpc.stats(), &provider, target).await
+    } else {
+        None
+    };
     Ok(AccountOverview {
         latest_block,
         balance_wei,
         transaction_count,
-        is_contract: !code.is_empty(),
+        pending_transaction_count,
+        queued_nonces,
+        is_contract: !code.is_empty() && delegate.is_none(),
+        delegate,
+        code,
     })
 }
 
-pub async fn fetch_latest_block(rpc_url: &str) -> Result<u64> {
-    let url = normalize_url(rpc_url);
-    let provider = connect_provider(&url).await?;
-    provider
-        .get_block_number()
-        .await
-        .wrap_err("failed to query latest block number")
+pub async fn fetch_latest_block(rpc: &Rpc, rpc_url: &str) -> Result<u64> {
+    with_failover(rpc, rpc_url, |url| async move {
+        rpc.stats().record_api_call("rpc");
+        let provider = rpc.connect(&url).await?;
+        provider
+            .get_block_number()
+            .await
+            .wrap_err("failed to query latest block number")
+    })
+    .await
+}
+
+/// Read `slots` at one block so the values are consistent with each other.
+/// Returns the block number alongside the values, in input order.
+pub async fn fetch_storage_slots(
+    rpc: &Rpc,
+    rpc_url: &str,
+    slots: Vec<(Address, U256)>,
+) -> Result<(u64, Vec<B256>)> {
+    with_failover(rpc, rpc_url, |url| {
+        let slots = slots.clone();
+        async move {
+            rpc.stats().record_api_call("rpc");
+            let provider = rpc.connect(&url).await?;
+            let block = provider
+                .get_block_number()
+                .await
+                .wrap_err("failed to query latest block number")?;
+            let mut values = Vec::with_capacity(slots.len());
+            for (address, slot) in slots {
+                rpc.stats().record_api_call("rpc");
+                let value = provider
+                    .get_storage_at(address, slot)
+                    .block_id(block.into())
+                    .await
+                    .wrap_err_with(|| format!("failed to read slot {slot:#x} of {address}"))?;
+                values.push(B256::from(value));
+            }
+            Ok((block, values))
+        }
+    })
+    .await
+}
+
+/// Block number and timestamp of a mined transaction; `None` while pending
+/// or when the node does not know the hash.
+pub async fn fetch_transaction_inclusion(
+    rpc: &Rpc,
+    rpc_url: &str,
+    hash: &str,
+) -> Result<Option<(u64, u64)>> {
+    let hash: B256 = hash.parse().wrap_err("invalid transaction hash")?;
+    with_failover(rpc, rpc_url, |url| async move {
+        rpc.stats().record_api_call("rpc");
+        let provider = rpc.connect(&url).await?;
+        let Some(block) = provider
+            .get_transaction_by_hash(hash)
+            .await
+            .wrap_err("failed to load transaction")?
+            .and_then(|tx| tx.block_number)
+        else {
+            return Ok(None);
+        };
+        rpc.stats().record_api_call("rpc");
+        let header = provider
+            .get_block_by_number(block.into())
+            .await
+            .wrap_err_with(|| format!("failed to load block {block}"))?;
+        Ok(header.map(|block| (block.header.number, block.header.timestamp)))
+    })
+    .await
+}
+
+/// Block position of `hash`; `None` while pending or when the node does not
+/// know the hash.
+pub async fn fetch_block_context(
+    rpc: &Rpc,
+    rpc_url: &str,
+    hash: &str,
+) -> Result<Option<BlockContext>> {
+    let hash: B256 = hash.parse().wrap_err("invalid transaction hash")?;
+    with_failover(rpc, rpc_url, |url| async move {
+        rpc.stats().record_api_call("rpc");
+        let provider = rpc.connect(&url).await?;
+        let Some((block_number, index)) = provider
+            .get_transaction_by_hash(hash)
+            .await
+            .wrap_err("failed to load transaction")?
+            .and_then(|tx| Some((tx.block_number?, tx.transaction_index?)))
+        else {
+            return Ok(None);
+        };
+        rpc.stats().record_api_call("rpc");
+        let Some(block) = provider
+            .get_block_by_number(block_number.into())
+            .await
+            .wrap_err_with(|| format!("failed to load block {block_number}"))?
+        else {
+            return Ok(None);
+        };
+        Ok(Some(BlockContext {
+            block_number,
+            timestamp: block.header.timestamp,
+            index: index as usize,
+            hashes: block.transactions.hashes().collect(),
+        }))
+    })
+    .await
+}
+
+/// Load one block's header and transaction hashes; `None` when the node
+/// does not know it.
+pub async fn fetch_block(rpc: &Rpc, rpc_url: &str, block: BlockId) -> Result<Option<BlockSummary>> {
+    with_failover(rpc, rpc_url, |url| async move {
+        rpc.stats().record_api_call("rpc");
+        let provider = rpc.connect(&url).await?;
+        let Some(block) = provider
+            .get_block(block)
+            .await
+            .wrap_err_with(|| format!("failed to load block {block}"))?
+        else {
+            return Ok(None);
+        };
+        Ok(Some(BlockSummary {
+            number: block.header.number,
+            hash: block.header.hash,
+            parent_hash: block.header.parent_hash,
+            timestamp: block.header.timestamp,
+            miner: block.header.beneficiary,
+            gas_used: block.header.gas_used,
+            gas_limit: block.header.gas_limit,
+            base_fee_per_gas: block.header.base_fee_per_gas,
+            transactions: block.transactions.hashes().collect(),
+        }))
+    })
+    .await
+}
+
+/// Whether the node knows a transaction with this hash, mined or pending.
+pub async fn transaction_exists(rpc: &Rpc, rpc_url: &str, hash: B256) -> Result<bool> {
+    with_failover(rpc, rpc_url, |url| async move {
+        rpc.stats().record_api_call("rpc");
+        let provider = rpc.connect(&url).await?;
+        let transaction = provider
+            .get_transaction_by_hash(hash)
+            .await
+            .wrap_err("failed to load transaction")?;
+        Ok(transaction.is_some())
+    })
+    .await
+}
+
+/// Size of the deployed code at each of `targets`, from one endpoint.
+pub async fn fetch_code_sizes(
+    rpc: &Rpc,
+    rpc_url: &str,
+    targets: Vec<Address>,
+) -> Result<Vec<usize>> {
+    with_failover(rpc, rpc_url, |url| {
+        let targets = targets.clone();
+        async move {
+            let provider = rpc.connect(&url).await?;
+            let mut sizes = Vec::with_capacity(targets.len());
+            for target in targets {
+                rpc.stats().record_api_call("rpc");
+                let code = provider
+                    .get_code_at(target)
+                    .await
+                    .wrap_err_with(|| format!("failed to query code at {target}"))?;
+                sizes.push(code.len());
+            }
+            Ok(sizes)
+        }
+    })
+    .await
+}
+
+/// Blob details of `hash`; `None` for anything but a type-3 transaction or
+/// when the node does not know the hash.
+pub async fn fetch_blob_details(
+    rpc: &Rpc,
+    rpc_url: &str,
+    hash: &str,
+) -> Result<Option<BlobDetails>> {
+    let hash: B256 = hash.parse().wrap_err("invalid transaction hash")?;
+    with_failover(rpc, rpc_url, |url| async move {
+        rpc.stats().record_api_call("rpc");
+        let provider = rpc.connect(&url).await?;
+        let Some(transaction) = provider
+            .get_transaction_by_hash(hash)
+            .await
+            .wrap_err("failed to load transaction")?
+        else {
+            return Ok(None);
+        };
+        let Some(versioned_hashes) = transaction.blob_versioned_hashes() else {
+            return Ok(None);
+        };
+        rpc.stats().record_api_call("rpc");
+        let receipt = provider
+            .get_transaction_receipt(hash)
+            .await
+            .wrap_err("failed to load receipt")?;
+        Ok(Some(BlobDetails {
+            versioned_hashes: versioned_hashes.to_vec(),
+            max_fee_per_blob_gas: transaction.max_fee_per_blob_gas(),
+            blob_gas_used: receipt.as_ref().and_then(|receipt| receipt.blob_gas_used),
+            blob_gas_price: receipt.and_then(|receipt| receipt.blob_gas_price),
+        }))
+    })
+    .await
+}
+
+pub async fn fetch_chain_id(rpc: &Rpc, rpc_url: &str) -> Result<u64> {
+    let chain_id = with_failover(rpc, rpc_url, |url| async move {
+        rpc.stats().record_api_call("rpc");
+        let provider = rpc.connect(&url).await?;
+        provider
+            .get_chain_id()
+            .await
+            .wrap_err("failed to query chain id")
+    })
+    .await?;
+    if let Ok(mut cache) = CHAIN_IDS.lock() {
+        cache.insert(rpc_url.to_string(), chain_id);
+    }
+    Ok(chain_id)
+}
+
+/// Transactions in blocks `from..=to` sent by or to `target` (or whose hash
+/// is in `also`), newest first, with their receipt status.
+pub async fn fetch_block_transactions(
+    rpc: &Rpc,
+    rpc_url: &str,
+    target: Address,
+    also: &HashSet<String>,
+    from: u64,
+    to: u64,
+) -> Result<Vec<AddressTransaction>> {
+    with_failover(rpc, rpc_url, |url| {
+        block_transactions(rpc, url, target, also, from, to)
+    })
+    .await
+}
+
+async fn block_transactions(
+    rpc: &Rpc,
+    url: String,
+    target: Address,
+    also: &HashSet<String>,
+    from: u64,
+    to: u64,
+) -> Result<Vec<AddressTransaction>> {
+    let provider = rpc.connect(&url).await?;
+    let mut matches = Vec::new();
+    for number in (from..=to).rev() {
+        rpc.stats().record_api_call("rpc");
+        let Some(block) = provider
+            .get_block_by_number(BlockNumberOrTag::Number(number))
+            .full()
+            .await
+            .wrap_err_with(|| format!("failed to fetch block {number}"))?
+        else {
+            continue;
+        };
+        for tx in block.transactions.txns() {
+            let hash = format!("{:#x}", tx.tx_hash());
+            if tx.from() != target && tx.to() != Some(target) && !also.contains(&hash) {
+                continue;
+            }
+            rpc.stats().record_api_call("rpc");
+            let receipt = provider
+                .get_transaction_receipt(tx.tx_hash())
+                .await
+                .wrap_err("failed to fetch transaction receipt")?;
+            matches.push(AddressTransaction {
+                hash,
+                block_number: number,
+                from: format!("{:#x}", tx.from()),
+                to: tx.to().map(|to| format!("{to:#x}")),
+                value_wei: tx.value(),
+                is_error: receipt.as_ref().is_some_and(|receipt| !receipt.status()),
+                input: Some(tx.input().to_string()),
+                timestamp: Some(block.header.timestamp),
+                gas_used: receipt.map(|receipt| receipt.gas_used),
+            });
+        }
+    }
+    Ok(matches)
+}
+
+/// Chain id of `rpc_url`, from the session cache when it was already probed.
+pub async fn endpoint_chain_id(rpc: &Rpc, rpc_url: &str) -> Result<u64> {
+    let cached = CHAIN_IDS
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(rpc_url).copied());
+    rpc.stats()
+        .record_cache_lookup("chain id", cached.is_some());
+    match cached {
+        Some(chain_id) => Ok(chain_id),
+        None => fetch_chain_id(rpc, rpc_url).await,
+    }
 }
 
 #[cfg(test)]
@@ -91,7 +573,11 @@ mod tests {
             latest_block: 42,
             balance_wei: U256::from(1_000_000_000_000_000_000u128),
             transaction_count: 7,
+            pending_transaction_count: 9,
+            queued_nonces: Some(vec![11]),
             is_contract: false,
+            delegate: None,
+            code: Default::default(),
         };
 
         let hydrated = crate::app::build_address_view(
@@ -124,12 +610,16 @@ mod tests {
             address: format!("{:#x}", address),
             chain: "Local".into(),
         };
-        let secrets = SecretsState {
-            etherscan_api_key: None,
-            anvil_rpc_url: None,
-        };
+        let secrets = SecretsState::default();
 
-        let hydrated = App::hydrate_address(addr_ref, secrets).await;
+        let hydrated = App::hydrate_address(
+            addr_ref,
+            secrets,
+            None,
+            Default::default(),
+            Default::default(),
+        )
+        .await;
 
         assert!(
             hydrated
@@ -140,4 +630,43 @@ mod tests {
         );
         assert!(hydrated.overview.is_none());
     }
+
+    #[test]
+    fn delegation_designator_is_decoded() {
+        let delegate = Address::from_str("0x63c0c19a282a1b52b07dd5a65b58948a07dae32b").unwrap();
+        let mut code = EIP7702_DELEGATION_DESIGNATOR.to_vec();
+        code.extend_from_slice(delegate.as_slice());
+        assert_eq!(delegation_target(&code), Some(delegate));
+        assert_eq!(delegation_target(&code[..22]), None);
+        assert_eq!(delegation_target(&[0x60, 0x80, 0x60, 0x40]), None);
+    }
+
+    #[test]
+    fn blob_fee_needs_receipt_fields() {
+        let mut blobs = BlobDetails {
+            versioned_hashes: vec![B256::ZERO; 2],
+            max_fee_per_blob_gas: Some(10),
+            blob_gas_used: Some(262_144),
+            blob_gas_price: Some(3),
+        };
+        assert_eq!(blobs.fee_wei(), Some(U256::from(786_432)));
+        blobs.blob_gas_price = None;
+        assert_eq!(blobs.fee_wei(), None);
+    }
+
+    #[test]
+    fn block_context_steps_within_the_block() {
+        let hashes: Vec<B256> = (1..=3).map(B256::repeat_byte).collect();
+        let mut context = BlockContext {
+            block_number: 10,
+            timestamp: 0,
+            index: 0,
+            hashes: hashes.clone(),
+        };
+        assert_eq!(context.neighbor(false), None);
+        assert_eq!(context.neighbor(true), Some(hashes[1]));
+        context.index = 2;
+        assert_eq!(context.neighbor(true), None);
+        assert_eq!(context.neighbor(false), Some(hashes[1]));
+    }
 }
@@ -0,0 +1,274 @@
+use super::{
+    explorer::{ContractSource, SourceMatch},
+    storage_layout::StorageLayout,
+};
+use alloy::{
+    hex,
+    primitives::{B256, keccak256},
+};
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Compiled contract from a Foundry project's output directory, keyed by
+/// the hash of its deployed bytecode with immutables and library links zeroed.
+#[derive(Debug, Clone)]
+pub struct Artifact {
+    pub name: String,
+    pub source_path: Option<String>,
+    pub compiler: Option<String>,
+    pub abi: String,
+    pub storage_layout: Option<StorageLayout>,
+    masked: Vec<(usize, usize)>,
+    code_len: usize,
+    code_hash: B256,
+}
+
+impl Artifact {
+    /// Whether `code` is this contract's deployed bytecode once the
+    /// artifact's immutable and link ranges are masked out of it.
+    pub fn matches(&self, code: &[u8]) -> bool {
+        code.len() == self.code_len
+            && keccak256(mask(code.to_vec(), &self.masked)) == self.code_hash
+    }
+
+    fn into_source(self, project: &Path) -> ContractSource {
+        let source = self
+            .source_path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(project.join(path)).ok())
+            .unwrap_or_default();
+        ContractSource {
+            name: self.name,
+            compiler: self
+                .compiler
+                .map(|version| format!("solc {version}"))
+                .unwrap_or_else(|| "unknown compiler".into()),
+            abi: self.abi,
+            source,
+            source_label: "Foundry",
+            match_type: SourceMatch::LocalArtifact,
+            storage_layout: self.storage_layout,
+        }
+    }
+}
+
+fn mask(mut code: Vec<u8>, ranges: &[(usize, usize)]) -> Vec<u8> {
+    for &(start, length) in ranges {
+        if let Some(bytes) = code.get_mut(start..start + length) {
+            bytes.fill(0);
+        }
+    }
+    code
+}
+
+/// Output directory of the project: `out` unless `foundry.toml` sets another.
+fn out_dir(project: &Path) -> PathBuf {
+    let configured = fs::read_to_string(project.join("foundry.toml"))
+        .ok()
+        .and_then(|config| {
+            config.lines().find_map(|line| {
+                let (key, value) = line.split_once('=')?;
+                (key.trim() == "out").then(|| value.trim().trim_matches(['"', '\'']).to_string())
+            })
+        });
+    project.join(configured.unwrap_or_else(|| "out".into()))
+}
+
+/// Every artifact with deployed bytecode under the project's output
+/// directory. Unreadable files are skipped; a missing directory is an error.
+pub fn load(project: &Path) -> Result<Vec<Artifact>, String> {
+    let out = out_dir(project);
+    let entries = fs::read_dir(&out)
+        .map_err(|err| format!("cannot read Foundry output {}: {err}", out.display()))?;
+    let mut artifacts = Vec::new();
+    for dir in entries.flatten() {
+        // `out/<File>.sol/<Contract>.json`; skips `build-info` and the like.
+        if !dir.file_name().to_string_lossy().ends_with(".sol") {
+            continue;
+        }
+        let Ok(files) = fs::read_dir(dir.path()) else {
+            continue;
+        };
+        for file in files.flatten() {
+            let path = file.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            if let Some(artifact) = fs::read_to_string(&path)
+                .ok()
+                .and_then(|text| parse_artifact(name, &text))
+            {
+                artifacts.push(artifact);
+            }
+        }
+    }
+    Ok(artifacts)
+}
+
+fn parse_artifact(name: &str, text: &str) -> Option<Artifact> {
+    let raw: RawArtifact = serde_json::from_str(text).ok()?;
+    let deployed = raw.deployed_bytecode?;
+    let mut masked: Vec<(usize, usize)> = deployed
+        .immutable_references
+        .values()
+        .flatten()
+        .chain(
+            deployed
+                .link_references
+                .values()
+                .flat_map(|libs| libs.values().flatten()),
+        )
+        .map(|range| (range.start, range.length))
+        .collect();
+    masked.sort_unstable();
+
+    // Unlinked libraries leave `__$…$__` placeholders, which are not hex.
+    let mut object = deployed
+        .object
+        .strip_prefix("0x")
+        .unwrap_or(&deployed.object)
+        .to_string();
+    for &(start, length) in &masked {
+        if let Some(range) = object.get(start * 2..(start + length) * 2) {
+            let zeros = "0".repeat(range.len());
+            object.replace_range(start * 2..(start + length) * 2, &zeros);
+        }
+    }
+    let code = hex::decode(&object).ok().filter(|code| !code.is_empty())?;
+
+    let metadata = raw.metadata.unwrap_or_default();
+    Some(Artifact {
+        name: name.to_string(),
+        source_path: metadata
+            .settings
+            .compilation_target
+            .into_keys()
+            .next()
+            .or(raw.ast.map(|ast| ast.absolute_path)),
+        compiler: metadata.compiler.map(|compiler| compiler.version),
+        abi: raw.abi.to_string(),
+        storage_layout: raw.storage_layout,
+        code_len: code.len(),
+        code_hash: keccak256(mask(code, &masked)),
+        masked,
+    })
+}
+
+/// The project's contract deployed as `code`, as contract source with its
+/// ABI. Runs on a blocking thread since it reads the whole output directory.
+pub async fn match_code(project: PathBuf, code: Vec<u8>) -> Result<Option<ContractSource>, String> {
+    tokio::task::spawn_blocking(move || {
+        let found = load(&project)?
+            .into_iter()
+            .find(|artifact| artifact.matches(&code));
+        Ok(found.map(|artifact| artifact.into_source(&project)))
+    })
+    .await
+    .map_err(|err| format!("artifact scan failed: {err}"))?
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawArtifact {
+    #[serde(default)]
+    abi: serde_json::Value,
+    deployed_bytecode: Option<RawBytecode>,
+    metadata: Option<RawMetadata>,
+    ast: Option<RawAst>,
+    /// Present when the project builds with `extra_output = ["storageLayout"]`.
+    storage_layout: Option<StorageLayout>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawBytecode {
+    object: String,
+    #[serde(default)]
+    immutable_references: BTreeMap<String, Vec<RawRange>>,
+    #[serde(default)]
+    link_references: BTreeMap<String, BTreeMap<String, Vec<RawRange>>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawRange {
+    start: usize,
+    length: usize,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RawMetadata {
+    compiler: Option<RawCompiler>,
+    #[serde(default)]
+    settings: RawSettings,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawCompiler {
+    version: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawSettings {
+    #[serde(default)]
+    compilation_target: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawAst {
+    absolute_path: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    #[test]
+    fn matches_deployed_code_with_immutables_masked() {
+        let temp = tempdir().unwrap();
+        let dir = temp.path().join("build/Vault.sol");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            temp.path().join("foundry.toml"),
+            "[profile.default]\nout = \"build\"\n",
+        )
+        .unwrap();
+        let artifact = json!({
+            "abi": [{"type": "function", "name": "owner", "inputs": [], "outputs": [], "stateMutability": "view"}],
+            "deployedBytecode": {
+                "object": "0x6080aaaa0000000000",
+                "immutableReferences": {"7": [{"start": 4, "length": 5}]},
+                "linkReferences": {}
+            },
+            "metadata": {
+                "compiler": {"version": "0.8.24+commit.e11b9ed9"},
+                "settings": {"compilationTarget": {"src/Vault.sol": "Vault"}}
+            }
+        });
+        fs::write(dir.join("Vault.json"), artifact.to_string()).unwrap();
+        fs::write(
+            dir.join("IVault.json"),
+            json!({"abi": [], "deployedBytecode": {"object": "0x"}}).to_string(),
+        )
+        .unwrap();
+
+        let artifacts = load(temp.path()).unwrap();
+        assert_eq!(artifacts.len(), 1);
+        let vault = &artifacts[0];
+        assert_eq!(vault.name, "Vault");
+        assert_eq!(vault.source_path.as_deref(), Some("src/Vault.sol"));
+        assert!(vault.matches(&hex::decode("6080aaaa1122334455").unwrap()));
+        assert!(!vault.matches(&hex::decode("6080aaab1122334455").unwrap()));
+        assert!(!vault.matches(&hex::decode("6080aaaa11223344").unwrap()));
+    }
+}
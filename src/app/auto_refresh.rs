@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+/// Intervals `Shift-R` cycles through after "off", shortest first.
+const INTERVALS: [Duration; 4] = [
+    Duration::from_secs(15),
+    Duration::from_secs(30),
+    Duration::from_secs(60),
+    Duration::from_secs(300),
+];
+
+/// The interval after `current` in the cycle, wrapping back to off. Intervals
+/// outside the cycle (edited by hand in storage) move to the next longer one.
+pub fn next_interval(current: Option<Duration>) -> Option<Duration> {
+    match current {
+        None => Some(INTERVALS[0]),
+        Some(current) => INTERVALS
+            .iter()
+            .copied()
+            .find(|interval| *interval > current),
+    }
+}
+
+/// Stored as whole seconds, with 0 meaning off.
+pub fn from_setting(seconds: u64) -> Option<Duration> {
+    (seconds > 0).then(|| Duration::from_secs(seconds))
+}
+
+pub fn to_setting(interval: Option<Duration>) -> u64 {
+    interval.map(|interval| interval.as_secs()).unwrap_or(0)
+}
+
+pub fn label(interval: Duration) -> String {
+    let seconds = interval.as_secs();
+    if seconds >= 60 && seconds.is_multiple_of(60) {
+        format!("{}m", seconds / 60)
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interval_cycle_wraps_to_off() {
+        let mut interval = None;
+        let mut labels = Vec::new();
+        for _ in 0..5 {
+            interval = next_interval(interval);
+            labels.push(interval.map(label).unwrap_or_else(|| "off".into()));
+        }
+        assert_eq!(labels, vec!["15s", "30s", "1m", "5m", "off"]);
+        assert_eq!(
+            next_interval(from_setting(45)),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(to_setting(from_setting(0)), 0);
+    }
+}
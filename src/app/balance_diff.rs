@@ -0,0 +1,251 @@
+use super::{
+    anvil::normalize_url,
+    providers::Rpc,
+    stats::ApiStats,
+    tenderly::Tenderly,
+    transfers::{self, Asset, TokenCache, Transfer},
+};
+use crate::storage::TokenRecord;
+use alloy::{
+    primitives::{Address, B256, I256, U256},
+    providers::{Provider, ext::DebugApi},
+    rpc::types::trace::geth::{DiffMode, GethDebugTracingOptions, PreStateConfig, PreStateFrame},
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Net change of one asset for one account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct BalanceDelta {
+    pub account: Address,
+    pub asset: Asset,
+    pub delta: I256,
+}
+
+/// Every non-zero balance change a transaction caused.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BalanceDiff {
+    /// Grouped by account; ETH first within each account.
+    pub deltas: Vec<BalanceDelta>,
+    pub tokens: BTreeMap<Address, TokenRecord>,
+    /// Set when the ETH side could not be traced.
+    pub note: Option<String>,
+}
+
+/// State of the Balance Diff tab for the selected transaction.
+#[derive(Debug, Default)]
+pub struct BalanceDiffView {
+    /// Transaction the tab was loaded for.
+    pub hash: Option<String>,
+    pub loading: bool,
+    pub result: Option<Result<BalanceDiff, String>>,
+}
+
+impl BalanceDiffView {
+    pub fn needs_load(&self, hash: &str) -> bool {
+        self.hash.as_deref() != Some(hash)
+    }
+}
+
+fn signed(value: U256) -> I256 {
+    I256::try_from(value).unwrap_or(I256::MAX)
+}
+
+/// ETH deltas from a `prestateTracer` diff. Post state only lists changed
+/// fields, and an account missing from it was destroyed.
+fn native_deltas(diff: &DiffMode) -> BTreeMap<(Address, Asset), I256> {
+    let mut deltas = BTreeMap::new();
+    for (account, pre) in &diff.pre {
+        let before = pre.balance.unwrap_or_default();
+        let after = match diff.post.get(account) {
+            Some(post) => post.balance.unwrap_or(before),
+            None => U256::ZERO,
+        };
+        deltas.insert((*account, Asset::Native), signed(after) - signed(before));
+    }
+    for (account, post) in &diff.post {
+        if !diff.pre.contains_key(account)
+            && let Some(balance) = post.balance
+        {
+            deltas.insert((*account, Asset::Native), signed(balance));
+        }
+    }
+    deltas
+}
+
+/// Token deltas from decoded transfer logs; NFTs count one per token id.
+pub fn token_deltas(transfers: &[Transfer]) -> BTreeMap<(Address, Asset), I256> {
+    let mut deltas = BTreeMap::new();
+    for transfer in transfers {
+        if transfer.asset == Asset::Native {
+            continue;
+        }
+        let amount = signed(transfer.amount);
+        *deltas
+            .entry((transfer.from, transfer.asset))
+            .or_insert(I256::ZERO) -= amount;
+        *deltas
+            .entry((transfer.to, transfer.asset))
+            .or_insert(I256::ZERO) += amount;
+    }
+    deltas
+}
+
+/// Replay `hash` with the `prestateTracer` in diff mode (or on Tenderly when
+/// given one) for ETH balances and net the receipt's token transfer logs per
+/// account. The sender's ETH delta includes the gas fee.
+pub async fn fetch_balance_diff(
+    rpc: Rpc,
+    rpc_url: String,
+    hash: String,
+    cache: Option<TokenCache>,
+    tenderly: Option<Tenderly>,
+) -> Result<BalanceDiff, String> {
+    let tx_hash: B256 = hash
+        .parse()
+        .map_err(|_| "invalid transaction hash".to_string())?;
+    let provider = rpc
+        .connect(&normalize_url(&rpc, &rpc_url))
+        .await
+        .map_err(|err| format!("{err:#}"))?;
+
+    rpc.stats().record_api_call("rpc");
+    let receipt = provider
+        .get_transaction_receipt(tx_hash)
+        .await
+        .map_err(|err| format!("failed to load receipt: {err}"))?
+        .ok_or("transaction is still pending")?;
+    let token_transfers: Vec<Transfer> = receipt
+        .inner
+        .logs()
+        .iter()
+        .flat_map(|log| transfers::transfers_from_log(log.address(), log.data()))
+        .collect();
+
+    let mut diff = BalanceDiff::default();
+    let mut deltas = token_deltas(&token_transfers);
+    if let Some(tenderly) = tenderly {
+        rpc.stats().record_api_call("rpc");
+        let replay = match provider.get_transaction_by_hash(tx_hash).await {
+            Ok(Some(transaction)) => {
+                tenderly
+                    .replay(rpc.stats(), &transaction, receipt.effective_gas_price)
+                    .await
+            }
+            Ok(None) => Err("transaction not found on this RPC".into()),
+            Err(err) => Err(format!("failed to load transaction: {err}")),
+        };
+        match replay {
+            Ok(simulation) => deltas.extend(native_deltas(&simulation.balances)),
+            Err(err) => diff.note = Some(format!("ETH deltas unavailable (Tenderly: {err})")),
+        }
+        return Ok(finish(
+            rpc.stats(),
+            diff,
+            deltas,
+            &provider,
+            &token_transfers,
+            cache,
+        )
+        .await);
+    }
+    rpc.stats().record_api_call("rpc");
+    let options = GethDebugTracingOptions::prestate_tracer(PreStateConfig {
+        diff_mode: Some(true),
+        disable_code: Some(true),
+        disable_storage: Some(true),
+    });
+    let trace = provider
+        .debug_trace_transaction(tx_hash, options)
+        .await
+        .map_err(|err| err.to_string())
+        .and_then(|trace| {
+            trace
+                .try_into_pre_state_frame()
+                .map_err(|err| err.to_string())
+        });
+    match trace {
+        Ok(PreStateFrame::Diff(state)) => deltas.extend(native_deltas(&state)),
+        Ok(PreStateFrame::Default(_)) => {
+            diff.note = Some("Node ignored diffMode; ETH deltas unavailable".into());
+        }
+        Err(err) => {
+            diff.note = Some(format!(
+                "ETH deltas unavailable (debug_traceTransaction prestateTracer: {err})"
+            ));
+        }
+    }
+
+    Ok(finish(
+        rpc.stats(),
+        diff,
+        deltas,
+        &provider,
+        &token_transfers,
+        cache,
+    )
+    .await)
+}
+
+/// Keep the non-zero deltas and look up the tokens involved.
+async fn finish(
+    stats: &ApiStats,
+    mut diff: BalanceDiff,
+    deltas: BTreeMap<(Address, Asset), I256>,
+    provider: &impl Provider,
+    token_transfers: &[Transfer],
+    cache: Option<TokenCache>,
+) -> BalanceDiff {
+    diff.deltas = deltas
+        .into_iter()
+        .filter(|(_, delta)| !delta.is_zero())
+        .map(|((account, asset), delta)| BalanceDelta {
+            account,
+            asset,
+            delta,
+        })
+        .collect();
+    diff.tokens = transfers::token_metadata(stats, provider, token_transfers, cache.as_ref()).await;
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::rpc::types::trace::geth::AccountState;
+
+    #[test]
+    fn nets_native_and_token_deltas() {
+        let (alice, bob, pool) = (
+            Address::repeat_byte(1),
+            Address::repeat_byte(2),
+            Address::repeat_byte(3),
+        );
+        let state = |balance: u64| AccountState {
+            balance: Some(U256::from(balance)),
+            ..AccountState::default()
+        };
+        let diff = DiffMode {
+            pre: BTreeMap::from([(alice, state(100)), (pool, state(7))]),
+            post: BTreeMap::from([(alice, state(60)), (bob, state(40))]),
+        };
+        let native = native_deltas(&diff);
+        assert_eq!(
+            native[&(alice, Asset::Native)],
+            I256::try_from(-40).unwrap()
+        );
+        assert_eq!(native[&(bob, Asset::Native)], I256::try_from(40).unwrap());
+        assert_eq!(native[&(pool, Asset::Native)], I256::try_from(-7).unwrap());
+
+        let token = Asset::Erc20(Address::repeat_byte(0x70));
+        let transfer = |from, to, amount: u64| Transfer {
+            from,
+            to,
+            asset: token,
+            amount: U256::from(amount),
+        };
+        let tokens = token_deltas(&[transfer(alice, pool, 10), transfer(pool, bob, 4)]);
+        assert_eq!(tokens[&(pool, token)], I256::try_from(6).unwrap());
+        assert_eq!(tokens[&(alice, token)], I256::try_from(-10).unwrap());
+    }
+}
@@ -0,0 +1,107 @@
+use super::{csv_export::file_name_id, explorer::ContractSource};
+use alloy::json_abi::JsonAbi;
+use color_eyre::{Result, eyre::WrapErr};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const EXPORT_DIR: &str = "exports";
+
+/// Render an alloy `sol!` block for the contract's ABI, ready to paste into a crate.
+pub fn sol_snippet(source: &ContractSource) -> Result<String> {
+    let abi: JsonAbi =
+        serde_json::from_str(&source.abi).wrap_err("contract ABI is not valid JSON")?;
+    let name = interface_name(&source.name);
+    let interface = abi.to_sol(&name, None);
+
+    let mut out = String::new();
+    out.push_str(&format!(
+        "// Generated by evm-tui from the {} ABI published on {}.\n",
+        source.name, source.source_label
+    ));
+    out.push_str("alloy::sol! {\n");
+    out.push_str("    #[sol(rpc)]\n");
+    for line in interface.lines() {
+        if line.is_empty() {
+            out.push('\n');
+        } else {
+            out.push_str("    ");
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out.push_str("}\n");
+    Ok(out)
+}
+
+/// Write the `sol!` snippet to `exports/<name>_<address>.rs` and return the path.
+pub fn export_sol_bindings(source: &ContractSource, address: &str) -> Result<PathBuf> {
+    export_sol_bindings_to(Path::new(EXPORT_DIR), source, address)
+}
+
+fn export_sol_bindings_to(dir: &Path, source: &ContractSource, address: &str) -> Result<PathBuf> {
+    let snippet = sol_snippet(source)?;
+    fs::create_dir_all(dir).wrap_err("failed to create exports directory")?;
+    let file_name = format!(
+        "{}_{}.rs",
+        interface_name(&source.name).to_ascii_lowercase(),
+        file_name_id(address)
+    );
+    let path = dir.join(file_name);
+    fs::write(&path, snippet).wrap_err("failed to write bindings")?;
+    Ok(path)
+}
+
+fn interface_name(contract_name: &str) -> String {
+    let cleaned: String = contract_name
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
+        .collect();
+    match cleaned.chars().next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => cleaned,
+        Some(_) => format!("I{cleaned}"),
+        None => "Contract".into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::explorer::SourceMatch;
+    use tempfile::tempdir;
+
+    fn erc20_source() -> ContractSource {
+        ContractSource {
+            name: "Token-V2".into(),
+            compiler: "v0.8.24".into(),
+            abi: r#"[{"type":"function","name":"balanceOf","stateMutability":"view","inputs":[{"name":"owner","type":"address"}],"outputs":[{"name":"","type":"uint256"}]}]"#.into(),
+            source: String::new(),
+            source_label: "Etherscan",
+            match_type: SourceMatch::Verified,
+            storage_layout: None,
+        }
+    }
+
+    #[test]
+    fn snippet_wraps_interface_in_sol_macro() -> Result<()> {
+        let snippet = sol_snippet(&erc20_source())?;
+        assert!(snippet.contains("alloy::sol! {"));
+        assert!(snippet.contains("interface TokenV2 {"));
+        assert!(snippet.contains("function balanceOf(address owner) external view returns"));
+        Ok(())
+    }
+
+    #[test]
+    fn export_writes_file_named_after_contract() -> Result<()> {
+        let temp = tempdir().unwrap();
+        let path = export_sol_bindings_to(
+            temp.path(),
+            &erc20_source(),
+            "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+        )?;
+        assert_eq!(path.file_name().unwrap(), "tokenv2_a0b86991.rs");
+        assert!(fs::read_to_string(path)?.contains("#[sol(rpc)]"));
+        Ok(())
+    }
+}
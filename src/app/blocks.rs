@@ -0,0 +1,140 @@
+use super::{
+    anvil::{BlockSummary, fetch_block, transaction_exists},
+    providers::Rpc,
+};
+use alloy::{
+    eips::{BlockId, BlockNumberOrTag},
+    primitives::B256,
+};
+
+/// What a 32-byte hash typed into search turned out to be.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HashLookup {
+    Transaction,
+    Block(Box<BlockSummary>),
+    /// Both or neither matched, or a lookup failed; the user picks.
+    Ambiguous(String),
+}
+
+/// A search for a block: a decimal number (`_` and `,` separators allowed,
+/// optionally prefixed with `#`) or one of the `latest`, `safe` and
+/// `finalized` tags.
+pub fn parse_block_query(input: &str) -> Option<BlockId> {
+    let input = input.trim();
+    let tag = match input.to_ascii_lowercase().as_str() {
+        "latest" => Some(BlockNumberOrTag::Latest),
+        "safe" => Some(BlockNumberOrTag::Safe),
+        "finalized" => Some(BlockNumberOrTag::Finalized),
+        _ => None,
+    };
+    if let Some(tag) = tag {
+        return Some(BlockId::Number(tag));
+    }
+    let digits: String = input
+        .strip_prefix('#')
+        .unwrap_or(input)
+        .chars()
+        .filter(|c| !matches!(c, '_' | ','))
+        .collect();
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok().map(BlockId::number)
+}
+
+/// Decide from the two probes. A found transaction wins unless the hash is
+/// also a block; a failed transaction lookup never opens a transaction.
+fn classify(
+    transaction: Result<bool, String>,
+    block: Result<Option<BlockSummary>, String>,
+) -> HashLookup {
+    match (transaction, block) {
+        (Ok(true), Ok(None) | Err(_)) => HashLookup::Transaction,
+        (Ok(false) | Err(_), Ok(Some(block))) => HashLookup::Block(Box::new(block)),
+        (Ok(true), Ok(Some(_))) => {
+            HashLookup::Ambiguous("Matches both a transaction and a block".into())
+        }
+        (Ok(false), Ok(None)) => {
+            HashLookup::Ambiguous("Not found as a transaction or block on this RPC".into())
+        }
+        (Ok(false), Err(err)) | (Err(err), Ok(None) | Err(_)) => {
+            HashLookup::Ambiguous(format!("Lookup failed: {err}"))
+        }
+    }
+}
+
+/// Load a block for the block view.
+pub async fn load_block(
+    rpc: Rpc,
+    rpc_url: String,
+    block: BlockId,
+) -> Result<Option<BlockSummary>, String> {
+    fetch_block(&rpc, &rpc_url, block)
+        .await
+        .map_err(|err| format!("{err:#}"))
+}
+
+/// Probe `hash` as both a transaction and a block hash.
+pub async fn lookup_hash(rpc: Rpc, rpc_url: String, hash: B256) -> HashLookup {
+    let (transaction, block) = tokio::join!(
+        transaction_exists(&rpc, &rpc_url, hash),
+        fetch_block(&rpc, &rpc_url, BlockId::hash(hash)),
+    );
+    classify(
+        transaction.map_err(|err| format!("{err:#}")),
+        block.map_err(|err| format!("{err:#}")),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::Address;
+
+    #[test]
+    fn parses_block_numbers_and_tags() {
+        assert_eq!(
+            parse_block_query("#19_000,000"),
+            Some(BlockId::number(19_000_000))
+        );
+        assert_eq!(
+            parse_block_query(" Finalized "),
+            Some(BlockId::Number(BlockNumberOrTag::Finalized))
+        );
+        assert_eq!(parse_block_query("0x1234"), None);
+        assert_eq!(parse_block_query("pending"), None);
+        assert_eq!(parse_block_query("#"), None);
+    }
+
+    #[test]
+    fn only_unambiguous_probes_skip_the_chooser() {
+        let block = BlockSummary {
+            number: 1,
+            hash: B256::repeat_byte(1),
+            parent_hash: B256::ZERO,
+            timestamp: 0,
+            miner: Address::ZERO,
+            gas_used: 0,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: None,
+            transactions: Vec::new(),
+        };
+        assert_eq!(classify(Ok(true), Ok(None)), HashLookup::Transaction);
+        assert_eq!(
+            classify(Err("timeout".into()), Ok(Some(block.clone()))),
+            HashLookup::Block(Box::new(block.clone()))
+        );
+        assert!(matches!(
+            classify(Ok(true), Ok(Some(block))),
+            HashLookup::Ambiguous(_)
+        ));
+        assert!(matches!(
+            classify(Ok(false), Ok(None)),
+            HashLookup::Ambiguous(_)
+        ));
+        assert_eq!(
+            classify(Err("timeout".into()), Ok(None)),
+            HashLookup::Ambiguous("Lookup failed: timeout".into())
+        );
+    }
+}
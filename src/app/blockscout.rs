@@ -0,0 +1,91 @@
+use super::{
+    etherscan::{
+        ExplorerClient, creation_params, history_params, parse_contract_creation,
+        parse_contract_source, parse_internal_transactions, parse_token_transfers,
+        parse_transactions, query_compatible_api, source_params, txlist_params,
+    },
+    explorer::{
+        AddressTransaction, ContractCreation, ContractSource, ExplorerBackend, ExplorerSession,
+        InternalTransaction, TokenTransferRecord, TransactionFetchError, TransactionListSource,
+    },
+};
+
+/// Blockscout backend using the instance's Etherscan-compatible `/api` endpoint.
+/// Public instances do not require an API key.
+pub struct BlockscoutBackend {
+    client: ExplorerClient,
+    api_url: &'static str,
+    label: &'static str,
+}
+
+impl BlockscoutBackend {
+    pub fn new(
+        session: &ExplorerSession,
+        api_url: &'static str,
+        label: &'static str,
+    ) -> Result<Self, TransactionFetchError> {
+        Ok(Self {
+            client: ExplorerClient::new(session)?,
+            api_url,
+            label,
+        })
+    }
+}
+
+impl ExplorerBackend for BlockscoutBackend {
+    fn source(&self) -> TransactionListSource {
+        TransactionListSource {
+            label: self.label,
+            api_version: "blockscout",
+        }
+    }
+
+    async fn address_transactions(
+        &self,
+        address: &str,
+        limit: usize,
+    ) -> Result<Vec<AddressTransaction>, TransactionFetchError> {
+        let result =
+            query_compatible_api(&self.client, self.api_url, &txlist_params(address, limit))
+                .await?;
+        parse_transactions(result)
+    }
+
+    async fn contract_source(
+        &self,
+        address: &str,
+    ) -> Result<Option<ContractSource>, TransactionFetchError> {
+        let result =
+            query_compatible_api(&self.client, self.api_url, &source_params(address)).await?;
+        parse_contract_source(result, self.label)
+    }
+
+    async fn contract_creation(
+        &self,
+        address: &str,
+    ) -> Result<Option<ContractCreation>, TransactionFetchError> {
+        let result =
+            query_compatible_api(&self.client, self.api_url, &creation_params(address)).await?;
+        parse_contract_creation(result)
+    }
+
+    async fn internal_transactions(
+        &self,
+        address: &str,
+        limit: usize,
+    ) -> Result<Vec<InternalTransaction>, TransactionFetchError> {
+        let params = history_params("txlistinternal", address, limit);
+        let result = query_compatible_api(&self.client, self.api_url, &params).await?;
+        parse_internal_transactions(result)
+    }
+
+    async fn token_transfers(
+        &self,
+        address: &str,
+        limit: usize,
+    ) -> Result<Vec<TokenTransferRecord>, TransactionFetchError> {
+        let params = history_params("tokentx", address, limit);
+        let result = query_compatible_api(&self.client, self.api_url, &params).await?;
+        parse_token_transfers(result)
+    }
+}
@@ -0,0 +1,490 @@
+use alloy::primitives::{Address, address};
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// Explorer family used to load transaction lists, ABIs, and verified sources for a chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExplorerKind {
+    /// Etherscan's multichain v2 API, addressed by `chainid`.
+    EtherscanV2 { label: &'static str },
+    /// A Blockscout instance exposing the Etherscan-compatible `/api` endpoint.
+    Blockscout {
+        label: &'static str,
+        api_url: &'static str,
+    },
+}
+
+/// Rollup stack of an L2 settling on Mainnet, with the L1 contract that
+/// executes its withdrawals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Rollup {
+    /// Arbitrum Nitro: withdrawals execute through the L1 `Outbox`.
+    Arbitrum { outbox: Address },
+    /// OP Stack: withdrawals are proven and finalized on the `OptimismPortal`.
+    OpStack { portal: Address },
+}
+
+impl Rollup {
+    pub fn label(self) -> &'static str {
+        match self {
+            Rollup::Arbitrum { .. } => "Arbitrum Nitro",
+            Rollup::OpStack { .. } => "OP Stack",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ChainInfo {
+    pub name: &'static str,
+    pub chain_id: u64,
+    pub explorer: ExplorerKind,
+    /// Symbol of the native currency balances and values are shown in.
+    pub currency: &'static str,
+    /// Safe Transaction Service base URL, where Safe runs one for the chain.
+    pub safe_service: Option<&'static str>,
+    /// Set for L2s, whose transactions can come from or go to Mainnet.
+    pub rollup: Option<Rollup>,
+    aliases: &'static [&'static str],
+}
+
+impl ChainInfo {
+    fn matches(&self, normalized: &str) -> bool {
+        self.name.eq_ignore_ascii_case(normalized) || self.aliases.contains(&normalized)
+    }
+
+    /// Ethereum's own networks, whose consensus layer credits withdrawals
+    /// (EIP-4895) that Etherscan indexes. Rollups and sidechains have none.
+    pub fn has_beacon_withdrawals(&self) -> bool {
+        matches!(self.chain_id, 1 | 11155111 | 17000 | 560048)
+    }
+}
+
+pub const CHAINS: &[ChainInfo] = &[
+    ChainInfo {
+        name: "Mainnet",
+        chain_id: 1,
+        explorer: ExplorerKind::EtherscanV2 { label: "Etherscan" },
+        currency: "ETH",
+        safe_service: Some("https://safe-transaction-mainnet.safe.global"),
+        rollup: None,
+        aliases: &["ethereum", "ethereum mainnet"],
+    },
+    ChainInfo {
+        name: "Arbitrum",
+        chain_id: 42161,
+        explorer: ExplorerKind::EtherscanV2 { label: "Arbiscan" },
+        currency: "ETH",
+        safe_service: Some("https://safe-transaction-arbitrum.safe.global"),
+        rollup: Some(Rollup::Arbitrum {
+            outbox: address!("0x0B9857ae2D4A3DBe74ffE1d7DF045bb7F96E4840"),
+        }),
+        aliases: &["arbitrum one"],
+    },
+    ChainInfo {
+        name: "Base",
+        chain_id: 8453,
+        explorer: ExplorerKind::EtherscanV2 { label: "Basescan" },
+        currency: "ETH",
+        safe_service: Some("https://safe-transaction-base.safe.global"),
+        rollup: Some(Rollup::OpStack {
+            portal: address!("0x49048044D57e1C92A77f79988d21Fa8fAF74E97e"),
+        }),
+        aliases: &["base mainnet"],
+    },
+    ChainInfo {
+        name: "Sepolia",
+        chain_id: 11155111,
+        explorer: ExplorerKind::EtherscanV2 {
+            label: "Etherscan (Sepolia)",
+        },
+        currency: "ETH",
+        safe_service: Some("https://safe-transaction-sepolia.safe.global"),
+        rollup: None,
+        aliases: &["ethereum sepolia"],
+    },
+    ChainInfo {
+        name: "Zora",
+        chain_id: 7777777,
+        explorer: ExplorerKind::Blockscout {
+            label: "Zora Explorer",
+            api_url: "https://explorer.zora.energy/api",
+        },
+        currency: "ETH",
+        safe_service: Some("https://safe-transaction-zora.safe.global"),
+        rollup: Some(Rollup::OpStack {
+            portal: address!("0x1a0ad011913A150f69f6A19DF447A0CfD9551054"),
+        }),
+        aliases: &["zora network"],
+    },
+    ChainInfo {
+        name: "Mode",
+        chain_id: 34443,
+        explorer: ExplorerKind::Blockscout {
+            label: "Mode Explorer",
+            api_url: "https://explorer.mode.network/api",
+        },
+        currency: "ETH",
+        safe_service: None,
+        rollup: Some(Rollup::OpStack {
+            portal: address!("0x8B34b14c7c7123459Cf3076b8Cb929BE097d0C07"),
+        }),
+        aliases: &["mode network"],
+    },
+    ChainInfo {
+        name: "Ink",
+        chain_id: 57073,
+        explorer: ExplorerKind::Blockscout {
+            label: "Ink Explorer",
+            api_url: "https://explorer.inkonchain.com/api",
+        },
+        currency: "ETH",
+        safe_service: None,
+        rollup: Some(Rollup::OpStack {
+            portal: address!("0x5d66C1782664115999C47c9fA5cd031f495D3e4F"),
+        }),
+        aliases: &[],
+    },
+    ChainInfo {
+        name: "Holesky",
+        chain_id: 17000,
+        explorer: ExplorerKind::EtherscanV2 {
+            label: "Etherscan (Holesky)",
+        },
+        currency: "ETH",
+        safe_service: None,
+        rollup: None,
+        aliases: &["ethereum holesky"],
+    },
+    ChainInfo {
+        name: "Hoodi",
+        chain_id: 560048,
+        explorer: ExplorerKind::EtherscanV2 {
+            label: "Etherscan (Hoodi)",
+        },
+        currency: "ETH",
+        safe_service: None,
+        rollup: None,
+        aliases: &["ethereum hoodi"],
+    },
+    ChainInfo {
+        name: "Optimism",
+        chain_id: 10,
+        explorer: ExplorerKind::EtherscanV2 {
+            label: "Optimistic Etherscan",
+        },
+        currency: "ETH",
+        safe_service: Some("https://safe-transaction-optimism.safe.global"),
+        rollup: Some(Rollup::OpStack {
+            portal: address!("0xbEb5Fc579115071764c7423A4f12eDde41f106Ed"),
+        }),
+        aliases: &["op", "op mainnet", "optimism mainnet"],
+    },
+    ChainInfo {
+        name: "Optimism Sepolia",
+        chain_id: 11155420,
+        explorer: ExplorerKind::EtherscanV2 {
+            label: "Optimistic Etherscan (Sepolia)",
+        },
+        currency: "ETH",
+        safe_service: None,
+        rollup: None,
+        aliases: &["op sepolia"],
+    },
+    ChainInfo {
+        name: "Arbitrum Sepolia",
+        chain_id: 421614,
+        explorer: ExplorerKind::EtherscanV2 {
+            label: "Arbiscan (Sepolia)",
+        },
+        currency: "ETH",
+        safe_service: None,
+        rollup: None,
+        aliases: &[],
+    },
+    ChainInfo {
+        name: "Base Sepolia",
+        chain_id: 84532,
+        explorer: ExplorerKind::EtherscanV2 {
+            label: "Basescan (Sepolia)",
+        },
+        currency: "ETH",
+        safe_service: None,
+        rollup: None,
+        aliases: &[],
+    },
+    ChainInfo {
+        name: "Polygon",
+        chain_id: 137,
+        explorer: ExplorerKind::EtherscanV2 {
+            label: "Polygonscan",
+        },
+        currency: "POL",
+        safe_service: Some("https://safe-transaction-polygon.safe.global"),
+        rollup: None,
+        aliases: &["polygon pos", "matic"],
+    },
+    ChainInfo {
+        name: "Polygon Amoy",
+        chain_id: 80002,
+        explorer: ExplorerKind::EtherscanV2 {
+            label: "Polygonscan (Amoy)",
+        },
+        currency: "POL",
+        safe_service: None,
+        rollup: None,
+        aliases: &["amoy"],
+    },
+    ChainInfo {
+        name: "BSC",
+        chain_id: 56,
+        explorer: ExplorerKind::EtherscanV2 { label: "BscScan" },
+        currency: "BNB",
+        safe_service: Some("https://safe-transaction-bsc.safe.global"),
+        rollup: None,
+        aliases: &["bnb", "bnb chain", "bnb smart chain", "binance smart chain"],
+    },
+    ChainInfo {
+        name: "BSC Testnet",
+        chain_id: 97,
+        explorer: ExplorerKind::EtherscanV2 {
+            label: "BscScan (Testnet)",
+        },
+        currency: "tBNB",
+        safe_service: None,
+        rollup: None,
+        aliases: &["bnb testnet"],
+    },
+    ChainInfo {
+        name: "Avalanche",
+        chain_id: 43114,
+        explorer: ExplorerKind::EtherscanV2 { label: "Snowscan" },
+        currency: "AVAX",
+        safe_service: Some("https://safe-transaction-avalanche.safe.global"),
+        rollup: None,
+        aliases: &["avalanche c-chain", "avax"],
+    },
+    ChainInfo {
+        name: "Avalanche Fuji",
+        chain_id: 43113,
+        explorer: ExplorerKind::EtherscanV2 {
+            label: "Snowscan (Fuji)",
+        },
+        currency: "AVAX",
+        safe_service: None,
+        rollup: None,
+        aliases: &["fuji"],
+    },
+    ChainInfo {
+        name: "Gnosis",
+        chain_id: 100,
+        explorer: ExplorerKind::EtherscanV2 {
+            label: "Gnosisscan",
+        },
+        currency: "xDAI",
+        safe_service: Some("https://safe-transaction-gnosis-chain.safe.global"),
+        rollup: None,
+        aliases: &["gnosis chain", "xdai"],
+    },
+    ChainInfo {
+        name: "Gnosis Chiado",
+        chain_id: 10200,
+        explorer: ExplorerKind::Blockscout {
+            label: "Blockscout (Chiado)",
+            api_url: "https://gnosis-chiado.blockscout.com/api",
+        },
+        currency: "xDAI",
+        safe_service: None,
+        rollup: None,
+        aliases: &["chiado"],
+    },
+    ChainInfo {
+        name: "Scroll",
+        chain_id: 534352,
+        explorer: ExplorerKind::EtherscanV2 {
+            label: "Scrollscan",
+        },
+        currency: "ETH",
+        safe_service: Some("https://safe-transaction-scroll.safe.global"),
+        rollup: None,
+        aliases: &["scroll mainnet"],
+    },
+    ChainInfo {
+        name: "Scroll Sepolia",
+        chain_id: 534351,
+        explorer: ExplorerKind::EtherscanV2 {
+            label: "Scrollscan (Sepolia)",
+        },
+        currency: "ETH",
+        safe_service: None,
+        rollup: None,
+        aliases: &[],
+    },
+    ChainInfo {
+        name: "Linea",
+        chain_id: 59144,
+        explorer: ExplorerKind::EtherscanV2 { label: "Lineascan" },
+        currency: "ETH",
+        safe_service: Some("https://safe-transaction-linea.safe.global"),
+        rollup: None,
+        aliases: &["linea mainnet"],
+    },
+    ChainInfo {
+        name: "Linea Sepolia",
+        chain_id: 59141,
+        explorer: ExplorerKind::EtherscanV2 {
+            label: "Lineascan (Sepolia)",
+        },
+        currency: "ETH",
+        safe_service: None,
+        rollup: None,
+        aliases: &[],
+    },
+    ChainInfo {
+        name: "zkSync",
+        chain_id: 324,
+        explorer: ExplorerKind::EtherscanV2 {
+            label: "zkSync Era Explorer",
+        },
+        currency: "ETH",
+        safe_service: Some("https://safe-transaction-zksync.safe.global"),
+        rollup: None,
+        aliases: &["zksync era", "zksync mainnet"],
+    },
+    ChainInfo {
+        name: "zkSync Sepolia",
+        chain_id: 300,
+        explorer: ExplorerKind::EtherscanV2 {
+            label: "zkSync Era Explorer (Sepolia)",
+        },
+        currency: "ETH",
+        safe_service: None,
+        rollup: None,
+        aliases: &["zksync era sepolia"],
+    },
+    ChainInfo {
+        name: "Blast",
+        chain_id: 81457,
+        explorer: ExplorerKind::EtherscanV2 { label: "Blastscan" },
+        currency: "ETH",
+        safe_service: Some("https://safe-transaction-blast.safe.global"),
+        rollup: Some(Rollup::OpStack {
+            portal: address!("0x0Ec68c5B10F21EffB74f2A5C61DFe6b08C0Db6Cb"),
+        }),
+        aliases: &["blast mainnet"],
+    },
+    ChainInfo {
+        name: "Blast Sepolia",
+        chain_id: 168587773,
+        explorer: ExplorerKind::EtherscanV2 {
+            label: "Blastscan (Sepolia)",
+        },
+        currency: "ETH",
+        safe_service: None,
+        rollup: None,
+        aliases: &[],
+    },
+];
+
+/// Look up a chain by display name or alias (case-insensitive).
+pub fn resolve_chain(chain: &str) -> Option<&'static ChainInfo> {
+    let normalized = chain.trim().to_ascii_lowercase();
+    CHAINS.iter().find(|info| info.matches(&normalized))
+}
+
+/// Native currency symbol for `chain`, ETH for chains we don't know.
+pub fn currency(chain: &str) -> &'static str {
+    resolve_chain(chain).map_or("ETH", |info| info.currency)
+}
+
+/// Chains not hidden with `:chains hide`, in registry order. `hidden` holds
+/// lowercase chain names.
+pub fn visible(hidden: &BTreeSet<String>) -> impl Iterator<Item = &'static ChainInfo> + '_ {
+    CHAINS
+        .iter()
+        .filter(|info| !hidden.contains(&info.name.to_ascii_lowercase()))
+}
+
+/// Warning shown when an endpoint serves a different chain than the entity
+/// being viewed. Chains we don't know (e.g. "Local") are never flagged.
+pub fn chain_mismatch(chain: &str, reported_chain_id: u64) -> Option<String> {
+    let expected = resolve_chain(chain)?;
+    if expected.chain_id == reported_chain_id {
+        return None;
+    }
+    let reported = CHAINS
+        .iter()
+        .find(|info| info.chain_id == reported_chain_id)
+        .map_or_else(
+            || format!("chain id {reported_chain_id}"),
+            |info| format!("{} ({reported_chain_id})", info.name),
+        );
+    Some(format!(
+        "⚠ CHAIN MISMATCH: the RPC endpoint serves {reported}, but this entry is on {} ({}). \
+         Balances, nonce, and code below come from the wrong network.",
+        expected.name, expected.chain_id
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_names_and_aliases() {
+        assert_eq!(resolve_chain("Mainnet").map(|c| c.chain_id), Some(1));
+        assert_eq!(
+            resolve_chain(" Arbitrum One ").map(|c| c.chain_id),
+            Some(42161)
+        );
+        assert!(matches!(
+            resolve_chain("zora").map(|c| c.explorer),
+            Some(ExplorerKind::Blockscout { .. })
+        ));
+        assert!(resolve_chain("Local").is_none());
+        assert_eq!(resolve_chain("op").map(|c| c.chain_id), Some(10));
+        assert_eq!(resolve_chain("BNB Chain").map(|c| c.chain_id), Some(56));
+        assert_eq!(resolve_chain("amoy").map(|c| c.chain_id), Some(80002));
+        assert_eq!(currency("Polygon"), "POL");
+        assert_eq!(currency("gnosis chain"), "xDAI");
+        assert_eq!(currency("Local"), "ETH");
+        assert!(resolve_chain("Hoodi").unwrap().has_beacon_withdrawals());
+        assert!(!resolve_chain("Polygon").unwrap().has_beacon_withdrawals());
+        assert!(matches!(
+            resolve_chain("Blast").and_then(|c| c.rollup),
+            Some(Rollup::OpStack { .. })
+        ));
+    }
+
+    #[test]
+    fn registry_has_unique_ids_and_names() {
+        let ids: BTreeSet<u64> = CHAINS.iter().map(|c| c.chain_id).collect();
+        assert_eq!(ids.len(), CHAINS.len());
+        for chain in CHAINS {
+            let name = chain.name.to_ascii_lowercase();
+            assert_eq!(
+                resolve_chain(&name).map(|c| c.chain_id),
+                Some(chain.chain_id)
+            );
+            for alias in chain.aliases {
+                assert_eq!(
+                    resolve_chain(alias).map(|c| c.chain_id),
+                    Some(chain.chain_id)
+                );
+            }
+        }
+
+        let hidden = BTreeSet::from(["bsc testnet".to_string(), "polygon".to_string()]);
+        let shown: Vec<&str> = visible(&hidden).map(|c| c.name).collect();
+        assert_eq!(shown.len(), CHAINS.len() - 2);
+        assert!(!shown.contains(&"Polygon") && shown.contains(&"Polygon Amoy"));
+    }
+
+    #[test]
+    fn flags_endpoints_serving_another_chain() {
+        assert!(chain_mismatch("Mainnet", 1).is_none());
+        assert!(chain_mismatch("Local", 31337).is_none());
+        let warning = chain_mismatch("Mainnet", 11155111).unwrap();
+        assert!(warning.contains("Sepolia (11155111)"));
+        assert!(warning.contains("Mainnet (1)"));
+    }
+}
@@ -0,0 +1,211 @@
+use crate::ui::util::format_timestamp;
+use alloy::{
+    hex,
+    primitives::{
+        U256, keccak256,
+        utils::{format_units, parse_units},
+    },
+};
+
+/// Timestamps past this (year 5138) are more likely amounts than dates.
+const MAX_TIMESTAMP: u64 = 100_000_000_000;
+
+/// Every reading of `input` the converter knows: an amount with an optional
+/// unit (`1.5 ether`, `20 gwei`, `0x2386f26fc10000`), a UTC date
+/// (`2024-03-13 13:55`), and always the keccak256 of the input itself.
+pub fn convert(input: &str) -> Vec<(&'static str, String)> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Vec::new();
+    }
+    let mut rows = Vec::new();
+    if let Some((value, unit)) = parse_amount(input) {
+        match unit {
+            Some(unit) => rows.push(("unit", unit.to_string())),
+            None => {
+                rows.push(("decimal", value.to_string()));
+                rows.push(("hex", format!("{value:#x}")));
+            }
+        }
+        rows.push(("wei", value.to_string()));
+        rows.push(("gwei", units(value, "gwei")));
+        rows.push(("ether", units(value, "ether")));
+        if let (None, Some(secs)) = (
+            unit,
+            u64::try_from(value)
+                .ok()
+                .filter(|secs| *secs < MAX_TIMESTAMP),
+        ) {
+            rows.push(("as Unix time", format_timestamp(secs)));
+        }
+    }
+    if let Some(secs) = parse_date(input) {
+        rows.push(("Unix time", secs.to_string()));
+    }
+    match input.strip_prefix("0x").map(hex::decode) {
+        Some(Ok(bytes)) => rows.push(("keccak256 (bytes)", format!("{:#x}", keccak256(bytes)))),
+        _ => {
+            let hash = keccak256(input.as_bytes());
+            rows.push(("keccak256 (text)", format!("{hash:#x}")));
+            if input.ends_with(')') && input.contains('(') {
+                rows.push(("selector", hex::encode_prefixed(&hash[..4])));
+            }
+        }
+    }
+    rows
+}
+
+/// A whole number (decimal or `0x`) of wei, or a decimal followed by
+/// `wei`/`gwei`/`ether`. A bare fraction is read as ether.
+pub(super) fn parse_amount(input: &str) -> Option<(U256, Option<&'static str>)> {
+    let (number, unit) = match input.split_once(char::is_whitespace) {
+        Some((number, unit)) => (number, Some(unit.trim())),
+        None => {
+            let split = input
+                .find(|c: char| c.is_ascii_alphabetic() && c != 'x')
+                .filter(|_| !input.starts_with("0x"))
+                .unwrap_or(input.len());
+            let (number, unit) = input.split_at(split);
+            (number, (!unit.is_empty()).then_some(unit))
+        }
+    };
+    let number = number.replace('_', "");
+    let unit = match unit.map(str::to_ascii_lowercase).as_deref() {
+        None => None,
+        Some("wei") => Some("wei"),
+        Some("gwei") => Some("gwei"),
+        Some("ether" | "eth") => Some("ether"),
+        Some(_) => return None,
+    };
+    if let Some(digits) = number.strip_prefix("0x") {
+        return (unit.is_none() && !digits.is_empty())
+            .then(|| U256::from_str_radix(digits, 16).ok())?
+            .map(|value| (value, None));
+    }
+    if !number.bytes().all(|b| b.is_ascii_digit() || b == b'.') || number.is_empty() {
+        return None;
+    }
+    match unit {
+        None if !number.contains('.') => U256::from_str_radix(&number, 10)
+            .ok()
+            .map(|value| (value, None)),
+        unit => {
+            let unit = unit.unwrap_or("ether");
+            let value = parse_units(&number, unit).ok()?.get_absolute();
+            Some((value, Some(unit)))
+        }
+    }
+}
+
+/// `value` wei in `unit`, without trailing fractional zeros.
+fn units(value: U256, unit: &str) -> String {
+    let formatted = format_units(value, unit).unwrap_or_else(|_| value.to_string());
+    match formatted.split_once('.') {
+        Some((whole, fraction)) => match fraction.trim_end_matches('0') {
+            "" => whole.to_string(),
+            fraction => format!("{whole}.{fraction}"),
+        },
+        None => formatted,
+    }
+}
+
+/// `YYYY-MM-DD`, optionally followed by ` HH:MM[:SS]` (or `T…`), read as UTC.
+fn parse_date(input: &str) -> Option<u64> {
+    let input = input
+        .trim_end_matches("UTC")
+        .trim_end_matches('Z')
+        .trim_end();
+    let (date, time) = match input.split_once([' ', 'T']) {
+        Some((date, time)) => (date, Some(time.trim())),
+        None => (input, None),
+    };
+    let mut parts = date.split('-').map(|part| part.parse::<u32>().ok());
+    let (year, month, day) = (parts.next()??, parts.next()??, parts.next()??);
+    if parts.next().is_some() || !(1970..=9999).contains(&year) {
+        return None;
+    }
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+    let mut seconds = 0;
+    if let Some(time) = time {
+        let mut parts = time.split(':').map(|part| part.parse::<u64>().ok());
+        let hours = parts.next()??;
+        let minutes = parts.next()??;
+        let secs = parts.next().unwrap_or(Some(0))?;
+        if parts.next().is_some() || hours > 23 || minutes > 59 || secs > 59 {
+            return None;
+        }
+        seconds = hours * 3_600 + minutes * 60 + secs;
+    }
+    Some(days_from_civil(year, month, day) * 86_400 + seconds)
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01; the inverse of the civil date in `format_timestamp`.
+fn days_from_civil(year: u32, month: u32, day: u32) -> u64 {
+    let year = u64::from(year) - u64::from(month <= 2);
+    let era = year / 400;
+    let year_of_era = year % 400;
+    let shifted_month = u64::from((month + 9) % 12);
+    let day_of_year = (153 * shifted_month + 2) / 5 + u64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(rows: &[(&str, String)], label: &str) -> Option<String> {
+        rows.iter()
+            .find(|(name, _)| *name == label)
+            .map(|(_, value)| value.clone())
+    }
+
+    #[test]
+    fn converts_amounts_dates_and_hashes() {
+        let rows = convert("1.5 ether");
+        assert_eq!(row(&rows, "wei").as_deref(), Some("1500000000000000000"));
+        assert_eq!(row(&rows, "gwei").as_deref(), Some("1500000000"));
+        assert_eq!(row(&rows, "as Unix time"), None);
+        assert_eq!(
+            row(&convert("20gwei"), "wei").as_deref(),
+            Some("20000000000")
+        );
+
+        let rows = convert("0x2386f26fc10000");
+        assert_eq!(row(&rows, "decimal").as_deref(), Some("10000000000000000"));
+        assert_eq!(row(&rows, "ether").as_deref(), Some("0.01"));
+        assert!(row(&rows, "keccak256 (bytes)").is_some());
+
+        let rows = convert("1663224179");
+        assert_eq!(
+            row(&rows, "as Unix time").as_deref(),
+            Some("2022-09-15 06:42 UTC")
+        );
+        assert_eq!(
+            row(&convert("2022-09-15 06:42:59"), "Unix time").as_deref(),
+            Some("1663224179")
+        );
+        assert_eq!(
+            row(&convert("2000-02-29"), "Unix time").as_deref(),
+            Some("951782400")
+        );
+        assert_eq!(row(&convert("2001-02-29"), "Unix time"), None);
+
+        let rows = convert("transfer(address,uint256)");
+        assert_eq!(row(&rows, "selector").as_deref(), Some("0xa9059cbb"));
+        assert_eq!(row(&rows, "wei"), None);
+    }
+}
@@ -0,0 +1,339 @@
+use super::{
+    AddressRef, AddressTransactionRow,
+    explorer::{AddressTransaction, ExplorerBackend, InternalTransaction, TokenTransferRecord},
+    providers::Providers,
+    trim_decimal,
+};
+use crate::ui::util::format_timestamp;
+use alloy::primitives::{U256, utils::format_units};
+use color_eyre::{Result, eyre::WrapErr};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const EXPORT_DIR: &str = "exports";
+
+/// Rows requested from each list; explorers cap pages at 10,000 entries.
+const EXPORT_LIMIT: usize = 10_000;
+
+const TRANSACTION_HEADER: &[&str] = &[
+    "hash",
+    "block",
+    "unix_time",
+    "date_utc",
+    "direction",
+    "from",
+    "to",
+    "counterparty",
+    "value_eth",
+    "value_wei",
+    "status",
+];
+
+const TOKEN_HEADER: &[&str] = &[
+    "hash",
+    "block",
+    "unix_time",
+    "date_utc",
+    "direction",
+    "from",
+    "to",
+    "counterparty",
+    "token",
+    "symbol",
+    "amount",
+    "raw_amount",
+    "decimals",
+];
+
+/// Everything fetched for one address. Internal and token lists are kept
+/// as errors when the chain's history source does not offer them.
+#[derive(Debug, Clone)]
+pub struct History {
+    pub transactions: Vec<AddressTransaction>,
+    pub internal: Result<Vec<InternalTransaction>, String>,
+    pub tokens: Result<Vec<TokenTransferRecord>, String>,
+}
+
+/// What [`write_history`] wrote; `None` counts mark skipped files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportSummary {
+    pub dir: PathBuf,
+    pub transactions: usize,
+    pub internal: Option<usize>,
+    pub tokens: Option<usize>,
+}
+
+impl ExportSummary {
+    pub fn describe(&self) -> String {
+        let count = |rows: Option<usize>| match rows {
+            Some(rows) => rows.to_string(),
+            None => "n/a".into(),
+        };
+        format!(
+            "Exported {} transactions, {} internal, {} token transfers to {}",
+            self.transactions,
+            count(self.internal),
+            count(self.tokens),
+            self.dir.display()
+        )
+    }
+}
+
+/// `exports/history_<address prefix>`, used when no path is given.
+pub fn default_dir(address: &str) -> PathBuf {
+    Path::new(EXPORT_DIR).join(format!("history_{}", file_name_id(address)))
+}
+
+/// The first 8 hex digits of an address or hash, lowercased, which default
+/// export file names end in.
+pub fn file_name_id(id: &str) -> String {
+    id.trim_start_matches("0x")
+        .chars()
+        .take(8)
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+/// Expand a leading `~/` so paths typed in the command line behave like a shell.
+pub fn expand_path(input: &str) -> PathBuf {
+    match input.strip_prefix("~/").zip(dirs::home_dir()) {
+        Some((rest, home)) => home.join(rest),
+        None => PathBuf::from(input),
+    }
+}
+
+/// Fetch the address's transactions from its history source and its
+/// internal and token transfers from the chain explorer.
+pub async fn fetch_history(
+    providers: Providers,
+    address: AddressRef,
+    api_key: Option<String>,
+    rpc_url: Option<String>,
+) -> Result<History, String> {
+    let (transactions, _) = providers
+        .explorer
+        .address_transactions(
+            &address,
+            api_key.as_deref(),
+            rpc_url.as_deref(),
+            EXPORT_LIMIT,
+        )
+        .await
+        .map_err(|err| err.to_string())?;
+    let (internal, tokens) = match providers
+        .explorer
+        .explorer(&address.chain, api_key.as_deref())
+    {
+        Ok(explorer) => (
+            explorer
+                .internal_transactions(&address.address, EXPORT_LIMIT)
+                .await
+                .map_err(|err| err.to_string()),
+            explorer
+                .token_transfers(&address.address, EXPORT_LIMIT)
+                .await
+                .map_err(|err| err.to_string()),
+        ),
+        Err(err) => (Err(err.to_string()), Err(err.to_string())),
+    };
+    Ok(History {
+        transactions,
+        internal,
+        tokens,
+    })
+}
+
+/// Write `transactions.csv`, `internal.csv` and `token_transfers.csv` into
+/// `dir`. Lists that could not be fetched are skipped.
+pub fn write_history(dir: &Path, address: &str, history: &History) -> Result<ExportSummary> {
+    fs::create_dir_all(dir).wrap_err("failed to create export directory")?;
+    let rows: Vec<Vec<String>> = history
+        .transactions
+        .iter()
+        .map(|tx| transaction_record(address, tx))
+        .collect();
+    write_csv(&dir.join("transactions.csv"), TRANSACTION_HEADER, &rows)?;
+
+    let internal = match &history.internal {
+        Ok(internal) => {
+            let rows: Vec<Vec<String>> = internal
+                .iter()
+                .map(|entry| {
+                    let tx = AddressTransaction {
+                        hash: entry.hash.clone(),
+                        block_number: entry.block_number,
+                        from: entry.from.clone(),
+                        to: entry.to.clone(),
+                        value_wei: entry.value_wei,
+                        is_error: entry.is_error,
+                        input: None,
+                        timestamp: entry.timestamp,
+                        gas_used: None,
+                    };
+                    transaction_record(address, &tx)
+                })
+                .collect();
+            write_csv(&dir.join("internal.csv"), TRANSACTION_HEADER, &rows)?;
+            Some(rows.len())
+        }
+        Err(_) => None,
+    };
+
+    let tokens = match &history.tokens {
+        Ok(tokens) => {
+            let rows: Vec<Vec<String>> = tokens
+                .iter()
+                .map(|entry| token_record(address, entry))
+                .collect();
+            write_csv(&dir.join("token_transfers.csv"), TOKEN_HEADER, &rows)?;
+            Some(rows.len())
+        }
+        Err(_) => None,
+    };
+
+    Ok(ExportSummary {
+        dir: dir.to_path_buf(),
+        transactions: history.transactions.len(),
+        internal,
+        tokens,
+    })
+}
+
+/// Hash through counterparty, classified the same way as the Transactions tab.
+fn common_fields(address: &str, tx: &AddressTransaction) -> Vec<String> {
+    // Only the classification is used; values get their own columns.
+    let row = AddressTransactionRow::from_transaction(address, tx, "ETH");
+    vec![
+        tx.hash.clone(),
+        tx.block_number.to_string(),
+        tx.timestamp.map(|t| t.to_string()).unwrap_or_default(),
+        tx.timestamp.map(format_timestamp).unwrap_or_default(),
+        row.direction.label().to_string(),
+        row.from.clone(),
+        row.to.clone().unwrap_or_default(),
+        row.counterparty_address().unwrap_or_default().to_string(),
+    ]
+}
+
+fn transaction_record(address: &str, tx: &AddressTransaction) -> Vec<String> {
+    let mut record = common_fields(address, tx);
+    record.push(format_amount(tx.value_wei, 18));
+    record.push(tx.value_wei.to_string());
+    record.push(if tx.is_error { "Failed" } else { "OK" }.to_string());
+    record
+}
+
+fn token_record(address: &str, entry: &TokenTransferRecord) -> Vec<String> {
+    let tx = AddressTransaction {
+        hash: entry.hash.clone(),
+        block_number: entry.block_number,
+        from: entry.from.clone(),
+        to: Some(entry.to.clone()),
+        value_wei: U256::ZERO,
+        is_error: false,
+        input: None,
+        timestamp: entry.timestamp,
+        gas_used: None,
+    };
+    let mut record = common_fields(address, &tx);
+    record.push(entry.token.clone());
+    record.push(entry.symbol.clone());
+    record.push(
+        entry
+            .decimals
+            .map(|decimals| format_amount(entry.amount, decimals))
+            .unwrap_or_default(),
+    );
+    record.push(entry.amount.to_string());
+    record.push(
+        entry
+            .decimals
+            .map(|decimals| decimals.to_string())
+            .unwrap_or_default(),
+    );
+    record
+}
+
+fn format_amount(value: U256, decimals: u8) -> String {
+    match format_units(value, decimals) {
+        Ok(mut amount) => {
+            trim_decimal(&mut amount);
+            amount
+        }
+        Err(_) => value.to_string(),
+    }
+}
+
+/// Quote fields containing separators, quotes or line breaks (RFC 4180).
+fn escape_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn write_csv(path: &Path, header: &[&str], rows: &[Vec<String>]) -> Result<()> {
+    let mut out = header.join(",");
+    out.push('\n');
+    for row in rows {
+        let fields: Vec<String> = row.iter().map(|field| escape_field(field)).collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    fs::write(path, out).wrap_err_with(|| format!("failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn writes_each_history_list_with_eth_and_wei() -> Result<()> {
+        let me = "0x00000000000000000000000000000000000000aa";
+        let other = "0x00000000000000000000000000000000000000bb";
+        let history = History {
+            transactions: vec![AddressTransaction {
+                hash: "0x01".into(),
+                block_number: 7,
+                from: other.into(),
+                to: Some(me.into()),
+                value_wei: U256::from(1_500_000_000_000_000_000u128),
+                is_error: false,
+                input: None,
+                timestamp: Some(1_700_000_000),
+                gas_used: None,
+            }],
+            internal: Err("Otterscan does not list internal transactions".into()),
+            tokens: Ok(vec![TokenTransferRecord {
+                hash: "0x02".into(),
+                block_number: 8,
+                timestamp: None,
+                from: me.into(),
+                to: other.into(),
+                token: "0x00000000000000000000000000000000000000cc".into(),
+                symbol: "US,D".into(),
+                decimals: Some(6),
+                amount: U256::from(2_500_000),
+            }]),
+        };
+        let dir = tempdir()?;
+        let summary = write_history(dir.path(), me, &history)?;
+        assert_eq!((summary.transactions, summary.internal), (1, None));
+        assert!(!dir.path().join("internal.csv").exists());
+
+        let transactions = fs::read_to_string(dir.path().join("transactions.csv"))?;
+        let row = transactions.lines().nth(1).unwrap();
+        assert!(row.starts_with("0x01,7,1700000000,2023-11-14 22:13 UTC,Incoming,"));
+        assert!(row.ends_with(",1.5,1500000000000000000,OK"));
+
+        let tokens = fs::read_to_string(dir.path().join("token_transfers.csv"))?;
+        let row = tokens.lines().nth(1).unwrap();
+        assert!(row.contains(",Outgoing,"));
+        assert!(row.ends_with(",\"US,D\",2.5,2500000,6"));
+        Ok(())
+    }
+}
@@ -0,0 +1,227 @@
+use super::{MainViewMode, MainViewTab, SelectedEntity, plugins::PluginEvent};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, process::Stdio, time::Duration};
+use tokio::{io::AsyncWriteExt, process::Command, time::timeout};
+
+/// How long a tab's script may run before it is killed.
+const SCRIPT_TIMEOUT: Duration = Duration::from_secs(20);
+/// Output lines kept; longer output is cut.
+const MAX_LINES: usize = 500;
+
+/// Which selections a custom tab shows up for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TabScope {
+    #[default]
+    Both,
+    Address,
+    Transaction,
+}
+
+/// A main view tab filled by a script run for each selection and/or by
+/// plugin panels sent to it; stored under the `view.custom_tabs` setting.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomTab {
+    pub title: String,
+    #[serde(default)]
+    pub scope: TabScope,
+    /// Empty for tabs only plugins fill.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+}
+
+impl CustomTab {
+    pub fn applies(&self, mode: MainViewMode) -> bool {
+        match self.scope {
+            TabScope::Both => true,
+            TabScope::Address => mode == MainViewMode::Address,
+            TabScope::Transaction => mode == MainViewMode::Transaction,
+        }
+    }
+}
+
+/// A script's output for one selection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TabContent {
+    Loading,
+    Ready(Vec<String>),
+    Failed(String),
+}
+
+/// Lowercase address or transaction hash a tab's content belongs to.
+pub fn subject(entity: &SelectedEntity) -> String {
+    match entity {
+        SelectedEntity::Address(addr) => addr.address.to_ascii_lowercase(),
+        SelectedEntity::Transaction(tx) => tx.hash.to_ascii_lowercase(),
+    }
+}
+
+/// Custom tabs and their scripts' output per selection for the session.
+#[derive(Debug, Default)]
+pub struct CustomTabState {
+    pub tabs: Vec<CustomTab>,
+    /// Keyed by lowercase title and subject.
+    content: HashMap<(String, String), TabContent>,
+}
+
+impl CustomTabState {
+    pub fn new(tabs: Vec<CustomTab>) -> Self {
+        Self {
+            tabs,
+            ..Self::default()
+        }
+    }
+
+    /// Indexes of the tabs shown in `mode`, in the order they were added.
+    pub fn for_mode(&self, mode: MainViewMode) -> Vec<usize> {
+        (0..self.tabs.len())
+            .filter(|index| self.tabs[*index].applies(mode))
+            .collect()
+    }
+
+    /// The tab `tab` shows in `mode`; custom tabs missing there fall back to
+    /// the mode's first tab.
+    pub fn resolve(&self, tab: MainViewTab, mode: MainViewMode) -> MainViewTab {
+        match tab.normalize(mode) {
+            MainViewTab::Custom(index)
+                if !self.tabs.get(index).is_some_and(|tab| tab.applies(mode)) =>
+            {
+                MainViewTab::default().normalize(mode)
+            }
+            tab => tab,
+        }
+    }
+
+    /// Add `tab`, or replace the one with the same title in place.
+    pub fn set(&mut self, tab: CustomTab) {
+        let title = tab.title.to_ascii_lowercase();
+        self.content.retain(|(tab, _), _| *tab != title);
+        match self
+            .tabs
+            .iter_mut()
+            .find(|existing| existing.title.eq_ignore_ascii_case(&title))
+        {
+            Some(existing) => *existing = tab,
+            None => self.tabs.push(tab),
+        }
+    }
+
+    pub fn remove(&mut self, title: &str) -> Option<CustomTab> {
+        let index = self
+            .tabs
+            .iter()
+            .position(|tab| tab.title.eq_ignore_ascii_case(title))?;
+        let title = title.to_ascii_lowercase();
+        self.content.retain(|(tab, _), _| *tab != title);
+        Some(self.tabs.remove(index))
+    }
+
+    pub fn content(&self, tab: &CustomTab, subject: &str) -> Option<&TabContent> {
+        self.content
+            .get(&(tab.title.to_ascii_lowercase(), subject.to_ascii_lowercase()))
+    }
+
+    pub fn set_content(&mut self, title: &str, subject: &str, content: TabContent) {
+        self.content.insert(
+            (title.to_ascii_lowercase(), subject.to_ascii_lowercase()),
+            content,
+        );
+    }
+
+    /// Drop `tab`'s output for `subject` so the script runs again.
+    pub fn invalidate(&mut self, tab: &CustomTab, subject: &str) {
+        self.content
+            .remove(&(tab.title.to_ascii_lowercase(), subject.to_ascii_lowercase()));
+    }
+}
+
+/// Run `tab`'s script for `entity`: the selection arrives as a
+/// `selection_changed` plugin event line on stdin and in the
+/// `EVM_TUI_KIND`, `EVM_TUI_CHAIN` and `EVM_TUI_ID` variables; stdout is the
+/// tab's content.
+pub async fn run(tab: CustomTab, entity: SelectedEntity) -> Result<Vec<String>, String> {
+    let (kind, chain, id) = match &entity {
+        SelectedEntity::Address(addr) => ("address", &addr.chain, &addr.address),
+        SelectedEntity::Transaction(tx) => ("transaction", &tx.chain, &tx.hash),
+    };
+    let mut child = Command::new(&tab.command)
+        .args(&tab.args)
+        .env("EVM_TUI_KIND", kind)
+        .env("EVM_TUI_CHAIN", chain)
+        .env("EVM_TUI_ID", id)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|err| format!("failed to start {}: {err}", tab.command))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        let event = PluginEvent::selection_changed(&entity);
+        let mut line = serde_json::to_string(&event).unwrap_or_default();
+        line.push('\n');
+        // Scripts that only read the environment may close stdin early.
+        let _ = stdin.write_all(line.as_bytes()).await;
+    }
+    let output = timeout(SCRIPT_TIMEOUT, child.wait_with_output())
+        .await
+        .map_err(|_| format!("{} timed out", tab.command))?
+        .map_err(|err| format!("{} failed: {err}", tab.command))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr.lines().find(|line| !line.trim().is_empty());
+        return Err(match reason {
+            Some(reason) => format!("{} {}: {reason}", tab.command, output.status),
+            None => format!("{} {}", tab.command, output.status),
+        });
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(stdout.lines().take(MAX_LINES).map(str::to_string).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tab(title: &str, scope: TabScope) -> CustomTab {
+        CustomTab {
+            title: title.into(),
+            scope,
+            command: "compliance-check".into(),
+            args: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn tabs_follow_their_scope_and_keep_output_per_subject() {
+        let mut tabs = CustomTabState::new(vec![
+            tab("Compliance", TabScope::Address),
+            tab("Internal DB", TabScope::Both),
+        ]);
+        assert_eq!(tabs.for_mode(MainViewMode::Address), vec![0, 1]);
+        assert_eq!(tabs.for_mode(MainViewMode::Transaction), vec![1]);
+        assert_eq!(
+            tabs.resolve(MainViewTab::Custom(0), MainViewMode::Transaction),
+            MainViewTab::TransactionSummary
+        );
+        assert_eq!(
+            tabs.resolve(MainViewTab::Custom(1), MainViewMode::Transaction),
+            MainViewTab::Custom(1)
+        );
+
+        tabs.set_content("compliance", "0xAB", TabContent::Ready(vec!["ok".into()]));
+        let compliance = tabs.tabs[0].clone();
+        assert_eq!(
+            tabs.content(&compliance, "0xab"),
+            Some(&TabContent::Ready(vec!["ok".into()]))
+        );
+        tabs.set(tab("COMPLIANCE", TabScope::Both));
+        assert_eq!(tabs.tabs.len(), 2);
+        assert_eq!(tabs.for_mode(MainViewMode::Transaction), vec![0, 1]);
+        assert_eq!(tabs.content(&compliance, "0xab"), None);
+
+        assert!(tabs.remove("internal db").is_some());
+        assert!(tabs.remove("internal db").is_none());
+    }
+}
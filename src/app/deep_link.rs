@@ -0,0 +1,238 @@
+use super::search::parse_address_input;
+use super::{
+    AddressRef, MainViewTab, SelectedEntity, TransactionRef, chains::resolve_chain,
+    custom_tabs::CustomTabState,
+};
+use crate::ui::util::short_hex;
+use std::fmt::Write as _;
+
+pub const SCHEME: &str = "evmtui://";
+
+/// Built-in tabs by their slug in a link's `tab` parameter.
+const TABS: [(&str, MainViewTab); 15] = [
+    ("info", MainViewTab::AddressInfo),
+    ("transactions", MainViewTab::AddressTransactions),
+    ("internal", MainViewTab::AddressInternal),
+    ("events", MainViewTab::AddressEvents),
+    ("storage", MainViewTab::AddressStorage),
+    ("balances", MainViewTab::AddressBalances),
+    ("permissions", MainViewTab::AddressPermissions),
+    ("chains", MainViewTab::AddressChains),
+    ("summary", MainViewTab::TransactionSummary),
+    ("fees", MainViewTab::TransactionFees),
+    ("balance-diff", MainViewTab::TransactionBalanceDiff),
+    ("access-list", MainViewTab::TransactionAccessList),
+    ("debug", MainViewTab::TransactionDebug),
+    ("storage-diff", MainViewTab::TransactionStorageDiff),
+    ("raw", MainViewTab::TransactionRaw),
+];
+
+/// A view someone can open from `evmtui://<chain>/address/<0x…>?tab=<tab>`
+/// or `evmtui://<chain>/tx/<0x…>?tab=<tab>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeepLink {
+    pub entity: SelectedEntity,
+    /// A built-in tab's slug or a custom tab's title, as given.
+    pub tab: Option<String>,
+}
+
+fn builtin_tab(slug: &str) -> Option<MainViewTab> {
+    TABS.iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(slug))
+        .map(|(_, tab)| *tab)
+}
+
+/// A link's `tab` for `tab`: its slug, or a custom tab's title.
+pub fn tab_param(tab: MainViewTab, custom: &CustomTabState) -> Option<String> {
+    match tab {
+        MainViewTab::Custom(index) => custom.tabs.get(index).map(|tab| tab.title.clone()),
+        tab => TABS
+            .iter()
+            .find(|(_, known)| *known == tab)
+            .map(|(slug, _)| slug.to_string()),
+    }
+}
+
+/// The tab a link's `tab` names: a built-in slug, else a custom tab of that
+/// title. Unknown tabs are ignored, so links still open without the sender's
+/// custom tabs.
+pub fn resolve_tab(tab: &str, custom: &CustomTabState) -> Option<MainViewTab> {
+    builtin_tab(tab).or_else(|| {
+        custom
+            .tabs
+            .iter()
+            .position(|custom| custom.title.eq_ignore_ascii_case(tab))
+            .map(MainViewTab::Custom)
+    })
+}
+
+fn encode(value: &str) -> String {
+    let mut encoded = String::new();
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+    }
+    encoded
+}
+
+fn decode(value: &str) -> Result<String, String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'%' => {
+                let byte = value
+                    .get(index + 1..index + 3)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or_else(|| format!("Bad escape in {value}"))?;
+                decoded.push(byte);
+                index += 3;
+            }
+            b'+' => {
+                decoded.push(b' ');
+                index += 1;
+            }
+            byte => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).map_err(|_| format!("{value} is not UTF-8"))
+}
+
+/// Link to `entity` on `tab` (a built-in tab's slug or a custom tab's
+/// title). Known chains are written by their lowercase name.
+pub fn to_uri(entity: &SelectedEntity, tab: Option<&str>) -> String {
+    let chain_segment = |chain: &str| match resolve_chain(chain) {
+        Some(info) => info.name.to_ascii_lowercase(),
+        None => encode(chain),
+    };
+    let mut uri = match entity {
+        SelectedEntity::Address(addr) => format!(
+            "{SCHEME}{}/address/{}",
+            chain_segment(&addr.chain),
+            addr.address
+        ),
+        SelectedEntity::Transaction(tx) => {
+            format!("{SCHEME}{}/tx/{}", chain_segment(&tx.chain), tx.hash)
+        }
+    };
+    if let Some(tab) = tab {
+        let _ = write!(uri, "?tab={}", encode(tab));
+    }
+    uri
+}
+
+/// Parse a link. Chains the tool knows resolve by name or alias to their
+/// display name; others (e.g. `local`) are kept as written.
+pub fn parse(input: &str) -> Result<DeepLink, String> {
+    let rest = input
+        .trim()
+        .strip_prefix(SCHEME)
+        .ok_or_else(|| format!("Links start with {SCHEME}"))?;
+    let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let [chain, kind, id] = segments.as_slice() else {
+        return Err(format!(
+            "Expected {SCHEME}<chain>/address/<0x…> or {SCHEME}<chain>/tx/<0x…>"
+        ));
+    };
+    let chain = decode(chain)?;
+    let chain = resolve_chain(&chain)
+        .map(|info| info.name.to_string())
+        .unwrap_or(chain);
+    let entity = match kind.to_ascii_lowercase().as_str() {
+        "address" => {
+            let address = parse_address_input(id)?.to_checksum(None);
+            SelectedEntity::Address(AddressRef {
+                label: format!("Address {}", short_hex(&address)),
+                address,
+                chain,
+            })
+        }
+        "tx" | "transaction" => {
+            let hex = id.strip_prefix("0x").unwrap_or(id);
+            if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(format!("{id} is not a transaction hash"));
+            }
+            let hash = format!("0x{}", hex.to_ascii_lowercase());
+            SelectedEntity::Transaction(TransactionRef {
+                label: format!("Txn {}", short_hex(&hash)),
+                hash,
+                chain,
+            })
+        }
+        other => return Err(format!("Unknown link kind {other}; use address or tx")),
+    };
+    let mut tab = None;
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        if let Some(("tab", value)) = pair.split_once('=') {
+            tab = Some(decode(value)?).filter(|value| !value.is_empty());
+        }
+    }
+    Ok(DeepLink { entity, tab })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_links() {
+        let address = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+        let link = parse(&format!(
+            "evmtui://ethereum/address/{}?tab=transactions",
+            address.to_ascii_lowercase()
+        ))
+        .unwrap();
+        let SelectedEntity::Address(addr) = &link.entity else {
+            panic!("expected an address");
+        };
+        assert_eq!(
+            (addr.chain.as_str(), addr.address.as_str()),
+            ("Mainnet", address)
+        );
+        assert_eq!(link.tab.as_deref(), Some("transactions"));
+        assert_eq!(
+            to_uri(&link.entity, link.tab.as_deref()),
+            format!("evmtui://mainnet/address/{address}?tab=transactions")
+        );
+        let custom = CustomTabState::default();
+        assert_eq!(
+            resolve_tab("Transactions", &custom),
+            Some(MainViewTab::AddressTransactions)
+        );
+        assert_eq!(
+            tab_param(MainViewTab::TransactionBalanceDiff, &custom).as_deref(),
+            Some("balance-diff")
+        );
+        assert_eq!(resolve_tab("Gas report", &custom), None);
+
+        let hash = format!("0x{}", "ab".repeat(32));
+        let entity = SelectedEntity::Transaction(TransactionRef {
+            label: "swap".into(),
+            hash: hash.clone(),
+            chain: "My Devnet".into(),
+        });
+        let uri = to_uri(&entity, Some("Gas report"));
+        assert_eq!(
+            uri,
+            format!("evmtui://My%20Devnet/tx/{hash}?tab=Gas%20report")
+        );
+        let link = parse(&uri).unwrap();
+        assert!(matches!(
+            &link.entity,
+            SelectedEntity::Transaction(tx) if tx.chain == "My Devnet" && tx.hash == hash
+        ));
+        assert_eq!(link.tab.as_deref(), Some("Gas report"));
+
+        assert!(parse("https://mainnet/address/0x00").is_err());
+        assert!(parse("evmtui://mainnet/tx/0x1234").is_err());
+        assert!(parse("evmtui://mainnet/block/1").is_err());
+    }
+}
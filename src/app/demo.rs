@@ -0,0 +1,958 @@
+use super::{
+    AddressRef, SecretsState,
+    chains::{CHAINS, resolve_chain},
+    explorer::{
+        AddressTransaction, BeaconWithdrawal, ContractCreation, ContractSource, Explorer,
+        ExplorerBackend, ExplorerSession, InternalTransaction, SourceMatch, TokenTransferRecord,
+        TransactionFetchError, TransactionListSource,
+    },
+    providers::{BoxFuture, ExplorerProvider, Providers, Rpc, RpcAccountData, RpcConnector},
+};
+use crate::storage::{FavoriteRecord, FavoritesTable, SecretKey, Storage};
+use alloy::{
+    primitives::{Address, B256, Bytes, U256, address, keccak256},
+    providers::{DynProvider, Provider, ProviderBuilder},
+    rpc::{
+        client::RpcClient,
+        json_rpc::{
+            ErrorPayload, RequestPacket, Response, ResponsePacket, ResponsePayload,
+            SerializedRequest,
+        },
+    },
+    sol,
+    sol_types::{SolCall, SolEvent, SolValue},
+    transports::{TransportError, TransportFut},
+};
+use color_eyre::Result;
+use serde_json::{Value, json, value::RawValue};
+use std::{
+    path::Path,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tempfile::TempDir;
+
+sol! {
+    #[sol(abi)]
+    interface IDemoToken {
+        function transfer(address to, uint256 amount) external returns (bool);
+        function approve(address spender, uint256 amount) external returns (bool);
+        function balanceOf(address owner) external view returns (uint256);
+        function totalSupply() external view returns (uint256);
+        function symbol() external view returns (string);
+        function name() external view returns (string);
+        function decimals() external view returns (uint8);
+        event Transfer(address indexed from, address indexed to, uint256 value);
+    }
+}
+
+const URL_SCHEME: &str = "demo://";
+pub const API_KEY: &str = "demo";
+/// Head of every demo chain; fixture blocks count back from it.
+pub const LATEST_BLOCK: u64 = 21_000_000;
+const LATEST_TIMESTAMP: u64 = 1_730_000_000;
+const BASE_FEE: u64 = 12_000_000_000;
+const PRIORITY_FEE: u64 = 1_000_000_000;
+const ETH_PRICE_USD: f64 = 2_500.0;
+const ETHER: u128 = 1_000_000_000_000_000_000;
+/// Chain id answered for chains the registry does not know, like Anvil's.
+const LOCAL_CHAIN_ID: u64 = 31_337;
+
+pub const TREASURY: Address = address!("d3e0000000000000000000000000000000000001");
+pub const TOKEN: Address = address!("d3e0000000000000000000000000000000000002");
+pub const TRADER: Address = address!("d3e0000000000000000000000000000000000003");
+const TOKEN_SUPPLY: u128 = 1_000_000 * ETHER;
+/// Runtime code of the token; only its presence and size matter here.
+const TOKEN_CODE: &str = "0x608060405234801561001057600080fd5b50600436106100575760003560e01c806306fdde031461005c578063095ea7b31461007a57806318160ddd1461009d57806370a08231146100af578063a9059cbb146100d8575b600080fd5b";
+
+const TOKEN_SOURCE: &str = "// SPDX-License-Identifier: MIT
+pragma solidity 0.8.26;
+
+import {ERC20} from \"solmate/tokens/ERC20.sol\";
+
+contract DemoToken is ERC20(\"Demo Token\", \"DEMO\", 18) {
+    constructor() {
+        _mint(msg.sender, 1_000_000e18);
+    }
+}
+";
+
+/// Providers answering every RPC and explorer request from the fixtures
+/// below instead of the network, for `--demo`.
+pub fn providers() -> Providers {
+    let rpc = Rpc::new(Arc::new(DemoConnector));
+    Providers {
+        accounts: Arc::new(RpcAccountData(rpc.clone())),
+        explorer: Arc::new(DemoExplorers),
+        session: ExplorerSession {
+            stats: rpc.stats().clone(),
+            ..Default::default()
+        },
+        rpc,
+    }
+}
+
+fn rpc_url(chain: &str) -> String {
+    format!("{URL_SCHEME}{}", chain.to_ascii_lowercase())
+}
+
+struct Account {
+    label: &'static str,
+    address: Address,
+    balance_wei: u128,
+    nonce: u64,
+    code: &'static str,
+}
+
+const ACCOUNTS: [Account; 3] = [
+    Account {
+        label: "Demo Treasury",
+        address: TREASURY,
+        balance_wei: 1_234 * ETHER + ETHER / 2,
+        nonce: 42,
+        code: "0x",
+    },
+    Account {
+        label: "DEMO Token",
+        address: TOKEN,
+        balance_wei: 0,
+        nonce: 1,
+        code: TOKEN_CODE,
+    },
+    Account {
+        label: "Demo Trader",
+        address: TRADER,
+        balance_wei: 3 * ETHER + ETHER / 5,
+        nonce: 7,
+        code: "0x",
+    },
+];
+
+fn account(address: Address) -> Option<&'static Account> {
+    ACCOUNTS.iter().find(|account| account.address == address)
+}
+
+/// A mined fixture transaction.
+struct DemoTx {
+    hash: B256,
+    from: Address,
+    to: Option<Address>,
+    value_wei: u128,
+    input: Bytes,
+    block: u64,
+    index: u64,
+    nonce: u64,
+    gas_limit: u64,
+    gas_used: u64,
+    success: bool,
+    /// ERC-20 `Transfer` the call emits: from, to, amount.
+    transfer: Option<(Address, Address, U256)>,
+}
+
+impl DemoTx {
+    fn touches(&self, address: Address) -> bool {
+        self.from == address || self.to == Some(address)
+    }
+}
+
+/// A plain ETH transfer.
+fn send(position: u32, from: Address, to: Address, value_wei: u128, block: u64) -> DemoTx {
+    DemoTx {
+        hash: keccak256(format!("demo transaction {position}")),
+        from,
+        to: Some(to),
+        value_wei,
+        input: Bytes::new(),
+        block,
+        index: 0,
+        nonce: 0,
+        gas_limit: 21_000,
+        gas_used: 21_000,
+        success: true,
+        transfer: None,
+    }
+}
+
+/// The fixture history, oldest first.
+fn transactions() -> Vec<DemoTx> {
+    let token_call = |position, from, input: Vec<u8>, block, gas_used, success| DemoTx {
+        input: input.into(),
+        gas_limit: 80_000,
+        gas_used,
+        success,
+        ..send(position, from, TOKEN, 0, block)
+    };
+    vec![
+        // Deploying the token mints the supply to the treasury.
+        DemoTx {
+            to: None,
+            input: Bytes::from_static(&[0x60, 0x80, 0x60, 0x40, 0x52]),
+            nonce: 38,
+            gas_limit: 1_500_000,
+            gas_used: 1_214_330,
+            transfer: Some((Address::ZERO, TREASURY, U256::from(TOKEN_SUPPLY))),
+            ..send(0, TREASURY, TOKEN, 0, LATEST_BLOCK - 5_000)
+        },
+        DemoTx {
+            index: 3,
+            nonce: 39,
+            ..send(1, TREASURY, TRADER, 5 * ETHER, LATEST_BLOCK - 1_200)
+        },
+        DemoTx {
+            index: 11,
+            nonce: 40,
+            transfer: Some((TREASURY, TRADER, U256::from(10_000 * ETHER))),
+            ..token_call(
+                2,
+                TREASURY,
+                IDemoToken::transferCall {
+                    to: TRADER,
+                    amount: U256::from(10_000 * ETHER),
+                }
+                .abi_encode(),
+                LATEST_BLOCK - 900,
+                51_234,
+                true,
+            )
+        },
+        DemoTx {
+            index: 2,
+            nonce: 4,
+            transfer: Some((TRADER, TREASURY, U256::from(250 * ETHER))),
+            ..token_call(
+                3,
+                TRADER,
+                IDemoToken::transferCall {
+                    to: TREASURY,
+                    amount: U256::from(250 * ETHER),
+                }
+                .abi_encode(),
+                LATEST_BLOCK - 300,
+                34_512,
+                true,
+            )
+        },
+        // Runs out of gas.
+        DemoTx {
+            index: 7,
+            nonce: 5,
+            gas_limit: 24_000,
+            ..token_call(
+                4,
+                TRADER,
+                IDemoToken::approveCall {
+                    spender: TREASURY,
+                    amount: U256::MAX,
+                }
+                .abi_encode(),
+                LATEST_BLOCK - 120,
+                24_000,
+                false,
+            )
+        },
+        // Two transactions in one block, to step between with p/n.
+        DemoTx {
+            nonce: 6,
+            ..send(5, TRADER, TREASURY, ETHER / 2, LATEST_BLOCK - 12)
+        },
+        DemoTx {
+            index: 1,
+            nonce: 41,
+            ..send(6, TREASURY, TRADER, ETHER, LATEST_BLOCK - 12)
+        },
+    ]
+}
+
+fn find_transaction(hash: &str) -> Option<DemoTx> {
+    let hash: B256 = hash.parse().ok()?;
+    transactions().into_iter().find(|tx| tx.hash == hash)
+}
+
+fn block_hash(number: u64) -> B256 {
+    keccak256(format!("demo block {number}"))
+}
+
+fn block_timestamp(number: u64) -> u64 {
+    LATEST_TIMESTAMP.saturating_sub(LATEST_BLOCK.saturating_sub(number) * 12)
+}
+
+/// Secrets pointing every chain at the fixtures, so nothing asks for keys.
+pub fn secrets() -> SecretsState {
+    let mut secrets = SecretsState::default();
+    secrets.set(&SecretKey::EtherscanApiKey, Some(API_KEY.into()));
+    secrets.set(&SecretKey::AnvilRpcUrl, Some(rpc_url("local")));
+    for chain in CHAINS {
+        let name = chain.name.to_ascii_lowercase();
+        secrets.set(&SecretKey::RpcUrl(name.clone()), Some(rpc_url(&name)));
+    }
+    secrets
+}
+
+/// A throwaway data directory under the system temp directory, removed
+/// when dropped, so a demo never reads or changes the real one.
+pub fn data_dir() -> Result<TempDir> {
+    Ok(tempfile::Builder::new().prefix("evm-tui-demo-").tempdir()?)
+}
+
+/// Storage in `root`, seeded with the fixture favorites.
+pub fn open_storage(root: &Path) -> Result<Storage> {
+    let storage = Storage::open(root)?;
+    let mut batch = storage.favorites_batch();
+    for (order, account) in ACCOUNTS.iter().enumerate() {
+        batch = batch.upsert(
+            FavoritesTable::Addresses,
+            FavoriteRecord {
+                label: Some(account.label.to_string()),
+                identifier: account.address.to_checksum(None),
+                chain: "Mainnet".into(),
+                order: order as u64,
+            },
+        );
+    }
+    let transactions = transactions();
+    for (order, tx) in [&transactions[2], &transactions[4]].into_iter().enumerate() {
+        batch = batch.upsert(
+            FavoritesTable::Transactions,
+            FavoriteRecord {
+                label: Some(if tx.success {
+                    "DEMO transfer".into()
+                } else {
+                    "Failed approval".into()
+                }),
+                identifier: format!("{:#x}", tx.hash),
+                chain: "Mainnet".into(),
+                order: order as u64,
+            },
+        );
+    }
+    batch.commit()?;
+    Ok(storage)
+}
+
+/// Explorer answering from the fixtures, for every chain.
+pub struct DemoExplorer;
+
+impl ExplorerBackend for DemoExplorer {
+    fn source(&self) -> TransactionListSource {
+        TransactionListSource {
+            label: "Demo fixtures",
+            api_version: "demo",
+        }
+    }
+
+    async fn address_transactions(
+        &self,
+        address: &str,
+        limit: usize,
+    ) -> Result<Vec<AddressTransaction>, TransactionFetchError> {
+        let address = parse_address(address)?;
+        Ok(transactions()
+            .into_iter()
+            .rev()
+            .filter(|tx| tx.touches(address))
+            .take(limit)
+            .map(|tx| AddressTransaction {
+                hash: format!("{:#x}", tx.hash),
+                block_number: tx.block,
+                from: format!("{:#x}", tx.from),
+                to: tx.to.map(|to| format!("{to:#x}")),
+                value_wei: U256::from(tx.value_wei),
+                is_error: !tx.success,
+                input: Some(tx.input.to_string()),
+                timestamp: Some(block_timestamp(tx.block)),
+                gas_used: Some(tx.gas_used),
+            })
+            .collect())
+    }
+
+    async fn contract_source(
+        &self,
+        address: &str,
+    ) -> Result<Option<ContractSource>, TransactionFetchError> {
+        if parse_address(address)? != TOKEN {
+            return Ok(None);
+        }
+        Ok(Some(ContractSource {
+            name: "DemoToken".into(),
+            compiler: "v0.8.26+commit.8a97fa7a".into(),
+            abi: serde_json::to_string(&IDemoToken::abi::contract())
+                .map_err(TransactionFetchError::Parse)?,
+            source: TOKEN_SOURCE.into(),
+            source_label: "Demo fixtures",
+            match_type: SourceMatch::Verified,
+            storage_layout: None,
+        }))
+    }
+
+    async fn contract_creation(
+        &self,
+        address: &str,
+    ) -> Result<Option<ContractCreation>, TransactionFetchError> {
+        if parse_address(address)? != TOKEN {
+            return Ok(None);
+        }
+        let deployment = &transactions()[0];
+        Ok(Some(ContractCreation {
+            creator: deployment.from.to_checksum(None),
+            transaction_hash: format!("{:#x}", deployment.hash),
+            block_number: Some(deployment.block),
+            timestamp: Some(block_timestamp(deployment.block)),
+        }))
+    }
+
+    async fn internal_transactions(
+        &self,
+        _address: &str,
+        _limit: usize,
+    ) -> Result<Vec<InternalTransaction>, TransactionFetchError> {
+        Ok(Vec::new())
+    }
+
+    async fn token_transfers(
+        &self,
+        address: &str,
+        limit: usize,
+    ) -> Result<Vec<TokenTransferRecord>, TransactionFetchError> {
+        let address = parse_address(address)?;
+        Ok(transactions()
+            .into_iter()
+            .rev()
+            .filter_map(|tx| {
+                let (from, to, amount) = tx.transfer?;
+                (address == TOKEN || from == address || to == address).then(|| {
+                    TokenTransferRecord {
+                        hash: format!("{:#x}", tx.hash),
+                        block_number: tx.block,
+                        timestamp: Some(block_timestamp(tx.block)),
+                        from: format!("{from:#x}"),
+                        to: format!("{to:#x}"),
+                        token: format!("{TOKEN:#x}"),
+                        symbol: "DEMO".into(),
+                        decimals: Some(18),
+                        amount,
+                    }
+                })
+            })
+            .take(limit)
+            .collect())
+    }
+
+    async fn beacon_withdrawals(
+        &self,
+        _address: &str,
+        _limit: usize,
+    ) -> Result<Vec<BeaconWithdrawal>, TransactionFetchError> {
+        Ok(Vec::new())
+    }
+
+    async fn transaction_exists(&self, hash: &str) -> Result<bool, TransactionFetchError> {
+        Ok(find_transaction(hash).is_some())
+    }
+}
+
+/// [`DemoExplorer`] for every chain, with a fixed native price.
+struct DemoExplorers;
+
+impl ExplorerProvider for DemoExplorers {
+    fn address_transactions<'a>(
+        &'a self,
+        address: &'a AddressRef,
+        _api_key: Option<&'a str>,
+        _rpc_url: Option<&'a str>,
+        limit: usize,
+    ) -> BoxFuture<
+        'a,
+        Result<(Vec<AddressTransaction>, TransactionListSource), TransactionFetchError>,
+    > {
+        Box::pin(async move {
+            let transactions = DemoExplorer
+                .address_transactions(&address.address, limit)
+                .await?;
+            Ok((transactions, DemoExplorer.source()))
+        })
+    }
+
+    fn contract_source<'a>(
+        &'a self,
+        address: &'a AddressRef,
+        _api_key: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<Option<ContractSource>, TransactionFetchError>> {
+        Box::pin(DemoExplorer.contract_source(&address.address))
+    }
+
+    fn contract_creation<'a>(
+        &'a self,
+        address: &'a AddressRef,
+        _api_key: Option<&'a str>,
+        _rpc_url: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<Option<ContractCreation>, TransactionFetchError>> {
+        Box::pin(DemoExplorer.contract_creation(&address.address))
+    }
+
+    fn beacon_withdrawals<'a>(
+        &'a self,
+        address: &'a AddressRef,
+        _api_key: Option<&'a str>,
+        limit: usize,
+    ) -> BoxFuture<'a, Result<Option<Vec<BeaconWithdrawal>>, TransactionFetchError>> {
+        Box::pin(async move {
+            DemoExplorer
+                .beacon_withdrawals(&address.address, limit)
+                .await
+                .map(Some)
+        })
+    }
+
+    fn native_usd_price<'a>(
+        &'a self,
+        _chain: &'a str,
+        _api_key: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<Option<f64>, TransactionFetchError>> {
+        Box::pin(async { Ok(Some(ETH_PRICE_USD)) })
+    }
+
+    fn explorer(
+        &self,
+        _chain: &str,
+        _api_key: Option<&str>,
+    ) -> Result<Explorer, TransactionFetchError> {
+        Ok(Explorer::Demo(DemoExplorer))
+    }
+
+    fn fresh(&self) -> Arc<dyn ExplorerProvider> {
+        Arc::new(DemoExplorers)
+    }
+
+    fn with_timeout(&self, _timeout: Duration) -> Arc<dyn ExplorerProvider> {
+        Arc::new(DemoExplorers)
+    }
+}
+
+fn parse_address(address: &str) -> Result<Address, TransactionFetchError> {
+    address
+        .parse()
+        .map_err(|_| TransactionFetchError::Api(format!("invalid address {address}")))
+}
+
+/// Connects every URL to [`DemoRpc`].
+struct DemoConnector;
+
+impl RpcConnector for DemoConnector {
+    fn connect<'a>(&'a self, rpc_url: &'a str) -> BoxFuture<'a, Result<DynProvider>> {
+        Box::pin(async move { Ok(provider(rpc_url)) })
+    }
+}
+
+/// A provider whose requests are answered by [`DemoRpc`].
+fn provider(url: &str) -> DynProvider {
+    let chain = url.strip_prefix(URL_SCHEME).unwrap_or(url);
+    let chain_id = resolve_chain(chain)
+        .map(|info| info.chain_id)
+        .unwrap_or(LOCAL_CHAIN_ID);
+    ProviderBuilder::new()
+        .connect_client(RpcClient::new(DemoRpc { chain_id }, true))
+        .erased()
+}
+
+/// JSON-RPC transport serving the fixtures. Methods without fixture data
+/// fail like an endpoint that does not support them.
+#[derive(Debug, Clone)]
+struct DemoRpc {
+    chain_id: u64,
+}
+
+impl tower_service::Service<RequestPacket> for DemoRpc {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let chain_id = self.chain_id;
+        let response = match request {
+            RequestPacket::Single(request) => {
+                ResponsePacket::Single(respond_to(chain_id, &request))
+            }
+            RequestPacket::Batch(requests) => ResponsePacket::Batch(
+                requests
+                    .iter()
+                    .map(|request| respond_to(chain_id, request))
+                    .collect(),
+            ),
+        };
+        Box::pin(async move { Ok(response) })
+    }
+}
+
+fn respond_to(chain_id: u64, request: &SerializedRequest) -> Response {
+    let params: Vec<Value> = request
+        .params()
+        .and_then(|params| serde_json::from_str(params.get()).ok())
+        .unwrap_or_default();
+    let payload = match answer(chain_id, request.method(), &params) {
+        Ok(value) => match RawValue::from_string(value.to_string()) {
+            Ok(raw) => ResponsePayload::Success(raw),
+            Err(err) => ResponsePayload::Failure(ErrorPayload::internal_error_message(
+                err.to_string().into(),
+            )),
+        },
+        Err(payload) => ResponsePayload::Failure(payload),
+    };
+    Response {
+        id: request.id().clone(),
+        payload,
+    }
+}
+
+fn hex(value: impl Into<u128>) -> Value {
+    Value::String(format!("{:#x}", value.into()))
+}
+
+fn param_address(params: &[Value], index: usize) -> Option<Address> {
+    params.get(index)?.as_str()?.parse().ok()
+}
+
+/// `latest`-style tags are the fixture head; numbers are hex quantities.
+fn param_block(params: &[Value], index: usize) -> Option<u64> {
+    match params.get(index).and_then(Value::as_str) {
+        None | Some("latest" | "pending" | "safe" | "finalized") => Some(LATEST_BLOCK),
+        Some("earliest") => Some(0),
+        Some(number) => u64::from_str_radix(number.trim_start_matches("0x"), 16).ok(),
+    }
+}
+
+fn unsupported(method: &str) -> ErrorPayload {
+    ErrorPayload {
+        code: -32601,
+        message: format!("{method} is not available in demo mode").into(),
+        data: None,
+    }
+}
+
+fn answer(chain_id: u64, method: &str, params: &[Value]) -> Result<Value, ErrorPayload> {
+    let found = |tx: Option<DemoTx>, render: fn(u64, &DemoTx) -> Value| {
+        tx.map_or(Value::Null, |tx| render(chain_id, &tx))
+    };
+    Ok(match method {
+        "eth_chainId" => hex(chain_id),
+        "net_version" => Value::String(chain_id.to_string()),
+        "eth_blockNumber" => hex(LATEST_BLOCK),
+        "eth_gasPrice" => hex(BASE_FEE + PRIORITY_FEE),
+        "eth_maxPriorityFeePerGas" => hex(PRIORITY_FEE),
+        "eth_getBalance" => hex(param_address(params, 0)
+            .and_then(account)
+            .map_or(0, |account| account.balance_wei)),
+        "eth_getTransactionCount" => hex(param_address(params, 0)
+            .and_then(account)
+            .map_or(0, |account| account.nonce)),
+        "eth_getCode" => json!(
+            param_address(params, 0)
+                .and_then(account)
+                .map_or("0x", |account| account.code)
+        ),
+        "eth_getStorageAt" => json!(B256::ZERO),
+        "eth_getBlockByNumber" => match param_block(params, 0) {
+            Some(number) if number <= LATEST_BLOCK => {
+                block_json(chain_id, number, params.get(1) == Some(&Value::Bool(true)))
+            }
+            _ => Value::Null,
+        },
+        "eth_getBlockByHash" => {
+            let hash = params.first().and_then(Value::as_str).unwrap_or_default();
+            let known = transactions()
+                .iter()
+                .map(|tx| tx.block)
+                .chain([LATEST_BLOCK])
+                .find(|number| format!("{:#x}", block_hash(*number)) == hash);
+            match known {
+                Some(number) => {
+                    block_json(chain_id, number, params.get(1) == Some(&Value::Bool(true)))
+                }
+                None => Value::Null,
+            }
+        }
+        "eth_getTransactionByHash" => found(
+            params
+                .first()
+                .and_then(Value::as_str)
+                .and_then(find_transaction),
+            transaction_json,
+        ),
+        "eth_getTransactionReceipt" => found(
+            params
+                .first()
+                .and_then(Value::as_str)
+                .and_then(find_transaction),
+            |_, tx| receipt_json(tx),
+        ),
+        "eth_getLogs" => logs_json(params.first().unwrap_or(&Value::Null)),
+        "eth_call" => call(params.first().unwrap_or(&Value::Null))?,
+        other => return Err(unsupported(other)),
+    })
+}
+
+/// Token reads against the DEMO token; other calls revert.
+fn call(request: &Value) -> Result<Value, ErrorPayload> {
+    let to = request.get("to").and_then(Value::as_str);
+    let data = request
+        .get("input")
+        .or_else(|| request.get("data"))
+        .and_then(Value::as_str)
+        .and_then(|data| data.parse::<Bytes>().ok())
+        .unwrap_or_default();
+    let reverted = || ErrorPayload {
+        code: 3,
+        message: "execution reverted".into(),
+        data: None,
+    };
+    if to.and_then(|to| to.parse::<Address>().ok()) != Some(TOKEN) || data.len() < 4 {
+        return Err(reverted());
+    }
+    let encoded = match <[u8; 4]>::try_from(&data[..4]).unwrap_or_default() {
+        IDemoToken::balanceOfCall::SELECTOR => {
+            let owner = IDemoToken::balanceOfCall::abi_decode(&data)
+                .map_err(|_| reverted())?
+                .owner;
+            let balance: u128 = match owner {
+                TREASURY => TOKEN_SUPPLY - 10_000 * ETHER + 250 * ETHER,
+                TRADER => 10_000 * ETHER - 250 * ETHER,
+                _ => 0,
+            };
+            U256::from(balance).abi_encode()
+        }
+        IDemoToken::totalSupplyCall::SELECTOR => U256::from(TOKEN_SUPPLY).abi_encode(),
+        IDemoToken::decimalsCall::SELECTOR => U256::from(18).abi_encode(),
+        IDemoToken::symbolCall::SELECTOR => "DEMO".to_string().abi_encode(),
+        IDemoToken::nameCall::SELECTOR => "Demo Token".to_string().abi_encode(),
+        _ => return Err(reverted()),
+    };
+    Ok(json!(Bytes::from(encoded)))
+}
+
+fn transaction_json(chain_id: u64, tx: &DemoTx) -> Value {
+    json!({
+        "type": "0x2",
+        "chainId": hex(chain_id),
+        "nonce": hex(tx.nonce),
+        "gas": hex(tx.gas_limit),
+        "maxFeePerGas": hex(BASE_FEE * 2 + PRIORITY_FEE),
+        "maxPriorityFeePerGas": hex(PRIORITY_FEE),
+        "gasPrice": hex(BASE_FEE + PRIORITY_FEE),
+        "to": tx.to,
+        "value": hex(tx.value_wei),
+        "accessList": [],
+        "input": tx.input,
+        "r": "0x1",
+        "s": "0x1",
+        "yParity": "0x0",
+        "v": "0x0",
+        "hash": tx.hash,
+        "blockHash": block_hash(tx.block),
+        "blockNumber": hex(tx.block),
+        "transactionIndex": hex(tx.index),
+        "from": tx.from,
+    })
+}
+
+fn transfer_log(tx: &DemoTx) -> Option<Value> {
+    let (from, to, amount) = tx.transfer?;
+    let event = IDemoToken::Transfer {
+        from,
+        to,
+        value: amount,
+    };
+    let topics: Vec<B256> = event
+        .encode_topics()
+        .into_iter()
+        .map(|topic| topic.0)
+        .collect();
+    Some(json!({
+        "address": TOKEN,
+        "topics": topics,
+        "data": Bytes::from(event.encode_data()),
+        "blockNumber": hex(tx.block),
+        "blockHash": block_hash(tx.block),
+        "blockTimestamp": hex(block_timestamp(tx.block)),
+        "transactionHash": tx.hash,
+        "transactionIndex": hex(tx.index),
+        "logIndex": "0x0",
+        "removed": false,
+    }))
+}
+
+fn receipt_json(tx: &DemoTx) -> Value {
+    json!({
+        "type": "0x2",
+        "status": if tx.success { "0x1" } else { "0x0" },
+        "cumulativeGasUsed": hex(tx.gas_used),
+        "logs": transfer_log(tx).into_iter().collect::<Vec<_>>(),
+        "logsBloom": format!("0x{}", "0".repeat(512)),
+        "transactionHash": tx.hash,
+        "transactionIndex": hex(tx.index),
+        "blockHash": block_hash(tx.block),
+        "blockNumber": hex(tx.block),
+        "gasUsed": hex(tx.gas_used),
+        "effectiveGasPrice": hex(BASE_FEE + PRIORITY_FEE),
+        "from": tx.from,
+        "to": tx.to,
+        "contractAddress": tx.to.is_none().then_some(TOKEN),
+    })
+}
+
+/// Transfer logs of the DEMO token matching the filter's address, block range
+/// and first topic.
+fn logs_json(filter: &Value) -> Value {
+    let addresses: Vec<Address> = match filter.get("address") {
+        Some(Value::String(address)) => address.parse().into_iter().collect(),
+        Some(Value::Array(addresses)) => addresses
+            .iter()
+            .filter_map(|address| address.as_str()?.parse().ok())
+            .collect(),
+        _ => Vec::new(),
+    };
+    let from = param_block(
+        std::slice::from_ref(filter.get("fromBlock").unwrap_or(&Value::Null)),
+        0,
+    )
+    .unwrap_or(0);
+    let to = param_block(
+        std::slice::from_ref(filter.get("toBlock").unwrap_or(&Value::Null)),
+        0,
+    )
+    .unwrap_or(LATEST_BLOCK);
+    let topic0 = filter
+        .get("topics")
+        .and_then(|topics| topics.get(0))
+        .and_then(Value::as_str)
+        .and_then(|topic| topic.parse::<B256>().ok());
+    let logs: Vec<Value> = transactions()
+        .iter()
+        .filter(|tx| (from..=to).contains(&tx.block))
+        .filter(|_| addresses.is_empty() || addresses.contains(&TOKEN))
+        .filter(|_| topic0.is_none_or(|topic| topic == IDemoToken::Transfer::SIGNATURE_HASH))
+        .filter_map(transfer_log)
+        .collect();
+    Value::Array(logs)
+}
+
+fn block_json(chain_id: u64, number: u64, full: bool) -> Value {
+    let transactions: Vec<DemoTx> = transactions()
+        .into_iter()
+        .filter(|tx| tx.block == number)
+        .collect();
+    let gas_used: u64 = transactions.iter().map(|tx| tx.gas_used).sum();
+    let listed: Vec<Value> = transactions
+        .iter()
+        .map(|tx| {
+            if full {
+                transaction_json(chain_id, tx)
+            } else {
+                json!(tx.hash)
+            }
+        })
+        .collect();
+    json!({
+        "hash": block_hash(number),
+        "parentHash": block_hash(number.saturating_sub(1)),
+        "sha3Uncles": alloy::consensus::constants::EMPTY_OMMER_ROOT_HASH,
+        "miner": address!("4838b106fce9647bdf1e7877bf73ce8b0bad5f97"),
+        "stateRoot": B256::ZERO,
+        "transactionsRoot": B256::ZERO,
+        "receiptsRoot": B256::ZERO,
+        "logsBloom": format!("0x{}", "0".repeat(512)),
+        "difficulty": "0x0",
+        "number": hex(number),
+        "gasLimit": hex(30_000_000u64),
+        "gasUsed": hex(gas_used),
+        "timestamp": hex(block_timestamp(number)),
+        "extraData": "0x",
+        "mixHash": B256::ZERO,
+        "nonce": "0x0000000000000000",
+        "baseFeePerGas": hex(BASE_FEE),
+        "withdrawalsRoot": B256::ZERO,
+        "blobGasUsed": "0x0",
+        "excessBlobGas": "0x0",
+        "parentBeaconBlockRoot": B256::ZERO,
+        "size": hex(1_024u64),
+        "uncles": [],
+        "transactions": listed,
+        "withdrawals": [],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::anvil::{fetch_account_overview, fetch_block_context, fetch_chain_id};
+
+    #[tokio::test]
+    async fn answers_rpc_and_explorer_requests_from_fixtures() {
+        let url = rpc_url("base");
+        let providers = providers();
+        let rpc = &providers.rpc;
+        assert_eq!(fetch_chain_id(rpc, &url).await.unwrap(), 8453);
+
+        let overview = fetch_account_overview(rpc, &url, TOKEN).await.unwrap();
+        assert_eq!(overview.latest_block, LATEST_BLOCK);
+        assert!(overview.is_contract);
+        assert_eq!(overview.transaction_count, 1);
+
+        let hashes: Vec<String> = transactions()
+            .iter()
+            .map(|tx| format!("{:#x}", tx.hash))
+            .collect();
+        let context = fetch_block_context(rpc, &url, &hashes[6])
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            (context.block_number, context.index),
+            (LATEST_BLOCK - 12, 1)
+        );
+        assert_eq!(
+            context.neighbor(false).map(|hash| format!("{hash:#x}")),
+            Some(hashes[5].clone())
+        );
+
+        let receipt = provider(&url)
+            .get_transaction_receipt(hashes[2].parse().unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(receipt.inner.logs().len(), 1);
+        assert!(provider(&url).get_proof(TOKEN, vec![]).await.is_err());
+
+        let trader = AddressRef {
+            label: "Trader".into(),
+            address: format!("{TRADER:#x}"),
+            chain: "Base".into(),
+        };
+        let (history, source) = providers
+            .explorer
+            .address_transactions(&trader, None, Some(&url), 3)
+            .await
+            .unwrap();
+        assert_eq!(source.label, "Demo fixtures");
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].hash, hashes[6]);
+        assert!(history.iter().any(|tx| tx.is_error));
+        let source = DemoExplorer
+            .contract_source(&format!("{TOKEN:#x}"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(source.abi.contains("balanceOf"));
+        assert_eq!(
+            providers
+                .explorer
+                .native_usd_price("Base", None)
+                .await
+                .unwrap(),
+            Some(ETH_PRICE_USD)
+        );
+    }
+}
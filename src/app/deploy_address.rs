@@ -0,0 +1,153 @@
+use super::{anvil::fetch_code_sizes, providers::Rpc, search::parse_address_input};
+use alloy::{
+    hex,
+    primitives::{Address, B256, U256, keccak256},
+};
+
+/// Which address derivation a computed result came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    Create,
+    Create2,
+}
+
+impl Scheme {
+    pub fn label(self) -> &'static str {
+        match self {
+            Scheme::Create => "CREATE",
+            Scheme::Create2 => "CREATE2",
+        }
+    }
+}
+
+/// Calculator inputs as typed. Empty fields leave their scheme out.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeployInputs {
+    pub deployer: String,
+    pub nonce: String,
+    pub salt: String,
+    pub init_code: String,
+}
+
+impl DeployInputs {
+    /// Addresses for every scheme with its inputs filled in; an error
+    /// names the field that did not parse.
+    pub fn compute(&self) -> Vec<(Scheme, Result<Address, String>)> {
+        let deployer =
+            || parse_address_input(&self.deployer).map_err(|err| format!("Deployer: {err}"));
+        let mut results = Vec::new();
+        if !self.deployer.trim().is_empty() && !self.nonce.trim().is_empty() {
+            let address = deployer().and_then(|deployer| {
+                let nonce = parse_nonce(&self.nonce)?;
+                Ok(deployer.create(nonce))
+            });
+            results.push((Scheme::Create, address));
+        }
+        if !self.deployer.trim().is_empty()
+            && !self.salt.trim().is_empty()
+            && !self.init_code.trim().is_empty()
+        {
+            let address = deployer().and_then(|deployer| {
+                let salt = parse_salt(&self.salt)?;
+                let hash = parse_init_code_hash(&self.init_code)?;
+                Ok(deployer.create2(salt, hash))
+            });
+            results.push((Scheme::Create2, address));
+        }
+        results
+    }
+}
+
+fn parse_nonce(input: &str) -> Result<u64, String> {
+    input
+        .trim()
+        .parse()
+        .map_err(|_| format!("Nonce: {:?} is not a whole number", input.trim()))
+}
+
+/// A `0x` value of up to 32 bytes (left-padded) or a decimal number.
+fn parse_salt(input: &str) -> Result<B256, String> {
+    let input = input.trim();
+    let invalid = || format!("Salt: {input:?} is not a 32-byte hex value or a number");
+    match input.strip_prefix("0x") {
+        Some(digits) => {
+            let bytes = hex::decode(format!("{digits:0>64}")).map_err(|_| invalid())?;
+            (bytes.len() == 32)
+                .then(|| B256::from_slice(&bytes))
+                .ok_or_else(invalid)
+        }
+        None => U256::from_str_radix(input, 10)
+            .map(B256::from)
+            .map_err(|_| invalid()),
+    }
+}
+
+/// A 32-byte value is taken as the init code hash; longer hex as the init
+/// code itself, which gets hashed.
+fn parse_init_code_hash(input: &str) -> Result<B256, String> {
+    let bytes = hex::decode(input.trim())
+        .map_err(|_| "Init code: expected a hex hash or init code".to_string())?;
+    match bytes.len() {
+        32 => Ok(B256::from_slice(&bytes)),
+        0 => Err("Init code: empty".into()),
+        _ => Ok(keccak256(&bytes)),
+    }
+}
+
+/// Code size at each computed address, so the modal can flag ones already
+/// deployed.
+pub async fn check_deployed(
+    rpc: Rpc,
+    rpc_url: String,
+    addresses: Vec<Address>,
+) -> Result<Vec<(Address, usize)>, String> {
+    let sizes = fetch_code_sizes(&rpc, &rpc_url, addresses.clone())
+        .await
+        .map_err(|err| format!("{err:#}"))?;
+    Ok(addresses.into_iter().zip(sizes).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::address;
+
+    #[test]
+    fn computes_create_and_create2_addresses() {
+        // EIP-1014 example 2 and the first CREATE of a well-known deployer.
+        let inputs = DeployInputs {
+            deployer: "0xdeadbeef00000000000000000000000000000000".into(),
+            nonce: String::new(),
+            salt: "0x0".into(),
+            init_code: "0x00".into(),
+        };
+        let computed = inputs.compute();
+        assert_eq!(computed.len(), 1);
+        assert_eq!(
+            computed[0],
+            (
+                Scheme::Create2,
+                Ok(address!("0xB928f69Bb1D91Cd65274e3c79d8986362984fDA3"))
+            )
+        );
+
+        let inputs = DeployInputs {
+            deployer: "0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0".into(),
+            nonce: "0".into(),
+            ..DeployInputs::default()
+        };
+        assert_eq!(
+            inputs.compute(),
+            vec![(
+                Scheme::Create,
+                Ok(address!("0xcd234a471b72ba2f1ccf0a70fcaba648a5eecd8d"))
+            )]
+        );
+
+        let inputs = DeployInputs {
+            nonce: "x".into(),
+            ..inputs
+        };
+        assert!(matches!(&inputs.compute()[0].1, Err(err) if err.starts_with("Nonce")));
+    }
+}
@@ -0,0 +1,102 @@
+use super::{anvil::with_failover, providers::Rpc};
+use alloy::{
+    primitives::{Address, B256, address, keccak256},
+    sol,
+};
+use color_eyre::{Result, eyre::WrapErr};
+
+sol! {
+    #[sol(rpc)]
+    interface IEnsRegistry {
+        function resolver(bytes32 node) external view returns (address);
+    }
+
+    #[sol(rpc)]
+    interface IEnsResolver {
+        function addr(bytes32 node) external view returns (address);
+    }
+}
+
+/// The ENS registry, at the same address on Mainnet and its testnets.
+const ENS_REGISTRY: Address = address!("0x00000000000C2E074eC69A0dFb2997BA6C7d2e1e");
+
+/// Whether search should treat `query` as an ENS name: dot-separated labels
+/// of letters, digits, `-` and `_`, ending in a label that is not all digits.
+pub fn is_ens_name(query: &str) -> bool {
+    let labels: Vec<&str> = query.split('.').collect();
+    labels.len() >= 2
+        && labels.iter().all(|label| {
+            !label.is_empty()
+                && label
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        })
+        && labels
+            .last()
+            .is_some_and(|tld| !tld.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// EIP-137 namehash of `name`. Names are only lowercased, not fully
+/// UTS-46 normalized, so exotic Unicode names may hash differently from
+/// other clients.
+pub fn namehash(name: &str) -> B256 {
+    let name = name.to_lowercase();
+    name.rsplit('.')
+        .filter(|label| !label.is_empty())
+        .fold(B256::ZERO, |node, label| {
+            let mut buf = [0u8; 64];
+            buf[..32].copy_from_slice(node.as_slice());
+            buf[32..].copy_from_slice(keccak256(label.as_bytes()).as_slice());
+            keccak256(buf)
+        })
+}
+
+/// Resolve `name` to an address through the registry and the name's
+/// resolver. `Ok(None)` when the name has no resolver or no address set.
+pub async fn resolve(rpc: &Rpc, rpc_url: &str, name: &str) -> Result<Option<Address>> {
+    let node = namehash(name);
+    with_failover(rpc, rpc_url, |url| async move {
+        let provider = rpc.connect(&url).await?;
+        rpc.stats().record_api_call("rpc");
+        let resolver = IEnsRegistry::new(ENS_REGISTRY, &provider)
+            .resolver(node)
+            .call()
+            .await
+            .wrap_err("failed to query the ENS registry")?;
+        if resolver.is_zero() {
+            return Ok(None);
+        }
+        rpc.stats().record_api_call("rpc");
+        let resolved = IEnsResolver::new(resolver, &provider)
+            .addr(node)
+            .call()
+            .await
+            .wrap_err("failed to query the name's resolver")?;
+        Ok((!resolved.is_zero()).then_some(resolved))
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::b256;
+
+    #[test]
+    fn hashes_names_and_recognizes_them() {
+        assert_eq!(namehash(""), B256::ZERO);
+        assert_eq!(
+            namehash("eth"),
+            b256!("0x93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae")
+        );
+        assert_eq!(
+            namehash("Foo.ETH"),
+            b256!("0xde9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f")
+        );
+        assert!(is_ens_name("vitalik.eth"));
+        assert!(is_ens_name("sub.my-dao.xyz"));
+        assert!(!is_ens_name("treasury"));
+        assert!(!is_ens_name("1.5"));
+        assert!(!is_ens_name("a..eth"));
+    }
+}
@@ -1,165 +1,328 @@
-use crate::app::AddressRef;
+use super::{
+    explorer::{
+        AddressTransaction, BeaconWithdrawal, ContractCreation, ContractSource, ExplorerBackend,
+        ExplorerSession, InternalTransaction, SourceMatch, TokenTransferRecord,
+        TransactionFetchError, TransactionListSource,
+    },
+    retry,
+};
 use alloy::primitives::U256;
 use serde::Deserialize;
-use std::{fmt, str::FromStr, time::Duration};
+use std::{str::FromStr, time::Duration};
 
 const ETHERSCAN_V2_BASE: &str = "https://api.etherscan.io/v2/api";
 
-#[derive(Debug, Clone)]
-pub struct TransactionListSource {
-    pub label: &'static str,
-    pub api_version: &'static str,
-}
-
-#[derive(Debug, Clone)]
-pub struct AddressTransaction {
-    pub hash: String,
-    pub block_number: u64,
-    pub from: String,
-    pub to: Option<String>,
-    pub value_wei: U256,
-    pub is_error: bool,
-    pub input: Option<String>,
-}
-
-#[derive(Debug)]
-pub enum TransactionFetchError {
-    MissingApiKey,
-    UnsupportedChain(String),
-    Http(reqwest::Error),
-    Parse(serde_json::Error),
-    Api(String),
-}
-
-impl fmt::Display for TransactionFetchError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            TransactionFetchError::MissingApiKey => f.write_str("no Etherscan API key configured"),
-            TransactionFetchError::UnsupportedChain(chain) => {
-                write!(f, "no Etherscan-compatible chain mapping for \"{chain}\"")
-            }
-            TransactionFetchError::Http(err) => write!(f, "network error: {err}"),
-            TransactionFetchError::Parse(err) => write!(f, "response parse error: {err}"),
-            TransactionFetchError::Api(message) => write!(f, "{message}"),
-        }
+/// Etherscan multichain (v2) backend; one API key covers every supported `chainid`.
+pub struct EtherscanBackend {
+    client: ExplorerClient,
+    chain_id: u64,
+    label: &'static str,
+    api_key: String,
+}
+
+impl EtherscanBackend {
+    pub fn new(
+        session: &ExplorerSession,
+        chain_id: u64,
+        label: &'static str,
+        api_key: &str,
+    ) -> Result<Self, TransactionFetchError> {
+        Ok(Self {
+            client: ExplorerClient::new(session)?,
+            chain_id,
+            label,
+            api_key: api_key.to_string(),
+        })
+    }
+
+    async fn query(
+        &self,
+        params: &[(&str, String)],
+    ) -> Result<serde_json::Value, TransactionFetchError> {
+        let mut query = vec![("chainid", self.chain_id.to_string())];
+        query.extend(params.iter().cloned());
+        query.push(("apikey", self.api_key.clone()));
+        query_compatible_api(&self.client, ETHERSCAN_V2_BASE, &query).await
     }
 }
 
-impl std::error::Error for TransactionFetchError {
-    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        match self {
-            TransactionFetchError::Http(err) => Some(err),
-            TransactionFetchError::Parse(err) => Some(err),
-            _ => None,
+impl ExplorerBackend for EtherscanBackend {
+    fn source(&self) -> TransactionListSource {
+        TransactionListSource {
+            label: self.label,
+            api_version: "v2",
         }
     }
-}
 
-impl From<reqwest::Error> for TransactionFetchError {
-    fn from(value: reqwest::Error) -> Self {
-        TransactionFetchError::Http(value)
+    async fn address_transactions(
+        &self,
+        address: &str,
+        limit: usize,
+    ) -> Result<Vec<AddressTransaction>, TransactionFetchError> {
+        let result = self.query(&txlist_params(address, limit)).await?;
+        parse_transactions(result)
+    }
+
+    async fn contract_source(
+        &self,
+        address: &str,
+    ) -> Result<Option<ContractSource>, TransactionFetchError> {
+        let result = self.query(&source_params(address)).await?;
+        parse_contract_source(result, self.label)
+    }
+
+    async fn contract_creation(
+        &self,
+        address: &str,
+    ) -> Result<Option<ContractCreation>, TransactionFetchError> {
+        let result = self.query(&creation_params(address)).await?;
+        parse_contract_creation(result)
+    }
+
+    async fn internal_transactions(
+        &self,
+        address: &str,
+        limit: usize,
+    ) -> Result<Vec<InternalTransaction>, TransactionFetchError> {
+        let result = self
+            .query(&history_params("txlistinternal", address, limit))
+            .await?;
+        parse_internal_transactions(result)
+    }
+
+    async fn token_transfers(
+        &self,
+        address: &str,
+        limit: usize,
+    ) -> Result<Vec<TokenTransferRecord>, TransactionFetchError> {
+        let result = self
+            .query(&history_params("tokentx", address, limit))
+            .await?;
+        parse_token_transfers(result)
     }
-}
 
-impl From<serde_json::Error> for TransactionFetchError {
-    fn from(value: serde_json::Error) -> Self {
-        TransactionFetchError::Parse(value)
+    async fn beacon_withdrawals(
+        &self,
+        address: &str,
+        limit: usize,
+    ) -> Result<Vec<BeaconWithdrawal>, TransactionFetchError> {
+        let result = self
+            .query(&history_params("txsBeaconWithdrawal", address, limit))
+            .await?;
+        parse_beacon_withdrawals(result)
+    }
+
+    async fn transaction_exists(&self, hash: &str) -> Result<bool, TransactionFetchError> {
+        // The proxy module answers in JSON-RPC form rather than the usual envelope.
+        let query = [
+            ("chainid", self.chain_id.to_string()),
+            ("module", "proxy".to_string()),
+            ("action", "eth_getTransactionByHash".to_string()),
+            ("txhash", hash.to_string()),
+            ("apikey", self.api_key.clone()),
+        ];
+        let payload: serde_json::Value = retry::NETWORK
+            .run(|| async {
+                self.client.session.stats.record_api_call("explorer");
+                self.client.session.quota.record_call();
+                Ok::<_, TransactionFetchError>(
+                    self.client
+                        .http
+                        .get(ETHERSCAN_V2_BASE)
+                        .query(&query)
+                        .send()
+                        .await?
+                        .error_for_status()?
+                        .json()
+                        .await?,
+                )
+            })
+            .await?;
+        parse_proxy_transaction(payload)
+            .inspect_err(|err| self.client.session.quota.record_failure(err))
     }
 }
 
-#[derive(Debug, Clone)]
-struct ChainConfig {
+/// Cheap authenticated call used to confirm a key before it is saved.
+pub(super) async fn check_api_key(
+    session: &ExplorerSession,
     chain_id: u64,
-    label: &'static str,
+    api_key: &str,
+) -> Result<(), TransactionFetchError> {
+    let query = [
+        ("chainid", chain_id.to_string()),
+        ("module", "stats".to_string()),
+        ("action", "ethsupply".to_string()),
+        ("apikey", api_key.to_string()),
+    ];
+    query_compatible_api(&ExplorerClient::new(session)?, ETHERSCAN_V2_BASE, &query)
+        .await
+        .map(|_| ())
 }
 
-const ETHEREUM_MAINNET: ChainConfig = ChainConfig {
-    chain_id: 1,
-    label: "Etherscan",
-};
-const ARBITRUM_ONE: ChainConfig = ChainConfig {
-    chain_id: 42161,
-    label: "Arbiscan",
-};
-const BASE_MAINNET: ChainConfig = ChainConfig {
-    chain_id: 8453,
-    label: "Basescan",
-};
-const ETHEREUM_SEPOLIA: ChainConfig = ChainConfig {
-    chain_id: 11155111,
-    label: "Etherscan (Sepolia)",
-};
+/// Latest native token price in USD for `chain_id`.
+pub(super) async fn fetch_native_price(
+    session: &ExplorerSession,
+    chain_id: u64,
+    api_key: &str,
+) -> Result<f64, TransactionFetchError> {
+    let query = [
+        ("chainid", chain_id.to_string()),
+        ("module", "stats".to_string()),
+        ("action", "ethprice".to_string()),
+        ("apikey", api_key.to_string()),
+    ];
+    let result =
+        query_compatible_api(&ExplorerClient::new(session)?, ETHERSCAN_V2_BASE, &query).await?;
+    let price = serde_json::from_value::<RawPrice>(result)?;
+    price
+        .ethusd
+        .parse()
+        .map_err(|_| TransactionFetchError::Api(format!("unexpected price {:?}", price.ethusd)))
+}
+
+/// HTTP client for explorer-style APIs, giving up on a request after
+/// `timeout`.
+pub(super) fn build_client(timeout: Duration) -> Result<reqwest::Client, TransactionFetchError> {
+    Ok(reqwest::Client::builder()
+        .user_agent("evm-tui/0.1.0")
+        .timeout(timeout)
+        .build()?)
+}
 
-fn resolve_chain(chain: &str) -> Option<&'static ChainConfig> {
-    let normalized = chain.trim().to_ascii_lowercase();
-    match normalized.as_str() {
-        "mainnet" | "ethereum" | "ethereum mainnet" => Some(&ETHEREUM_MAINNET),
-        "arbitrum" | "arbitrum one" => Some(&ARBITRUM_ONE),
-        "base" | "base mainnet" => Some(&BASE_MAINNET),
-        "sepolia" | "ethereum sepolia" => Some(&ETHEREUM_SEPOLIA),
-        _ => None,
+/// [`build_client`] with the session its requests report to.
+pub(super) struct ExplorerClient {
+    http: reqwest::Client,
+    session: ExplorerSession,
+}
+
+impl ExplorerClient {
+    pub(super) fn new(session: &ExplorerSession) -> Result<Self, TransactionFetchError> {
+        Ok(Self {
+            http: build_client(session.timeout)?,
+            session: session.clone(),
+        })
     }
 }
 
-pub async fn fetch_address_transactions(
-    address: &AddressRef,
-    api_key: Option<&str>,
+pub(super) fn txlist_params(address: &str, limit: usize) -> Vec<(&'static str, String)> {
+    history_params("txlist", address, limit)
+}
+
+/// Newest-first page of one of the `account` module's per-address lists.
+pub(super) fn history_params(
+    action: &str,
+    address: &str,
     limit: usize,
-) -> Result<(Vec<AddressTransaction>, TransactionListSource), TransactionFetchError> {
-    let api_key = api_key
-        .filter(|value| !value.trim().is_empty())
-        .ok_or(TransactionFetchError::MissingApiKey)?;
-    let chain = resolve_chain(&address.chain)
-        .ok_or_else(|| TransactionFetchError::UnsupportedChain(address.chain.clone()))?;
-
-    let client = reqwest::Client::builder()
-        .user_agent("evm-tui/0.1.0")
-        .timeout(Duration::from_secs(10))
-        .build()?;
+) -> Vec<(&'static str, String)> {
+    vec![
+        ("module", "account".into()),
+        ("action", action.into()),
+        ("address", address.to_string()),
+        ("startblock", "0".into()),
+        ("endblock", "999999999".into()),
+        ("page", "1".into()),
+        ("offset", limit.max(1).to_string()),
+        ("sort", "desc".into()),
+    ]
+}
+
+pub(super) fn source_params(address: &str) -> Vec<(&'static str, String)> {
+    vec![
+        ("module", "contract".into()),
+        ("action", "getsourcecode".into()),
+        ("address", address.to_string()),
+    ]
+}
 
+pub(super) fn creation_params(address: &str) -> Vec<(&'static str, String)> {
+    vec![
+        ("module", "contract".into()),
+        ("action", "getcontractcreation".into()),
+        ("contractaddresses", address.to_string()),
+    ]
+}
+
+/// Issue a request against an Etherscan-style `/api` endpoint and unwrap its
+/// `{status, message, result}` envelope. Blockscout speaks the same dialect.
+/// Network errors and 5xx answers are retried under
+/// [`retry::NETWORK`]. History and contract lookups are answered from
+/// [`explorer_cache`] while a previous answer is fresh.
+pub(super) async fn query_compatible_api(
+    client: &ExplorerClient,
+    base_url: &str,
+    query: &[(&str, String)],
+) -> Result<serde_json::Value, TransactionFetchError> {
+    if let Some(cached) = client
+        .session
+        .cache
+        .lookup(&client.session.stats, base_url, query)
+    {
+        return Ok(cached);
+    }
+    let result = retry::NETWORK
+        .run(|| query_once(client, base_url, query))
+        .await?;
+    client.session.cache.store(base_url, query, &result);
+    Ok(result)
+}
+
+async fn query_once(
+    client: &ExplorerClient,
+    base_url: &str,
+    query: &[(&str, String)],
+) -> Result<serde_json::Value, TransactionFetchError> {
+    client.session.stats.record_api_call("explorer");
+    let counts_against_quota = base_url == ETHERSCAN_V2_BASE;
+    if counts_against_quota {
+        client.session.quota.record_call();
+    }
     let response = client
-        .get(ETHERSCAN_V2_BASE)
-        .query(&[
-            ("chainid", chain.chain_id.to_string()),
-            ("module", "account".into()),
-            ("action", "txlist".into()),
-            ("address", address.address.clone()),
-            ("startblock", "0".into()),
-            ("endblock", "999999999".into()),
-            ("page", "1".into()),
-            ("offset", limit.max(1).to_string()),
-            ("sort", "desc".into()),
-            ("apikey", api_key.to_string()),
-        ])
+        .http
+        .get(base_url)
+        .query(query)
         .send()
         .await?
         .error_for_status()?;
+    if counts_against_quota {
+        client.session.quota.record_headers(response.headers());
+    }
 
     let payload: ApiResponse = response.json().await?;
-
-    let transactions = match payload.status.as_str() {
-        "1" => serde_json::from_value::<Vec<RawTransaction>>(payload.result)?,
+    let result = match payload.status.as_str() {
+        "1" => Ok(payload.result),
         "0" => {
             if payload
                 .message
                 .eq_ignore_ascii_case("No transactions found")
+                || payload.message.eq_ignore_ascii_case("No records found")
             {
-                Vec::new()
+                Ok(serde_json::Value::Array(Vec::new()))
             } else if let serde_json::Value::String(reason) = payload.result {
-                return Err(TransactionFetchError::Api(reason));
+                Err(TransactionFetchError::from_api_message(reason))
             } else if let serde_json::Value::Array(_) = payload.result {
-                serde_json::from_value::<Vec<RawTransaction>>(payload.result)?
+                Ok(payload.result)
             } else {
-                return Err(TransactionFetchError::Api(payload.message));
+                Err(TransactionFetchError::from_api_message(payload.message))
             }
         }
-        _ => {
-            if let serde_json::Value::String(reason) = payload.result {
-                return Err(TransactionFetchError::Api(reason));
+        _ => match payload.result {
+            serde_json::Value::String(reason) => {
+                Err(TransactionFetchError::from_api_message(reason))
             }
-            return Err(TransactionFetchError::Api(payload.message));
-        }
+            _ => Err(TransactionFetchError::from_api_message(payload.message)),
+        },
     };
+    if counts_against_quota && let Err(err) = &result {
+        client.session.quota.record_failure(err);
+    }
+    result
+}
+
+pub(super) fn parse_transactions(
+    result: serde_json::Value,
+) -> Result<Vec<AddressTransaction>, TransactionFetchError> {
+    let transactions = serde_json::from_value::<Vec<RawTransaction>>(result)?;
 
     let parsed = transactions
         .into_iter()
@@ -187,17 +350,133 @@ pub async fn fetch_address_transactions(
                 value_wei,
                 is_error,
                 input,
+                timestamp: raw.time_stamp.and_then(|n| n.parse().ok()),
+                gas_used: raw.gas_used.and_then(|n| n.parse().ok()),
             }
         })
         .collect();
 
-    Ok((
-        parsed,
-        TransactionListSource {
-            label: chain.label,
-            api_version: "v2",
-        },
-    ))
+    Ok(parsed)
+}
+
+pub(super) fn parse_internal_transactions(
+    result: serde_json::Value,
+) -> Result<Vec<InternalTransaction>, TransactionFetchError> {
+    let entries = serde_json::from_value::<Vec<RawInternalTransaction>>(result)?;
+    Ok(entries
+        .into_iter()
+        .map(|raw| InternalTransaction {
+            hash: raw.hash,
+            block_number: raw.block_number.parse().unwrap_or_default(),
+            timestamp: raw.time_stamp.and_then(|n| n.parse().ok()),
+            from: raw.from,
+            to: Some(raw.to).filter(|to| !to.trim().is_empty()),
+            value_wei: U256::from_str(&raw.value).unwrap_or_default(),
+            is_error: matches!(raw.is_error.as_deref(), Some("1")),
+        })
+        .collect())
+}
+
+pub(super) fn parse_token_transfers(
+    result: serde_json::Value,
+) -> Result<Vec<TokenTransferRecord>, TransactionFetchError> {
+    let entries = serde_json::from_value::<Vec<RawTokenTransfer>>(result)?;
+    Ok(entries
+        .into_iter()
+        .map(|raw| TokenTransferRecord {
+            hash: raw.hash,
+            block_number: raw.block_number.parse().unwrap_or_default(),
+            timestamp: raw.time_stamp.and_then(|n| n.parse().ok()),
+            from: raw.from,
+            to: raw.to,
+            token: raw.contract_address,
+            symbol: raw.token_symbol,
+            decimals: raw.token_decimal.parse().ok(),
+            amount: U256::from_str(&raw.value).unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// Etherscan reports withdrawal amounts in gwei.
+pub(super) fn parse_beacon_withdrawals(
+    result: serde_json::Value,
+) -> Result<Vec<BeaconWithdrawal>, TransactionFetchError> {
+    let entries = serde_json::from_value::<Vec<RawBeaconWithdrawal>>(result)?;
+    Ok(entries
+        .into_iter()
+        .map(|raw| BeaconWithdrawal {
+            withdrawal_index: raw.withdrawal_index.parse().unwrap_or_default(),
+            validator_index: raw.validator_index.parse().unwrap_or_default(),
+            block_number: raw.block_number.parse().unwrap_or_default(),
+            timestamp: raw.timestamp.and_then(|n| n.parse().ok()),
+            amount_wei: U256::from_str(&raw.amount).unwrap_or_default()
+                * U256::from(1_000_000_000u64),
+        })
+        .collect())
+}
+
+pub(super) fn parse_contract_source(
+    result: serde_json::Value,
+    source_label: &'static str,
+) -> Result<Option<ContractSource>, TransactionFetchError> {
+    let entries = serde_json::from_value::<Vec<RawSourceCode>>(result)?;
+    let Some(entry) = entries.into_iter().next() else {
+        return Ok(None);
+    };
+    let verified = !entry.contract_name.trim().is_empty()
+        && !entry.abi.trim().is_empty()
+        && !entry.abi.starts_with("Contract source code not verified");
+    if !verified {
+        return Ok(None);
+    }
+    Ok(Some(ContractSource {
+        name: entry.contract_name,
+        compiler: entry.compiler_version,
+        abi: entry.abi,
+        source: entry.source_code,
+        source_label,
+        match_type: SourceMatch::Verified,
+        storage_layout: None,
+    }))
+}
+
+pub(super) fn parse_contract_creation(
+    result: serde_json::Value,
+) -> Result<Option<ContractCreation>, TransactionFetchError> {
+    // Blockscout answers `null` for addresses it has no creation record for.
+    if result.is_null() {
+        return Ok(None);
+    }
+    let entries = serde_json::from_value::<Vec<RawContractCreation>>(result)?;
+    Ok(entries.into_iter().next().map(|entry| ContractCreation {
+        creator: entry.contract_creator,
+        transaction_hash: entry.tx_hash,
+        block_number: entry.block_number.and_then(|n| n.parse().ok()),
+        timestamp: entry.timestamp.and_then(|n| n.parse().ok()),
+    }))
+}
+
+/// `true` when a proxied `eth_getTransactionByHash` returned a transaction.
+/// Key and rate-limit failures arrive as the regular `status: "0"` envelope.
+fn parse_proxy_transaction(payload: serde_json::Value) -> Result<bool, TransactionFetchError> {
+    if let Some(error) = payload.get("error") {
+        let message = error
+            .get("message")
+            .and_then(|message| message.as_str())
+            .map_or_else(|| error.to_string(), str::to_string);
+        return Err(TransactionFetchError::from_api_message(message));
+    }
+    if payload.get("status").and_then(|status| status.as_str()) == Some("0") {
+        let reason = payload
+            .get("result")
+            .and_then(|result| result.as_str())
+            .or_else(|| payload.get("message").and_then(|message| message.as_str()))
+            .unwrap_or("request failed");
+        return Err(TransactionFetchError::from_api_message(reason));
+    }
+    Ok(payload
+        .get("result")
+        .is_some_and(|result| !result.is_null()))
 }
 
 #[derive(Debug, Deserialize)]
@@ -222,4 +501,79 @@ struct RawTransaction {
     txreceipt_status: Option<String>,
     #[serde(default)]
     input: String,
+    #[serde(default)]
+    time_stamp: Option<String>,
+    #[serde(default)]
+    gas_used: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawInternalTransaction {
+    block_number: String,
+    #[serde(default)]
+    time_stamp: Option<String>,
+    hash: String,
+    from: String,
+    #[serde(default)]
+    to: String,
+    value: String,
+    #[serde(default)]
+    is_error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTokenTransfer {
+    block_number: String,
+    #[serde(default)]
+    time_stamp: Option<String>,
+    hash: String,
+    from: String,
+    to: String,
+    value: String,
+    contract_address: String,
+    #[serde(default)]
+    token_symbol: String,
+    #[serde(default)]
+    token_decimal: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawBeaconWithdrawal {
+    withdrawal_index: String,
+    validator_index: String,
+    block_number: String,
+    amount: String,
+    #[serde(default)]
+    timestamp: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawSourceCode {
+    #[serde(rename = "SourceCode", default)]
+    source_code: String,
+    #[serde(rename = "ABI", default)]
+    abi: String,
+    #[serde(rename = "ContractName", default)]
+    contract_name: String,
+    #[serde(rename = "CompilerVersion", default)]
+    compiler_version: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawContractCreation {
+    contract_creator: String,
+    tx_hash: String,
+    #[serde(default)]
+    block_number: Option<String>,
+    #[serde(default)]
+    timestamp: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawPrice {
+    ethusd: String,
 }
@@ -0,0 +1,355 @@
+use super::{anvil::normalize_url, providers::Rpc, upgrade_impact::format_value};
+use alloy::{
+    dyn_abi::{EventExt, Specifier},
+    json_abi::{Event, JsonAbi},
+    primitives::{Address, B256, keccak256},
+    providers::Provider,
+    rpc::types::{Filter, Log},
+};
+use std::collections::HashMap;
+
+/// Events kept per query; the scan stops once this many matched.
+const MAX_EVENTS: usize = 200;
+/// How far back from the head a query looks.
+const LOOKBACK_BLOCKS: u64 = 100_000;
+/// First `eth_getLogs` range; halved whenever a provider rejects a range.
+const INITIAL_CHUNK: u64 = 5_000;
+const MIN_CHUNK: u64 = 16;
+
+/// Parsed form of the Events tab filter: an optional event name followed by
+/// `argument=value` pairs, e.g. `Transfer from=0xabc… to=0xdef…`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventFilter {
+    pub name: Option<String>,
+    pub args: Vec<(String, String)>,
+}
+
+impl EventFilter {
+    pub fn parse(input: &str) -> Self {
+        let mut filter = EventFilter::default();
+        for token in input.split_whitespace() {
+            match token.split_once('=') {
+                Some((key, value)) => filter.args.push((key.to_string(), value.to_string())),
+                None if filter.name.is_none() => filter.name = Some(token.to_string()),
+                None => {}
+            }
+        }
+        filter
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedEvent {
+    pub block_number: Option<u64>,
+    pub transaction_hash: Option<String>,
+    /// Event name, or the raw topic0 when the ABI does not know it.
+    pub name: String,
+    /// Full topic0 of an event the ABI does not know, for signature lookup.
+    pub unknown_topic: Option<String>,
+    pub fields: Vec<(String, String)>,
+}
+
+impl DecodedEvent {
+    pub fn arguments(&self) -> String {
+        self.fields
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Result of one query: matched events (newest first) and the range scanned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventPage {
+    pub address: String,
+    pub filter: String,
+    pub events: Vec<DecodedEvent>,
+    pub from_block: u64,
+    pub to_block: u64,
+}
+
+/// What the Events tab shows for the selected contract.
+#[derive(Debug, Default)]
+pub struct EventsView {
+    /// Address whose query is running or last finished.
+    pub address: Option<String>,
+    pub page: Option<EventPage>,
+    pub loading: bool,
+    pub error: Option<String>,
+    pub filter: String,
+    /// Filter text being typed while the prompt is open.
+    pub editing: Option<String>,
+    pub selected_index: usize,
+}
+
+impl EventsView {
+    pub fn needs_load(&self, address: &str) -> bool {
+        self.address.as_deref() != Some(address)
+    }
+
+    pub fn rows(&self) -> &[DecodedEvent] {
+        self.page
+            .as_ref()
+            .map(|page| page.events.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn selected(&self) -> Option<&DecodedEvent> {
+        self.rows().get(self.selected_index)
+    }
+
+    pub fn move_selection(&mut self, down: bool) {
+        let last = self.rows().len().saturating_sub(1);
+        self.selected_index = if down {
+            (self.selected_index + 1).min(last)
+        } else {
+            self.selected_index.saturating_sub(1)
+        };
+    }
+}
+
+/// An [`EventFilter`] resolved against the ABI.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(super) struct LogQuery {
+    /// Accepted topic0 values (overloads share a name).
+    pub selectors: Vec<B256>,
+    /// Topic position (1-3) and value for indexed arguments.
+    pub indexed: Vec<(usize, B256)>,
+    /// Non-indexed arguments, matched after decoding.
+    pub body: Vec<(String, String)>,
+}
+
+impl LogQuery {
+    /// `eth_getLogs` filter for `address` over `from..=to`.
+    pub fn filter(&self, address: Address, from: u64, to: u64) -> Filter {
+        let mut query = Filter::new().address(address).from_block(from).to_block(to);
+        if !self.selectors.is_empty() {
+            query = query.event_signature(self.selectors.clone());
+        }
+        for (position, topic) in &self.indexed {
+            query = match position {
+                1 => query.topic1(*topic),
+                2 => query.topic2(*topic),
+                _ => query.topic3(*topic),
+            };
+        }
+        query
+    }
+
+    /// Whether a decoded event carries every non-indexed argument value.
+    pub fn body_matches(&self, event: &DecodedEvent) -> bool {
+        self.body.iter().all(|(key, value)| {
+            event.fields.iter().any(|(name, field)| {
+                name.eq_ignore_ascii_case(key) && field.eq_ignore_ascii_case(value)
+            })
+        })
+    }
+}
+
+/// Topic filters for `filter`: topic0 from the event name, topics 1-3 from
+/// indexed argument values.
+pub(super) fn topics_for(abi: Option<&JsonAbi>, filter: &EventFilter) -> Result<LogQuery, String> {
+    let Some(name) = filter.name.as_ref() else {
+        return if filter.args.is_empty() {
+            Ok(LogQuery::default())
+        } else {
+            Err("argument filters need an event name first".into())
+        };
+    };
+    let abi = abi.ok_or("filtering by event name needs a verified ABI")?;
+    let events: Vec<&Event> = abi
+        .events()
+        .filter(|event| event.name.eq_ignore_ascii_case(name))
+        .collect();
+    let Some(event) = events.first() else {
+        return Err(format!("no event named {name} in the ABI"));
+    };
+    let selectors = events.iter().map(|event| event.selector()).collect();
+
+    let mut indexed = Vec::new();
+    let mut body = Vec::new();
+    for (key, value) in &filter.args {
+        let position = event
+            .inputs
+            .iter()
+            .filter(|input| input.indexed)
+            .position(|input| input.name.eq_ignore_ascii_case(key));
+        let Some(position) = position else {
+            if event
+                .inputs
+                .iter()
+                .any(|input| input.name.eq_ignore_ascii_case(key))
+            {
+                body.push((key.clone(), value.clone()));
+                continue;
+            }
+            return Err(format!("{} has no argument named {key}", event.name));
+        };
+        let param = event
+            .inputs
+            .iter()
+            .filter(|input| input.indexed)
+            .nth(position)
+            .expect("position came from the same iterator");
+        let ty = param.resolve().map_err(|err| err.to_string())?;
+        let value = ty
+            .coerce_str(value)
+            .map_err(|err| format!("{key}: {err}"))?;
+        // Dynamic indexed values are stored as the hash of their encoding.
+        let topic = value
+            .as_word()
+            .unwrap_or_else(|| keccak256(value.abi_encode_packed()));
+        indexed.push((position + 1, topic));
+    }
+    Ok(LogQuery {
+        selectors,
+        indexed,
+        body,
+    })
+}
+
+pub(super) fn decode(log: &Log, events: &HashMap<B256, &Event>) -> DecodedEvent {
+    let topics = log.topics();
+    let known = topics.first().and_then(|topic0| events.get(topic0));
+    let (name, fields) = match known {
+        Some(event) => match event.decode_log_parts(topics.iter().copied(), &log.data().data) {
+            Ok(decoded) => {
+                let mut indexed = decoded.indexed.into_iter();
+                let mut body = decoded.body.into_iter();
+                let fields = event
+                    .inputs
+                    .iter()
+                    .filter_map(|input| {
+                        let value = if input.indexed {
+                            indexed.next()
+                        } else {
+                            body.next()
+                        }?;
+                        Some((input.name.clone(), format_value(&value)))
+                    })
+                    .collect();
+                (event.name.clone(), fields)
+            }
+            Err(err) => (event.name.clone(), vec![("error".into(), err.to_string())]),
+        },
+        None => (
+            topics
+                .first()
+                .map(|topic0| format!("{:#x}", topic0)[..10].to_string())
+                .unwrap_or_else(|| "anonymous".into()),
+            vec![("topics".into(), topics.len().to_string())],
+        ),
+    };
+    DecodedEvent {
+        block_number: log.block_number,
+        transaction_hash: log.transaction_hash.map(|hash| format!("{hash:#x}")),
+        name,
+        unknown_topic: topics
+            .first()
+            .filter(|_| known.is_none())
+            .map(|topic0| format!("{topic0:#x}")),
+        fields,
+    }
+}
+
+/// Fetch recent logs of `address` matching `filter_text`, newest first,
+/// walking back from the head in `eth_getLogs` chunks until enough matched or
+/// the lookback is exhausted.
+pub async fn fetch_events(
+    rpc: Rpc,
+    rpc_url: String,
+    address: String,
+    abi: Option<String>,
+    filter_text: String,
+) -> Result<EventPage, String> {
+    let target: Address = address.parse().map_err(|_| "invalid address".to_string())?;
+    let abi: Option<JsonAbi> = abi
+        .map(|abi| {
+            serde_json::from_str(&abi).map_err(|err| format!("ABI is not valid JSON: {err}"))
+        })
+        .transpose()?;
+    let filter = EventFilter::parse(&filter_text);
+    let query = topics_for(abi.as_ref(), &filter)?;
+    let known: HashMap<B256, &Event> = abi
+        .iter()
+        .flat_map(|abi| abi.events())
+        .map(|event| (event.selector(), event))
+        .collect();
+
+    let provider = rpc
+        .connect(&normalize_url(&rpc, &rpc_url))
+        .await
+        .map_err(|err| format!("{err:#}"))?;
+    rpc.stats().record_api_call("rpc");
+    let head = provider
+        .get_block_number()
+        .await
+        .map_err(|err| format!("failed to query latest block number: {err}"))?;
+    let floor = head.saturating_sub(LOOKBACK_BLOCKS);
+
+    let mut events = Vec::new();
+    let mut chunk = INITIAL_CHUNK;
+    let mut to = head;
+    let mut from = to;
+    while events.len() < MAX_EVENTS && to >= floor {
+        from = to.saturating_sub(chunk - 1).max(floor);
+        rpc.stats().record_api_call("rpc");
+        match provider.get_logs(&query.filter(target, from, to)).await {
+            Ok(logs) => {
+                events.extend(
+                    logs.iter()
+                        .rev()
+                        .map(|log| decode(log, &known))
+                        .filter(|event| query.body_matches(event)),
+                );
+                if from == 0 {
+                    break;
+                }
+                to = from - 1;
+            }
+            // Providers cap ranges and result sizes differently; shrink and retry.
+            Err(_) if chunk > MIN_CHUNK => chunk /= 2,
+            Err(err) => return Err(format!("eth_getLogs failed: {err}")),
+        }
+    }
+    events.truncate(MAX_EVENTS);
+    Ok(EventPage {
+        address,
+        filter: filter_text,
+        events,
+        from_block: from,
+        to_block: head,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ERC20_TRANSFER: &str = r#"[{"type":"event","name":"Transfer","anonymous":false,"inputs":[
+        {"name":"from","type":"address","indexed":true},
+        {"name":"to","type":"address","indexed":true},
+        {"name":"value","type":"uint256","indexed":false}]}]"#;
+
+    #[test]
+    fn filter_maps_indexed_arguments_to_topics() {
+        let abi: JsonAbi = serde_json::from_str(ERC20_TRANSFER).unwrap();
+        let filter =
+            EventFilter::parse("transfer to=0x00000000000000000000000000000000000000aa value=5");
+        assert_eq!(filter.name.as_deref(), Some("transfer"));
+
+        let query = topics_for(Some(&abi), &filter).unwrap();
+        assert_eq!(
+            query.selectors,
+            vec![keccak256("Transfer(address,address,uint256)")]
+        );
+        let mut expected = B256::ZERO;
+        expected.0[31] = 0xaa;
+        assert_eq!(query.indexed, vec![(2, expected)]);
+        assert_eq!(query.body, vec![("value".to_string(), "5".to_string())]);
+
+        assert!(topics_for(Some(&abi), &EventFilter::parse("Approval")).is_err());
+        assert!(topics_for(None, &EventFilter::parse("to=0x01")).is_err());
+    }
+}
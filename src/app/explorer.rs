@@ -0,0 +1,646 @@
+use super::{
+    anvil::fetch_transaction_inclusion,
+    blockscout::BlockscoutBackend,
+    chains::{ExplorerKind, resolve_chain},
+    demo::DemoExplorer,
+    etherscan::{EtherscanBackend, fetch_native_price},
+    explorer_cache::ExplorerCache,
+    fetch_limits::FetchLimits,
+    otterscan::OtterscanBackend,
+    providers::Rpc,
+    quota::{self, Quota},
+    sourcify,
+    stats::ApiStats,
+    storage_layout::StorageLayout,
+};
+use crate::app::AddressRef;
+use alloy::primitives::U256;
+use serde::Serialize;
+use std::{fmt, time::Duration};
+
+#[derive(Debug, Clone)]
+pub struct TransactionListSource {
+    pub label: &'static str,
+    pub api_version: &'static str,
+}
+
+#[derive(Debug, Clone)]
+pub struct AddressTransaction {
+    pub hash: String,
+    pub block_number: u64,
+    pub from: String,
+    pub to: Option<String>,
+    pub value_wei: U256,
+    pub is_error: bool,
+    pub input: Option<String>,
+    /// Unix seconds of the including block, when the source reports it.
+    pub timestamp: Option<u64>,
+    /// Gas the receipt reports, when the source includes it.
+    pub gas_used: Option<u64>,
+}
+
+/// ETH moved by a contract call inside a transaction (`txlistinternal`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InternalTransaction {
+    pub hash: String,
+    pub block_number: u64,
+    pub timestamp: Option<u64>,
+    pub from: String,
+    /// Empty for contract creations.
+    pub to: Option<String>,
+    pub value_wei: U256,
+    pub is_error: bool,
+}
+
+/// An ERC-20 `Transfer` involving the address (`tokentx`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenTransferRecord {
+    pub hash: String,
+    pub block_number: u64,
+    pub timestamp: Option<u64>,
+    pub from: String,
+    pub to: String,
+    pub token: String,
+    pub symbol: String,
+    pub decimals: Option<u8>,
+    pub amount: U256,
+}
+
+/// A consensus-layer withdrawal credited to the address in a block body
+/// (EIP-4895); it moves ETH without any transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BeaconWithdrawal {
+    pub withdrawal_index: u64,
+    pub validator_index: u64,
+    pub block_number: u64,
+    pub timestamp: Option<u64>,
+    pub amount_wei: U256,
+}
+
+/// Verified contract metadata returned by an explorer's `getsourcecode` endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ContractSource {
+    pub name: String,
+    pub compiler: String,
+    pub abi: String,
+    pub source: String,
+    pub source_label: &'static str,
+    pub match_type: SourceMatch,
+    /// Slot layout of the state variables, when the source reports one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_layout: Option<StorageLayout>,
+}
+
+/// Who deployed a contract and in which transaction. Block and timestamp are
+/// filled from the RPC when the explorer does not report them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ContractCreation {
+    pub creator: String,
+    pub transaction_hash: String,
+    pub block_number: Option<u64>,
+    pub timestamp: Option<u64>,
+}
+
+/// How strongly the published source is tied to the deployed bytecode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SourceMatch {
+    /// Verified by an explorer (Etherscan/Blockscout), which does not report match depth.
+    Verified,
+    /// Sourcify exact match: bytecode and metadata hash both match.
+    FullMatch,
+    /// Sourcify partial match: bytecode matches but metadata (comments, paths) differs.
+    PartialMatch,
+    /// Deployed bytecode matches an artifact of the configured Foundry project.
+    LocalArtifact,
+}
+
+impl SourceMatch {
+    pub fn label(self) -> &'static str {
+        match self {
+            SourceMatch::Verified => "verified",
+            SourceMatch::FullMatch => "full match",
+            SourceMatch::PartialMatch => "partial match",
+            SourceMatch::LocalArtifact => "local artifact",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum TransactionFetchError {
+    MissingApiKey,
+    UnsupportedChain(String),
+    Http(reqwest::Error),
+    Parse(serde_json::Error),
+    /// The explorer rejected the API key.
+    InvalidApiKey(String),
+    /// The per-second limit, or with `daily` the daily quota, was reached.
+    RateLimited {
+        daily: bool,
+        message: String,
+    },
+    /// The endpoint was retired, like Etherscan's V1 API.
+    DeprecatedEndpoint(String),
+    /// The endpoint or the chain needs a paid API plan.
+    ProOnly(String),
+    Api(String),
+}
+
+impl TransactionFetchError {
+    /// Sort an explorer's error message into the failures it reports in its
+    /// own words; anything else stays [`TransactionFetchError::Api`].
+    pub fn from_api_message(message: impl Into<String>) -> Self {
+        let message = message.into();
+        let text = message.to_ascii_lowercase();
+        let mentions = |needles: &[&str]| needles.iter().any(|needle| text.contains(needle));
+        if mentions(&["invalid api key", "missing/invalid"]) {
+            TransactionFetchError::InvalidApiKey(message)
+        } else if quota::is_rate_limit_message(&message) {
+            TransactionFetchError::RateLimited {
+                daily: text.contains("daily"),
+                message,
+            }
+        } else if mentions(&["deprecated"]) {
+            TransactionFetchError::DeprecatedEndpoint(message)
+        } else if mentions(&["api pro", "pro endpoint", "upgrade your api plan"]) {
+            TransactionFetchError::ProOnly(message)
+        } else {
+            TransactionFetchError::Api(message)
+        }
+    }
+
+    /// What to do about an error the explorer reported.
+    pub fn guidance(&self) -> Option<&'static str> {
+        match self {
+            TransactionFetchError::MissingApiKey => {
+                Some("Add an Etherscan API key under Settings → Secrets.")
+            }
+            TransactionFetchError::InvalidApiKey(_) => Some(
+                "The explorer rejected the API key: copy it again from etherscan.io/myapikey into Settings → Secrets.",
+            ),
+            TransactionFetchError::RateLimited { daily: false, .. } => Some(
+                "Over the per-second limit: wait a moment, slow auto-refresh (Shift-R) or raise `:cache` TTLs.",
+            ),
+            TransactionFetchError::RateLimited { daily: true, .. } => Some(
+                "The daily quota is used up until it resets: raise `:cache` TTLs or move to a paid plan.",
+            ),
+            TransactionFetchError::DeprecatedEndpoint(_) => Some(
+                "The explorer retired this endpoint: update evm-tui to a release on its current API.",
+            ),
+            TransactionFetchError::ProOnly(_) => Some(
+                "Needs a paid API plan for this endpoint or chain; Blockscout chains and Otterscan nodes work without one.",
+            ),
+            TransactionFetchError::UnsupportedChain(_)
+            | TransactionFetchError::Http(_)
+            | TransactionFetchError::Parse(_)
+            | TransactionFetchError::Api(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for TransactionFetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransactionFetchError::MissingApiKey => f.write_str("no Etherscan API key configured"),
+            TransactionFetchError::UnsupportedChain(chain) => {
+                write!(f, "no explorer mapping for \"{chain}\"")
+            }
+            TransactionFetchError::Http(err) => write!(f, "network error: {err}"),
+            TransactionFetchError::Parse(err) => write!(f, "response parse error: {err}"),
+            TransactionFetchError::InvalidApiKey(message) => {
+                write!(f, "API key rejected: {message}")
+            }
+            TransactionFetchError::RateLimited { message, .. } => {
+                write!(f, "rate limited: {message}")
+            }
+            TransactionFetchError::DeprecatedEndpoint(message) => {
+                write!(f, "endpoint deprecated: {message}")
+            }
+            TransactionFetchError::ProOnly(message) => {
+                write!(f, "paid API plan required: {message}")
+            }
+            TransactionFetchError::Api(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for TransactionFetchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TransactionFetchError::Http(err) => Some(err),
+            TransactionFetchError::Parse(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for TransactionFetchError {
+    fn from(value: reqwest::Error) -> Self {
+        TransactionFetchError::Http(value)
+    }
+}
+
+impl From<serde_json::Error> for TransactionFetchError {
+    fn from(value: serde_json::Error) -> Self {
+        TransactionFetchError::Parse(value)
+    }
+}
+
+/// Data source for address history and verified contract metadata.
+pub trait ExplorerBackend {
+    fn source(&self) -> TransactionListSource;
+
+    /// Fetch up to `limit` transactions touching `address`, newest first.
+    async fn address_transactions(
+        &self,
+        address: &str,
+        limit: usize,
+    ) -> Result<Vec<AddressTransaction>, TransactionFetchError>;
+
+    /// Fetch verified source and ABI; `Ok(None)` when the contract is not verified.
+    async fn contract_source(
+        &self,
+        address: &str,
+    ) -> Result<Option<ContractSource>, TransactionFetchError>;
+
+    /// Fetch the deployer and creation transaction; `Ok(None)` when unknown
+    /// (e.g. genesis allocations or not a contract).
+    async fn contract_creation(
+        &self,
+        address: &str,
+    ) -> Result<Option<ContractCreation>, TransactionFetchError>;
+
+    /// Fetch up to `limit` internal ETH transfers touching `address`, newest first.
+    async fn internal_transactions(
+        &self,
+        _address: &str,
+        _limit: usize,
+    ) -> Result<Vec<InternalTransaction>, TransactionFetchError> {
+        Err(TransactionFetchError::Api(format!(
+            "{} does not list internal transactions",
+            self.source().label
+        )))
+    }
+
+    /// Fetch up to `limit` ERC-20 transfers touching `address`, newest first.
+    async fn token_transfers(
+        &self,
+        _address: &str,
+        _limit: usize,
+    ) -> Result<Vec<TokenTransferRecord>, TransactionFetchError> {
+        Err(TransactionFetchError::Api(format!(
+            "{} does not list token transfers",
+            self.source().label
+        )))
+    }
+
+    /// Fetch up to `limit` beacon withdrawals credited to `address`, newest first.
+    async fn beacon_withdrawals(
+        &self,
+        _address: &str,
+        _limit: usize,
+    ) -> Result<Vec<BeaconWithdrawal>, TransactionFetchError> {
+        Err(TransactionFetchError::Api(format!(
+            "{} does not list beacon withdrawals",
+            self.source().label
+        )))
+    }
+
+    /// Whether the explorer knows a transaction with this hash.
+    async fn transaction_exists(&self, _hash: &str) -> Result<bool, TransactionFetchError> {
+        Err(TransactionFetchError::Api(format!(
+            "{} cannot look up transactions by hash",
+            self.source().label
+        )))
+    }
+}
+
+/// Session state every explorer request reports to, shared by all clones,
+/// and how long each request may take.
+#[derive(Debug, Clone)]
+pub struct ExplorerSession {
+    pub stats: ApiStats,
+    pub quota: Quota,
+    pub cache: ExplorerCache,
+    pub timeout: Duration,
+}
+
+impl Default for ExplorerSession {
+    fn default() -> Self {
+        Self {
+            stats: ApiStats::default(),
+            quota: Quota::default(),
+            cache: ExplorerCache::default(),
+            timeout: FetchLimits::default().explorer_timeout(),
+        }
+    }
+}
+
+impl ExplorerSession {
+    /// This session, fetching address history past the response cache.
+    pub fn fresh(&self) -> Self {
+        Self {
+            cache: self.cache.fresh(),
+            ..self.clone()
+        }
+    }
+}
+
+/// Explorer backend selected for a chain from the chain registry.
+pub enum Explorer {
+    Etherscan(EtherscanBackend),
+    Blockscout(BlockscoutBackend),
+    Otterscan(OtterscanBackend),
+    Demo(DemoExplorer),
+}
+
+impl Explorer {
+    pub fn for_chain(
+        session: &ExplorerSession,
+        chain: &str,
+        api_key: Option<&str>,
+    ) -> Result<Self, TransactionFetchError> {
+        let info = resolve_chain(chain)
+            .ok_or_else(|| TransactionFetchError::UnsupportedChain(chain.to_string()))?;
+        match info.explorer {
+            ExplorerKind::EtherscanV2 { label } => {
+                let api_key = api_key
+                    .filter(|value| !value.trim().is_empty())
+                    .ok_or(TransactionFetchError::MissingApiKey)?;
+                Ok(Explorer::Etherscan(EtherscanBackend::new(
+                    session,
+                    info.chain_id,
+                    label,
+                    api_key,
+                )?))
+            }
+            ExplorerKind::Blockscout { label, api_url } => Ok(Explorer::Blockscout(
+                BlockscoutBackend::new(session, api_url, label)?,
+            )),
+        }
+    }
+
+    /// Prefer the node's `ots_` namespace for address history when the RPC
+    /// advertises it, falling back to the chain's explorer otherwise.
+    pub async fn for_history(
+        rpc: &Rpc,
+        session: &ExplorerSession,
+        chain: &str,
+        api_key: Option<&str>,
+        rpc_url: Option<&str>,
+    ) -> Result<Self, TransactionFetchError> {
+        if let Some(url) = rpc_url
+            && OtterscanBackend::is_supported(rpc, url).await
+        {
+            return Ok(Explorer::Otterscan(OtterscanBackend::new(rpc.clone(), url)));
+        }
+        Self::for_chain(session, chain, api_key)
+    }
+}
+
+impl ExplorerBackend for Explorer {
+    fn source(&self) -> TransactionListSource {
+        match self {
+            Explorer::Etherscan(backend) => backend.source(),
+            Explorer::Blockscout(backend) => backend.source(),
+            Explorer::Otterscan(backend) => backend.source(),
+            Explorer::Demo(backend) => backend.source(),
+        }
+    }
+
+    async fn address_transactions(
+        &self,
+        address: &str,
+        limit: usize,
+    ) -> Result<Vec<AddressTransaction>, TransactionFetchError> {
+        match self {
+            Explorer::Etherscan(backend) => backend.address_transactions(address, limit).await,
+            Explorer::Blockscout(backend) => backend.address_transactions(address, limit).await,
+            Explorer::Otterscan(backend) => backend.address_transactions(address, limit).await,
+            Explorer::Demo(backend) => backend.address_transactions(address, limit).await,
+        }
+    }
+
+    async fn contract_source(
+        &self,
+        address: &str,
+    ) -> Result<Option<ContractSource>, TransactionFetchError> {
+        match self {
+            Explorer::Etherscan(backend) => backend.contract_source(address).await,
+            Explorer::Blockscout(backend) => backend.contract_source(address).await,
+            Explorer::Otterscan(backend) => backend.contract_source(address).await,
+            Explorer::Demo(backend) => backend.contract_source(address).await,
+        }
+    }
+
+    async fn contract_creation(
+        &self,
+        address: &str,
+    ) -> Result<Option<ContractCreation>, TransactionFetchError> {
+        match self {
+            Explorer::Etherscan(backend) => backend.contract_creation(address).await,
+            Explorer::Blockscout(backend) => backend.contract_creation(address).await,
+            Explorer::Otterscan(backend) => backend.contract_creation(address).await,
+            Explorer::Demo(backend) => backend.contract_creation(address).await,
+        }
+    }
+
+    async fn internal_transactions(
+        &self,
+        address: &str,
+        limit: usize,
+    ) -> Result<Vec<InternalTransaction>, TransactionFetchError> {
+        match self {
+            Explorer::Etherscan(backend) => backend.internal_transactions(address, limit).await,
+            Explorer::Blockscout(backend) => backend.internal_transactions(address, limit).await,
+            Explorer::Otterscan(backend) => backend.internal_transactions(address, limit).await,
+            Explorer::Demo(backend) => backend.internal_transactions(address, limit).await,
+        }
+    }
+
+    async fn token_transfers(
+        &self,
+        address: &str,
+        limit: usize,
+    ) -> Result<Vec<TokenTransferRecord>, TransactionFetchError> {
+        match self {
+            Explorer::Etherscan(backend) => backend.token_transfers(address, limit).await,
+            Explorer::Blockscout(backend) => backend.token_transfers(address, limit).await,
+            Explorer::Otterscan(backend) => backend.token_transfers(address, limit).await,
+            Explorer::Demo(backend) => backend.token_transfers(address, limit).await,
+        }
+    }
+
+    async fn beacon_withdrawals(
+        &self,
+        address: &str,
+        limit: usize,
+    ) -> Result<Vec<BeaconWithdrawal>, TransactionFetchError> {
+        match self {
+            Explorer::Etherscan(backend) => backend.beacon_withdrawals(address, limit).await,
+            Explorer::Blockscout(backend) => backend.beacon_withdrawals(address, limit).await,
+            Explorer::Otterscan(backend) => backend.beacon_withdrawals(address, limit).await,
+            Explorer::Demo(backend) => backend.beacon_withdrawals(address, limit).await,
+        }
+    }
+
+    async fn transaction_exists(&self, hash: &str) -> Result<bool, TransactionFetchError> {
+        match self {
+            Explorer::Etherscan(backend) => backend.transaction_exists(hash).await,
+            Explorer::Blockscout(backend) => backend.transaction_exists(hash).await,
+            Explorer::Otterscan(backend) => backend.transaction_exists(hash).await,
+            Explorer::Demo(backend) => backend.transaction_exists(hash).await,
+        }
+    }
+}
+
+pub async fn fetch_address_transactions(
+    rpc: &Rpc,
+    session: &ExplorerSession,
+    address: &AddressRef,
+    api_key: Option<&str>,
+    rpc_url: Option<&str>,
+    limit: usize,
+) -> Result<(Vec<AddressTransaction>, TransactionListSource), TransactionFetchError> {
+    let explorer = Explorer::for_history(rpc, session, &address.chain, api_key, rpc_url).await?;
+    let transactions = explorer
+        .address_transactions(&address.address, limit)
+        .await?;
+    Ok((transactions, explorer.source()))
+}
+
+/// Fetch verified source from the chain explorer, falling back to Sourcify when
+/// the explorer has no verification (or cannot be queried).
+pub async fn fetch_contract_source(
+    session: &ExplorerSession,
+    address: &AddressRef,
+    api_key: Option<&str>,
+) -> Result<Option<ContractSource>, TransactionFetchError> {
+    let explorer_result = match Explorer::for_chain(session, &address.chain, api_key) {
+        Ok(explorer) => explorer.contract_source(&address.address).await,
+        Err(err) => Err(err),
+    };
+    if let Ok(Some(source)) = explorer_result {
+        return Ok(Some(source));
+    }
+    let Some(chain) = resolve_chain(&address.chain) else {
+        return explorer_result;
+    };
+    match sourcify::fetch_verified_source(
+        &session.stats,
+        session.timeout,
+        chain.chain_id,
+        &address.address,
+    )
+    .await
+    {
+        Ok(Some(source)) => Ok(Some(source)),
+        Ok(None) | Err(_) => explorer_result,
+    }
+}
+
+/// Resolve who deployed `address`, preferring the node's `ots_` namespace
+/// like address history does. Missing block or timestamp details are looked
+/// up from the creation transaction over RPC.
+pub async fn fetch_contract_creation(
+    rpc: &Rpc,
+    session: &ExplorerSession,
+    address: &AddressRef,
+    api_key: Option<&str>,
+    rpc_url: Option<&str>,
+) -> Result<Option<ContractCreation>, TransactionFetchError> {
+    let explorer = Explorer::for_history(rpc, session, &address.chain, api_key, rpc_url).await?;
+    let Some(mut creation) = explorer.contract_creation(&address.address).await? else {
+        return Ok(None);
+    };
+    if let Some(url) = rpc_url
+        && (creation.block_number.is_none() || creation.timestamp.is_none())
+        && let Ok(Some((block, timestamp))) =
+            fetch_transaction_inclusion(rpc, url, &creation.transaction_hash).await
+    {
+        creation.block_number.get_or_insert(block);
+        creation.timestamp.get_or_insert(timestamp);
+    }
+    Ok(Some(creation))
+}
+
+/// Beacon withdrawals credited to `address`, from the chain explorer's index
+/// of block bodies; `Ok(None)` off Ethereum's own networks.
+pub async fn fetch_beacon_withdrawals(
+    session: &ExplorerSession,
+    address: &AddressRef,
+    api_key: Option<&str>,
+    limit: usize,
+) -> Result<Option<Vec<BeaconWithdrawal>>, TransactionFetchError> {
+    let info = resolve_chain(&address.chain)
+        .ok_or_else(|| TransactionFetchError::UnsupportedChain(address.chain.clone()))?;
+    if !info.has_beacon_withdrawals() {
+        return Ok(None);
+    }
+    let explorer = Explorer::for_chain(session, &address.chain, api_key)?;
+    explorer
+        .beacon_withdrawals(&address.address, limit)
+        .await
+        .map(Some)
+}
+
+/// Native token price in USD; `Ok(None)` on chains whose explorer has no
+/// price feed (Blockscout) or without an API key.
+pub async fn fetch_native_usd_price(
+    session: &ExplorerSession,
+    chain: &str,
+    api_key: Option<&str>,
+) -> Result<Option<f64>, TransactionFetchError> {
+    let info = resolve_chain(chain)
+        .ok_or_else(|| TransactionFetchError::UnsupportedChain(chain.to_string()))?;
+    match (info.explorer, api_key.filter(|key| !key.trim().is_empty())) {
+        (ExplorerKind::EtherscanV2 { .. }, Some(api_key)) => {
+            fetch_native_price(session, info.chain_id, api_key)
+                .await
+                .map(Some)
+        }
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_etherscan_error_messages() {
+        let classify = TransactionFetchError::from_api_message;
+        assert!(matches!(
+            classify("Invalid API Key (#err2)|MYKEY"),
+            TransactionFetchError::InvalidApiKey(_)
+        ));
+        assert!(matches!(
+            classify("Missing/Invalid API Key"),
+            TransactionFetchError::InvalidApiKey(_)
+        ));
+        assert!(matches!(
+            classify("Max calls per sec rate limit reached (3/sec)"),
+            TransactionFetchError::RateLimited { daily: false, .. }
+        ));
+        assert!(matches!(
+            classify("Max daily rate limit reached. 100000 calls per day"),
+            TransactionFetchError::RateLimited { daily: true, .. }
+        ));
+        assert!(matches!(
+            classify("You are using a deprecated V1 endpoint, switch to Etherscan API V2"),
+            TransactionFetchError::DeprecatedEndpoint(_)
+        ));
+        let pro_only = classify(
+            "Free API access is not supported for this chain. Please upgrade your api plan for full chain coverage.",
+        );
+        assert!(matches!(pro_only, TransactionFetchError::ProOnly(_)));
+        assert!(pro_only.guidance().unwrap().contains("paid API plan"));
+        let other = classify("Query Timeout occured. Please select a smaller result dataset");
+        assert!(matches!(other, TransactionFetchError::Api(_)));
+        assert!(other.guidance().is_none());
+        assert!(
+            other
+                .to_string()
+                .contains("Please select a smaller result dataset")
+        );
+    }
+}
@@ -0,0 +1,271 @@
+use super::{stats::ApiStats, watchlist::unix_now};
+use crate::storage::{CachedResponse, RESPONSE_CACHE_RETENTION, ResponseCacheRepository};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    fmt::{self, Write as _},
+    sync::{Arc, Mutex},
+};
+
+/// How long cached explorer answers stay fresh, set with `:cache` and stored
+/// under the `explorer.cache_ttls` setting. Zero stops caching that kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheTtls {
+    /// Seconds for address history pages: transactions, internal
+    /// transactions, token transfers and beacon withdrawals.
+    pub history_secs: u64,
+    /// Seconds for contract source, ABI and creation lookups.
+    pub contract_secs: u64,
+}
+
+impl Default for CacheTtls {
+    fn default() -> Self {
+        Self {
+            history_secs: 60,
+            contract_secs: 24 * 60 * 60,
+        }
+    }
+}
+
+/// Parse `90`, `90s`, `15m`, `6h` or `2d` into seconds.
+fn parse_secs(value: &str) -> Option<u64> {
+    let (number, unit) = match value.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+        Some((index, _)) => value.split_at(index),
+        None => (value, "s"),
+    };
+    let scale = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return None,
+    };
+    number.parse::<u64>().ok()?.checked_mul(scale)
+}
+
+fn format_secs(secs: u64) -> String {
+    match secs {
+        0 => "off".into(),
+        secs if secs % 86_400 == 0 => format!("{}d", secs / 86_400),
+        secs if secs % 3_600 == 0 => format!("{}h", secs / 3_600),
+        secs if secs % 60 == 0 => format!("{}m", secs / 60),
+        secs => format!("{secs}s"),
+    }
+}
+
+impl CacheTtls {
+    /// Apply one `name=duration` argument: `history` or `contract`.
+    pub fn apply(&mut self, arg: &str) -> Result<(), String> {
+        let (name, value) = arg
+            .split_once('=')
+            .ok_or_else(|| format!("expected name=duration, got {arg}"))?;
+        let secs = parse_secs(value)
+            .ok_or_else(|| format!("{name} needs a duration like 90s, 15m or 1d, got {value}"))?;
+        let max = RESPONSE_CACHE_RETENTION.as_secs();
+        if secs > max {
+            return Err(format!("{name} must be at most {}", format_secs(max)));
+        }
+        match name {
+            "history" => self.history_secs = secs,
+            "contract" => self.contract_secs = secs,
+            _ => return Err(format!("unknown TTL {name} (history, contract)")),
+        }
+        Ok(())
+    }
+
+    pub fn label(&self) -> String {
+        format!(
+            "history {} • contract {}",
+            format_secs(self.history_secs),
+            format_secs(self.contract_secs)
+        )
+    }
+
+    /// Freshness for a request, `None` for ones never cached (prices, key
+    /// checks, proxy calls).
+    fn ttl_for(&self, query: &[(&str, String)]) -> Option<u64> {
+        let secs = match action(query)? {
+            action if HISTORY_ACTIONS.contains(&action) => self.history_secs,
+            "getsourcecode" | "getabi" | "getcontractcreation" => self.contract_secs,
+            _ => return None,
+        };
+        (secs > 0).then_some(secs)
+    }
+}
+
+const HISTORY_ACTIONS: [&str; 4] = ["txlist", "txlistinternal", "tokentx", "txsBeaconWithdrawal"];
+
+fn action<'a>(query: &'a [(&str, String)]) -> Option<&'a str> {
+    query
+        .iter()
+        .find(|(name, _)| *name == "action")
+        .map(|(_, action)| action.as_str())
+}
+
+/// Where explorer answers are kept and how long they stay fresh, shared by
+/// every clone. Caches nothing until [`ExplorerCache::install`].
+#[derive(Clone, Default)]
+pub struct ExplorerCache {
+    store: Arc<Mutex<Option<(ResponseCacheRepository, CacheTtls)>>>,
+    /// Answer address history from the network, for refreshes, retries and
+    /// polls that exist to see new activity.
+    skip_history: bool,
+}
+
+impl fmt::Debug for ExplorerCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExplorerCache")
+            .field("ttls", &self.ttls())
+            .field("skip_history", &self.skip_history)
+            .finish()
+    }
+}
+
+impl ExplorerCache {
+    /// Serve and keep explorer answers in `responses` from now on.
+    pub fn install(&self, responses: ResponseCacheRepository, ttls: CacheTtls) {
+        if let Ok(mut store) = self.store.lock() {
+            *store = Some((responses, ttls));
+        }
+    }
+
+    pub fn set_ttls(&self, ttls: CacheTtls) {
+        if let Ok(mut store) = self.store.lock()
+            && let Some((_, current)) = store.as_mut()
+        {
+            *current = ttls;
+        }
+    }
+
+    pub fn ttls(&self) -> CacheTtls {
+        self.store
+            .lock()
+            .ok()
+            .and_then(|store| store.as_ref().map(|(_, ttls)| *ttls))
+            .unwrap_or_default()
+    }
+
+    /// The same cache, fetching address history past it. What comes back is
+    /// still stored for later lookups.
+    pub fn fresh(&self) -> Self {
+        Self {
+            store: self.store.clone(),
+            skip_history: true,
+        }
+    }
+
+    /// Cached repository and freshness for a request worth caching.
+    fn entry_for(&self, query: &[(&str, String)]) -> Option<(ResponseCacheRepository, u64)> {
+        let store = self.store.lock().ok()?;
+        let (responses, ttls) = store.as_ref()?;
+        Some((responses.clone(), ttls.ttl_for(query)?))
+    }
+
+    /// A fresh cached answer to this request, if any.
+    pub fn lookup(
+        &self,
+        stats: &ApiStats,
+        base_url: &str,
+        query: &[(&str, String)],
+    ) -> Option<Value> {
+        if self.skip_history
+            && action(query).is_some_and(|action| HISTORY_ACTIONS.contains(&action))
+        {
+            return None;
+        }
+        let (responses, ttl) = self.entry_for(query)?;
+        let cached = responses
+            .get(&request_key(base_url, query))
+            .ok()
+            .flatten()
+            .filter(|cached| unix_now().saturating_sub(cached.fetched_at) < ttl);
+        stats.record_cache_lookup("explorer response", cached.is_some());
+        cached.map(|cached| cached.result)
+    }
+
+    /// Keep a successful answer for later lookups. Failing to save only costs
+    /// a refetch, so errors are dropped.
+    pub fn store(&self, base_url: &str, query: &[(&str, String)], result: &Value) {
+        let Some((responses, _)) = self.entry_for(query) else {
+            return;
+        };
+        let response = CachedResponse {
+            fetched_at: unix_now(),
+            result: result.clone(),
+        };
+        let _ = responses.put(&request_key(base_url, query), &response);
+    }
+}
+
+/// The request a response is cached under: the endpoint and its parameters
+/// in order, without `apikey` so keys never reach the store and a new key
+/// keeps the cache.
+fn request_key(base_url: &str, query: &[(&str, String)]) -> String {
+    let mut key = base_url.to_string();
+    for (index, (name, value)) in query
+        .iter()
+        .filter(|(name, _)| *name != "apikey")
+        .enumerate()
+    {
+        let separator = if index == 0 { '?' } else { '&' };
+        let _ = write!(key, "{separator}{name}={}", value.to_ascii_lowercase());
+    }
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_and_ttls_follow_the_request() {
+        let query = [
+            ("chainid", "1".to_string()),
+            ("module", "account".to_string()),
+            ("action", "txlist".to_string()),
+            ("address", "0xAbC".to_string()),
+            ("apikey", "SECRET".to_string()),
+        ];
+        assert_eq!(
+            request_key("https://api.etherscan.io/v2/api", &query),
+            "https://api.etherscan.io/v2/api?chainid=1&module=account&action=txlist&address=0xabc"
+        );
+
+        let mut ttls = CacheTtls::default();
+        assert_eq!(ttls.ttl_for(&query), Some(60));
+        let source = [("action", "getsourcecode".to_string())];
+        assert_eq!(ttls.ttl_for(&source), Some(86_400));
+        assert_eq!(ttls.ttl_for(&[("action", "ethprice".to_string())]), None);
+
+        ttls.apply("history=0").unwrap();
+        ttls.apply("contract=6h").unwrap();
+        assert_eq!(ttls.ttl_for(&query), None);
+        assert_eq!(ttls.label(), "history off • contract 6h");
+        assert!(ttls.apply("contract=30d").is_err());
+        assert!(ttls.apply("history=soon").is_err());
+        assert!(ttls.apply("prices=1m").is_err());
+    }
+
+    #[test]
+    fn fresh_handles_fetch_history_past_the_cache() {
+        let temp = tempfile::tempdir().unwrap();
+        let storage = crate::storage::Storage::open(temp.path()).unwrap();
+        let cache = ExplorerCache::default();
+        cache.install(storage.responses().clone(), CacheTtls::default());
+        let stats = ApiStats::default();
+        let base = "https://api.etherscan.io/v2/api";
+        let history = [("action", "txlist".to_string())];
+        let source = [("action", "getsourcecode".to_string())];
+        cache.store(base, &history, &Value::from(1));
+        cache.store(base, &source, &Value::from(2));
+
+        assert_eq!(cache.lookup(&stats, base, &history), Some(Value::from(1)));
+        let fresh = cache.fresh();
+        assert_eq!(fresh.lookup(&stats, base, &history), None);
+        assert_eq!(fresh.lookup(&stats, base, &source), Some(Value::from(2)));
+
+        fresh.store(base, &history, &Value::from(3));
+        assert_eq!(cache.lookup(&stats, base, &history), Some(Value::from(3)));
+    }
+}
@@ -0,0 +1,414 @@
+use super::{HydratedAddress, MainViewTab, explorer::TransactionFetchError, retry::TimedOut};
+use alloy::transports::{HttpError, RpcError, TransportErrorKind};
+use color_eyre::Report;
+use serde::Serialize;
+use std::error::Error;
+
+/// Independently fetched parts of an address view. One failing leaves the
+/// others populated, with a banner on the tab the section belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum AddressSection {
+    /// Balance, nonce and code from the chain's RPC.
+    Account,
+    /// Recent transactions from the explorer or an RPC scan.
+    Transactions,
+    /// Verified source from the explorer or Sourcify.
+    Source,
+    /// Deployer and creation transaction.
+    Creation,
+    /// Beacon withdrawals credited to the address.
+    Withdrawals,
+}
+
+impl AddressSection {
+    pub fn label(self) -> &'static str {
+        match self {
+            AddressSection::Account => "Account data",
+            AddressSection::Transactions => "Transactions",
+            AddressSection::Source => "Contract source",
+            AddressSection::Creation => "Deployment info",
+            AddressSection::Withdrawals => "Beacon withdrawals",
+        }
+    }
+
+    /// Where the section is fetched from, for error reports.
+    pub fn source(self) -> &'static str {
+        match self {
+            AddressSection::Account => "RPC",
+            AddressSection::Transactions => "Explorer or RPC scan",
+            AddressSection::Source => "Explorer and Sourcify",
+            AddressSection::Creation => "Explorer",
+            AddressSection::Withdrawals => "Etherscan",
+        }
+    }
+
+    /// Tab whose content the section fills.
+    pub fn tab(self) -> MainViewTab {
+        match self {
+            AddressSection::Transactions => MainViewTab::AddressTransactions,
+            AddressSection::Account
+            | AddressSection::Source
+            | AddressSection::Creation
+            | AddressSection::Withdrawals => MainViewTab::AddressInfo,
+        }
+    }
+}
+
+/// An error kept whole rather than flattened into a line, for the error
+/// details modal and the copied report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ErrorDetail {
+    /// Who answered (or failed to): the endpoint or service asked.
+    pub source: String,
+    /// HTTP status of the failed response, when there was one.
+    pub status: Option<u16>,
+    pub message: String,
+    /// Underlying errors, outermost first.
+    pub causes: Vec<String>,
+    pub suggestion: Option<String>,
+}
+
+impl ErrorDetail {
+    pub fn from_report(source: impl Into<String>, err: &Report) -> Self {
+        let status = err.chain().find_map(status_of);
+        let timed_out = err.chain().any(|cause| cause.is::<TimedOut>());
+        Self::build(
+            source.into(),
+            status,
+            err.to_string(),
+            err.chain().skip(1).map(ToString::to_string).collect(),
+            timed_out,
+        )
+    }
+
+    pub fn from_fetch(source: impl Into<String>, err: &TransactionFetchError) -> Self {
+        let mut causes = Vec::new();
+        let mut status = None;
+        let mut timed_out = false;
+        let mut next = err.source();
+        while let Some(cause) = next {
+            status = status.or_else(|| status_of(cause));
+            timed_out |= cause
+                .downcast_ref::<reqwest::Error>()
+                .is_some_and(reqwest::Error::is_timeout);
+            causes.push(cause.to_string());
+            next = cause.source();
+        }
+        // The first cause repeats the top-level message for wrapped errors.
+        if causes
+            .first()
+            .is_some_and(|first| err.to_string().ends_with(first))
+        {
+            causes.remove(0);
+        }
+        let mut detail = Self::build(source.into(), status, err.to_string(), causes, timed_out);
+        if let Some(guidance) = err.guidance() {
+            detail.suggestion = Some(guidance.into());
+        }
+        detail
+    }
+
+    /// Whether the source refused for rate limiting. These are not retried
+    /// but shown to the user.
+    pub fn is_rate_limited(&self) -> bool {
+        self.status == Some(429)
+            || std::iter::once(&self.message)
+                .chain(&self.causes)
+                .any(|text| mentions_rate_limit(&text.to_ascii_lowercase()))
+    }
+
+    fn build(
+        source: String,
+        status: Option<u16>,
+        message: String,
+        causes: Vec<String>,
+        timed_out: bool,
+    ) -> Self {
+        let suggestion = suggest(status, &message, &causes, timed_out).map(str::to_string);
+        Self {
+            source,
+            status,
+            message,
+            causes,
+            suggestion,
+        }
+    }
+}
+
+fn status_of(cause: &(dyn Error + 'static)) -> Option<u16> {
+    if let Some(err) = cause.downcast_ref::<reqwest::Error>() {
+        return err.status().map(|status| status.as_u16());
+    }
+    if let Some(err) = cause.downcast_ref::<HttpError>() {
+        return Some(err.status);
+    }
+    match cause.downcast_ref::<RpcError<TransportErrorKind>>() {
+        Some(RpcError::Transport(TransportErrorKind::HttpError(err))) => Some(err.status),
+        _ => None,
+    }
+}
+
+fn mentions_rate_limit(text: &str) -> bool {
+    text.contains("rate limit") || text.contains("too many requests")
+}
+
+/// What the user can do about an error, from its status and wording.
+fn suggest(
+    status: Option<u16>,
+    message: &str,
+    causes: &[String],
+    timed_out: bool,
+) -> Option<&'static str> {
+    let text = std::iter::once(message)
+        .chain(causes.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_ascii_lowercase();
+    let mentions = |needles: &[&str]| needles.iter().any(|needle| text.contains(needle));
+    if matches!(status, Some(401 | 403)) || mentions(&["invalid api key", "missing/invalid"]) {
+        Some("Check the API key under Settings → Secrets.")
+    } else if status == Some(429) || mentions_rate_limit(&text) {
+        Some(
+            "The provider is rate limiting: wait a moment, slow auto-refresh (Shift-R) or use a paid tier.",
+        )
+    } else if timed_out || mentions(&["timed out", "timeout"]) {
+        Some(
+            "The endpoint is slow: raise it with `:limits timeout=30` (`explorer=30` for explorer APIs) or add a fallback RPC URL.",
+        )
+    } else if mentions(&[
+        "dns",
+        "resolve",
+        "connection refused",
+        "error trying to connect",
+        "connect error",
+    ]) {
+        Some("The endpoint could not be reached: check the URL and your network connection.")
+    } else if status.is_some_and(|status| status >= 500) {
+        Some("The provider is having trouble: retry later or add a fallback endpoint.")
+    } else if mentions(&["no etherscan api key"]) {
+        Some("Add an Etherscan API key under Settings → Secrets.")
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SectionFailure {
+    pub section: AddressSection,
+    pub detail: ErrorDetail,
+    /// Results of an earlier load are still shown in the section's place.
+    pub kept_earlier: bool,
+}
+
+impl SectionFailure {
+    pub fn new(section: AddressSection, detail: ErrorDetail) -> Self {
+        Self {
+            section,
+            detail,
+            kept_earlier: false,
+        }
+    }
+
+    /// A failed explorer (or explorer-style) fetch.
+    pub fn fetch(section: AddressSection, err: &TransactionFetchError) -> Self {
+        Self::new(section, ErrorDetail::from_fetch(section.source(), err))
+    }
+
+    pub fn banner(&self) -> String {
+        let kept = if self.kept_earlier {
+            " (showing earlier results)"
+        } else {
+            ""
+        };
+        format!(
+            "⚠ {} failed: {}{kept} • [r] Retry • [!] Details",
+            self.section.label(),
+            self.detail.message
+        )
+    }
+
+    /// Plain-text report of the failure, for pasting into an issue. URL paths
+    /// and queries are cut, since RPC URLs often carry an API key.
+    pub fn report(&self) -> String {
+        let detail = &self.detail;
+        let mut lines = vec![
+            format!("Section: {}", self.section.label()),
+            format!("Source: {}", detail.source),
+        ];
+        if let Some(status) = detail.status {
+            lines.push(format!("HTTP status: {status}"));
+        }
+        lines.push(format!("Error: {}", detail.message));
+        for (depth, cause) in detail.causes.iter().enumerate() {
+            lines.push(format!("  {}. caused by: {cause}", depth + 1));
+        }
+        if let Some(suggestion) = detail.suggestion.as_ref() {
+            lines.push(format!("Suggestion: {suggestion}"));
+        }
+        if self.kept_earlier {
+            lines.push("Earlier results are still shown.".into());
+        }
+        redact_urls(&lines.join("\n"))
+    }
+}
+
+/// Keep only the scheme and host of every URL in `text`.
+pub fn redact_urls(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("://") {
+        let (before, after) = rest.split_at(start + 3);
+        out.push_str(before);
+        let end = after
+            .find(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | ')' | ','))
+            .unwrap_or(after.len());
+        let (url, tail) = after.split_at(end);
+        let host_end = url.find(['/', '?', '#']).unwrap_or(url.len());
+        let host = &url[..host_end];
+        // Credentials in the authority are secrets too.
+        out.push_str(host.rsplit_once('@').map_or(host, |(_, host)| host));
+        if host_end < url.len() {
+            out.push_str("/…");
+        }
+        rest = tail;
+    }
+    out.push_str(rest);
+    out
+}
+
+/// After a reload of the same address, keep what `previous` had for sections
+/// that failed this time but had loaded before, so a transient error does not
+/// blank out a tab that was showing data.
+pub fn keep_earlier(data: &mut HydratedAddress, previous: &HydratedAddress) {
+    if data.identifier != previous.identifier {
+        return;
+    }
+    for failure in &mut data.failures {
+        let earlier_failed = previous
+            .failures
+            .iter()
+            .any(|earlier| earlier.section == failure.section && !earlier.kept_earlier);
+        if earlier_failed {
+            continue;
+        }
+        let kept = match failure.section {
+            AddressSection::Transactions if previous.transactions_table.is_some() => {
+                data.transactions = previous.transactions.clone();
+                data.transactions_table = previous.transactions_table.clone();
+                true
+            }
+            AddressSection::Source if previous.contract_source.is_some() => {
+                data.contract_source = previous.contract_source.clone();
+                true
+            }
+            AddressSection::Creation if previous.creation.is_some() => {
+                data.creation = previous.creation.clone();
+                true
+            }
+            AddressSection::Account if previous.overview.is_some() => {
+                data.overview = previous.overview.clone();
+                data.info = previous.info.clone();
+                true
+            }
+            _ => false,
+        };
+        failure.kept_earlier = kept;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::AddressTransactionsTable;
+
+    fn address(failures: Vec<SectionFailure>) -> HydratedAddress {
+        HydratedAddress {
+            identifier: "0xaa".into(),
+            info: vec!["Balance: 1 ETH".into()],
+            transactions: Vec::new(),
+            transactions_table: None,
+            internal: Vec::new(),
+            balances: Vec::new(),
+            permissions: Vec::new(),
+            overview: None,
+            contract_source: None,
+            creation: None,
+            failures,
+        }
+    }
+
+    #[test]
+    fn failed_reload_keeps_sections_that_loaded_before() {
+        let failure = |section, message: &str| {
+            SectionFailure::fetch(section, &TransactionFetchError::Api(message.into()))
+        };
+        let mut previous = address(vec![failure(
+            AddressSection::Source,
+            "Etherscan unavailable",
+        )]);
+        previous.transactions_table = Some(AddressTransactionsTable {
+            source_label: "Etherscan".into(),
+            source_api_version: "v2".into(),
+            limit: 25,
+            rows: Vec::new(),
+        });
+
+        let mut reloaded = address(vec![
+            failure(AddressSection::Transactions, "network error"),
+            failure(AddressSection::Source, "network error"),
+        ]);
+        keep_earlier(&mut reloaded, &previous);
+
+        assert!(reloaded.transactions_table.is_some());
+        assert!(reloaded.failures[0].kept_earlier);
+        assert!(!reloaded.failures[1].kept_earlier);
+        assert_eq!(
+            reloaded.failures[0].banner(),
+            "⚠ Transactions failed: network error (showing earlier results) • [r] Retry • [!] Details"
+        );
+        assert_eq!(reloaded.failures[1].section.tab(), MainViewTab::AddressInfo);
+    }
+
+    #[test]
+    fn reports_keep_the_cause_chain_and_a_suggestion() {
+        let err = Report::new(TimedOut(
+            "RPC request to https://rpc.test/v2/key timed out".into(),
+        ))
+        .wrap_err("failed to load account overview");
+        let failure = SectionFailure::new(
+            AddressSection::Account,
+            ErrorDetail::from_report("RPC https://rpc.test", &err),
+        );
+        assert_eq!(
+            failure.detail.causes,
+            vec!["RPC request to https://rpc.test/v2/key timed out"]
+        );
+        let report = failure.report();
+        assert!(report.contains("Error: failed to load account overview"));
+        assert!(report.contains("1. caused by: RPC request to https://rpc.test/… timed out"));
+        assert!(!report.contains("key"));
+        assert!(report.contains("Suggestion: The endpoint is slow"));
+        assert!(!failure.detail.is_rate_limited());
+
+        let throttled = ErrorDetail::from_fetch(
+            "Etherscan",
+            &TransactionFetchError::Api("Max rate limit reached".into()),
+        );
+        assert!(throttled.is_rate_limited());
+        assert!(throttled.suggestion.unwrap().contains("rate limiting"));
+
+        let pro_only = ErrorDetail::from_fetch(
+            "Etherscan",
+            &TransactionFetchError::from_api_message(
+                "Free API access is not supported for this chain. Please upgrade your api plan for full chain coverage.",
+            ),
+        );
+        assert!(pro_only.message.starts_with("paid API plan required"));
+        assert!(pro_only.suggestion.unwrap().contains("paid API plan"));
+        let daily = ErrorDetail::from_fetch(
+            "Etherscan",
+            &TransactionFetchError::from_api_message("Max daily rate limit reached"),
+        );
+        assert!(daily.suggestion.unwrap().contains("daily quota"));
+    }
+}
@@ -0,0 +1,166 @@
+use super::{anvil::normalize_url, providers::Rpc};
+use alloy::{
+    consensus::{Transaction as ConsensusTransaction, Typed2718},
+    network::{ReceiptResponse, TransactionResponse},
+    primitives::{B256, U256},
+    providers::Provider,
+};
+
+/// Gas pricing of one mined transaction, from the transaction, its receipt,
+/// and the including block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeBreakdown {
+    pub hash: String,
+    pub transaction_type: u8,
+    pub block_number: u64,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    /// `maxFeePerGas` for EIP-1559 style transactions, `gasPrice` otherwise.
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: Option<u128>,
+    pub effective_gas_price: u128,
+    /// `None` before London.
+    pub base_fee_per_gas: Option<u64>,
+    /// Lowest priority fee per gas any transaction in the block paid.
+    pub lowest_block_tip: Option<u128>,
+    /// Native token price in USD when the explorer reports one.
+    pub native_usd: Option<f64>,
+}
+
+impl FeeBreakdown {
+    pub fn total_fee_wei(&self) -> U256 {
+        U256::from(self.gas_used) * U256::from(self.effective_gas_price)
+    }
+
+    /// Priority fee per gas actually paid to the block producer.
+    pub fn tip_per_gas(&self) -> u128 {
+        self.effective_gas_price
+            .saturating_sub(self.base_fee_per_gas.unwrap_or_default().into())
+    }
+
+    pub fn burned_wei(&self) -> Option<U256> {
+        Some(U256::from(self.gas_used) * U256::from(self.base_fee_per_gas?))
+    }
+
+    /// Tip per gas above the cheapest tip that still made it into the same
+    /// block: what the sender could have saved and still been included.
+    pub fn overpaid_tip_per_gas(&self) -> Option<u128> {
+        Some(self.tip_per_gas().saturating_sub(self.lowest_block_tip?))
+    }
+
+    pub fn overpaid_wei(&self) -> Option<U256> {
+        Some(U256::from(self.gas_used) * U256::from(self.overpaid_tip_per_gas()?))
+    }
+
+    /// `wei` converted at the native token price.
+    pub fn usd(&self, wei: U256) -> Option<f64> {
+        let ether = f64::from(wei) / 1e18;
+        Some(ether * self.native_usd?)
+    }
+}
+
+/// State of the Fees tab for the selected transaction.
+#[derive(Debug, Default)]
+pub struct FeesView {
+    /// Transaction whose breakdown is loading or loaded.
+    pub hash: Option<String>,
+    pub loading: bool,
+    pub result: Option<Result<FeeBreakdown, String>>,
+}
+
+impl FeesView {
+    pub fn needs_load(&self, hash: &str) -> bool {
+        self.hash.as_deref() != Some(hash)
+    }
+}
+
+/// Load the fee breakdown of `hash`; `native_usd` is attached as-is.
+pub async fn fetch_fees(
+    rpc: Rpc,
+    rpc_url: String,
+    hash: String,
+    native_usd: Option<f64>,
+) -> Result<FeeBreakdown, String> {
+    let tx_hash: B256 = hash
+        .parse()
+        .map_err(|_| "invalid transaction hash".to_string())?;
+    let provider = rpc
+        .connect(&normalize_url(&rpc, &rpc_url))
+        .await
+        .map_err(|err| format!("{err:#}"))?;
+
+    rpc.stats().record_api_call("rpc");
+    let transaction = provider
+        .get_transaction_by_hash(tx_hash)
+        .await
+        .map_err(|err| format!("failed to load transaction: {err}"))?
+        .ok_or("transaction not found on this RPC")?;
+    rpc.stats().record_api_call("rpc");
+    let receipt = provider
+        .get_transaction_receipt(tx_hash)
+        .await
+        .map_err(|err| format!("failed to load receipt: {err}"))?
+        .ok_or("transaction is still pending")?;
+    let block_number = receipt
+        .block_number()
+        .ok_or("receipt has no block number")?;
+
+    rpc.stats().record_api_call("rpc");
+    let block = provider
+        .get_block_by_number(block_number.into())
+        .full()
+        .await
+        .map_err(|err| format!("failed to load block {block_number}: {err}"))?
+        .ok_or_else(|| format!("block {block_number} not found"))?;
+    let base_fee_per_gas = block.header.base_fee_per_gas;
+    let lowest_block_tip = block
+        .transactions
+        .txns()
+        .filter(|tx| tx.tx_hash() != tx_hash)
+        .map(|tx| tx.effective_tip_per_gas(base_fee_per_gas.unwrap_or_default()))
+        .map(Option::unwrap_or_default)
+        .min();
+
+    Ok(FeeBreakdown {
+        hash,
+        transaction_type: transaction.ty(),
+        block_number,
+        gas_limit: transaction.gas_limit(),
+        gas_used: receipt.gas_used(),
+        max_fee_per_gas: ConsensusTransaction::max_fee_per_gas(&transaction),
+        max_priority_fee_per_gas: transaction.max_priority_fee_per_gas(),
+        effective_gas_price: receipt.effective_gas_price(),
+        base_fee_per_gas,
+        lowest_block_tip,
+        native_usd,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_fee_into_burn_and_tip() {
+        let gwei = 1_000_000_000u128;
+        let fees = FeeBreakdown {
+            hash: "0x01".into(),
+            transaction_type: 2,
+            block_number: 1,
+            gas_limit: 30_000,
+            gas_used: 21_000,
+            max_fee_per_gas: 50 * gwei,
+            max_priority_fee_per_gas: Some(3 * gwei),
+            effective_gas_price: 13 * gwei,
+            base_fee_per_gas: Some(10 * gwei as u64),
+            lowest_block_tip: Some(gwei),
+            native_usd: Some(2_000.0),
+        };
+        assert_eq!(fees.tip_per_gas(), 3 * gwei);
+        assert_eq!(fees.total_fee_wei(), U256::from(21_000 * 13 * gwei));
+        assert_eq!(fees.burned_wei(), Some(U256::from(21_000 * 10 * gwei)));
+        assert_eq!(fees.overpaid_tip_per_gas(), Some(2 * gwei));
+        let usd = fees.usd(fees.total_fee_wei()).unwrap();
+        assert!((usd - 0.546).abs() < 1e-9);
+    }
+}
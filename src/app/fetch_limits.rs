@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, time::Duration};
+
+/// Most transactions one hydration may ask for; explorers cap a page near here.
+const MAX_TRANSACTIONS: usize = 1_000;
+/// Longest timeout accepted, in seconds.
+const MAX_TIMEOUT_SECS: u64 = 120;
+
+/// How deep and how patiently addresses are hydrated on one chain, set with
+/// `:limits` and stored per chain under the `hydration.fetch_limits` setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FetchLimits {
+    /// Recent transactions requested for the address table.
+    pub transactions: usize,
+    /// Seconds allowed for the account query (balance, nonce, code).
+    pub request_timeout_secs: u64,
+    /// Seconds allowed for quick probes: chain id and latest block.
+    pub probe_timeout_secs: u64,
+    /// Seconds allowed for each explorer request (history, source, prices).
+    pub explorer_timeout_secs: u64,
+}
+
+impl Default for FetchLimits {
+    fn default() -> Self {
+        Self {
+            transactions: 25,
+            request_timeout_secs: 10,
+            probe_timeout_secs: 4,
+            explorer_timeout_secs: 10,
+        }
+    }
+}
+
+impl FetchLimits {
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_secs(self.request_timeout_secs)
+    }
+
+    pub fn probe_timeout(&self) -> Duration {
+        Duration::from_secs(self.probe_timeout_secs)
+    }
+
+    pub fn explorer_timeout(&self) -> Duration {
+        Duration::from_secs(self.explorer_timeout_secs)
+    }
+
+    /// Apply one `name=value` argument: `txs`, `timeout`, `probe` or
+    /// `explorer`.
+    pub fn apply(&mut self, arg: &str) -> Result<(), String> {
+        let (name, value) = arg
+            .split_once('=')
+            .ok_or_else(|| format!("expected name=value, got {arg}"))?;
+        let value: u64 = value
+            .trim_end_matches('s')
+            .parse()
+            .map_err(|_| format!("{name} needs a whole number, got {value}"))?;
+        match name {
+            "txs" => {
+                if !(1..=MAX_TRANSACTIONS as u64).contains(&value) {
+                    return Err(format!("txs must be between 1 and {MAX_TRANSACTIONS}"));
+                }
+                self.transactions = value as usize;
+            }
+            "timeout" | "probe" | "explorer" => {
+                if !(1..=MAX_TIMEOUT_SECS).contains(&value) {
+                    return Err(format!("{name} must be between 1 and {MAX_TIMEOUT_SECS}s"));
+                }
+                match name {
+                    "timeout" => self.request_timeout_secs = value,
+                    "probe" => self.probe_timeout_secs = value,
+                    _ => self.explorer_timeout_secs = value,
+                }
+            }
+            _ => {
+                return Err(format!(
+                    "unknown limit {name} (txs, timeout, probe, explorer)"
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn label(&self) -> String {
+        format!(
+            "{} txs • {}s timeout • {}s probe • {}s explorer",
+            self.transactions,
+            self.request_timeout_secs,
+            self.probe_timeout_secs,
+            self.explorer_timeout_secs
+        )
+    }
+}
+
+/// Limits for `chain`, the defaults when none were saved.
+pub fn for_chain(limits: &BTreeMap<String, FetchLimits>, chain: &str) -> FetchLimits {
+    limits
+        .get(&chain.to_ascii_lowercase())
+        .copied()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arguments_update_one_limit_each() {
+        let mut limits = FetchLimits::default();
+        limits.apply("txs=100").unwrap();
+        limits.apply("timeout=30s").unwrap();
+        limits.apply("probe=8").unwrap();
+        limits.apply("explorer=20s").unwrap();
+        assert_eq!(
+            limits.label(),
+            "100 txs • 30s timeout • 8s probe • 20s explorer"
+        );
+        assert_eq!(limits.request_timeout(), Duration::from_secs(30));
+        assert_eq!(limits.explorer_timeout(), Duration::from_secs(20));
+
+        assert!(limits.apply("txs=0").is_err());
+        assert!(limits.apply("timeout=600").is_err());
+        assert!(limits.apply("explorer=0").is_err());
+        assert!(limits.apply("depth=3").is_err());
+        assert!(limits.apply("txs").is_err());
+        assert_eq!(limits.transactions, 100);
+
+        let saved = BTreeMap::from([("base".to_string(), limits)]);
+        assert_eq!(for_chain(&saved, "Base"), limits);
+        assert_eq!(for_chain(&saved, "Mainnet"), FetchLimits::default());
+    }
+}
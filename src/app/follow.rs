@@ -0,0 +1,202 @@
+use super::{
+    AddressTransactionRow,
+    anvil::{fetch_block_transactions, fetch_latest_block},
+    explorer::AddressTransaction,
+    providers::Rpc,
+    ws::SubscriptionHandle,
+};
+use alloy::primitives::Address;
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant},
+};
+
+/// How often the chain head is checked while following an address.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(4);
+/// Safety-net poll interval when new heads arrive over a WebSocket.
+pub const LIVE_FALLBACK_INTERVAL: Duration = Duration::from_secs(30);
+/// Blocks scanned per poll; a longer gap (e.g. after sleep) skips older blocks.
+const MAX_BLOCKS_PER_POLL: u64 = 20;
+/// How long freshly landed rows stay highlighted.
+const HIGHLIGHT_DURATION: Duration = Duration::from_secs(3);
+
+/// Result of one poll: the head it scanned up to and the matching transactions.
+#[derive(Debug, Clone)]
+pub struct FollowUpdate {
+    pub address: String,
+    pub latest_block: u64,
+    pub transactions: Vec<AddressTransaction>,
+}
+
+/// Follow mode on the Transactions tab: which address is live and which rows
+/// recently landed.
+#[derive(Debug, Default)]
+pub struct FollowState {
+    address: Option<String>,
+    last_block: Option<u64>,
+    highlights: HashMap<String, Instant>,
+    /// newHeads/logs subscriptions when the chain has a WebSocket endpoint.
+    subscriptions: Vec<SubscriptionHandle>,
+    /// Transactions that emitted logs from the address without calling it
+    /// directly (e.g. through a router); the next scan includes them.
+    log_hashes: HashSet<String>,
+}
+
+impl FollowState {
+    pub fn start(&mut self, address: &str) {
+        *self = Self {
+            address: Some(address.to_string()),
+            ..Self::default()
+        };
+    }
+
+    pub fn stop(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn address(&self) -> Option<&str> {
+        self.address.as_deref()
+    }
+
+    pub fn is_following(&self, address: &str) -> bool {
+        self.address.as_deref() == Some(address)
+    }
+
+    pub fn last_block(&self) -> Option<u64> {
+        self.last_block
+    }
+
+    pub fn set_last_block(&mut self, block: u64) {
+        self.last_block = Some(block);
+    }
+
+    pub fn set_subscriptions(&mut self, subscriptions: Vec<SubscriptionHandle>) {
+        self.subscriptions = subscriptions;
+    }
+
+    /// Whether new blocks are pushed over a WebSocket instead of polled.
+    pub fn is_live(&self) -> bool {
+        !self.subscriptions.is_empty()
+    }
+
+    /// Remember a transaction that logged from the address; if its block was
+    /// already scanned, rewind so the next poll scans it again.
+    pub fn note_log(&mut self, hash: String, block: Option<u64>) {
+        self.log_hashes.insert(hash);
+        if let (Some(block), Some(last)) = (block, self.last_block)
+            && block <= last
+        {
+            self.last_block = Some(block.saturating_sub(1));
+        }
+    }
+
+    pub fn take_log_hashes(&mut self) -> HashSet<String> {
+        std::mem::take(&mut self.log_hashes)
+    }
+
+    pub fn highlight(&mut self, hash: &str) {
+        self.highlights
+            .retain(|_, landed| landed.elapsed() < HIGHLIGHT_DURATION);
+        self.highlights.insert(hash.to_string(), Instant::now());
+    }
+
+    /// Whether a landed row is still fading, so frames must keep coming.
+    pub fn is_highlighting(&self) -> bool {
+        self.highlights
+            .values()
+            .any(|landed| landed.elapsed() < HIGHLIGHT_DURATION)
+    }
+
+    /// Remaining highlight for `hash`, from 1.0 when it landed down to 0.0.
+    pub fn highlight_level(&self, hash: &str) -> f32 {
+        self.highlights
+            .get(hash)
+            .map(|landed| 1.0 - landed.elapsed().as_secs_f32() / HIGHLIGHT_DURATION.as_secs_f32())
+            .unwrap_or(0.0)
+            .max(0.0)
+    }
+}
+
+/// Insert `new` rows (newest first) ahead of `rows`, skipping hashes already
+/// listed. Returns how many were inserted.
+pub fn prepend_rows(
+    rows: &mut Vec<AddressTransactionRow>,
+    new: Vec<AddressTransactionRow>,
+) -> usize {
+    let known: HashSet<String> = rows.iter().map(|row| row.hash.clone()).collect();
+    let fresh: Vec<_> = new
+        .into_iter()
+        .filter(|row| !known.contains(&row.hash))
+        .collect();
+    let count = fresh.len();
+    rows.splice(0..0, fresh);
+    count
+}
+
+/// Scan blocks after `after_block` up to the current head for transactions
+/// touching `address` or listed in `also`. The first poll (no `after_block`)
+/// only records the head.
+pub async fn poll(
+    rpc: Rpc,
+    rpc_url: String,
+    address: String,
+    after_block: Option<u64>,
+    also: HashSet<String>,
+) -> Result<FollowUpdate, String> {
+    let target: Address = address.parse().map_err(|_| "invalid address".to_string())?;
+    let latest_block = fetch_latest_block(&rpc, &rpc_url)
+        .await
+        .map_err(|err| format!("{err:#}"))?;
+    let transactions = match after_block {
+        Some(after) if latest_block > after => {
+            let from = (after + 1).max(latest_block.saturating_sub(MAX_BLOCKS_PER_POLL - 1));
+            fetch_block_transactions(&rpc, &rpc_url, target, &also, from, latest_block)
+                .await
+                .map_err(|err| format!("{err:#}"))?
+        }
+        _ => Vec::new(),
+    };
+    Ok(FollowUpdate {
+        address,
+        latest_block,
+        transactions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{TransactionDirection, TransactionStatus};
+    use alloy::primitives::U256;
+
+    fn row(hash: &str) -> AddressTransactionRow {
+        AddressTransactionRow {
+            hash: hash.into(),
+            from: "0xaa".into(),
+            to: None,
+            value_wei: U256::ZERO,
+            block_number: None,
+            direction: TransactionDirection::Interaction,
+            counterparty: String::new(),
+            value_display: String::new(),
+            status: TransactionStatus::Success,
+            calldata: None,
+            timestamp: None,
+        }
+    }
+
+    #[test]
+    fn new_rows_are_prepended_once() {
+        let mut rows = vec![row("0x02"), row("0x01")];
+        let inserted = prepend_rows(&mut rows, vec![row("0x04"), row("0x03"), row("0x02")]);
+        assert_eq!(inserted, 2);
+        let hashes: Vec<_> = rows.iter().map(|row| row.hash.as_str()).collect();
+        assert_eq!(hashes, vec!["0x04", "0x03", "0x02", "0x01"]);
+
+        let mut follow = FollowState::default();
+        follow.start("0xaa");
+        follow.highlight("0x04");
+        assert!(follow.highlight_level("0x04") > 0.9);
+        assert_eq!(follow.highlight_level("0x03"), 0.0);
+    }
+}
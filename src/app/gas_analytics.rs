@@ -0,0 +1,176 @@
+use super::{AddressRef, explorer::AddressTransaction, providers::Providers};
+use std::collections::BTreeMap;
+
+/// Transactions fetched for the analysis, newest first.
+pub const ANALYTICS_FETCH_LIMIT: usize = 1_000;
+
+/// Gas spent on one entry point of a contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectorStats {
+    /// `0x`-prefixed 4-byte selector; `None` for calls without calldata,
+    /// which run the `receive`/`fallback` function.
+    pub selector: Option<String>,
+    pub calls: usize,
+    pub failed: usize,
+    /// Calls whose source reported gas used; the averages cover only these.
+    pub measured: usize,
+    pub total_gas: u64,
+    pub average_gas: u64,
+    pub median_gas: u64,
+    pub max_gas: u64,
+    /// Gas burned by the failed calls alone.
+    pub failed_gas: u64,
+}
+
+impl SelectorStats {
+    pub fn failure_rate(&self) -> f64 {
+        self.failed as f64 / self.calls.max(1) as f64
+    }
+}
+
+/// Per-selector gas usage of the calls into a contract, costliest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GasReport {
+    /// Transactions fetched, including ones the contract only sent.
+    pub scanned: usize,
+    /// Transactions that called the contract.
+    pub calls: usize,
+    pub total_gas: u64,
+    pub source: String,
+    pub selectors: Vec<SelectorStats>,
+}
+
+fn median(sorted: &[u64]) -> u64 {
+    match sorted.len() {
+        0 => 0,
+        len if !len.is_multiple_of(2) => sorted[len / 2],
+        len => (sorted[len / 2 - 1] + sorted[len / 2]) / 2,
+    }
+}
+
+/// Group the transactions sent to `contract` by function selector.
+pub fn summarize(contract: &str, transactions: &[AddressTransaction], source: String) -> GasReport {
+    let mut groups: BTreeMap<Option<String>, Vec<&AddressTransaction>> = BTreeMap::new();
+    for tx in transactions.iter().filter(|tx| {
+        tx.to
+            .as_deref()
+            .is_some_and(|to| to.eq_ignore_ascii_case(contract))
+    }) {
+        let selector = tx
+            .input
+            .as_deref()
+            .and_then(|input| input.get(..10))
+            .map(str::to_ascii_lowercase);
+        groups.entry(selector).or_default().push(tx);
+    }
+    let mut selectors: Vec<SelectorStats> = groups
+        .into_iter()
+        .map(|(selector, calls)| {
+            let mut gas: Vec<u64> = calls.iter().filter_map(|tx| tx.gas_used).collect();
+            gas.sort_unstable();
+            let total_gas = gas.iter().sum::<u64>();
+            SelectorStats {
+                selector,
+                calls: calls.len(),
+                failed: calls.iter().filter(|tx| tx.is_error).count(),
+                measured: gas.len(),
+                total_gas,
+                average_gas: total_gas / gas.len().max(1) as u64,
+                median_gas: median(&gas),
+                max_gas: gas.last().copied().unwrap_or_default(),
+                failed_gas: calls
+                    .iter()
+                    .filter(|tx| tx.is_error)
+                    .filter_map(|tx| tx.gas_used)
+                    .sum(),
+            }
+        })
+        .collect();
+    selectors.sort_by(|a, b| {
+        (b.total_gas, b.calls)
+            .cmp(&(a.total_gas, a.calls))
+            .then_with(|| a.selector.cmp(&b.selector))
+    });
+    GasReport {
+        scanned: transactions.len(),
+        calls: selectors.iter().map(|stats| stats.calls).sum(),
+        total_gas: selectors.iter().map(|stats| stats.total_gas).sum(),
+        source,
+        selectors,
+    }
+}
+
+/// Fetch the contract's latest history and summarize its gas per selector.
+pub async fn fetch_gas_report(
+    providers: Providers,
+    address: AddressRef,
+    api_key: Option<String>,
+    rpc_url: Option<String>,
+) -> Result<GasReport, String> {
+    let (transactions, source) = providers
+        .explorer
+        .address_transactions(
+            &address,
+            api_key.as_deref(),
+            rpc_url.as_deref(),
+            ANALYTICS_FETCH_LIMIT,
+        )
+        .await
+        .map_err(|err| err.to_string())?;
+    Ok(summarize(
+        &address.address,
+        &transactions,
+        format!("{} ({})", source.label, source.api_version),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::U256;
+
+    fn call(input: Option<&str>, gas_used: Option<u64>, is_error: bool) -> AddressTransaction {
+        AddressTransaction {
+            hash: "0x01".into(),
+            block_number: 1,
+            from: "0x00000000000000000000000000000000000000aa".into(),
+            to: Some("0x00000000000000000000000000000000000000CC".into()),
+            value_wei: U256::ZERO,
+            is_error,
+            input: input.map(str::to_string),
+            timestamp: None,
+            gas_used,
+        }
+    }
+
+    #[test]
+    fn groups_gas_by_selector() {
+        let contract = "0x00000000000000000000000000000000000000cc";
+        let mut outgoing = call(None, Some(1), false);
+        outgoing.to = Some("0x00000000000000000000000000000000000000dd".into());
+        let transactions = [
+            call(Some("0xa9059cbb0000"), Some(50_000), false),
+            call(Some("0xA9059CBB0001"), Some(30_000), true),
+            call(Some("0xa9059cbb0002"), Some(40_000), false),
+            call(Some("0x095ea7b3"), Some(200_000), false),
+            call(None, None, false),
+            outgoing,
+        ];
+        let report = summarize(contract, &transactions, "Etherscan (v2)".into());
+        assert_eq!((report.scanned, report.calls), (6, 5));
+        assert_eq!(report.total_gas, 320_000);
+
+        let approve = &report.selectors[0];
+        assert_eq!(approve.selector.as_deref(), Some("0x095ea7b3"));
+        let transfer = &report.selectors[1];
+        assert_eq!((transfer.calls, transfer.failed), (3, 1));
+        assert_eq!(transfer.average_gas, 40_000);
+        assert_eq!(transfer.median_gas, 40_000);
+        assert_eq!(transfer.failed_gas, 30_000);
+        assert!((transfer.failure_rate() - 1.0 / 3.0).abs() < 1e-9);
+
+        let plain = &report.selectors[2];
+        assert_eq!(plain.selector, None);
+        assert_eq!((plain.calls, plain.measured, plain.median_gas), (1, 0, 0));
+    }
+}
@@ -0,0 +1,393 @@
+use super::{
+    anvil::{self, AccountOverview, BlockContext},
+    providers::{AccountDataProvider, BoxFuture, Rpc},
+    stats::ApiStats,
+};
+use alloy::primitives::{Address, B256, Bytes, U256};
+use color_eyre::{
+    Result,
+    eyre::{WrapErr, eyre},
+};
+use serde::{Deserialize, Deserializer, Serialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+use std::{collections::BTreeMap, time::Duration};
+
+/// Which node API account data is read through on a chain.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NodeBackend {
+    /// JSON-RPC, one request per field.
+    #[default]
+    Rpc,
+    /// The node's EIP-1767 GraphQL endpoint (`geth --graphql`, Besu), one
+    /// query per view.
+    Graphql,
+}
+
+impl NodeBackend {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.to_ascii_lowercase().as_str() {
+            "rpc" | "jsonrpc" | "json-rpc" => Some(NodeBackend::Rpc),
+            "graphql" | "gql" => Some(NodeBackend::Graphql),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            NodeBackend::Rpc => "JSON-RPC",
+            NodeBackend::Graphql => "GraphQL (EIP-1767)",
+        }
+    }
+}
+
+/// Backend for `chain`, JSON-RPC when none was chosen.
+pub fn for_chain(backends: &BTreeMap<String, NodeBackend>, chain: &str) -> NodeBackend {
+    backends
+        .get(&chain.to_ascii_lowercase())
+        .copied()
+        .unwrap_or_default()
+}
+
+/// The GraphQL endpoint served next to an RPC URL: Geth and Besu answer on
+/// `/graphql` of the HTTP port.
+pub fn endpoint(rpc_url: &str) -> String {
+    let url = rpc_url.trim().trim_end_matches('/');
+    if url.ends_with("/graphql") {
+        url.to_string()
+    } else {
+        format!("{url}/graphql")
+    }
+}
+
+/// Account state at the head in one query: the pending nonce comes from the
+/// same request instead of a second `eth_getTransactionCount`.
+const ACCOUNT_QUERY: &str = "query Account($address: Address!) {
+  block { number account(address: $address) { balance transactionCount code } }
+  pending { account(address: $address) { transactionCount } }
+}";
+
+const HEAD_QUERY: &str = "{ block { number } }";
+
+const CHAIN_ID_QUERY: &str = "{ chainID }";
+
+/// The transaction's position and its block's hashes in one query.
+const INCLUSION_QUERY: &str = "query Inclusion($hash: Bytes32!) {
+  transaction(hash: $hash) { index block { number timestamp transactions { hash } } }
+}";
+
+/// `Long` and `BigInt` scalars: nodes send them as hex strings, older ones
+/// as JSON numbers or decimal strings.
+fn quantity<'de, D: Deserializer<'de>>(deserializer: D) -> Result<U256, D::Error> {
+    match Value::deserialize(deserializer)? {
+        Value::Number(number) => number
+            .as_u64()
+            .map(U256::from)
+            .ok_or_else(|| serde::de::Error::custom(format!("{number} is not a quantity"))),
+        Value::String(text) => match text.strip_prefix("0x") {
+            Some(hex) => U256::from_str_radix(hex, 16),
+            None => U256::from_str_radix(&text, 10),
+        }
+        .map_err(|_| serde::de::Error::custom(format!("{text} is not a quantity"))),
+        other => Err(serde::de::Error::custom(format!(
+            "{other} is not a quantity"
+        ))),
+    }
+}
+
+fn long<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    let value = quantity(deserializer)?;
+    u64::try_from(value).map_err(|_| serde::de::Error::custom(format!("{value} exceeds u64")))
+}
+
+#[derive(Deserialize)]
+struct Head {
+    #[serde(deserialize_with = "long")]
+    number: u64,
+}
+
+#[derive(Deserialize)]
+struct HeadResponse {
+    block: Head,
+}
+
+#[derive(Deserialize)]
+struct ChainIdResponse {
+    #[serde(rename = "chainID", deserialize_with = "long")]
+    chain_id: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Account {
+    #[serde(deserialize_with = "quantity")]
+    balance: U256,
+    #[serde(deserialize_with = "long")]
+    transaction_count: u64,
+    code: Bytes,
+}
+
+#[derive(Deserialize)]
+struct AccountBlock {
+    #[serde(deserialize_with = "long")]
+    number: u64,
+    account: Account,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PendingAccount {
+    #[serde(deserialize_with = "long")]
+    transaction_count: u64,
+}
+
+#[derive(Deserialize)]
+struct Pending {
+    account: PendingAccount,
+}
+
+#[derive(Deserialize)]
+struct AccountResponse {
+    block: AccountBlock,
+    pending: Pending,
+}
+
+impl From<AccountResponse> for AccountOverview {
+    fn from(response: AccountResponse) -> Self {
+        let AccountResponse { block, pending } = response;
+        let code = block.account.code;
+        let delegate = anvil::delegation_target(&code);
+        AccountOverview {
+            latest_block: block.number,
+            balance_wei: block.account.balance,
+            transaction_count: block.account.transaction_count,
+            pending_transaction_count: pending.account.transaction_count,
+            // The schema has no view of the txpool's queued transactions.
+            queued_nonces: None,
+            is_contract: !code.is_empty() && delegate.is_none(),
+            delegate,
+            code,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct HashOnly {
+    hash: B256,
+}
+
+#[derive(Deserialize)]
+struct InclusionBlock {
+    #[serde(deserialize_with = "long")]
+    number: u64,
+    #[serde(deserialize_with = "long")]
+    timestamp: u64,
+    transactions: Vec<HashOnly>,
+}
+
+#[derive(Deserialize)]
+struct Inclusion {
+    #[serde(default, deserialize_with = "optional_long")]
+    index: Option<u64>,
+    block: Option<InclusionBlock>,
+}
+
+fn optional_long<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u64>, D::Error> {
+    #[derive(Deserialize)]
+    struct Wrapped(#[serde(deserialize_with = "long")] u64);
+    Ok(Option::<Wrapped>::deserialize(deserializer)?.map(|Wrapped(value)| value))
+}
+
+#[derive(Deserialize)]
+struct InclusionResponse {
+    transaction: Option<Inclusion>,
+}
+
+#[derive(Deserialize)]
+struct GraphqlError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct Envelope<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphqlError>,
+}
+
+/// Run one query against the GraphQL endpoint next to `rpc_url`.
+async fn request<T: DeserializeOwned>(
+    stats: &ApiStats,
+    rpc_url: String,
+    query: &str,
+    variables: Value,
+) -> Result<T> {
+    let url = endpoint(&rpc_url);
+    let client = reqwest::Client::builder()
+        .user_agent("evm-tui/0.1.0")
+        .timeout(Duration::from_secs(15))
+        .build()
+        .wrap_err("failed to build GraphQL client")?;
+    stats.record_api_call("graphql");
+    let response = client
+        .post(&url)
+        .json(&json!({ "query": query, "variables": variables }))
+        .send()
+        .await
+        .wrap_err_with(|| format!("GraphQL request to {url} failed"))?;
+    let status = response.status();
+    let text = response
+        .text()
+        .await
+        .wrap_err("failed to read GraphQL response")?;
+    let envelope: Envelope<T> = serde_json::from_str(&text).wrap_err_with(|| {
+        format!(
+            "{url} did not answer with GraphQL (HTTP {status}); is the node started with --graphql?"
+        )
+    })?;
+    if let Some(error) = envelope.errors.first() {
+        return Err(eyre!("GraphQL error from {url}: {}", error.message));
+    }
+    envelope
+        .data
+        .ok_or_else(|| eyre!("GraphQL response from {url} has no data"))
+}
+
+pub async fn fetch_account_overview(
+    rpc: &Rpc,
+    rpc_url: &str,
+    target: Address,
+) -> Result<AccountOverview> {
+    anvil::with_failover(rpc, rpc_url, |url| async move {
+        let response: AccountResponse = request(
+            rpc.stats(),
+            url,
+            ACCOUNT_QUERY,
+            json!({ "address": target }),
+        )
+        .await?;
+        Ok(response.into())
+    })
+    .await
+}
+
+pub async fn fetch_latest_block(rpc: &Rpc, rpc_url: &str) -> Result<u64> {
+    anvil::with_failover(rpc, rpc_url, |url| async move {
+        let response: HeadResponse = request(rpc.stats(), url, HEAD_QUERY, Value::Null).await?;
+        Ok(response.block.number)
+    })
+    .await
+}
+
+pub async fn fetch_chain_id(rpc: &Rpc, rpc_url: &str) -> Result<u64> {
+    anvil::with_failover(rpc, rpc_url, |url| async move {
+        let response: ChainIdResponse =
+            request(rpc.stats(), url, CHAIN_ID_QUERY, Value::Null).await?;
+        Ok(response.chain_id)
+    })
+    .await
+}
+
+/// [`anvil::fetch_block_context`] in one query instead of a transaction and
+/// a block lookup.
+pub async fn fetch_block_context(
+    rpc: &Rpc,
+    rpc_url: &str,
+    hash: &str,
+) -> Result<Option<BlockContext>> {
+    let hash: B256 = hash.parse().wrap_err("invalid transaction hash")?;
+    anvil::with_failover(rpc, rpc_url, |url| async move {
+        let response: InclusionResponse =
+            request(rpc.stats(), url, INCLUSION_QUERY, json!({ "hash": hash })).await?;
+        Ok(response.transaction.and_then(|transaction| {
+            let block = transaction.block?;
+            Some(BlockContext {
+                block_number: block.number,
+                timestamp: block.timestamp,
+                index: transaction.index? as usize,
+                hashes: block.transactions.into_iter().map(|tx| tx.hash).collect(),
+            })
+        }))
+    })
+    .await
+}
+
+/// Account data from the node's GraphQL endpoint, for chains set to
+/// [`NodeBackend::Graphql`].
+pub struct GraphqlAccountData(pub Rpc);
+
+impl AccountDataProvider for GraphqlAccountData {
+    fn chain_id<'a>(&'a self, rpc_url: &'a str) -> BoxFuture<'a, Result<u64>> {
+        Box::pin(fetch_chain_id(&self.0, rpc_url))
+    }
+
+    fn account_overview<'a>(
+        &'a self,
+        rpc_url: &'a str,
+        address: Address,
+    ) -> BoxFuture<'a, Result<AccountOverview>> {
+        Box::pin(fetch_account_overview(&self.0, rpc_url, address))
+    }
+
+    fn latest_block<'a>(&'a self, rpc_url: &'a str) -> BoxFuture<'a, Result<u64>> {
+        Box::pin(fetch_latest_block(&self.0, rpc_url))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_account_and_inclusion_queries() {
+        assert_eq!(
+            endpoint("http://localhost:8545/"),
+            "http://localhost:8545/graphql"
+        );
+        assert_eq!(
+            endpoint("http://node:8547/graphql"),
+            "http://node:8547/graphql"
+        );
+        assert_eq!(NodeBackend::parse("GQL"), Some(NodeBackend::Graphql));
+        let saved = BTreeMap::from([("base".to_string(), NodeBackend::Graphql)]);
+        assert_eq!(for_chain(&saved, "Base"), NodeBackend::Graphql);
+        assert_eq!(for_chain(&saved, "Mainnet"), NodeBackend::Rpc);
+
+        // Geth sends hex strings; a decimal number must read the same.
+        let account: Envelope<AccountResponse> = serde_json::from_str(
+            r#"{"data":{
+                "block":{"number":"0x1406f40","account":{
+                    "balance":"0xde0b6b3a7640000","transactionCount":7,"code":"0x"}},
+                "pending":{"account":{"transactionCount":"0x9"}}}}"#,
+        )
+        .unwrap();
+        let overview = AccountOverview::from(account.data.unwrap());
+        assert_eq!(overview.latest_block, 21_000_000);
+        assert_eq!(
+            overview.balance_wei,
+            U256::from(1_000_000_000_000_000_000u64)
+        );
+        assert_eq!(
+            (
+                overview.transaction_count,
+                overview.pending_transaction_count
+            ),
+            (7, 9)
+        );
+        assert!(!overview.is_contract);
+
+        let hash = format!("0x{}", "22".repeat(32));
+        let inclusion: Envelope<InclusionResponse> = serde_json::from_str(&format!(
+            r#"{{"data":{{"transaction":{{"index":"0x0","block":{{
+                "number":"0x10","timestamp":"0x65","transactions":[{{"hash":"{hash}"}}]}}}}}}}}"#
+        ))
+        .unwrap();
+        let transaction = inclusion.data.unwrap().transaction.unwrap();
+        assert_eq!(transaction.index, Some(0));
+        assert_eq!(transaction.block.unwrap().timestamp, 101);
+
+        let failed: Envelope<HeadResponse> =
+            serde_json::from_str(r#"{"errors":[{"message":"boom"}]}"#).unwrap();
+        assert!(failed.data.is_none());
+        assert_eq!(failed.errors[0].message, "boom");
+    }
+}
@@ -0,0 +1,189 @@
+use super::{
+    AddressRef, HydratedAddress, HydratedTransaction, MainViewTab, SelectedEntity, TransactionRef,
+    balance_diff::BalanceDiff, csv_export::file_name_id, transfers::TransferSummary,
+};
+use color_eyre::{Result, eyre::WrapErr};
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const EXPORT_DIR: &str = "exports";
+
+/// The hydrated view of the selection, tagged with what it describes.
+/// Transactions also carry the traced transfers and balance diff when their
+/// tabs have been loaded.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ViewExport<'a> {
+    Address {
+        chain: &'a str,
+        address: &'a str,
+        label: Option<&'a str>,
+        data: &'a HydratedAddress,
+    },
+    Transaction {
+        chain: &'a str,
+        hash: &'a str,
+        data: &'a HydratedTransaction,
+        transfers: Option<&'a TransferSummary>,
+        balance_diff: Option<&'a BalanceDiff>,
+    },
+}
+
+impl<'a> ViewExport<'a> {
+    pub fn address(
+        addr: &'a AddressRef,
+        label: Option<&'a str>,
+        data: &'a HydratedAddress,
+    ) -> Self {
+        ViewExport::Address {
+            chain: &addr.chain,
+            address: &addr.address,
+            label,
+            data,
+        }
+    }
+
+    pub fn transaction(
+        tx: &'a TransactionRef,
+        data: &'a HydratedTransaction,
+        transfers: Option<&'a TransferSummary>,
+        balance_diff: Option<&'a BalanceDiff>,
+    ) -> Self {
+        ViewExport::Transaction {
+            chain: &tx.chain,
+            hash: &tx.hash,
+            data,
+            transfers,
+            balance_diff,
+        }
+    }
+
+    /// `exports/<kind>_<id prefix>.json`, used when no path is given.
+    pub fn default_path(&self) -> PathBuf {
+        let (kind, id) = match self {
+            ViewExport::Address { address, .. } => ("address", *address),
+            ViewExport::Transaction { hash, .. } => ("transaction", *hash),
+        };
+        Path::new(EXPORT_DIR).join(format!("{kind}_{}.json", file_name_id(id)))
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).wrap_err("failed to serialize view")
+    }
+
+    /// Only what `tab` shows, as `data` next to the kind, chain and id; tabs
+    /// without a section of their own get the whole view.
+    pub fn tab_json(&self, tab: MainViewTab) -> Result<String> {
+        let section = match tab {
+            MainViewTab::AddressTransactions => Some(("transactions", "/data/transactions_table")),
+            MainViewTab::AddressInternal => Some(("internal", "/data/internal")),
+            MainViewTab::AddressBalances => Some(("balances", "/data/balances")),
+            MainViewTab::AddressPermissions => Some(("permissions", "/data/permissions")),
+            MainViewTab::TransactionBalanceDiff => Some(("balance_diff", "/balance_diff")),
+            MainViewTab::TransactionDebug => Some(("debug", "/data/debug")),
+            MainViewTab::TransactionStorageDiff => Some(("storage_diff", "/data/storage_diff")),
+            _ => None,
+        };
+        let Some((name, pointer)) = section else {
+            return self.to_json();
+        };
+        let mut value = serde_json::to_value(self).wrap_err("failed to serialize view")?;
+        let data = value.pointer(pointer).cloned().unwrap_or(Value::Null);
+        if let Some(object) = value.as_object_mut() {
+            object.retain(|key, _| {
+                matches!(
+                    key.as_str(),
+                    "kind" | "chain" | "address" | "hash" | "label"
+                )
+            });
+            object.insert("tab".into(), name.into());
+            object.insert("data".into(), data);
+        }
+        serde_json::to_string_pretty(&value).wrap_err("failed to serialize view")
+    }
+}
+
+/// A custom tab's output for `entity`, shaped like [`ViewExport::tab_json`].
+pub fn custom_tab_json(entity: &SelectedEntity, title: &str, lines: &[String]) -> Result<String> {
+    let value = match entity {
+        SelectedEntity::Address(addr) => json!({
+            "kind": "address",
+            "chain": addr.chain,
+            "address": addr.address,
+            "tab": title,
+            "data": lines,
+        }),
+        SelectedEntity::Transaction(tx) => json!({
+            "kind": "transaction",
+            "chain": tx.chain,
+            "hash": tx.hash,
+            "tab": title,
+            "data": lines,
+        }),
+    };
+    serde_json::to_string_pretty(&value).wrap_err("failed to serialize tab")
+}
+
+/// Write `json` to `path`, creating parent directories as needed.
+pub fn write_json(path: &Path, json: &str) -> Result<()> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent).wrap_err("failed to create export directory")?;
+    }
+    fs::write(path, json).wrap_err_with(|| format!("failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn tags_export_and_writes_pretty_json() -> Result<()> {
+        let addr = AddressRef {
+            label: "Treasury".into(),
+            address: "0xABCDEF0123456789abcdef0123456789abcdef01".into(),
+            chain: "Mainnet".into(),
+        };
+        let data = HydratedAddress {
+            identifier: addr.address.clone(),
+            info: vec!["Balance: 1 ETH".into()],
+            transactions: Vec::new(),
+            transactions_table: None,
+            internal: Vec::new(),
+            balances: Vec::new(),
+            permissions: Vec::new(),
+            overview: None,
+            contract_source: None,
+            creation: None,
+            failures: Vec::new(),
+        };
+        let export = ViewExport::address(&addr, Some("Treasury"), &data);
+        assert_eq!(
+            export.default_path(),
+            Path::new("exports").join("address_abcdef01.json")
+        );
+        let json = export.to_json()?;
+        let value: serde_json::Value = serde_json::from_str(&json)?;
+        assert_eq!(value["kind"], "address");
+        assert_eq!(value["chain"], "Mainnet");
+        assert_eq!(value["data"]["info"][0], "Balance: 1 ETH");
+        let tab: serde_json::Value =
+            serde_json::from_str(&export.tab_json(MainViewTab::AddressBalances)?)?;
+        assert_eq!(tab["tab"], "balances");
+        assert_eq!(tab["label"], "Treasury");
+        assert_eq!(tab["data"], serde_json::json!([]));
+
+        let dir = tempdir()?;
+        let path = dir.path().join("nested").join("view.json");
+        write_json(&path, &json)?;
+        assert_eq!(fs::read_to_string(path)?, json);
+        Ok(())
+    }
+}
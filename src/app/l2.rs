@@ -0,0 +1,395 @@
+use super::{anvil::normalize_url, chains::Rollup, providers::Rpc, stats::ApiStats};
+use alloy::{
+    primitives::{Address, B256, U160, U256, address},
+    providers::{DynProvider, Provider},
+    rpc::types::Log,
+    sol,
+    sol_types::SolEvent,
+};
+use serde::Serialize;
+use serde_json::Value;
+
+sol! {
+    /// ArbSys precompile.
+    interface IArbSys {
+        event L2ToL1Tx(address caller, address indexed destination, uint256 indexed hash, uint256 indexed position, uint256 arbBlockNum, uint256 ethBlockNum, uint256 timestamp, uint256 callvalue, bytes data);
+    }
+
+    #[sol(rpc)]
+    interface IArbRetryableTx {
+        function getTimeout(bytes32 ticketId) external view returns (uint256);
+    }
+
+    #[sol(rpc)]
+    interface IOutbox {
+        function isSpent(uint256 index) external view returns (bool);
+    }
+
+    /// OP Stack `L2ToL1MessagePasser` predeploy.
+    interface IL2ToL1MessagePasser {
+        event MessagePassed(uint256 indexed nonce, address indexed sender, address indexed target, uint256 value, uint256 gasLimit, bytes data, bytes32 withdrawalHash);
+    }
+
+    #[sol(rpc)]
+    interface IOptimismPortal {
+        function finalizedWithdrawals(bytes32 withdrawalHash) external view returns (bool);
+    }
+}
+
+const ARB_SYS: Address = address!("0x0000000000000000000000000000000000000064");
+const ARB_RETRYABLE_TX: Address = address!("0x000000000000000000000000000000000000006e");
+const MESSAGE_PASSER: Address = address!("0x4200000000000000000000000000000000000016");
+
+/// Offset both stacks add to an L1 contract's address when it calls into
+/// the L2, so it cannot pose as an L2 account with the same address.
+const ALIAS_OFFSET: U160 =
+    U160::from_limbs([0x0000_0000_0000_1111, 0x0000_0000_0000_0000, 0x1111_0000]);
+
+/// What a transaction is on its rollup, beyond the plain L1-shaped fields.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct L2Context {
+    pub rollup: Rollup,
+    pub origin: Origin,
+    /// L1 data fee charged on top of L2 execution (OP Stack receipts).
+    pub l1_fee: Option<U256>,
+    /// Gas spent on posting the transaction to L1: `l1GasUsed` on the OP
+    /// Stack, `gasUsedForL1` (in L2 gas) on Arbitrum.
+    pub l1_gas_used: Option<u64>,
+    /// L1 block the L2 block was built against (Arbitrum).
+    pub l1_block: Option<u64>,
+    pub withdrawals: Vec<Withdrawal>,
+}
+
+/// Where the transaction came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum Origin {
+    /// Signed and sent on the L2 itself.
+    L2,
+    /// OP Stack deposit (type 0x7e) derived from an L1 `OptimismPortal` call.
+    OpDeposit {
+        source_hash: B256,
+        /// ETH minted on L2 from the deposit.
+        mint: U256,
+        system: bool,
+    },
+    /// Arbitrum ETH deposit (type 0x64) from the L1 inbox.
+    ArbitrumDeposit { request_id: Option<B256> },
+    /// Arbitrum retryable ticket creation (type 0x69); the ticket id is this
+    /// transaction's hash.
+    RetryableSubmission {
+        request_id: Option<B256>,
+        retry_to: Option<Address>,
+        beneficiary: Option<Address>,
+        deposit_value: U256,
+        max_submission_fee: U256,
+        ticket: TicketStatus,
+    },
+    /// Arbitrum redemption of a retryable ticket (type 0x68).
+    RetryableRedeem { ticket_id: Option<B256> },
+    /// Another Arbitrum system type: unsigned or contract L1 messages, or
+    /// ArbOS internal bookkeeping.
+    ArbitrumSystem { kind: &'static str },
+}
+
+/// Whether a retryable ticket can still be redeemed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum TicketStatus {
+    /// Redeemable until this Unix time.
+    Live {
+        timeout: u64,
+    },
+    /// Redeemed, or expired without a redemption.
+    Gone,
+    Unknown(String),
+}
+
+/// An L2→L1 message the transaction sent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Withdrawal {
+    pub target: Address,
+    pub value: U256,
+    /// Withdrawal hash on the OP Stack, outbox position on Arbitrum.
+    pub id: U256,
+    pub status: WithdrawalStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum WithdrawalStatus {
+    /// Finalized on the portal, or executed from the outbox.
+    Executed,
+    /// Not executed on L1 yet: still in the challenge period, or waiting
+    /// for someone to prove and finalize (or execute) it.
+    Waiting,
+    Unknown(String),
+}
+
+impl Origin {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Origin::L2 => "Sent on L2",
+            Origin::OpDeposit { system: true, .. } => "L1 attributes deposit (system)",
+            Origin::OpDeposit { .. } => "L1→L2 deposit",
+            Origin::ArbitrumDeposit { .. } => "L1→L2 ETH deposit",
+            Origin::RetryableSubmission { .. } => "L1→L2 retryable ticket",
+            Origin::RetryableRedeem { .. } => "Retryable ticket redemption",
+            Origin::ArbitrumSystem { kind } => kind,
+        }
+    }
+}
+
+/// The L1 address behind an aliased L2 sender; deposits from L1 contracts
+/// arrive with the alias as `from`.
+pub fn unalias(address: Address) -> Address {
+    Address::from(U160::from_be_bytes(address.into_array()).wrapping_sub(ALIAS_OFFSET))
+}
+
+fn quantity(value: &Value) -> Option<U256> {
+    value.as_str()?.parse().ok()
+}
+
+fn small(value: &Value) -> Option<u64> {
+    quantity(value).and_then(|value| u64::try_from(value).ok())
+}
+
+fn field<T: serde::de::DeserializeOwned>(value: &Value) -> Option<T> {
+    serde_json::from_value(value.clone()).ok()
+}
+
+/// Read the rollup-specific fields of a transaction and its receipt, as the
+/// nodes return them; ticket and withdrawal statuses are left unknown.
+pub fn read(rollup: Rollup, tx: &Value, receipt: &Value) -> L2Context {
+    let kind = small(&tx["type"]).unwrap_or_default();
+    let origin = match (rollup, kind) {
+        (Rollup::OpStack { .. }, 0x7e) => Origin::OpDeposit {
+            source_hash: field(&tx["sourceHash"]).unwrap_or_default(),
+            mint: quantity(&tx["mint"]).unwrap_or_default(),
+            system: tx["isSystemTx"].as_bool().unwrap_or_default(),
+        },
+        (Rollup::Arbitrum { .. }, 0x64) => Origin::ArbitrumDeposit {
+            request_id: field(&tx["requestId"]),
+        },
+        (Rollup::Arbitrum { .. }, 0x65) => Origin::ArbitrumSystem {
+            kind: "Unsigned L1→L2 message",
+        },
+        (Rollup::Arbitrum { .. }, 0x66) => Origin::ArbitrumSystem {
+            kind: "L1 contract message",
+        },
+        (Rollup::Arbitrum { .. }, 0x68) => Origin::RetryableRedeem {
+            ticket_id: field(&tx["ticketId"]),
+        },
+        (Rollup::Arbitrum { .. }, 0x69) => Origin::RetryableSubmission {
+            request_id: field(&tx["requestId"]),
+            retry_to: field(&tx["retryTo"]),
+            beneficiary: field(&tx["beneficiary"]),
+            deposit_value: quantity(&tx["depositValue"]).unwrap_or_default(),
+            max_submission_fee: quantity(&tx["maxSubmissionFee"]).unwrap_or_default(),
+            ticket: TicketStatus::Unknown("not checked".into()),
+        },
+        (Rollup::Arbitrum { .. }, 0x6a) => Origin::ArbitrumSystem {
+            kind: "ArbOS internal",
+        },
+        _ => Origin::L2,
+    };
+    let logs: Vec<Log> = field(&receipt["logs"]).unwrap_or_default();
+    let withdrawals = logs
+        .iter()
+        .filter_map(|log| match rollup {
+            Rollup::Arbitrum { .. } if log.address() == ARB_SYS => {
+                let event = IArbSys::L2ToL1Tx::decode_log_data(log.data()).ok()?;
+                Some(Withdrawal {
+                    target: event.destination,
+                    value: event.callvalue,
+                    id: event.position,
+                    status: WithdrawalStatus::Unknown("not checked".into()),
+                })
+            }
+            Rollup::OpStack { .. } if log.address() == MESSAGE_PASSER => {
+                let event =
+                    IL2ToL1MessagePasser::MessagePassed::decode_log_data(log.data()).ok()?;
+                Some(Withdrawal {
+                    target: event.target,
+                    value: event.value,
+                    id: event.withdrawalHash.into(),
+                    status: WithdrawalStatus::Unknown("not checked".into()),
+                })
+            }
+            _ => None,
+        })
+        .collect();
+    let (l1_gas_used, l1_block) = match rollup {
+        Rollup::Arbitrum { .. } => (
+            small(&receipt["gasUsedForL1"]),
+            small(&receipt["l1BlockNumber"]),
+        ),
+        Rollup::OpStack { .. } => (small(&receipt["l1GasUsed"]), None),
+    };
+    L2Context {
+        rollup,
+        origin,
+        l1_fee: quantity(&receipt["l1Fee"]),
+        l1_gas_used,
+        l1_block,
+        withdrawals,
+    }
+}
+
+async fn raw(
+    stats: &ApiStats,
+    provider: &DynProvider,
+    method: &'static str,
+    hash: B256,
+) -> Result<Value, String> {
+    stats.record_api_call("rpc");
+    provider
+        .raw_request(method.into(), (hash,))
+        .await
+        .map_err(|err| format!("{method} failed: {err}"))
+}
+
+/// Rollup context of `hash`, with its retryable ticket checked on the L2
+/// and its withdrawals checked on Mainnet when `l1_rpc_url` is set.
+pub async fn fetch_l2_context(
+    rpc: &Rpc,
+    rpc_url: &str,
+    l1_rpc_url: Option<&str>,
+    rollup: Rollup,
+    hash: &str,
+) -> Result<Option<L2Context>, String> {
+    let tx_hash: B256 = hash
+        .parse()
+        .map_err(|_| "invalid transaction hash".to_string())?;
+    // Raw JSON: the rollup transaction types do not decode as Ethereum ones.
+    let provider = rpc
+        .connect(&normalize_url(rpc, rpc_url))
+        .await
+        .map_err(|err| format!("{err:#}"))?;
+    let tx = raw(rpc.stats(), &provider, "eth_getTransactionByHash", tx_hash).await?;
+    if tx.is_null() {
+        return Ok(None);
+    }
+    let receipt = raw(rpc.stats(), &provider, "eth_getTransactionReceipt", tx_hash).await?;
+    let mut context = read(rollup, &tx, &receipt);
+
+    if let Origin::RetryableSubmission { ticket, .. } = &mut context.origin {
+        rpc.stats().record_api_call("rpc");
+        let retryables = IArbRetryableTx::new(ARB_RETRYABLE_TX, &provider);
+        *ticket = match retryables.getTimeout(tx_hash).call().await {
+            Ok(timeout) => TicketStatus::Live {
+                timeout: timeout.try_into().unwrap_or(u64::MAX),
+            },
+            Err(err) if err.as_revert_data().is_some() => TicketStatus::Gone,
+            Err(err) => TicketStatus::Unknown(err.to_string()),
+        };
+    }
+
+    if context.withdrawals.is_empty() {
+        return Ok(Some(context));
+    }
+    let Some(l1_rpc_url) = l1_rpc_url else {
+        for withdrawal in &mut context.withdrawals {
+            withdrawal.status =
+                WithdrawalStatus::Unknown("configure a Mainnet RPC to check L1".into());
+        }
+        return Ok(Some(context));
+    };
+    let l1 = rpc
+        .connect(&normalize_url(rpc, l1_rpc_url))
+        .await
+        .map_err(|err| format!("{err:#}"))?;
+    for withdrawal in &mut context.withdrawals {
+        rpc.stats().record_api_call("rpc");
+        let executed = match rollup {
+            Rollup::Arbitrum { outbox } => {
+                IOutbox::new(outbox, &l1)
+                    .isSpent(withdrawal.id)
+                    .call()
+                    .await
+            }
+            Rollup::OpStack { portal } => {
+                IOptimismPortal::new(portal, &l1)
+                    .finalizedWithdrawals(withdrawal.id.into())
+                    .call()
+                    .await
+            }
+        };
+        withdrawal.status = match executed {
+            Ok(true) => WithdrawalStatus::Executed,
+            Ok(false) => WithdrawalStatus::Waiting,
+            Err(err) => WithdrawalStatus::Unknown(err.to_string()),
+        };
+    }
+    Ok(Some(context))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const PORTAL: Rollup = Rollup::OpStack {
+        portal: Address::ZERO,
+    };
+    const OUTBOX: Rollup = Rollup::Arbitrum {
+        outbox: Address::ZERO,
+    };
+
+    #[test]
+    fn reads_deposits_retryables_and_withdrawals() {
+        let deposit = json!({"type": "0x7e",
+            "sourceHash": "0x1111111111111111111111111111111111111111111111111111111111111111",
+            "mint": "0xde0b6b3a7640000", "isSystemTx": false});
+        let receipt = json!({"logs": [], "l1Fee": null});
+        let context = read(PORTAL, &deposit, &receipt);
+        assert_eq!(
+            context.origin,
+            Origin::OpDeposit {
+                source_hash: B256::repeat_byte(0x11),
+                mint: U256::from(1_000_000_000_000_000_000u128),
+                system: false,
+            }
+        );
+        // The same type means nothing on Arbitrum.
+        assert_eq!(read(OUTBOX, &deposit, &receipt).origin, Origin::L2);
+
+        let ticket = json!({"type": "0x69", "depositValue": "0x64", "maxSubmissionFee": "0x5",
+            "retryTo": "0x0000000000000000000000000000000000000009"});
+        let receipt = json!({"logs": [], "gasUsedForL1": "0x2a", "l1BlockNumber": "0x10"});
+        let context = read(OUTBOX, &ticket, &receipt);
+        assert!(matches!(
+            context.origin,
+            Origin::RetryableSubmission { deposit_value, retry_to: Some(_), .. }
+                if deposit_value == U256::from(100)
+        ));
+        assert_eq!(
+            (context.l1_gas_used, context.l1_block),
+            (Some(42), Some(16))
+        );
+
+        let event = IL2ToL1MessagePasser::MessagePassed {
+            nonce: U256::from(1),
+            sender: Address::repeat_byte(1),
+            target: Address::repeat_byte(2),
+            value: U256::from(7),
+            gasLimit: U256::from(100_000),
+            data: Default::default(),
+            withdrawalHash: B256::repeat_byte(0x22),
+        };
+        let data = event.encode_log_data();
+        let receipt = json!({"l1Fee": "0x3e8", "l1GasUsed": "0x640", "logs": [{
+            "address": MESSAGE_PASSER, "topics": data.topics(), "data": data.data,
+            "blockHash": null, "blockNumber": null, "transactionHash": null,
+            "transactionIndex": null, "logIndex": null, "removed": false}]});
+        let context = read(PORTAL, &json!({"type": "0x2"}), &receipt);
+        assert_eq!(context.origin, Origin::L2);
+        assert_eq!(context.l1_fee, Some(U256::from(1000)));
+        assert_eq!(context.l1_gas_used, Some(1600));
+        assert_eq!(context.withdrawals.len(), 1);
+        assert_eq!(context.withdrawals[0].target, Address::repeat_byte(2));
+        assert_eq!(context.withdrawals[0].id, U256::from_be_bytes([0x22; 32]));
+
+        assert_eq!(
+            unalias(address!("0x7300000000000000000000000000000000001a2b")),
+            address!("0x61ef00000000000000000000000000000000091a")
+        );
+    }
+}
@@ -0,0 +1,297 @@
+use super::chains::{CHAINS, resolve_chain};
+use crate::ui::util::short_hex;
+use alloy::primitives::{Address, address};
+use serde::{Deserialize, Serialize};
+
+/// A well-known address shipped with the app. `chain_id: None` applies to
+/// contracts deployed at the same address on every chain.
+struct BuiltinLabel {
+    chain_id: Option<u64>,
+    address: Address,
+    name: &'static str,
+    tags: &'static [&'static str],
+}
+
+const fn builtin(
+    chain_id: Option<u64>,
+    address: Address,
+    name: &'static str,
+    tags: &'static [&'static str],
+) -> BuiltinLabel {
+    BuiltinLabel {
+        chain_id,
+        address,
+        name,
+        tags,
+    }
+}
+
+const MAINNET: Option<u64> = Some(1);
+const ARBITRUM: Option<u64> = Some(42161);
+const BASE: Option<u64> = Some(8453);
+const ANY: Option<u64> = None;
+
+#[rustfmt::skip]
+const BUILTIN: &[BuiltinLabel] = &[
+    builtin(ANY, address!("0xcA11bde05977b3631167028862bE2a173976CA11"), "Multicall3", &["infra"]),
+    builtin(ANY, address!("0x000000000022D473030F116dDEE9F6B43aC78BA3"), "Uniswap: Permit2", &["infra"]),
+    builtin(MAINNET, address!("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"), "WETH", &["token"]),
+    builtin(MAINNET, address!("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"), "USDC", &["token", "stablecoin"]),
+    builtin(MAINNET, address!("0xdAC17F958D2ee523a2206206994597C13D831ec7"), "USDT", &["token", "stablecoin"]),
+    builtin(MAINNET, address!("0x6B175474E89094C44Da98b954EedeAC495271d0F"), "DAI", &["token", "stablecoin"]),
+    builtin(MAINNET, address!("0x2260FAC5E5542a773Aa44fBCfeDf7C193bc2C599"), "WBTC", &["token"]),
+    builtin(MAINNET, address!("0xae7ab96520DE3A18E5e111B5EaAb095312D7fE84"), "Lido: stETH", &["token", "staking"]),
+    builtin(MAINNET, address!("0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D"), "Uniswap V2: Router 2", &["dex"]),
+    builtin(MAINNET, address!("0x5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f"), "Uniswap V2: Factory", &["dex"]),
+    builtin(MAINNET, address!("0xE592427A0AEce92De3Edee1F18E0157C05861564"), "Uniswap V3: Router", &["dex"]),
+    builtin(MAINNET, address!("0x68b3465833fb72A70ecDF485E0e4C7bD8665Fc45"), "Uniswap V3: Router 2", &["dex"]),
+    builtin(MAINNET, address!("0x1F98431c8aD98523631AE4a59f267346ea31F984"), "Uniswap V3: Factory", &["dex"]),
+    builtin(MAINNET, address!("0x3fC91A3afd70395Cd496C647d5a6CC9D4B2b7FAD"), "Uniswap: Universal Router", &["dex"]),
+    builtin(MAINNET, address!("0x1111111254EEB25477B68fb85Ed929f73A960582"), "1inch v5: Aggregation Router", &["dex"]),
+    builtin(MAINNET, address!("0xDef1C0ded9bec7F1a1670819833240f027b25EfF"), "0x: Exchange Proxy", &["dex"]),
+    builtin(MAINNET, address!("0x87870Bca3F3fD6335C3F4ce8392D69350B4fA4E2"), "Aave V3: Pool", &["lending"]),
+    builtin(MAINNET, address!("0x00000000219ab540356cBB839Cbe05303d7705Fa"), "Beacon Deposit Contract", &["staking"]),
+    builtin(MAINNET, address!("0x00000000000C2E074eC69A0dFb2997BA6C7d2e1e"), "ENS: Registry", &["infra"]),
+    builtin(MAINNET, address!("0xd9Db270c1B5E3Bd161E8c8503c55cEABeE709552"), "Safe: Singleton 1.3.0", &["wallet"]),
+    builtin(MAINNET, address!("0x99C9fc46f92E8a1c0deC1b1747d010903E884bE1"), "Optimism: L1 Standard Bridge", &["bridge"]),
+    builtin(MAINNET, address!("0x3154Cf16ccdb4C6d922629664174b904d80F2C35"), "Base: L1 Standard Bridge", &["bridge"]),
+    builtin(MAINNET, address!("0x72Ce9c846789fdB6fC1f34aC4AD25Dd9ef7031ef"), "Arbitrum: L1 Gateway Router", &["bridge"]),
+    builtin(MAINNET, address!("0x28C6c06298d514Db089934071355E5743bf21d60"), "Binance 14", &["exchange"]),
+    builtin(MAINNET, address!("0xA9D1e08C7793af67e9d92fe308d5697FB81d3E43"), "Coinbase 10", &["exchange"]),
+    builtin(ARBITRUM, address!("0x82aF49447D8a07e3bd95BD0d56f35241523fBab1"), "WETH", &["token"]),
+    builtin(ARBITRUM, address!("0xaf88d065e77c8cC2239327C5EDb3A432268e5831"), "USDC", &["token", "stablecoin"]),
+    builtin(BASE, address!("0x833589fCD6eDb6E08f4c7C32D4f71b54bdA02913"), "USDC", &["token", "stablecoin"]),
+    // Predeploy shared by OP Stack chains (Base, Zora, Mode, Ink).
+    builtin(ANY, address!("0x4200000000000000000000000000000000000006"), "WETH (OP Stack)", &["token"]),
+];
+
+/// A label the user attached to an address on one chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserLabel {
+    pub chain: String,
+    pub address: Address,
+    pub name: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// A resolved label, from the user's labels or the bundled registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Label<'a> {
+    pub name: &'a str,
+    pub tags: LabelTags<'a>,
+    pub user: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelTags<'a> {
+    Builtin(&'static [&'static str]),
+    User(&'a [String]),
+}
+
+impl LabelTags<'_> {
+    pub fn joined(&self) -> String {
+        match self {
+            LabelTags::Builtin(tags) => tags.join(", "),
+            LabelTags::User(tags) => tags.join(", "),
+        }
+    }
+}
+
+fn same_chain(a: &str, b: &str) -> bool {
+    match (resolve_chain(a), resolve_chain(b)) {
+        (Some(a), Some(b)) => a.chain_id == b.chain_id,
+        _ => a.trim().eq_ignore_ascii_case(b.trim()),
+    }
+}
+
+/// Bundled labels plus the user's own, which take precedence.
+#[derive(Debug, Clone, Default)]
+pub struct LabelRegistry {
+    pub user: Vec<UserLabel>,
+    /// Contracts matched to local build artifacts this session; ranked
+    /// below the user's labels and above the bundled ones.
+    pub local: Vec<UserLabel>,
+    /// Label text being typed while the prompt is open.
+    pub editing: Option<String>,
+}
+
+impl LabelRegistry {
+    pub fn new(user: Vec<UserLabel>) -> Self {
+        Self {
+            user,
+            local: Vec::new(),
+            editing: None,
+        }
+    }
+
+    /// Prompt text that recreates the user's label for `address`.
+    pub fn prompt_for(&self, chain: &str, address: &str) -> String {
+        match self.lookup(chain, address) {
+            Some(Label {
+                name,
+                tags: LabelTags::User(tags),
+                ..
+            }) => std::iter::once(name.to_string())
+                .chain(tags.iter().map(|tag| format!("#{tag}")))
+                .collect::<Vec<_>>()
+                .join(" "),
+            _ => String::new(),
+        }
+    }
+
+    pub fn lookup(&self, chain: &str, address: &str) -> Option<Label<'_>> {
+        let address: Address = address.trim().parse().ok()?;
+        if let Some(label) = self
+            .user
+            .iter()
+            .find(|label| label.address == address && same_chain(&label.chain, chain))
+        {
+            return Some(Label {
+                name: &label.name,
+                tags: LabelTags::User(&label.tags),
+                user: true,
+            });
+        }
+        if let Some(label) = self
+            .local
+            .iter()
+            .find(|label| label.address == address && same_chain(&label.chain, chain))
+        {
+            return Some(Label {
+                name: &label.name,
+                tags: LabelTags::Builtin(&["local"]),
+                user: false,
+            });
+        }
+        let chain_id = resolve_chain(chain).map(|info| info.chain_id);
+        BUILTIN
+            .iter()
+            .find(|label| {
+                label.address == address && (label.chain_id.is_none() || label.chain_id == chain_id)
+            })
+            .map(|label| Label {
+                name: label.name,
+                tags: LabelTags::Builtin(label.tags),
+                user: false,
+            })
+    }
+
+    /// The label's name, or the shortened address when there is none.
+    pub fn display(&self, chain: &str, address: &str) -> String {
+        self.lookup(chain, address)
+            .map(|label| label.name.to_string())
+            .unwrap_or_else(|| short_hex(address))
+    }
+
+    /// Set or replace the user's label for `address`; `None` removes it.
+    /// Returns whether anything changed.
+    pub fn set(
+        &mut self,
+        chain: &str,
+        address: Address,
+        label: Option<(String, Vec<String>)>,
+    ) -> bool {
+        let before = self.user.len();
+        self.user.retain(|existing| {
+            !(existing.address == address && same_chain(&existing.chain, chain))
+        });
+        let removed = self.user.len() != before;
+        match label {
+            Some((name, tags)) => {
+                self.user.push(UserLabel {
+                    chain: chain.to_string(),
+                    address,
+                    name,
+                    tags,
+                });
+                true
+            }
+            None => removed,
+        }
+    }
+
+    /// Label `address` with the name of the local contract it matched.
+    pub fn set_local(&mut self, chain: &str, address: Address, name: &str) {
+        self.local.retain(|existing| {
+            !(existing.address == address && same_chain(&existing.chain, chain))
+        });
+        self.local.push(UserLabel {
+            chain: chain.to_string(),
+            address,
+            name: name.to_string(),
+            tags: vec!["local".into()],
+        });
+    }
+}
+
+/// The bundled registry as plain labels, for searching by name. Labels
+/// shared by every chain are listed once, on Mainnet.
+pub fn builtin_labels() -> Vec<UserLabel> {
+    BUILTIN
+        .iter()
+        .map(|label| {
+            let chain_id = label.chain_id.unwrap_or(1);
+            let chain = CHAINS
+                .iter()
+                .find(|info| info.chain_id == chain_id)
+                .map_or("Mainnet", |info| info.name);
+            UserLabel {
+                chain: chain.to_string(),
+                address: label.address,
+                name: label.name.to_string(),
+                tags: label.tags.iter().map(|tag| tag.to_string()).collect(),
+            }
+        })
+        .collect()
+}
+
+/// Parse the label prompt: a name followed by optional `#tag` words. An empty
+/// prompt clears the label.
+pub fn parse_label(input: &str) -> Option<(String, Vec<String>)> {
+    let (tags, words): (Vec<&str>, Vec<&str>) = input
+        .split_whitespace()
+        .partition(|word| word.starts_with('#') && word.len() > 1);
+    let name = words.join(" ");
+    if name.is_empty() {
+        return None;
+    }
+    let tags = tags
+        .iter()
+        .map(|tag| tag.trim_start_matches('#').to_ascii_lowercase())
+        .collect();
+    Some((name, tags))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_labels_override_builtin_per_chain() {
+        let mut registry = LabelRegistry::default();
+        let router = "0xE592427A0AEce92De3Edee1F18E0157C05861564";
+        assert_eq!(registry.display("Mainnet", router), "Uniswap V3: Router");
+        assert_eq!(registry.lookup("Sepolia", router), None);
+        assert_eq!(
+            registry
+                .lookup("base", "0xcA11bde05977b3631167028862bE2a173976CA11")
+                .map(|label| label.name),
+            Some("Multicall3")
+        );
+
+        let parsed = parse_label("My router #dex #Mine").unwrap();
+        assert_eq!(
+            parsed,
+            ("My router".to_string(), vec!["dex".into(), "mine".into()])
+        );
+        assert!(registry.set("ethereum", router.parse().unwrap(), Some(parsed)));
+        let label = registry.lookup("Mainnet", router).unwrap();
+        assert_eq!((label.name, label.user), ("My router", true));
+        assert_eq!(label.tags.joined(), "dex, mine");
+
+        assert_eq!(parse_label("  #only-tags "), None);
+        assert!(registry.set("Mainnet", router.parse().unwrap(), None));
+        assert_eq!(registry.display("Mainnet", router), "Uniswap V3: Router");
+    }
+}
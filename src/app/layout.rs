@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+const MIN_SIDEBAR_WIDTH: u16 = 20;
+const MAX_SIDEBAR_WIDTH: u16 = 80;
+/// Columns added or removed by one `Ctrl+Left`/`Ctrl+Right`.
+const RESIZE_STEP: u16 = 4;
+
+/// Pane sizes the user picked, stored under the `view.layout` setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PaneLayout {
+    pub sidebar_width: u16,
+    /// Hidden with `Ctrl+B`, leaving the main view the full width.
+    pub sidebar_hidden: bool,
+}
+
+impl Default for PaneLayout {
+    fn default() -> Self {
+        Self {
+            sidebar_width: 32,
+            sidebar_hidden: false,
+        }
+    }
+}
+
+impl PaneLayout {
+    /// Grow (`wider`) or shrink the sidebar one step, showing it if hidden.
+    pub fn resize_sidebar(&mut self, wider: bool) {
+        let width = if wider {
+            self.sidebar_width.saturating_add(RESIZE_STEP)
+        } else {
+            self.sidebar_width.saturating_sub(RESIZE_STEP)
+        };
+        self.sidebar_width = width.clamp(MIN_SIDEBAR_WIDTH, MAX_SIDEBAR_WIDTH);
+        self.sidebar_hidden = false;
+    }
+
+    /// Columns the sidebar takes in a `total` columns wide terminal, leaving
+    /// the main view at least as wide as the smallest sidebar.
+    pub fn sidebar_columns(&self, total: u16) -> u16 {
+        if self.sidebar_hidden {
+            return 0;
+        }
+        self.sidebar_width
+            .clamp(MIN_SIDEBAR_WIDTH, MAX_SIDEBAR_WIDTH)
+            .min(total.saturating_sub(MIN_SIDEBAR_WIDTH))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sidebar_resizes_within_bounds() {
+        let mut layout = PaneLayout::default();
+        layout.resize_sidebar(true);
+        assert_eq!(layout.sidebar_width, 36);
+        for _ in 0..20 {
+            layout.resize_sidebar(false);
+        }
+        assert_eq!(layout.sidebar_width, MIN_SIDEBAR_WIDTH);
+
+        layout.sidebar_hidden = true;
+        assert_eq!(layout.sidebar_columns(120), 0);
+        layout.resize_sidebar(true);
+        assert!(!layout.sidebar_hidden);
+        assert_eq!(layout.sidebar_columns(120), 24);
+        assert_eq!(layout.sidebar_columns(30), 10);
+
+        // Hand-edited widths are clamped when drawn.
+        let stored: PaneLayout = serde_json::from_str(r#"{"sidebar_width":500}"#).unwrap();
+        assert_eq!(stored.sidebar_columns(300), MAX_SIDEBAR_WIDTH);
+    }
+}
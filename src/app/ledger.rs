@@ -0,0 +1,461 @@
+use super::{etherscan::build_client, fetch_limits::FetchLimits, stats::ApiStats};
+use alloy::{
+    consensus::{SignableTransaction, TxEip1559},
+    primitives::{Address, Signature, TxKind, hex, normalize_v},
+    signers::ledger::{
+        HDPath,
+        coins_ledger::{
+            common::{APDUCommand, APDUData},
+            transports::{Ledger, LedgerAsync},
+        },
+    },
+};
+use serde::Deserialize;
+use std::{collections::BTreeMap, fmt, sync::Arc};
+use tokio::sync::Mutex;
+
+/// Ledger's crypto asset list, which serves the signed descriptors the
+/// Ethereum app needs before it decodes token and dApp calls.
+const CAL_BASE: &str = "https://crypto-assets-service.api.ledger.com/v1";
+
+const INS_GET_PUBLIC_KEY: u8 = 0x02;
+const INS_SIGN: u8 = 0x04;
+const INS_PROVIDE_ERC20_TOKEN_INFORMATION: u8 = 0x0a;
+const INS_SET_EXTERNAL_PLUGIN: u8 = 0x12;
+const P1_FIRST_CHUNK: u8 = 0x00;
+const P1_MORE_CHUNKS: u8 = 0x80;
+const STATUS_OK: u16 = 0x9000;
+
+/// `transfer(address,uint256)` and `approve(address,uint256)`, which the
+/// app shows with the token's ticker and decimals once it has them.
+const ERC20_TRANSFER: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+const ERC20_APPROVE: [u8; 4] = [0x09, 0x5e, 0xa7, 0xb3];
+
+/// What the Ethereum app shows for a transaction, with the signed metadata
+/// it is given first.
+#[derive(Clone, PartialEq, Eq)]
+pub enum ClearSigning {
+    /// A plain transfer: the app shows the recipient and amount itself.
+    Transfer,
+    /// An ERC-20 transfer or approval, shown with the amount in the token's
+    /// units and the recipient or spender.
+    Token { ticker: String, descriptor: Vec<u8> },
+    /// A call the named Ledger plugin app decodes.
+    Plugin { name: String, payload: Vec<u8> },
+    /// Ledger has no metadata for this call: the app shows only the signing
+    /// hash and needs blind signing enabled.
+    Blind,
+}
+
+impl fmt::Debug for ClearSigning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.describe())
+    }
+}
+
+impl ClearSigning {
+    pub fn is_blind(&self) -> bool {
+        matches!(self, ClearSigning::Blind)
+    }
+
+    /// What to check on the device before approving.
+    pub fn describe(&self) -> String {
+        match self {
+            ClearSigning::Transfer => {
+                "Clear-signed: the Ledger shows the recipient and amount".into()
+            }
+            ClearSigning::Token { ticker, .. } => format!(
+                "Clear-signed: the Ledger shows the {ticker} amount and the recipient or spender"
+            ),
+            ClearSigning::Plugin { name, .. } => {
+                format!("Clear-signed: the Ledger's {name} plugin decodes the call")
+            }
+            ClearSigning::Blind => {
+                "Blind signing: Ledger has no metadata for this call, so the device shows only \
+                 the signing hash"
+                    .into()
+            }
+        }
+    }
+}
+
+/// Look up what lets the app decode `tx`: Ledger's signed descriptor for a
+/// dApp plugin handling the call, else for the token of an ERC-20 transfer
+/// or approval. Calls without one, or when the list cannot be reached, are
+/// blind-signed.
+pub async fn clear_signing(stats: &ApiStats, tx: &TxEip1559) -> ClearSigning {
+    let TxKind::Call(to) = tx.to else {
+        return ClearSigning::Blind;
+    };
+    if tx.input.is_empty() {
+        return ClearSigning::Transfer;
+    }
+    let Some(selector) = tx.input.get(..4) else {
+        return ClearSigning::Blind;
+    };
+    let Ok(client) = build_client(FetchLimits::default().explorer_timeout()) else {
+        return ClearSigning::Blind;
+    };
+    let contract = format!("{to:#x}");
+    let chain_id = tx.chain_id.to_string();
+    stats.record_api_call("ledger");
+    let dapps = client
+        .get(format!("{CAL_BASE}/dapps"))
+        .query(&[
+            ("output", "b2c_signatures"),
+            ("chain_id", chain_id.as_str()),
+            ("contracts", contract.as_str()),
+        ])
+        .send()
+        .await;
+    if let Ok(response) = dapps
+        && let Ok(dapps) = response.json::<Vec<RawDapp>>().await
+        && let Some(plugin) = plugin_for(&dapps, &to, selector)
+    {
+        return plugin;
+    }
+    if selector != ERC20_TRANSFER && selector != ERC20_APPROVE {
+        return ClearSigning::Blind;
+    }
+    stats.record_api_call("ledger");
+    let tokens = client
+        .get(format!("{CAL_BASE}/tokens"))
+        .query(&[
+            ("output", "ticker,decimals,live_signature"),
+            ("chain_id", chain_id.as_str()),
+            ("contract_address", contract.as_str()),
+        ])
+        .send()
+        .await;
+    match tokens {
+        Ok(response) => match response.json::<Vec<RawToken>>().await {
+            Ok(tokens) => tokens
+                .first()
+                .and_then(|token| token_descriptor(token, &to, tx.chain_id))
+                .unwrap_or(ClearSigning::Blind),
+            Err(_) => ClearSigning::Blind,
+        },
+        Err(_) => ClearSigning::Blind,
+    }
+}
+
+/// The `SET_EXTERNAL_PLUGIN` payload Ledger signed for `selector` on `to`.
+fn plugin_for(dapps: &[RawDapp], to: &Address, selector: &[u8]) -> Option<ClearSigning> {
+    let contract = format!("{to:#x}");
+    let selector = format!("0x{}", hex::encode(selector));
+    dapps.iter().find_map(|dapp| {
+        let (_, methods) = dapp
+            .b2c_signatures
+            .iter()
+            .find(|(address, _)| address.eq_ignore_ascii_case(&contract))?;
+        let (_, method) = methods
+            .iter()
+            .find(|(method, _)| method.eq_ignore_ascii_case(&selector))?;
+        let mut payload = hex::decode(&method.serialized_data).ok()?;
+        payload.extend(hex::decode(&method.signature).ok()?);
+        Some(ClearSigning::Plugin {
+            name: method.plugin.clone(),
+            payload,
+        })
+    })
+}
+
+/// `PROVIDE_ERC20_TOKEN_INFORMATION` data: the ticker with its length, the
+/// contract, decimals and chain id as big-endian `u32`s, then Ledger's
+/// signature over all of it.
+fn token_descriptor(token: &RawToken, to: &Address, chain_id: u64) -> Option<ClearSigning> {
+    let ticker = token.ticker.as_bytes();
+    let signature = hex::decode(token.live_signature.as_deref()?).ok()?;
+    let mut descriptor = vec![u8::try_from(ticker.len()).ok()?];
+    descriptor.extend(ticker);
+    descriptor.extend(to.as_slice());
+    descriptor.extend(token.decimals.to_be_bytes());
+    descriptor.extend(u32::try_from(chain_id).ok()?.to_be_bytes());
+    descriptor.extend(signature);
+    Some(ClearSigning::Token {
+        ticker: token.ticker.clone(),
+        descriptor,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct RawDapp {
+    #[serde(default)]
+    b2c_signatures: BTreeMap<String, BTreeMap<String, RawPluginMethod>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawPluginMethod {
+    plugin: String,
+    serialized_data: String,
+    signature: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawToken {
+    ticker: String,
+    decimals: u32,
+    #[serde(default)]
+    live_signature: Option<String>,
+}
+
+/// A connected Ethereum app account. The key never leaves the device.
+#[derive(Debug, Clone)]
+pub struct LedgerWallet {
+    transport: Arc<Mutex<Ledger>>,
+    path: HDPath,
+    address: Address,
+}
+
+impl LedgerWallet {
+    /// Open the device and show the account at the Ledger Live `index` for
+    /// the user to approve there.
+    pub async fn connect(index: usize) -> Result<Self, String> {
+        let transport = Ledger::init().await.map_err(|err| {
+            format!("cannot open the Ledger (unlocked, Ethereum app open?): {err}")
+        })?;
+        let path = HDPath::LedgerLive(index);
+        let address = verify_address(&transport, &path).await?;
+        Ok(Self {
+            transport: Arc::new(Mutex::new(transport)),
+            path,
+            address,
+        })
+    }
+
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Hand the app the metadata `clear` carries, then the transaction, and
+    /// wait for the user to approve it on the device.
+    pub async fn sign(&self, tx: &TxEip1559, clear: &ClearSigning) -> Result<Signature, String> {
+        let transport = self.transport.lock().await;
+        let metadata = match clear {
+            ClearSigning::Token { descriptor, .. } => {
+                Some((INS_PROVIDE_ERC20_TOKEN_INFORMATION, descriptor))
+            }
+            ClearSigning::Plugin { payload, .. } => Some((INS_SET_EXTERNAL_PLUGIN, payload)),
+            ClearSigning::Transfer | ClearSigning::Blind => None,
+        };
+        if let Some((ins, data)) = metadata {
+            let answer = exchange(&transport, ins, P1_FIRST_CHUNK, data).await?;
+            if answer != STATUS_OK {
+                return Err(format!(
+                    "the Ledger refused the clear-signing metadata (status {answer:#06x}); \
+                     update the Ethereum app or install the plugin"
+                ));
+            }
+        }
+        let mut payload = path_bytes(&self.path);
+        payload.extend(tx.encoded_for_signing());
+        let mut response = Vec::new();
+        for (position, chunk) in sign_chunks(&payload).enumerate() {
+            let p1 = if position == 0 {
+                P1_FIRST_CHUNK
+            } else {
+                P1_MORE_CHUNKS
+            };
+            let command = command(INS_SIGN, p1, chunk);
+            let answer = transport
+                .exchange(&command)
+                .await
+                .map_err(|err| format!("Ledger signing failed: {err}"))?;
+            if answer.retcode() != STATUS_OK {
+                return Err(rejection(answer.retcode()));
+            }
+            response = answer.data().unwrap_or_default().to_vec();
+        }
+        parse_signature(&response)
+    }
+}
+
+fn command(ins: u8, p1: u8, data: &[u8]) -> APDUCommand {
+    APDUCommand {
+        cla: 0xe0,
+        ins,
+        p1,
+        p2: 0x00,
+        data: APDUData::new(data),
+        response_len: None,
+    }
+}
+
+/// Send one APDU and return its status word.
+async fn exchange(transport: &Ledger, ins: u8, p1: u8, data: &[u8]) -> Result<u16, String> {
+    transport
+        .exchange(&command(ins, p1, data))
+        .await
+        .map(|answer| answer.retcode())
+        .map_err(|err| format!("Ledger request failed: {err}"))
+}
+
+fn rejection(status: u16) -> String {
+    match status {
+        0x6985 => "the transaction was rejected on the Ledger".into(),
+        0x6a80 => "the Ledger needs blind signing enabled in its Ethereum app settings".into(),
+        status => format!("the Ledger refused to sign (status {status:#06x})"),
+    }
+}
+
+/// BIP-32 path as the Ethereum app takes it: the depth, then each index
+/// big-endian with the hardened bit set where marked.
+fn path_bytes(path: &HDPath) -> Vec<u8> {
+    let path = path.to_string();
+    let elements: Vec<&str> = path.split('/').skip(1).collect();
+    let mut bytes = vec![elements.len() as u8];
+    for element in elements {
+        let index = element
+            .trim_end_matches('\'')
+            .parse::<u32>()
+            .unwrap_or_default();
+        let hardened = if element.ends_with('\'') {
+            0x8000_0000
+        } else {
+            0
+        };
+        bytes.extend((index | hardened).to_be_bytes());
+    }
+    bytes
+}
+
+/// The signing payload in APDU-sized chunks. A chunk size that leaves a
+/// final chunk of exactly three bytes trips the app, so it is avoided.
+fn sign_chunks(payload: &[u8]) -> std::slice::Chunks<'_, u8> {
+    let size = (1..=255)
+        .rev()
+        .find(|size| payload.len() % size != 3)
+        .unwrap_or(255);
+    payload.chunks(size)
+}
+
+/// `v`, `r` and `s` as the app answers a signing request.
+fn parse_signature(response: &[u8]) -> Result<Signature, String> {
+    let [v, rs @ ..] = response else {
+        return Err("the Ledger returned no signature".into());
+    };
+    if rs.len() != 64 {
+        return Err(format!(
+            "unexpected Ledger signature of {} bytes",
+            response.len()
+        ));
+    }
+    let parity = normalize_v(u64::from(*v))
+        .ok_or_else(|| format!("unexpected Ledger signature parity {v}"))?;
+    Ok(Signature::from_bytes_and_parity(rs, parity))
+}
+
+/// Show the account's address on the Ledger and wait for the user to
+/// approve it there (`GET_PUBLIC_KEY` with confirmation).
+async fn verify_address(transport: &Ledger, path: &HDPath) -> Result<Address, String> {
+    let answer = transport
+        .exchange(&command(INS_GET_PUBLIC_KEY, 0x01, &path_bytes(path)))
+        .await
+        .map_err(|err| format!("Ledger address check failed: {err}"))?;
+    if answer.retcode() != STATUS_OK {
+        return Err(format!(
+            "address was rejected on the Ledger (status {:#06x})",
+            answer.retcode()
+        ));
+    }
+    // Public key length and key, then address length and ASCII hex address.
+    let data = answer.data().unwrap_or_default();
+    let offset = 1 + usize::from(*data.first().unwrap_or(&0));
+    data.get(offset + 1..offset + 1 + usize::from(*data.get(offset).unwrap_or(&0)))
+        .and_then(|hex| std::str::from_utf8(hex).ok())
+        .and_then(|hex| format!("0x{hex}").parse().ok())
+        .ok_or_else(|| "unexpected Ledger address response".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{Bytes, U256, address};
+    use serde_json::json;
+
+    #[test]
+    fn encodes_ledger_live_paths() {
+        assert_eq!(
+            path_bytes(&HDPath::LedgerLive(1)),
+            [
+                5, 0x80, 0, 0, 44, 0x80, 0, 0, 60, 0x80, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0
+            ]
+        );
+    }
+
+    #[test]
+    fn builds_token_descriptor_and_finds_plugin_payloads() {
+        let usdc = address!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48");
+        let token: RawToken = serde_json::from_value(json!({
+            "ticker": "USDC",
+            "decimals": 6,
+            "live_signature": "3045aabb",
+        }))
+        .unwrap();
+        let Some(ClearSigning::Token { ticker, descriptor }) = token_descriptor(&token, &usdc, 1)
+        else {
+            panic!("expected a token descriptor");
+        };
+        assert_eq!(ticker, "USDC");
+        let mut expected = vec![4];
+        expected.extend(b"USDC");
+        expected.extend(usdc.as_slice());
+        expected.extend([0, 0, 0, 6, 0, 0, 0, 1, 0x30, 0x45, 0xaa, 0xbb]);
+        assert_eq!(descriptor, expected);
+
+        let router = address!("def1c0ded9bec7f1a1670819833240f027b25eff");
+        let dapps: Vec<RawDapp> = serde_json::from_value(json!([{
+            "b2c_signatures": {
+                "0xDef1C0ded9bec7F1a1670819833240f027b25EfF": {
+                    "0x415565b0": {
+                        "plugin": "0x",
+                        "serialized_data": "0102",
+                        "signature": "0304",
+                    }
+                }
+            }
+        }]))
+        .unwrap();
+        assert_eq!(
+            plugin_for(&dapps, &router, &[0x41, 0x55, 0x65, 0xb0]),
+            Some(ClearSigning::Plugin {
+                name: "0x".into(),
+                payload: vec![1, 2, 3, 4],
+            })
+        );
+        assert_eq!(plugin_for(&dapps, &router, &ERC20_TRANSFER), None);
+    }
+
+    #[tokio::test]
+    async fn plain_transfers_need_no_metadata() {
+        let tx = TxEip1559 {
+            chain_id: 1,
+            to: TxKind::Call(Address::repeat_byte(0x11)),
+            value: U256::from(1),
+            input: Bytes::new(),
+            ..Default::default()
+        };
+        assert_eq!(
+            clear_signing(&ApiStats::default(), &tx).await,
+            ClearSigning::Transfer
+        );
+        assert!(ClearSigning::Blind.is_blind());
+    }
+
+    #[test]
+    fn splits_payloads_and_reads_signatures() {
+        let payload = vec![0u8; 258];
+        let chunks: Vec<usize> = sign_chunks(&payload).map(<[u8]>::len).collect();
+        assert!(chunks.iter().all(|len| *len <= 255));
+        assert_ne!(chunks.last(), Some(&3));
+        assert_eq!(chunks.iter().sum::<usize>(), 258);
+
+        let mut response = vec![1];
+        response.extend([0x11; 32]);
+        response.extend([0x22; 32]);
+        let signature = parse_signature(&response).unwrap();
+        assert!(signature.v());
+        assert_eq!(signature.r(), U256::from_be_bytes([0x11; 32]));
+        assert!(parse_signature(&response[..40]).is_err());
+    }
+}
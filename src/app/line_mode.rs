@@ -0,0 +1,98 @@
+use super::MainViewTab;
+use std::collections::HashMap;
+
+/// Columns one `h`/`l` scrolls by.
+const SCROLL_STEP: usize = 8;
+
+/// How a text tab fits lines wider than the pane, cycled with `w`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineMode {
+    /// Cut at the pane edge with `…` so the cut is visible.
+    #[default]
+    Truncate,
+    Wrap,
+    /// Keep lines whole and pan with `h`/`l`.
+    Scroll,
+}
+
+impl LineMode {
+    pub fn next(self) -> Self {
+        match self {
+            LineMode::Truncate => LineMode::Wrap,
+            LineMode::Wrap => LineMode::Scroll,
+            LineMode::Scroll => LineMode::Truncate,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LineMode::Truncate => "truncate",
+            LineMode::Wrap => "wrap",
+            LineMode::Scroll => "scroll",
+        }
+    }
+}
+
+/// Line mode and horizontal offset of each main view tab, kept for the
+/// session so switching tabs does not reset them.
+#[derive(Debug, Default)]
+pub struct LineModes {
+    modes: HashMap<MainViewTab, LineMode>,
+    offsets: HashMap<MainViewTab, usize>,
+}
+
+impl LineModes {
+    pub fn mode(&self, tab: MainViewTab) -> LineMode {
+        self.modes.get(&tab).copied().unwrap_or_default()
+    }
+
+    pub fn cycle(&mut self, tab: MainViewTab) -> LineMode {
+        let mode = self.mode(tab).next();
+        self.modes.insert(tab, mode);
+        self.offsets.remove(&tab);
+        mode
+    }
+
+    /// Columns scrolled past on `tab`; only used in [`LineMode::Scroll`].
+    pub fn offset(&self, tab: MainViewTab) -> usize {
+        self.offsets.get(&tab).copied().unwrap_or(0)
+    }
+
+    /// Pan `tab` one step right (or left); ignored unless it scrolls.
+    pub fn scroll(&mut self, tab: MainViewTab, right: bool) {
+        if self.mode(tab) != LineMode::Scroll {
+            return;
+        }
+        let offset = self.offsets.entry(tab).or_default();
+        *offset = if right {
+            offset.saturating_add(SCROLL_STEP)
+        } else {
+            offset.saturating_sub(SCROLL_STEP)
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modes_and_offsets_are_per_tab() {
+        let mut modes = LineModes::default();
+        let raw = MainViewTab::TransactionRaw;
+        assert_eq!(modes.mode(raw), LineMode::Truncate);
+        modes.scroll(raw, true);
+        assert_eq!(modes.offset(raw), 0);
+
+        assert_eq!(modes.cycle(raw), LineMode::Wrap);
+        assert_eq!(modes.cycle(raw), LineMode::Scroll);
+        modes.scroll(raw, true);
+        modes.scroll(raw, true);
+        modes.scroll(raw, false);
+        assert_eq!(modes.offset(raw), SCROLL_STEP);
+        assert_eq!(modes.mode(MainViewTab::AddressInfo), LineMode::Truncate);
+
+        assert_eq!(modes.cycle(raw), LineMode::Truncate);
+        assert_eq!(modes.offset(raw), 0);
+    }
+}
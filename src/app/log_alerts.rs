@@ -0,0 +1,499 @@
+use super::{
+    chains::resolve_chain,
+    events::{EventFilter, LogQuery, decode, topics_for},
+    notify::WatchEvent,
+    providers::Rpc,
+    scheduler::{Priority, Scheduler, host_key},
+};
+use crate::{
+    storage::{AlertOutputs, GroupMember},
+    ui::util::short_hex,
+};
+use alloy::{
+    json_abi::{Event, JsonAbi},
+    primitives::Address,
+    providers::Provider,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    time::Duration,
+};
+use tokio::{task::JoinSet, time::timeout};
+
+/// How often the rules' chains are checked for new blocks; roughly once
+/// per mainnet block.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(12);
+/// Blocks scanned per poll; a longer gap (e.g. after sleep) skips older blocks.
+const MAX_BLOCKS_PER_POLL: u64 = 50;
+/// Matches kept per rule for the session.
+const MAX_HITS: usize = 20;
+
+/// An event on a contract to alert on, stored under the `alerts.log_rules`
+/// setting.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogAlertRule {
+    pub chain: String,
+    pub address: String,
+    /// Human-readable signature with argument names, e.g.
+    /// `Transfer(address indexed from, address indexed to, uint256 value)`.
+    pub event: String,
+    /// `argument=value` pairs, as in the Events tab filter; empty matches
+    /// every emission.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub filter: String,
+    #[serde(flatten)]
+    pub alerts: AlertOutputs,
+}
+
+impl LogAlertRule {
+    pub fn key(&self) -> String {
+        let event = self.event.trim();
+        format!(
+            "{}:{}:{}:{}",
+            self.chain.to_ascii_lowercase(),
+            self.address.to_ascii_lowercase(),
+            event
+                .strip_prefix("event ")
+                .unwrap_or(event)
+                .split_whitespace()
+                .collect::<String>(),
+            self.filter.trim()
+        )
+    }
+
+    /// Event name and contract, e.g. `Transfer on 0x1234...abcd`.
+    pub fn title(&self) -> String {
+        let name = self.event.split('(').next().unwrap_or_default().trim();
+        let name = name.strip_prefix("event ").unwrap_or(name).trim();
+        format!("{name} on {}", short_hex(&self.address))
+    }
+
+    /// Check the chain, contract, signature and filter; the error says what
+    /// to fix.
+    pub fn validate(&self) -> Result<(), String> {
+        if resolve_chain(&self.chain).is_none() {
+            return Err(format!("unknown chain {}", self.chain));
+        }
+        compile(self).map(|_| ())
+    }
+}
+
+/// A rule on `address` prefilled from an Events tab filter: the named event's
+/// full signature from the contract's ABI (or `event`, the highlighted row's
+/// name, when the filter has none) and the filter's arguments.
+pub fn draft(
+    chain: &str,
+    address: &str,
+    abi: Option<&str>,
+    filter_text: &str,
+    event: Option<&str>,
+) -> LogAlertRule {
+    let filter = EventFilter::parse(filter_text);
+    let name = filter.name.as_deref().or(event);
+    let signature = abi
+        .and_then(|abi| serde_json::from_str::<JsonAbi>(abi).ok())
+        .and_then(|abi| {
+            let name = name?;
+            abi.events()
+                .find(|event| event.name.eq_ignore_ascii_case(name))
+                .map(|event| event.full_signature())
+        })
+        .unwrap_or_default();
+    LogAlertRule {
+        chain: chain.to_string(),
+        address: address.to_string(),
+        event: signature,
+        filter: filter
+            .args
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>()
+            .join(" "),
+        alerts: AlertOutputs::default(),
+    }
+}
+
+/// A rule resolved to an `eth_getLogs` query and its event for decoding.
+struct CompiledRule {
+    address: Address,
+    event: Event,
+    query: LogQuery,
+}
+
+fn compile(rule: &LogAlertRule) -> Result<CompiledRule, String> {
+    let address = rule
+        .address
+        .trim()
+        .parse::<Address>()
+        .map_err(|_| "contract must be a 0x address".to_string())?;
+    let signature = rule.event.trim();
+    let event = Event::parse(signature.strip_prefix("event ").unwrap_or(signature))
+        .map_err(|err| format!("invalid event signature: {err}"))?;
+    let mut filter = EventFilter::parse(&rule.filter);
+    if let Some(word) = filter.name.take() {
+        return Err(format!("filter takes argument=value pairs, not {word}"));
+    }
+    filter.name = Some(event.name.clone());
+    let mut abi = JsonAbi::new();
+    abi.events
+        .entry(event.name.clone())
+        .or_default()
+        .push(event.clone());
+    let query = topics_for(Some(&abi), &filter)?;
+    Ok(CompiledRule {
+        address,
+        event,
+        query,
+    })
+}
+
+/// A log that matched a rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogHit {
+    pub block: u64,
+    pub transaction_hash: String,
+    /// Decoded arguments as `name=value` pairs.
+    pub arguments: String,
+}
+
+/// One chain's scan: the head it reached and each rule's matches, or why
+/// the chain could not be read.
+#[derive(Debug, Clone)]
+pub struct ChainScan {
+    pub chain: String,
+    pub outcome: Result<ScannedBlocks, String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScannedBlocks {
+    pub head: u64,
+    /// Matches oldest first, or the rule's error, keyed by [`LogAlertRule::key`].
+    pub rules: Vec<(String, Result<Vec<LogHit>, String>)>,
+}
+
+/// Log alert rules with the blocks scanned and matches seen this session.
+#[derive(Debug, Default)]
+pub struct LogAlertState {
+    pub rules: Vec<LogAlertRule>,
+    /// Last block scanned per lowercase chain name.
+    scanned: HashMap<String, u64>,
+    /// Matches per rule key, newest first.
+    hits: HashMap<String, VecDeque<LogHit>>,
+    errors: HashMap<String, String>,
+    /// Key of the rule that matched last, for the modal to open on.
+    pub latest: Option<String>,
+}
+
+impl LogAlertState {
+    pub fn new(rules: Vec<LogAlertRule>) -> Self {
+        Self {
+            rules,
+            ..Self::default()
+        }
+    }
+
+    /// Add `rule` unless the same one exists; returns whether it was added.
+    pub fn add(&mut self, rule: LogAlertRule) -> bool {
+        let key = rule.key();
+        if self.rules.iter().any(|existing| existing.key() == key) {
+            return false;
+        }
+        self.rules.push(rule);
+        true
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<LogAlertRule> {
+        if index >= self.rules.len() {
+            return None;
+        }
+        let rule = self.rules.remove(index);
+        let key = rule.key();
+        self.hits.remove(&key);
+        self.errors.remove(&key);
+        Some(rule)
+    }
+
+    /// Move the rule at `index` to the next [`AlertOutputs`].
+    pub fn cycle_alerts(&mut self, index: usize) -> Option<AlertOutputs> {
+        let rule = self.rules.get_mut(index)?;
+        rule.alerts = rule.alerts.next();
+        Some(rule.alerts)
+    }
+
+    pub fn hits(&self, rule: &LogAlertRule) -> impl Iterator<Item = &LogHit> {
+        self.hits.get(&rule.key()).into_iter().flatten()
+    }
+
+    pub fn error(&self, rule: &LogAlertRule) -> Option<&str> {
+        self.errors.get(&rule.key()).map(String::as_str)
+    }
+
+    /// Rules grouped per chain with the last block scanned there.
+    pub fn batches(&self) -> Vec<(String, Option<u64>, Vec<LogAlertRule>)> {
+        let mut batches: BTreeMap<String, Vec<LogAlertRule>> = BTreeMap::new();
+        for rule in &self.rules {
+            batches
+                .entry(rule.chain.to_ascii_lowercase())
+                .or_default()
+                .push(rule.clone());
+        }
+        batches
+            .into_iter()
+            .map(|(chain, rules)| {
+                let after = self.scanned.get(&chain).copied();
+                (rules[0].chain.clone(), after, rules)
+            })
+            .collect()
+    }
+
+    /// Record a poll's scans and return one event per rule that matched.
+    /// A chain's first scan only finds its head.
+    pub fn apply_poll(&mut self, scans: Vec<ChainScan>) -> Vec<(AlertOutputs, WatchEvent)> {
+        let mut raised = Vec::new();
+        for scan in scans {
+            let Ok(scanned) = scan.outcome else {
+                continue;
+            };
+            self.scanned
+                .insert(scan.chain.to_ascii_lowercase(), scanned.head);
+            for (key, outcome) in scanned.rules {
+                let found = match outcome {
+                    Ok(found) => found,
+                    Err(err) => {
+                        self.errors.insert(key, err);
+                        continue;
+                    }
+                };
+                self.errors.remove(&key);
+                let Some(latest) = found.last().cloned() else {
+                    continue;
+                };
+                let hits = self.hits.entry(key.clone()).or_default();
+                for hit in &found {
+                    hits.push_front(hit.clone());
+                }
+                hits.truncate(MAX_HITS);
+                self.latest = Some(key.clone());
+                let Some(rule) = self.rules.iter().find(|rule| rule.key() == key) else {
+                    continue;
+                };
+                let count = match found.len() {
+                    1 => String::new(),
+                    n => format!(" ({n} matches)"),
+                };
+                raised.push((
+                    rule.alerts,
+                    WatchEvent {
+                        source: "log_rule",
+                        title: format!("{} ({})", rule.title(), rule.chain),
+                        message: format!(
+                            "{} in tx {} at block {}{count}",
+                            latest.arguments,
+                            short_hex(&latest.transaction_hash),
+                            latest.block
+                        ),
+                        subject: Some(GroupMember {
+                            address: rule.address.clone(),
+                            chain: rule.chain.clone(),
+                        }),
+                        transaction: Some(latest.transaction_hash),
+                    },
+                ));
+            }
+        }
+        raised
+    }
+}
+
+/// A chain, its RPC endpoint, the last block scanned there and its rules.
+pub type RuleBatch = (String, Option<String>, Option<u64>, Vec<LogAlertRule>);
+
+/// Scan each chain's new blocks for the rules' logs.
+pub async fn poll_rules(rpc: Rpc, scheduler: Scheduler, batches: Vec<RuleBatch>) -> Vec<ChainScan> {
+    let mut tasks = JoinSet::new();
+    let mut scans = Vec::new();
+    for (chain, rpc_url, after, rules) in batches {
+        let Some(url) = rpc_url else {
+            scans.push(ChainScan {
+                outcome: Err(format!("no RPC endpoint for {chain}")),
+                chain,
+            });
+            continue;
+        };
+        let rpc = rpc.clone();
+        let scheduler = scheduler.clone();
+        tasks.spawn(async move {
+            let host = host_key(Some(&url), &chain);
+            let _permit = scheduler.acquire(&host, Priority::Poll).await;
+            let outcome =
+                match timeout(Duration::from_secs(20), scan(&rpc, &url, after, &rules)).await {
+                    Ok(outcome) => outcome,
+                    Err(_) => Err("timed out".to_string()),
+                };
+            ChainScan { chain, outcome }
+        });
+    }
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok(scan) = joined {
+            scans.push(scan);
+        }
+    }
+    scans
+}
+
+async fn scan(
+    rpc: &Rpc,
+    rpc_url: &str,
+    after: Option<u64>,
+    rules: &[LogAlertRule],
+) -> Result<ScannedBlocks, String> {
+    let provider = rpc
+        .connect(rpc_url)
+        .await
+        .map_err(|err| format!("{err:#}"))?;
+    rpc.stats().record_api_call("rpc");
+    let head = provider
+        .get_block_number()
+        .await
+        .map_err(|err| format!("failed to query latest block number: {err}"))?;
+    let Some(after) = after.filter(|after| *after < head) else {
+        return Ok(ScannedBlocks {
+            head,
+            rules: Vec::new(),
+        });
+    };
+    let from = (after + 1).max(head.saturating_sub(MAX_BLOCKS_PER_POLL - 1));
+    let mut results = Vec::new();
+    for rule in rules {
+        let outcome = match compile(rule) {
+            Ok(compiled) => {
+                rpc.stats().record_api_call("rpc");
+                provider
+                    .get_logs(&compiled.query.filter(compiled.address, from, head))
+                    .await
+                    .map(|logs| matches(&compiled, &logs))
+                    .map_err(|err| format!("eth_getLogs failed: {err}"))
+            }
+            Err(err) => Err(err),
+        };
+        results.push((rule.key(), outcome));
+    }
+    Ok(ScannedBlocks {
+        head,
+        rules: results,
+    })
+}
+
+/// Logs of `rule`'s event whose arguments pass its filter, oldest first.
+fn matches(rule: &CompiledRule, logs: &[alloy::rpc::types::Log]) -> Vec<LogHit> {
+    let known = HashMap::from([(rule.event.selector(), &rule.event)]);
+    logs.iter()
+        .filter_map(|log| {
+            let decoded = decode(log, &known);
+            if decoded.unknown_topic.is_some() || !rule.query.body_matches(&decoded) {
+                return None;
+            }
+            Some(LogHit {
+                block: log.block_number?,
+                transaction_hash: decoded.transaction_hash.clone()?,
+                arguments: decoded.arguments(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::{
+        primitives::{B256, Bytes, LogData, U256},
+        rpc::types::Log,
+    };
+
+    fn rule(filter: &str) -> LogAlertRule {
+        LogAlertRule {
+            chain: "Mainnet".into(),
+            address: "0x00000000000000000000000000000000000000cc".into(),
+            event: "event Transfer(address indexed from, address indexed to, uint256 value)".into(),
+            filter: filter.into(),
+            alerts: AlertOutputs::default(),
+        }
+    }
+
+    fn transfer_log(to: u8, value: u64) -> Log {
+        let event =
+            Event::parse("Transfer(address indexed from, address indexed to, uint256 value)")
+                .unwrap();
+        let topics = vec![
+            event.selector(),
+            B256::left_padding_from(&[0xaa]),
+            B256::left_padding_from(&[to]),
+        ];
+        Log {
+            inner: alloy::primitives::Log {
+                address: Address::repeat_byte(0xcc),
+                data: LogData::new_unchecked(
+                    topics,
+                    Bytes::from(U256::from(value).to_be_bytes::<32>().to_vec()),
+                ),
+            },
+            block_number: Some(100),
+            transaction_hash: Some(B256::repeat_byte(0x11)),
+            ..Log::default()
+        }
+    }
+
+    #[test]
+    fn rules_filter_decoded_logs() {
+        assert_eq!(rule("").title(), "Transfer on 0x0000...00cc");
+        assert!(rule("").validate().is_ok());
+        assert!(rule("Approval").validate().is_err());
+        assert!(rule("amount=5").validate().is_err());
+        let mut unknown = rule("");
+        unknown.chain = "Nowhere".into();
+        assert!(unknown.validate().is_err());
+
+        let compiled = compile(&rule("value=5")).unwrap();
+        let hits = matches(&compiled, &[transfer_log(0xbb, 5), transfer_log(0xbb, 6)]);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].block, 100);
+        assert!(hits[0].arguments.ends_with("value=5"));
+
+        let mut state = LogAlertState::new(vec![rule("value=5")]);
+        assert!(!state.add(rule("value=5")));
+        let key = rule("value=5").key();
+        let scan = |head, rules| ChainScan {
+            chain: "Mainnet".into(),
+            outcome: Ok(ScannedBlocks { head, rules }),
+        };
+        assert!(state.apply_poll(vec![scan(99, Vec::new())]).is_empty());
+        assert_eq!(state.batches()[0].1, Some(99));
+        let raised = state.apply_poll(vec![scan(100, vec![(key.clone(), Ok(hits))])]);
+        assert_eq!(raised.len(), 1);
+        assert_eq!(
+            raised[0].1.transaction.as_deref(),
+            Some(format!("{:#x}", B256::repeat_byte(0x11)).as_str())
+        );
+        assert_eq!(state.latest.as_deref(), Some(key.as_str()));
+        assert_eq!(state.hits(&rule("value=5")).count(), 1);
+
+        let abi = r#"[{"type":"event","name":"Transfer","anonymous":false,"inputs":[
+            {"name":"from","type":"address","indexed":true},
+            {"name":"to","type":"address","indexed":true},
+            {"name":"value","type":"uint256","indexed":false}]}]"#;
+        let drafted = draft(
+            "Mainnet",
+            "0x00000000000000000000000000000000000000cc",
+            Some(abi),
+            "transfer value=5",
+            None,
+        );
+        assert_eq!(drafted.key(), rule("value=5").key());
+        assert!(
+            draft("Mainnet", "0xcc", None, "", Some("Transfer"))
+                .event
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::HashMap;
+
+/// What the next key names a register for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Awaiting {
+    Record,
+    Replay,
+}
+
+/// Keyboard macros: `Ctrl-r <a-z>` records the keys that follow into a
+/// register until `Ctrl-r` again, `@<a-z>` replays one and `@@` the last
+/// replayed. Registers last for the session. Keys typed into signing and
+/// secrets dialogs are never recorded, and a replay stops when it opens one.
+#[derive(Debug, Default)]
+pub struct Macros {
+    registers: HashMap<char, Vec<KeyEvent>>,
+    recording: Option<(char, Vec<KeyEvent>)>,
+    awaiting: Option<Awaiting>,
+    last_replayed: Option<char>,
+    /// Set while a macro's keys are fed back, so replays do not nest.
+    pub replaying: bool,
+}
+
+/// The key that starts and stops recording outside modals.
+pub fn is_record_key(key: &KeyEvent) -> bool {
+    key.modifiers.contains(KeyModifiers::CONTROL)
+        && matches!(key.code, KeyCode::Char('r') | KeyCode::Char('R'))
+}
+
+impl Macros {
+    /// Register being recorded into.
+    pub fn recording(&self) -> Option<char> {
+        self.recording.as_ref().map(|(register, _)| *register)
+    }
+
+    pub fn await_register(&mut self, awaiting: Awaiting) {
+        self.awaiting = Some(awaiting);
+    }
+
+    pub fn take_awaiting(&mut self) -> Option<Awaiting> {
+        self.awaiting.take()
+    }
+
+    /// Start recording into `register`; only `a`-`z` are registers.
+    pub fn start(&mut self, register: char) -> bool {
+        if !register.is_ascii_lowercase() {
+            return false;
+        }
+        self.recording = Some((register, Vec::new()));
+        true
+    }
+
+    /// Stop recording and keep the keys; returns the register and how many
+    /// keys it holds.
+    pub fn stop(&mut self) -> Option<(char, usize)> {
+        let (register, keys) = self.recording.take()?;
+        let count = keys.len();
+        self.registers.insert(register, keys);
+        Some((register, count))
+    }
+
+    /// Append `key` to the recording, if one is running; keys fed back by a
+    /// replay are left out.
+    pub fn record(&mut self, key: KeyEvent) {
+        if self.replaying {
+            return;
+        }
+        if let Some((_, keys)) = self.recording.as_mut() {
+            keys.push(key);
+        }
+    }
+
+    /// Keys of `register` (`@` for the last replayed) to feed back, or why
+    /// there are none.
+    pub fn replay(&mut self, register: char) -> Result<(char, Vec<KeyEvent>), String> {
+        if self.replaying {
+            return Err("Macros cannot replay other macros".into());
+        }
+        let register = match register {
+            '@' => self
+                .last_replayed
+                .ok_or_else(|| "No macro replayed yet".to_string())?,
+            register => register,
+        };
+        if self.recording() == Some(register) {
+            return Err(format!("Still recording @{register}"));
+        }
+        let keys = self
+            .registers
+            .get(&register)
+            .filter(|keys| !keys.is_empty())
+            .ok_or_else(|| format!("Register @{register} is empty"))?
+            .clone();
+        self.last_replayed = Some(register);
+        Ok((register, keys))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn records_and_replays_registers() {
+        assert!(is_record_key(&KeyEvent::new(
+            KeyCode::Char('r'),
+            KeyModifiers::CONTROL
+        )));
+        let mut macros = Macros::default();
+        assert!(!macros.start('1'));
+        assert!(macros.start('a'));
+        macros.record(key('j'));
+        macros.record(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(macros.replay('a').is_err());
+        assert_eq!(macros.stop(), Some(('a', 2)));
+        assert_eq!(macros.recording(), None);
+
+        assert!(macros.replay('@').is_err());
+        let (register, keys) = macros.replay('a').unwrap();
+        assert_eq!((register, keys.len()), ('a', 2));
+        assert_eq!(macros.replay('@').unwrap().1, keys);
+        assert!(macros.replay('b').is_err());
+
+        macros.replaying = true;
+        macros.start('b');
+        macros.record(key('x'));
+        assert!(macros.replay('a').is_err());
+        assert_eq!(macros.stop(), Some(('b', 0)));
+    }
+}
@@ -1,60 +1,198 @@
 use crate::{
     components::Component,
-    storage::{FavoriteRecord, SecretKey, SecretsRepository, Storage},
-    ui::util::short_hex,
+    storage::{
+        AlertOutputs, FavoriteRecord, FavoritesTable, GroupMember, ScratchNote, SecretKey,
+        SecretsRepository, SettingKey, SignatureKind, Storage, TrashRecord,
+    },
+    ui::util::{SPINNER_FRAME, checksum_address, is_compact, short_hex},
     ui::{
         bottom_bar::BottomBar,
+        copy_mode::CopyCommand,
         main_view::{MainView, MainViewCommand},
-        modal::{SecretsModal, secrets::SecretsFormCommand},
+        modal::{
+            BlockModal, ConvertModal, DeployAddressModal, DiagnosticsModal, ErrorsModal,
+            GasAnalyticsModal, HealthModal, LogAlertsModal, PendingModal, PermissionsModal,
+            PipeModal, PortfolioModal, QrModal, SafeQueueModal, ScratchpadModal, SecretsModal,
+            SessionsModal, ThresholdsModal, TrashModal, TypedDataModal, UpdateModal,
+            UpgradeImpactModal, WriteModal, block::BlockCommand,
+            deploy_address::DeployAddressCommand, gas_analytics::GasAnalyticsCommand,
+            health::HealthCommand, pending::PendingCommand, permissions::PermissionsCommand,
+            safe_queue::SafeQueueCommand, secrets::SecretsFormCommand,
+            upgrade_impact::UpgradeImpactCommand, write::WriteCommand,
+        },
         sidebar::{Sidebar, SidebarCommand},
         top::{TopBar, TopCommand},
     },
 };
 pub type AppResult<T> = color_eyre::Result<T>;
 use alloy::primitives::{Address, U256, utils::format_units};
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::{
+    cursor::MoveTo,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    queue,
+};
 use ratatui::{
     DefaultTerminal, Frame,
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Layout, Rect},
 };
+use serde::{Deserialize, Serialize};
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     env,
-    sync::mpsc,
+    io::Write,
+    path::PathBuf,
+    sync::{Arc, mpsc},
     time::{Duration as StdDuration, Instant},
 };
 
+use tempfile::TempDir;
 use tokio::runtime::{Handle, Runtime};
+use tokio::task::AbortHandle;
 use tokio::time::{Duration, sleep, timeout};
 
 pub use navigation::{FocusedPane, MainViewMode, MainViewTab, SidebarTab};
 
+pub mod access_list;
+pub mod activity;
 mod anvil;
-use self::anvil::{AccountOverview, fetch_account_overview, fetch_latest_block};
+mod artifacts;
+pub mod auto_refresh;
+pub mod balance_diff;
+mod bindings;
+use self::anvil::{AccountOverview, fetch_blob_details, fetch_block_context};
+pub use self::anvil::{BlobDetails, BlockContext, BlockSummary};
+pub mod blocks;
+mod blockscout;
+pub mod chains;
+pub mod convert;
+pub mod csv_export;
+pub mod custom_tabs;
+pub mod deep_link;
+pub mod demo;
+pub mod deploy_address;
+mod ens;
 mod etherscan;
-use self::etherscan::{AddressTransaction, TransactionFetchError, fetch_address_transactions};
-
-#[derive(Debug, Clone, PartialEq, Eq)]
+pub mod events;
+mod explorer;
+pub mod explorer_cache;
+pub mod failures;
+pub mod fees;
+pub mod fetch_limits;
+pub mod follow;
+pub mod gas_analytics;
+pub mod graphql;
+pub mod json_export;
+pub mod l2;
+pub mod labels;
+pub mod layout;
+pub mod ledger;
+pub mod line_mode;
+pub mod log_alerts;
+pub mod macros;
+pub mod multichain;
+pub mod nonces;
+pub mod notify;
+mod otterscan;
+pub mod pending;
+pub mod permissions;
+pub mod pipe;
+pub mod plugins;
+pub mod prefetch;
+pub mod preview_cache;
+pub mod providers;
+pub mod qr;
+pub mod quota;
+pub mod raw_tx;
+pub mod retry;
+pub mod rpc_health;
+pub mod safe;
+pub mod scheduler;
+pub mod scratchpad;
+pub mod screenshot;
+pub mod search;
+pub mod secrets_check;
+pub mod sessions;
+pub mod signatures;
+pub mod signing;
+pub mod slots;
+mod sourcify;
+pub mod stats;
+pub mod storage_layout;
+pub mod suggestions;
+pub mod tenderly;
+pub mod thresholds;
+pub mod transfers;
+pub mod typed_data;
+pub mod updates;
+pub mod upgrade_impact;
+pub mod upgrades;
+pub mod watch;
+pub mod watchlist;
+pub mod webhook;
+pub mod withdrawals;
+pub mod write;
+pub mod ws;
+use self::access_list::AccessListView;
+use self::balance_diff::BalanceDiffView;
+use self::chains::{CHAINS, Rollup, chain_mismatch, resolve_chain};
+use self::custom_tabs::{CustomTabState, TabContent};
+use self::deep_link::DeepLink;
+use self::events::EventsView;
+use self::explorer::{
+    AddressTransaction, ContractCreation, ContractSource, SourceMatch, TransactionFetchError,
+};
+use self::failures::{AddressSection, ErrorDetail, SectionFailure};
+use self::fees::FeesView;
+use self::fetch_limits::FetchLimits;
+use self::follow::FollowState;
+use self::labels::LabelRegistry;
+use self::layout::PaneLayout;
+use self::line_mode::LineModes;
+use self::log_alerts::LogAlertState;
+use self::macros::{Awaiting, Macros};
+use self::multichain::MultichainView;
+use self::notify::{Notifier, WatchEvent};
+use self::plugins::{PluginEvent, PluginState};
+use self::prefetch::{PrefetchCache, Prefetched};
+use self::preview_cache::TransactionPreviewCache;
+use self::providers::Providers;
+use self::qr::Graphics;
+use self::raw_tx::RawView;
+use self::retry::TimedOut;
+use self::scheduler::{Priority, Scheduler, host_key};
+use self::sessions::{Session, SessionState};
+use self::signatures::SignatureBook;
+use self::slots::{PinnedSlot, SlotWatch};
+pub use self::stats::UsageStats;
+use self::thresholds::ThresholdState;
+use self::transfers::{TokenCache, TransfersView};
+pub use self::updates::ReleaseInfo;
+use self::upgrades::UpgradesView;
+use self::watch::WatchState;
+use self::watchlist::WatchlistState;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
 pub enum SelectedEntity {
     Address(AddressRef),
     Transaction(TransactionRef),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AddressRef {
     pub label: String,
     pub address: String,
     pub chain: String,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TransactionRef {
     pub label: String,
     pub hash: String,
     pub chain: String,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct HydratedAddress {
     pub identifier: String,
     pub info: Vec<String>,
@@ -64,9 +202,31 @@ pub struct HydratedAddress {
     pub balances: Vec<String>,
     pub permissions: Vec<String>,
     pub overview: Option<AccountOverview>,
+    pub contract_source: Option<ContractSource>,
+    pub creation: Option<ContractCreation>,
+    /// Sections that could not be loaded; the rest of the view is still valid.
+    pub failures: Vec<SectionFailure>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+impl HydratedAddress {
+    /// Entities the Info tab offers to open, in display order.
+    pub fn info_links(&self) -> Vec<InfoLink> {
+        let mut links = Vec::new();
+        if self
+            .overview
+            .as_ref()
+            .is_some_and(|overview| overview.delegate.is_some())
+        {
+            links.push(InfoLink::Delegate);
+        }
+        if self.creation.is_some() {
+            links.extend([InfoLink::Deployer, InfoLink::CreationTransaction]);
+        }
+        links
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct AddressTransactionsTable {
     pub source_label: String,
     pub source_api_version: String,
@@ -74,7 +234,7 @@ pub struct AddressTransactionsTable {
     pub rows: Vec<AddressTransactionRow>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct AddressTransactionRow {
     pub hash: String,
     pub from: String,
@@ -86,15 +246,16 @@ pub struct AddressTransactionRow {
     pub value_display: String,
     pub status: TransactionStatus,
     pub calldata: Option<String>,
+    pub timestamp: Option<u64>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum TransactionStatus {
     Success,
     Failed,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum TransactionDirection {
     Incoming,
     Outgoing,
@@ -123,7 +284,8 @@ impl TransactionDirection {
 }
 
 impl AddressTransactionRow {
-    pub fn from_transaction(target_address: &str, tx: &AddressTransaction) -> Self {
+    /// `currency` is the native symbol of the address's chain.
+    pub fn from_transaction(target_address: &str, tx: &AddressTransaction, currency: &str) -> Self {
         let is_sender = tx.from.eq_ignore_ascii_case(target_address);
         let is_recipient = tx
             .to
@@ -141,23 +303,23 @@ impl AddressTransactionRow {
             TransactionDirection::Interaction
         };
 
+        let from = checksum_address(&tx.from);
+        let to = tx.to.as_deref().map(checksum_address);
         let counterparty = if is_sender && is_recipient {
             "Self".to_string()
         } else if is_sender {
-            tx.to
-                .as_ref()
+            to.as_ref()
                 .map(|addr| short_hex(addr))
                 .unwrap_or_else(|| "Contract creation".into())
         } else if is_recipient {
-            short_hex(&tx.from)
+            short_hex(&from)
         } else {
-            tx.to
-                .as_ref()
+            to.as_ref()
                 .map(|addr| short_hex(addr))
-                .unwrap_or_else(|| short_hex(&tx.from))
+                .unwrap_or_else(|| short_hex(&from))
         };
 
-        let mut value = format_eth_value(&tx.value_wei);
+        let mut value = format_native_value(&tx.value_wei, currency);
         if !tx.value_wei.is_zero() {
             match direction {
                 TransactionDirection::Outgoing => value = format!("-{value}"),
@@ -168,8 +330,8 @@ impl AddressTransactionRow {
 
         AddressTransactionRow {
             hash: tx.hash.clone(),
-            from: tx.from.clone(),
-            to: tx.to.clone(),
+            from,
+            to,
             value_wei: tx.value_wei,
             block_number: (tx.block_number > 0).then_some(tx.block_number),
             direction,
@@ -181,11 +343,22 @@ impl AddressTransactionRow {
                 TransactionStatus::Success
             },
             calldata: tx.input.clone(),
+            timestamp: tx.timestamp,
+        }
+    }
+
+    /// Address behind the counterparty column; `None` for self transfers.
+    pub fn counterparty_address(&self) -> Option<&str> {
+        match self.direction {
+            TransactionDirection::SelfTransfer => None,
+            TransactionDirection::Outgoing => self.to.as_deref(),
+            TransactionDirection::Incoming => Some(&self.from),
+            TransactionDirection::Interaction => self.to.as_deref().or(Some(&self.from)),
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct HydratedTransaction {
     pub identifier: String,
     pub summary: Vec<String>,
@@ -197,25 +370,92 @@ pub struct HydratedTransaction {
     pub calldata: Option<String>,
     pub block_number: Option<u64>,
     pub status: Option<TransactionStatus>,
+    pub blobs: Option<BlobDetails>,
+    /// Other transactions sharing this one's sender and nonce.
+    pub nonce_history: Option<nonces::NonceHistory>,
+    pub block_context: Option<BlockContext>,
+    /// Deposit, retryable and withdrawal details on rollup chains.
+    pub l2: Option<l2::L2Context>,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct SecretsState {
     pub etherscan_api_key: Option<String>,
     pub anvil_rpc_url: Option<String>,
+    /// Every other namespaced secret: per-chain explorer keys, chain RPC URLs,
+    /// and provider credentials.
+    pub scoped: BTreeMap<SecretKey, String>,
 }
 
 impl SecretsState {
     fn load(storage: &Storage) -> AppResult<Self> {
         let repo = storage.secrets();
-        Ok(Self {
-            etherscan_api_key: Self::resolve_secret(repo, SecretKey::EtherscanApiKey)?,
-            anvil_rpc_url: Self::resolve_secret(repo, SecretKey::AnvilRpcUrl)?,
-        })
+        let mut state = Self {
+            etherscan_api_key: Self::resolve_secret(repo, &SecretKey::EtherscanApiKey)?,
+            anvil_rpc_url: Self::resolve_secret(repo, &SecretKey::AnvilRpcUrl)?,
+            scoped: BTreeMap::new(),
+        };
+        for chain in CHAINS {
+            for key in [
+                SecretKey::ExplorerApiKey(chain.chain_id),
+                SecretKey::RpcUrl(chain.name.to_ascii_lowercase()),
+            ] {
+                if let Some(value) = Self::resolve_secret(repo, &key)? {
+                    state.scoped.insert(key, value);
+                }
+            }
+        }
+        for (key, value) in repo.list()? {
+            let trimmed = value.trim();
+            let scoped = !matches!(key, SecretKey::EtherscanApiKey | SecretKey::AnvilRpcUrl);
+            if scoped && !trimmed.is_empty() && !state.scoped.contains_key(&key) {
+                state.scoped.insert(key, trimmed.to_string());
+            }
+        }
+        Ok(state)
+    }
+
+    pub fn get(&self, key: &SecretKey) -> Option<&str> {
+        match key {
+            SecretKey::EtherscanApiKey => self.etherscan_api_key.as_deref(),
+            SecretKey::AnvilRpcUrl => self.anvil_rpc_url.as_deref(),
+            other => self.scoped.get(other).map(String::as_str),
+        }
+    }
+
+    pub fn set(&mut self, key: &SecretKey, value: Option<String>) {
+        match key {
+            SecretKey::EtherscanApiKey => self.etherscan_api_key = value,
+            SecretKey::AnvilRpcUrl => self.anvil_rpc_url = value,
+            other => match value {
+                Some(value) => {
+                    self.scoped.insert(other.clone(), value);
+                }
+                None => {
+                    self.scoped.remove(other);
+                }
+            },
+        }
+    }
+
+    /// Explorer key for `chain`, falling back to the shared Etherscan key.
+    pub fn explorer_api_key(&self, chain: &str) -> Option<&str> {
+        resolve_chain(chain)
+            .and_then(|info| self.get(&SecretKey::ExplorerApiKey(info.chain_id)))
+            .or(self.etherscan_api_key.as_deref())
     }
 
-    fn resolve_secret(repo: &SecretsRepository, key: SecretKey) -> AppResult<Option<String>> {
-        if let Ok(value) = env::var(key.env_var()) {
+    /// RPC endpoint configured for `chain`, falling back to the Anvil URL.
+    pub fn rpc_url(&self, chain: &str) -> Option<&str> {
+        resolve_chain(chain)
+            .and_then(|info| self.get(&SecretKey::RpcUrl(info.name.to_ascii_lowercase())))
+            .or(self.anvil_rpc_url.as_deref())
+    }
+
+    fn resolve_secret(repo: &SecretsRepository, key: &SecretKey) -> AppResult<Option<String>> {
+        if let Some(var) = key.env_var()
+            && let Ok(value) = env::var(var)
+        {
             let trimmed = value.trim();
             if !trimmed.is_empty() {
                 repo.set(key, trimmed)?;
@@ -236,6 +476,10 @@ impl SecretsState {
     }
 }
 
+/// Redraw interval while nothing changed, so clocks and ages on screen still
+/// move along.
+const IDLE_REDRAW: Duration = Duration::from_secs(1);
+
 /// Central application type that orchestrates state and delegates to UI components.
 pub struct App {
     running: bool,
@@ -248,14 +492,201 @@ pub struct App {
     runtime: Runtime,
     message_rx: mpsc::Receiver<Message>,
     message_tx: mpsc::Sender<Message>,
-    secrets_modal: Option<SecretsModal>,
+    /// Shared by every [`CommandBus`] handed out, so limits span all tasks.
+    scheduler: Scheduler,
+    /// Where address hydration reads account state and history from.
+    providers: Providers,
+    modal: Option<ActiveModal>,
+    hydration_started: Option<Instant>,
+    last_watch_poll: Option<Instant>,
+    watch_poll_in_flight: bool,
+    last_watchlist_poll: Option<Instant>,
+    last_log_alert_poll: Option<Instant>,
+    log_alert_poll_in_flight: bool,
+    last_threshold_poll: Option<Instant>,
+    threshold_poll_in_flight: bool,
+    watchlist_poll_in_flight: bool,
+    last_safe_poll: Option<Instant>,
+    safe_poll_in_flight: bool,
+    notifier: Notifier,
+    last_hydrated: Option<Instant>,
+    refresh_in_flight: bool,
+    last_follow_poll: Option<Instant>,
+    follow_poll_in_flight: bool,
+    last_slot_poll: Option<Instant>,
+    slot_poll_in_flight: bool,
+    last_prefetch: Option<Instant>,
+    /// Set by input, messages and actions; the frame is only redrawn when set
+    /// (or an animation runs, or [`IDLE_REDRAW`] passed since the last draw).
+    dirty: bool,
+    last_draw: Instant,
+    /// Exports queued for stdout, printed once the terminal is restored.
+    stdout: Vec<String>,
+    /// Opened on first copy and kept, see [`App::copy_to_clipboard`].
+    clipboard: Option<arboard::Clipboard>,
+    /// Image protocol for the QR modal, detected once at startup.
+    graphics: Option<Graphics>,
+    /// Where the QR image was last drawn, see [`App::sync_qr_image`].
+    qr_image: Option<Rect>,
+    /// Where to save the next frame drawn, see [`App::screenshot_command`].
+    pending_screenshot: Option<(PathBuf, screenshot::Format)>,
+    /// The `--demo` data directory; last so it outlives the storage in it.
+    demo_dir: Option<TempDir>,
+}
+
+/// The dialog currently capturing input, if any. Only one modal is shown at a time.
+enum ActiveModal {
+    Secrets(SecretsModal),
+    Update(UpdateModal),
+    Diagnostics(DiagnosticsModal),
+    Trash(TrashModal),
+    Portfolio(PortfolioModal),
+    Permissions(PermissionsModal),
+    UpgradeImpact(UpgradeImpactModal),
+    Health(HealthModal),
+    Block(BlockModal),
+    DeployAddress(DeployAddressModal),
+    Convert(ConvertModal),
+    TypedData(TypedDataModal),
+    Write(WriteModal),
+    Pending(PendingModal),
+    SafeQueue(SafeQueueModal),
+    GasAnalytics(GasAnalyticsModal),
+    Errors(ErrorsModal),
+    Qr(QrModal),
+    Scratchpad(ScratchpadModal),
+    LogAlerts(LogAlertsModal),
+    Thresholds(ThresholdsModal),
+    Pipe(PipeModal),
+    Sessions(SessionsModal),
 }
 
 impl App {
-    pub fn new() -> AppResult<Self> {
-        let mut state = AppState::default();
-        let mut storage = Storage::open_default()?;
-        state.secrets = SecretsState::load(&storage)?;
+    /// Start on `link`'s view when given, else on the first favorite,
+    /// reading chain data through `providers`.
+    pub fn new(link: Option<DeepLink>, providers: Providers) -> AppResult<Self> {
+        let storage = Storage::open_default()?;
+        let secrets = SecretsState::load(&storage)?;
+        let mut app = Self::open(link, storage, secrets, providers)?;
+        app.start_update_check()?;
+        Ok(app)
+    }
+
+    /// Like [`App::new`], but on a throwaway data directory seeded with the
+    /// demo favorites and secrets pointing every chain at the fixtures.
+    pub fn demo(link: Option<DeepLink>, providers: Providers) -> AppResult<Self> {
+        let data_dir = demo::data_dir()?;
+        let storage = demo::open_storage(data_dir.path())?;
+        let mut app = Self::open(link, storage, demo::secrets(), providers)?;
+        app.demo_dir = Some(data_dir);
+        app.show_status("Demo mode: fixture data, nothing saved");
+        Ok(app)
+    }
+
+    fn open(
+        link: Option<DeepLink>,
+        mut storage: Storage,
+        secrets: SecretsState,
+        providers: Providers,
+    ) -> AppResult<Self> {
+        let mut state = AppState {
+            secrets,
+            ..Default::default()
+        };
+        state.usage = UsageStats::load(storage.settings())?;
+        state.watch = WatchState::new(storage.groups().list()?);
+        state.watchlist = WatchlistState::new(
+            storage
+                .settings()
+                .get_json(SettingKey::Watchlist)?
+                .unwrap_or_default(),
+        );
+        state.log_alerts = LogAlertState::new(
+            storage
+                .settings()
+                .get_json(SettingKey::LogAlerts)?
+                .unwrap_or_default(),
+        );
+        state.thresholds = ThresholdState::new(
+            storage
+                .settings()
+                .get_json(SettingKey::Thresholds)?
+                .unwrap_or_default(),
+        );
+        state.plugins = PluginState::new(
+            storage
+                .settings()
+                .get_json(SettingKey::Plugins)?
+                .unwrap_or_default(),
+        );
+        state.custom_tabs = CustomTabState::new(
+            storage
+                .settings()
+                .get_json(SettingKey::CustomTabs)?
+                .unwrap_or_default(),
+        );
+        state.auto_refresh = auto_refresh::from_setting(
+            storage
+                .settings()
+                .get_json(SettingKey::AutoRefresh)?
+                .unwrap_or(0),
+        );
+        state.layout = storage
+            .settings()
+            .get_json(SettingKey::Layout)?
+            .unwrap_or_default();
+        state.investigation = storage
+            .settings()
+            .get_json(SettingKey::Investigation)?
+            .unwrap_or_else(|| scratchpad::DEFAULT_INVESTIGATION.to_string());
+        state.sessions = SessionState::new(
+            storage
+                .settings()
+                .get_json(SettingKey::Sessions)?
+                .unwrap_or_default(),
+        );
+        state.labels = LabelRegistry::new(
+            storage
+                .settings()
+                .get_json(SettingKey::AddressLabels)?
+                .unwrap_or_default(),
+        );
+        signatures::seed(storage.signatures())?;
+        state.foundry_project = storage.settings().get_json(SettingKey::FoundryProject)?;
+        state.signer = storage.settings().get_json(SettingKey::Signer)?;
+        providers.session.cache.install(
+            storage.responses().clone(),
+            storage
+                .settings()
+                .get_json(SettingKey::ExplorerCacheTtls)?
+                .unwrap_or_default(),
+        );
+        state.trace_backends = storage
+            .settings()
+            .get_json(SettingKey::TraceBackends)?
+            .unwrap_or_default();
+        state.fetch_limits = storage
+            .settings()
+            .get_json(SettingKey::FetchLimits)?
+            .unwrap_or_default();
+        state.node_backends = storage
+            .settings()
+            .get_json(SettingKey::NodeBackends)?
+            .unwrap_or_default();
+        state.hidden_chains = storage
+            .settings()
+            .get_json(SettingKey::HiddenChains)?
+            .unwrap_or_default();
+        state.pending_transactions = storage
+            .settings()
+            .get_json(SettingKey::PendingTransactions)?
+            .unwrap_or_default();
+        state.slots = SlotWatch::new(
+            storage
+                .settings()
+                .get_json(SettingKey::PinnedSlots)?
+                .unwrap_or_default(),
+        );
         let mut top_bar = TopBar::default();
         let mut sidebar = Sidebar::default();
         let mut main_view = MainView::default();
@@ -263,12 +694,25 @@ impl App {
         let runtime = Runtime::new()?;
         let runtime_handle = runtime.handle().clone();
         let (message_tx, message_rx) = mpsc::channel();
+        let scheduler = Scheduler::default();
+        let failures = message_tx.clone();
+        storage.start_writer(move |what, err| {
+            let _ = failures.send(Message::StorageFailed {
+                what,
+                error: format!("{err:#}"),
+            });
+        })?;
 
         {
             let mut ctx = AppContext {
                 state: &mut state,
                 storage: &mut storage,
-                commands: CommandBus::new(message_tx.clone(), runtime_handle.clone()),
+                commands: CommandBus::new(
+                    message_tx.clone(),
+                    runtime_handle.clone(),
+                    scheduler.clone(),
+                    providers.clone(),
+                ),
             };
             top_bar.init(&mut ctx)?;
             sidebar.init(&mut ctx)?;
@@ -276,19 +720,24 @@ impl App {
             bottom_bar.init(&mut ctx)?;
         }
 
-        let mut secrets_modal = None;
+        let mut modal = None;
         if state.secrets.etherscan_api_key.is_none() || state.secrets.anvil_rpc_url.is_none() {
-            let mut modal = SecretsModal::new();
+            let mut secrets_modal = SecretsModal::new(&state.hidden_chains);
             {
                 let mut ctx = AppContext {
                     state: &mut state,
                     storage: &mut storage,
-                    commands: CommandBus::new(message_tx.clone(), runtime_handle.clone()),
+                    commands: CommandBus::new(
+                        message_tx.clone(),
+                        runtime_handle.clone(),
+                        scheduler.clone(),
+                        providers.clone(),
+                    ),
                 };
-                modal.init(&mut ctx)?;
+                secrets_modal.init(&mut ctx)?;
             }
             state.navigation.focus_modal();
-            secrets_modal = Some(modal);
+            modal = Some(ActiveModal::Secrets(secrets_modal));
         }
 
         // Hydrate favorites from storage
@@ -323,13 +772,22 @@ impl App {
             });
         }
         sidebar.set_transactions(transaction_refs, state.navigation.sidebar_tab);
-
-        state.selected = sidebar
-            .current_selection(state.navigation.sidebar_tab, 0)
-            .or_else(|| match state.navigation.sidebar_tab {
-                SidebarTab::Addresses => sidebar.current_selection(SidebarTab::Transactions, 0),
-                SidebarTab::Transactions => sidebar.current_selection(SidebarTab::Addresses, 0),
-            });
+        sidebar.set_watchlist(
+            watchlist_refs(&state.watchlist),
+            state.navigation.sidebar_tab,
+        );
+
+        state.selected = match &link {
+            Some(link) => Some(link.entity.clone()),
+            None => sidebar
+                .current_selection(state.navigation.sidebar_tab, 0)
+                .or_else(|| match state.navigation.sidebar_tab {
+                    SidebarTab::Addresses => sidebar.current_selection(SidebarTab::Transactions, 0),
+                    SidebarTab::Transactions | SidebarTab::Watchlist => {
+                        sidebar.current_selection(SidebarTab::Addresses, 0)
+                    }
+                }),
+        };
         if let Some(entity) = state.selected.clone() {
             match entity {
                 SelectedEntity::Address(_) => {
@@ -341,6 +799,14 @@ impl App {
                     state.navigation.main_view_tab = MainViewTab::TransactionSummary;
                 }
             }
+            if let Some(tab) = link
+                .as_ref()
+                .and_then(|link| link.tab.as_deref())
+                .and_then(|tab| deep_link::resolve_tab(tab, &state.custom_tabs))
+            {
+                let mode = state.navigation.main_view_mode;
+                state.navigation.main_view_tab = tab.normalize(mode);
+            }
         }
 
         let mut app = Self {
@@ -354,31 +820,133 @@ impl App {
             runtime,
             message_rx,
             message_tx: message_tx.clone(),
-            secrets_modal,
+            scheduler,
+            providers,
+            modal,
+            hydration_started: None,
+            last_watch_poll: None,
+            watch_poll_in_flight: false,
+            last_watchlist_poll: None,
+            last_log_alert_poll: None,
+            log_alert_poll_in_flight: false,
+            last_threshold_poll: None,
+            threshold_poll_in_flight: false,
+            watchlist_poll_in_flight: false,
+            last_safe_poll: None,
+            safe_poll_in_flight: false,
+            notifier: Notifier::default(),
+            last_hydrated: None,
+            refresh_in_flight: false,
+            last_follow_poll: None,
+            follow_poll_in_flight: false,
+            last_slot_poll: None,
+            slot_poll_in_flight: false,
+            last_prefetch: None,
+            dirty: true,
+            last_draw: Instant::now(),
+            stdout: Vec::new(),
+            clipboard: None,
+            graphics: Graphics::detect(),
+            qr_image: None,
+            pending_screenshot: None,
+            demo_dir: None,
         };
 
         if let Some(entity) = app.state.selected.clone() {
-            app.start_hydration(entity);
+            app.start_hydration(entity, false);
+        }
+        let recovered = app.storage.recovered_journal_entries();
+        if recovered > 0 {
+            app.show_status(format!(
+                "Recovered {recovered} interrupted favorites edit(s) from the journal"
+            ));
         }
 
         Ok(app)
     }
 
-    pub fn run(mut self, mut terminal: DefaultTerminal) -> AppResult<()> {
+    /// Run until quit and return the exports queued for stdout.
+    pub fn run(mut self, mut terminal: DefaultTerminal) -> AppResult<Vec<String>> {
         self.running = true;
+        self.start_plugins();
         while self.running {
             self.tick()?;
-            terminal.draw(|frame| self.render(frame))?;
+            if self.needs_redraw() {
+                let frame = terminal.draw(|frame| self.render(frame))?;
+                self.dirty = false;
+                self.last_draw = Instant::now();
+                if let Some((path, format)) = self.pending_screenshot.take() {
+                    match screenshot::write(frame.buffer, &path, format) {
+                        Ok(()) => self.show_status(format!("Saved screen to {}", path.display())),
+                        Err(err) => self.show_status(format!("Screenshot failed: {err}")),
+                    }
+                }
+                self.sync_qr_image(&mut terminal)?;
+            }
             self.handle_events()?;
         }
+        self.storage.flush();
+        self.state.usage.save(self.storage.settings())?;
+        Ok(self.stdout)
+    }
+
+    /// Lay the QR modal's code out as an image on terminals that can show
+    /// one, over the half blocks just drawn, and take it down when the
+    /// modal closes. Images outlive the cells under them, so this only acts
+    /// when the spot changes.
+    fn sync_qr_image(&mut self, terminal: &mut DefaultTerminal) -> AppResult<()> {
+        let Some(graphics) = self.graphics else {
+            return Ok(());
+        };
+        let wanted = match self.modal.as_ref() {
+            Some(ActiveModal::Qr(modal)) => modal.image_area().map(|area| (area, modal)),
+            _ => None,
+        };
+        if wanted.map(|(area, _)| area) == self.qr_image {
+            return Ok(());
+        }
+        let backend = terminal.backend_mut();
+        if let Some(clear) = graphics.clear_sequence() {
+            backend.write_all(clear.as_bytes())?;
+        }
+        match wanted {
+            Some((area, modal)) => {
+                queue!(backend, MoveTo(area.x, area.y))?;
+                let image = graphics.sequence(modal.matrix(), area.width, area.height);
+                backend.write_all(image.as_bytes())?;
+                backend.flush()?;
+                self.qr_image = Some(area);
+            }
+            None => {
+                backend.flush()?;
+                if graphics.clear_sequence().is_none() {
+                    // Without a delete command the image goes when its
+                    // cells are drawn again.
+                    terminal.clear()?;
+                    self.dirty = true;
+                }
+                self.qr_image = None;
+            }
+        }
         Ok(())
     }
 
+    fn needs_redraw(&self) -> bool {
+        let interval = if self.state.loading.any() {
+            SPINNER_FRAME
+        } else {
+            IDLE_REDRAW
+        };
+        self.dirty || self.state.follow.is_highlighting() || self.last_draw.elapsed() >= interval
+    }
+
     fn render(&mut self, frame: &mut Frame<'_>) {
+        // The compact top bar stacks the selection above the search hint.
+        let top_height = if is_compact(frame.area().width) { 4 } else { 3 };
         let layout = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(3),
+                Constraint::Length(top_height),
                 Constraint::Min(1),
                 Constraint::Length(3),
             ])
@@ -388,33 +956,81 @@ impl App {
         let main_area = layout[1];
         let bottom_area = layout[2];
 
+        let sidebar_width = self.state.layout.sidebar_columns(main_area.width);
         let app_panes = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Length(32), Constraint::Min(1)])
+            .constraints([Constraint::Length(sidebar_width), Constraint::Min(1)])
             .split(main_area);
 
         let sidebar_area = app_panes[0];
         let content_area = app_panes[1];
 
-        let view = AppView { state: &self.state };
+        let view = AppView {
+            state: &self.state,
+            providers: &self.providers,
+        };
 
+        let hints = match self.state.navigation.focused_pane {
+            FocusedPane::Top => self.top_bar.key_hints(&view),
+            FocusedPane::Sidebar => self.sidebar.key_hints(&view),
+            FocusedPane::MainView => self.main_view.key_hints(&view),
+            FocusedPane::BottomBar | FocusedPane::Modal => Vec::new(),
+        };
+        self.bottom_bar.set_hints(hints);
         self.top_bar.render(frame, top_area, &view);
-        self.sidebar.render(frame, sidebar_area, &view);
+        if sidebar_width > 0 {
+            self.sidebar.render(frame, sidebar_area, &view);
+        }
         self.main_view.render(frame, content_area, &view);
         self.bottom_bar.render(frame, bottom_area, &view);
-
-        if let Some(modal) = self.secrets_modal.as_mut() {
-            let area = frame.area();
-            modal.render(frame, area, &view);
+        self.top_bar.render_suggestions(frame, top_area);
+
+        let area = frame.area();
+        match self.modal.as_mut() {
+            Some(ActiveModal::Secrets(modal)) => modal.render(frame, area, &view),
+            Some(ActiveModal::Update(modal)) => modal.render(frame, area, &view),
+            Some(ActiveModal::Diagnostics(modal)) => modal.render(frame, area, &view),
+            Some(ActiveModal::Trash(modal)) => modal.render(frame, area, &view),
+            Some(ActiveModal::Portfolio(modal)) => modal.render(frame, area, &view),
+            Some(ActiveModal::Permissions(modal)) => modal.render(frame, area, &view),
+            Some(ActiveModal::UpgradeImpact(modal)) => modal.render(frame, area, &view),
+            Some(ActiveModal::Health(modal)) => modal.render(frame, area, &view),
+            Some(ActiveModal::Block(modal)) => modal.render(frame, area, &view),
+            Some(ActiveModal::DeployAddress(modal)) => modal.render(frame, area, &view),
+            Some(ActiveModal::Convert(modal)) => modal.render(frame, area, &view),
+            Some(ActiveModal::TypedData(modal)) => modal.render(frame, area, &view),
+            Some(ActiveModal::Write(modal)) => modal.render(frame, area, &view),
+            Some(ActiveModal::Pending(modal)) => modal.render(frame, area, &view),
+            Some(ActiveModal::SafeQueue(modal)) => modal.render(frame, area, &view),
+            Some(ActiveModal::GasAnalytics(modal)) => modal.render(frame, area, &view),
+            Some(ActiveModal::Errors(modal)) => modal.render(frame, area, &view),
+            Some(ActiveModal::Qr(modal)) => modal.render(frame, area, &view),
+            Some(ActiveModal::Scratchpad(modal)) => modal.render(frame, area, &view),
+            Some(ActiveModal::LogAlerts(modal)) => modal.render(frame, area, &view),
+            Some(ActiveModal::Thresholds(modal)) => modal.render(frame, area, &view),
+            Some(ActiveModal::Pipe(modal)) => modal.render(frame, area, &view),
+            Some(ActiveModal::Sessions(modal)) => modal.render(frame, area, &view),
+            None => {}
         }
     }
 
     fn handle_events(&mut self) -> AppResult<()> {
         if event::poll(StdDuration::from_millis(50))? {
             match event::read()? {
-                Event::Key(key) if key.kind == KeyEventKind::Press => self.on_key_event(key)?,
-                Event::Paste(content) => self.on_paste_event(content)?,
-                Event::Mouse(_) | Event::Resize(_, _) => {}
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    self.dirty = true;
+                    self.on_key_event(key)?;
+                }
+                Event::Paste(content) => {
+                    self.dirty = true;
+                    self.on_paste_event(content)?;
+                }
+                Event::Resize(_, _) => {
+                    self.dirty = true;
+                    // The resize clears the screen, image included.
+                    self.qr_image = None;
+                }
+                Event::Mouse(_) => {}
                 _ => {}
             }
         }
@@ -422,11 +1038,99 @@ impl App {
     }
 
     fn on_key_event(&mut self, key: KeyEvent) -> AppResult<()> {
+        if let Some(awaiting) = self.state.macros.take_awaiting() {
+            return self.macro_register(awaiting, key);
+        }
+        let in_modal = matches!(self.state.navigation.focused_pane, FocusedPane::Modal);
+        if !in_modal && !self.state.macros.replaying && macros::is_record_key(&key) {
+            self.toggle_macro_recording();
+            return Ok(());
+        }
+        if !self.sensitive_modal_focused() {
+            self.state.macros.record(key);
+        }
         if matches!(self.state.navigation.focused_pane, FocusedPane::Modal) {
             self.handle_modal_key(key)?;
             return Ok(());
         }
 
+        if let Some(text) = self.state.events.editing.as_mut() {
+            match key.code {
+                KeyCode::Esc => self.state.events.editing = None,
+                KeyCode::Enter => {
+                    self.state.events.filter = text.trim().to_string();
+                    self.state.events.editing = None;
+                    self.state.events.address = None;
+                    self.load_events_if_needed();
+                }
+                KeyCode::Backspace => {
+                    text.pop();
+                }
+                KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => text.push(c),
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        if let Some(text) = self.state.labels.editing.as_mut() {
+            match key.code {
+                KeyCode::Esc => self.state.labels.editing = None,
+                KeyCode::Enter => {
+                    let input = text.clone();
+                    self.state.labels.editing = None;
+                    self.label_selected_address(&input);
+                }
+                KeyCode::Backspace => {
+                    text.pop();
+                }
+                KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => text.push(c),
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        if let Some(text) = self.state.slots.editing.as_mut() {
+            match key.code {
+                KeyCode::Esc => self.state.slots.editing = None,
+                KeyCode::Enter => {
+                    let input = text.clone();
+                    self.state.slots.editing = None;
+                    self.pin_slot(&input);
+                }
+                KeyCode::Backspace => {
+                    text.pop();
+                }
+                KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => text.push(c),
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        if let Some(text) = self.state.command_line.as_mut() {
+            match key.code {
+                KeyCode::Esc => self.state.command_line = None,
+                KeyCode::Enter => {
+                    let input = text.clone();
+                    self.state.command_line = None;
+                    self.run_command(&input);
+                }
+                KeyCode::Backspace => {
+                    text.pop();
+                }
+                KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => text.push(c),
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        if let Some(command) = self.main_view.copy_command(key) {
+            self.main_view_command(MainViewCommand::Copy(command))?;
+            return Ok(());
+        }
+        if self.main_view.is_copying() {
+            return Ok(());
+        }
+
         if self.top_bar.is_search_active() {
             match key.code {
                 KeyCode::Esc => {
@@ -441,6 +1145,14 @@ impl App {
                     self.top_bar_command(TopCommand::Backspace)?;
                     return Ok(());
                 }
+                KeyCode::Down => {
+                    self.top_bar_command(TopCommand::NextSuggestion)?;
+                    return Ok(());
+                }
+                KeyCode::Up => {
+                    self.top_bar_command(TopCommand::PreviousSuggestion)?;
+                    return Ok(());
+                }
                 KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
                     self.top_bar_command(TopCommand::InputChar(c))?;
                     return Ok(());
@@ -454,10 +1166,16 @@ impl App {
             | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => {
                 self.dispatch(Action::Quit)
             }
+            (KeyModifiers::CONTROL, KeyCode::Char('v')) => self.open_clipboard()?,
+            (KeyModifiers::CONTROL, KeyCode::Left) => self.resize_sidebar(false),
+            (KeyModifiers::CONTROL, KeyCode::Right) => self.resize_sidebar(true),
+            (KeyModifiers::CONTROL, KeyCode::Char('b')) => self.toggle_sidebar(),
+            (_, KeyCode::Char('@')) => self.state.macros.await_register(Awaiting::Replay),
             (KeyModifiers::NONE, KeyCode::Char('/')) => {
                 self.dispatch(Action::FocusPane(FocusedPane::Top));
                 self.top_bar_command(TopCommand::ActivateSearch)?;
             }
+            (_, KeyCode::Char(':')) => self.state.command_line = Some(String::new()),
             (KeyModifiers::NONE, KeyCode::Tab) => self.dispatch(Action::FocusNextPane),
             (KeyModifiers::SHIFT, KeyCode::Tab) => self.dispatch(Action::FocusPreviousPane),
             (KeyModifiers::NONE, KeyCode::Char('[')) => {
@@ -511,6 +1229,211 @@ impl App {
             {
                 self.toggle_favorite()?;
             }
+            (KeyModifiers::SHIFT, KeyCode::Char('U')) => self.open_update_modal(),
+            (KeyModifiers::SHIFT, KeyCode::Char('D')) => self.open_diagnostics_modal(),
+            (KeyModifiers::SHIFT, KeyCode::Char('T')) => self.open_trash_modal(),
+            (KeyModifiers::SHIFT, KeyCode::Char('G')) => self.open_portfolio_modal(),
+            (KeyModifiers::SHIFT, KeyCode::Char('P')) => self.open_permissions_modal()?,
+            (KeyModifiers::SHIFT, KeyCode::Char('I')) => self.open_upgrade_impact_modal(),
+            (KeyModifiers::SHIFT, KeyCode::Char('H')) => self.open_health_modal(),
+            (KeyModifiers::SHIFT, KeyCode::Char('C')) => self.open_deploy_address_modal(),
+            (KeyModifiers::SHIFT, KeyCode::Char('R')) => self.cycle_auto_refresh(),
+            (KeyModifiers::NONE, KeyCode::Char('r'))
+                if matches!(self.state.navigation.focused_pane, FocusedPane::MainView)
+                    && matches!(self.active_main_view_tab(), MainViewTab::Custom(_)) =>
+            {
+                self.rerun_custom_tab();
+            }
+            (KeyModifiers::NONE, KeyCode::Char('r'))
+                if matches!(self.state.navigation.focused_pane, FocusedPane::MainView)
+                    && self.state.navigation.main_view_mode == MainViewMode::Address =>
+            {
+                self.retry_failed_sections();
+            }
+            (_, KeyCode::Char('!'))
+                if matches!(self.state.navigation.focused_pane, FocusedPane::MainView)
+                    && self.state.navigation.main_view_mode == MainViewMode::Address =>
+            {
+                self.open_errors_modal();
+            }
+            (KeyModifiers::SHIFT, KeyCode::Char('Q')) => self.open_pending_modal(),
+            (KeyModifiers::SHIFT, KeyCode::Char('O'))
+                if matches!(self.state.navigation.focused_pane, FocusedPane::MainView)
+                    && self.state.navigation.main_view_mode == MainViewMode::Address =>
+            {
+                self.open_qr_modal();
+            }
+            (KeyModifiers::NONE, KeyCode::Char('e'))
+                if matches!(self.state.navigation.focused_pane, FocusedPane::MainView)
+                    && self.state.navigation.main_view_mode == MainViewMode::Address
+                    && self
+                        .state
+                        .navigation
+                        .main_view_tab
+                        .normalize(MainViewMode::Address)
+                        == MainViewTab::AddressEvents =>
+            {
+                self.state.events.editing = Some(self.state.events.filter.clone());
+            }
+            (KeyModifiers::NONE, KeyCode::Char('a'))
+                if matches!(self.state.navigation.focused_pane, FocusedPane::MainView)
+                    && self.state.navigation.main_view_mode == MainViewMode::Address
+                    && self
+                        .state
+                        .navigation
+                        .main_view_tab
+                        .normalize(MainViewMode::Address)
+                        == MainViewTab::AddressEvents =>
+            {
+                self.alert_on_events_filter();
+            }
+            (KeyModifiers::NONE, KeyCode::Char('p'))
+                if matches!(self.state.navigation.focused_pane, FocusedPane::MainView)
+                    && self.state.navigation.main_view_mode == MainViewMode::Address
+                    && self
+                        .state
+                        .navigation
+                        .main_view_tab
+                        .normalize(MainViewMode::Address)
+                        == MainViewTab::AddressStorage =>
+            {
+                self.state.slots.editing = Some(String::new());
+            }
+            (KeyModifiers::NONE, KeyCode::Char(key @ ('p' | 'n')))
+                if matches!(self.state.navigation.focused_pane, FocusedPane::MainView)
+                    && self.state.navigation.main_view_mode == MainViewMode::Transaction =>
+            {
+                self.step_in_block(key == 'n');
+            }
+            (KeyModifiers::SHIFT, KeyCode::Char('N'))
+                if matches!(self.state.navigation.focused_pane, FocusedPane::MainView)
+                    && self.state.navigation.main_view_mode == MainViewMode::Address =>
+            {
+                if let Some(SelectedEntity::Address(addr)) = self.state.selected.as_ref() {
+                    let prompt = self.state.labels.prompt_for(&addr.chain, &addr.address);
+                    self.state.labels.editing = Some(prompt);
+                }
+            }
+            (KeyModifiers::NONE, KeyCode::Char('x'))
+                if matches!(self.state.navigation.focused_pane, FocusedPane::MainView)
+                    && self.state.navigation.main_view_mode == MainViewMode::Address
+                    && self
+                        .state
+                        .navigation
+                        .main_view_tab
+                        .normalize(MainViewMode::Address)
+                        == MainViewTab::AddressStorage =>
+            {
+                self.unpin_selected_slot();
+            }
+            (KeyModifiers::NONE, KeyCode::Char('a'))
+                if matches!(self.state.navigation.focused_pane, FocusedPane::MainView)
+                    && self.state.navigation.main_view_mode == MainViewMode::Transaction
+                    && self
+                        .state
+                        .navigation
+                        .main_view_tab
+                        .normalize(MainViewMode::Transaction)
+                        == MainViewTab::TransactionAccessList =>
+            {
+                self.generate_access_list();
+            }
+            (KeyModifiers::SHIFT, KeyCode::Char('L'))
+                if matches!(self.state.navigation.focused_pane, FocusedPane::MainView)
+                    && self.state.navigation.main_view_mode == MainViewMode::Address
+                    && self
+                        .state
+                        .navigation
+                        .main_view_tab
+                        .normalize(MainViewMode::Address)
+                        == MainViewTab::AddressTransactions =>
+            {
+                self.toggle_follow();
+            }
+            (KeyModifiers::NONE, KeyCode::Char('w'))
+                if matches!(self.state.navigation.focused_pane, FocusedPane::MainView) =>
+            {
+                self.main_view_command(MainViewCommand::CycleLineMode)?;
+            }
+            (KeyModifiers::NONE, KeyCode::Char('v'))
+                if matches!(self.state.navigation.focused_pane, FocusedPane::MainView) =>
+            {
+                self.main_view_command(MainViewCommand::EnterCopyMode)?;
+            }
+            (KeyModifiers::NONE, KeyCode::Char('m'))
+                if matches!(self.state.navigation.focused_pane, FocusedPane::MainView) =>
+            {
+                self.main_view_command(MainViewCommand::PinHighlighted)?;
+            }
+            (KeyModifiers::SHIFT, KeyCode::Char('M')) => self.open_scratchpad_modal(),
+            (_, KeyCode::Char('+')) => self.toggle_watchlist(self.state.selected.clone())?,
+            (KeyModifiers::NONE, KeyCode::Char('n'))
+                if matches!(self.state.navigation.focused_pane, FocusedPane::Sidebar)
+                    && self.state.navigation.sidebar_tab == SidebarTab::Watchlist =>
+            {
+                self.cycle_watchlist_alerts();
+            }
+            (KeyModifiers::NONE, KeyCode::Char('n'))
+                if matches!(self.state.navigation.focused_pane, FocusedPane::MainView)
+                    && self.state.navigation.main_view_mode == MainViewMode::Address
+                    && self
+                        .state
+                        .navigation
+                        .main_view_tab
+                        .normalize(MainViewMode::Address)
+                        == MainViewTab::AddressStorage =>
+            {
+                self.cycle_slot_alerts();
+            }
+            (KeyModifiers::NONE, KeyCode::Char('x'))
+                if matches!(self.state.navigation.focused_pane, FocusedPane::Sidebar)
+                    && self.state.navigation.sidebar_tab == SidebarTab::Watchlist =>
+            {
+                let entry = self.sidebar.active_selection(SidebarTab::Watchlist);
+                if entry.is_some() {
+                    self.toggle_watchlist(entry)?;
+                }
+            }
+            (KeyModifiers::CONTROL, KeyCode::Char('f'))
+                if matches!(self.state.navigation.focused_pane, FocusedPane::MainView) =>
+            {
+                self.main_view_command(MainViewCommand::EnterCopyMode)?;
+                self.main_view_command(MainViewCommand::Copy(CopyCommand::Search))?;
+            }
+            (KeyModifiers::SHIFT, KeyCode::Char('J'))
+                if matches!(self.state.navigation.focused_pane, FocusedPane::MainView) =>
+            {
+                self.export_json(None);
+            }
+            (KeyModifiers::SHIFT, KeyCode::Char('E'))
+                if matches!(self.state.navigation.focused_pane, FocusedPane::MainView)
+                    && self.state.navigation.main_view_mode == MainViewMode::Address =>
+            {
+                self.export_history(None);
+            }
+            (KeyModifiers::NONE, KeyCode::Char('b'))
+                if matches!(self.state.navigation.focused_pane, FocusedPane::MainView) =>
+            {
+                self.export_bindings();
+            }
+            (KeyModifiers::SHIFT, KeyCode::Char('A'))
+                if matches!(self.state.navigation.focused_pane, FocusedPane::MainView)
+                    && self.state.navigation.main_view_mode == MainViewMode::Address =>
+            {
+                self.open_gas_analytics_modal();
+            }
+            (KeyModifiers::SHIFT, KeyCode::Char('S'))
+                if matches!(self.state.navigation.focused_pane, FocusedPane::MainView)
+                    && self.state.navigation.main_view_mode == MainViewMode::Address =>
+            {
+                self.open_safe_queue_modal();
+            }
+            (KeyModifiers::SHIFT, KeyCode::Char('W'))
+                if matches!(self.state.navigation.focused_pane, FocusedPane::MainView)
+                    && self.state.navigation.main_view_mode == MainViewMode::Address =>
+            {
+                self.open_write_modal();
+            }
             _ => {}
         }
         Ok(())
@@ -525,86 +1448,292 @@ impl App {
         Ok(())
     }
 
-    fn handle_modal_key(&mut self, key: KeyEvent) -> AppResult<()> {
-        use crossterm::event::KeyCode;
-
-        if key.modifiers.contains(KeyModifiers::CONTROL)
-            && matches!(key.code, KeyCode::Char('c') | KeyCode::Char('C'))
-        {
-            self.dispatch(Action::Quit);
-            return Ok(());
-        }
-
-        if let Some(command) = SecretsModal::command_from_key(key) {
-            let commands = self.command_bus();
-            let action = if let Some(modal) = self.secrets_modal.as_mut() {
-                let mut ctx = AppContext {
-                    state: &mut self.state,
-                    storage: &mut self.storage,
-                    commands,
-                };
-                modal.update(&command, &mut ctx)?
-            } else {
-                None
-            };
-            if let Some(action) = action {
-                self.dispatch(action);
-            }
+    /// Open whatever the system clipboard holds, skipping the search prompt.
+    fn open_clipboard(&mut self) -> AppResult<()> {
+        match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.get_text()) {
+            Ok(text) => self.open_clipboard_text(text)?,
+            Err(err) => self.show_status(format!("Clipboard unavailable: {err}")),
         }
         Ok(())
     }
 
-    fn handle_modal_paste(&mut self, content: String) -> AppResult<()> {
-        if self.secrets_modal.is_some() {
-            let commands = self.command_bus();
-            let action = if let Some(modal) = self.secrets_modal.as_mut() {
-                let mut ctx = AppContext {
-                    state: &mut self.state,
-                    storage: &mut self.storage,
-                    commands,
-                };
-                modal.update(&SecretsFormCommand::InsertText(content), &mut ctx)?
-            } else {
-                None
-            };
-            if let Some(action) = action {
-                self.dispatch(action);
+    /// Open copied `text`: a deep link, or anything the search prompt takes.
+    fn open_clipboard_text(&mut self, text: String) -> AppResult<()> {
+        match text {
+            text if text.trim().starts_with(deep_link::SCHEME) => match deep_link::parse(&text) {
+                Ok(link) => self.open_link(link),
+                Err(err) => self.show_status(err),
+            },
+            text if !text.trim().is_empty() => {
+                self.state.usage.record_feature("clipboard open");
+                self.top_bar_command(TopCommand::QuickOpen(text))?;
             }
+            _ => self.show_status("Clipboard is empty"),
         }
         Ok(())
     }
 
-    fn handle_search_paste(&mut self, content: String) -> AppResult<()> {
-        for ch in content.chars() {
-            if matches!(ch, '\r' | '\n') {
-                continue;
+    fn copy_to_clipboard(&mut self, text: String) {
+        // On X11 the text is served by the clipboard handle, so it is kept for
+        // the session rather than dropped right after copying.
+        if self.clipboard.is_none() {
+            match arboard::Clipboard::new() {
+                Ok(clipboard) => self.clipboard = Some(clipboard),
+                Err(err) => {
+                    self.show_status(format!("Clipboard unavailable: {err}"));
+                    return;
+                }
             }
-            self.top_bar_command(TopCommand::InputChar(ch))?;
         }
-        Ok(())
+        let copied = self
+            .clipboard
+            .as_mut()
+            .map(|clipboard| clipboard.set_text(text));
+        match copied {
+            Some(Ok(())) => self.show_status("Copied to clipboard"),
+            Some(Err(err)) => self.show_status(format!("Clipboard unavailable: {err}")),
+            None => {}
+        }
     }
 
-    async fn hydrate_address(addr: AddressRef, secrets: SecretsState) -> HydratedAddress {
-        const TRANSACTION_FETCH_LIMIT: usize = 25;
-        let mut rpc_url = secrets.anvil_rpc_url.clone();
-        if rpc_url.is_none() {
-            if let Ok(env_url) = std::env::var("ANVIL_RPC_URL") {
-                if !env_url.trim().is_empty() {
-                    rpc_url = Some(env_url);
-                }
-            }
+    /// Details of the sections of the selected address that failed to load.
+    fn open_errors_modal(&mut self) {
+        if self.modal.is_some() {
+            return;
         }
+        let Some(SelectedEntity::Address(addr)) = self.state.selected.as_ref() else {
+            return;
+        };
+        let failures = match self.state.current_address.as_ref() {
+            Some(data) if data.identifier == addr.address && !data.failures.is_empty() => {
+                data.failures.clone()
+            }
+            _ => {
+                self.show_status("Nothing failed to load");
+                return;
+            }
+        };
+        let subject = format!("{} on {}", addr.address, addr.chain);
+        self.open_modal(ActiveModal::Errors(ErrorsModal::new(subject, failures)));
+    }
 
-        let mut overview: Option<AccountOverview> = None;
-        let mut note: Option<String> = None;
-        let mut block_note: Option<String> = None;
+    fn handle_modal_key(&mut self, key: KeyEvent) -> AppResult<()> {
+        use crossterm::event::KeyCode;
 
-        if let Some(rpc_value) = rpc_url.clone() {
-            match addr.address.parse::<Address>() {
+        if key.modifiers.contains(KeyModifiers::CONTROL)
+            && matches!(key.code, KeyCode::Char('c') | KeyCode::Char('C'))
+        {
+            self.dispatch(Action::Quit);
+            return Ok(());
+        }
+
+        let commands = self.command_bus();
+        let mut ctx = AppContext {
+            state: &mut self.state,
+            storage: &mut self.storage,
+            commands,
+        };
+        let action = match self.modal.as_mut() {
+            Some(ActiveModal::Secrets(modal)) => match SecretsModal::command_from_key(key) {
+                Some(command) => modal.update(&command, &mut ctx)?,
+                None => None,
+            },
+            Some(ActiveModal::Update(modal)) => match UpdateModal::command_from_key(key) {
+                Some(command) => modal.update(&command, &mut ctx)?,
+                None => None,
+            },
+            Some(ActiveModal::Diagnostics(modal)) => {
+                match DiagnosticsModal::command_from_key(key) {
+                    Some(command) => modal.update(&command, &mut ctx)?,
+                    None => None,
+                }
+            }
+            Some(ActiveModal::Trash(modal)) => match TrashModal::command_from_key(key) {
+                Some(command) => modal.update(&command, &mut ctx)?,
+                None => None,
+            },
+            Some(ActiveModal::Portfolio(modal)) => match modal.command_from_key(key) {
+                Some(command) => modal.update(&command, &mut ctx)?,
+                None => None,
+            },
+            Some(ActiveModal::Permissions(modal)) => {
+                match PermissionsModal::command_from_key(key) {
+                    Some(command) => modal.update(&command, &mut ctx)?,
+                    None => None,
+                }
+            }
+            Some(ActiveModal::UpgradeImpact(modal)) => match modal.command_from_key(key) {
+                Some(command) => modal.update(&command, &mut ctx)?,
+                None => None,
+            },
+            Some(ActiveModal::Health(modal)) => match HealthModal::command_from_key(key) {
+                Some(command) => modal.update(&command, &mut ctx)?,
+                None => None,
+            },
+            Some(ActiveModal::Block(modal)) => match modal.command_from_key(key) {
+                Some(command) => modal.update(&command, &mut ctx)?,
+                None => None,
+            },
+            Some(ActiveModal::DeployAddress(modal)) => {
+                match DeployAddressModal::command_from_key(key) {
+                    Some(command) => modal.update(&command, &mut ctx)?,
+                    None => None,
+                }
+            }
+            Some(ActiveModal::Convert(modal)) => match ConvertModal::command_from_key(key) {
+                Some(command) => modal.update(&command, &mut ctx)?,
+                None => None,
+            },
+            Some(ActiveModal::TypedData(modal)) => match TypedDataModal::command_from_key(key) {
+                Some(command) => modal.update(&command, &mut ctx)?,
+                None => None,
+            },
+            Some(ActiveModal::Write(modal)) => match modal.command_from_key(key) {
+                Some(command) => modal.update(&command, &mut ctx)?,
+                None => None,
+            },
+            Some(ActiveModal::Pending(modal)) => match modal.command_from_key(key) {
+                Some(command) => modal.update(&command, &mut ctx)?,
+                None => None,
+            },
+            Some(ActiveModal::SafeQueue(modal)) => match SafeQueueModal::command_from_key(key) {
+                Some(command) => modal.update(&command, &mut ctx)?,
+                None => None,
+            },
+            Some(ActiveModal::GasAnalytics(modal)) => {
+                match GasAnalyticsModal::command_from_key(key) {
+                    Some(command) => modal.update(&command, &mut ctx)?,
+                    None => None,
+                }
+            }
+            Some(ActiveModal::Errors(modal)) => match ErrorsModal::command_from_key(key) {
+                Some(command) => modal.update(&command, &mut ctx)?,
+                None => None,
+            },
+            Some(ActiveModal::Qr(modal)) => match QrModal::command_from_key(key) {
+                Some(command) => modal.update(&command, &mut ctx)?,
+                None => None,
+            },
+            Some(ActiveModal::Scratchpad(modal)) => match ScratchpadModal::command_from_key(key) {
+                Some(command) => modal.update(&command, &mut ctx)?,
+                None => None,
+            },
+            Some(ActiveModal::LogAlerts(modal)) => match modal.command_from_key(key) {
+                Some(command) => modal.update(&command, &mut ctx)?,
+                None => None,
+            },
+            Some(ActiveModal::Thresholds(modal)) => match modal.command_from_key(key) {
+                Some(command) => modal.update(&command, &mut ctx)?,
+                None => None,
+            },
+            Some(ActiveModal::Pipe(modal)) => match PipeModal::command_from_key(key) {
+                Some(command) => modal.update(&command, &mut ctx)?,
+                None => None,
+            },
+            Some(ActiveModal::Sessions(modal)) => match modal.command_from_key(key) {
+                Some(command) => modal.update(&command, &mut ctx)?,
+                None => None,
+            },
+            None => None,
+        };
+        if let Some(action) = action {
+            // Opening an entity from a modal leaves it for the main view.
+            if matches!(action, Action::SelectionChanged(_)) {
+                self.close_modal();
+                self.dispatch(Action::FocusPane(FocusedPane::MainView));
+            }
+            self.dispatch(action);
+        }
+        Ok(())
+    }
+
+    fn handle_modal_paste(&mut self, content: String) -> AppResult<()> {
+        if let Some(ActiveModal::UpgradeImpact(modal)) = self.modal.as_mut() {
+            modal.insert_text(&content);
+            return Ok(());
+        }
+        if let Some(ActiveModal::DeployAddress(modal)) = self.modal.as_mut() {
+            modal.insert_text(&content);
+            return Ok(());
+        }
+        if let Some(ActiveModal::Convert(modal)) = self.modal.as_mut() {
+            modal.insert_text(&content);
+            return Ok(());
+        }
+        if let Some(ActiveModal::TypedData(modal)) = self.modal.as_mut() {
+            modal.insert_text(&content);
+            return Ok(());
+        }
+        if let Some(ActiveModal::Write(modal)) = self.modal.as_mut() {
+            modal.insert_text(&content);
+            return Ok(());
+        }
+        if let Some(ActiveModal::Pending(modal)) = self.modal.as_mut() {
+            modal.insert_text(&content);
+            return Ok(());
+        }
+        let commands = self.command_bus();
+        if let Some(ActiveModal::Secrets(modal)) = self.modal.as_mut() {
+            let mut ctx = AppContext {
+                state: &mut self.state,
+                storage: &mut self.storage,
+                commands,
+            };
+            if let Some(action) =
+                modal.update(&SecretsFormCommand::InsertText(content), &mut ctx)?
+            {
+                self.dispatch(action);
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_search_paste(&mut self, content: String) -> AppResult<()> {
+        for ch in content.chars() {
+            if matches!(ch, '\r' | '\n') {
+                continue;
+            }
+            self.top_bar_command(TopCommand::InputChar(ch))?;
+        }
+        Ok(())
+    }
+
+    async fn hydrate_address(
+        addr: AddressRef,
+        secrets: SecretsState,
+        project: Option<PathBuf>,
+        limits: FetchLimits,
+        providers: Providers,
+    ) -> HydratedAddress {
+        let currency = chains::currency(&addr.chain);
+        let mut rpc_url = secrets.rpc_url(&addr.chain).map(str::to_string);
+        if rpc_url.is_none()
+            && let Ok(env_url) = std::env::var("ANVIL_RPC_URL")
+            && !env_url.trim().is_empty()
+        {
+            rpc_url = Some(env_url);
+        }
+
+        let mut overview: Option<AccountOverview> = None;
+        let mut note: Option<String> = None;
+        let mut block_note: Option<String> = None;
+        let mut chain_warning: Option<String> = None;
+        let mut failures = Vec::new();
+
+        if let Some(rpc_value) = rpc_url.clone() {
+            if let Ok(Ok(chain_id)) = timeout(
+                limits.probe_timeout(),
+                providers.accounts.chain_id(&rpc_value),
+            )
+            .await
+            {
+                chain_warning = chain_mismatch(&addr.chain, chain_id);
+            }
+            match addr.address.parse::<Address>() {
                 Ok(parsed) => {
                     match timeout(
-                        Duration::from_secs(10),
-                        fetch_account_overview(&rpc_value, parsed),
+                        limits.request_timeout(),
+                        providers.accounts.account_overview(&rpc_value, parsed),
                     )
                     .await
                     {
@@ -613,25 +1742,37 @@ impl App {
                             overview = Some(data);
                         }
                         Ok(Err(error)) => {
-                            note = Some(format!("Failed to load account data: {error}"));
-                            if let Ok(result) =
-                                timeout(Duration::from_secs(4), fetch_latest_block(&rpc_value))
-                                    .await
+                            failures.push(SectionFailure::new(
+                                AddressSection::Account,
+                                ErrorDetail::from_report(rpc_source(&rpc_value), &error),
+                            ));
+                            if let Ok(result) = timeout(
+                                limits.probe_timeout(),
+                                providers.accounts.latest_block(&rpc_value),
+                            )
+                            .await
+                                && let Ok(block) = result
                             {
-                                if let Ok(block) = result {
-                                    block_note = Some(format!("Latest block observed: {block}"));
-                                }
+                                block_note = Some(format!("Latest block observed: {block}"));
                             }
                         }
                         Err(_) => {
-                            note = Some(format!("Account query to {rpc_value} timed out"));
-                            if let Ok(result) =
-                                timeout(Duration::from_secs(4), fetch_latest_block(&rpc_value))
-                                    .await
+                            failures.push(SectionFailure::new(
+                                AddressSection::Account,
+                                ErrorDetail::from_report(
+                                    rpc_source(&rpc_value),
+                                    &TimedOut(format!("account query to {rpc_value} timed out"))
+                                        .into(),
+                                ),
+                            ));
+                            if let Ok(result) = timeout(
+                                limits.probe_timeout(),
+                                providers.accounts.latest_block(&rpc_value),
+                            )
+                            .await
+                                && let Ok(block) = result
                             {
-                                if let Ok(block) = result {
-                                    block_note = Some(format!("Latest block observed: {block}"));
-                                }
+                                block_note = Some(format!("Latest block observed: {block}"));
                             }
                         }
                     }
@@ -644,26 +1785,154 @@ impl App {
             note = Some("Configure an Anvil RPC endpoint to load account data.".into());
         }
 
-        let transactions_result = fetch_address_transactions(
-            &addr,
-            secrets.etherscan_api_key.as_deref(),
-            TRANSACTION_FETCH_LIMIT,
-        )
-        .await;
+        let transactions_result = providers
+            .explorer
+            .address_transactions(
+                &addr,
+                secrets.explorer_api_key(&addr.chain),
+                rpc_url.as_deref(),
+                limits.transactions,
+            )
+            .await;
+
+        let is_contract = overview
+            .as_ref()
+            .map(|summary| summary.is_contract)
+            .unwrap_or(false);
+        // A local build that matches the deployed code beats asking the
+        // explorer, which rarely knows devnet contracts.
+        let local_result = match (project, overview.as_ref()) {
+            (Some(project), Some(overview)) if is_contract => {
+                Some(artifacts::match_code(project, overview.code.to_vec()).await)
+            }
+            _ => None,
+        };
+        let source_result = if is_contract && !matches!(local_result, Some(Ok(Some(_)))) {
+            Some(
+                providers
+                    .explorer
+                    .contract_source(&addr, secrets.explorer_api_key(&addr.chain))
+                    .await,
+            )
+        } else {
+            None
+        };
+        let creation_result = if is_contract {
+            Some(
+                providers
+                    .explorer
+                    .contract_creation(
+                        &addr,
+                        secrets.explorer_api_key(&addr.chain),
+                        rpc_url.as_deref(),
+                    )
+                    .await,
+            )
+        } else {
+            None
+        };
+
+        // Staking payouts arrive in block bodies, not as transactions.
+        let withdrawals_result = providers
+            .explorer
+            .beacon_withdrawals(
+                &addr,
+                secrets.explorer_api_key(&addr.chain),
+                withdrawals::WITHDRAWAL_FETCH_LIMIT,
+            )
+            .await;
 
         let mut hydrated = build_address_view(addr, overview, note, rpc_url, block_note);
+        hydrated.failures = failures;
+        if let Some(warning) = chain_warning {
+            hydrated.info.insert(0, warning);
+        }
+
+        match local_result {
+            Some(Ok(Some(source))) => {
+                hydrated.info.push(format!(
+                    "Local contract: {} ({}) • bytecode matches a {} artifact",
+                    source.name, source.compiler, source.source_label
+                ));
+                hydrated.contract_source = Some(source);
+            }
+            Some(Err(err)) => hydrated.info.push(format!("Local artifacts: {err}")),
+            Some(Ok(None)) | None => {}
+        }
+
+        match source_result {
+            Some(Ok(Some(source))) => {
+                hydrated.info.push(format!(
+                    "Verified contract: {} ({}) via {} • {}",
+                    source.name,
+                    source.compiler,
+                    source.source_label,
+                    source.match_type.label()
+                ));
+                hydrated.contract_source = Some(source);
+            }
+            Some(Ok(None)) => hydrated
+                .info
+                .push("Contract source is not verified on the explorer or Sourcify.".into()),
+            Some(Err(TransactionFetchError::MissingApiKey)) => hydrated
+                .info
+                .push("Add an Etherscan API key to load verified source.".into()),
+            Some(Err(TransactionFetchError::UnsupportedChain(_))) | None => {}
+            Some(Err(err)) => hydrated
+                .failures
+                .push(SectionFailure::fetch(AddressSection::Source, &err)),
+        }
+
+        match creation_result {
+            Some(Ok(Some(creation))) => hydrated.creation = Some(creation),
+            Some(Ok(None)) => hydrated
+                .info
+                .push("Deployer unknown (genesis allocation or not indexed).".into()),
+            Some(Err(
+                TransactionFetchError::MissingApiKey | TransactionFetchError::UnsupportedChain(_),
+            ))
+            | None => {}
+            Some(Err(err)) => hydrated
+                .failures
+                .push(SectionFailure::fetch(AddressSection::Creation, &err)),
+        }
+
+        let withdrawal_count = match withdrawals_result {
+            Ok(Some(withdrawals)) => {
+                hydrated.info.extend(withdrawals::info_lines(&withdrawals));
+                withdrawals.len()
+            }
+            Ok(None)
+            | Err(
+                TransactionFetchError::MissingApiKey | TransactionFetchError::UnsupportedChain(_),
+            ) => 0,
+            Err(err) => {
+                hydrated
+                    .failures
+                    .push(SectionFailure::fetch(AddressSection::Withdrawals, &err));
+                0
+            }
+        };
 
         match transactions_result {
             Ok((entries, source)) => {
                 let rows: Vec<AddressTransactionRow> = entries
                     .iter()
-                    .map(|tx| AddressTransactionRow::from_transaction(&hydrated.identifier, tx))
+                    .map(|tx| {
+                        AddressTransactionRow::from_transaction(&hydrated.identifier, tx, currency)
+                    })
                     .collect();
                 if rows.is_empty() {
                     hydrated.transactions = vec![format!(
                         "No transactions available via {} ({}).",
                         source.label, source.api_version
                     )];
+                    if withdrawal_count > 0 {
+                        hydrated.transactions.push(format!(
+                            "Its balance comes from {withdrawal_count} beacon withdrawal(s) \
+                             instead; see the Info tab."
+                        ));
+                    }
                     hydrated.transactions_table = None;
                 } else {
                     hydrated.transactions = vec![format!(
@@ -671,12 +1940,12 @@ impl App {
                         rows.len(),
                         source.label,
                         source.api_version,
-                        TRANSACTION_FETCH_LIMIT
+                        limits.transactions
                     )];
                     hydrated.transactions_table = Some(AddressTransactionsTable {
                         source_label: source.label.into(),
                         source_api_version: source.api_version.into(),
-                        limit: TRANSACTION_FETCH_LIMIT,
+                        limit: limits.transactions,
                         rows,
                     });
                 }
@@ -690,12 +1959,15 @@ impl App {
             }
             Err(TransactionFetchError::UnsupportedChain(chain)) => {
                 hydrated.transactions = vec![format!(
-                    "No Etherscan-compatible explorer configured for chain {chain}."
+                    "No explorer (Etherscan or Blockscout) configured for chain {chain}."
                 )];
                 hydrated.transactions_table = None;
             }
             Err(err) => {
-                hydrated.transactions = vec![format!("Failed to load transactions: {err}")];
+                hydrated
+                    .failures
+                    .push(SectionFailure::fetch(AddressSection::Transactions, &err));
+                hydrated.transactions = Vec::new();
                 hydrated.transactions_table = None;
             }
         };
@@ -703,20 +1975,70 @@ impl App {
         hydrated
     }
 
+    /// What a modal's `update` gets, for driving one outside the App.
+    #[cfg(test)]
+    pub(crate) fn context(&mut self) -> AppContext<'_> {
+        let commands = self.command_bus();
+        AppContext {
+            state: &mut self.state,
+            storage: &mut self.storage,
+            commands,
+        }
+    }
+
+    /// What a modal's `render` gets, for rendering one outside the App.
+    #[cfg(test)]
+    pub(crate) fn view(&self) -> AppView<'_> {
+        AppView {
+            state: &self.state,
+            providers: &self.providers,
+        }
+    }
+
     #[cfg(test)]
     fn secrets_modal_mut(&mut self) -> Option<&mut SecretsModal> {
-        self.secrets_modal.as_mut()
+        match self.modal.as_mut() {
+            Some(ActiveModal::Secrets(modal)) => Some(modal),
+            _ => None,
+        }
     }
 
     fn dispatch(&mut self, action: Action) {
+        self.dirty = true;
         match action {
             Action::Quit => self.running = false,
-            Action::FocusPane(pane) => self.state.navigation.focus_pane(pane),
-            Action::FocusNextPane => self.state.navigation.focus_next(),
-            Action::FocusPreviousPane => self.state.navigation.focus_previous(),
+            Action::FocusPane(pane) => {
+                self.state.navigation.focus_pane(pane);
+                self.reveal_focused_sidebar();
+            }
+            Action::FocusNextPane => {
+                self.state.navigation.focus_next();
+                self.reveal_focused_sidebar();
+            }
+            Action::FocusPreviousPane => {
+                self.state.navigation.focus_previous();
+                self.reveal_focused_sidebar();
+            }
             Action::SelectionChanged(entity) => {
+                if !matches!(&entity, SelectedEntity::Address(addr) if self.state.follow.is_following(&addr.address))
+                {
+                    self.state.follow.stop();
+                }
+                self.state
+                    .plugins
+                    .broadcast(&PluginEvent::selection_changed(&entity));
+                // Choosing the selection again asks for its latest activity.
+                let reselected = self.state.selected.as_ref() == Some(&entity);
                 self.state.selected = Some(entity.clone());
                 self.state.search_error = None;
+                self.state.events = EventsView::default();
+                self.state.fees = FeesView::default();
+                self.state.access_list = AccessListView::default();
+                self.state.raw = RawView::default();
+                self.state.transfers = TransfersView::default();
+                self.state.balance_diff = BalanceDiffView::default();
+                self.state.slots.selected_index = 0;
+                self.state.info_link_index = 0;
                 match entity {
                     SelectedEntity::Address(_) => {
                         self.state.address_transactions_view.reset();
@@ -728,14 +2050,27 @@ impl App {
                         self.state.navigation.main_view_tab = MainViewTab::TransactionSummary;
                     }
                 }
-                self.start_hydration(entity);
+                self.start_hydration(entity, reselected);
             }
             Action::LoadingStarted(pane) => self.state.loading.set_loading(pane, true),
             Action::LoadingFinished(pane) => self.state.loading.set_loading(pane, false),
             Action::CloseModal => self.close_modal(),
+            Action::FavoriteRestored(entry) => self.restore_favorite(entry),
+            Action::CopyToClipboard(text) => self.copy_to_clipboard(text),
+            Action::PinNote { text, source } => self.pin_note(text, source),
+            Action::SwitchSession(name) => {
+                self.close_modal();
+                self.switch_session(&name);
+            }
             Action::SecretsSaved => {
                 self.close_modal();
                 self.show_status("Secrets updated");
+                // Prefetched favorites were loaded through the old endpoints.
+                self.state.prefetch = PrefetchCache::default();
+                // Reload with the new endpoints so a chain mismatch shows up immediately.
+                if let Some(entity) = self.state.selected.clone() {
+                    self.start_hydration(entity, false);
+                }
             }
         }
     }
@@ -765,7 +2100,8 @@ impl App {
             FocusedPane::MainView => match movement {
                 Movement::Up => self.main_view_command(MainViewCommand::MoveSelectionUp)?,
                 Movement::Down => self.main_view_command(MainViewCommand::MoveSelectionDown)?,
-                Movement::Left | Movement::Right => {}
+                Movement::Left => self.main_view_command(MainViewCommand::ScrollLeft)?,
+                Movement::Right => self.main_view_command(MainViewCommand::ScrollRight)?,
             },
             FocusedPane::Top | FocusedPane::BottomBar | FocusedPane::Modal => {}
         }
@@ -811,50 +2147,202 @@ impl App {
         Ok(())
     }
 
+    /// Scheduler host for work against `chain`'s RPC endpoint.
+    fn rpc_host(&self, chain: &str) -> String {
+        host_key(self.state.secrets.rpc_url(chain), chain)
+    }
+
     fn command_bus(&self) -> CommandBus {
         let handle = self.runtime.handle().clone();
-        CommandBus::new(self.message_tx.clone(), handle)
+        CommandBus::new(
+            self.message_tx.clone(),
+            handle,
+            self.scheduler.clone(),
+            self.providers.clone(),
+        )
+    }
+
+    /// Show `modal` unless another one is already open.
+    fn open_modal(&mut self, modal: ActiveModal) {
+        if self.modal.is_some() {
+            return;
+        }
+        self.modal = Some(modal);
+        self.state.navigation.focus_modal();
     }
 
     fn close_modal(&mut self) {
-        self.secrets_modal = None;
+        self.modal = None;
         self.state.navigation.restore_focus_after_modal();
     }
 
-    fn show_status(&mut self, message: impl Into<String>) {
-        if let Err(err) = self.top_bar_command(TopCommand::ShowStatus(message.into())) {
-            eprintln!("failed to update status: {err:?}");
+    /// `Ctrl-r`: stop the running recording, or ask for a register to start
+    /// one in.
+    fn toggle_macro_recording(&mut self) {
+        match self.state.macros.stop() {
+            Some((register, count)) => self.show_status(format!(
+                "Recorded {count} key(s) into @{register} • @{register} replays"
+            )),
+            None => {
+                self.state.macros.await_register(Awaiting::Record);
+                self.show_status("Record a macro into register a-z…");
+            }
+        }
+    }
+
+    /// Whether a dialog taking a passphrase, a signature or credentials has
+    /// focus. Macros neither record the keys typed there nor replay into it.
+    fn sensitive_modal_focused(&self) -> bool {
+        matches!(self.state.navigation.focused_pane, FocusedPane::Modal)
+            && matches!(
+                self.modal,
+                Some(ActiveModal::Write(_) | ActiveModal::Pending(_) | ActiveModal::Secrets(_))
+            )
+    }
+
+    /// The register key after `Ctrl-r` or `@`: start recording, or feed the
+    /// register's keys back through the key handler.
+    fn macro_register(&mut self, awaiting: Awaiting, key: KeyEvent) -> AppResult<()> {
+        let KeyCode::Char(register) = key.code else {
+            return Ok(());
+        };
+        match awaiting {
+            Awaiting::Record => {
+                if self.state.macros.start(register) {
+                    self.show_status(format!("Recording @{register} • Ctrl-r stops"));
+                } else {
+                    self.show_status("Macro registers are a-z");
+                }
+            }
+            Awaiting::Replay => {
+                // A recording that replays a macro keeps both keys.
+                self.state.macros.record(key);
+                let keys = match self.state.macros.replay(register) {
+                    Ok((_, keys)) => keys,
+                    Err(err) => {
+                        self.show_status(err);
+                        return Ok(());
+                    }
+                };
+                self.state.usage.record_feature("macro");
+                self.state.macros.replaying = true;
+                let mut outcome = Ok(());
+                for key in keys {
+                    if self.sensitive_modal_focused() {
+                        self.show_status(format!(
+                            "Stopped @{register}: macros do not type into signing or secrets dialogs"
+                        ));
+                        break;
+                    }
+                    outcome = self.on_key_event(key);
+                    if outcome.is_err() || !self.running {
+                        break;
+                    }
+                }
+                self.state.macros.replaying = false;
+                outcome?;
+            }
         }
+        Ok(())
+    }
+
+    fn show_status(&mut self, message: impl Into<String>) {
+        let _ = self.top_bar_command(TopCommand::ShowStatus(message.into()));
     }
 
-    fn start_hydration(&mut self, entity: SelectedEntity) {
+    /// Load the selection, with `fresh` reading address history past the
+    /// explorer cache.
+    fn start_hydration(&mut self, entity: SelectedEntity, fresh: bool) {
         match entity {
-            SelectedEntity::Address(addr) => self.start_address_hydration(addr),
+            SelectedEntity::Address(addr) => self.start_address_hydration(addr, fresh),
             SelectedEntity::Transaction(tx) => {
                 let mut preview = self.state.pending_transaction_preview.take();
                 if preview.is_none() {
                     preview = self.state.transaction_preview_cache.get(&tx.hash).cloned();
+                    self.providers
+                        .rpc
+                        .stats()
+                        .record_cache_lookup("transaction preview", preview.is_some());
                 }
                 self.start_transaction_hydration(tx, preview);
             }
         }
     }
 
-    fn start_address_hydration(&mut self, addr: AddressRef) {
+    fn start_address_hydration(&mut self, addr: AddressRef, fresh: bool) {
+        self.state.usage.record_feature("view address");
+        let cached = match self
+            .state
+            .prefetch
+            .get(&SelectedEntity::Address(addr.clone()))
+        {
+            Some((Prefetched::Address(data), fetched_at)) => Some((data.clone(), fetched_at)),
+            _ => None,
+        };
+        self.providers
+            .rpc
+            .stats()
+            .record_cache_lookup("favorite prefetch", cached.is_some());
+        if let Some((data, fetched_at)) = cached {
+            self.hydration_started = None;
+            self.refresh_in_flight = false;
+            self.show_hydrated_address(&addr, *data, true);
+            self.show_prefetched(fetched_at);
+            if fetched_at.elapsed() >= prefetch::PREFETCH_INTERVAL {
+                self.refresh_in_flight = true;
+                self.spawn_address_hydration(addr, true);
+            }
+            return;
+        }
+        self.hydration_started = Some(Instant::now());
+        self.refresh_in_flight = false;
         self.state.current_address = None;
         self.state.loading.set_loading(FocusedPane::MainView, true);
         self.show_status(format!(
             "Fetching latest activity for {}",
             short_hex(&addr.address)
         ));
+        self.spawn_address_hydration(addr, fresh);
+    }
+
+    /// The app's providers, with `chain`'s explorer timeout and account data
+    /// read over GraphQL when `chain` is set to it.
+    fn providers_for(&self, chain: &str) -> Providers {
+        let limits = fetch_limits::for_chain(&self.state.fetch_limits, chain);
+        let mut providers = self
+            .providers
+            .with_explorer_timeout(limits.explorer_timeout());
+        if graphql::for_chain(&self.state.node_backends, chain) == graphql::NodeBackend::Graphql {
+            providers.accounts = Arc::new(graphql::GraphqlAccountData(providers.rpc.clone()));
+        }
+        providers
+    }
+
+    fn spawn_address_hydration(&self, addr: AddressRef, fresh: bool) {
         let bus = self.command_bus();
         let secrets = self.state.secrets.clone();
-        bus.spawn_async(move || {
+        let project = self.state.foundry_project.clone();
+        let limits = fetch_limits::for_chain(&self.state.fetch_limits, &addr.chain);
+        let mut providers = self.providers_for(&addr.chain);
+        if fresh {
+            providers = providers.fresh_history();
+        }
+        let host = self.rpc_host(&addr.chain);
+        bus.spawn_scheduled(host, Priority::Foreground, move || {
             let addr_ref = addr.clone();
             let secrets_clone = secrets.clone();
+            let project = project.clone();
+            let providers = providers.clone();
             async move {
-                let data = Self::hydrate_address(addr_ref.clone(), secrets_clone).await;
-                Message::AddressHydrated(data)
+                let data = Self::hydrate_address(
+                    addr_ref.clone(),
+                    secrets_clone,
+                    project,
+                    limits,
+                    providers,
+                )
+                .await;
+                Message::AddressHydrated(Box::new(data))
             }
         });
     }
@@ -864,6 +2352,34 @@ impl App {
         tx: TransactionRef,
         preview: Option<AddressTransactionRow>,
     ) {
+        self.state.usage.record_feature("view transaction");
+        let cached = match self
+            .state
+            .prefetch
+            .get(&SelectedEntity::Transaction(tx.clone()))
+        {
+            Some((Prefetched::Transaction(data), fetched_at)) => Some((data.clone(), fetched_at)),
+            _ => None,
+        };
+        self.providers
+            .rpc
+            .stats()
+            .record_cache_lookup("favorite prefetch", cached.is_some());
+        if let Some((data, fetched_at)) = cached {
+            self.hydration_started = None;
+            self.refresh_in_flight = false;
+            self.state.current_transaction = Some(*data);
+            self.announce_transaction();
+            self.dispatch(Action::LoadingFinished(FocusedPane::MainView));
+            self.show_prefetched(fetched_at);
+            if fetched_at.elapsed() >= prefetch::PREFETCH_INTERVAL {
+                self.refresh_in_flight = true;
+                self.spawn_transaction_hydration(tx, preview);
+            }
+            return;
+        }
+        self.hydration_started = Some(Instant::now());
+        self.refresh_in_flight = false;
         self.state.current_transaction = None;
         self.state.loading.set_loading(FocusedPane::MainView, true);
         self.show_status(format!("Loading transaction {}", short_hex(&tx.hash)));
@@ -872,85 +2388,228 @@ impl App {
                 .transaction_preview_cache
                 .insert(row.hash.clone(), row.clone());
         }
+        self.spawn_transaction_hydration(tx, preview);
+    }
+
+    /// Load the selected address again in the background after some of its
+    /// sections failed. Sections that load keep their data if the retry fails.
+    fn retry_failed_sections(&mut self) {
+        let Some(SelectedEntity::Address(addr)) = self.state.selected.clone() else {
+            return;
+        };
+        let Some(failed) = self
+            .state
+            .current_address
+            .as_ref()
+            .filter(|data| data.identifier == addr.address && !data.failures.is_empty())
+            .map(|data| {
+                data.failures
+                    .iter()
+                    .map(|failure| failure.section.label().to_ascii_lowercase())
+                    .collect::<Vec<_>>()
+            })
+        else {
+            return;
+        };
+        if self.refresh_in_flight || self.state.loading.main_view.is_loading {
+            self.show_status("Already reloading");
+            return;
+        }
+        self.refresh_in_flight = true;
+        self.show_status(format!("Retrying {}", failed.join(", ")));
+        self.spawn_address_hydration(addr, true);
+    }
+
+    /// Note in the status bar that the selection came from the prefetch
+    /// cache, and let auto-refresh count its age from when it was fetched.
+    fn show_prefetched(&mut self, fetched_at: Instant) {
+        self.last_hydrated = Some(fetched_at);
+        self.show_status(format!(
+            "Showing favorite prefetched {} ago",
+            prefetch::age_label(fetched_at.elapsed())
+        ));
+    }
+
+    fn spawn_transaction_hydration(
+        &self,
+        tx: TransactionRef,
+        preview: Option<AddressTransactionRow>,
+    ) {
         let bus = self.command_bus();
-        bus.spawn_async(move || {
-            let tx_ref = tx.clone();
-            let preview_clone = preview.clone();
-            async move {
-                sleep(Duration::from_millis(350)).await;
-                let short = short_hex(&tx_ref.hash);
-                let mut summary = vec![format!("Hash: {}", short)];
-                let mut status = None;
-                let mut block_number = None;
-                let mut from = None;
-                let mut to = None;
-                let mut value_formatted = None;
-                let preview_calldata = preview_clone.as_ref().and_then(|row| row.calldata.clone());
-                let calldata_message = preview_calldata.clone().unwrap_or_else(|| {
-                    "Calldata unavailable (connect debugger or provider)".to_string()
-                });
-                if let Some(row) = preview_clone.as_ref() {
-                    from = Some(row.from.clone());
-                    to = row.to.clone();
-                    value_formatted = Some(row.value_display.clone());
-                    block_number = row.block_number;
-                    status = Some(row.status);
-                    summary.push(format!("Status: {}", row.status.label()));
-                    summary.push(format!("From: {}", short_hex(&row.from)));
-                    summary.push(format!(
-                        "To: {}",
-                        row.to
-                            .as_ref()
-                            .map(|addr| short_hex(addr))
-                            .unwrap_or_else(|| "Contract creation".into())
-                    ));
-                    summary.push(format!("Value: {}", row.value_display));
-                    if let Some(block) = row.block_number {
-                        summary.push(format!("Block: {block}"));
+        let rpc_url = self.state.secrets.rpc_url(&tx.chain).map(str::to_string);
+        // L2 withdrawals settle on Mainnet, where their status is read.
+        let rollup = resolve_chain(&tx.chain).and_then(|info| info.rollup);
+        let l1_rpc_url = self.state.secrets.rpc_url("Mainnet").map(str::to_string);
+        let node = graphql::for_chain(&self.state.node_backends, &tx.chain);
+        let host = self.rpc_host(&tx.chain);
+        let providers = self.providers.clone();
+        bus.spawn_scheduled(host, Priority::Foreground, move || async move {
+            sleep(Duration::from_millis(350)).await;
+            let data = Self::hydrate_transaction(
+                tx, preview, rpc_url, rollup, l1_rpc_url, node, providers,
+            )
+            .await;
+            Message::TransactionHydrated(Box::new(data))
+        });
+    }
+
+    async fn hydrate_transaction(
+        tx: TransactionRef,
+        preview: Option<AddressTransactionRow>,
+        rpc_url: Option<String>,
+        rollup: Option<Rollup>,
+        l1_rpc_url: Option<String>,
+        node: graphql::NodeBackend,
+        providers: Providers,
+    ) -> HydratedTransaction {
+        let rpc = &providers.rpc;
+        // Chains without blobs simply report no blob fields.
+        let (blobs, nonce_history, block_context, l2) = match rpc_url {
+            Some(url) => {
+                let blobs = timeout(
+                    Duration::from_secs(10),
+                    fetch_blob_details(rpc, &url, &tx.hash),
+                )
+                .await
+                .ok()
+                .and_then(Result::ok)
+                .flatten();
+                let nonce_history = timeout(
+                    Duration::from_secs(10),
+                    nonces::fetch_nonce_history(rpc, &url, &tx.hash),
+                )
+                .await
+                .ok()
+                .and_then(Result::ok);
+                let block_context = match node {
+                    graphql::NodeBackend::Rpc => {
+                        timeout(
+                            Duration::from_secs(10),
+                            fetch_block_context(rpc, &url, &tx.hash),
+                        )
+                        .await
                     }
-                } else {
-                    summary.push("Status: Not cached".into());
-                    summary.push("From: Not cached".into());
-                    summary.push("To: Not cached".into());
-                    summary.push("Value: Not cached".into());
-                }
-                summary.push(format!("Calldata: {calldata_message}"));
-                Message::TransactionHydrated(HydratedTransaction {
-                    identifier: tx_ref.hash.clone(),
-                    summary,
-                    debug: vec!["Trace data unavailable. Configure Alloy debug adapter.".into()],
-                    storage_diff: vec!["Storage diff requires debugger export (`e`).".into()],
-                    from,
-                    to,
-                    value_formatted,
-                    calldata: preview_calldata,
-                    block_number,
-                    status,
-                })
+                    graphql::NodeBackend::Graphql => {
+                        timeout(
+                            Duration::from_secs(10),
+                            graphql::fetch_block_context(rpc, &url, &tx.hash),
+                        )
+                        .await
+                    }
+                }
+                .ok()
+                .and_then(Result::ok)
+                .flatten();
+                let l2 = match rollup {
+                    Some(rollup) => timeout(
+                        Duration::from_secs(15),
+                        l2::fetch_l2_context(rpc, &url, l1_rpc_url.as_deref(), rollup, &tx.hash),
+                    )
+                    .await
+                    .ok()
+                    .and_then(Result::ok)
+                    .flatten(),
+                    None => None,
+                };
+                (blobs, nonce_history, block_context, l2)
             }
-        });
+            None => (None, None, None, None),
+        };
+        let short = short_hex(&tx.hash);
+        let mut summary = vec![format!("Hash: {}", short)];
+        let mut status = None;
+        let mut block_number = None;
+        let mut from = None;
+        let mut to = None;
+        let mut value_formatted = None;
+        let preview_calldata = preview.as_ref().and_then(|row| row.calldata.clone());
+        let calldata_message = preview_calldata
+            .clone()
+            .unwrap_or_else(|| "Calldata unavailable (connect debugger or provider)".to_string());
+        if let Some(row) = preview.as_ref() {
+            from = Some(row.from.clone());
+            to = row.to.clone();
+            value_formatted = Some(row.value_display.clone());
+            block_number = row.block_number;
+            status = Some(row.status);
+            summary.push(format!("Status: {}", row.status.label()));
+            summary.push(format!("From: {}", short_hex(&row.from)));
+            summary.push(format!(
+                "To: {}",
+                row.to
+                    .as_ref()
+                    .map(|addr| short_hex(addr))
+                    .unwrap_or_else(|| "Contract creation".into())
+            ));
+            summary.push(format!("Value: {}", row.value_display));
+            if let Some(block) = row.block_number {
+                summary.push(format!("Block: {block}"));
+            }
+        } else {
+            summary.push("Status: Not cached".into());
+            summary.push("From: Not cached".into());
+            summary.push("To: Not cached".into());
+            summary.push("Value: Not cached".into());
+        }
+        summary.push(format!("Calldata: {calldata_message}"));
+        HydratedTransaction {
+            identifier: tx.hash.clone(),
+            summary,
+            debug: vec!["Trace data unavailable. Configure Alloy debug adapter.".into()],
+            storage_diff: vec!["Storage diff requires debugger export (`e`).".into()],
+            from,
+            to,
+            value_formatted,
+            calldata: preview_calldata,
+            block_number,
+            status,
+            blobs,
+            nonce_history,
+            block_context,
+            l2,
+        }
     }
 
     fn toggle_favorite(&mut self) -> AppResult<()> {
+        self.state.usage.record_feature("favorite toggle");
         if let Some(selected) = self.state.selected.clone() {
             match &selected {
                 SelectedEntity::Address(addr) => {
                     let key = addr.address.clone();
                     if self.state.favorite_addresses.contains(&key) {
-                        self.storage.favorites_addresses().remove(&key)?;
+                        let record = FavoriteRecord {
+                            label: Some(addr.label.clone()),
+                            identifier: key.clone(),
+                            chain: addr.chain.clone(),
+                            order: 0,
+                        };
+                        self.storage.write("favorites", move |storage| {
+                            storage
+                                .favorites_batch()
+                                .trash(FavoritesTable::Addresses, record)
+                                .commit()
+                        });
                         self.state.favorite_addresses.remove(&key);
                         self.sidebar_command(SidebarCommand::RemoveFavorite(selected.clone()))?;
                         self.top_bar_command(TopCommand::ShowStatus(format!(
-                            "Removed {} from favorites",
+                            "Moved {} to trash • T to restore",
                             short_hex(&addr.address)
                         )))?;
                     } else {
-                        let record = FavoriteRecord {
+                        let mut record = FavoriteRecord {
                             label: Some(addr.label.clone()),
                             identifier: addr.address.clone(),
                             chain: addr.chain.clone(),
+                            order: 0,
                         };
-                        self.storage.favorites_addresses().upsert(&record)?;
+                        // The order is read on the writer so earlier queued adds count.
+                        self.storage.write("favorites", move |storage| {
+                            record.order = storage.favorites_addresses().next_order()?;
+                            storage
+                                .favorites_batch()
+                                .upsert(FavoritesTable::Addresses, record)
+                                .commit()
+                        });
                         self.state.favorite_addresses.insert(key);
                         self.sidebar_command(SidebarCommand::AddFavorite(selected.clone()))?;
                         self.top_bar_command(TopCommand::ShowStatus(format!(
@@ -962,20 +2621,39 @@ impl App {
                 SelectedEntity::Transaction(tx) => {
                     let key = tx.hash.clone();
                     if self.state.favorite_transactions.contains(&key) {
-                        self.storage.favorites_transactions().remove(&key)?;
+                        let record = FavoriteRecord {
+                            label: Some(tx.label.clone()),
+                            identifier: key.clone(),
+                            chain: tx.chain.clone(),
+                            order: 0,
+                        };
+                        self.storage.write("favorites", move |storage| {
+                            storage
+                                .favorites_batch()
+                                .trash(FavoritesTable::Transactions, record)
+                                .commit()
+                        });
                         self.state.favorite_transactions.remove(&key);
                         self.sidebar_command(SidebarCommand::RemoveFavorite(selected.clone()))?;
                         self.top_bar_command(TopCommand::ShowStatus(format!(
-                            "Removed {} from favorites",
+                            "Moved {} to trash • T to restore",
                             short_hex(&tx.hash)
                         )))?;
                     } else {
-                        let record = FavoriteRecord {
+                        let mut record = FavoriteRecord {
                             label: Some(tx.label.clone()),
                             identifier: tx.hash.clone(),
                             chain: tx.chain.clone(),
+                            order: 0,
                         };
-                        self.storage.favorites_transactions().upsert(&record)?;
+                        // The order is read on the writer so earlier queued adds count.
+                        self.storage.write("favorites", move |storage| {
+                            record.order = storage.favorites_transactions().next_order()?;
+                            storage
+                                .favorites_batch()
+                                .upsert(FavoritesTable::Transactions, record)
+                                .commit()
+                        });
                         self.state.favorite_transactions.insert(key);
                         self.sidebar_command(SidebarCommand::AddFavorite(selected.clone()))?;
                         self.top_bar_command(TopCommand::ShowStatus(format!(
@@ -989,30 +2667,2841 @@ impl App {
         Ok(())
     }
 
-    fn tick(&mut self) -> AppResult<()> {
-        {
-            let commands = self.command_bus();
-            let (state, storage) = (&mut self.state, &mut self.storage);
-            let mut ctx = AppContext {
-                state,
-                storage,
-                commands,
-            };
-            if let Some(action) = self.top_bar.tick(&mut ctx)? {
-                self.dispatch(action);
-            }
+    fn start_update_check(&mut self) -> AppResult<()> {
+        if !updates::is_enabled() {
+            return Ok(());
         }
-        {
-            let commands = self.command_bus();
-            let (state, storage) = (&mut self.state, &mut self.storage);
-            let mut ctx = AppContext {
-                state,
-                storage,
-                commands,
-            };
-            if let Some(action) = self.sidebar.tick(&mut ctx)? {
-                self.dispatch(action);
-            }
+        if let Some(release) = updates::cached_release(self.storage.settings())? {
+            self.apply_release_check(release);
+            return Ok(());
+        }
+        let stats = self.providers.rpc.stats().clone();
+        self.command_bus().spawn_async(move || async move {
+            Message::UpdateChecked(updates::fetch_latest_release(&stats).await)
+        });
+        Ok(())
+    }
+
+    fn apply_release_check(&mut self, release: ReleaseInfo) {
+        if release.is_newer_than_current() {
+            self.show_status(format!(
+                "evm-tui v{} is available • press U for the changelog",
+                release.version
+            ));
+            self.state.available_update = Some(release);
+        }
+    }
+
+    fn open_update_modal(&mut self) {
+        match self.state.available_update.clone() {
+            Some(release) if self.modal.is_none() => {
+                self.state.usage.record_feature("changelog");
+                self.modal = Some(ActiveModal::Update(UpdateModal::new(release)));
+                self.state.navigation.focus_modal();
+            }
+            Some(_) => {}
+            None => self.show_status("No update available"),
+        }
+    }
+
+    fn open_portfolio_modal(&mut self) {
+        if self.modal.is_some() {
+            return;
+        }
+        self.state.usage.record_feature("portfolio");
+        self.modal = Some(ActiveModal::Portfolio(PortfolioModal::new()));
+        self.state.navigation.focus_modal();
+    }
+
+    /// Build the permission graph for favorited addresses on the selected
+    /// address's chain (Mainnet when nothing is selected).
+    fn open_permissions_modal(&mut self) -> AppResult<()> {
+        if self.modal.is_some() {
+            return Ok(());
+        }
+        let chain = match self.state.selected.as_ref() {
+            Some(SelectedEntity::Address(addr)) => addr.chain.clone(),
+            _ => "Mainnet".to_string(),
+        };
+        let Some(rpc_url) = self.state.secrets.rpc_url(&chain).map(str::to_string) else {
+            self.show_status(format!("Configure an RPC endpoint for {chain} first"));
+            return Ok(());
+        };
+        let mut labels = HashMap::new();
+        for favorite in self.sidebar.favorites() {
+            let SelectedEntity::Address(addr) = favorite else {
+                continue;
+            };
+            if !addr.chain.eq_ignore_ascii_case(&chain) {
+                continue;
+            }
+            if let Ok(address) = addr.address.parse::<Address>() {
+                labels.insert(address, addr.label);
+            }
+        }
+        if labels.is_empty() {
+            self.show_status(format!("No favorited addresses on {chain}"));
+            return Ok(());
+        }
+        self.state.usage.record_feature("permissions graph");
+        let targets: Vec<Address> = labels.keys().copied().collect();
+        let host = self.rpc_host(&chain);
+        self.modal = Some(ActiveModal::Permissions(PermissionsModal::new(
+            chain, labels,
+        )));
+        self.state.navigation.focus_modal();
+        let rpc = self.providers.rpc.clone();
+        self.command_bus()
+            .spawn_scheduled(host, Priority::Foreground, move || async move {
+                Message::PermissionsLoaded(permissions::build_graph(&rpc, &rpc_url, targets).await)
+            });
+        Ok(())
+    }
+
+    /// Preview a proxy upgrade of the selected address to an implementation
+    /// entered in the modal.
+    fn open_upgrade_impact_modal(&mut self) {
+        if self.modal.is_some() {
+            return;
+        }
+        let Some(SelectedEntity::Address(addr)) = self.state.selected.clone() else {
+            self.show_status("Select a proxy address first");
+            return;
+        };
+        self.state.usage.record_feature("upgrade impact");
+        self.modal = Some(ActiveModal::UpgradeImpact(UpgradeImpactModal::new(addr)));
+        self.state.navigation.focus_modal();
+    }
+
+    fn poll_watch_groups(&mut self) {
+        if self.watch_poll_in_flight
+            || self
+                .last_watch_poll
+                .is_some_and(|last| last.elapsed() < watch::POLL_INTERVAL)
+        {
+            return;
+        }
+        let members: Vec<_> = self
+            .state
+            .watch
+            .polled_members()
+            .into_iter()
+            .map(|member| {
+                let rpc_url = self
+                    .state
+                    .secrets
+                    .rpc_url(&member.chain)
+                    .map(str::to_string);
+                (member, rpc_url)
+            })
+            .collect();
+        self.last_watch_poll = Some(Instant::now());
+        if members.is_empty() {
+            return;
+        }
+        self.watch_poll_in_flight = true;
+        let bus = self.command_bus();
+        let rpc = bus.providers().rpc.clone();
+        let scheduler = bus.scheduler().clone();
+        bus.spawn_async(move || async move {
+            Message::WatchPolled(watch::poll_members(rpc, scheduler, members).await)
+        });
+    }
+
+    /// Refresh watchlist balances in the background, skipped while the
+    /// selection is loading so it never competes with what is on screen.
+    fn poll_watchlist(&mut self) {
+        if self.watchlist_poll_in_flight
+            || self.state.loading.main_view.is_loading
+            || self
+                .last_watchlist_poll
+                .is_some_and(|last| last.elapsed() < watchlist::POLL_INTERVAL)
+        {
+            return;
+        }
+        let members: Vec<_> = self
+            .state
+            .watchlist
+            .entries
+            .iter()
+            .map(|entry| {
+                let rpc_url = self
+                    .state
+                    .secrets
+                    .rpc_url(&entry.member.chain)
+                    .map(str::to_string);
+                (entry.member.clone(), rpc_url)
+            })
+            .collect();
+        self.last_watchlist_poll = Some(Instant::now());
+        if members.is_empty() {
+            return;
+        }
+        self.watchlist_poll_in_flight = true;
+        let bus = self.command_bus();
+        let rpc = bus.providers().rpc.clone();
+        let scheduler = bus.scheduler().clone();
+        bus.spawn_async(move || async move {
+            Message::WatchlistPolled(watch::poll_members(rpc, scheduler, members).await)
+        });
+    }
+
+    /// Check the Safe queues of watchlist entries with alerts on, for
+    /// proposals to raise.
+    fn poll_safe_queues(&mut self) {
+        if self.safe_poll_in_flight
+            || self
+                .last_safe_poll
+                .is_some_and(|last| last.elapsed() < watchlist::SAFE_POLL_INTERVAL)
+        {
+            return;
+        }
+        self.last_safe_poll = Some(Instant::now());
+        let candidates: Vec<_> = self
+            .state
+            .watchlist
+            .safe_candidates()
+            .into_iter()
+            .filter_map(|member| {
+                let address = member.address.parse::<Address>().ok()?;
+                Some((watch::member_key(&member), member.chain, address))
+            })
+            .collect();
+        if candidates.is_empty() {
+            return;
+        }
+        self.safe_poll_in_flight = true;
+        let stats = self.providers.rpc.stats().clone();
+        self.command_bus().spawn_async(move || async move {
+            let mut results = Vec::new();
+            for (key, chain, address) in candidates {
+                results.push((key, safe::fetch_queue(&stats, chain, address).await));
+            }
+            Message::SafeQueuesPolled(results)
+        });
+    }
+
+    /// Raise each event and mention the latest in the status bar.
+    fn raise_all(&mut self, events: Vec<(AlertOutputs, WatchEvent)>) {
+        let Some((_, last)) = events.last() else {
+            return;
+        };
+        self.show_status(format!("🔔 {}", last.message));
+        for (outputs, event) in events {
+            self.raise(outputs, event);
+        }
+    }
+
+    /// Send `event` to the desktop and the webhook, as `outputs` asks.
+    fn raise(&mut self, outputs: AlertOutputs, event: WatchEvent) {
+        if outputs.notify {
+            self.notifier.notify(&event);
+        }
+        if !outputs.webhook {
+            return;
+        }
+        let Some(url) = webhook::url(&self.state.secrets).map(str::to_string) else {
+            return;
+        };
+        if self.notifier.allow_webhook() {
+            let payload = webhook::payload(&event, watchlist::unix_now());
+            let stats = self.providers.rpc.stats().clone();
+            self.command_bus().spawn_async(move || async move {
+                Message::WebhookPosted(webhook::post(&stats, url, payload).await.map(|()| None))
+            });
+        }
+    }
+
+    /// `:webhook`: post a sample event to check the configured URL.
+    fn test_webhook(&mut self) {
+        let Some(url) = webhook::url(&self.state.secrets).map(str::to_string) else {
+            self.show_status("No Webhook URL set; add one in the secrets form");
+            return;
+        };
+        let event = WatchEvent {
+            source: "test",
+            title: "evm-tui".into(),
+            message: "Webhook test: watch alerts will arrive here".into(),
+            subject: None,
+            transaction: None,
+        };
+        let payload = webhook::payload(&event, watchlist::unix_now());
+        self.show_status("Posting a test event to the webhook…");
+        let stats = self.providers.rpc.stats().clone();
+        self.command_bus().spawn_async(move || async move {
+            Message::WebhookPosted(
+                webhook::post(&stats, url, payload)
+                    .await
+                    .map(|()| Some("Webhook accepted the test event".to_string())),
+            )
+        });
+    }
+
+    /// Status line for a watch moved to `outputs`, flagging a webhook
+    /// output with no URL to post to.
+    fn show_alerts_status(&mut self, outputs: AlertOutputs, what: &str) {
+        let missing = if outputs.webhook && webhook::url(&self.state.secrets).is_none() {
+            " • set a Webhook URL in the secrets form"
+        } else {
+            ""
+        };
+        self.show_status(format!("Alerts for {what}: {}{missing}", outputs.label()));
+    }
+
+    /// `n` on the Watchlist tab: alert outputs of the highlighted entry.
+    fn cycle_watchlist_alerts(&mut self) {
+        let Some(SelectedEntity::Address(addr)) =
+            self.sidebar.active_selection(SidebarTab::Watchlist)
+        else {
+            return;
+        };
+        let member = GroupMember {
+            address: addr.address,
+            chain: addr.chain,
+        };
+        let Some(outputs) = self.state.watchlist.cycle_alerts(&member) else {
+            return;
+        };
+        // A Safe's queue is baselined on the next poll rather than after the interval.
+        self.last_safe_poll = None;
+        self.save_watchlist();
+        self.show_alerts_status(outputs, &short_hex(&member.address));
+    }
+
+    fn save_watchlist(&mut self) {
+        let entries = self.state.watchlist.entries.clone();
+        self.storage.write("watchlist", move |storage| {
+            storage.settings().set_json(SettingKey::Watchlist, &entries)
+        });
+    }
+
+    /// `n` on the Storage tab: alert outputs of the selected pinned slot.
+    fn cycle_slot_alerts(&mut self) {
+        let Some(SelectedEntity::Address(addr)) = self.state.selected.as_ref() else {
+            return;
+        };
+        let Some(pin) = self
+            .state
+            .slots
+            .pins_for(&addr.address)
+            .get(self.state.slots.selected_index)
+            .map(|pin| (*pin).clone())
+        else {
+            return;
+        };
+        if let Some(outputs) = self.state.slots.cycle_alerts(&pin) {
+            self.save_pinned_slots();
+            self.show_alerts_status(outputs, &pin.label);
+        }
+    }
+
+    /// Put `entity` on the watchlist, or take it off when already there.
+    fn toggle_watchlist(&mut self, entity: Option<SelectedEntity>) -> AppResult<()> {
+        let Some(SelectedEntity::Address(addr)) = entity else {
+            self.show_status("Select an address to watch");
+            return Ok(());
+        };
+        let member = GroupMember {
+            address: addr.address.clone(),
+            chain: addr.chain.clone(),
+        };
+        let who = short_hex(&member.address);
+        let added = self.state.watchlist.toggle(member);
+        if added {
+            self.state.usage.record_feature("watchlist");
+            // Poll the newcomer on the next tick instead of after a full interval.
+            self.last_watchlist_poll = None;
+            if let Some(data) = self.state.current_address.as_ref() {
+                self.state.watchlist.observe(&addr.chain, data);
+            }
+        }
+        self.save_watchlist();
+        let tab = self.state.navigation.sidebar_tab;
+        self.sidebar
+            .set_watchlist(watchlist_refs(&self.state.watchlist), tab);
+        self.show_status(if added {
+            format!("Watching {who} • Watchlist tab in the sidebar")
+        } else {
+            format!("Stopped watching {who}")
+        });
+        Ok(())
+    }
+
+    /// Hydrate one favorite in the background when nothing else is loading,
+    /// starting with those never fetched, then those past the prefetch interval.
+    fn prefetch_favorites(&mut self) {
+        if self.state.prefetch.in_flight.is_some()
+            || self.refresh_in_flight
+            || self.state.loading.main_view.is_loading
+            || self
+                .last_prefetch
+                .is_some_and(|last| last.elapsed() < prefetch::PREFETCH_SPACING)
+        {
+            return;
+        }
+        let favorites = self.sidebar.favorites();
+        let Some(entity) = self.state.prefetch.next_due(&favorites) else {
+            return;
+        };
+        self.state.prefetch.in_flight = Some(prefetch::key(&entity));
+        let bus = self.command_bus();
+        match entity {
+            SelectedEntity::Address(addr) => {
+                let secrets = self.state.secrets.clone();
+                let project = self.state.foundry_project.clone();
+                let limits = fetch_limits::for_chain(&self.state.fetch_limits, &addr.chain);
+                let providers = self.providers_for(&addr.chain).fresh_history();
+                let host = self.rpc_host(&addr.chain);
+                bus.spawn_scheduled(host, Priority::Prefetch, move || async move {
+                    let data =
+                        Self::hydrate_address(addr.clone(), secrets, project, limits, providers)
+                            .await;
+                    Message::FavoritePrefetched(
+                        SelectedEntity::Address(addr),
+                        Prefetched::Address(Box::new(data)),
+                    )
+                });
+            }
+            SelectedEntity::Transaction(tx) => {
+                let preview = self.state.transaction_preview_cache.get(&tx.hash).cloned();
+                let rpc_url = self.state.secrets.rpc_url(&tx.chain).map(str::to_string);
+                let rollup = resolve_chain(&tx.chain).and_then(|info| info.rollup);
+                let l1_rpc_url = self.state.secrets.rpc_url("Mainnet").map(str::to_string);
+                let node = graphql::for_chain(&self.state.node_backends, &tx.chain);
+                let host = self.rpc_host(&tx.chain);
+                let providers = self.providers.clone();
+                bus.spawn_scheduled(host, Priority::Prefetch, move || async move {
+                    let data = Self::hydrate_transaction(
+                        tx.clone(),
+                        preview,
+                        rpc_url,
+                        rollup,
+                        l1_rpc_url,
+                        node,
+                        providers,
+                    )
+                    .await;
+                    Message::FavoritePrefetched(
+                        SelectedEntity::Transaction(tx),
+                        Prefetched::Transaction(Box::new(data)),
+                    )
+                });
+            }
+        }
+    }
+
+    fn poll_pinned_slots(&mut self) {
+        if self.slot_poll_in_flight
+            || self
+                .last_slot_poll
+                .is_some_and(|last| last.elapsed() < slots::POLL_INTERVAL)
+        {
+            return;
+        }
+        let pins: Vec<_> = self
+            .state
+            .slots
+            .pins
+            .iter()
+            .map(|pin| {
+                let rpc_url = self.state.secrets.rpc_url(&pin.chain).map(str::to_string);
+                (pin.clone(), rpc_url)
+            })
+            .collect();
+        self.last_slot_poll = Some(Instant::now());
+        if pins.is_empty() {
+            return;
+        }
+        self.slot_poll_in_flight = true;
+        let bus = self.command_bus();
+        let rpc = bus.providers().rpc.clone();
+        let scheduler = bus.scheduler().clone();
+        bus.spawn_async(move || async move {
+            Message::SlotsPolled(slots::poll_pins(rpc, scheduler, pins).await)
+        });
+    }
+
+    /// Scan new blocks on the log alert rules' chains.
+    fn poll_log_alerts(&mut self) {
+        if self.log_alert_poll_in_flight
+            || self
+                .last_log_alert_poll
+                .is_some_and(|last| last.elapsed() < log_alerts::POLL_INTERVAL)
+        {
+            return;
+        }
+        self.last_log_alert_poll = Some(Instant::now());
+        let batches: Vec<_> = self
+            .state
+            .log_alerts
+            .batches()
+            .into_iter()
+            .map(|(chain, after, rules)| {
+                let rpc_url = self.state.secrets.rpc_url(&chain).map(str::to_string);
+                (chain, rpc_url, after, rules)
+            })
+            .collect();
+        if batches.is_empty() {
+            return;
+        }
+        self.log_alert_poll_in_flight = true;
+        let bus = self.command_bus();
+        let rpc = bus.providers().rpc.clone();
+        let scheduler = bus.scheduler().clone();
+        bus.spawn_async(move || async move {
+            Message::LogAlertsPolled(log_alerts::poll_rules(rpc, scheduler, batches).await)
+        });
+    }
+
+    /// Check the threshold alerts' base fees and balances.
+    fn poll_thresholds(&mut self) {
+        if self.threshold_poll_in_flight
+            || self
+                .last_threshold_poll
+                .is_some_and(|last| last.elapsed() < thresholds::POLL_INTERVAL)
+        {
+            return;
+        }
+        self.last_threshold_poll = Some(Instant::now());
+        let batches: Vec<_> = self
+            .state
+            .thresholds
+            .batches()
+            .into_iter()
+            .map(|(chain, alerts)| {
+                let rpc_url = self.state.secrets.rpc_url(&chain).map(str::to_string);
+                (chain, rpc_url, alerts)
+            })
+            .collect();
+        if batches.is_empty() {
+            return;
+        }
+        self.threshold_poll_in_flight = true;
+        let bus = self.command_bus();
+        let rpc = bus.providers().rpc.clone();
+        let scheduler = bus.scheduler().clone();
+        bus.spawn_async(move || async move {
+            Message::ThresholdsPolled(thresholds::poll_alerts(rpc, scheduler, batches).await)
+        });
+    }
+
+    /// Pin a slot of the selected address from the Storage tab prompt.
+    fn pin_slot(&mut self, input: &str) {
+        let Some(SelectedEntity::Address(addr)) = self.state.selected.clone() else {
+            return;
+        };
+        let layout = self
+            .state
+            .current_address
+            .as_ref()
+            .filter(|data| data.identifier == addr.address)
+            .and_then(|data| data.contract_source.as_ref())
+            .and_then(|source| source.storage_layout.as_ref());
+        let (slot, label, variables) = match slots::parse_slot(input, layout) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                self.show_status(err);
+                return;
+            }
+        };
+        let pin = PinnedSlot {
+            address: addr.address,
+            chain: addr.chain,
+            slot,
+            label,
+            variables,
+            alerts: AlertOutputs::default(),
+        };
+        if !self.state.slots.pin(pin.clone()) {
+            self.show_status(format!("{} is already pinned", pin.label));
+            return;
+        }
+        self.state.usage.record_feature("storage slot watcher");
+        self.save_pinned_slots();
+        // Read the new pin right away instead of waiting for the next round.
+        self.last_slot_poll = None;
+        self.show_status(format!("Pinned {}", pin.label));
+    }
+
+    fn unpin_selected_slot(&mut self) {
+        let Some(SelectedEntity::Address(addr)) = self.state.selected.as_ref() else {
+            return;
+        };
+        let Some(pin) = self
+            .state
+            .slots
+            .pins_for(&addr.address)
+            .get(self.state.slots.selected_index)
+            .map(|pin| (*pin).clone())
+        else {
+            return;
+        };
+        self.state.slots.unpin(&pin);
+        self.state.slots.selected_index = self.state.slots.selected_index.saturating_sub(1);
+        self.save_pinned_slots();
+        self.show_status(format!("Unpinned {}", pin.label));
+    }
+
+    fn label_selected_address(&mut self, input: &str) {
+        let Some(SelectedEntity::Address(addr)) = self.state.selected.clone() else {
+            return;
+        };
+        let Ok(address) = addr.address.parse() else {
+            return;
+        };
+        let label = labels::parse_label(input);
+        let status = match label.as_ref() {
+            Some((name, _)) => format!("Labelled {} as {name}", short_hex(&addr.address)),
+            None => format!("Removed your label for {}", short_hex(&addr.address)),
+        };
+        if !self.state.labels.set(&addr.chain, address, label) {
+            return;
+        }
+        self.state.usage.record_feature("address labels");
+        let user = self.state.labels.user.clone();
+        self.storage.write("address labels", move |storage| {
+            storage
+                .settings()
+                .set_json(SettingKey::AddressLabels, &user)
+        });
+        self.show_status(status);
+    }
+
+    fn save_pending_transactions(&mut self) {
+        let pending = self.state.pending_transactions.clone();
+        self.storage.write("pending transactions", move |storage| {
+            storage
+                .settings()
+                .set_json(SettingKey::PendingTransactions, &pending)
+        });
+    }
+
+    fn save_pinned_slots(&mut self) {
+        let pins = self.state.slots.pins.clone();
+        self.storage.write("pinned slots", move |storage| {
+            storage.settings().set_json(SettingKey::PinnedSlots, &pins)
+        });
+    }
+
+    /// Re-hydrate the selection once the auto-refresh interval has passed since
+    /// it was last loaded. Unlike selecting it, this keeps the current data on
+    /// screen until the fresh copy arrives, so scroll and selection survive.
+    fn refresh_selection(&mut self) {
+        let Some(interval) = self.state.auto_refresh else {
+            return;
+        };
+        if self.refresh_in_flight
+            || self.state.loading.main_view.is_loading
+            || self
+                .last_hydrated
+                .is_none_or(|last| last.elapsed() < interval)
+        {
+            return;
+        }
+        let Some(selected) = self.state.selected.clone() else {
+            return;
+        };
+        self.refresh_in_flight = true;
+        match selected {
+            SelectedEntity::Address(addr) => self.spawn_address_hydration(addr, true),
+            SelectedEntity::Transaction(tx) => {
+                let preview = self.state.transaction_preview_cache.get(&tx.hash).cloned();
+                self.spawn_transaction_hydration(tx, preview);
+            }
+        }
+    }
+
+    /// Query logs for the selected contract once the Events tab is showing and
+    /// its hydration finished (the ABI comes from the verified source).
+    fn load_events_if_needed(&mut self) {
+        if self.state.navigation.main_view_mode != MainViewMode::Address
+            || self
+                .state
+                .navigation
+                .main_view_tab
+                .normalize(MainViewMode::Address)
+                != MainViewTab::AddressEvents
+            || self.state.events.loading
+        {
+            return;
+        }
+        let Some(SelectedEntity::Address(addr)) = self.state.selected.clone() else {
+            return;
+        };
+        let Some(data) = self
+            .state
+            .current_address
+            .as_ref()
+            .filter(|data| data.identifier == addr.address)
+        else {
+            return;
+        };
+        if !self.state.events.needs_load(&addr.address) {
+            return;
+        }
+        let abi = data
+            .contract_source
+            .as_ref()
+            .map(|source| source.abi.clone());
+        let view = &mut self.state.events;
+        view.address = Some(addr.address.clone());
+        let Some(rpc_url) = self.state.secrets.rpc_url(&addr.chain).map(str::to_string) else {
+            view.error = Some(format!(
+                "Configure an RPC endpoint for {} to load events.",
+                addr.chain
+            ));
+            return;
+        };
+        view.loading = true;
+        view.error = None;
+        self.state.usage.record_feature("event logs");
+        let filter = view.filter.clone();
+        let rpc = self.providers.rpc.clone();
+        let host = self.rpc_host(&addr.chain);
+        self.command_bus()
+            .spawn_scheduled(host, Priority::Foreground, move || async move {
+                let address = addr.address;
+                let result = events::fetch_events(rpc, rpc_url, address.clone(), abi, filter).await;
+                Message::EventsLoaded { address, result }
+            });
+    }
+
+    /// Scan the selected proxy's upgrade history once the Permissions tab is
+    /// showing and its hydration finished (the scan starts at deployment).
+    fn load_upgrades_if_needed(&mut self) {
+        if self.state.navigation.main_view_mode != MainViewMode::Address
+            || self
+                .state
+                .navigation
+                .main_view_tab
+                .normalize(MainViewMode::Address)
+                != MainViewTab::AddressPermissions
+            || self.state.upgrades.loading
+        {
+            return;
+        }
+        let Some(SelectedEntity::Address(addr)) = self.state.selected.clone() else {
+            return;
+        };
+        let Some(data) = self
+            .state
+            .current_address
+            .as_ref()
+            .filter(|data| data.identifier == addr.address)
+        else {
+            return;
+        };
+        if !self.state.upgrades.needs_load(&addr.address) {
+            return;
+        }
+        let is_contract = data
+            .overview
+            .as_ref()
+            .is_some_and(|overview| overview.is_contract);
+        let floor = data
+            .creation
+            .as_ref()
+            .and_then(|creation| creation.block_number);
+        let view = &mut self.state.upgrades;
+        view.address = Some(addr.address.clone());
+        view.history = None;
+        view.error = None;
+        if !is_contract {
+            return;
+        }
+        let Some(rpc_url) = self.state.secrets.rpc_url(&addr.chain).map(str::to_string) else {
+            view.error = Some(format!(
+                "Configure an RPC endpoint for {} to load upgrade history.",
+                addr.chain
+            ));
+            return;
+        };
+        view.loading = true;
+        self.state.usage.record_feature("upgrade history");
+        let rpc = self.providers.rpc.clone();
+        let host = self.rpc_host(&addr.chain);
+        self.command_bus()
+            .spawn_scheduled(host, Priority::Foreground, move || async move {
+                let address = addr.address;
+                let result = upgrades::fetch_upgrades(rpc, rpc_url, address.clone(), floor).await;
+                Message::UpgradesLoaded { address, result }
+            });
+    }
+
+    /// Load the fee breakdown once the Fees tab of a transaction is showing.
+    fn load_fees_if_needed(&mut self) {
+        if self.state.navigation.main_view_mode != MainViewMode::Transaction
+            || self
+                .state
+                .navigation
+                .main_view_tab
+                .normalize(MainViewMode::Transaction)
+                != MainViewTab::TransactionFees
+            || self.state.fees.loading
+        {
+            return;
+        }
+        let Some(SelectedEntity::Transaction(tx)) = self.state.selected.clone() else {
+            return;
+        };
+        if !self.state.fees.needs_load(&tx.hash) {
+            return;
+        }
+        let view = &mut self.state.fees;
+        view.hash = Some(tx.hash.clone());
+        let Some(rpc_url) = self.state.secrets.rpc_url(&tx.chain).map(str::to_string) else {
+            view.result = Some(Err(format!(
+                "Configure an RPC endpoint for {} to load fees.",
+                tx.chain
+            )));
+            return;
+        };
+        view.loading = true;
+        view.result = None;
+        self.state.usage.record_feature("fee breakdown");
+        let api_key = self
+            .state
+            .secrets
+            .explorer_api_key(&tx.chain)
+            .map(str::to_string);
+        let providers = self.providers_for(&tx.chain);
+        let host = self.rpc_host(&tx.chain);
+        self.command_bus()
+            .spawn_scheduled(host, Priority::Foreground, move || async move {
+                // Fiat is a nice-to-have; fees still load without a price.
+                let native_usd = providers
+                    .explorer
+                    .native_usd_price(&tx.chain, api_key.as_deref())
+                    .await
+                    .ok()
+                    .flatten();
+                let result =
+                    fees::fetch_fees(providers.rpc, rpc_url, tx.hash.clone(), native_usd).await;
+                Message::FeesLoaded {
+                    hash: tx.hash,
+                    result,
+                }
+            });
+    }
+
+    /// Load the declared access list once the Access List tab is showing.
+    fn load_access_list_if_needed(&mut self) {
+        if self.state.navigation.main_view_mode != MainViewMode::Transaction
+            || self
+                .state
+                .navigation
+                .main_view_tab
+                .normalize(MainViewMode::Transaction)
+                != MainViewTab::TransactionAccessList
+            || self.state.access_list.loading
+        {
+            return;
+        }
+        let Some(SelectedEntity::Transaction(tx)) = self.state.selected.clone() else {
+            return;
+        };
+        if !self.state.access_list.needs_load(&tx.hash) {
+            return;
+        }
+        let view = &mut self.state.access_list;
+        view.hash = Some(tx.hash.clone());
+        let Some(rpc_url) = self.state.secrets.rpc_url(&tx.chain).map(str::to_string) else {
+            view.declared = Some(Err(format!(
+                "Configure an RPC endpoint for {} to load the access list.",
+                tx.chain
+            )));
+            return;
+        };
+        view.loading = true;
+        view.declared = None;
+        let rpc = self.providers.rpc.clone();
+        let host = self.rpc_host(&tx.chain);
+        self.command_bus()
+            .spawn_scheduled(host, Priority::Foreground, move || async move {
+                let result = access_list::fetch_declared(rpc, rpc_url, tx.hash.clone()).await;
+                Message::AccessListLoaded {
+                    hash: tx.hash,
+                    result,
+                }
+            });
+    }
+
+    /// Open the previous or next transaction of the viewed one's block.
+    fn step_in_block(&mut self, forward: bool) {
+        let Some(SelectedEntity::Transaction(tx)) = self.state.selected.clone() else {
+            return;
+        };
+        let Some(context) = self
+            .state
+            .current_transaction
+            .as_ref()
+            .filter(|data| data.identifier == tx.hash)
+            .and_then(|data| data.block_context.as_ref())
+        else {
+            self.show_status("Block position not loaded for this transaction");
+            return;
+        };
+        let Some(hash) = context.neighbor(forward) else {
+            self.show_status(if forward {
+                "Last transaction in the block"
+            } else {
+                "First transaction in the block"
+            });
+            return;
+        };
+        let hash = format!("{hash:#x}");
+        self.dispatch(Action::SelectionChanged(SelectedEntity::Transaction(
+            TransactionRef {
+                label: short_hex(&hash),
+                hash,
+                chain: tx.chain,
+            },
+        )));
+    }
+
+    /// Load asset transfers once the Summary tab of a transaction is showing.
+    fn load_transfers_if_needed(&mut self) {
+        if self.state.navigation.main_view_mode != MainViewMode::Transaction
+            || self
+                .state
+                .navigation
+                .main_view_tab
+                .normalize(MainViewMode::Transaction)
+                != MainViewTab::TransactionSummary
+            || self.state.transfers.loading
+        {
+            return;
+        }
+        let Some(SelectedEntity::Transaction(tx)) = self.state.selected.clone() else {
+            return;
+        };
+        if !self.state.transfers.needs_load(&tx.hash) {
+            return;
+        }
+        let view = &mut self.state.transfers;
+        view.hash = Some(tx.hash.clone());
+        let Some(rpc_url) = self.state.secrets.rpc_url(&tx.chain).map(str::to_string) else {
+            view.result = Some(Err(format!(
+                "Configure an RPC endpoint for {} to list transfers.",
+                tx.chain
+            )));
+            return;
+        };
+        view.loading = true;
+        view.result = None;
+        let cache = TokenCache::for_chain(&self.storage, &tx.chain);
+        let tenderly = self.tenderly_for(&tx.chain);
+        let rpc = self.providers.rpc.clone();
+        let host = self.rpc_host(&tx.chain);
+        self.command_bus()
+            .spawn_scheduled(host, Priority::Foreground, move || async move {
+                let result =
+                    transfers::fetch_transfers(rpc, rpc_url, tx.hash.clone(), cache, tenderly)
+                        .await;
+                Message::TransfersLoaded {
+                    hash: tx.hash,
+                    result,
+                }
+            });
+    }
+
+    /// Tenderly credentials when `chain` traces through Tenderly.
+    fn tenderly_for(&self, chain: &str) -> Option<tenderly::Tenderly> {
+        let name = resolve_chain(chain)?.name.to_ascii_lowercase();
+        match self.state.trace_backends.get(&name) {
+            Some(tenderly::TraceBackend::Tenderly) => {
+                tenderly::Tenderly::for_chain(&self.state.secrets, chain)
+            }
+            _ => None,
+        }
+    }
+
+    /// Load balance deltas once the Balance Diff tab of a transaction is showing.
+    fn load_balance_diff_if_needed(&mut self) {
+        if self.state.navigation.main_view_mode != MainViewMode::Transaction
+            || self
+                .state
+                .navigation
+                .main_view_tab
+                .normalize(MainViewMode::Transaction)
+                != MainViewTab::TransactionBalanceDiff
+            || self.state.balance_diff.loading
+        {
+            return;
+        }
+        let Some(SelectedEntity::Transaction(tx)) = self.state.selected.clone() else {
+            return;
+        };
+        if !self.state.balance_diff.needs_load(&tx.hash) {
+            return;
+        }
+        let view = &mut self.state.balance_diff;
+        view.hash = Some(tx.hash.clone());
+        let Some(rpc_url) = self.state.secrets.rpc_url(&tx.chain).map(str::to_string) else {
+            view.result = Some(Err(format!(
+                "Configure an RPC endpoint for {} to compute balance changes.",
+                tx.chain
+            )));
+            return;
+        };
+        view.loading = true;
+        view.result = None;
+        self.state.usage.record_feature("balance diff");
+        let cache = TokenCache::for_chain(&self.storage, &tx.chain);
+        let tenderly = self.tenderly_for(&tx.chain);
+        let rpc = self.providers.rpc.clone();
+        let host = self.rpc_host(&tx.chain);
+        self.command_bus()
+            .spawn_scheduled(host, Priority::Foreground, move || async move {
+                let result = balance_diff::fetch_balance_diff(
+                    rpc,
+                    rpc_url,
+                    tx.hash.clone(),
+                    cache,
+                    tenderly,
+                )
+                .await;
+                Message::BalanceDiffLoaded {
+                    hash: tx.hash,
+                    result,
+                }
+            });
+    }
+
+    /// Hydrate the address on every configured chain once the Chains tab is
+    /// showing. Each chain reports back on its own, so rows fill in as they
+    /// arrive.
+    fn load_multichain_if_needed(&mut self) {
+        if self.state.navigation.main_view_mode != MainViewMode::Address
+            || self
+                .state
+                .navigation
+                .main_view_tab
+                .normalize(MainViewMode::Address)
+                != MainViewTab::AddressChains
+        {
+            return;
+        }
+        let Some(SelectedEntity::Address(addr)) = self.state.selected.clone() else {
+            return;
+        };
+        if !self.state.multichain.needs_load(&addr.address) {
+            return;
+        }
+        let chains = multichain::configured_chains(&self.state.secrets, &self.state.hidden_chains);
+        self.state.multichain = MultichainView {
+            address: Some(addr.address.clone()),
+            rows: chains
+                .iter()
+                .map(|(chain, _)| multichain::ChainRow {
+                    chain: chain.clone(),
+                    result: None,
+                })
+                .collect(),
+            selected_index: 0,
+        };
+        self.state.usage.record_feature("all chains");
+        for (chain, rpc_url) in chains {
+            let api_key = self
+                .state
+                .secrets
+                .explorer_api_key(&chain)
+                .map(str::to_string);
+            let target = AddressRef {
+                label: addr.label.clone(),
+                address: addr.address.clone(),
+                chain,
+            };
+            let host = host_key(Some(&rpc_url), &target.chain);
+            let providers = self.providers_for(&target.chain);
+            self.command_bus()
+                .spawn_scheduled(host, Priority::Foreground, move || async move {
+                    let result = multichain::fetch_chain_summary(
+                        providers,
+                        target.clone(),
+                        rpc_url,
+                        api_key,
+                    )
+                    .await;
+                    Message::ChainSummaryLoaded {
+                        address: target.address,
+                        chain: target.chain,
+                        result,
+                    }
+                });
+        }
+    }
+
+    /// Load the signed bytes once the Raw tab of a transaction is showing.
+    fn load_raw_if_needed(&mut self) {
+        if self.state.navigation.main_view_mode != MainViewMode::Transaction
+            || self
+                .state
+                .navigation
+                .main_view_tab
+                .normalize(MainViewMode::Transaction)
+                != MainViewTab::TransactionRaw
+            || self.state.raw.loading
+        {
+            return;
+        }
+        let Some(SelectedEntity::Transaction(tx)) = self.state.selected.clone() else {
+            return;
+        };
+        if !self.state.raw.needs_load(&tx.hash) {
+            return;
+        }
+        let view = &mut self.state.raw;
+        view.hash = Some(tx.hash.clone());
+        let Some(rpc_url) = self.state.secrets.rpc_url(&tx.chain).map(str::to_string) else {
+            view.result = Some(Err(format!(
+                "Configure an RPC endpoint for {} to load the raw transaction.",
+                tx.chain
+            )));
+            return;
+        };
+        view.loading = true;
+        view.result = None;
+        let rpc = self.providers.rpc.clone();
+        let host = self.rpc_host(&tx.chain);
+        self.command_bus()
+            .spawn_scheduled(host, Priority::Foreground, move || async move {
+                let result = raw_tx::fetch_raw(rpc, rpc_url, tx.hash.clone()).await;
+                Message::RawLoaded {
+                    hash: tx.hash,
+                    result,
+                }
+            });
+    }
+
+    /// The main view tab showing, with custom tabs resolved for the mode.
+    fn active_main_view_tab(&self) -> MainViewTab {
+        let navigation = &self.state.navigation;
+        self.state
+            .custom_tabs
+            .resolve(navigation.main_view_tab, navigation.main_view_mode)
+    }
+
+    /// Run a custom tab's script once it shows for a selection it has no
+    /// output for.
+    fn load_custom_tab_if_needed(&mut self) {
+        let MainViewTab::Custom(index) = self.active_main_view_tab() else {
+            return;
+        };
+        let Some(entity) = self.state.selected.clone() else {
+            return;
+        };
+        let Some(tab) = self.state.custom_tabs.tabs.get(index).cloned() else {
+            return;
+        };
+        let subject = custom_tabs::subject(&entity);
+        if tab.command.is_empty() || self.state.custom_tabs.content(&tab, &subject).is_some() {
+            return;
+        }
+        self.state
+            .custom_tabs
+            .set_content(&tab.title, &subject, TabContent::Loading);
+        self.command_bus().spawn_async(move || async move {
+            let title = tab.title.clone();
+            let result = custom_tabs::run(tab, entity).await;
+            Message::CustomTabLoaded {
+                title,
+                subject,
+                result,
+            }
+        });
+    }
+
+    /// `r` on a custom tab: run its script again for the selection.
+    fn rerun_custom_tab(&mut self) {
+        let MainViewTab::Custom(index) = self.active_main_view_tab() else {
+            return;
+        };
+        let (Some(entity), Some(tab)) = (
+            self.state.selected.as_ref(),
+            self.state.custom_tabs.tabs.get(index).cloned(),
+        ) else {
+            return;
+        };
+        if tab.command.is_empty() {
+            self.show_status(format!("{} is filled by plugins", tab.title));
+            return;
+        }
+        let subject = custom_tabs::subject(entity);
+        if self.state.custom_tabs.content(&tab, &subject) == Some(&TabContent::Loading) {
+            return;
+        }
+        self.state.custom_tabs.invalidate(&tab, &subject);
+    }
+
+    /// `:link`: copy an `evmtui://` link to the selection and tab on screen.
+    fn copy_link(&mut self) {
+        let Some(entity) = self.state.selected.clone() else {
+            self.show_status("Nothing selected to link to");
+            return;
+        };
+        let tab = deep_link::tab_param(self.active_main_view_tab(), &self.state.custom_tabs);
+        let uri = deep_link::to_uri(&entity, tab.as_deref());
+        self.state.usage.record_feature("deep link");
+        self.copy_to_clipboard(uri);
+    }
+
+    /// Select a link's address or transaction and switch to its tab.
+    fn open_link(&mut self, link: DeepLink) {
+        self.dispatch(Action::SelectionChanged(link.entity));
+        if let Some(tab) = link
+            .tab
+            .as_deref()
+            .and_then(|tab| deep_link::resolve_tab(tab, &self.state.custom_tabs))
+        {
+            let mode = self.state.navigation.main_view_mode;
+            self.state.navigation.main_view_tab = tab.normalize(mode);
+        }
+    }
+
+    /// `:tabs`: the custom main view tabs.
+    fn list_custom_tabs(&mut self) {
+        let tabs = &self.state.custom_tabs.tabs;
+        if tabs.is_empty() {
+            self.show_status(
+                "No custom tabs • :tab add <title> [--address|--transaction] [command args…]",
+            );
+            return;
+        }
+        let listed: Vec<String> = tabs
+            .iter()
+            .map(|tab| match tab.command.as_str() {
+                "" => format!("{} (plugins)", tab.title),
+                command => format!("{} ({command})", tab.title),
+            })
+            .collect();
+        self.show_status(format!("Custom tabs: {}", listed.join(" • ")));
+    }
+
+    /// `:tab add <title> [--address|--transaction] [command args…]` or
+    /// `:tab remove <title>`; a tab without a command is left to plugins.
+    fn custom_tab_command(&mut self, first: Option<&str>, rest: Vec<&str>) {
+        match (first, rest.as_slice()) {
+            (Some("add"), [title, rest @ ..]) => {
+                let (scope, command) = match rest {
+                    ["--address", command @ ..] => (custom_tabs::TabScope::Address, command),
+                    ["--transaction", command @ ..] => {
+                        (custom_tabs::TabScope::Transaction, command)
+                    }
+                    command => (custom_tabs::TabScope::Both, command),
+                };
+                let (command, args) = match command {
+                    [command, args @ ..] => (
+                        command.to_string(),
+                        args.iter().map(|arg| arg.to_string()).collect(),
+                    ),
+                    [] => (String::new(), Vec::new()),
+                };
+                self.state.custom_tabs.set(custom_tabs::CustomTab {
+                    title: title.to_string(),
+                    scope,
+                    command,
+                    args,
+                });
+                self.save_custom_tabs();
+                self.state.usage.record_feature("custom tab");
+                self.show_status(format!("Added tab {title}"));
+            }
+            (Some("remove"), [title]) => match self.state.custom_tabs.remove(title) {
+                Some(_) => {
+                    // Indexes past the removed tab shift, so leave custom tabs.
+                    if matches!(self.state.navigation.main_view_tab, MainViewTab::Custom(_)) {
+                        self.state.navigation.main_view_tab =
+                            MainViewTab::default().normalize(self.state.navigation.main_view_mode);
+                    }
+                    self.save_custom_tabs();
+                    self.show_status(format!("Removed tab {title}"));
+                }
+                None => self.show_status(format!("No custom tab named {title}")),
+            },
+            _ => self.show_status(
+                "Usage: :tab add <title> [--address|--transaction] [command args…] | :tab remove <title>",
+            ),
+        }
+    }
+
+    fn save_custom_tabs(&self) {
+        let tabs = self.state.custom_tabs.tabs.clone();
+        self.storage.write("custom tabs", move |storage| {
+            storage.settings().set_json(SettingKey::CustomTabs, &tabs)
+        });
+    }
+
+    /// Run `eth_createAccessList` for the selected transaction's call.
+    fn generate_access_list(&mut self) {
+        let Some(SelectedEntity::Transaction(tx)) = self.state.selected.clone() else {
+            return;
+        };
+        if self.state.access_list.generating {
+            return;
+        }
+        let Some(rpc_url) = self.state.secrets.rpc_url(&tx.chain).map(str::to_string) else {
+            self.show_status(format!("Configure an RPC endpoint for {}", tx.chain));
+            return;
+        };
+        self.state.access_list.generating = true;
+        self.state.access_list.generated = None;
+        self.state.usage.record_feature("create access list");
+        let rpc = self.providers.rpc.clone();
+        let host = self.rpc_host(&tx.chain);
+        self.command_bus()
+            .spawn_scheduled(host, Priority::Foreground, move || async move {
+                let result = access_list::generate(rpc, rpc_url, tx.hash.clone()).await;
+                Message::AccessListGenerated {
+                    hash: tx.hash,
+                    result,
+                }
+            });
+    }
+
+    fn toggle_follow(&mut self) {
+        let Some(SelectedEntity::Address(addr)) = self.state.selected.clone() else {
+            return;
+        };
+        if self.state.follow.is_following(&addr.address) {
+            self.state.follow.stop();
+            self.show_status("Stopped following new blocks");
+            return;
+        }
+        if self.state.secrets.rpc_url(&addr.chain).is_none() {
+            self.show_status(format!(
+                "Follow mode needs an RPC endpoint for {}",
+                addr.chain
+            ));
+            return;
+        }
+        self.state.usage.record_feature("follow address");
+        self.state.follow.start(&addr.address);
+        self.last_follow_poll = None;
+        // With a WebSocket endpoint, new heads and the address's logs are
+        // pushed and each one triggers a scan instead of waiting for the timer.
+        if let Some(url) = self
+            .state
+            .secrets
+            .rpc_url(&addr.chain)
+            .map(|urls| anvil::normalize_url(&self.providers.rpc, urls))
+            .filter(|url| ws::is_ws(url))
+            && let Ok(target) = addr.address.parse::<Address>()
+        {
+            let bus = self.command_bus();
+            self.state.follow.set_subscriptions(vec![
+                bus.subscribe(url.clone(), ws::SubscriptionKind::NewHeads),
+                bus.subscribe(url, ws::SubscriptionKind::Logs(target)),
+            ]);
+        }
+        self.show_status(format!(
+            "Following {} • new transactions appear at the top",
+            short_hex(&addr.address)
+        ));
+    }
+
+    /// While following, check the chain head every few seconds and scan any
+    /// new blocks for transactions touching the followed address.
+    fn poll_follow(&mut self) {
+        let Some(address) = self.state.follow.address().map(str::to_string) else {
+            return;
+        };
+        let interval = if self.state.follow.is_live() {
+            follow::LIVE_FALLBACK_INTERVAL
+        } else {
+            follow::POLL_INTERVAL
+        };
+        if self.follow_poll_in_flight
+            || self
+                .last_follow_poll
+                .is_some_and(|last| last.elapsed() < interval)
+        {
+            return;
+        }
+        let Some(SelectedEntity::Address(addr)) = self.state.selected.as_ref() else {
+            return;
+        };
+        let Some(rpc_url) = self.state.secrets.rpc_url(&addr.chain).map(str::to_string) else {
+            return;
+        };
+        let after_block = self.state.follow.last_block();
+        let also = self.state.follow.take_log_hashes();
+        self.last_follow_poll = Some(Instant::now());
+        self.follow_poll_in_flight = true;
+        let host = host_key(Some(&rpc_url), &addr.chain);
+        let rpc = self.providers.rpc.clone();
+        self.command_bus()
+            .spawn_scheduled(host, Priority::Poll, move || async move {
+                Message::FollowPolled(follow::poll(rpc, rpc_url, address, after_block, also).await)
+            });
+    }
+
+    fn handle_subscription_event(&mut self, event: ws::SubscriptionEvent) {
+        match event {
+            ws::SubscriptionEvent::NewHead(number) => {
+                if self.state.follow.is_live()
+                    && self
+                        .state
+                        .follow
+                        .last_block()
+                        .is_none_or(|last| number > last)
+                {
+                    self.last_follow_poll = None;
+                }
+            }
+            ws::SubscriptionEvent::Log {
+                block_number,
+                transaction_hash: Some(hash),
+            } if self.state.follow.is_live() => {
+                self.state.follow.note_log(hash, block_number);
+                self.last_follow_poll = None;
+            }
+            ws::SubscriptionEvent::Log { .. } => {}
+            ws::SubscriptionEvent::PendingTransaction(_) => {
+                let commands = self.command_bus();
+                if let Some(ActiveModal::Health(modal)) = self.modal.as_mut() {
+                    let mut ctx = AppContext {
+                        state: &mut self.state,
+                        storage: &mut self.storage,
+                        commands,
+                    };
+                    let _ = modal.update(&HealthCommand::PendingSeen, &mut ctx);
+                }
+            }
+        }
+    }
+
+    /// Prepend newly landed rows and shift the table selection with them so the
+    /// highlighted row stays the one the user picked.
+    fn apply_follow_update(&mut self, update: follow::FollowUpdate) {
+        if !self.state.follow.is_following(&update.address) {
+            return;
+        }
+        self.state.follow.set_last_block(update.latest_block);
+        let (limit, currency) = match self.state.selected.as_ref() {
+            Some(SelectedEntity::Address(addr)) => (
+                fetch_limits::for_chain(&self.state.fetch_limits, &addr.chain).transactions,
+                chains::currency(&addr.chain),
+            ),
+            _ => (FetchLimits::default().transactions, "ETH"),
+        };
+        let Some(address) = self
+            .state
+            .current_address
+            .as_mut()
+            .filter(|data| data.identifier == update.address)
+        else {
+            return;
+        };
+        let rows: Vec<_> = update
+            .transactions
+            .iter()
+            .map(|tx| AddressTransactionRow::from_transaction(&update.address, tx, currency))
+            .collect();
+        if rows.is_empty() {
+            return;
+        }
+        let table = address
+            .transactions_table
+            .get_or_insert_with(|| AddressTransactionsTable {
+                source_label: "RPC".into(),
+                source_api_version: "follow".into(),
+                limit,
+                rows: Vec::new(),
+            });
+        let inserted = follow::prepend_rows(&mut table.rows, rows);
+        if inserted == 0 {
+            return;
+        }
+        for row in table.rows[..inserted].iter().cloned() {
+            self.state.follow.highlight(&row.hash);
+            self.state
+                .transaction_preview_cache
+                .insert(row.hash.clone(), row);
+        }
+        self.state.address_transactions_view.selected_index += inserted;
+        self.show_status(format!(
+            "{inserted} new transaction(s) up to block {}",
+            update.latest_block
+        ));
+    }
+
+    fn cycle_auto_refresh(&mut self) {
+        let interval = auto_refresh::next_interval(self.state.auto_refresh);
+        self.state.auto_refresh = interval;
+        let seconds = auto_refresh::to_setting(interval);
+        self.storage.write("auto-refresh interval", move |storage| {
+            storage
+                .settings()
+                .set_json(SettingKey::AutoRefresh, &seconds)
+        });
+        self.show_status(match interval {
+            Some(interval) => format!("Auto-refresh every {}", auto_refresh::label(interval)),
+            None => "Auto-refresh off".to_string(),
+        });
+    }
+
+    fn resize_sidebar(&mut self, wider: bool) {
+        self.state.layout.resize_sidebar(wider);
+        self.save_layout();
+        self.show_status(format!(
+            "Sidebar width {} (Ctrl+←/→ resize • Ctrl+B hide)",
+            self.state.layout.sidebar_width
+        ));
+    }
+
+    fn toggle_sidebar(&mut self) {
+        let layout = &mut self.state.layout;
+        layout.sidebar_hidden = !layout.sidebar_hidden;
+        if layout.sidebar_hidden
+            && matches!(self.state.navigation.focused_pane, FocusedPane::Sidebar)
+        {
+            self.state.navigation.focus_pane(FocusedPane::MainView);
+        }
+        self.save_layout();
+        self.show_status(if self.state.layout.sidebar_hidden {
+            "Sidebar hidden (Ctrl+B or 2 shows it)"
+        } else {
+            "Sidebar shown"
+        });
+    }
+
+    /// Focusing a hidden sidebar brings it back rather than moving focus
+    /// somewhere invisible.
+    fn reveal_focused_sidebar(&mut self) {
+        if self.state.layout.sidebar_hidden
+            && matches!(self.state.navigation.focused_pane, FocusedPane::Sidebar)
+        {
+            self.state.layout.sidebar_hidden = false;
+            self.save_layout();
+        }
+    }
+
+    fn save_layout(&mut self) {
+        let layout = self.state.layout;
+        self.storage.write("layout", move |storage| {
+            storage.settings().set_json(SettingKey::Layout, &layout)
+        });
+    }
+
+    /// Load the trash on the storage thread; [`Message::TrashLoaded`] opens it.
+    fn open_trash_modal(&mut self) {
+        if self.modal.is_some() {
+            return;
+        }
+        self.command_bus().read_storage(
+            &self.storage,
+            |storage| storage.trash().list(),
+            Message::TrashLoaded,
+        );
+    }
+
+    fn restore_favorite(&mut self, entry: TrashRecord) {
+        let record = entry.record;
+        let label = record
+            .label
+            .clone()
+            .unwrap_or_else(|| record.identifier.clone());
+        let entity = match entry.table {
+            FavoritesTable::Addresses => {
+                self.state
+                    .favorite_addresses
+                    .insert(record.identifier.clone());
+                SelectedEntity::Address(AddressRef {
+                    label,
+                    address: record.identifier.clone(),
+                    chain: record.chain,
+                })
+            }
+            FavoritesTable::Transactions => {
+                self.state
+                    .favorite_transactions
+                    .insert(record.identifier.clone());
+                SelectedEntity::Transaction(TransactionRef {
+                    label,
+                    hash: record.identifier.clone(),
+                    chain: record.chain,
+                })
+            }
+        };
+        let status = match self.sidebar_command(SidebarCommand::AddFavorite(entity)) {
+            Ok(()) => format!("Restored {}", short_hex(&record.identifier)),
+            Err(err) => format!(
+                "Restored {}, but the sidebar did not update: {err:#}",
+                short_hex(&record.identifier)
+            ),
+        };
+        self.show_status(status);
+    }
+
+    fn open_diagnostics_modal(&mut self) {
+        if self.modal.is_some() {
+            return;
+        }
+        self.state.usage.record_feature("diagnostics");
+        self.state
+            .usage
+            .absorb_pending_api_calls(self.providers.rpc.stats());
+        self.modal = Some(ActiveModal::Diagnostics(DiagnosticsModal::new()));
+        self.state.navigation.focus_modal();
+    }
+
+    /// CREATE/CREATE2 calculator; code checks run on the selection's chain.
+    fn open_deploy_address_modal(&mut self) {
+        if self.modal.is_some() {
+            return;
+        }
+        let chain = match self.state.selected.as_ref() {
+            Some(SelectedEntity::Address(addr)) => addr.chain.clone(),
+            Some(SelectedEntity::Transaction(tx)) => tx.chain.clone(),
+            None => search::SEARCH_CHAIN.to_string(),
+        };
+        self.state.usage.record_feature("deployment address");
+        self.modal = Some(ActiveModal::DeployAddress(DeployAddressModal::new(chain)));
+        self.state.navigation.focus_modal();
+    }
+
+    /// The selected address as a QR code to scan with a mobile wallet.
+    fn open_qr_modal(&mut self) {
+        if self.modal.is_some() {
+            return;
+        }
+        let Some(SelectedEntity::Address(addr)) = self.state.selected.as_ref() else {
+            self.show_status("Select an address to show its QR code");
+            return;
+        };
+        let address = checksum_address(&addr.address);
+        match QrModal::new(address, addr.chain.clone()) {
+            Ok(modal) => {
+                self.state.usage.record_feature("address qr");
+                self.open_modal(ActiveModal::Qr(modal));
+            }
+            Err(err) => self.show_status(err),
+        }
+    }
+
+    fn pin_note(&mut self, text: String, source: String) {
+        if text.trim().is_empty() {
+            self.show_status("Nothing to pin here");
+            return;
+        }
+        let note = scratchpad::note(&self.state.investigation, &text, source);
+        self.storage
+            .write("scratchpad", move |storage| storage.scratchpad().add(&note));
+        self.state.usage.record_feature("scratchpad");
+        let first = text.lines().next().unwrap_or_default();
+        let preview = if first.chars().count() > 48 {
+            format!("{}…", first.chars().take(47).collect::<String>())
+        } else {
+            first.to_string()
+        };
+        self.show_status(format!("Pinned to {}: {preview}", self.state.investigation));
+    }
+
+    /// Load the investigation's notes on the storage thread;
+    /// [`Message::ScratchpadLoaded`] opens the modal.
+    fn open_scratchpad_modal(&mut self) {
+        if self.modal.is_some() {
+            return;
+        }
+        let investigation = self.state.investigation.clone();
+        let read = investigation.clone();
+        self.command_bus().read_storage(
+            &self.storage,
+            move |storage| {
+                let notes = storage.scratchpad().list(&read)?;
+                let others = storage
+                    .scratchpad()
+                    .investigations()?
+                    .into_iter()
+                    .filter(|name| *name != read)
+                    .collect();
+                Ok((notes, others))
+            },
+            |result| Message::ScratchpadLoaded {
+                investigation,
+                result,
+            },
+        );
+    }
+
+    /// Open the log alert rules on the one that matched last, or on the new
+    /// rule form when a `draft` is given.
+    fn open_log_alerts_modal(&mut self, draft: Option<log_alerts::LogAlertRule>) {
+        let alerts = &self.state.log_alerts;
+        let selected = alerts
+            .latest
+            .as_ref()
+            .and_then(|latest| alerts.rules.iter().position(|rule| rule.key() == *latest))
+            .unwrap_or_default();
+        self.open_modal(ActiveModal::LogAlerts(LogAlertsModal::new(draft, selected)));
+    }
+
+    /// `a` on the Events tab: a new log alert rule from the current filter.
+    fn alert_on_events_filter(&mut self) {
+        let Some(SelectedEntity::Address(addr)) = self.state.selected.as_ref() else {
+            return;
+        };
+        let abi = self
+            .state
+            .current_address
+            .as_ref()
+            .filter(|data| data.identifier == addr.address)
+            .and_then(|data| data.contract_source.as_ref())
+            .map(|source| source.abi.as_str());
+        let events = &self.state.events;
+        let draft = log_alerts::draft(
+            &addr.chain,
+            &addr.address,
+            abi,
+            &events.filter,
+            events.selected().map(|event| event.name.as_str()),
+        );
+        self.open_log_alerts_modal(Some(draft));
+    }
+
+    fn start_plugins(&mut self) {
+        let bus = self.command_bus();
+        for config in self.state.plugins.configs.clone() {
+            let name = config.name.clone();
+            self.state.plugins.started(&name, bus.plugin(config));
+        }
+    }
+
+    /// Tell plugins about the transaction just shown.
+    fn announce_transaction(&self) {
+        if let (Some(SelectedEntity::Transaction(tx)), Some(data)) = (
+            self.state.selected.as_ref(),
+            self.state.current_transaction.as_ref(),
+        ) {
+            self.state
+                .plugins
+                .broadcast(&PluginEvent::transaction_hydrated(&tx.chain, data));
+        }
+    }
+
+    fn apply_plugin_output(&mut self, name: String, output: plugins::PluginOutput) {
+        match output {
+            plugins::PluginOutput::Action(plugins::PluginAction::AddNote { text }) => {
+                self.pin_note(text, format!("plugin {name}"));
+            }
+            plugins::PluginOutput::Action(plugins::PluginAction::ShowPanel {
+                subject,
+                tab,
+                title,
+                lines,
+            }) => {
+                let subject = subject.or_else(|| {
+                    self.state.selected.as_ref().map(|entity| match entity {
+                        SelectedEntity::Address(addr) => addr.address.clone(),
+                        SelectedEntity::Transaction(tx) => tx.hash.clone(),
+                    })
+                });
+                if let Some(subject) = subject {
+                    self.state
+                        .plugins
+                        .show_panel(&name, &subject, tab.as_deref(), title, lines);
+                }
+            }
+            plugins::PluginOutput::Action(plugins::PluginAction::Status { text }) => {
+                self.show_status(format!("{name}: {text}"));
+            }
+            plugins::PluginOutput::Invalid(err) => {
+                self.show_status(format!("Plugin {name} wrote an unknown line: {err}"));
+            }
+            plugins::PluginOutput::Exited(reason) => {
+                self.show_status(format!("Plugin {name} {reason}"));
+                self.state.plugins.exited(&name, reason);
+            }
+        }
+    }
+
+    /// `:plugins`: each configured plugin with its state.
+    fn list_plugins(&mut self) {
+        let plugins = &self.state.plugins;
+        if plugins.configs.is_empty() {
+            self.show_status("No plugins • :plugin add <name> <command> [args…]");
+            return;
+        }
+        let listed: Vec<String> = plugins
+            .configs
+            .iter()
+            .map(|config| format!("{} ({})", config.name, plugins.status(&config.name)))
+            .collect();
+        self.show_status(format!("Plugins: {}", listed.join(" • ")));
+    }
+
+    /// `:plugin add <name> <command> [args…]`, `:plugin remove <name>` or
+    /// `:plugin restart <name>`.
+    fn plugin_command(&mut self, first: Option<&str>, rest: Vec<&str>) {
+        match (first, rest.as_slice()) {
+            (Some("add"), [name, command, args @ ..]) => {
+                let config = plugins::PluginConfig {
+                    name: name.to_string(),
+                    command: command.to_string(),
+                    args: args.iter().map(|arg| arg.to_string()).collect(),
+                };
+                self.state.plugins.set(config.clone());
+                self.save_plugins();
+                let handle = self.command_bus().plugin(config);
+                self.state.plugins.started(name, handle);
+                self.state.usage.record_feature("plugin");
+                self.show_status(format!("Started plugin {name}"));
+            }
+            (Some("remove"), [name]) => match self.state.plugins.remove(name) {
+                Some(_) => {
+                    self.save_plugins();
+                    self.show_status(format!("Removed plugin {name}"));
+                }
+                None => self.show_status(format!("No plugin named {name}")),
+            },
+            (Some("restart"), [name]) => match self.state.plugins.config(name).cloned() {
+                Some(config) => {
+                    let handle = self.command_bus().plugin(config);
+                    self.state.plugins.started(name, handle);
+                    self.show_status(format!("Restarted plugin {name}"));
+                }
+                None => self.show_status(format!("No plugin named {name}")),
+            },
+            _ => self.show_status(
+                "Usage: :plugin add <name> <command> [args…] | :plugin remove|restart <name>",
+            ),
+        }
+    }
+
+    fn save_plugins(&self) {
+        let configs = self.state.plugins.configs.clone();
+        self.storage.write("plugins", move |storage| {
+            storage.settings().set_json(SettingKey::Plugins, &configs)
+        });
+    }
+
+    /// `:scratchpad [name|export [file]]`: open the scratchpad, switching
+    /// investigation first when a name is given, or write it as Markdown.
+    fn scratchpad_command(&mut self, first: Option<&str>, rest: Vec<&str>) -> AppResult<()> {
+        match first {
+            None => {
+                self.open_scratchpad_modal();
+                Ok(())
+            }
+            Some("export") => {
+                let investigation = self.state.investigation.clone();
+                let path = if rest.is_empty() {
+                    scratchpad::default_path(&investigation)
+                } else {
+                    csv_export::expand_path(&rest.join(" "))
+                };
+                let written = path.clone();
+                self.command_bus().read_storage(
+                    &self.storage,
+                    move |storage| {
+                        let notes = storage.scratchpad().list(&investigation)?;
+                        scratchpad::write_markdown(&written, &investigation, &notes)?;
+                        Ok(notes.len())
+                    },
+                    |result| Message::ScratchpadExported { path, result },
+                );
+                Ok(())
+            }
+            Some(name) if rest.is_empty() => {
+                if self.switch_session(name) {
+                    self.open_scratchpad_modal();
+                }
+                Ok(())
+            }
+            Some(_) => {
+                self.show_status("Usage: :scratchpad [name] | :scratchpad export [file]");
+                Ok(())
+            }
+        }
+    }
+
+    /// Stash the open session's workspace (selection, tab, watchlist, log
+    /// alert rules and thresholds) and open `name`'s, a blank one if it is
+    /// new. Its notes are the scratchpad of the investigation of that name.
+    /// Returns whether `name` is open now.
+    fn switch_session(&mut self, name: &str) -> bool {
+        let name = match scratchpad::investigation_name(name) {
+            Ok(name) => name,
+            Err(err) => {
+                self.show_status(err);
+                return false;
+            }
+        };
+        if name == self.state.investigation {
+            return true;
+        }
+        let open = Session {
+            name: self.state.investigation.clone(),
+            saved_at: watchlist::unix_now(),
+            selected: self.state.selected.clone(),
+            tab: self.state.navigation.main_view_tab,
+            watchlist: self.state.watchlist.entries.clone(),
+            log_alerts: self.state.log_alerts.rules.clone(),
+            thresholds: self.state.thresholds.alerts.clone(),
+        };
+        self.state.sessions.stash(open);
+        let next = self.state.sessions.take(&name);
+        self.state.investigation = name.clone();
+        self.state.watchlist = WatchlistState::new(next.watchlist);
+        self.state.log_alerts = LogAlertState::new(next.log_alerts);
+        self.state.thresholds = ThresholdState::new(next.thresholds);
+        let saved = self.state.sessions.saved.clone();
+        let entries = self.state.watchlist.entries.clone();
+        let rules = self.state.log_alerts.rules.clone();
+        let alerts = self.state.thresholds.alerts.clone();
+        self.storage.write("session", move |storage| {
+            let settings = storage.settings();
+            settings.set_json(SettingKey::Sessions, &saved)?;
+            settings.set_json(SettingKey::Investigation, &name)?;
+            settings.set_json(SettingKey::Watchlist, &entries)?;
+            settings.set_json(SettingKey::LogAlerts, &rules)?;
+            settings.set_json(SettingKey::Thresholds, &alerts)
+        });
+        match next.selected {
+            Some(entity) => {
+                self.dispatch(Action::SelectionChanged(entity));
+                let mode = self.state.navigation.main_view_mode;
+                self.state.navigation.main_view_tab = next.tab.normalize(mode);
+            }
+            None => {
+                self.state.follow.stop();
+                self.state.selected = None;
+                self.state.current_address = None;
+                self.state.current_transaction = None;
+            }
+        }
+        self.state.usage.record_feature("session");
+        self.show_status(format!("Session {}", self.state.investigation));
+        true
+    }
+
+    /// Unit/base/date/hash converter, seeded with the text after `:convert`.
+    fn open_convert_modal(&mut self, input: String) {
+        if self.modal.is_some() {
+            return;
+        }
+        self.state.usage.record_feature("converter");
+        self.modal = Some(ActiveModal::Convert(ConvertModal::new(input)));
+        self.state.navigation.focus_modal();
+    }
+
+    /// EIP-712 / personal_sign digest and signer recovery; the signer opens
+    /// on the selection's chain.
+    fn open_typed_data_modal(&mut self) {
+        if self.modal.is_some() {
+            return;
+        }
+        let chain = match self.state.selected.as_ref() {
+            Some(SelectedEntity::Address(addr)) => addr.chain.clone(),
+            Some(SelectedEntity::Transaction(tx)) => tx.chain.clone(),
+            None => search::SEARCH_CHAIN.to_string(),
+        };
+        self.state.usage.record_feature("signature inspector");
+        self.modal = Some(ActiveModal::TypedData(TypedDataModal::new(chain)));
+        self.state.navigation.focus_modal();
+    }
+
+    /// Call a state-changing function of the viewed contract, signed with
+    /// the configured keystore or Ledger.
+    fn open_write_modal(&mut self) {
+        if self.modal.is_some() {
+            return;
+        }
+        let Some(signer) = self.state.signer.clone() else {
+            self.show_status("Set a signer first with :keystore <name|path> or :ledger [index]");
+            return;
+        };
+        let (addr, source) = match (&self.state.selected, &self.state.current_address) {
+            (Some(SelectedEntity::Address(addr)), Some(data))
+                if data.identifier == addr.address =>
+            {
+                (addr.clone(), data.contract_source.clone())
+            }
+            _ => return,
+        };
+        let Some(source) = source else {
+            self.show_status("No verified ABI available to build a transaction");
+            return;
+        };
+        let Ok(contract) = addr.address.parse::<Address>() else {
+            return;
+        };
+        let functions = match write::writable_functions(&source.abi) {
+            Ok(functions) => functions,
+            Err(err) => {
+                self.show_status(err);
+                return;
+            }
+        };
+        self.state.usage.record_feature("write contract");
+        self.modal = Some(ActiveModal::Write(WriteModal::new(
+            addr.chain,
+            contract,
+            source.name,
+            signer,
+            functions,
+        )));
+        self.state.navigation.focus_modal();
+    }
+
+    /// Transactions sent from here that are not mined yet, with speed-up and
+    /// cancel actions.
+    fn open_pending_modal(&mut self) {
+        if self.modal.is_some() {
+            return;
+        }
+        self.state.usage.record_feature("pending transactions");
+        self.modal = Some(ActiveModal::Pending(PendingModal::new()));
+        self.state.navigation.focus_modal();
+    }
+
+    /// Multisig transactions the selected Safe has queued but not executed.
+    fn open_safe_queue_modal(&mut self) {
+        if self.modal.is_some() {
+            return;
+        }
+        let Some(SelectedEntity::Address(addr)) = self.state.selected.clone() else {
+            return;
+        };
+        let Ok(safe) = addr.address.parse::<Address>() else {
+            return;
+        };
+        let Some(service) = resolve_chain(&addr.chain).and_then(|info| info.safe_service) else {
+            self.show_status(format!("No Safe Transaction Service for {}", addr.chain));
+            return;
+        };
+        let host = host_key(Some(service), &addr.chain);
+        self.state.usage.record_feature("safe queue");
+        self.modal = Some(ActiveModal::SafeQueue(SafeQueueModal::new(
+            addr.chain.clone(),
+            safe,
+        )));
+        self.state.navigation.focus_modal();
+        let stats = self.providers.rpc.stats().clone();
+        self.command_bus()
+            .spawn_scheduled(host, Priority::Foreground, move || async move {
+                Message::SafeQueueLoaded(safe::fetch_queue(&stats, addr.chain, safe).await)
+            });
+    }
+
+    /// Gas used per function selector over the contract's latest history.
+    fn open_gas_analytics_modal(&mut self) {
+        if self.modal.is_some() {
+            return;
+        }
+        let addr = match (&self.state.selected, &self.state.current_address) {
+            (Some(SelectedEntity::Address(addr)), Some(data))
+                if data.identifier == addr.address =>
+            {
+                if !data
+                    .overview
+                    .as_ref()
+                    .is_some_and(|overview| overview.is_contract)
+                {
+                    self.show_status("Gas analytics needs a contract");
+                    return;
+                }
+                addr.clone()
+            }
+            _ => return,
+        };
+        self.state.usage.record_feature("gas analytics");
+        let api_key = self
+            .state
+            .secrets
+            .explorer_api_key(&addr.chain)
+            .map(str::to_string);
+        let rpc_url = self.state.secrets.rpc_url(&addr.chain).map(str::to_string);
+        self.modal = Some(ActiveModal::GasAnalytics(GasAnalyticsModal::new(
+            addr.clone(),
+        )));
+        self.state.navigation.focus_modal();
+        let providers = self.providers_for(&addr.chain);
+        let host = self.rpc_host(&addr.chain);
+        self.command_bus()
+            .spawn_scheduled(host, Priority::Foreground, move || async move {
+                Message::GasAnalyticsLoaded(
+                    gas_analytics::fetch_gas_report(providers, addr, api_key, rpc_url).await,
+                )
+            });
+    }
+
+    fn open_health_modal(&mut self) {
+        if self.modal.is_some() {
+            return;
+        }
+        self.state.usage.record_feature("dependency health");
+        // Watching the mempool needs a WebSocket endpoint on the selected chain.
+        let chain = match self.state.selected.as_ref() {
+            Some(SelectedEntity::Address(addr)) => Some(addr.chain.as_str()),
+            Some(SelectedEntity::Transaction(tx)) => Some(tx.chain.as_str()),
+            None => None,
+        };
+        let mempool = chain
+            .and_then(|chain| self.state.secrets.rpc_url(chain))
+            .map(|urls| anvil::normalize_url(&self.providers.rpc, urls))
+            .filter(|url| ws::is_ws(url))
+            .map(|url| {
+                let handle = self
+                    .command_bus()
+                    .subscribe(url.clone(), ws::SubscriptionKind::PendingTransactions);
+                (url, handle)
+            });
+        self.modal = Some(ActiveModal::Health(HealthModal::new(mempool)));
+        self.state.navigation.focus_modal();
+    }
+
+    fn export_bindings(&mut self) {
+        self.state.usage.record_feature("export bindings");
+        let source = match (&self.state.selected, &self.state.current_address) {
+            (Some(SelectedEntity::Address(addr)), Some(data))
+                if data.identifier == addr.address =>
+            {
+                data.contract_source.clone()
+            }
+            _ => None,
+        };
+        let Some(source) = source else {
+            self.show_status("No verified ABI available to generate bindings");
+            return;
+        };
+        let address = self
+            .state
+            .current_address
+            .as_ref()
+            .map(|data| data.identifier.clone())
+            .unwrap_or_default();
+        match bindings::export_sol_bindings(&source, &address) {
+            Ok(path) => self.show_status(format!("Wrote sol! bindings to {}", path.display())),
+            Err(err) => self.show_status(format!("Failed to export bindings: {err}")),
+        }
+    }
+
+    /// Run a command typed after `:`.
+    fn run_command(&mut self, input: &str) {
+        let mut words = input.split_whitespace();
+        match (words.next(), words.next()) {
+            (None, _) => {}
+            (Some("export"), Some("csv")) => {
+                let rest: Vec<&str> = words.collect();
+                let path = (!rest.is_empty()).then(|| csv_export::expand_path(&rest.join(" ")));
+                self.export_history(path);
+            }
+            (Some("export"), Some("json")) => {
+                let rest: Vec<&str> = words.collect();
+                self.export_json((!rest.is_empty()).then(|| rest.join(" ")));
+            }
+            (Some("export"), _) => {
+                self.show_status("Usage: :export csv [directory] | :export json [file|-]")
+            }
+            (Some("pipe"), Some(_)) => {
+                let command = input.trim_start()["pipe".len()..].trim();
+                self.pipe_view(command.to_string());
+            }
+            (Some("pipe"), None) => self.show_status("Usage: :pipe <command>"),
+            (Some("screenshot"), first) => {
+                let rest: Vec<&str> = first.into_iter().chain(words).collect();
+                self.screenshot_command(rest.join(" "));
+            }
+            (Some("sync"), Some("signatures")) => self.sync_signatures(),
+            (Some("sync"), _) => self.show_status("Usage: :sync signatures"),
+            (Some("convert"), first) => {
+                let rest: Vec<&str> = first.into_iter().chain(words).collect();
+                self.open_convert_modal(rest.join(" "));
+            }
+            (Some("verify"), None) => self.open_typed_data_modal(),
+            (Some("verify"), _) => self.show_status("Usage: :verify"),
+            (Some("pending"), None) => self.open_pending_modal(),
+            (Some("qr"), None) => self.open_qr_modal(),
+            (Some("qr"), _) => self.show_status("Usage: :qr"),
+            (Some("alerts"), None) => self.open_log_alerts_modal(None),
+            (Some("alerts"), _) => self.show_status("Usage: :alerts"),
+            (Some("thresholds"), None) => {
+                self.open_modal(ActiveModal::Thresholds(ThresholdsModal::default()))
+            }
+            (Some("thresholds"), _) => self.show_status("Usage: :thresholds"),
+            (Some("plugins"), None) => self.list_plugins(),
+            (Some("plugins"), _) => self.show_status("Usage: :plugins"),
+            (Some("plugin"), first) => {
+                let rest: Vec<&str> = words.collect();
+                self.plugin_command(first, rest);
+            }
+            (Some("tabs"), None) => self.list_custom_tabs(),
+            (Some("tabs"), _) => self.show_status("Usage: :tabs"),
+            (Some("tab"), first) => {
+                let rest: Vec<&str> = words.collect();
+                self.custom_tab_command(first, rest);
+            }
+            (Some("sessions"), None) => {
+                self.open_modal(ActiveModal::Sessions(SessionsModal::default()))
+            }
+            (Some("sessions"), _) => self.show_status("Usage: :sessions"),
+            (Some("session"), Some(name)) if words.clone().next().is_none() => {
+                self.switch_session(name);
+            }
+            (Some("session"), _) => self.show_status("Usage: :session <name>"),
+            (Some("link"), None) => self.copy_link(),
+            (Some("link"), _) => self.show_status("Usage: :link"),
+            (Some("open"), Some(uri)) if words.clone().next().is_none() => {
+                match deep_link::parse(uri) {
+                    Ok(link) => self.open_link(link),
+                    Err(err) => self.show_status(err),
+                }
+            }
+            (Some("open"), _) => self.show_status("Usage: :open evmtui://<chain>/address/<0x…>"),
+            (Some("scratchpad"), first) => {
+                let rest: Vec<&str> = words.collect();
+                if let Err(err) = self.scratchpad_command(first, rest) {
+                    self.show_status(format!("Scratchpad unavailable: {err}"));
+                }
+            }
+            (Some("tracer"), backend) => {
+                let chain: Vec<&str> = words.collect();
+                match backend.map(tenderly::TraceBackend::parse) {
+                    Some(None) => self.show_status("Usage: :tracer [rpc|tenderly] [chain]"),
+                    backend => self.set_trace_backend(backend.flatten(), &chain.join(" ")),
+                }
+            }
+            (Some("node"), backend) => {
+                let chain: Vec<&str> = words.collect();
+                match backend.map(graphql::NodeBackend::parse) {
+                    Some(None) => self.show_status("Usage: :node [rpc|graphql] [chain]"),
+                    backend => self.set_node_backend(backend.flatten(), &chain.join(" ")),
+                }
+            }
+            (Some("chains"), action) => {
+                let chain: Vec<&str> = words.collect();
+                match action {
+                    None => self.list_chains(),
+                    Some("hide") if !chain.is_empty() => {
+                        self.set_chain_hidden(&chain.join(" "), true)
+                    }
+                    Some("show") if !chain.is_empty() => {
+                        self.set_chain_hidden(&chain.join(" "), false)
+                    }
+                    _ => self.show_status("Usage: :chains [hide|show <chain>]"),
+                }
+            }
+            (Some("cache"), first) => {
+                let args: Vec<&str> = first.into_iter().chain(words).collect();
+                self.explorer_cache_command(&args);
+            }
+            (Some("limits"), first) => {
+                let args: Vec<&str> = first.into_iter().chain(words).collect();
+                self.set_fetch_limits(&args);
+            }
+            (Some("pending"), _) => self.show_status("Usage: :pending"),
+            (Some("webhook"), None) => self.test_webhook(),
+            (Some("webhook"), _) => self.show_status("Usage: :webhook"),
+            (Some("keystore"), first) => {
+                let rest: Vec<&str> = first.into_iter().chain(words).collect();
+                self.set_signer((!rest.is_empty()).then(|| {
+                    signing::SignerSource::Keystore(signing::keystore_path(&rest.join(" ")))
+                }));
+            }
+            (Some("ledger"), index) => match index.map(str::parse::<usize>).transpose() {
+                Ok(index) => self.set_signer(Some(signing::SignerSource::Ledger(
+                    index.unwrap_or_default(),
+                ))),
+                Err(_) => self.show_status("Usage: :ledger [account index]"),
+            },
+            (Some("foundry"), first) => {
+                let rest: Vec<&str> = first.into_iter().chain(words).collect();
+                self.set_foundry_project(
+                    (!rest.is_empty()).then(|| csv_export::expand_path(&rest.join(" "))),
+                );
+            }
+            (Some(command), _) => self.show_status(format!("Unknown command: {command}")),
+        }
+    }
+
+    /// Point artifact matching at a Foundry project, or turn it off with `None`.
+    fn set_foundry_project(&mut self, project: Option<PathBuf>) {
+        let saved = project.clone();
+        self.storage.write("Foundry project", move |storage| {
+            storage
+                .settings()
+                .set_json(SettingKey::FoundryProject, &saved)
+        });
+        let status = match project.as_ref() {
+            None => "Local artifact matching off".to_string(),
+            Some(path) if !path.join("foundry.toml").is_file() => format!(
+                "No foundry.toml in {}; artifacts will be read from its out/ anyway",
+                path.display()
+            ),
+            Some(path) => format!("Matching contracts against artifacts in {}", path.display()),
+        };
+        self.state.foundry_project = project;
+        self.show_status(status);
+    }
+
+    /// Hydration depth and timeouts for a chain: `name=value` arguments change
+    /// them, `reset` restores the defaults, any other words name the chain
+    /// (the selection's when empty). No change only reports the current ones.
+    fn set_fetch_limits(&mut self, args: &[&str]) {
+        let (changes, chain): (Vec<&str>, Vec<&str>) = args
+            .iter()
+            .partition(|arg| arg.contains('=') || **arg == "reset");
+        let chain = match (chain.is_empty(), self.state.selected.as_ref()) {
+            (false, _) => chain.join(" "),
+            (true, Some(SelectedEntity::Address(addr))) => addr.chain.clone(),
+            (true, Some(SelectedEntity::Transaction(tx))) => tx.chain.clone(),
+            (true, None) => search::SEARCH_CHAIN.to_string(),
+        };
+        let Some(info) = resolve_chain(&chain) else {
+            self.show_status(format!("Unknown chain {chain}"));
+            return;
+        };
+        let name = info.name.to_ascii_lowercase();
+        let mut limits = fetch_limits::for_chain(&self.state.fetch_limits, &name);
+        if changes.is_empty() {
+            self.show_status(format!("{} hydration: {}", info.name, limits.label()));
+            return;
+        }
+        for change in changes {
+            let applied = if change == "reset" {
+                limits = FetchLimits::default();
+                Ok(())
+            } else {
+                limits.apply(change)
+            };
+            if let Err(err) = applied {
+                self.show_status(format!(
+                    "{err}. Usage: :limits [txs=N] [timeout=SECS] [probe=SECS] [explorer=SECS] [reset] [chain]"
+                ));
+                return;
+            }
+        }
+        if limits == FetchLimits::default() {
+            self.state.fetch_limits.remove(&name);
+        } else {
+            self.state.fetch_limits.insert(name, limits);
+        }
+        let saved = self.state.fetch_limits.clone();
+        self.storage.write("fetch limits", move |storage| {
+            storage.settings().set_json(SettingKey::FetchLimits, &saved)
+        });
+        self.show_status(format!(
+            "{} hydration: {} (applies from the next load)",
+            info.name,
+            limits.label()
+        ));
+    }
+
+    /// Where `chain` (the selection's when empty) gets transaction traces
+    /// from; `None` only reports the current choice.
+    fn set_trace_backend(&mut self, backend: Option<tenderly::TraceBackend>, chain: &str) {
+        let chain = match (chain.is_empty(), self.state.selected.as_ref()) {
+            (false, _) => chain.to_string(),
+            (true, Some(SelectedEntity::Address(addr))) => addr.chain.clone(),
+            (true, Some(SelectedEntity::Transaction(tx))) => tx.chain.clone(),
+            (true, None) => search::SEARCH_CHAIN.to_string(),
+        };
+        let Some(info) = resolve_chain(&chain) else {
+            self.show_status(format!("Unknown chain {chain}"));
+            return;
+        };
+        let name = info.name.to_ascii_lowercase();
+        let Some(backend) = backend else {
+            let current = self.state.trace_backends.get(&name).copied();
+            self.show_status(format!(
+                "{} traces with {}",
+                info.name,
+                current.unwrap_or_default().label()
+            ));
+            return;
+        };
+        let mut backends = self.state.trace_backends.clone();
+        match backend {
+            tenderly::TraceBackend::Rpc => backends.remove(&name),
+            tenderly::TraceBackend::Tenderly => backends.insert(name, backend),
+        };
+        let saved = backends.clone();
+        self.storage.write("trace backend", move |storage| {
+            storage
+                .settings()
+                .set_json(SettingKey::TraceBackends, &saved)
+        });
+        self.state.trace_backends = backends;
+        // Traces already shown came from the previous backend.
+        self.state.transfers = Default::default();
+        self.state.balance_diff = Default::default();
+        if backend == tenderly::TraceBackend::Tenderly
+            && tenderly::Tenderly::for_chain(&self.state.secrets, &chain).is_none()
+        {
+            self.show_status(format!(
+                "{} traces with Tenderly once its account, project and access key are set in secrets",
+                info.name
+            ));
+        } else {
+            self.show_status(format!("{} traces with {}", info.name, backend.label()));
+        }
+    }
+
+    /// `:cache`: `name=duration` arguments set how long explorer answers stay
+    /// fresh, `reset` restores the defaults and `clear` drops every cached
+    /// answer. No arguments report the TTLs and the number cached.
+    fn explorer_cache_command(&mut self, args: &[&str]) {
+        const USAGE: &str = "Usage: :cache [history=DUR] [contract=DUR] [reset] [clear]";
+        if args == ["clear"] {
+            self.command_bus().read_storage(
+                &self.storage,
+                |storage| storage.responses().purge_older_than(u64::MAX),
+                Message::ExplorerCacheCleared,
+            );
+            return;
+        }
+        let mut ttls = self.providers.session.cache.ttls();
+        if args.is_empty() {
+            self.command_bus().read_storage(
+                &self.storage,
+                |storage| storage.responses().len(),
+                Message::ExplorerCacheCounted,
+            );
+            return;
+        }
+        for arg in args {
+            let applied = match *arg {
+                "reset" => {
+                    ttls = Default::default();
+                    Ok(())
+                }
+                arg => ttls.apply(arg),
+            };
+            if let Err(err) = applied {
+                self.show_status(format!("{err}. {USAGE}"));
+                return;
+            }
+        }
+        self.providers.session.cache.set_ttls(ttls);
+        self.storage.write("explorer cache TTLs", move |storage| {
+            storage
+                .settings()
+                .set_json(SettingKey::ExplorerCacheTtls, &ttls)
+        });
+        self.show_status(format!("Explorer cache: {}", ttls.label()));
+    }
+
+    /// Whether `chain` (the selection's when empty) reads account data over
+    /// JSON-RPC or GraphQL; `None` only reports the current choice.
+    fn set_node_backend(&mut self, backend: Option<graphql::NodeBackend>, chain: &str) {
+        let chain = match (chain.is_empty(), self.state.selected.as_ref()) {
+            (false, _) => chain.to_string(),
+            (true, Some(SelectedEntity::Address(addr))) => addr.chain.clone(),
+            (true, Some(SelectedEntity::Transaction(tx))) => tx.chain.clone(),
+            (true, None) => search::SEARCH_CHAIN.to_string(),
+        };
+        let Some(info) = resolve_chain(&chain) else {
+            self.show_status(format!("Unknown chain {chain}"));
+            return;
+        };
+        let name = info.name.to_ascii_lowercase();
+        let Some(backend) = backend else {
+            let current = graphql::for_chain(&self.state.node_backends, &name);
+            self.show_status(format!(
+                "{} account data via {}",
+                info.name,
+                current.label()
+            ));
+            return;
+        };
+        match backend {
+            graphql::NodeBackend::Rpc => self.state.node_backends.remove(&name),
+            graphql::NodeBackend::Graphql => self.state.node_backends.insert(name, backend),
+        };
+        let saved = self.state.node_backends.clone();
+        self.storage.write("node backend", move |storage| {
+            storage
+                .settings()
+                .set_json(SettingKey::NodeBackends, &saved)
+        });
+        self.show_status(format!(
+            "{} account data via {} (applies from the next load)",
+            info.name,
+            backend.label()
+        ));
+    }
+
+    /// Which built-in chains are shown and which are hidden.
+    fn list_chains(&mut self) {
+        let hidden = &self.state.hidden_chains;
+        let shown: Vec<&str> = chains::visible(hidden).map(|info| info.name).collect();
+        let mut status = format!("Chains ({}): {}", shown.len(), shown.join(", "));
+        if !hidden.is_empty() {
+            let hidden: Vec<&str> = CHAINS
+                .iter()
+                .filter(|info| hidden.contains(&info.name.to_ascii_lowercase()))
+                .map(|info| info.name)
+                .collect();
+            status.push_str(&format!(" • hidden: {}", hidden.join(", ")));
+        }
+        self.show_status(status);
+    }
+
+    /// Hide a built-in chain from the secrets form and the Chains tab, or
+    /// show it again. Entries on a hidden chain still open.
+    fn set_chain_hidden(&mut self, chain: &str, hide: bool) {
+        let Some(info) = resolve_chain(chain) else {
+            self.show_status(format!("Unknown chain {chain}"));
+            return;
+        };
+        let name = info.name.to_ascii_lowercase();
+        let changed = if hide {
+            self.state.hidden_chains.insert(name)
+        } else {
+            self.state.hidden_chains.remove(&name)
+        };
+        let verb = if hide { "hidden" } else { "shown" };
+        if !changed {
+            self.show_status(format!("{} is already {verb}", info.name));
+            return;
+        }
+        let saved = self.state.hidden_chains.clone();
+        self.storage.write("hidden chains", move |storage| {
+            storage
+                .settings()
+                .set_json(SettingKey::HiddenChains, &saved)
+        });
+        self.state.multichain = MultichainView::default();
+        self.show_status(format!("{} {verb}", info.name));
+    }
+
+    /// Signer the write flow uses, or `None` to forget it.
+    fn set_signer(&mut self, signer: Option<signing::SignerSource>) {
+        let saved = signer.clone();
+        self.storage.write("signer", move |storage| {
+            storage.settings().set_json(SettingKey::Signer, &saved)
+        });
+        let status = match signer.as_ref() {
+            None => "Signer cleared".to_string(),
+            Some(signing::SignerSource::Keystore(path)) if !path.is_file() => {
+                format!("Keystore set, but {} does not exist", path.display())
+            }
+            Some(source) => format!("Signing with {}", source.describe()),
+        };
+        self.state.signer = signer;
+        self.show_status(status);
+    }
+
+    /// Read the selectors seen without a local signature on the storage
+    /// thread; [`Message::SignatureMissesLoaded`] starts the sync.
+    fn sync_signatures(&mut self) {
+        self.command_bus().read_storage(
+            &self.storage,
+            |storage| storage.signatures().misses(),
+            Message::SignatureMissesLoaded,
+        );
+    }
+
+    /// Ask OpenChain for `misses`.
+    fn lookup_signature_misses(&mut self, misses: Vec<(SignatureKind, String)>) {
+        if misses.is_empty() {
+            self.show_status("No unknown selectors to sync");
+            return;
+        }
+        self.state.usage.record_feature("signature sync");
+        self.show_status(format!(
+            "Looking up {} unknown selector(s) on OpenChain…",
+            misses.len()
+        ));
+        let stats = self.providers.rpc.stats().clone();
+        self.command_bus().spawn_async(move || async move {
+            let requested = misses.len();
+            Message::SignaturesSynced {
+                requested,
+                result: signatures::sync(&stats, misses).await,
+            }
+        });
+    }
+
+    fn apply_synced_signatures(
+        &mut self,
+        requested: usize,
+        found: Vec<signatures::SyncedSignature>,
+    ) {
+        let selectors: HashSet<_> = found
+            .iter()
+            .map(|entry| (entry.kind, entry.selector.as_str()))
+            .collect();
+        self.show_status(format!(
+            "Synced {} signature(s) for {} of {requested} selector(s)",
+            found.len(),
+            selectors.len()
+        ));
+        self.storage.write("signatures", move |storage| {
+            for entry in &found {
+                storage
+                    .signatures()
+                    .add(entry.kind, &entry.selector, &entry.signature)?;
+            }
+            Ok(())
+        });
+        // Queued after the inserts, so the new signatures are found.
+        self.state.signatures.forget_misses();
+        self.resolve_signatures();
+    }
+
+    /// Look up selectors on screen (transaction rows, the open transaction,
+    /// unknown events) that the signature book has not seen yet, on the
+    /// storage thread; [`Message::SignaturesResolved`] merges the answers.
+    fn resolve_signatures(&mut self) {
+        let mut functions: Vec<String> = self
+            .state
+            .current_address
+            .iter()
+            .filter_map(|data| data.transactions_table.as_ref())
+            .flat_map(|table| table.rows.iter())
+            .filter_map(|row| row.calldata.as_deref())
+            .chain(
+                self.state
+                    .current_transaction
+                    .iter()
+                    .filter_map(|data| data.calldata.as_deref()),
+            )
+            .filter_map(SignatureBook::function_selector)
+            .collect();
+        functions.sort();
+        functions.dedup();
+        let events: Vec<String> = self
+            .state
+            .events
+            .page
+            .iter()
+            .flat_map(|page| page.events.iter())
+            .filter_map(|event| event.unknown_topic.clone())
+            .collect();
+        let book = &mut self.state.signatures;
+        let functions = book.unresolved(SignatureKind::Function, functions);
+        let events = book.unresolved(SignatureKind::Event, events);
+        if functions.is_empty() && events.is_empty() {
+            return;
+        }
+        self.command_bus().read_storage(
+            &self.storage,
+            move |storage| {
+                let repo = storage.signatures();
+                Ok((
+                    signatures::lookup(repo, SignatureKind::Function, functions)?,
+                    signatures::lookup(repo, SignatureKind::Event, events)?,
+                ))
+            },
+            Message::SignaturesResolved,
+        );
+    }
+
+    /// Fetch the selected address's full history and write it as CSV into
+    /// `dir`, or `exports/history_<address>` by default.
+    fn export_history(&mut self, dir: Option<PathBuf>) {
+        let Some(SelectedEntity::Address(addr)) = self.state.selected.clone() else {
+            self.show_status("Select an address to export its history");
+            return;
+        };
+        self.state.usage.record_feature("csv export");
+        let dir = dir.unwrap_or_else(|| csv_export::default_dir(&addr.address));
+        let api_key = self
+            .state
+            .secrets
+            .explorer_api_key(&addr.chain)
+            .map(str::to_string);
+        let rpc_url = self.state.secrets.rpc_url(&addr.chain).map(str::to_string);
+        self.show_status(format!(
+            "Exporting history of {}…",
+            short_hex(&addr.address)
+        ));
+        let providers = self.providers_for(&addr.chain);
+        let host = self.rpc_host(&addr.chain);
+        self.command_bus()
+            .spawn_scheduled(host, Priority::Foreground, move || async move {
+                let result = match csv_export::fetch_history(
+                    providers,
+                    addr.clone(),
+                    api_key,
+                    rpc_url,
+                )
+                .await
+                {
+                    Ok(history) => csv_export::write_history(&dir, &addr.address, &history)
+                        .map(|summary| summary.describe())
+                        .map_err(|err| format!("{err:#}")),
+                    Err(err) => Err(err),
+                };
+                Message::HistoryExported(result)
+            });
+    }
+
+    /// The hydrated view of the selection as JSON with its default export
+    /// path, narrowed to `tab` when given, or why there is none.
+    fn selection_json(&self, tab: Option<MainViewTab>) -> Result<(String, PathBuf), String> {
+        let export = match self.state.selected.as_ref() {
+            Some(SelectedEntity::Address(addr)) => {
+                let Some(data) = self
+                    .state
+                    .current_address
+                    .as_ref()
+                    .filter(|data| data.identifier == addr.address)
+                else {
+                    return Err("Address is still loading".into());
+                };
+                let label = self
+                    .state
+                    .labels
+                    .lookup(&addr.chain, &addr.address)
+                    .map(|label| label.name);
+                json_export::ViewExport::address(addr, label, data)
+            }
+            Some(SelectedEntity::Transaction(tx)) => {
+                let Some(data) = self
+                    .state
+                    .current_transaction
+                    .as_ref()
+                    .filter(|data| data.identifier == tx.hash)
+                else {
+                    return Err("Transaction is still loading".into());
+                };
+                let transfers = match &self.state.transfers {
+                    TransfersView {
+                        hash: Some(hash),
+                        result: Some(Ok(summary)),
+                        ..
+                    } if *hash == tx.hash => Some(summary),
+                    _ => None,
+                };
+                let balance_diff = match &self.state.balance_diff {
+                    BalanceDiffView {
+                        hash: Some(hash),
+                        result: Some(Ok(diff)),
+                        ..
+                    } if *hash == tx.hash => Some(diff),
+                    _ => None,
+                };
+                json_export::ViewExport::transaction(tx, data, transfers, balance_diff)
+            }
+            None => return Err("Nothing selected to export".into()),
+        };
+        let json = match tab {
+            Some(tab) => export.tab_json(tab),
+            None => export.to_json(),
+        };
+        json.map(|json| (json, export.default_path()))
+            .map_err(|err| format!("JSON export failed: {err}"))
+    }
+
+    /// Dump the hydrated view of the selection as JSON to `target`, to
+    /// stdout on exit for `-`, or to `exports/<kind>_<id>.json` by default.
+    fn export_json(&mut self, target: Option<String>) {
+        let (json, default_path) = match self.selection_json(None) {
+            Ok(json) => json,
+            Err(err) => {
+                self.show_status(err);
+                return;
+            }
+        };
+        self.state.usage.record_feature("json export");
+        match target.as_deref() {
+            Some("-") => {
+                self.stdout.push(json);
+                self.show_status("JSON will be printed to stdout on exit");
+            }
+            _ => {
+                let path = target
+                    .as_deref()
+                    .map(csv_export::expand_path)
+                    .unwrap_or(default_path);
+                match json_export::write_json(&path, &json) {
+                    Ok(()) => self.show_status(format!("Wrote JSON to {}", path.display())),
+                    Err(err) => self.show_status(format!("JSON export failed: {err}")),
+                }
+            }
+        }
+    }
+
+    /// `:screenshot [ansi|file]`: save the next frame, as seen once the
+    /// command line closes, as text or, for `ansi` and `.ans` files, with
+    /// its colors.
+    fn screenshot_command(&mut self, target: String) {
+        let (path, format) = match target.as_str() {
+            "" => (
+                screenshot::Format::Text.default_path(),
+                screenshot::Format::Text,
+            ),
+            "ansi" => (
+                screenshot::Format::Ansi.default_path(),
+                screenshot::Format::Ansi,
+            ),
+            target => {
+                let path = csv_export::expand_path(target);
+                let format = screenshot::Format::for_path(&path);
+                (path, format)
+            }
+        };
+        self.state.usage.record_feature("screenshot");
+        self.pending_screenshot = Some((path, format));
+        self.dirty = true;
+    }
+
+    /// `:pipe <command>`: the current tab's data as JSON on the command's
+    /// stdin, with its output shown in a modal.
+    fn pipe_view(&mut self, command: String) {
+        let mode = self.state.navigation.main_view_mode;
+        let tab = self.active_main_view_tab();
+        let json = match (tab, self.state.selected.as_ref()) {
+            (MainViewTab::Custom(index), Some(entity)) => {
+                let Some(custom) = self.state.custom_tabs.tabs.get(index) else {
+                    return;
+                };
+                let lines = match self
+                    .state
+                    .custom_tabs
+                    .content(custom, &custom_tabs::subject(entity))
+                {
+                    Some(TabContent::Ready(lines)) => lines.as_slice(),
+                    _ => &[],
+                };
+                json_export::custom_tab_json(entity, &custom.title, lines)
+                    .map_err(|err| format!("JSON export failed: {err}"))
+            }
+            _ => self
+                .selection_json(Some(tab.normalize(mode)))
+                .map(|(json, _)| json),
+        };
+        let json = match json {
+            Ok(json) => json,
+            Err(err) => {
+                self.show_status(err);
+                return;
+            }
+        };
+        self.state.usage.record_feature("pipe");
+        self.show_status(format!("Running {command}…"));
+        self.command_bus()
+            .spawn_async(move || async move { Message::Piped(pipe::run(command, json).await) });
+    }
+
+    fn tick(&mut self) -> AppResult<()> {
+        {
+            let commands = self.command_bus();
+            let (state, storage) = (&mut self.state, &mut self.storage);
+            let mut ctx = AppContext {
+                state,
+                storage,
+                commands,
+            };
+            if let Some(action) = self.top_bar.tick(&mut ctx)? {
+                self.dispatch(action);
+            }
+        }
+        {
+            let commands = self.command_bus();
+            let (state, storage) = (&mut self.state, &mut self.storage);
+            let mut ctx = AppContext {
+                state,
+                storage,
+                commands,
+            };
+            if let Some(action) = self.sidebar.tick(&mut ctx)? {
+                self.dispatch(action);
+            }
         }
         {
             let commands = self.command_bus();
@@ -1038,29 +5527,151 @@ impl App {
                 self.dispatch(action);
             }
         }
-        if self.secrets_modal.is_some() {
+        if self.modal.is_some() {
             let commands = self.command_bus();
-            let action = if let Some(modal) = self.secrets_modal.as_mut() {
-                let mut ctx = AppContext {
-                    state: &mut self.state,
-                    storage: &mut self.storage,
-                    commands,
-                };
-                modal.tick(&mut ctx)?
-            } else {
-                None
+            let mut ctx = AppContext {
+                state: &mut self.state,
+                storage: &mut self.storage,
+                commands,
+            };
+            let action = match self.modal.as_mut() {
+                Some(ActiveModal::Secrets(modal)) => modal.tick(&mut ctx)?,
+                Some(ActiveModal::Update(modal)) => modal.tick(&mut ctx)?,
+                Some(ActiveModal::Diagnostics(modal)) => modal.tick(&mut ctx)?,
+                Some(ActiveModal::Trash(modal)) => modal.tick(&mut ctx)?,
+                Some(ActiveModal::Portfolio(modal)) => modal.tick(&mut ctx)?,
+                Some(ActiveModal::Permissions(modal)) => modal.tick(&mut ctx)?,
+                Some(ActiveModal::UpgradeImpact(modal)) => modal.tick(&mut ctx)?,
+                Some(ActiveModal::Health(modal)) => modal.tick(&mut ctx)?,
+                Some(ActiveModal::Block(modal)) => modal.tick(&mut ctx)?,
+                Some(ActiveModal::DeployAddress(modal)) => modal.tick(&mut ctx)?,
+                Some(ActiveModal::Convert(modal)) => modal.tick(&mut ctx)?,
+                Some(ActiveModal::TypedData(modal)) => modal.tick(&mut ctx)?,
+                Some(ActiveModal::Write(modal)) => modal.tick(&mut ctx)?,
+                Some(ActiveModal::Pending(modal)) => modal.tick(&mut ctx)?,
+                Some(ActiveModal::SafeQueue(modal)) => modal.tick(&mut ctx)?,
+                Some(ActiveModal::GasAnalytics(modal)) => modal.tick(&mut ctx)?,
+                Some(ActiveModal::Errors(modal)) => modal.tick(&mut ctx)?,
+                Some(ActiveModal::Qr(modal)) => modal.tick(&mut ctx)?,
+                Some(ActiveModal::Scratchpad(modal)) => modal.tick(&mut ctx)?,
+                Some(ActiveModal::LogAlerts(modal)) => modal.tick(&mut ctx)?,
+                Some(ActiveModal::Thresholds(modal)) => modal.tick(&mut ctx)?,
+                Some(ActiveModal::Pipe(modal)) => modal.tick(&mut ctx)?,
+                Some(ActiveModal::Sessions(modal)) => modal.tick(&mut ctx)?,
+                None => None,
             };
             if let Some(action) = action {
                 self.dispatch(action);
             }
         }
-        self.drain_messages();
-        Ok(())
+        self.poll_watch_groups();
+        self.poll_watchlist();
+        self.poll_log_alerts();
+        self.poll_thresholds();
+        self.poll_safe_queues();
+        self.refresh_selection();
+        self.poll_follow();
+        self.load_events_if_needed();
+        self.load_upgrades_if_needed();
+        self.poll_pinned_slots();
+        self.prefetch_favorites();
+        self.load_fees_if_needed();
+        self.load_access_list_if_needed();
+        self.load_raw_if_needed();
+        self.load_custom_tab_if_needed();
+        self.load_transfers_if_needed();
+        self.load_balance_diff_if_needed();
+        self.load_multichain_if_needed();
+        self.drain_messages();
+        Ok(())
+    }
+    fn finish_hydration_timing(&mut self) {
+        if let Some(started) = self.hydration_started.take() {
+            self.state.usage.record_hydration(started.elapsed());
+            self.state
+                .usage
+                .absorb_pending_api_calls(self.providers.rpc.stats());
+            self.state.usage.queue_save(&self.storage);
+        }
+    }
+
+    /// Put a hydrated address on screen, fresh from the network or from the
+    /// favorites prefetch cache.
+    fn show_hydrated_address(
+        &mut self,
+        addr: &AddressRef,
+        mut data: HydratedAddress,
+        background: bool,
+    ) {
+        if background && let Some(previous) = self.state.current_address.as_ref() {
+            failures::keep_earlier(&mut data, previous);
+        }
+        if let Some(source) = data.contract_source.as_ref()
+            && source.match_type == SourceMatch::LocalArtifact
+            && let Ok(address) = addr.address.parse()
+        {
+            self.state
+                .labels
+                .set_local(&addr.chain, address, &source.name);
+        }
+        let cached_rows = data
+            .transactions_table
+            .as_ref()
+            .map(|table| table.rows.clone());
+        let status_message = data
+            .overview
+            .as_ref()
+            .and_then(|ov| {
+                format_units(ov.balance_wei, "ether")
+                    .ok()
+                    .map(|balance| format!("Balance: {balance} {}", chains::currency(&addr.chain)))
+            })
+            .or_else(|| {
+                data.failures
+                    .iter()
+                    .find(|failure| failure.section == AddressSection::Account)
+                    .map(|failure| {
+                        format!(
+                            "{} failed: {}",
+                            failure.section.label(),
+                            failure.detail.message
+                        )
+                    })
+            })
+            .or_else(|| {
+                data.info
+                    .iter()
+                    .find(|line| line.contains("Balance") || line.contains("Configure an Anvil"))
+                    .cloned()
+            })
+            .or_else(|| data.info.first().cloned())
+            .unwrap_or_else(|| "No account data available.".into());
+        let row_count = cached_rows.as_ref().map(|rows| rows.len()).unwrap_or(0);
+        self.state.watchlist.observe(&addr.chain, &data);
+        self.state.current_address = Some(data);
+        self.state.address_transactions_view.clamp(row_count);
+        if let Some(rows) = cached_rows {
+            for row in rows {
+                self.state
+                    .transaction_preview_cache
+                    .insert(row.hash.clone(), row);
+            }
+        }
+        if !background {
+            self.show_status(status_message);
+        }
+        self.last_hydrated = Some(Instant::now());
+        self.finish_hydration_timing();
+        self.dispatch(Action::LoadingFinished(FocusedPane::MainView));
     }
+
     fn drain_messages(&mut self) {
+        let mut drained = false;
         while let Ok(message) = self.message_rx.try_recv() {
+            drained = true;
             match message {
                 Message::SearchCompleted { query, entity } => {
+                    self.state.usage.record_feature("search");
                     let _ = self.top_bar_command(TopCommand::SearchCompleted {
                         query: query.clone(),
                         entity: entity.clone(),
@@ -1069,6 +5680,28 @@ impl App {
                     self.dispatch(Action::SelectionChanged(entity));
                     self.dispatch(Action::FocusPane(FocusedPane::MainView));
                 }
+                Message::BlockFound { chain, block } => {
+                    self.state.usage.record_feature("search");
+                    self.dispatch(Action::LoadingFinished(FocusedPane::Top));
+                    self.show_status(format!("Opened block {}", block.number));
+                    self.open_modal(ActiveModal::Block(BlockModal::loaded(chain, *block)));
+                }
+                Message::HashAmbiguous { tx, reason } => {
+                    self.dispatch(Action::LoadingFinished(FocusedPane::Top));
+                    self.show_status(reason.clone());
+                    self.open_modal(ActiveModal::Block(BlockModal::choose(tx, reason)));
+                }
+                Message::BlockLoaded(result) => {
+                    let commands = self.command_bus();
+                    if let Some(ActiveModal::Block(modal)) = self.modal.as_mut() {
+                        let mut ctx = AppContext {
+                            state: &mut self.state,
+                            storage: &mut self.storage,
+                            commands,
+                        };
+                        let _ = modal.update(&BlockCommand::Loaded(result), &mut ctx);
+                    }
+                }
                 Message::SearchFailed { query, error } => {
                     let _ = self.top_bar_command(TopCommand::SearchFailed {
                         query: query.clone(),
@@ -1076,65 +5709,564 @@ impl App {
                     });
                     self.dispatch(Action::LoadingFinished(FocusedPane::Top));
                     self.state.search_error = Some(error.clone());
-                    eprintln!("search error: {error}");
                 }
                 Message::AddressHydrated(data) => {
-                    if let Some(SelectedEntity::Address(addr)) = self.state.selected.as_ref() {
-                        if addr.address == data.identifier {
-                            let cached_rows = data
-                                .transactions_table
-                                .as_ref()
-                                .map(|table| table.rows.clone());
-                            let status_message = data
-                                .overview
-                                .as_ref()
-                                .and_then(|ov| {
-                                    format_units(ov.balance_wei, "ether")
-                                        .ok()
-                                        .map(|balance| format!("Balance: {balance} ETH"))
-                                })
-                                .or_else(|| {
-                                    data.info
-                                        .iter()
-                                        .find(|line| {
-                                            line.contains("Balance")
-                                                || line.contains("Failed")
-                                                || line.contains("Account query")
-                                                || line.contains("Configure an Anvil")
-                                        })
-                                        .cloned()
-                                })
-                                .or_else(|| data.info.first().cloned())
-                                .unwrap_or_else(|| "No account data available.".into());
-                            let row_count =
-                                cached_rows.as_ref().map(|rows| rows.len()).unwrap_or(0);
-                            self.state.current_address = Some(data);
-                            self.state.address_transactions_view.clamp(row_count);
-                            if let Some(rows) = cached_rows {
-                                for row in rows {
-                                    self.state
-                                        .transaction_preview_cache
-                                        .insert(row.hash.clone(), row);
-                                }
+                    let background = std::mem::take(&mut self.refresh_in_flight);
+                    let selected = self.state.selected.clone();
+                    if let Some(entity @ SelectedEntity::Address(addr)) = selected.as_ref()
+                        && addr.address == data.identifier
+                    {
+                        if self.state.is_favorite(entity) {
+                            self.state
+                                .prefetch
+                                .insert(entity, Prefetched::Address(data.clone()));
+                        }
+                        self.show_hydrated_address(addr, *data, background);
+                        // Rate limits are not retried, so a load the user asked
+                        // for explains them in the errors dialog.
+                        if !background
+                            && self.state.current_address.as_ref().is_some_and(|data| {
+                                data.failures
+                                    .iter()
+                                    .any(|failure| failure.detail.is_rate_limited())
+                            })
+                        {
+                            self.open_errors_modal();
+                        }
+                    }
+                }
+                Message::SecretsValidated(results) => {
+                    let commands = self.command_bus();
+                    if let Some(ActiveModal::Secrets(modal)) = self.modal.as_mut() {
+                        let mut ctx = AppContext {
+                            state: &mut self.state,
+                            storage: &mut self.storage,
+                            commands,
+                        };
+                        match modal
+                            .update(&SecretsFormCommand::ValidationFinished(results), &mut ctx)
+                        {
+                            Ok(Some(action)) => self.dispatch(action),
+                            Ok(None) => {}
+                            Err(err) => {
+                                self.show_status(format!("Failed to save secrets: {err:#}"))
+                            }
+                        }
+                    }
+                }
+                Message::PermissionsLoaded(result) => {
+                    let commands = self.command_bus();
+                    if let Some(ActiveModal::Permissions(modal)) = self.modal.as_mut() {
+                        let mut ctx = AppContext {
+                            state: &mut self.state,
+                            storage: &mut self.storage,
+                            commands,
+                        };
+                        let _ = modal.update(&PermissionsCommand::Loaded(result), &mut ctx);
+                    }
+                }
+                Message::DeployedCodeChecked(result) => {
+                    let commands = self.command_bus();
+                    if let Some(ActiveModal::DeployAddress(modal)) = self.modal.as_mut() {
+                        let mut ctx = AppContext {
+                            state: &mut self.state,
+                            storage: &mut self.storage,
+                            commands,
+                        };
+                        let _ = modal.update(&DeployAddressCommand::Checked(result), &mut ctx);
+                    }
+                }
+                Message::WritePrepared(result) => {
+                    let commands = self.command_bus();
+                    if let Some(ActiveModal::Write(modal)) = self.modal.as_mut() {
+                        let mut ctx = AppContext {
+                            state: &mut self.state,
+                            storage: &mut self.storage,
+                            commands,
+                        };
+                        let _ = modal.update(&WriteCommand::Prepared(result), &mut ctx);
+                    }
+                }
+                Message::WriteSent(result) => {
+                    let result = result.map(|sent| {
+                        self.show_status(format!("Sent transaction {}", short_hex(&sent.hash)));
+                        let hash = sent.hash.clone();
+                        self.state.pending_transactions.push(*sent);
+                        self.save_pending_transactions();
+                        hash
+                    });
+                    let commands = self.command_bus();
+                    if let Some(ActiveModal::Write(modal)) = self.modal.as_mut() {
+                        let mut ctx = AppContext {
+                            state: &mut self.state,
+                            storage: &mut self.storage,
+                            commands,
+                        };
+                        let _ = modal.update(&WriteCommand::Sent(result), &mut ctx);
+                    }
+                }
+                Message::PendingChecked(result) => {
+                    if let Ok(check) = &result {
+                        let mined = pending::prune(&mut self.state.pending_transactions, check);
+                        if !mined.is_empty() {
+                            self.save_pending_transactions();
+                            self.show_status(format!(
+                                "{} pending transaction(s) mined or replaced",
+                                mined.len()
+                            ));
+                        }
+                    }
+                    let commands = self.command_bus();
+                    if let Some(ActiveModal::Pending(modal)) = self.modal.as_mut() {
+                        let mut ctx = AppContext {
+                            state: &mut self.state,
+                            storage: &mut self.storage,
+                            commands,
+                        };
+                        let _ = modal.update(&PendingCommand::Checked(result), &mut ctx);
+                    }
+                }
+                Message::PendingPrepared(result) => {
+                    let commands = self.command_bus();
+                    if let Some(ActiveModal::Pending(modal)) = self.modal.as_mut() {
+                        let mut ctx = AppContext {
+                            state: &mut self.state,
+                            storage: &mut self.storage,
+                            commands,
+                        };
+                        let _ = modal.update(&PendingCommand::Prepared(result), &mut ctx);
+                    }
+                }
+                Message::PendingReplaced { hash, kind, result } => {
+                    let result = result.map(|mut sent| {
+                        let queue = &mut self.state.pending_transactions;
+                        if let Some(index) = queue.iter().position(|tx| tx.hash == hash) {
+                            let original = queue.remove(index);
+                            sent.replaced = original.replaced;
+                            sent.replaced.push(original.hash);
+                        }
+                        sent.replacement = Some(kind);
+                        let sent_hash = sent.hash.clone();
+                        queue.push(*sent);
+                        queue.sort_by(|a, b| {
+                            (&a.chain, a.from, a.nonce).cmp(&(&b.chain, b.from, b.nonce))
+                        });
+                        self.save_pending_transactions();
+                        self.show_status(format!(
+                            "Sent {} {}",
+                            kind.label(),
+                            short_hex(&sent_hash)
+                        ));
+                        sent_hash
+                    });
+                    let commands = self.command_bus();
+                    if let Some(ActiveModal::Pending(modal)) = self.modal.as_mut() {
+                        let mut ctx = AppContext {
+                            state: &mut self.state,
+                            storage: &mut self.storage,
+                            commands,
+                        };
+                        let _ = modal.update(&PendingCommand::Replaced(result), &mut ctx);
+                    }
+                }
+                Message::SafeQueueLoaded(result) => {
+                    let commands = self.command_bus();
+                    if let Some(ActiveModal::SafeQueue(modal)) = self.modal.as_mut() {
+                        let mut ctx = AppContext {
+                            state: &mut self.state,
+                            storage: &mut self.storage,
+                            commands,
+                        };
+                        let _ = modal.update(&SafeQueueCommand::Loaded(result), &mut ctx);
+                    }
+                }
+                Message::GasAnalyticsLoaded(result) => {
+                    let commands = self.command_bus();
+                    if let Some(ActiveModal::GasAnalytics(modal)) = self.modal.as_mut() {
+                        let mut ctx = AppContext {
+                            state: &mut self.state,
+                            storage: &mut self.storage,
+                            commands,
+                        };
+                        let _ = modal.update(&GasAnalyticsCommand::Loaded(result), &mut ctx);
+                    }
+                }
+                Message::UpgradeSimulated(result) => {
+                    let commands = self.command_bus();
+                    if let Some(ActiveModal::UpgradeImpact(modal)) = self.modal.as_mut() {
+                        let mut ctx = AppContext {
+                            state: &mut self.state,
+                            storage: &mut self.storage,
+                            commands,
+                        };
+                        let _ = modal.update(&UpgradeImpactCommand::Loaded(result), &mut ctx);
+                    }
+                }
+                Message::FollowPolled(result) => {
+                    self.follow_poll_in_flight = false;
+                    match result {
+                        Ok(update) => self.apply_follow_update(update),
+                        Err(err) if self.state.follow.address().is_some() => {
+                            self.show_status(format!("Follow poll failed: {err}"));
+                        }
+                        Err(_) => {}
+                    }
+                }
+                Message::Subscription(event) => self.handle_subscription_event(event),
+                Message::UpgradesLoaded { address, result } => {
+                    let view = &mut self.state.upgrades;
+                    if view.address.as_deref() == Some(address.as_str()) {
+                        view.loading = false;
+                        match result {
+                            Ok(history) => view.history = Some(history),
+                            Err(err) => view.error = Some(err),
+                        }
+                    }
+                }
+                Message::EventsLoaded { address, result } => {
+                    let view = &mut self.state.events;
+                    if view.address.as_deref() == Some(address.as_str()) {
+                        view.loading = false;
+                        view.selected_index = 0;
+                        match result {
+                            Ok(page) => {
+                                view.error = None;
+                                view.page = Some(page);
+                            }
+                            Err(err) => {
+                                view.error = Some(err);
+                                view.page = None;
                             }
-                            self.show_status(status_message);
-                            self.dispatch(Action::LoadingFinished(FocusedPane::MainView));
                         }
                     }
                 }
+                Message::AccessListLoaded { hash, result } => {
+                    let view = &mut self.state.access_list;
+                    if view.hash.as_deref() == Some(hash.as_str()) {
+                        view.loading = false;
+                        view.declared = Some(result);
+                    }
+                }
+                Message::AccessListGenerated { hash, result } => {
+                    let view = &mut self.state.access_list;
+                    if view.hash.as_deref() == Some(hash.as_str()) {
+                        view.generating = false;
+                        view.generated = Some(result);
+                    }
+                }
+                Message::BalanceDiffLoaded { hash, result } => {
+                    let view = &mut self.state.balance_diff;
+                    if view.hash.as_deref() == Some(hash.as_str()) {
+                        view.loading = false;
+                        view.result = Some(result);
+                    }
+                }
+                Message::TransfersLoaded { hash, result } => {
+                    let view = &mut self.state.transfers;
+                    if view.hash.as_deref() == Some(hash.as_str()) {
+                        view.loading = false;
+                        view.result = Some(result);
+                    }
+                }
+                Message::RawLoaded { hash, result } => {
+                    let view = &mut self.state.raw;
+                    if view.hash.as_deref() == Some(hash.as_str()) {
+                        view.loading = false;
+                        view.result = Some(result);
+                    }
+                }
+                Message::CustomTabLoaded {
+                    title,
+                    subject,
+                    result,
+                } => {
+                    let tabs = &mut self.state.custom_tabs;
+                    // A tab removed while its script ran keeps no output.
+                    if tabs
+                        .tabs
+                        .iter()
+                        .any(|tab| tab.title.eq_ignore_ascii_case(&title))
+                    {
+                        let content = match result {
+                            Ok(lines) => TabContent::Ready(lines),
+                            Err(err) => TabContent::Failed(err),
+                        };
+                        tabs.set_content(&title, &subject, content);
+                    }
+                }
+                Message::FeesLoaded { hash, result } => {
+                    let view = &mut self.state.fees;
+                    if view.hash.as_deref() == Some(hash.as_str()) {
+                        view.loading = false;
+                        view.result = Some(result);
+                    }
+                }
+                Message::ChainSummaryLoaded {
+                    address,
+                    chain,
+                    result,
+                } => self.state.multichain.apply(&address, &chain, result),
+                Message::Piped(result) => match result {
+                    Ok(output) if self.modal.is_none() => {
+                        self.show_status(format!("Ran {}", output.command));
+                        self.open_modal(ActiveModal::Pipe(PipeModal::new(output)));
+                    }
+                    Ok(output) => {
+                        let first = output.stdout.first().map(String::as_str).unwrap_or("");
+                        self.show_status(format!("{}: {first}", output.command));
+                    }
+                    Err(err) => self.show_status(format!("Pipe failed: {err}")),
+                },
+                Message::HistoryExported(result) => match result {
+                    Ok(summary) => self.show_status(summary),
+                    Err(err) => self.show_status(format!("CSV export failed: {err}")),
+                },
+                Message::SignaturesSynced { requested, result } => match result {
+                    Ok(found) => self.apply_synced_signatures(requested, found),
+                    Err(err) => self.show_status(format!("Signature sync failed: {err}")),
+                },
+                Message::ExplorerCacheCleared(result) => match result {
+                    Ok(cleared) => {
+                        self.show_status(format!("Cleared {cleared} cached explorer answer(s)"))
+                    }
+                    Err(err) => {
+                        self.show_status(format!("Failed to clear the explorer cache: {err}"))
+                    }
+                },
+                Message::ExplorerCacheCounted(result) => {
+                    let ttls = self.providers.session.cache.ttls();
+                    match result {
+                        Ok(cached) => self.show_status(format!(
+                            "Explorer cache: {} • {cached} answer(s) cached",
+                            ttls.label()
+                        )),
+                        Err(err) => self.show_status(format!(
+                            "Explorer cache: {} • failed to count answers: {err}",
+                            ttls.label()
+                        )),
+                    }
+                }
+                Message::SignaturesResolved(result) => match result {
+                    Ok((functions, events)) => {
+                        let book = &mut self.state.signatures;
+                        book.insert(SignatureKind::Function, functions);
+                        book.insert(SignatureKind::Event, events);
+                    }
+                    Err(err) => {
+                        self.state.signatures.clear_pending();
+                        self.show_status(format!("Failed to look up signatures: {err}"));
+                    }
+                },
+                Message::LogAlertsPolled(scans) => {
+                    self.log_alert_poll_in_flight = false;
+                    let raised = self.state.log_alerts.apply_poll(scans);
+                    if let Some((_, event)) = raised.last() {
+                        self.show_status(format!(
+                            "🔔 {}: {} • :alerts, Enter opens it",
+                            event.title, event.message
+                        ));
+                    }
+                    for (outputs, event) in raised {
+                        self.raise(outputs, event);
+                    }
+                }
+                Message::ThresholdsPolled(readings) => {
+                    self.threshold_poll_in_flight = false;
+                    let raised = self.state.thresholds.apply_poll(readings);
+                    if let Some((_, event)) = raised.last() {
+                        self.show_status(format!(
+                            "🔔 {}: {} • :thresholds lists them",
+                            event.title, event.message
+                        ));
+                    }
+                    for (outputs, event) in raised {
+                        self.raise(outputs, event);
+                    }
+                }
+                Message::Plugin { name, output } => self.apply_plugin_output(name, output),
+                Message::SlotsPolled(results) => {
+                    self.slot_poll_in_flight = false;
+                    let changed = self.state.slots.apply_poll(results);
+                    if let Some(pin) = changed.last() {
+                        self.show_status(format!("{} changed on {}", pin.label, pin.address));
+                    }
+                    for pin in changed.iter().filter(|pin| pin.alerts.any()) {
+                        let value = self
+                            .state
+                            .slots
+                            .history(pin)
+                            .and_then(|history| history.back())
+                            .map(|sample| pin.describe(&sample.value))
+                            .unwrap_or_default();
+                        let event = WatchEvent {
+                            source: "storage_slot",
+                            title: format!("Slot changed: {}", pin.label),
+                            message: format!(
+                                "{} ({}) → {value}",
+                                short_hex(&pin.address),
+                                pin.chain
+                            ),
+                            transaction: None,
+                            subject: Some(GroupMember {
+                                address: pin.address.clone(),
+                                chain: pin.chain.clone(),
+                            }),
+                        };
+                        self.raise(pin.alerts, event);
+                    }
+                }
+                Message::WatchlistPolled(results) => {
+                    self.watchlist_poll_in_flight = false;
+                    let notices = self
+                        .state
+                        .watchlist
+                        .apply_poll(results, watchlist::unix_now());
+                    self.raise_all(notices);
+                }
+                Message::WebhookPosted(result) => match result {
+                    Ok(Some(message)) => self.show_status(message),
+                    Ok(None) => {}
+                    Err(err) => self.show_status(err),
+                },
+                Message::SafeQueuesPolled(results) => {
+                    self.safe_poll_in_flight = false;
+                    let notices = self.state.watchlist.apply_safe_queues(results);
+                    self.raise_all(notices);
+                }
+                Message::WatchPolled(results) => {
+                    self.watch_poll_in_flight = false;
+                    let snapshots = results
+                        .into_iter()
+                        .filter_map(|(key, outcome)| outcome.ok().map(|snapshot| (key, snapshot)))
+                        .collect();
+                    let alerts = self.state.watch.apply_snapshots(snapshots);
+                    for alert in &alerts {
+                        let outputs = self
+                            .state
+                            .watch
+                            .groups
+                            .iter()
+                            .find(|group| group.name == alert.group)
+                            .map(|group| group.alerts)
+                            .unwrap_or_default();
+                        let event = WatchEvent {
+                            source: "watch_group",
+                            title: format!("Watch group {}", alert.group),
+                            message: alert.message.clone(),
+                            subject: None,
+                            transaction: None,
+                        };
+                        self.raise(outputs, event);
+                    }
+                    if let Some(alert) = alerts.last() {
+                        let more = match alerts.len() {
+                            1 => String::new(),
+                            n => format!(" (+{} more, G for details)", n - 1),
+                        };
+                        self.show_status(format!("⚠ {}: {}{more}", alert.group, alert.message));
+                    }
+                }
+                Message::UpdateChecked(result) => {
+                    // Update checks are opt-in background work; failures stay silent.
+                    if let Ok(release) = result {
+                        let cached = release.clone();
+                        self.storage.write("update check", move |storage| {
+                            updates::store_release(storage.settings(), &cached)
+                        });
+                        self.apply_release_check(release);
+                    }
+                }
+                Message::StorageFailed { what, error } => {
+                    self.show_status(format!("Failed to save {what}: {error}"));
+                }
+                Message::SearchCandidatesLoaded(result) => {
+                    let _ = self.top_bar_command(TopCommand::CandidatesLoaded(result));
+                }
+                Message::TrashLoaded(result) => match result {
+                    Ok(entries) => self.open_modal(ActiveModal::Trash(TrashModal::new(entries))),
+                    Err(err) => self.show_status(format!("Failed to load the trash: {err}")),
+                },
+                Message::ScratchpadLoaded {
+                    investigation,
+                    result,
+                } => match result {
+                    // Dropped when the investigation changed while loading.
+                    Ok((notes, others)) if investigation == self.state.investigation => {
+                        self.open_modal(ActiveModal::Scratchpad(ScratchpadModal::new(
+                            investigation,
+                            notes,
+                            others,
+                        )));
+                    }
+                    Ok(_) => {}
+                    Err(err) => self.show_status(format!("Failed to load the scratchpad: {err}")),
+                },
+                Message::ScratchpadExported { path, result } => match result {
+                    Ok(count) => {
+                        self.show_status(format!("Wrote {count} notes to {}", path.display()))
+                    }
+                    Err(err) => self.show_status(format!("Scratchpad export failed: {err}")),
+                },
+                Message::SignatureMissesLoaded(result) => match result {
+                    Ok(misses) => self.lookup_signature_misses(misses),
+                    Err(err) => self.show_status(format!("Signature sync failed: {err}")),
+                },
+                Message::FavoritePrefetched(entity, data) => {
+                    self.state.prefetch.in_flight = None;
+                    self.last_prefetch = Some(Instant::now());
+                    if self.state.is_favorite(&entity) {
+                        self.state.prefetch.insert(&entity, data);
+                    }
+                }
                 Message::TransactionHydrated(data) => {
-                    if let Some(SelectedEntity::Transaction(tx)) = self.state.selected.as_ref() {
-                        if tx.hash == data.identifier {
-                            self.state.current_transaction = Some(data);
-                            self.dispatch(Action::LoadingFinished(FocusedPane::MainView));
+                    self.refresh_in_flight = false;
+                    if let Some(entity @ SelectedEntity::Transaction(tx)) =
+                        self.state.selected.as_ref()
+                        && tx.hash == data.identifier
+                    {
+                        if self.state.is_favorite(entity) {
+                            self.state
+                                .prefetch
+                                .insert(entity, Prefetched::Transaction(data.clone()));
                         }
+                        self.state.current_transaction = Some(*data);
+                        self.announce_transaction();
+                        self.last_hydrated = Some(Instant::now());
+                        self.finish_hydration_timing();
+                        self.dispatch(Action::LoadingFinished(FocusedPane::MainView));
                     }
                 }
             }
         }
+        if drained {
+            self.dirty = true;
+            self.resolve_signatures();
+        }
     }
 }
 
+/// Error source naming the configured RPC endpoints by host only.
+/// Watchlist entries as the sidebar lists them.
+fn watchlist_refs(watchlist: &WatchlistState) -> Vec<AddressRef> {
+    watchlist
+        .entries
+        .iter()
+        .map(|entry| AddressRef {
+            label: short_hex(&entry.member.address),
+            address: entry.member.address.clone(),
+            chain: entry.member.chain.clone(),
+        })
+        .collect()
+}
+
+fn rpc_source(rpc_urls: &str) -> String {
+    let hosts: Vec<String> = rpc_health::endpoints(rpc_urls)
+        .iter()
+        .map(|url| host_key(Some(url), url))
+        .collect();
+    format!("RPC {}", hosts.join(", "))
+}
+
 pub(crate) fn build_address_view(
     addr: AddressRef,
     overview: Option<AccountOverview>,
@@ -1154,17 +6286,46 @@ pub(crate) fn build_address_view(
         let balance_eth = format_units(summary.balance_wei, "ether")
             .unwrap_or_else(|_| summary.balance_wei.to_string());
         info.push(format!(
-            "Balance: {} ETH ({} wei)",
-            balance_eth, summary.balance_wei
+            "Balance: {} {} ({} wei)",
+            balance_eth,
+            chains::currency(&addr.chain),
+            summary.balance_wei
         ));
         info.push(format!(
-            "Transaction count (nonce): {}",
-            summary.transaction_count
+            "Transaction count (nonce): {} • pending {}",
+            summary.transaction_count, summary.pending_transaction_count
         ));
+        let in_mempool = summary
+            .pending_transaction_count
+            .saturating_sub(summary.transaction_count);
+        if in_mempool > 0 {
+            info.push(format!(
+                "{in_mempool} transaction(s) waiting in the mempool"
+            ));
+        }
+        if let Some(queued) = summary.queued_nonces.as_ref() {
+            let missing = nonces::missing_nonces(summary.pending_transaction_count, queued);
+            if !missing.is_empty() {
+                let list = |nonces: &[u64]| {
+                    nonces
+                        .iter()
+                        .map(u64::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+                info.push(format!(
+                    "Nonce gap: queued nonce(s) {} wait on missing {}",
+                    list(queued),
+                    list(&missing)
+                ));
+            }
+        }
         info.push(format!(
             "Account type: {}",
             if summary.is_contract {
                 "Contract"
+            } else if summary.delegate.is_some() {
+                "EOA with 7702 delegation"
             } else {
                 "Externally Owned Account"
             }
@@ -1200,20 +6361,28 @@ pub(crate) fn build_address_view(
         balances,
         permissions,
         overview,
+        contract_source: None,
+        creation: None,
+        failures: Vec::new(),
     }
 }
 
-fn format_eth_value(value: &U256) -> String {
+pub fn format_eth_value(value: &U256) -> String {
+    format_native_value(value, "ETH")
+}
+
+/// A wei amount in whole units of a chain's native currency, e.g. `1.5 POL`.
+pub fn format_native_value(value: &U256, symbol: &str) -> String {
     if value.is_zero() {
-        return "0 ETH".into();
+        return format!("0 {symbol}");
     }
     match format_units(*value, "ether") {
         Ok(mut eth) => {
             trim_decimal(&mut eth);
             if eth.is_empty() {
-                "0 ETH".into()
+                format!("0 {symbol}")
             } else {
-                format!("{eth} ETH")
+                format!("{eth} {symbol}")
             }
         }
         Err(_) => format!("{value} wei"),
@@ -1221,7 +6390,7 @@ fn format_eth_value(value: &U256) -> String {
 }
 
 fn trim_decimal(value: &mut String) {
-    if let Some(_) = value.find('.') {
+    if value.find('.').is_some() {
         while value.ends_with('0') {
             value.pop();
         }
@@ -1251,13 +6420,81 @@ pub struct AppState {
     pub selected: Option<SelectedEntity>,
     pub search_error: Option<String>,
     pub secrets: SecretsState,
+    pub usage: UsageStats,
+    pub watch: WatchState,
+    /// Addresses on the sidebar's Watchlist tab with their polled balances.
+    pub watchlist: WatchlistState,
+    /// Contract events that raise alerts, with their matches this session.
+    pub log_alerts: LogAlertState,
+    /// Recorded key macros and the recording in progress.
+    pub macros: Macros,
+    /// External plugins with their processes and the panels they showed.
+    pub plugins: PluginState,
+    /// Main view tabs filled by scripts or plugins, with their output.
+    pub custom_tabs: CustomTabState,
+    /// Base fee and token balance thresholds with their last readings.
+    pub thresholds: ThresholdState,
     pub favorite_addresses: HashSet<String>,
     pub favorite_transactions: HashSet<String>,
     pub current_address: Option<HydratedAddress>,
     pub current_transaction: Option<HydratedTransaction>,
     pub address_transactions_view: AddressTransactionsViewState,
     pub pending_transaction_preview: Option<AddressTransactionRow>,
-    pub transaction_preview_cache: HashMap<String, AddressTransactionRow>,
+    pub transaction_preview_cache: TransactionPreviewCache,
+    /// Favorites hydrated in the background, shown at once when selected.
+    pub prefetch: PrefetchCache,
+    pub available_update: Option<ReleaseInfo>,
+    /// Interval between background re-hydrations of the selection, if enabled.
+    pub auto_refresh: Option<Duration>,
+    /// Sidebar width and visibility, persisted across sessions.
+    pub layout: PaneLayout,
+    pub follow: FollowState,
+    pub events: EventsView,
+    pub upgrades: UpgradesView,
+    pub slots: SlotWatch,
+    pub fees: FeesView,
+    pub access_list: AccessListView,
+    pub raw: RawView,
+    /// Wrap, truncate or scroll, per main view tab.
+    pub line_modes: LineModes,
+    /// Scratchpad that `m` pins to, persisted across sessions.
+    pub investigation: String,
+    /// Sessions stashed while another investigation is open.
+    pub sessions: SessionState,
+    pub transfers: TransfersView,
+    pub labels: LabelRegistry,
+    /// Function and event signatures looked up for what is on screen.
+    pub signatures: SignatureBook,
+    /// Foundry project whose artifacts identify unverified contracts.
+    pub foundry_project: Option<PathBuf>,
+    /// Keystore or Ledger account the write flow signs with.
+    pub signer: Option<signing::SignerSource>,
+    /// Transactions sent by the write flow that are not seen mined yet.
+    pub pending_transactions: Vec<pending::PendingTransaction>,
+    /// Trace backend chosen per chain (lowercase name); RPC when absent.
+    pub trace_backends: BTreeMap<String, tenderly::TraceBackend>,
+    /// Hydration depth and timeouts saved per chain (lowercase name).
+    pub fetch_limits: BTreeMap<String, FetchLimits>,
+    /// Node API for account data chosen per chain (lowercase name); JSON-RPC
+    /// when absent.
+    pub node_backends: BTreeMap<String, graphql::NodeBackend>,
+    /// Built-in chains hidden with `:chains hide` (lowercase names).
+    pub hidden_chains: BTreeSet<String>,
+    pub balance_diff: BalanceDiffView,
+    pub multichain: MultichainView,
+    /// Text typed after `:` while the command line is open.
+    pub command_line: Option<String>,
+    /// Highlighted entry of [`HydratedAddress::info_links`] on the Info tab.
+    pub info_link_index: usize,
+}
+
+/// Related entity listed on the Info tab that `Enter` can open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InfoLink {
+    /// Implementation an EIP-7702 delegated EOA runs.
+    Delegate,
+    Deployer,
+    CreationTransaction,
 }
 
 #[derive(Debug, Default)]
@@ -1317,7 +6554,6 @@ impl NavigationState {
 
     pub fn restore_focus_after_modal(&mut self) {
         self.focused_pane = self.modal_return_focus;
-        self.modal_return_focus = self.focused_pane;
     }
 
     pub fn focus_next(&mut self) {
@@ -1341,12 +6577,34 @@ impl NavigationState {
         self.focus_pane(previous);
     }
 
-    pub fn next_main_view_tab(&mut self) {
-        self.main_view_tab = self.main_view_tab.next(self.main_view_mode);
+    /// Move one tab right; the mode's `custom` tabs (indexes in order) come
+    /// after the built-in ones.
+    pub fn next_main_view_tab(&mut self, custom: &[usize]) {
+        let mode = self.main_view_mode;
+        let first = MainViewTab::default().normalize(mode);
+        let last = first.previous(mode);
+        self.main_view_tab = match self.main_view_tab.normalize(mode) {
+            MainViewTab::Custom(index) => match custom.iter().position(|tab| *tab == index) {
+                Some(at) if at + 1 < custom.len() => MainViewTab::Custom(custom[at + 1]),
+                _ => first,
+            },
+            tab if tab == last && !custom.is_empty() => MainViewTab::Custom(custom[0]),
+            tab => tab.next(mode),
+        };
     }
 
-    pub fn previous_main_view_tab(&mut self) {
-        self.main_view_tab = self.main_view_tab.previous(self.main_view_mode);
+    pub fn previous_main_view_tab(&mut self, custom: &[usize]) {
+        let mode = self.main_view_mode;
+        let first = MainViewTab::default().normalize(mode);
+        let last = first.previous(mode);
+        self.main_view_tab = match self.main_view_tab.normalize(mode) {
+            MainViewTab::Custom(index) => match custom.iter().position(|tab| *tab == index) {
+                Some(at) if at > 0 => MainViewTab::Custom(custom[at - 1]),
+                _ => last,
+            },
+            tab if tab == first => custom.last().map_or(last, |tab| MainViewTab::Custom(*tab)),
+            tab => tab.previous(mode),
+        };
     }
 }
 
@@ -1368,6 +6626,10 @@ impl LoadingState {
         target.is_loading = value;
         target.started_at = if value { Some(Instant::now()) } else { None };
     }
+
+    pub fn any(&self) -> bool {
+        self.top.is_loading || self.sidebar.is_loading || self.main_view.is_loading
+    }
 }
 
 #[derive(Debug, Default)]
@@ -1376,6 +6638,15 @@ pub struct PaneLoading {
     pub started_at: Option<Instant>,
 }
 
+impl PaneLoading {
+    /// Time spent on the current fetch, `None` when the pane is idle.
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.started_at
+            .filter(|_| self.is_loading)
+            .map(|started| started.elapsed())
+    }
+}
+
 /// Mutable context passed to components while handling logic.
 pub struct AppContext<'a> {
     pub state: &'a mut AppState,
@@ -1386,19 +6657,59 @@ pub struct AppContext<'a> {
 /// Read-only context used during rendering.
 pub struct AppView<'a> {
     pub state: &'a AppState,
+    /// For the session state the providers keep, like endpoint health.
+    pub providers: &'a Providers,
 }
 
 #[derive(Clone)]
 pub struct CommandBus {
     sender: mpsc::Sender<Message>,
     handle: Handle,
+    scheduler: Scheduler,
+    providers: Providers,
 }
 
 impl CommandBus {
-    pub fn new(sender: mpsc::Sender<Message>, handle: Handle) -> Self {
-        Self { sender, handle }
+    pub fn new(
+        sender: mpsc::Sender<Message>,
+        handle: Handle,
+        scheduler: Scheduler,
+        providers: Providers,
+    ) -> Self {
+        Self {
+            sender,
+            handle,
+            scheduler,
+            providers,
+        }
+    }
+
+    pub fn scheduler(&self) -> &Scheduler {
+        &self.scheduler
+    }
+
+    /// Where tasks spawned on this bus read chain data from.
+    pub fn providers(&self) -> &Providers {
+        &self.providers
+    }
+
+    /// Run `read` on the storage thread, after the writes queued before it,
+    /// and deliver its result as the message `message` builds.
+    pub fn read_storage<T: Send + 'static>(
+        &self,
+        storage: &Storage,
+        read: impl FnOnce(&Storage) -> color_eyre::Result<T> + Send + 'static,
+        message: impl FnOnce(Result<T, String>) -> Message + Send + 'static,
+    ) {
+        let sender = self.sender.clone();
+        storage.read(read, move |result| {
+            let _ = sender.send(message(result.map_err(|err| format!("{err:#}"))));
+        });
     }
 
+    /// Run `task` at once, for work that does not read chain data: signing
+    /// and broadcasts, local scripts, update checks and webhooks. Chain reads
+    /// go through [`spawn_scheduled`](Self::spawn_scheduled).
     pub fn spawn_async<F, Fut>(&self, task: F)
     where
         F: FnOnce() -> Fut + Send + 'static,
@@ -1410,6 +6721,54 @@ impl CommandBus {
             let _ = sender.send(message);
         });
     }
+
+    /// Like [`spawn_async`](Self::spawn_async), but the task can be aborted
+    /// through the returned handle before it reports back.
+    pub fn spawn_cancellable<F, Fut>(&self, task: F) -> AbortHandle
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = Message> + Send + 'static,
+    {
+        let sender = self.sender.clone();
+        self.handle
+            .spawn(async move {
+                let message = task().await;
+                let _ = sender.send(message);
+            })
+            .abort_handle()
+    }
+
+    /// Like [`spawn_async`](Self::spawn_async), but the task waits for a
+    /// slot on `host`, behind any more urgent work queued there.
+    pub fn spawn_scheduled<F, Fut>(&self, host: String, priority: Priority, task: F)
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = Message> + Send + 'static,
+    {
+        let scheduler = self.scheduler.clone();
+        self.spawn_async(move || async move {
+            let _permit = scheduler.acquire(&host, priority).await;
+            task().await
+        });
+    }
+
+    /// Start a plugin process whose output arrives as [`Message::Plugin`]
+    /// until the handle is dropped.
+    pub fn plugin(&self, config: plugins::PluginConfig) -> plugins::PluginHandle {
+        plugins::spawn(&self.handle, self.sender.clone(), config)
+    }
+
+    /// Start a WebSocket subscription whose events arrive as
+    /// [`Message::Subscription`] until the handle is dropped.
+    pub fn subscribe(&self, url: String, kind: ws::SubscriptionKind) -> ws::SubscriptionHandle {
+        ws::subscribe(
+            &self.handle,
+            self.sender.clone(),
+            self.providers.rpc.ws().clone(),
+            url,
+            kind,
+        )
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -1418,12 +6777,126 @@ pub enum Message {
         query: String,
         entity: SelectedEntity,
     },
+    /// A hash search resolved to a block rather than a transaction.
+    BlockFound {
+        chain: String,
+        block: Box<BlockSummary>,
+    },
+    /// A hash search could be a transaction or a block.
+    HashAmbiguous {
+        tx: TransactionRef,
+        reason: String,
+    },
+    BlockLoaded(Result<Option<BlockSummary>, String>),
     SearchFailed {
         query: String,
         error: String,
     },
-    AddressHydrated(HydratedAddress),
-    TransactionHydrated(HydratedTransaction),
+    AddressHydrated(Box<HydratedAddress>),
+    TransactionHydrated(Box<HydratedTransaction>),
+    FavoritePrefetched(SelectedEntity, Prefetched),
+    /// A write queued with [`Storage::write`] failed.
+    StorageFailed {
+        what: &'static str,
+        error: String,
+    },
+    SearchCandidatesLoaded(Result<Vec<suggestions::Suggestion>, String>),
+    TrashLoaded(Result<Vec<TrashRecord>, String>),
+    ScratchpadLoaded {
+        investigation: String,
+        result: Result<(Vec<ScratchNote>, Vec<String>), String>,
+    },
+    /// Notes written by `:scratchpad export`, or why not.
+    ScratchpadExported {
+        path: PathBuf,
+        result: Result<usize, String>,
+    },
+    SignatureMissesLoaded(Result<Vec<(SignatureKind, String)>, String>),
+    UpdateChecked(Result<ReleaseInfo, String>),
+    SecretsValidated(Vec<(SecretKey, secrets_check::SecretCheck)>),
+    WatchPolled(Vec<(String, Result<watch::MemberSnapshot, String>)>),
+    WatchlistPolled(Vec<(String, Result<watch::MemberSnapshot, String>)>),
+    SafeQueuesPolled(Vec<(String, Result<safe::SafeQueue, String>)>),
+    /// Outcome of a webhook post; `Some` carries a status for `:webhook`.
+    WebhookPosted(Result<Option<String>, String>),
+    PermissionsLoaded(Result<permissions::PermissionGraph, String>),
+    UpgradeSimulated(Result<upgrade_impact::UpgradeReport, String>),
+    DeployedCodeChecked(Result<Vec<(Address, usize)>, String>),
+    WritePrepared(Result<Box<signing::PreparedTransaction>, String>),
+    WriteSent(Result<Box<pending::PendingTransaction>, String>),
+    PendingChecked(Result<pending::AccountCheck, String>),
+    /// A speed-up or cancellation of the pending transaction `hash` was sent.
+    PendingPrepared(Result<Box<signing::PreparedTransaction>, String>),
+    PendingReplaced {
+        hash: String,
+        kind: nonces::ReplacementKind,
+        result: Result<Box<pending::PendingTransaction>, String>,
+    },
+    SafeQueueLoaded(Result<safe::SafeQueue, String>),
+    GasAnalyticsLoaded(Result<gas_analytics::GasReport, String>),
+    FollowPolled(Result<follow::FollowUpdate, String>),
+    EventsLoaded {
+        address: String,
+        result: Result<events::EventPage, String>,
+    },
+    UpgradesLoaded {
+        address: String,
+        result: Result<upgrades::UpgradeHistory, String>,
+    },
+    AccessListLoaded {
+        hash: String,
+        result: Result<access_list::DeclaredAccessList, String>,
+    },
+    AccessListGenerated {
+        hash: String,
+        result: Result<access_list::GeneratedAccessList, String>,
+    },
+    BalanceDiffLoaded {
+        hash: String,
+        result: Result<balance_diff::BalanceDiff, String>,
+    },
+    TransfersLoaded {
+        hash: String,
+        result: Result<transfers::TransferSummary, String>,
+    },
+    RawLoaded {
+        hash: String,
+        result: Result<raw_tx::RawTransaction, String>,
+    },
+    CustomTabLoaded {
+        title: String,
+        subject: String,
+        result: Result<Vec<String>, String>,
+    },
+    FeesLoaded {
+        hash: String,
+        result: Result<fees::FeeBreakdown, String>,
+    },
+    ChainSummaryLoaded {
+        address: String,
+        chain: String,
+        result: Result<multichain::ChainSummary, String>,
+    },
+    HistoryExported(Result<String, String>),
+    Piped(Result<pipe::PipeOutput, String>),
+    SignaturesSynced {
+        requested: usize,
+        result: Result<Vec<signatures::SyncedSignature>, String>,
+    },
+    /// How many answers `:cache clear` dropped, or why not.
+    ExplorerCacheCleared(Result<usize, String>),
+    /// How many answers the explorer cache holds, for `:cache`.
+    ExplorerCacheCounted(Result<usize, String>),
+    /// Stored signatures for the function and event selectors on screen.
+    SignaturesResolved(Result<(signatures::Resolved, signatures::Resolved), String>),
+    SlotsPolled(Vec<(String, Result<slots::SlotSample, String>)>),
+    LogAlertsPolled(Vec<log_alerts::ChainScan>),
+    ThresholdsPolled(Vec<thresholds::ChainReading>),
+    Plugin {
+        name: String,
+        output: plugins::PluginOutput,
+    },
+    Subscription(ws::SubscriptionEvent),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -1437,11 +6910,22 @@ pub enum Action {
     LoadingFinished(FocusedPane),
     CloseModal,
     SecretsSaved,
+    FavoriteRestored(TrashRecord),
+    /// Put text on the system clipboard, e.g. an error report.
+    CopyToClipboard(String),
+    /// Add `text` to the current investigation's scratchpad.
+    PinNote {
+        text: String,
+        source: String,
+    },
+    /// Stash the open session and open the named one.
+    SwitchSession(String),
 }
 
 mod navigation {
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
     pub enum FocusedPane {
+        #[default]
         Top,
         Sidebar,
         MainView,
@@ -1462,56 +6946,62 @@ mod navigation {
         }
     }
 
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
     pub enum SidebarTab {
+        #[default]
         Addresses,
         Transactions,
+        Watchlist,
     }
 
     impl SidebarTab {
         pub fn next(self) -> Self {
             match self {
                 SidebarTab::Addresses => SidebarTab::Transactions,
-                SidebarTab::Transactions => SidebarTab::Addresses,
+                SidebarTab::Transactions => SidebarTab::Watchlist,
+                SidebarTab::Watchlist => SidebarTab::Addresses,
             }
         }
 
         pub fn previous(self) -> Self {
             match self {
-                SidebarTab::Addresses => SidebarTab::Transactions,
+                SidebarTab::Addresses => SidebarTab::Watchlist,
                 SidebarTab::Transactions => SidebarTab::Addresses,
+                SidebarTab::Watchlist => SidebarTab::Transactions,
             }
         }
     }
 
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
     pub enum MainViewMode {
+        #[default]
         Address,
         Transaction,
     }
 
-    impl Default for MainViewMode {
-        fn default() -> Self {
-            Self::Address
-        }
-    }
-
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[derive(
+        Debug, Clone, Copy, PartialEq, Eq, Hash, Default, serde::Serialize, serde::Deserialize,
+    )]
+    #[serde(rename_all = "snake_case")]
     pub enum MainViewTab {
+        #[default]
         AddressInfo,
         AddressTransactions,
         AddressInternal,
+        AddressEvents,
+        AddressStorage,
         AddressBalances,
         AddressPermissions,
+        AddressChains,
         TransactionSummary,
+        TransactionFees,
+        TransactionBalanceDiff,
+        TransactionAccessList,
         TransactionDebug,
         TransactionStorageDiff,
-    }
-
-    impl Default for MainViewTab {
-        fn default() -> Self {
-            Self::AddressInfo
-        }
+        TransactionRaw,
+        /// A user-defined tab, by index into the custom tabs.
+        Custom(usize),
     }
 
     impl MainViewTab {
@@ -1521,14 +7011,23 @@ mod navigation {
                     MainViewTab::AddressInfo
                     | MainViewTab::AddressTransactions
                     | MainViewTab::AddressInternal
+                    | MainViewTab::AddressEvents
+                    | MainViewTab::AddressStorage
                     | MainViewTab::AddressBalances
-                    | MainViewTab::AddressPermissions => self,
+                    | MainViewTab::AddressPermissions
+                    | MainViewTab::AddressChains
+                    | MainViewTab::Custom(_) => self,
                     _ => MainViewTab::AddressInfo,
                 },
                 MainViewMode::Transaction => match self {
                     MainViewTab::TransactionSummary
+                    | MainViewTab::TransactionFees
+                    | MainViewTab::TransactionBalanceDiff
+                    | MainViewTab::TransactionAccessList
                     | MainViewTab::TransactionDebug
-                    | MainViewTab::TransactionStorageDiff => self,
+                    | MainViewTab::TransactionStorageDiff
+                    | MainViewTab::TransactionRaw
+                    | MainViewTab::Custom(_) => self,
                     _ => MainViewTab::TransactionSummary,
                 },
             }
@@ -1539,15 +7038,22 @@ mod navigation {
                 MainViewMode::Address => match self.normalize(mode) {
                     MainViewTab::AddressInfo => MainViewTab::AddressTransactions,
                     MainViewTab::AddressTransactions => MainViewTab::AddressInternal,
-                    MainViewTab::AddressInternal => MainViewTab::AddressBalances,
+                    MainViewTab::AddressInternal => MainViewTab::AddressEvents,
+                    MainViewTab::AddressEvents => MainViewTab::AddressStorage,
+                    MainViewTab::AddressStorage => MainViewTab::AddressBalances,
                     MainViewTab::AddressBalances => MainViewTab::AddressPermissions,
-                    MainViewTab::AddressPermissions => MainViewTab::AddressInfo,
+                    MainViewTab::AddressPermissions => MainViewTab::AddressChains,
+                    MainViewTab::AddressChains => MainViewTab::AddressInfo,
                     other => other,
                 },
                 MainViewMode::Transaction => match self.normalize(mode) {
-                    MainViewTab::TransactionSummary => MainViewTab::TransactionDebug,
+                    MainViewTab::TransactionSummary => MainViewTab::TransactionFees,
+                    MainViewTab::TransactionFees => MainViewTab::TransactionBalanceDiff,
+                    MainViewTab::TransactionBalanceDiff => MainViewTab::TransactionAccessList,
+                    MainViewTab::TransactionAccessList => MainViewTab::TransactionDebug,
                     MainViewTab::TransactionDebug => MainViewTab::TransactionStorageDiff,
-                    MainViewTab::TransactionStorageDiff => MainViewTab::TransactionSummary,
+                    MainViewTab::TransactionStorageDiff => MainViewTab::TransactionRaw,
+                    MainViewTab::TransactionRaw => MainViewTab::TransactionSummary,
                     other => other,
                 },
             }
@@ -1556,39 +7062,35 @@ mod navigation {
         pub fn previous(self, mode: MainViewMode) -> Self {
             match mode {
                 MainViewMode::Address => match self.normalize(mode) {
-                    MainViewTab::AddressInfo => MainViewTab::AddressPermissions,
+                    MainViewTab::AddressInfo => MainViewTab::AddressChains,
                     MainViewTab::AddressTransactions => MainViewTab::AddressInfo,
                     MainViewTab::AddressInternal => MainViewTab::AddressTransactions,
-                    MainViewTab::AddressBalances => MainViewTab::AddressInternal,
+                    MainViewTab::AddressEvents => MainViewTab::AddressInternal,
+                    MainViewTab::AddressStorage => MainViewTab::AddressEvents,
+                    MainViewTab::AddressBalances => MainViewTab::AddressStorage,
                     MainViewTab::AddressPermissions => MainViewTab::AddressBalances,
+                    MainViewTab::AddressChains => MainViewTab::AddressPermissions,
                     other => other,
                 },
                 MainViewMode::Transaction => match self.normalize(mode) {
-                    MainViewTab::TransactionSummary => MainViewTab::TransactionStorageDiff,
-                    MainViewTab::TransactionDebug => MainViewTab::TransactionSummary,
+                    MainViewTab::TransactionSummary => MainViewTab::TransactionRaw,
+                    MainViewTab::TransactionFees => MainViewTab::TransactionSummary,
+                    MainViewTab::TransactionAccessList => MainViewTab::TransactionBalanceDiff,
+                    MainViewTab::TransactionBalanceDiff => MainViewTab::TransactionFees,
+                    MainViewTab::TransactionDebug => MainViewTab::TransactionAccessList,
                     MainViewTab::TransactionStorageDiff => MainViewTab::TransactionDebug,
+                    MainViewTab::TransactionRaw => MainViewTab::TransactionStorageDiff,
                     other => other,
                 },
             }
         }
     }
-
-    impl Default for FocusedPane {
-        fn default() -> Self {
-            Self::Top
-        }
-    }
-
-    impl Default for SidebarTab {
-        fn default() -> Self {
-            Self::Addresses
-        }
-    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{components::KeyHint, storage::CachedResponse};
     use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
     use tempfile::tempdir;
 
@@ -1599,7 +7101,7 @@ mod tests {
             std::env::set_var("EVM_TUI_DATA_DIR", tmp.path());
         }
 
-        let mut app = App::new()?;
+        let mut app = App::new(None, Providers::default())?;
         assert!(app.secrets_modal_mut().is_some());
 
         app.handle_modal_paste("H43UPPAU7H4KBX99TSWMD3IHDG9F86IK43".into())?;
@@ -1607,6 +7109,16 @@ mod tests {
         let url = "https://eth-mainnet.g.alchemy.com/v2/example-key";
         app.handle_modal_paste(url.into())?;
         app.handle_modal_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))?;
+        // Saving waits for the background credential checks.
+        assert_eq!(app.state.secrets.anvil_rpc_url, None);
+
+        app.message_tx
+            .send(Message::SecretsValidated(vec![
+                (SecretKey::EtherscanApiKey, Ok("key accepted".into())),
+                (SecretKey::AnvilRpcUrl, Ok("chain id 1".into())),
+            ]))
+            .unwrap();
+        app.drain_messages();
 
         assert_eq!(app.state.secrets.anvil_rpc_url.as_deref(), Some(url));
 
@@ -1615,4 +7127,156 @@ mod tests {
         }
         Ok(())
     }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn macros_skip_keys_typed_into_signing_dialogs() -> AppResult<()> {
+        let mut app = App::demo(None, demo::providers())?;
+        app.on_key_event(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL))?;
+        app.on_key_event(key(KeyCode::Char('a')))?;
+        assert_eq!(app.state.macros.recording(), Some('a'));
+
+        app.on_key_event(KeyEvent::new(KeyCode::Char('Q'), KeyModifiers::SHIFT))?;
+        assert!(matches!(app.modal, Some(ActiveModal::Pending(_))));
+        app.on_key_event(key(KeyCode::Char('s')))?;
+        app.on_key_event(key(KeyCode::Esc))?;
+        assert!(app.modal.is_none());
+        app.on_key_event(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL))?;
+
+        let (_, keys) = app.state.macros.replay('a').unwrap();
+        assert_eq!(
+            keys,
+            [KeyEvent::new(KeyCode::Char('Q'), KeyModifiers::SHIFT)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn copied_addresses_and_links_open_without_the_prompt() -> AppResult<()> {
+        let mut app = App::demo(None, demo::providers())?;
+        let address = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+        app.open_clipboard_text(format!("  {}\n", address.to_ascii_lowercase()))?;
+        let opened = |app: &App| matches!(&app.state.selected, Some(SelectedEntity::Address(addr)) if addr.address == address);
+        for _ in 0..200 {
+            app.drain_messages();
+            if opened(&app) {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(opened(&app), "expected the copied address to open");
+
+        let hash = format!("0x{}", "ab".repeat(32));
+        app.open_clipboard_text(format!("{}base/tx/{hash}", deep_link::SCHEME))?;
+        let Some(SelectedEntity::Transaction(tx)) = app.state.selected.clone() else {
+            panic!("expected the copied link to open");
+        };
+        assert_eq!(
+            (tx.chain.as_str(), tx.hash.as_str()),
+            ("Base", hash.as_str())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn main_view_hints_follow_the_active_tab() -> AppResult<()> {
+        let mut app = App::demo(None, demo::providers())?;
+        app.state.navigation.focused_pane = FocusedPane::MainView;
+        app.state.navigation.main_view_mode = MainViewMode::Address;
+        app.state.navigation.main_view_tab = MainViewTab::AddressTransactions;
+        let view = AppView {
+            state: &app.state,
+            providers: &app.providers,
+        };
+        let hints = app.main_view.key_hints(&view);
+        assert_eq!(hints.first(), Some(&KeyHint::new("Enter", "Open")));
+        assert!(hints.contains(&KeyHint::new("L", "Follow")));
+        assert!(hints.contains(&KeyHint::new("E", "Export CSV")));
+        Ok(())
+    }
+
+    #[test]
+    fn signatures_resolve_and_sync_through_the_storage_thread() -> AppResult<()> {
+        let mut app = App::demo(None, demo::providers())?;
+        let resolved = |app: &App| {
+            app.state
+                .current_address
+                .iter()
+                .filter_map(|data| data.transactions_table.as_ref())
+                .flat_map(|table| table.rows.iter())
+                .filter_map(|row| row.calldata.as_deref())
+                .any(|calldata| {
+                    app.state
+                        .signatures
+                        .method(calldata)
+                        .is_some_and(|method| signatures::method_name(method) == "transfer")
+                })
+        };
+        for _ in 0..200 {
+            app.drain_messages();
+            if resolved(&app) {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(resolved(&app), "expected the demo transfers to resolve");
+
+        app.apply_synced_signatures(
+            1,
+            vec![signatures::SyncedSignature {
+                kind: SignatureKind::Function,
+                selector: "0xdeadbeef".into(),
+                signature: "foo()".into(),
+            }],
+        );
+        app.storage.flush();
+        assert_eq!(
+            app.storage
+                .signatures()
+                .get(SignatureKind::Function, "0xdeadbeef")?,
+            ["foo()"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn explorer_cache_clear_runs_on_the_storage_thread() -> AppResult<()> {
+        let mut app = App::demo(None, demo::providers())?;
+        let cached = CachedResponse {
+            fetched_at: 1,
+            result: serde_json::Value::Null,
+        };
+        app.storage.responses().put("request", &cached)?;
+        app.explorer_cache_command(&["clear"]);
+        app.storage.flush();
+        assert_eq!(app.storage.responses().len()?, 0);
+        app.drain_messages();
+        Ok(())
+    }
+
+    #[test]
+    fn macro_replay_stops_at_signing_dialogs() -> AppResult<()> {
+        let mut app = App::demo(None, demo::providers())?;
+        app.state.macros.start('b');
+        for key in [
+            KeyEvent::new(KeyCode::Char('Q'), KeyModifiers::SHIFT),
+            key(KeyCode::Char('s')),
+            key(KeyCode::Esc),
+        ] {
+            app.state.macros.record(key);
+        }
+        app.state.macros.stop();
+
+        app.on_key_event(key(KeyCode::Char('@')))?;
+        app.on_key_event(key(KeyCode::Char('b')))?;
+
+        // Replay opened the queue, then left it for the user instead of
+        // typing the rest into it.
+        assert!(matches!(app.modal, Some(ActiveModal::Pending(_))));
+        assert!(!app.state.macros.replaying);
+        Ok(())
+    }
 }
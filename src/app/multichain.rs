@@ -0,0 +1,207 @@
+use super::{
+    AddressRef, SecretsState,
+    anvil::{AccountOverview, fetch_transaction_inclusion},
+    chains,
+    providers::Providers,
+};
+use crate::storage::SecretKey;
+use alloy::primitives::Address;
+use std::collections::BTreeSet;
+
+/// Chain name used for the Anvil endpoint, which serves no registry chain.
+pub const LOCAL_CHAIN: &str = "Local";
+
+/// Newest transaction touching the address on one chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LastActivity {
+    pub hash: String,
+    pub block_number: u64,
+    pub timestamp: Option<u64>,
+}
+
+/// What one chain knows about the address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainSummary {
+    pub overview: AccountOverview,
+    /// `Ok(None)` when the address has never been used on this chain.
+    pub last_activity: Result<Option<LastActivity>, String>,
+}
+
+impl ChainSummary {
+    /// No balance, no nonce and no code: the address was never touched here.
+    pub fn is_unused(&self) -> bool {
+        self.overview.balance_wei.is_zero()
+            && self.overview.transaction_count == 0
+            && !self.overview.is_contract
+            && self.overview.delegate.is_none()
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match (&self.overview, self.is_unused()) {
+            (overview, _) if overview.is_contract => "Contract",
+            (overview, _) if overview.delegate.is_some() => "EOA + 7702",
+            (_, true) => "Unused",
+            _ => "EOA",
+        }
+    }
+}
+
+/// One row of the matrix; `result` is `None` while the chain is loading.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainRow {
+    pub chain: String,
+    pub result: Option<Result<ChainSummary, String>>,
+}
+
+/// State of the Chains tab for the selected address.
+#[derive(Debug, Default)]
+pub struct MultichainView {
+    /// Address the rows were loaded for.
+    pub address: Option<String>,
+    pub rows: Vec<ChainRow>,
+    pub selected_index: usize,
+}
+
+impl MultichainView {
+    pub fn needs_load(&self, address: &str) -> bool {
+        !self
+            .address
+            .as_deref()
+            .is_some_and(|loaded| loaded.eq_ignore_ascii_case(address))
+    }
+
+    pub fn apply(&mut self, address: &str, chain: &str, result: Result<ChainSummary, String>) {
+        if self.needs_load(address) {
+            return;
+        }
+        if let Some(row) = self.rows.iter_mut().find(|row| row.chain == chain) {
+            row.result = Some(result);
+        }
+    }
+
+    pub fn selected(&self) -> Option<&ChainRow> {
+        self.rows.get(self.selected_index)
+    }
+
+    pub fn move_selection(&mut self, down: bool) {
+        let last = self.rows.len().saturating_sub(1);
+        self.selected_index = if down {
+            (self.selected_index + 1).min(last)
+        } else {
+            self.selected_index.saturating_sub(1)
+        };
+    }
+}
+
+/// Every shown chain with its own RPC endpoint, in registry order, then the
+/// Anvil endpoint as [`LOCAL_CHAIN`]. The Anvil URL is not used as a fallback
+/// here, since it would show the same node under every chain.
+pub fn configured_chains(
+    secrets: &SecretsState,
+    hidden: &BTreeSet<String>,
+) -> Vec<(String, String)> {
+    let mut chains: Vec<(String, String)> = chains::visible(hidden)
+        .filter_map(|info| {
+            let url = secrets.get(&SecretKey::RpcUrl(info.name.to_ascii_lowercase()))?;
+            Some((info.name.to_string(), url.to_string()))
+        })
+        .collect();
+    if let Some(url) = secrets.anvil_rpc_url.as_deref() {
+        chains.push((LOCAL_CHAIN.to_string(), url.to_string()));
+    }
+    chains
+}
+
+/// Read the account overview on one chain and, when the address was used
+/// there, its newest transaction from the chain's history source.
+pub async fn fetch_chain_summary(
+    providers: Providers,
+    address: AddressRef,
+    rpc_url: String,
+    api_key: Option<String>,
+) -> Result<ChainSummary, String> {
+    let target: Address = address
+        .address
+        .parse()
+        .map_err(|_| "invalid address".to_string())?;
+    let overview = providers
+        .accounts
+        .account_overview(&rpc_url, target)
+        .await
+        .map_err(|err| format!("{err:#}"))?;
+    let mut summary = ChainSummary {
+        overview,
+        last_activity: Ok(None),
+    };
+    if summary.is_unused() {
+        return Ok(summary);
+    }
+    summary.last_activity = match providers
+        .explorer
+        .address_transactions(&address, api_key.as_deref(), Some(&rpc_url), 1)
+        .await
+    {
+        Ok((transactions, _)) => match transactions.into_iter().next() {
+            Some(transaction) => {
+                let timestamp =
+                    fetch_transaction_inclusion(&providers.rpc, &rpc_url, &transaction.hash)
+                        .await
+                        .ok()
+                        .flatten()
+                        .map(|(_, timestamp)| timestamp);
+                Ok(Some(LastActivity {
+                    hash: transaction.hash,
+                    block_number: transaction.block_number,
+                    timestamp,
+                }))
+            }
+            None => Ok(None),
+        },
+        Err(err) => Err(err.to_string()),
+    };
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::U256;
+
+    #[test]
+    fn lists_chains_with_their_own_rpc() {
+        let mut secrets = SecretsState::default();
+        secrets.set(
+            &SecretKey::RpcUrl("base".into()),
+            Some("https://base.example".into()),
+        );
+        secrets.set(
+            &SecretKey::RpcUrl("mainnet".into()),
+            Some("https://eth.example".into()),
+        );
+        secrets.anvil_rpc_url = Some("http://127.0.0.1:8545".into());
+        let chains: Vec<String> = configured_chains(&secrets, &BTreeSet::new())
+            .into_iter()
+            .map(|(chain, _)| chain)
+            .collect();
+        assert_eq!(chains, vec!["Mainnet", "Base", LOCAL_CHAIN]);
+        let hidden = BTreeSet::from(["base".to_string()]);
+        assert_eq!(configured_chains(&secrets, &hidden).len(), 2);
+
+        let mut summary = ChainSummary {
+            overview: AccountOverview {
+                latest_block: 1,
+                balance_wei: U256::ZERO,
+                transaction_count: 0,
+                pending_transaction_count: 0,
+                queued_nonces: None,
+                is_contract: false,
+                delegate: None,
+                code: Default::default(),
+            },
+            last_activity: Ok(None),
+        };
+        assert_eq!(summary.kind(), "Unused");
+        summary.overview.balance_wei = U256::from(1);
+        assert_eq!(summary.kind(), "EOA");
+    }
+}
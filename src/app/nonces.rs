@@ -0,0 +1,219 @@
+use super::{anvil::normalize_url, providers::Rpc, stats::ApiStats};
+use alloy::{
+    consensus::Transaction as ConsensusTransaction,
+    network::TransactionResponse,
+    primitives::{Address, B256},
+    providers::{Provider, ext::TxPoolApi},
+    rpc::types::Transaction,
+};
+use serde::{Deserialize, Serialize};
+
+/// Nonces missing below the highest queued one: the pool cannot execute the
+/// queued transactions until these are sent.
+pub fn missing_nonces(pending: u64, queued: &[u64]) -> Vec<u64> {
+    let Some(highest) = queued.iter().copied().max() else {
+        return Vec::new();
+    };
+    (pending..highest)
+        .filter(|nonce| !queued.contains(nonce))
+        .collect()
+}
+
+/// Nonces the txpool queues for `address` (waiting on a gap), or `None` when
+/// the node does not expose `txpool_contentFrom`.
+pub(super) async fn queued_nonces(
+    stats: &ApiStats,
+    provider: &impl Provider,
+    address: Address,
+) -> Option<Vec<u64>> {
+    stats.record_api_call("rpc");
+    let content = provider.txpool_content_from(address).await.ok()?;
+    Some(
+        content
+            .queued
+            .keys()
+            .filter_map(|nonce| nonce.parse().ok())
+            .collect(),
+    )
+}
+
+/// How another transaction with the same sender and nonce relates to the
+/// viewed one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReplacementKind {
+    /// Zero-value, empty-calldata transfer to the sender itself.
+    Cancel,
+    /// Same call with a different fee.
+    SpeedUp,
+    /// A different call reusing the nonce.
+    Replacement,
+}
+
+impl ReplacementKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            ReplacementKind::Cancel => "cancel",
+            ReplacementKind::SpeedUp => "speed-up",
+            ReplacementKind::Replacement => "replacement",
+        }
+    }
+}
+
+pub fn classify(
+    sender: Address,
+    viewed: &impl ConsensusTransaction,
+    other: &impl ConsensusTransaction,
+) -> ReplacementKind {
+    if other.to() == Some(sender) && other.value().is_zero() && other.input().is_empty() {
+        ReplacementKind::Cancel
+    } else if other.to() == viewed.to()
+        && other.value() == viewed.value()
+        && other.input() == viewed.input()
+    {
+        ReplacementKind::SpeedUp
+    } else {
+        ReplacementKind::Replacement
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NonceSibling {
+    pub hash: B256,
+    pub kind: ReplacementKind,
+    /// `true` for the transaction the chain included at this nonce.
+    pub mined: bool,
+    pub max_fee_per_gas: u128,
+}
+
+/// Every transaction known to share the viewed transaction's sender and nonce.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NonceHistory {
+    pub sender: Address,
+    pub nonce: u64,
+    /// Hash the chain included at this nonce, when the node can tell.
+    pub mined: Option<B256>,
+    pub siblings: Vec<NonceSibling>,
+}
+
+impl NonceHistory {
+    /// The viewed transaction lost its nonce to another one.
+    pub fn replaced(&self, hash: B256) -> bool {
+        self.mined.is_some_and(|mined| mined != hash)
+    }
+}
+
+/// Look up transactions sharing `hash`'s (sender, nonce): the mined one via
+/// Otterscan's `ots_getTransactionBySenderAndNonce` and pending ones via
+/// `txpool_contentFrom`. Either source is skipped when the node lacks it.
+pub async fn fetch_nonce_history(
+    rpc: &Rpc,
+    rpc_url: &str,
+    hash: &str,
+) -> Result<NonceHistory, String> {
+    let tx_hash: B256 = hash
+        .parse()
+        .map_err(|_| "invalid transaction hash".to_string())?;
+    let provider = rpc
+        .connect(&normalize_url(rpc, rpc_url))
+        .await
+        .map_err(|err| format!("{err:#}"))?;
+    rpc.stats().record_api_call("rpc");
+    let viewed = provider
+        .get_transaction_by_hash(tx_hash)
+        .await
+        .map_err(|err| format!("failed to load transaction: {err}"))?
+        .ok_or("transaction not found on this RPC")?;
+    let sender = viewed.from();
+    let nonce = viewed.nonce();
+
+    let mut candidates: Vec<(Transaction, bool)> = Vec::new();
+    let mined: Option<B256> = match viewed.block_number {
+        Some(_) => Some(tx_hash),
+        None => {
+            rpc.stats().record_api_call("rpc");
+            provider
+                .raw_request("ots_getTransactionBySenderAndNonce".into(), (sender, nonce))
+                .await
+                .ok()
+                .flatten()
+        }
+    };
+    if let Some(mined) = mined.filter(|mined| *mined != tx_hash) {
+        rpc.stats().record_api_call("rpc");
+        if let Ok(Some(transaction)) = provider.get_transaction_by_hash(mined).await {
+            candidates.push((transaction, true));
+        }
+    }
+    if viewed.block_number.is_none() {
+        rpc.stats().record_api_call("rpc");
+        if let Ok(content) = provider.txpool_content_from(sender).await {
+            candidates.extend(
+                content
+                    .pending
+                    .into_values()
+                    .chain(content.queued.into_values())
+                    .filter(|transaction| transaction.nonce() == nonce)
+                    .map(|transaction| (transaction, false)),
+            );
+        }
+    }
+
+    let siblings = candidates
+        .into_iter()
+        .filter(|(transaction, _)| transaction.tx_hash() != tx_hash)
+        .map(|(transaction, mined)| NonceSibling {
+            hash: transaction.tx_hash(),
+            kind: classify(sender, &viewed, &transaction),
+            mined,
+            max_fee_per_gas: ConsensusTransaction::max_fee_per_gas(&transaction),
+        })
+        .collect();
+    Ok(NonceHistory {
+        sender,
+        nonce,
+        mined,
+        siblings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::{
+        consensus::TxLegacy,
+        primitives::{Bytes, TxKind, U256},
+    };
+
+    #[test]
+    fn finds_gaps_and_classifies_replacements() {
+        assert_eq!(missing_nonces(5, &[7, 9]), vec![5, 6, 8]);
+        assert!(missing_nonces(5, &[]).is_empty());
+
+        let sender = Address::repeat_byte(0x11);
+        let viewed = TxLegacy {
+            to: TxKind::Call(Address::repeat_byte(0x22)),
+            value: U256::from(1),
+            input: Bytes::from_static(&[0xa9, 0x05]),
+            gas_price: 10,
+            ..TxLegacy::default()
+        };
+        let faster = TxLegacy {
+            gas_price: 20,
+            ..viewed.clone()
+        };
+        let cancel = TxLegacy {
+            to: TxKind::Call(sender),
+            ..TxLegacy::default()
+        };
+        let other = TxLegacy {
+            value: U256::from(2),
+            ..viewed.clone()
+        };
+        assert_eq!(classify(sender, &viewed, &faster), ReplacementKind::SpeedUp);
+        assert_eq!(classify(sender, &viewed, &cancel), ReplacementKind::Cancel);
+        assert_eq!(
+            classify(sender, &viewed, &other),
+            ReplacementKind::Replacement
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use crate::storage::GroupMember;
+use notify_rust::Notification;
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// At most this many notifications (and as many webhook posts) per
+/// [`WINDOW`]; a burst of changes (a reorg, a busy multisig) is summed up in
+/// the status bar instead.
+const BURST: usize = 5;
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// Something a watch saw, sent wherever the watch's
+/// [`AlertOutputs`](crate::storage::AlertOutputs) point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchEvent {
+    /// What raised it: `watch_group`, `watchlist`, `safe_queue`,
+    /// `storage_slot` or `log_rule`.
+    pub source: &'static str,
+    pub title: String,
+    pub message: String,
+    /// The watched address, when the event is about a single one.
+    pub subject: Option<GroupMember>,
+    /// Hash of the transaction that set it off, when known.
+    pub transaction: Option<String>,
+}
+
+/// Desktop notifications for watches that opted in, so changes are noticed
+/// while the terminal is in the background, and the rate limit of webhook
+/// posts.
+#[derive(Debug, Default)]
+pub struct Notifier {
+    desktop: Burst,
+    webhook: Burst,
+}
+
+impl Notifier {
+    /// Raise a notification unless the burst limit is reached. Shown from a
+    /// thread of its own: the D-Bus round trip on Linux can take a while.
+    pub fn notify(&mut self, event: &WatchEvent) {
+        if !self.desktop.allow(Instant::now()) {
+            return;
+        }
+        let (summary, body) = (event.title.clone(), event.message.clone());
+        std::thread::spawn(move || {
+            // Nothing to do without a notification daemon; the status bar
+            // already shows the change.
+            let _ = Notification::new()
+                .appname("evm-tui")
+                .summary(&summary)
+                .body(&body)
+                .show();
+        });
+    }
+
+    /// Whether a webhook post may go out now.
+    pub fn allow_webhook(&mut self) -> bool {
+        self.webhook.allow(Instant::now())
+    }
+}
+
+#[derive(Debug, Default)]
+struct Burst {
+    sent: VecDeque<Instant>,
+}
+
+impl Burst {
+    fn allow(&mut self, now: Instant) -> bool {
+        while self
+            .sent
+            .front()
+            .is_some_and(|sent| now.duration_since(*sent) >= WINDOW)
+        {
+            self.sent.pop_front();
+        }
+        if self.sent.len() >= BURST {
+            return false;
+        }
+        self.sent.push_back(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bursts_are_capped_per_window() {
+        let mut burst = Burst::default();
+        let start = Instant::now();
+        for _ in 0..BURST {
+            assert!(burst.allow(start));
+        }
+        assert!(!burst.allow(start + Duration::from_secs(30)));
+        assert!(burst.allow(start + WINDOW));
+    }
+}
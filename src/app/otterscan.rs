@@ -0,0 +1,299 @@
+use super::{
+    anvil::normalize_url,
+    explorer::{
+        AddressTransaction, ContractCreation, ContractSource, ExplorerBackend,
+        TransactionFetchError, TransactionListSource,
+    },
+    providers::Rpc,
+};
+use alloy::{
+    primitives::{Address, U64, U256},
+    providers::Provider,
+};
+use serde::Deserialize;
+use std::{
+    borrow::Cow,
+    cmp::Reverse,
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+/// Address history served by an Erigon node's `ots_` namespace (Otterscan API).
+/// Needs no explorer API key and returns the complete history, including
+/// transactions that only touch the address through internal calls.
+pub struct OtterscanBackend {
+    rpc: Rpc,
+    rpc_url: String,
+}
+
+impl OtterscanBackend {
+    pub fn new(rpc: Rpc, rpc_url: &str) -> Self {
+        Self {
+            rpc_url: normalize_url(&rpc, rpc_url),
+            rpc,
+        }
+    }
+
+    /// Returns `true` when the node answers `ots_getApiLevel`. The answer is
+    /// remembered per endpoint, so only the first history fetch probes it.
+    pub async fn is_supported(rpc: &Rpc, rpc_url: &str) -> bool {
+        let url = normalize_url(rpc, rpc_url);
+        if let Some(supported) = rpc.otterscan().get(&url) {
+            return supported;
+        }
+        rpc.stats().record_api_call("otterscan");
+        // An endpoint that cannot be reached is asked again next time.
+        let Ok(provider) = rpc.connect(&url).await else {
+            return false;
+        };
+        let supported = provider
+            .raw_request::<_, u64>(Cow::Borrowed("ots_getApiLevel"), ())
+            .await
+            .is_ok();
+        rpc.otterscan().record(&url, supported);
+        supported
+    }
+}
+
+/// Which endpoints serve the `ots_` namespace, learned once per session.
+/// Clones share the answers.
+#[derive(Clone, Default)]
+pub struct OtterscanSupport(Arc<Mutex<BTreeMap<String, bool>>>);
+
+impl OtterscanSupport {
+    pub fn get(&self, url: &str) -> Option<bool> {
+        self.0.lock().ok()?.get(url).copied()
+    }
+
+    pub fn record(&self, url: &str, supported: bool) {
+        if let Ok(mut support) = self.0.lock() {
+            support.insert(url.to_string(), supported);
+        }
+    }
+}
+
+impl ExplorerBackend for OtterscanBackend {
+    fn source(&self) -> TransactionListSource {
+        TransactionListSource {
+            label: "Otterscan",
+            api_version: "ots",
+        }
+    }
+
+    async fn address_transactions(
+        &self,
+        address: &str,
+        limit: usize,
+    ) -> Result<Vec<AddressTransaction>, TransactionFetchError> {
+        let target = address
+            .parse::<Address>()
+            .map_err(|err| TransactionFetchError::Api(format!("invalid address: {err}")))?;
+        self.rpc.stats().record_api_call("otterscan");
+        let provider = self
+            .rpc
+            .connect(&self.rpc_url)
+            .await
+            .map_err(|err| TransactionFetchError::Api(format!("{err:#}")))?;
+        // Block 0 means "start from the chain head" for the backwards search.
+        let page: serde_json::Value = provider
+            .raw_request(
+                Cow::Borrowed("ots_searchTransactionsBefore"),
+                (target, 0u64, limit.max(1) as u64),
+            )
+            .await
+            .map_err(|err| TransactionFetchError::Api(format!("ots request failed: {err}")))?;
+        let mut transactions = parse_search_page(page)?;
+        transactions.sort_by_key(|tx| Reverse(tx.block_number));
+        transactions.truncate(limit.max(1));
+        Ok(transactions)
+    }
+
+    async fn contract_source(
+        &self,
+        _address: &str,
+    ) -> Result<Option<ContractSource>, TransactionFetchError> {
+        // Otterscan does not index verified sources; callers use the chain explorer.
+        Ok(None)
+    }
+
+    async fn contract_creation(
+        &self,
+        address: &str,
+    ) -> Result<Option<ContractCreation>, TransactionFetchError> {
+        let target = address
+            .parse::<Address>()
+            .map_err(|err| TransactionFetchError::Api(format!("invalid address: {err}")))?;
+        self.rpc.stats().record_api_call("otterscan");
+        let provider = self
+            .rpc
+            .connect(&self.rpc_url)
+            .await
+            .map_err(|err| TransactionFetchError::Api(format!("{err:#}")))?;
+        let creator: Option<RawOtsCreator> = provider
+            .raw_request(Cow::Borrowed("ots_getContractCreator"), (target,))
+            .await
+            .map_err(|err| TransactionFetchError::Api(format!("ots request failed: {err}")))?;
+        // The caller reads block and timestamp from the creation transaction.
+        Ok(creator.map(|creator| ContractCreation {
+            creator: creator.creator,
+            transaction_hash: creator.hash,
+            block_number: None,
+            timestamp: None,
+        }))
+    }
+}
+
+fn parse_search_page(
+    page: serde_json::Value,
+) -> Result<Vec<AddressTransaction>, TransactionFetchError> {
+    let page = serde_json::from_value::<RawSearchPage>(page)?;
+    let transactions = page
+        .txs
+        .into_iter()
+        .map(|tx| {
+            let receipt = page
+                .receipts
+                .iter()
+                .find(|receipt| receipt.transaction_hash.eq_ignore_ascii_case(&tx.hash));
+            let is_error = receipt
+                .and_then(|receipt| receipt.status)
+                .map(|status| status.is_zero())
+                .unwrap_or(false);
+            let timestamp = receipt
+                .and_then(|receipt| receipt.timestamp)
+                .map(|timestamp| timestamp.to::<u64>());
+            let gas_used = receipt
+                .and_then(|receipt| receipt.gas_used)
+                .map(|gas| gas.to::<u64>());
+            let input = tx.input.trim();
+            let input = if input.is_empty() || input.eq_ignore_ascii_case("0x") {
+                None
+            } else {
+                Some(input.to_string())
+            };
+            AddressTransaction {
+                block_number: tx.block_number.map(|n| n.to::<u64>()).unwrap_or_default(),
+                hash: tx.hash,
+                from: tx.from,
+                to: tx.to,
+                value_wei: tx.value,
+                is_error,
+                input,
+                timestamp,
+                gas_used,
+            }
+        })
+        .collect();
+    Ok(transactions)
+}
+
+#[derive(Debug, Deserialize)]
+struct RawSearchPage {
+    #[serde(default)]
+    txs: Vec<RawOtsTransaction>,
+    #[serde(default)]
+    receipts: Vec<RawOtsReceipt>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawOtsTransaction {
+    hash: String,
+    #[serde(default)]
+    block_number: Option<U64>,
+    from: String,
+    #[serde(default)]
+    to: Option<String>,
+    #[serde(default)]
+    value: U256,
+    #[serde(default)]
+    input: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawOtsReceipt {
+    transaction_hash: String,
+    #[serde(default)]
+    status: Option<U64>,
+    /// Block time Otterscan adds to its search receipts.
+    #[serde(default)]
+    timestamp: Option<U64>,
+    #[serde(default)]
+    gas_used: Option<U64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawOtsCreator {
+    hash: String,
+    creator: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::providers::{BoxFuture, RpcConnector};
+    use alloy::{
+        providers::{DynProvider, ProviderBuilder},
+        transports::mock::Asserter,
+    };
+    use serde_json::json;
+
+    /// Connects every URL to the same mocked provider.
+    struct Mocked(DynProvider);
+
+    impl RpcConnector for Mocked {
+        fn connect<'a>(
+            &'a self,
+            _rpc_url: &'a str,
+        ) -> BoxFuture<'a, color_eyre::Result<DynProvider>> {
+            let provider = self.0.clone();
+            Box::pin(async move { Ok(provider) })
+        }
+    }
+
+    #[tokio::test]
+    async fn probes_each_endpoint_once() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new()
+            .connect_mocked_client(asserter.clone())
+            .erased();
+        let rpc = Rpc::new(Arc::new(Mocked(provider)));
+        asserter.push_success(&8u64);
+
+        // The mock has a single answer queued, so a second probe would fail.
+        assert!(OtterscanBackend::is_supported(&rpc, "http://erigon:8545").await);
+        assert!(OtterscanBackend::is_supported(&rpc.clone(), "http://erigon:8545").await);
+        assert!(!OtterscanBackend::is_supported(&rpc, "http://geth:8545").await);
+        assert_eq!(rpc.otterscan().get("http://geth:8545"), Some(false));
+    }
+
+    #[test]
+    fn parses_search_page_with_receipt_status() {
+        let page = json!({
+            "txs": [{
+                "hash": "0xaa",
+                "blockNumber": "0x10",
+                "from": "0x1111111111111111111111111111111111111111",
+                "to": null,
+                "value": "0xde0b6b3a7640000",
+                "input": "0x"
+            }],
+            "receipts": [{ "transactionHash": "0xAA", "status": "0x0", "timestamp": 1700000000 }],
+            "firstPage": true,
+            "lastPage": true
+        });
+
+        let parsed = parse_search_page(page).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].block_number, 16);
+        assert!(parsed[0].to.is_none());
+        assert!(parsed[0].is_error);
+        assert!(parsed[0].input.is_none());
+        assert_eq!(parsed[0].timestamp, Some(1_700_000_000));
+        assert_eq!(
+            parsed[0].value_wei,
+            U256::from(1_000_000_000_000_000_000u128)
+        );
+    }
+}
@@ -0,0 +1,243 @@
+use super::{
+    anvil::normalize_url, nonces::ReplacementKind, providers::Rpc, signing::PreparedTransaction,
+    watchlist::unix_now,
+};
+use alloy::{
+    consensus::TxEip1559,
+    primitives::{Address, Bytes, TxKind, U256},
+    providers::Provider,
+};
+use serde::{Deserialize, Serialize};
+
+/// A transaction unmined this long is flagged as stuck.
+pub const STUCK_AFTER_SECS: u64 = 180;
+
+/// Gas a plain ETH transfer (and so a cancellation) uses.
+const TRANSFER_GAS: u64 = 21_000;
+
+/// A transaction sent from the write flow that has not been seen mined.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingTransaction {
+    pub chain: String,
+    pub chain_id: u64,
+    pub hash: String,
+    pub from: Address,
+    pub nonce: u64,
+    pub to: Address,
+    pub input: Bytes,
+    pub value: U256,
+    pub gas_limit: u64,
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+    /// Unix time it was broadcast.
+    pub sent_at: u64,
+    /// Set when this transaction replaced earlier ones at the same nonce.
+    pub replacement: Option<ReplacementKind>,
+    /// Hashes of the transactions it replaced, oldest first.
+    pub replaced: Vec<String>,
+}
+
+impl PendingTransaction {
+    pub fn sent(chain: String, prepared: &PreparedTransaction, hash: String) -> Self {
+        let tx = &prepared.tx;
+        Self {
+            chain,
+            chain_id: tx.chain_id,
+            hash,
+            from: prepared.from(),
+            nonce: tx.nonce,
+            to: tx.to.to().copied().unwrap_or_default(),
+            input: tx.input.clone(),
+            value: tx.value,
+            gas_limit: tx.gas_limit,
+            max_fee_per_gas: tx.max_fee_per_gas,
+            max_priority_fee_per_gas: tx.max_priority_fee_per_gas,
+            sent_at: unix_now(),
+            replacement: None,
+            replaced: Vec::new(),
+        }
+    }
+
+    /// The same nonce re-sent with higher fees: the original call for a
+    /// speed-up, a zero-value transfer to the sender for a cancellation.
+    /// Both fees rise at least 12.5%, over the 10% nodes require to accept
+    /// a replacement, and never below the current estimate.
+    pub fn replacement_tx(
+        &self,
+        kind: ReplacementKind,
+        max_fee_per_gas: u128,
+        max_priority_fee_per_gas: u128,
+    ) -> TxEip1559 {
+        let bump = |old: u128, current: u128| (old + old / 8 + 1).max(current);
+        let priority = bump(self.max_priority_fee_per_gas, max_priority_fee_per_gas);
+        let (to, value, input, gas_limit) = match kind {
+            ReplacementKind::Cancel => (self.from, U256::ZERO, Bytes::new(), TRANSFER_GAS),
+            _ => (self.to, self.value, self.input.clone(), self.gas_limit),
+        };
+        TxEip1559 {
+            chain_id: self.chain_id,
+            nonce: self.nonce,
+            gas_limit,
+            max_fee_per_gas: bump(self.max_fee_per_gas, max_fee_per_gas).max(priority),
+            max_priority_fee_per_gas: priority,
+            to: TxKind::Call(to),
+            value,
+            access_list: Default::default(),
+            input,
+        }
+    }
+
+    /// Why the transaction looks stuck: its fee cap is under the latest base
+    /// fee, or it has waited longer than [`STUCK_AFTER_SECS`].
+    pub fn stuck_reason(&self, now: u64, base_fee: Option<u128>) -> Option<&'static str> {
+        if base_fee.is_some_and(|base_fee| self.max_fee_per_gas < base_fee) {
+            Some("fee cap below base fee")
+        } else if now.saturating_sub(self.sent_at) >= STUCK_AFTER_SECS {
+            Some("waiting over 3 minutes")
+        } else {
+            None
+        }
+    }
+}
+
+/// Nonce for the next transaction from `from`: the node's pending count, or
+/// past the highest one still queued locally when the node has dropped or
+/// not yet seen it, so a new call never collides with a queued one.
+pub fn next_nonce(
+    node_pending: u64,
+    queue: &[PendingTransaction],
+    chain_id: u64,
+    from: Address,
+) -> u64 {
+    queue
+        .iter()
+        .filter(|tx| tx.chain_id == chain_id && tx.from == from)
+        .map(|tx| tx.nonce + 1)
+        .fold(node_pending, u64::max)
+}
+
+/// Where an account's queue stands on chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountCheck {
+    pub chain: String,
+    pub from: Address,
+    /// Transactions mined from the account; queued nonces below it are done.
+    pub mined_nonce: u64,
+    pub base_fee: Option<u128>,
+}
+
+/// Read the account's mined nonce and the latest base fee.
+pub async fn check_account(
+    rpc: Rpc,
+    rpc_url: String,
+    chain: String,
+    from: Address,
+) -> Result<AccountCheck, String> {
+    let provider = rpc
+        .connect(&normalize_url(&rpc, &rpc_url))
+        .await
+        .map_err(|err| format!("{err:#}"))?;
+    rpc.stats().record_api_call("rpc");
+    let mined_nonce = provider
+        .get_transaction_count(from)
+        .latest()
+        .await
+        .map_err(|err| format!("failed to read the nonce of {from}: {err}"))?;
+    rpc.stats().record_api_call("rpc");
+    let base_fee = provider
+        .get_block_by_number(Default::default())
+        .await
+        .ok()
+        .flatten()
+        .and_then(|block| block.header.base_fee_per_gas)
+        .map(u128::from);
+    Ok(AccountCheck {
+        chain,
+        from,
+        mined_nonce,
+        base_fee,
+    })
+}
+
+/// Drop queued transactions the check shows mined (or replaced by one that
+/// was), returning their hashes.
+pub fn prune(queue: &mut Vec<PendingTransaction>, check: &AccountCheck) -> Vec<String> {
+    let mut done = Vec::new();
+    queue.retain(|tx| {
+        let mined =
+            tx.chain == check.chain && tx.from == check.from && tx.nonce < check.mined_nonce;
+        if mined {
+            done.push(tx.hash.clone());
+        }
+        !mined
+    });
+    done
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{B256, address};
+
+    fn queued(nonce: u64, sent_at: u64) -> PendingTransaction {
+        PendingTransaction {
+            chain: "mainnet".into(),
+            chain_id: 1,
+            hash: format!("{:#x}", B256::with_last_byte(nonce as u8)),
+            from: address!("0x00000000000000000000000000000000000000aa"),
+            nonce,
+            to: address!("0x00000000000000000000000000000000000000bb"),
+            input: Bytes::from_static(&[1, 2, 3, 4]),
+            value: U256::from(5),
+            gas_limit: 50_000,
+            max_fee_per_gas: 40,
+            max_priority_fee_per_gas: 8,
+            sent_at,
+            replacement: None,
+            replaced: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn manages_nonces_and_replacements() {
+        let mut queue = vec![queued(7, 1_000), queued(8, 1_100)];
+        let from = queue[0].from;
+        assert_eq!(next_nonce(7, &queue, 1, from), 9);
+        assert_eq!(next_nonce(12, &queue, 1, from), 12);
+        assert_eq!(next_nonce(7, &queue, 10, from), 7);
+
+        let speed_up = queue[0].replacement_tx(ReplacementKind::SpeedUp, 30, 2);
+        assert_eq!((speed_up.nonce, speed_up.gas_limit), (7, 50_000));
+        assert_eq!(
+            (speed_up.max_fee_per_gas, speed_up.max_priority_fee_per_gas),
+            (46, 10)
+        );
+        assert_eq!(speed_up.input, queue[0].input);
+        let cancel = queue[0].replacement_tx(ReplacementKind::Cancel, 100, 2);
+        assert_eq!(cancel.to, TxKind::Call(from));
+        assert_eq!((cancel.value, cancel.gas_limit), (U256::ZERO, 21_000));
+        assert_eq!(cancel.max_fee_per_gas, 100);
+        assert!(cancel.input.is_empty());
+
+        assert_eq!(queue[0].stuck_reason(1_100, Some(30)), None);
+        assert!(queue[0].stuck_reason(1_100, Some(41)).is_some());
+        assert!(
+            queue[0]
+                .stuck_reason(1_000 + STUCK_AFTER_SECS, None)
+                .is_some()
+        );
+
+        let check = AccountCheck {
+            chain: "mainnet".into(),
+            from,
+            mined_nonce: 8,
+            base_fee: None,
+        };
+        assert_eq!(
+            prune(&mut queue, &check),
+            [format!("{:#x}", B256::with_last_byte(7))]
+        );
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue[0].nonce, 8);
+    }
+}
@@ -0,0 +1,251 @@
+use super::{anvil::normalize_url, providers::Rpc};
+use alloy::{
+    primitives::{Address, B256, U256, b256},
+    providers::Provider,
+    sol,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+sol! {
+    #[sol(rpc)]
+    interface IPermissioned {
+        function owner() external view returns (address);
+        function getRoleMemberCount(bytes32 role) external view returns (uint256);
+        function getRoleMember(bytes32 role, uint256 index) external view returns (address);
+        function getOwners() external view returns (address[]);
+        function getThreshold() external view returns (uint256);
+    }
+}
+
+/// `bytes32(uint256(keccak256("eip1967.proxy.admin")) - 1)`.
+pub(super) const EIP1967_ADMIN_SLOT: B256 =
+    b256!("0xb53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103");
+/// Upper bound on role members read per contract, to keep one call from fanning out.
+const MAX_ROLE_MEMBERS: u64 = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PermissionRole {
+    Owner,
+    ProxyAdmin,
+    DefaultAdmin,
+    SafeOwner,
+}
+
+impl PermissionRole {
+    pub fn label(self) -> &'static str {
+        match self {
+            PermissionRole::Owner => "owner",
+            PermissionRole::ProxyAdmin => "proxy admin",
+            PermissionRole::DefaultAdmin => "DEFAULT_ADMIN_ROLE",
+            PermissionRole::SafeOwner => "Safe owner",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HolderKind {
+    Eoa,
+    Contract,
+    Safe { threshold: u64, owners: usize },
+}
+
+impl HolderKind {
+    pub fn label(self) -> String {
+        match self {
+            HolderKind::Eoa => "EOA".into(),
+            HolderKind::Contract => "contract".into(),
+            HolderKind::Safe { threshold, owners } => format!("Safe {threshold}/{owners}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PermissionEdge {
+    pub holder: Address,
+    pub target: Address,
+    pub role: PermissionRole,
+}
+
+/// Who holds which privileged role over a set of contracts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PermissionGraph {
+    pub targets: Vec<Address>,
+    pub edges: BTreeSet<PermissionEdge>,
+    pub holder_kinds: BTreeMap<Address, HolderKind>,
+}
+
+impl PermissionGraph {
+    /// Every holder with the contracts and roles it controls, most powerful first.
+    pub fn by_holder(&self) -> Vec<(Address, Vec<PermissionEdge>)> {
+        let mut grouped: BTreeMap<Address, Vec<PermissionEdge>> = BTreeMap::new();
+        for edge in &self.edges {
+            grouped.entry(edge.holder).or_default().push(*edge);
+        }
+        let mut holders: Vec<_> = grouped.into_iter().collect();
+        holders.sort_by_key(|(holder, edges)| (std::cmp::Reverse(controlled(edges)), *holder));
+        holders
+    }
+
+    pub fn kind(&self, holder: &Address) -> HolderKind {
+        self.holder_kinds
+            .get(holder)
+            .copied()
+            .unwrap_or(HolderKind::Contract)
+    }
+
+    /// EOAs that alone control more than one of the inspected contracts.
+    pub fn single_points_of_failure(&self) -> Vec<(Address, usize)> {
+        self.by_holder()
+            .into_iter()
+            .filter(|(holder, _)| self.kind(holder) == HolderKind::Eoa)
+            .map(|(holder, edges)| (holder, controlled(&edges)))
+            .filter(|(_, count)| *count > 1)
+            .collect()
+    }
+}
+
+fn controlled(edges: &[PermissionEdge]) -> usize {
+    edges
+        .iter()
+        .map(|edge| edge.target)
+        .collect::<BTreeSet<_>>()
+        .len()
+}
+
+/// Probe `owner()`, the EIP-1967 admin slot, and `DEFAULT_ADMIN_ROLE` members of
+/// every target, then classify each holder (EOA, contract, or Safe).
+pub async fn build_graph(
+    rpc: &Rpc,
+    rpc_url: &str,
+    targets: Vec<Address>,
+) -> Result<PermissionGraph, String> {
+    let url = normalize_url(rpc, rpc_url);
+    let provider = rpc.connect(&url).await.map_err(|err| format!("{err:#}"))?;
+    let mut graph = PermissionGraph {
+        targets: targets.clone(),
+        ..PermissionGraph::default()
+    };
+
+    for target in targets {
+        rpc.stats().record_api_call("rpc");
+        let contract = IPermissioned::new(target, &provider);
+        if let Ok(owner) = contract.owner().call().await {
+            add_edge(&mut graph, owner, target, PermissionRole::Owner);
+        }
+        if let Ok(slot) = provider
+            .get_storage_at(target, U256::from_be_bytes(EIP1967_ADMIN_SLOT.0))
+            .await
+        {
+            let admin = Address::from_word(B256::from(slot));
+            add_edge(&mut graph, admin, target, PermissionRole::ProxyAdmin);
+        }
+        if let Ok(count) = contract.getRoleMemberCount(B256::ZERO).call().await {
+            let count = count.saturating_to::<u64>().min(MAX_ROLE_MEMBERS);
+            for index in 0..count {
+                if let Ok(member) = contract
+                    .getRoleMember(B256::ZERO, U256::from(index))
+                    .call()
+                    .await
+                {
+                    add_edge(&mut graph, member, target, PermissionRole::DefaultAdmin);
+                }
+            }
+        }
+    }
+
+    let holders: BTreeSet<Address> = graph.edges.iter().map(|edge| edge.holder).collect();
+    for holder in holders {
+        rpc.stats().record_api_call("rpc");
+        let code = provider
+            .get_code_at(holder)
+            .await
+            .map_err(|err| format!("failed to load code for {holder}: {err}"))?;
+        let kind = if code.is_empty() {
+            HolderKind::Eoa
+        } else {
+            let safe = IPermissioned::new(holder, &provider);
+            match (
+                safe.getOwners().call().await,
+                safe.getThreshold().call().await,
+            ) {
+                (Ok(owners), Ok(threshold)) => {
+                    for owner in &owners {
+                        add_edge(&mut graph, *owner, holder, PermissionRole::SafeOwner);
+                    }
+                    HolderKind::Safe {
+                        threshold: threshold.saturating_to(),
+                        owners: owners.len(),
+                    }
+                }
+                _ => HolderKind::Contract,
+            }
+        };
+        graph.holder_kinds.insert(holder, kind);
+    }
+
+    // Safe owners were discovered in the pass above; classify the new holders too.
+    let unclassified: Vec<Address> = graph
+        .edges
+        .iter()
+        .map(|edge| edge.holder)
+        .filter(|holder| !graph.holder_kinds.contains_key(holder))
+        .collect();
+    for holder in unclassified {
+        rpc.stats().record_api_call("rpc");
+        if let Ok(code) = provider.get_code_at(holder).await {
+            let kind = if code.is_empty() {
+                HolderKind::Eoa
+            } else {
+                HolderKind::Contract
+            };
+            graph.holder_kinds.insert(holder, kind);
+        }
+    }
+
+    Ok(graph)
+}
+
+fn add_edge(graph: &mut PermissionGraph, holder: Address, target: Address, role: PermissionRole) {
+    if holder != Address::ZERO {
+        graph.edges.insert(PermissionEdge {
+            holder,
+            target,
+            role,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::address;
+
+    #[test]
+    fn flags_eoa_controlling_several_contracts() {
+        let eoa = address!("0x00000000000000000000000000000000000000e0");
+        let safe = address!("0x000000000000000000000000000000000000005a");
+        let vault = address!("0x0000000000000000000000000000000000000001");
+        let token = address!("0x0000000000000000000000000000000000000002");
+
+        let mut graph = PermissionGraph {
+            targets: vec![vault, token],
+            ..PermissionGraph::default()
+        };
+        add_edge(&mut graph, eoa, vault, PermissionRole::Owner);
+        add_edge(&mut graph, eoa, token, PermissionRole::ProxyAdmin);
+        add_edge(&mut graph, safe, token, PermissionRole::Owner);
+        add_edge(&mut graph, Address::ZERO, vault, PermissionRole::ProxyAdmin);
+        graph.holder_kinds.insert(eoa, HolderKind::Eoa);
+        graph.holder_kinds.insert(
+            safe,
+            HolderKind::Safe {
+                threshold: 3,
+                owners: 5,
+            },
+        );
+
+        assert_eq!(graph.edges.len(), 3);
+        assert_eq!(graph.by_holder()[0].0, eoa);
+        assert_eq!(graph.single_points_of_failure(), vec![(eoa, 2)]);
+    }
+}
@@ -0,0 +1,89 @@
+use std::{process::Stdio, time::Duration};
+use tokio::{io::AsyncWriteExt, process::Command, time::timeout};
+
+/// How long a piped command may run before it is killed.
+const PIPE_TIMEOUT: Duration = Duration::from_secs(30);
+/// Output lines kept for the modal; the rest is counted, not shown.
+const MAX_LINES: usize = 500;
+
+/// What a `:pipe` command printed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipeOutput {
+    pub command: String,
+    pub stdout: Vec<String>,
+    pub stderr: Vec<String>,
+    /// Lines of stdout past [`MAX_LINES`].
+    pub hidden: usize,
+    /// The exit status when the command failed.
+    pub failure: Option<String>,
+}
+
+/// Up to `max` lines of `bytes` and how many more there were.
+fn split_lines(bytes: &[u8], max: usize) -> (Vec<String>, usize) {
+    let text = String::from_utf8_lossy(bytes);
+    let total = text.lines().count();
+    let lines = text.lines().take(max).map(str::to_string).collect();
+    (lines, total.saturating_sub(max))
+}
+
+/// Run `command` through `sh -c` with `input` on stdin.
+pub async fn run(command: String, input: String) -> Result<PipeOutput, String> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(&command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|err| format!("failed to start sh: {err}"))?;
+    // Written alongside reading, so output larger than the pipe buffer
+    // cannot stall the command.
+    let writer = child.stdin.take().map(|mut stdin| {
+        tokio::spawn(async move {
+            // Commands like `head` stop reading early.
+            let _ = stdin.write_all(input.as_bytes()).await;
+        })
+    });
+    let output = timeout(PIPE_TIMEOUT, child.wait_with_output())
+        .await
+        .map_err(|_| format!("{command} timed out"))?
+        .map_err(|err| format!("{command} failed: {err}"))?;
+    if let Some(writer) = writer {
+        writer.abort();
+    }
+    let (stdout, hidden) = split_lines(&output.stdout, MAX_LINES);
+    let (stderr, _) = split_lines(&output.stderr, MAX_LINES);
+    Ok(PipeOutput {
+        command,
+        stdout,
+        stderr,
+        hidden,
+        failure: (!output.status.success()).then(|| output.status.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn pipes_input_through_the_shell() {
+        let output = run("tr a-z A-Z | head -n 2".into(), "one\ntwo\nthree\n".into())
+            .await
+            .unwrap();
+        assert_eq!(output.stdout, vec!["ONE", "TWO"]);
+        assert_eq!((output.hidden, output.failure), (0, None));
+
+        let output = run("echo oops >&2; exit 4".into(), String::new())
+            .await
+            .unwrap();
+        assert_eq!(output.stderr, vec!["oops"]);
+        assert_eq!(output.failure.as_deref(), Some("exit status: 4"));
+
+        assert_eq!(
+            split_lines(b"a\nb\nc", 2),
+            (vec!["a".into(), "b".into()], 1)
+        );
+    }
+}
@@ -0,0 +1,434 @@
+use super::{HydratedTransaction, Message, SelectedEntity, TransactionStatus};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, process::Stdio, sync::mpsc};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    process::Command,
+    runtime::Handle,
+    sync::mpsc as async_mpsc,
+    task::JoinHandle,
+};
+
+/// Sent in the `hello` event; bumped when events or actions change shape.
+pub const PROTOCOL_VERSION: u32 = 1;
+/// Lines kept per panel; longer panels are cut.
+const MAX_PANEL_LINES: usize = 200;
+/// Panels kept across subjects, oldest dropped first.
+const MAX_PANELS: usize = 50;
+/// Events waiting for a plugin to read them; newer ones are dropped while
+/// it is this far behind.
+const MAX_QUEUED_EVENTS: usize = 256;
+
+/// An external executable that receives events as JSON lines on stdin and
+/// answers with actions on stdout; stored under the `plugins.list` setting.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginConfig {
+    pub name: String,
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+}
+
+/// What the app tells plugins, one JSON object per line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum PluginEvent {
+    Hello {
+        version: u32,
+    },
+    SelectionChanged {
+        /// `address` or `transaction`.
+        kind: &'static str,
+        chain: String,
+        id: String,
+    },
+    TransactionHydrated {
+        chain: String,
+        hash: String,
+        from: Option<String>,
+        to: Option<String>,
+        value: Option<String>,
+        block: Option<u64>,
+        /// `success` or `failed`.
+        status: Option<&'static str>,
+    },
+}
+
+impl PluginEvent {
+    pub fn selection_changed(entity: &SelectedEntity) -> Self {
+        let (kind, chain, id) = match entity {
+            SelectedEntity::Address(addr) => ("address", &addr.chain, &addr.address),
+            SelectedEntity::Transaction(tx) => ("transaction", &tx.chain, &tx.hash),
+        };
+        PluginEvent::SelectionChanged {
+            kind,
+            chain: chain.clone(),
+            id: id.clone(),
+        }
+    }
+
+    pub fn transaction_hydrated(chain: &str, data: &HydratedTransaction) -> Self {
+        PluginEvent::TransactionHydrated {
+            chain: chain.to_string(),
+            hash: data.identifier.clone(),
+            from: data.from.clone(),
+            to: data.to.clone(),
+            value: data.value_formatted.clone(),
+            block: data.block_number,
+            status: data.status.map(|status| match status {
+                TransactionStatus::Success => "success",
+                TransactionStatus::Failed => "failed",
+            }),
+        }
+    }
+}
+
+/// What a plugin can ask for, one JSON object per line.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum PluginAction {
+    /// Add a note to the current investigation's scratchpad.
+    AddNote { text: String },
+    /// Show `lines` under the main view's Info or Summary tab, or on the
+    /// custom `tab` with that title, of `subject` (an address or transaction
+    /// hash) or of the current selection.
+    ShowPanel {
+        #[serde(default)]
+        subject: Option<String>,
+        #[serde(default)]
+        tab: Option<String>,
+        #[serde(default)]
+        title: Option<String>,
+        lines: Vec<String>,
+    },
+    /// Show `text` in the status line.
+    Status { text: String },
+}
+
+/// A line a plugin wrote, or its end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PluginOutput {
+    Action(PluginAction),
+    /// A line that is not a known action, with the parse error.
+    Invalid(String),
+    /// The process could not start or has ended.
+    Exited(String),
+}
+
+pub fn parse_line(line: &str) -> PluginOutput {
+    match serde_json::from_str(line) {
+        Ok(action) => PluginOutput::Action(action),
+        Err(err) => PluginOutput::Invalid(format!("{err}")),
+    }
+}
+
+/// A running plugin; dropping it kills the process.
+#[derive(Debug)]
+pub struct PluginHandle {
+    lines: async_mpsc::Sender<String>,
+    task: JoinHandle<()>,
+}
+
+impl PluginHandle {
+    pub fn send(&self, event: &PluginEvent) {
+        if let Ok(line) = serde_json::to_string(event) {
+            let _ = self.lines.try_send(line);
+        }
+    }
+}
+
+impl Drop for PluginHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Start `config`'s process, greet it, and relay what it writes as
+/// [`Message::Plugin`] until it exits or the handle is dropped.
+pub fn spawn(
+    runtime: &Handle,
+    sender: mpsc::Sender<Message>,
+    config: PluginConfig,
+) -> PluginHandle {
+    let (lines, mut outgoing) = async_mpsc::channel::<String>(MAX_QUEUED_EVENTS);
+    let _ = lines.try_send(
+        serde_json::to_string(&PluginEvent::Hello {
+            version: PROTOCOL_VERSION,
+        })
+        .unwrap_or_default(),
+    );
+    let task = runtime.spawn(async move {
+        let name = config.name.clone();
+        let report = |output| {
+            let _ = sender.send(Message::Plugin {
+                name: name.clone(),
+                output,
+            });
+        };
+        let mut child = match Command::new(&config.command)
+            .args(&config.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(err) => {
+                report(PluginOutput::Exited(format!("failed to start: {err}")));
+                return;
+            }
+        };
+        let (Some(mut stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            report(PluginOutput::Exited("no stdio pipes".into()));
+            return;
+        };
+        // Writing and reading run side by side, so a plugin that stops
+        // reading stdin only backs up its own event queue; its output still
+        // counts.
+        let writer = async move {
+            while let Some(mut line) = outgoing.recv().await {
+                line.push('\n');
+                if stdin.write_all(line.as_bytes()).await.is_err() || stdin.flush().await.is_err() {
+                    break;
+                }
+            }
+            std::future::pending::<()>().await
+        };
+        let reader = async {
+            let mut stdout = BufReader::new(stdout).lines();
+            loop {
+                match stdout.next_line().await {
+                    Ok(Some(line)) if line.trim().is_empty() => {}
+                    Ok(Some(line)) => report(parse_line(&line)),
+                    Ok(None) | Err(_) => break,
+                }
+            }
+            let status = match child.wait().await {
+                Ok(status) => format!("exited ({status})"),
+                Err(err) => format!("exited: {err}"),
+            };
+            report(PluginOutput::Exited(status));
+        };
+        tokio::select! {
+            () = writer => {}
+            () = reader => {}
+        }
+    });
+    PluginHandle { lines, task }
+}
+
+/// Content a plugin showed for an address or transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginPanel {
+    pub plugin: String,
+    /// Lowercase address or transaction hash.
+    pub subject: String,
+    /// Lowercase title of the custom tab it shows on; Info or Summary if none.
+    pub tab: Option<String>,
+    pub title: String,
+    pub lines: Vec<String>,
+}
+
+/// Configured plugins, their processes and the panels they showed.
+#[derive(Debug, Default)]
+pub struct PluginState {
+    pub configs: Vec<PluginConfig>,
+    handles: HashMap<String, PluginHandle>,
+    /// Why each stopped plugin is not running.
+    stopped: HashMap<String, String>,
+    /// Newest last.
+    panels: Vec<PluginPanel>,
+}
+
+impl PluginState {
+    pub fn new(configs: Vec<PluginConfig>) -> Self {
+        Self {
+            configs,
+            ..Self::default()
+        }
+    }
+
+    pub fn config(&self, name: &str) -> Option<&PluginConfig> {
+        self.configs.iter().find(|config| config.name == name)
+    }
+
+    /// Add or replace the plugin named like `config`.
+    pub fn set(&mut self, config: PluginConfig) {
+        self.remove(&config.name);
+        self.configs.push(config);
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<PluginConfig> {
+        self.handles.remove(name);
+        self.stopped.remove(name);
+        self.panels.retain(|panel| panel.plugin != name);
+        let index = self.configs.iter().position(|config| config.name == name)?;
+        Some(self.configs.remove(index))
+    }
+
+    pub fn started(&mut self, name: &str, handle: PluginHandle) {
+        self.stopped.remove(name);
+        self.handles.insert(name.to_string(), handle);
+    }
+
+    pub fn exited(&mut self, name: &str, reason: String) {
+        self.handles.remove(name);
+        self.stopped.insert(name.to_string(), reason);
+    }
+
+    /// `running`, or why the plugin stopped.
+    pub fn status(&self, name: &str) -> &str {
+        match self.stopped.get(name) {
+            Some(reason) => reason,
+            None if self.handles.contains_key(name) => "running",
+            None => "not started",
+        }
+    }
+
+    pub fn broadcast(&self, event: &PluginEvent) {
+        for handle in self.handles.values() {
+            handle.send(event);
+        }
+    }
+
+    /// Keep `plugin`'s panel for `subject` on `tab`, replacing the one it
+    /// showed there before.
+    pub fn show_panel(
+        &mut self,
+        plugin: &str,
+        subject: &str,
+        tab: Option<&str>,
+        title: Option<String>,
+        lines: Vec<String>,
+    ) {
+        let subject = subject.to_ascii_lowercase();
+        let tab = tab.map(str::to_ascii_lowercase);
+        self.panels
+            .retain(|panel| panel.plugin != plugin || panel.subject != subject || panel.tab != tab);
+        let mut lines = lines;
+        lines.truncate(MAX_PANEL_LINES);
+        self.panels.push(PluginPanel {
+            plugin: plugin.to_string(),
+            subject,
+            tab,
+            title: title.unwrap_or_else(|| plugin.to_string()),
+            lines,
+        });
+        if self.panels.len() > MAX_PANELS {
+            self.panels.remove(0);
+        }
+    }
+
+    /// Panels for `subject` on the custom `tab`, or on Info and Summary.
+    pub fn panels_for<'a>(
+        &'a self,
+        subject: &str,
+        tab: Option<&str>,
+    ) -> impl Iterator<Item = &'a PluginPanel> {
+        let subject = subject.to_ascii_lowercase();
+        let tab = tab.map(str::to_ascii_lowercase);
+        self.panels
+            .iter()
+            .filter(move |panel| panel.subject == subject && panel.tab == tab)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::AddressRef;
+
+    #[test]
+    fn events_and_actions_are_json_lines() {
+        let entity = SelectedEntity::Address(AddressRef {
+            label: "0xab".into(),
+            address: "0xAB".into(),
+            chain: "Mainnet".into(),
+        });
+        assert_eq!(
+            serde_json::to_string(&PluginEvent::selection_changed(&entity)).unwrap(),
+            r#"{"event":"selection_changed","kind":"address","chain":"Mainnet","id":"0xAB"}"#
+        );
+
+        assert_eq!(
+            parse_line(r#"{"action":"add_note","text":"seen before"}"#),
+            PluginOutput::Action(PluginAction::AddNote {
+                text: "seen before".into()
+            })
+        );
+        let PluginOutput::Action(PluginAction::ShowPanel { subject, lines, .. }) =
+            parse_line(r#"{"action":"show_panel","lines":["risk: low"]}"#)
+        else {
+            panic!("expected a panel");
+        };
+        assert_eq!((subject, lines), (None, vec!["risk: low".to_string()]));
+        assert!(matches!(
+            parse_line(r#"{"action":"launch"}"#),
+            PluginOutput::Invalid(_)
+        ));
+
+        let mut state = PluginState::new(vec![PluginConfig {
+            name: "risk".into(),
+            command: "risk-plugin".into(),
+            args: Vec::new(),
+        }]);
+        assert_eq!(state.status("risk"), "not started");
+        state.show_panel("risk", "0xAB", None, None, vec!["a".into()]);
+        state.show_panel("risk", "0xab", None, Some("Risk".into()), vec!["b".into()]);
+        state.show_panel("risk", "0xab", Some("Compliance"), None, vec!["c".into()]);
+        assert_eq!(state.panels_for("0xab", Some("compliance")).count(), 1);
+        let panels: Vec<_> = state.panels_for("0xAb", None).collect();
+        assert_eq!(panels.len(), 1);
+        assert_eq!(
+            (panels[0].title.as_str(), panels[0].lines[0].as_str()),
+            ("Risk", "b")
+        );
+        state.exited("risk", "exited (exit status: 1)".into());
+        assert_eq!(state.status("risk"), "exited (exit status: 1)");
+        assert!(state.remove("risk").is_some());
+        assert_eq!(state.panels_for("0xab", None).count(), 0);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn output_is_read_while_the_plugin_ignores_stdin() {
+        let (sender, messages) = mpsc::channel();
+        let handle = spawn(
+            &Handle::current(),
+            sender,
+            PluginConfig {
+                name: "deaf".into(),
+                command: "sh".into(),
+                args: vec![
+                    "-c".into(),
+                    r#"sleep 1; echo '{"action":"status","text":"still here"}'; sleep 10"#.into(),
+                ],
+            },
+        );
+        // Far more than a pipe buffer, none of it read.
+        let id = "0".repeat(4096);
+        for _ in 0..MAX_QUEUED_EVENTS * 2 {
+            handle.send(&PluginEvent::SelectionChanged {
+                kind: "address",
+                chain: "Mainnet".into(),
+                id: id.clone(),
+            });
+        }
+        let message = tokio::task::spawn_blocking(move || {
+            messages.recv_timeout(std::time::Duration::from_secs(5))
+        })
+        .await
+        .unwrap();
+        let Ok(Message::Plugin { name, output }) = message else {
+            panic!("expected the plugin's status, got {message:?}");
+        };
+        assert_eq!(name, "deaf");
+        assert_eq!(
+            output,
+            PluginOutput::Action(PluginAction::Status {
+                text: "still here".into()
+            })
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use super::{HydratedAddress, HydratedTransaction, SelectedEntity};
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant},
+};
+
+/// Age after which a prefetched favorite is hydrated again in the background.
+pub const PREFETCH_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Pause between background hydrations, leaving explorer quota for whatever
+/// the user opens in the meantime.
+pub const PREFETCH_SPACING: Duration = Duration::from_secs(3);
+
+/// A favorite hydrated ahead of being selected.
+#[derive(Debug, Clone)]
+pub enum Prefetched {
+    Address(Box<HydratedAddress>),
+    Transaction(Box<HydratedTransaction>),
+}
+
+/// How current the prefetched copy of a favorite is, shown in the sidebar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Freshness {
+    Missing,
+    Loading,
+    Fresh(Duration),
+    Stale(Duration),
+}
+
+impl Freshness {
+    /// Age of the cached copy, if there is one.
+    pub fn age_label(&self) -> Option<String> {
+        match self {
+            Freshness::Fresh(age) | Freshness::Stale(age) => Some(age_label(*age)),
+            Freshness::Missing | Freshness::Loading => None,
+        }
+    }
+}
+
+/// Compact age, e.g. `45s`, `3m`, `2h` or `5d`.
+pub fn age_label(age: Duration) -> String {
+    let seconds = age.as_secs();
+    match seconds {
+        0..60 => format!("{seconds}s"),
+        60..3600 => format!("{}m", seconds / 60),
+        3600..86_400 => format!("{}h", seconds / 3600),
+        _ => format!("{}d", seconds / 86_400),
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    data: Prefetched,
+    fetched_at: Instant,
+}
+
+/// Hydrated favorites keyed by chain and identifier, refreshed one at a time
+/// in the background so selecting a favorite shows data at once.
+#[derive(Debug, Default)]
+pub struct PrefetchCache {
+    entries: HashMap<String, Entry>,
+    /// Favorite whose background hydration is running.
+    pub in_flight: Option<String>,
+}
+
+pub fn key(entity: &SelectedEntity) -> String {
+    let (chain, identifier) = match entity {
+        SelectedEntity::Address(addr) => (&addr.chain, &addr.address),
+        SelectedEntity::Transaction(tx) => (&tx.chain, &tx.hash),
+    };
+    format!(
+        "{}:{}",
+        chain.to_ascii_lowercase(),
+        identifier.to_ascii_lowercase()
+    )
+}
+
+impl PrefetchCache {
+    pub fn insert(&mut self, entity: &SelectedEntity, data: Prefetched) {
+        self.insert_at(entity, data, Instant::now());
+    }
+
+    fn insert_at(&mut self, entity: &SelectedEntity, data: Prefetched, fetched_at: Instant) {
+        self.entries.insert(key(entity), Entry { data, fetched_at });
+    }
+
+    /// Cached copy of `entity` and when it was fetched.
+    pub fn get(&self, entity: &SelectedEntity) -> Option<(&Prefetched, Instant)> {
+        self.entries
+            .get(&key(entity))
+            .map(|entry| (&entry.data, entry.fetched_at))
+    }
+
+    pub fn freshness(&self, entity: &SelectedEntity) -> Freshness {
+        let key = key(entity);
+        if self.in_flight.as_ref() == Some(&key) {
+            return Freshness::Loading;
+        }
+        match self.entries.get(&key) {
+            None => Freshness::Missing,
+            Some(entry) => {
+                let age = entry.fetched_at.elapsed();
+                if age < PREFETCH_INTERVAL {
+                    Freshness::Fresh(age)
+                } else {
+                    Freshness::Stale(age)
+                }
+            }
+        }
+    }
+
+    /// The first favorite never fetched, else the one fetched longest ago once
+    /// it is older than [`PREFETCH_INTERVAL`]. Entries of favorites no longer
+    /// listed are dropped.
+    pub fn next_due(&mut self, favorites: &[SelectedEntity]) -> Option<SelectedEntity> {
+        let listed: HashSet<String> = favorites.iter().map(key).collect();
+        self.entries.retain(|key, _| listed.contains(key));
+        if let Some(missing) = favorites
+            .iter()
+            .find(|entity| !self.entries.contains_key(&key(entity)))
+        {
+            return Some(missing.clone());
+        }
+        favorites
+            .iter()
+            .filter_map(|entity| {
+                let fetched_at = self.entries.get(&key(entity))?.fetched_at;
+                (fetched_at.elapsed() >= PREFETCH_INTERVAL).then_some((fetched_at, entity))
+            })
+            .min_by_key(|(fetched_at, _)| *fetched_at)
+            .map(|(_, entity)| entity.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::AddressRef;
+
+    fn favorite(address: &str) -> SelectedEntity {
+        SelectedEntity::Address(AddressRef {
+            label: address.into(),
+            address: address.into(),
+            chain: "Mainnet".into(),
+        })
+    }
+
+    fn hydrated(address: &str) -> Prefetched {
+        Prefetched::Address(Box::new(HydratedAddress {
+            identifier: address.into(),
+            info: Vec::new(),
+            transactions: Vec::new(),
+            transactions_table: None,
+            internal: Vec::new(),
+            balances: Vec::new(),
+            permissions: Vec::new(),
+            overview: None,
+            contract_source: None,
+            creation: None,
+            failures: Vec::new(),
+        }))
+    }
+
+    #[test]
+    fn fetches_missing_then_oldest_stale_favorite() {
+        let (a, b, c) = (favorite("0xaa"), favorite("0xbb"), favorite("0xcc"));
+        let favorites = [a.clone(), b.clone(), c.clone()];
+        let now = Instant::now();
+        let ago = |minutes: u64| now - Duration::from_secs(minutes * 60);
+
+        let mut cache = PrefetchCache::default();
+        cache.insert_at(&a, hydrated("0xaa"), ago(1));
+        cache.insert_at(&c, hydrated("0xcc"), ago(30));
+        cache.insert_at(&favorite("0xdd"), hydrated("0xdd"), ago(1));
+        assert_eq!(cache.next_due(&favorites), Some(b.clone()));
+        assert!(cache.get(&favorite("0xdd")).is_none());
+
+        cache.insert_at(&b, hydrated("0xbb"), ago(20));
+        assert_eq!(cache.next_due(&favorites), Some(c.clone()));
+        assert!(matches!(cache.freshness(&c), Freshness::Stale(_)));
+        assert_eq!(cache.freshness(&a).age_label().as_deref(), Some("1m"));
+
+        cache.insert_at(&b, hydrated("0xbb"), now);
+        cache.insert_at(&c, hydrated("0xcc"), now);
+        assert_eq!(cache.next_due(&favorites), None);
+
+        cache.in_flight = Some(key(&favorite("0xAA")));
+        assert_eq!(cache.freshness(&a), Freshness::Loading);
+    }
+}
@@ -0,0 +1,116 @@
+use super::AddressTransactionRow;
+use std::collections::{BTreeMap, HashMap};
+
+/// Rows kept before the least recently used one is evicted; about a hundred
+/// address pages, a few megabytes even with long calldata.
+pub const PREVIEW_CACHE_CAPACITY: usize = 2_000;
+
+/// Transaction rows seen in address tables, keyed by hash, so opening one shows
+/// its summary before the full hydration lands. Bounded: a long session
+/// browsing thousands of transactions keeps only the most recently used.
+#[derive(Debug)]
+pub struct TransactionPreviewCache {
+    capacity: usize,
+    /// Bumped on every access; orders entries by recency.
+    clock: u64,
+    entries: HashMap<String, (AddressTransactionRow, u64)>,
+    /// Last access of each entry mapped back to its hash, oldest first.
+    recency: BTreeMap<u64, String>,
+}
+
+impl Default for TransactionPreviewCache {
+    fn default() -> Self {
+        Self::with_capacity(PREVIEW_CACHE_CAPACITY)
+    }
+}
+
+impl TransactionPreviewCache {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            clock: 0,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+        }
+    }
+
+    fn touch(&mut self, hash: &str) -> u64 {
+        self.clock += 1;
+        if let Some((_, used)) = self.entries.get_mut(hash) {
+            self.recency.remove(used);
+            *used = self.clock;
+            self.recency.insert(self.clock, hash.to_string());
+        }
+        self.clock
+    }
+
+    /// The cached row for `hash`, marking it as recently used.
+    pub fn get(&mut self, hash: &str) -> Option<&AddressTransactionRow> {
+        self.touch(hash);
+        self.entries.get(hash).map(|(row, _)| row)
+    }
+
+    pub fn insert(&mut self, hash: String, row: AddressTransactionRow) {
+        if let Some((_, used)) = self.entries.remove(&hash) {
+            self.recency.remove(&used);
+        }
+        while self.entries.len() >= self.capacity {
+            let Some((_, oldest)) = self.recency.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+        self.clock += 1;
+        self.recency.insert(self.clock, hash.clone());
+        self.entries.insert(hash, (row, self.clock));
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{TransactionDirection, TransactionStatus};
+    use alloy::primitives::U256;
+
+    fn row(hash: &str) -> AddressTransactionRow {
+        AddressTransactionRow {
+            hash: hash.into(),
+            from: "0xaa".into(),
+            to: None,
+            value_wei: U256::ZERO,
+            block_number: None,
+            direction: TransactionDirection::Outgoing,
+            counterparty: String::new(),
+            value_display: "0 ETH".into(),
+            status: TransactionStatus::Success,
+            calldata: None,
+            timestamp: None,
+        }
+    }
+
+    #[test]
+    fn evicts_least_recently_used_row() {
+        let mut cache = TransactionPreviewCache::with_capacity(2);
+        cache.insert("0x01".into(), row("0x01"));
+        cache.insert("0x02".into(), row("0x02"));
+        assert!(cache.get("0x01").is_some());
+
+        cache.insert("0x03".into(), row("0x03"));
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("0x02").is_none());
+        assert!(cache.get("0x01").is_some());
+
+        cache.insert("0x03".into(), row("0x03"));
+        cache.insert("0x04".into(), row("0x04"));
+        assert!(cache.get("0x01").is_none());
+        assert_eq!(cache.get("0x03").map(|row| row.hash.as_str()), Some("0x03"));
+    }
+}
@@ -0,0 +1,512 @@
+use super::{
+    AddressRef,
+    anvil::{self, AccountOverview, NodeConnector},
+    explorer::{
+        self, AddressTransaction, BeaconWithdrawal, ContractCreation, ContractSource, Explorer,
+        ExplorerSession, TransactionFetchError, TransactionListSource,
+    },
+    otterscan::OtterscanSupport,
+    rpc_health::RpcHealth,
+    stats::ApiStats,
+    ws::WsConnections,
+};
+use alloy::{primitives::Address, providers::DynProvider};
+use color_eyre::Result;
+use std::{future::Future, pin::Pin, sync::Arc, time::Duration};
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Opens a provider for one endpoint URL.
+pub trait RpcConnector: Send + Sync {
+    fn connect<'a>(&'a self, rpc_url: &'a str) -> BoxFuture<'a, Result<DynProvider>>;
+}
+
+/// How every read reaches a node, cloned into the tasks that make one.
+/// Clones share the session's endpoint health, WebSocket connections,
+/// Otterscan support and request counters.
+#[derive(Clone)]
+pub struct Rpc {
+    connector: Arc<dyn RpcConnector>,
+    health: RpcHealth,
+    ws: WsConnections,
+    otterscan: OtterscanSupport,
+    stats: ApiStats,
+}
+
+impl Rpc {
+    pub fn new(connector: Arc<dyn RpcConnector>) -> Self {
+        Self {
+            connector,
+            health: RpcHealth::default(),
+            ws: WsConnections::default(),
+            otterscan: OtterscanSupport::default(),
+            stats: ApiStats::default(),
+        }
+    }
+
+    pub async fn connect(&self, rpc_url: &str) -> Result<DynProvider> {
+        self.connector.connect(rpc_url).await
+    }
+
+    pub fn health(&self) -> &RpcHealth {
+        &self.health
+    }
+
+    pub fn ws(&self) -> &WsConnections {
+        &self.ws
+    }
+
+    pub fn otterscan(&self) -> &OtterscanSupport {
+        &self.otterscan
+    }
+
+    /// Counts every outbound call this session, RPC or not.
+    pub fn stats(&self) -> &ApiStats {
+        &self.stats
+    }
+}
+
+impl Default for Rpc {
+    fn default() -> Self {
+        let ws = WsConnections::default();
+        Self {
+            connector: Arc::new(NodeConnector(ws.clone())),
+            health: RpcHealth::default(),
+            ws,
+            otterscan: OtterscanSupport::default(),
+            stats: ApiStats::default(),
+        }
+    }
+}
+
+/// Account state for address hydration, read from the chain's endpoint.
+pub trait AccountDataProvider: Send + Sync {
+    fn chain_id<'a>(&'a self, rpc_url: &'a str) -> BoxFuture<'a, Result<u64>>;
+
+    fn account_overview<'a>(
+        &'a self,
+        rpc_url: &'a str,
+        address: Address,
+    ) -> BoxFuture<'a, Result<AccountOverview>>;
+
+    fn latest_block<'a>(&'a self, rpc_url: &'a str) -> BoxFuture<'a, Result<u64>>;
+}
+
+/// Address history and contract metadata for address hydration.
+pub trait ExplorerProvider: Send + Sync {
+    /// Up to `limit` transactions, newest first, and where they came from.
+    fn address_transactions<'a>(
+        &'a self,
+        address: &'a AddressRef,
+        api_key: Option<&'a str>,
+        rpc_url: Option<&'a str>,
+        limit: usize,
+    ) -> BoxFuture<
+        'a,
+        Result<(Vec<AddressTransaction>, TransactionListSource), TransactionFetchError>,
+    >;
+
+    fn contract_source<'a>(
+        &'a self,
+        address: &'a AddressRef,
+        api_key: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<Option<ContractSource>, TransactionFetchError>>;
+
+    fn contract_creation<'a>(
+        &'a self,
+        address: &'a AddressRef,
+        api_key: Option<&'a str>,
+        rpc_url: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<Option<ContractCreation>, TransactionFetchError>>;
+
+    /// `Ok(None)` where the chain credits no beacon withdrawals.
+    fn beacon_withdrawals<'a>(
+        &'a self,
+        address: &'a AddressRef,
+        api_key: Option<&'a str>,
+        limit: usize,
+    ) -> BoxFuture<'a, Result<Option<Vec<BeaconWithdrawal>>, TransactionFetchError>>;
+
+    /// Native token price in USD; `Ok(None)` where the explorer has no feed.
+    fn native_usd_price<'a>(
+        &'a self,
+        chain: &'a str,
+        api_key: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<Option<f64>, TransactionFetchError>>;
+
+    /// The explorer for `chain`, for lookups beyond the ones above.
+    fn explorer(
+        &self,
+        chain: &str,
+        api_key: Option<&str>,
+    ) -> Result<Explorer, TransactionFetchError>;
+
+    /// These explorers, reading address history past any response cache.
+    fn fresh(&self) -> Arc<dyn ExplorerProvider>;
+
+    /// These explorers, giving up on each request after `timeout`.
+    fn with_timeout(&self, timeout: Duration) -> Arc<dyn ExplorerProvider>;
+}
+
+/// RPC endpoints with failover.
+pub struct RpcAccountData(pub Rpc);
+
+impl AccountDataProvider for RpcAccountData {
+    fn chain_id<'a>(&'a self, rpc_url: &'a str) -> BoxFuture<'a, Result<u64>> {
+        Box::pin(anvil::endpoint_chain_id(&self.0, rpc_url))
+    }
+
+    fn account_overview<'a>(
+        &'a self,
+        rpc_url: &'a str,
+        address: Address,
+    ) -> BoxFuture<'a, Result<AccountOverview>> {
+        Box::pin(anvil::fetch_account_overview(&self.0, rpc_url, address))
+    }
+
+    fn latest_block<'a>(&'a self, rpc_url: &'a str) -> BoxFuture<'a, Result<u64>> {
+        Box::pin(anvil::fetch_latest_block(&self.0, rpc_url))
+    }
+}
+
+/// The chain's explorer from the registry, Otterscan when the node serves
+/// it, and Sourcify for source the explorer has not verified.
+pub struct ChainExplorers {
+    pub rpc: Rpc,
+    pub session: ExplorerSession,
+}
+
+impl ExplorerProvider for ChainExplorers {
+    fn address_transactions<'a>(
+        &'a self,
+        address: &'a AddressRef,
+        api_key: Option<&'a str>,
+        rpc_url: Option<&'a str>,
+        limit: usize,
+    ) -> BoxFuture<
+        'a,
+        Result<(Vec<AddressTransaction>, TransactionListSource), TransactionFetchError>,
+    > {
+        Box::pin(explorer::fetch_address_transactions(
+            &self.rpc,
+            &self.session,
+            address,
+            api_key,
+            rpc_url,
+            limit,
+        ))
+    }
+
+    fn contract_source<'a>(
+        &'a self,
+        address: &'a AddressRef,
+        api_key: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<Option<ContractSource>, TransactionFetchError>> {
+        Box::pin(explorer::fetch_contract_source(
+            &self.session,
+            address,
+            api_key,
+        ))
+    }
+
+    fn contract_creation<'a>(
+        &'a self,
+        address: &'a AddressRef,
+        api_key: Option<&'a str>,
+        rpc_url: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<Option<ContractCreation>, TransactionFetchError>> {
+        Box::pin(explorer::fetch_contract_creation(
+            &self.rpc,
+            &self.session,
+            address,
+            api_key,
+            rpc_url,
+        ))
+    }
+
+    fn beacon_withdrawals<'a>(
+        &'a self,
+        address: &'a AddressRef,
+        api_key: Option<&'a str>,
+        limit: usize,
+    ) -> BoxFuture<'a, Result<Option<Vec<BeaconWithdrawal>>, TransactionFetchError>> {
+        Box::pin(explorer::fetch_beacon_withdrawals(
+            &self.session,
+            address,
+            api_key,
+            limit,
+        ))
+    }
+
+    fn native_usd_price<'a>(
+        &'a self,
+        chain: &'a str,
+        api_key: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<Option<f64>, TransactionFetchError>> {
+        Box::pin(explorer::fetch_native_usd_price(
+            &self.session,
+            chain,
+            api_key,
+        ))
+    }
+
+    fn explorer(
+        &self,
+        chain: &str,
+        api_key: Option<&str>,
+    ) -> Result<Explorer, TransactionFetchError> {
+        Explorer::for_chain(&self.session, chain, api_key)
+    }
+
+    fn fresh(&self) -> Arc<dyn ExplorerProvider> {
+        Arc::new(ChainExplorers {
+            rpc: self.rpc.clone(),
+            session: self.session.fresh(),
+        })
+    }
+
+    fn with_timeout(&self, timeout: Duration) -> Arc<dyn ExplorerProvider> {
+        Arc::new(ChainExplorers {
+            rpc: self.rpc.clone(),
+            session: ExplorerSession {
+                timeout,
+                ..self.session.clone()
+            },
+        })
+    }
+}
+
+/// The sources every chain read goes through, held by the app and cloned
+/// into the tasks it spawns. Defaults to the live network.
+#[derive(Clone)]
+pub struct Providers {
+    pub rpc: Rpc,
+    pub accounts: Arc<dyn AccountDataProvider>,
+    pub explorer: Arc<dyn ExplorerProvider>,
+    /// What explorer requests report to, for lookups made outside
+    /// [`Providers::explorer`] and for the dashboards.
+    pub session: ExplorerSession,
+}
+
+impl Providers {
+    /// Account data and explorers reading through `rpc`.
+    pub fn over(rpc: Rpc) -> Self {
+        let session = ExplorerSession {
+            stats: rpc.stats().clone(),
+            ..Default::default()
+        };
+        Self {
+            accounts: Arc::new(RpcAccountData(rpc.clone())),
+            explorer: Arc::new(ChainExplorers {
+                rpc: rpc.clone(),
+                session: session.clone(),
+            }),
+            rpc,
+            session,
+        }
+    }
+
+    /// These providers, giving up on each explorer request after `timeout`.
+    pub fn with_explorer_timeout(&self, timeout: Duration) -> Self {
+        Self {
+            explorer: self.explorer.with_timeout(timeout),
+            session: ExplorerSession {
+                timeout,
+                ..self.session.clone()
+            },
+            ..self.clone()
+        }
+    }
+
+    /// These providers, with address history read past the explorer cache
+    /// for loads that exist to see new activity.
+    pub fn fresh_history(&self) -> Self {
+        Self {
+            explorer: self.explorer.fresh(),
+            session: self.session.fresh(),
+            ..self.clone()
+        }
+    }
+}
+
+impl Default for Providers {
+    fn default() -> Self {
+        Self::over(Rpc::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{App, SecretsState, storage_layout::StorageLayout};
+    use alloy::primitives::U256;
+    use color_eyre::eyre::eyre;
+
+    struct FixedAccount;
+
+    impl AccountDataProvider for FixedAccount {
+        fn chain_id<'a>(&'a self, _rpc_url: &'a str) -> BoxFuture<'a, Result<u64>> {
+            Box::pin(async { Ok(10) })
+        }
+
+        fn account_overview<'a>(
+            &'a self,
+            _rpc_url: &'a str,
+            _address: Address,
+        ) -> BoxFuture<'a, Result<AccountOverview>> {
+            Box::pin(async {
+                Ok(AccountOverview {
+                    latest_block: 500,
+                    balance_wei: U256::from(2_000_000_000_000_000_000u128),
+                    transaction_count: 3,
+                    pending_transaction_count: 3,
+                    queued_nonces: None,
+                    is_contract: true,
+                    delegate: None,
+                    code: vec![0x60, 0x80].into(),
+                })
+            })
+        }
+
+        fn latest_block<'a>(&'a self, _rpc_url: &'a str) -> BoxFuture<'a, Result<u64>> {
+            Box::pin(async { Err(eyre!("unused")) })
+        }
+    }
+
+    struct FixedExplorer;
+
+    impl ExplorerProvider for FixedExplorer {
+        fn address_transactions<'a>(
+            &'a self,
+            address: &'a AddressRef,
+            _api_key: Option<&'a str>,
+            _rpc_url: Option<&'a str>,
+            _limit: usize,
+        ) -> BoxFuture<
+            'a,
+            Result<(Vec<AddressTransaction>, TransactionListSource), TransactionFetchError>,
+        > {
+            Box::pin(async move {
+                let transaction = AddressTransaction {
+                    hash: format!("0x{}", "11".repeat(32)),
+                    block_number: 499,
+                    from: "0x00000000000000000000000000000000000000aa".into(),
+                    to: Some(address.address.to_ascii_lowercase()),
+                    value_wei: U256::from(1_000u64),
+                    is_error: false,
+                    input: None,
+                    timestamp: Some(1_700_000_000),
+                    gas_used: Some(21_000),
+                };
+                let source = TransactionListSource {
+                    label: "Fixture",
+                    api_version: "test",
+                };
+                Ok((vec![transaction], source))
+            })
+        }
+
+        fn contract_source<'a>(
+            &'a self,
+            _address: &'a AddressRef,
+            _api_key: Option<&'a str>,
+        ) -> BoxFuture<'a, Result<Option<ContractSource>, TransactionFetchError>> {
+            Box::pin(async {
+                Ok(Some(ContractSource {
+                    name: "Vault".into(),
+                    compiler: "v0.8.26".into(),
+                    abi: "[]".into(),
+                    source: String::new(),
+                    source_label: "Fixture",
+                    match_type: explorer::SourceMatch::Verified,
+                    storage_layout: None::<StorageLayout>,
+                }))
+            })
+        }
+
+        fn contract_creation<'a>(
+            &'a self,
+            _address: &'a AddressRef,
+            _api_key: Option<&'a str>,
+            _rpc_url: Option<&'a str>,
+        ) -> BoxFuture<'a, Result<Option<ContractCreation>, TransactionFetchError>> {
+            Box::pin(async { Ok(None) })
+        }
+
+        fn beacon_withdrawals<'a>(
+            &'a self,
+            _address: &'a AddressRef,
+            _api_key: Option<&'a str>,
+            _limit: usize,
+        ) -> BoxFuture<'a, Result<Option<Vec<BeaconWithdrawal>>, TransactionFetchError>> {
+            Box::pin(async { Err(TransactionFetchError::MissingApiKey) })
+        }
+
+        fn native_usd_price<'a>(
+            &'a self,
+            _chain: &'a str,
+            _api_key: Option<&'a str>,
+        ) -> BoxFuture<'a, Result<Option<f64>, TransactionFetchError>> {
+            Box::pin(async { Ok(None) })
+        }
+
+        fn explorer(
+            &self,
+            _chain: &str,
+            _api_key: Option<&str>,
+        ) -> Result<Explorer, TransactionFetchError> {
+            Err(TransactionFetchError::MissingApiKey)
+        }
+
+        fn fresh(&self) -> Arc<dyn ExplorerProvider> {
+            Arc::new(FixedExplorer)
+        }
+
+        fn with_timeout(&self, _timeout: Duration) -> Arc<dyn ExplorerProvider> {
+            Arc::new(FixedExplorer)
+        }
+    }
+
+    #[tokio::test]
+    async fn hydrates_an_address_from_plugged_in_providers() {
+        let providers = Providers {
+            rpc: Rpc::default(),
+            accounts: Arc::new(FixedAccount),
+            explorer: Arc::new(FixedExplorer),
+            session: Default::default(),
+        };
+        let secrets = SecretsState {
+            anvil_rpc_url: Some("http://fixture.invalid".into()),
+            ..Default::default()
+        };
+        let addr = AddressRef {
+            label: "Vault".into(),
+            address: "0x00000000000000000000000000000000000000bb".into(),
+            chain: "Optimism".into(),
+        };
+
+        let hydrated =
+            App::hydrate_address(addr, secrets, None, Default::default(), providers).await;
+
+        assert_eq!(
+            hydrated.overview.as_ref().map(|o| o.latest_block),
+            Some(500)
+        );
+        assert!(hydrated.failures.is_empty());
+        assert!(
+            hydrated
+                .info
+                .iter()
+                .any(|line| line.starts_with("Verified contract: Vault"))
+        );
+        assert!(
+            hydrated
+                .info
+                .iter()
+                .any(|line| line.contains("Deployer unknown"))
+        );
+        let table = hydrated.transactions_table.expect("transactions table");
+        assert_eq!(table.source_label, "Fixture");
+        assert_eq!(table.rows.len(), 1);
+    }
+}
@@ -0,0 +1,201 @@
+use base64::{Engine, engine::general_purpose::STANDARD};
+use qrcode::{Color, EcLevel, QrCode};
+
+/// Light modules around the code; scanners want some, four is the standard
+/// but two is plenty against a terminal's own border.
+const QUIET_ZONE: usize = 2;
+/// Pixels per module in the images sent to graphics-capable terminals.
+const MODULE_PIXELS: usize = 8;
+/// Kitty takes base64 payloads in chunks of at most this many bytes.
+const KITTY_CHUNK: usize = 4096;
+
+/// A QR code as a square grid of modules, quiet zone included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QrMatrix {
+    pub size: usize,
+    dark: Vec<bool>,
+}
+
+impl QrMatrix {
+    pub fn encode(text: &str) -> Result<Self, String> {
+        let code = QrCode::with_error_correction_level(text, EcLevel::M)
+            .map_err(|err| format!("Cannot encode QR code: {err}"))?;
+        let width = code.width();
+        let modules = code.to_colors();
+        let size = width + 2 * QUIET_ZONE;
+        let mut dark = vec![false; size * size];
+        for y in 0..width {
+            for x in 0..width {
+                dark[(y + QUIET_ZONE) * size + x + QUIET_ZONE] =
+                    modules[y * width + x] == Color::Dark;
+            }
+        }
+        Ok(Self { size, dark })
+    }
+
+    fn is_dark(&self, x: usize, y: usize) -> bool {
+        x < self.size && y < self.size && self.dark[y * self.size + x]
+    }
+
+    /// Two modules per character cell using half blocks, meant to be drawn
+    /// dark on light; the result is `size` columns by `size / 2` rows, rounded up.
+    pub fn half_block_lines(&self) -> Vec<String> {
+        (0..self.size)
+            .step_by(2)
+            .map(|y| {
+                (0..self.size)
+                    .map(|x| match (self.is_dark(x, y), self.is_dark(x, y + 1)) {
+                        (true, true) => '█',
+                        (true, false) => '▀',
+                        (false, true) => '▄',
+                        (false, false) => ' ',
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Black on white RGB pixels, `MODULE_PIXELS` to a module, top row first.
+    fn rgb(&self) -> (usize, Vec<u8>) {
+        let pixels = self.size * MODULE_PIXELS;
+        let mut data = Vec::with_capacity(pixels * pixels * 3);
+        for y in 0..pixels {
+            for x in 0..pixels {
+                let shade = if self.is_dark(x / MODULE_PIXELS, y / MODULE_PIXELS) {
+                    0
+                } else {
+                    255
+                };
+                data.extend([shade; 3]);
+            }
+        }
+        (pixels, data)
+    }
+
+    /// Kitty graphics protocol escape drawing the code over `columns` ×
+    /// `rows` cells from the cursor. Raw RGB, sent in chunks.
+    pub fn kitty_sequence(&self, columns: u16, rows: u16) -> String {
+        let (pixels, data) = self.rgb();
+        let payload = STANDARD.encode(data);
+        let chunks: Vec<&[u8]> = payload.as_bytes().chunks(KITTY_CHUNK).collect();
+        let mut out = String::new();
+        for (index, chunk) in chunks.iter().enumerate() {
+            let more = u8::from(index + 1 < chunks.len());
+            let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+            if index == 0 {
+                out.push_str(&format!(
+                    "\x1b_Ga=T,f=24,s={pixels},v={pixels},c={columns},r={rows},C=1,q=2,m={more};{chunk}\x1b\\"
+                ));
+            } else {
+                out.push_str(&format!("\x1b_Gm={more};{chunk}\x1b\\"));
+            }
+        }
+        out
+    }
+
+    /// iTerm2 inline image escape (`OSC 1337 File=`) with a BMP, which
+    /// macOS decodes without an image encoder on our side.
+    pub fn iterm_sequence(&self, columns: u16, rows: u16) -> String {
+        let bmp = self.bmp();
+        format!(
+            "\x1b]1337;File=inline=1;size={};width={columns};height={rows};preserveAspectRatio=1:{}\x07",
+            bmp.len(),
+            STANDARD.encode(bmp)
+        )
+    }
+
+    /// Uncompressed 24-bit BMP: rows bottom-up, BGR, padded to four bytes.
+    fn bmp(&self) -> Vec<u8> {
+        let (pixels, rgb) = self.rgb();
+        let row_bytes = (pixels * 3).next_multiple_of(4);
+        let image_bytes = row_bytes * pixels;
+        let mut out = Vec::with_capacity(54 + image_bytes);
+        out.extend(b"BM");
+        out.extend(((54 + image_bytes) as u32).to_le_bytes());
+        out.extend([0; 4]);
+        out.extend(54u32.to_le_bytes());
+        out.extend(40u32.to_le_bytes());
+        out.extend((pixels as i32).to_le_bytes());
+        out.extend((pixels as i32).to_le_bytes());
+        out.extend(1u16.to_le_bytes());
+        out.extend(24u16.to_le_bytes());
+        out.extend([0; 4]);
+        out.extend((image_bytes as u32).to_le_bytes());
+        out.extend([0; 16]);
+        for row in rgb.chunks(pixels * 3).rev() {
+            for pixel in row.chunks(3) {
+                out.extend([pixel[2], pixel[1], pixel[0]]);
+            }
+            out.resize(out.len() + row_bytes - pixels * 3, 0);
+        }
+        out
+    }
+}
+
+/// Image protocol the terminal speaks, if any; the half-block rendering is
+/// used everywhere else.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Graphics {
+    Kitty,
+    Iterm,
+}
+
+impl Graphics {
+    /// Guessed from the environment: terminals answer protocol queries
+    /// through stdin, which the event loop owns.
+    pub fn detect() -> Option<Self> {
+        let var = |name: &str| std::env::var(name).unwrap_or_default();
+        // tmux and screen swallow the escapes unless passthrough is set up.
+        if var("TERM").starts_with("screen") || var("TERM").starts_with("tmux") {
+            return None;
+        }
+        if std::env::var_os("KITTY_WINDOW_ID").is_some()
+            || var("TERM") == "xterm-kitty"
+            || matches!(var("TERM_PROGRAM").as_str(), "ghostty" | "WezTerm")
+        {
+            return Some(Graphics::Kitty);
+        }
+        (var("TERM_PROGRAM") == "iTerm.app").then_some(Graphics::Iterm)
+    }
+
+    pub fn sequence(self, matrix: &QrMatrix, columns: u16, rows: u16) -> String {
+        match self {
+            Graphics::Kitty => matrix.kitty_sequence(columns, rows),
+            Graphics::Iterm => matrix.iterm_sequence(columns, rows),
+        }
+    }
+
+    /// Escape removing what [`Graphics::sequence`] drew, if the protocol has
+    /// one; iTerm2 images go away when the cells are redrawn.
+    pub fn clear_sequence(self) -> Option<&'static str> {
+        match self {
+            Graphics::Kitty => Some("\x1b_Ga=d,q=2\x1b\\"),
+            Graphics::Iterm => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn addresses_encode_to_a_square_grid() {
+        let matrix = QrMatrix::encode("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap();
+        // Version 3 at level M holds the 42 characters: 29 modules a side.
+        assert_eq!(matrix.size, 29 + 2 * QUIET_ZONE);
+        let lines = matrix.half_block_lines();
+        assert_eq!(lines.len(), matrix.size.div_ceil(2));
+        assert!(lines.iter().all(|line| line.chars().count() == matrix.size));
+        // The finder pattern's top edge sits just inside the quiet zone.
+        assert!(lines[1].chars().nth(QUIET_ZONE).is_some_and(|c| c != ' '));
+
+        let kitty = matrix.kitty_sequence(33, 17);
+        assert!(kitty.starts_with("\x1b_Ga=T,f=24,s=264,v=264,c=33,r=17"));
+        assert!(kitty.contains("\x1b_Gm=0;"));
+
+        let bmp = matrix.bmp();
+        assert_eq!(&bmp[..2], b"BM");
+        assert_eq!(bmp.len(), 54 + (264 * 3) * 264);
+    }
+}
@@ -0,0 +1,187 @@
+use super::explorer::TransactionFetchError;
+use reqwest::header::HeaderMap;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
+
+/// Etherscan free-tier limits that usage is measured against.
+pub const PER_SECOND_LIMIT: usize = 5;
+pub const DAILY_LIMIT: usize = 100_000;
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Etherscan usage this session, shared by every clone.
+#[derive(Debug, Clone, Default)]
+pub struct Quota(Arc<Mutex<QuotaLog>>);
+
+#[derive(Debug, Default)]
+struct QuotaLog {
+    /// Requests sent, oldest first, pruned to the last day.
+    calls: VecDeque<SystemTime>,
+    last_throttle: Option<(SystemTime, String)>,
+    last_failure: Option<(SystemTime, String, Option<&'static str>)>,
+    reported: Option<(SystemTime, ReportedQuota)>,
+}
+
+/// Allowance the API stated in its rate-limit headers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReportedQuota {
+    pub remaining: u64,
+    pub limit: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotaUsage {
+    pub last_second: usize,
+    pub last_day: usize,
+    /// How long ago the API last answered with a rate-limit error, and its message.
+    pub last_throttle: Option<(Duration, String)>,
+    /// How long ago the API last failed a request, its error and guidance.
+    pub last_failure: Option<(Duration, String, Option<&'static str>)>,
+    /// The latest allowance from response headers, and how long ago it came.
+    pub reported: Option<(Duration, ReportedQuota)>,
+}
+
+impl QuotaUsage {
+    /// Requests left today and out of how many: the API's own figures when
+    /// its headers carried them, else the free tier minus this session's
+    /// calls. The flag tells which.
+    pub fn remaining_today(&self) -> (u64, u64, bool) {
+        let daily = DAILY_LIMIT as u64;
+        match self.reported {
+            Some((_, reported)) => (reported.remaining, reported.limit.unwrap_or(daily), true),
+            None => (daily.saturating_sub(self.last_day as u64), daily, false),
+        }
+    }
+}
+
+impl Quota {
+    pub fn record_call(&self) {
+        self.record_call_at(SystemTime::now());
+    }
+
+    fn record_call_at(&self, now: SystemTime) {
+        if let Ok(mut log) = self.0.lock() {
+            log.calls.push_back(now);
+            while log
+                .calls
+                .front()
+                .is_some_and(|first| now.duration_since(*first).unwrap_or_default() > DAY)
+            {
+                log.calls.pop_front();
+            }
+        }
+    }
+
+    /// Remember a rate-limit response so the dashboard can show when it happened.
+    pub fn record_throttle(&self, message: &str) {
+        if let Ok(mut log) = self.0.lock() {
+            log.last_throttle = Some((SystemTime::now(), message.to_string()));
+        }
+    }
+
+    /// Remember a failed request, its rate limit included, for the diagnostics
+    /// view.
+    pub fn record_failure(&self, err: &TransactionFetchError) {
+        if let TransactionFetchError::RateLimited { message, .. } = err {
+            self.record_throttle(message);
+        }
+        if let Ok(mut log) = self.0.lock() {
+            log.last_failure = Some((SystemTime::now(), err.to_string(), err.guidance()));
+        }
+    }
+
+    /// Keep the allowance a response states in `X-RateLimit-Remaining` /
+    /// `X-RateLimit-Limit` (or the unprefixed `RateLimit-*` names), when present.
+    pub fn record_headers(&self, headers: &HeaderMap) {
+        let read = |names: [&str; 2]| {
+            names.iter().find_map(|name| {
+                headers
+                    .get(*name)?
+                    .to_str()
+                    .ok()?
+                    .trim()
+                    .parse::<u64>()
+                    .ok()
+            })
+        };
+        let Some(remaining) = read(["x-ratelimit-remaining", "ratelimit-remaining"]) else {
+            return;
+        };
+        let limit = read(["x-ratelimit-limit", "ratelimit-limit"]);
+        if let Ok(mut log) = self.0.lock() {
+            log.reported = Some((SystemTime::now(), ReportedQuota { remaining, limit }));
+        }
+    }
+
+    pub fn usage(&self) -> QuotaUsage {
+        let now = SystemTime::now();
+        let ago = |at: &SystemTime| now.duration_since(*at).unwrap_or_default();
+        let Ok(log) = self.0.lock() else {
+            return QuotaUsage {
+                last_second: 0,
+                last_day: 0,
+                last_throttle: None,
+                last_failure: None,
+                reported: None,
+            };
+        };
+        let within = |window: Duration| log.calls.iter().filter(|at| ago(at) <= window).count();
+        QuotaUsage {
+            last_second: within(Duration::from_secs(1)),
+            last_day: within(DAY),
+            last_throttle: log
+                .last_throttle
+                .as_ref()
+                .map(|(at, message)| (ago(at), message.clone())),
+            last_failure: log
+                .last_failure
+                .as_ref()
+                .map(|(at, message, guidance)| (ago(at), message.clone(), *guidance)),
+            reported: log.reported.as_ref().map(|(at, quota)| (ago(at), *quota)),
+        }
+    }
+}
+
+pub fn is_rate_limit_message(message: &str) -> bool {
+    message.to_ascii_lowercase().contains("rate limit")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_recent_calls_and_detects_throttling() {
+        let quota = Quota::default();
+        let now = SystemTime::now();
+        quota.record_call_at(now - Duration::from_secs(2 * 24 * 60 * 60));
+        quota.record_call_at(now - Duration::from_secs(60));
+        quota.record_call_at(now);
+        let usage = quota.usage();
+        assert_eq!(usage.last_second, 1);
+        assert_eq!(usage.last_day, 2);
+        assert_eq!(quota.0.lock().unwrap().calls.len(), 2);
+        assert_eq!(Quota::default().usage().last_day, 0);
+
+        assert!(is_rate_limit_message(
+            "Max calls per sec rate limit reached (5/sec)"
+        ));
+        assert!(!is_rate_limit_message("Invalid API Key"));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", "99120".parse().unwrap());
+        headers.insert("x-ratelimit-limit", "100000".parse().unwrap());
+        quota.record_headers(&headers);
+        quota.record_failure(&TransactionFetchError::from_api_message(
+            "Max daily rate limit reached",
+        ));
+        let failed = quota.usage();
+        assert_eq!(failed.remaining_today(), (99_120, 100_000, true));
+        let (_, message, guidance) = failed.last_failure.unwrap();
+        assert_eq!(message, "rate limited: Max daily rate limit reached");
+        assert!(guidance.is_some_and(|guidance| guidance.contains("daily quota")));
+        assert!(failed.last_throttle.is_some());
+    }
+}
@@ -0,0 +1,210 @@
+use super::{anvil::normalize_url, providers::Rpc};
+use alloy::{
+    consensus::{
+        Transaction as ConsensusTransaction, TxEnvelope, Typed2718,
+        transaction::{SignerRecoverable, to_eip155_value},
+    },
+    eips::{Decodable2718, Encodable2718},
+    network::TransactionResponse,
+    primitives::{Address, B256, Bytes, hex},
+    providers::Provider,
+};
+
+/// Labelled transaction fields in encoding order, then the signature.
+pub type RawFields = Vec<(&'static str, String)>;
+
+/// Where the signed bytes came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RawSource {
+    /// `eth_getRawTransactionByHash`.
+    Node,
+    /// The node lacks the raw method; the RPC transaction was re-encoded.
+    Reencoded,
+}
+
+/// Signed bytes of a transaction, its decoded fields and the recovered signer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawTransaction {
+    pub bytes: Bytes,
+    pub source: RawSource,
+    pub fields: RawFields,
+    pub signer: Result<Address, String>,
+    /// `from` as reported by `eth_getTransactionByHash`.
+    pub reported_from: Option<Address>,
+}
+
+impl RawTransaction {
+    /// `None` until both addresses are known.
+    pub fn signer_matches(&self) -> Option<bool> {
+        Some(*self.signer.as_ref().ok()? == self.reported_from?)
+    }
+}
+
+/// State of the Raw tab for the selected transaction.
+#[derive(Debug, Default)]
+pub struct RawView {
+    /// Transaction the tab was loaded for.
+    pub hash: Option<String>,
+    pub loading: bool,
+    pub result: Option<Result<RawTransaction, String>>,
+}
+
+impl RawView {
+    pub fn needs_load(&self, hash: &str) -> bool {
+        self.hash.as_deref() != Some(hash)
+    }
+}
+
+fn hex_bytes(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+/// Decode EIP-2718 `bytes` into labelled fields and recover the signer.
+pub fn decode(bytes: &[u8]) -> Result<(RawFields, Result<Address, String>), String> {
+    let envelope = TxEnvelope::decode_2718(&mut &bytes[..])
+        .map_err(|err| format!("not a valid signed transaction: {err}"))?;
+    let mut fields = vec![("type", envelope.ty().to_string())];
+    if let Some(chain_id) = envelope.chain_id() {
+        fields.push(("chainId", chain_id.to_string()));
+    }
+    fields.push(("nonce", envelope.nonce().to_string()));
+    match envelope.gas_price() {
+        Some(gas_price) => fields.push(("gasPrice", gas_price.to_string())),
+        None => {
+            if let Some(tip) = envelope.max_priority_fee_per_gas() {
+                fields.push(("maxPriorityFeePerGas", tip.to_string()));
+            }
+            fields.push(("maxFeePerGas", envelope.max_fee_per_gas().to_string()));
+        }
+    }
+    fields.push(("gasLimit", envelope.gas_limit().to_string()));
+    fields.push((
+        "to",
+        envelope
+            .to()
+            .map(|to| to.to_string())
+            .unwrap_or_else(|| "(contract creation)".into()),
+    ));
+    fields.push(("value", envelope.value().to_string()));
+    let input = envelope.input();
+    fields.push((
+        "input",
+        if input.len() > 36 {
+            format!("{}… ({} bytes)", hex_bytes(&input[..36]), input.len())
+        } else {
+            hex_bytes(input)
+        },
+    ));
+    if let Some(list) = envelope.access_list() {
+        fields.push(("accessList", format!("{} entries", list.len())));
+    }
+    if let Some(blob_fee) = envelope.max_fee_per_blob_gas() {
+        fields.push(("maxFeePerBlobGas", blob_fee.to_string()));
+    }
+    if let Some(hashes) = envelope.blob_versioned_hashes() {
+        fields.push(("blobVersionedHashes", format!("{} hashes", hashes.len())));
+    }
+    if let Some(authorizations) = envelope.authorization_list() {
+        fields.push((
+            "authorizationList",
+            format!("{} authorizations", authorizations.len()),
+        ));
+    }
+
+    let signature = envelope.signature();
+    if envelope.is_legacy() {
+        let v = to_eip155_value(signature.v(), envelope.chain_id());
+        fields.push(("v", v.to_string()));
+    } else {
+        fields.push(("yParity", u8::from(signature.v()).to_string()));
+    }
+    fields.push(("r", format!("{:#066x}", signature.r())));
+    fields.push(("s", format!("{:#066x}", signature.s())));
+
+    let signer = envelope
+        .recover_signer()
+        .map_err(|err| format!("signer recovery failed: {err}"));
+    Ok((fields, signer))
+}
+
+/// Load the signed bytes of `hash`, falling back to re-encoding the RPC
+/// transaction when the node does not serve `eth_getRawTransactionByHash`.
+pub async fn fetch_raw(rpc: Rpc, rpc_url: String, hash: String) -> Result<RawTransaction, String> {
+    let tx_hash: B256 = hash
+        .parse()
+        .map_err(|_| "invalid transaction hash".to_string())?;
+    let provider = rpc
+        .connect(&normalize_url(&rpc, &rpc_url))
+        .await
+        .map_err(|err| format!("{err:#}"))?;
+
+    rpc.stats().record_api_call("rpc");
+    let transaction = provider
+        .get_transaction_by_hash(tx_hash)
+        .await
+        .map_err(|err| format!("failed to load transaction: {err}"))?;
+    rpc.stats().record_api_call("rpc");
+    let raw: Result<Option<Bytes>, _> = provider
+        .raw_request("eth_getRawTransactionByHash".into(), (tx_hash,))
+        .await;
+    let (bytes, source) = match (raw, transaction.as_ref()) {
+        (Ok(Some(bytes)), _) if !bytes.is_empty() => (bytes, RawSource::Node),
+        (_, Some(transaction)) => (
+            transaction.inner.inner().encoded_2718().into(),
+            RawSource::Reencoded,
+        ),
+        (Err(err), None) => return Err(format!("eth_getRawTransactionByHash failed: {err}")),
+        (Ok(_), None) => return Err("transaction not found on this RPC".into()),
+    };
+
+    let (fields, signer) = decode(&bytes)?;
+    Ok(RawTransaction {
+        bytes,
+        source,
+        fields,
+        signer,
+        reported_from: transaction.map(|transaction| transaction.from()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::{
+        consensus::{SignableTransaction, TxLegacy},
+        primitives::{TxKind, U256},
+        signers::{SignerSync, local::PrivateKeySigner},
+    };
+
+    #[test]
+    fn decodes_fields_and_recovers_signer() {
+        let signer = PrivateKeySigner::random();
+        let tx = TxLegacy {
+            chain_id: Some(1),
+            nonce: 7,
+            gas_price: 1_000_000_000,
+            gas_limit: 21_000,
+            to: TxKind::Call(Address::repeat_byte(0xbb)),
+            value: U256::from(5),
+            input: Bytes::new(),
+        };
+        let signature = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+        let bytes = TxEnvelope::from(tx.into_signed(signature)).encoded_2718();
+
+        let (fields, recovered) = decode(&bytes).unwrap();
+        assert_eq!(recovered, Ok(signer.address()));
+        let field = |name| {
+            fields
+                .iter()
+                .find(|(key, _)| *key == name)
+                .unwrap()
+                .1
+                .clone()
+        };
+        assert_eq!(field("nonce"), "7");
+        assert_eq!(field("gasPrice"), "1000000000");
+        // EIP-155: v = parity + chain_id * 2 + 35.
+        assert!(["37", "38"].contains(&field("v").as_str()));
+        assert!(decode(&[0x02, 0x01]).is_err());
+    }
+}
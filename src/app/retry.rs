@@ -0,0 +1,232 @@
+use super::explorer::TransactionFetchError;
+use alloy::transports::{RpcError, TransportErrorKind};
+use color_eyre::Report;
+use std::{
+    collections::hash_map::RandomState,
+    fmt,
+    future::Future,
+    hash::{BuildHasher, Hasher},
+    io::ErrorKind,
+    time::Duration,
+};
+use tokio::time::sleep;
+
+/// How often and how patiently a failed call is tried again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts in total, the first included.
+    pub max_attempts: u32,
+    /// Delay before the second attempt; doubled for each one after.
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+/// Policy shared by RPC and explorer requests: enough to ride out a DNS blip
+/// or a dropped connection without holding a tab for long.
+pub const NETWORK: RetryPolicy = RetryPolicy {
+    max_attempts: 3,
+    base_delay: Duration::from_millis(250),
+    max_delay: Duration::from_secs(2),
+};
+
+/// Whether an error may go away on its own: timeouts, connection failures
+/// and 5xx answers. Anything else fails on the first attempt, rate limits
+/// included: retrying only spends more of the quota, so they go to the user.
+pub trait Transient {
+    fn is_transient(&self) -> bool;
+}
+
+/// A request that ran out of time, always worth another attempt.
+#[derive(Debug)]
+pub struct TimedOut(pub String);
+
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for TimedOut {}
+
+impl Transient for reqwest::Error {
+    fn is_transient(&self) -> bool {
+        if self.is_timeout() || self.is_connect() {
+            return true;
+        }
+        match self.status() {
+            Some(status) => status.is_server_error(),
+            // Failed before any answer, e.g. the connection dropped mid-request.
+            None => self.is_request(),
+        }
+    }
+}
+
+impl Transient for std::io::Error {
+    fn is_transient(&self) -> bool {
+        matches!(
+            self.kind(),
+            ErrorKind::ConnectionRefused
+                | ErrorKind::ConnectionReset
+                | ErrorKind::ConnectionAborted
+                | ErrorKind::NotConnected
+                | ErrorKind::TimedOut
+                | ErrorKind::Interrupted
+        )
+    }
+}
+
+impl Transient for TransactionFetchError {
+    fn is_transient(&self) -> bool {
+        match self {
+            TransactionFetchError::Http(err) => err.is_transient(),
+            TransactionFetchError::RateLimited { .. }
+            | TransactionFetchError::MissingApiKey
+            | TransactionFetchError::InvalidApiKey(_)
+            | TransactionFetchError::DeprecatedEndpoint(_)
+            | TransactionFetchError::ProOnly(_)
+            | TransactionFetchError::Api(_)
+            | TransactionFetchError::UnsupportedChain(_)
+            | TransactionFetchError::Parse(_) => false,
+        }
+    }
+}
+
+impl Transient for Report {
+    fn is_transient(&self) -> bool {
+        self.chain().any(|cause| {
+            if cause.is::<TimedOut>() {
+                return true;
+            }
+            if let Some(err) = cause.downcast_ref::<std::io::Error>() {
+                return err.is_transient();
+            }
+            if let Some(err) = cause.downcast_ref::<reqwest::Error>() {
+                return err.is_transient();
+            }
+            match cause.downcast_ref::<RpcError<TransportErrorKind>>() {
+                Some(RpcError::Transport(kind)) => match kind {
+                    TransportErrorKind::HttpError(err) => err.status >= 500,
+                    TransportErrorKind::MissingBatchResponse(_)
+                    | TransportErrorKind::BackendGone => true,
+                    _ => false,
+                },
+                Some(RpcError::NullResp) => true,
+                _ => false,
+            }
+        })
+    }
+}
+
+impl RetryPolicy {
+    /// Wait before attempt `attempt + 1`: exponential in `attempt`, capped,
+    /// then half of it spread by `jitter` (0..1) so concurrent retries against
+    /// one host do not line up.
+    pub fn delay(&self, attempt: u32, jitter: f64) -> Duration {
+        let exponential = self
+            .base_delay
+            .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+            .min(self.max_delay);
+        exponential / 2 + exponential.mul_f64(jitter.clamp(0.0, 1.0) / 2.0)
+    }
+
+    /// Run `attempt` until it succeeds, fails with a permanent error, or the
+    /// attempts run out; the last error is returned.
+    pub async fn run<T, E, F, Fut>(&self, mut attempt: F) -> Result<T, E>
+    where
+        E: Transient,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut tries = 1;
+        loop {
+            match attempt().await {
+                Err(err) if tries < self.max_attempts && err.is_transient() => {
+                    sleep(self.delay(tries, jitter())).await;
+                    tries += 1;
+                }
+                outcome => return outcome,
+            }
+        }
+    }
+}
+
+/// A fresh value in 0..1; `RandomState` is seeded randomly per instance.
+fn jitter() -> f64 {
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use color_eyre::eyre::eyre;
+    use std::cell::Cell;
+
+    #[tokio::test]
+    async fn retries_transient_errors_only() {
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(4),
+        };
+        assert_eq!(policy.delay(1, 0.0), Duration::from_micros(500));
+        assert_eq!(policy.delay(2, 1.0), Duration::from_millis(2));
+        assert_eq!(policy.delay(9, 1.0), Duration::from_millis(4));
+
+        let calls = Cell::new(0);
+        let outcome: Result<u32, Report> = policy
+            .run(|| async {
+                calls.set(calls.get() + 1);
+                if calls.get() < 3 {
+                    Err(Report::new(TimedOut("timed out".into())))
+                } else {
+                    Ok(7)
+                }
+            })
+            .await;
+        assert_eq!(outcome.unwrap(), 7);
+        assert_eq!(calls.get(), 3);
+
+        calls.set(0);
+        let outcome: Result<u32, Report> = policy
+            .run(|| async {
+                calls.set(calls.get() + 1);
+                Err(eyre!("execution reverted"))
+            })
+            .await;
+        assert!(outcome.is_err());
+        assert_eq!(calls.get(), 1);
+
+        let throttled = TransactionFetchError::from_api_message("Max rate limit reached");
+        assert!(!throttled.is_transient());
+        assert!(!TransactionFetchError::from_api_message("Invalid API Key").is_transient());
+        assert!(
+            !TransactionFetchError::from_api_message("Max daily rate limit reached").is_transient()
+        );
+
+        let rpc = |kind| Report::new(RpcError::<TransportErrorKind>::Transport(kind));
+        let http = |status| {
+            rpc(TransportErrorKind::HttpError(
+                alloy::transports::HttpError {
+                    status,
+                    body: String::new(),
+                },
+            ))
+        };
+        assert!(!http(429).is_transient());
+        assert!(http(503).is_transient());
+        assert!(rpc(TransportErrorKind::BackendGone).is_transient());
+        let payload = alloy::rpc::json_rpc::ErrorPayload {
+            code: 429,
+            message: "rate limit exceeded".into(),
+            data: None,
+        };
+        assert!(!Report::new(RpcError::<TransportErrorKind>::ErrorResp(payload)).is_transient());
+
+        let io = |kind| Report::new(std::io::Error::from(kind));
+        assert!(io(std::io::ErrorKind::ConnectionReset).is_transient());
+        assert!(io(std::io::ErrorKind::Interrupted).is_transient());
+        assert!(!io(std::io::ErrorKind::NotFound).is_transient());
+        assert!(!io(std::io::ErrorKind::PermissionDenied).is_transient());
+    }
+}
@@ -0,0 +1,99 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// Latest observation of one RPC endpoint this session.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EndpointHealth {
+    pub latency: Option<Duration>,
+    pub last_error: Option<String>,
+    /// Failures since the last success; non-zero endpoints are tried last.
+    pub consecutive_failures: u32,
+}
+
+impl EndpointHealth {
+    pub fn is_failing(&self) -> bool {
+        self.consecutive_failures > 0
+    }
+}
+
+/// Every endpoint's [`EndpointHealth`] this session, shared by all clones.
+#[derive(Debug, Clone, Default)]
+pub struct RpcHealth(Arc<Mutex<BTreeMap<String, EndpointHealth>>>);
+
+/// Split a configured RPC value into its endpoints, in priority order.
+/// Several URLs may be given separated by commas or whitespace.
+pub fn endpoints(value: &str) -> Vec<String> {
+    value
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+impl RpcHealth {
+    /// Endpoints in the order to try them: configured order, with endpoints
+    /// that failed their last request moved behind the healthy ones.
+    pub fn attempt_order(&self, value: &str) -> Vec<String> {
+        let mut urls = endpoints(value);
+        if let Ok(health) = self.0.lock() {
+            urls.sort_by_key(|url| health.get(url).is_some_and(EndpointHealth::is_failing));
+        }
+        urls
+    }
+
+    pub fn record_success(&self, url: &str, latency: Duration) {
+        if let Ok(mut health) = self.0.lock() {
+            health.insert(
+                url.to_string(),
+                EndpointHealth {
+                    latency: Some(latency),
+                    last_error: None,
+                    consecutive_failures: 0,
+                },
+            );
+        }
+    }
+
+    pub fn record_failure(&self, url: &str, error: String) {
+        if let Ok(mut health) = self.0.lock() {
+            let entry = health.entry(url.to_string()).or_default();
+            entry.last_error = Some(error);
+            entry.consecutive_failures += 1;
+        }
+    }
+
+    pub fn get(&self, url: &str) -> Option<EndpointHealth> {
+        self.0.lock().ok()?.get(url).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failing_endpoints_are_tried_last() {
+        let value = "https://a.test, https://b.test\nhttps://c.test";
+        assert_eq!(
+            endpoints(value),
+            vec!["https://a.test", "https://b.test", "https://c.test"]
+        );
+
+        let health = RpcHealth::default();
+        health.record_failure("https://a.test", "connection refused".into());
+        health.record_success("https://b.test", Duration::from_millis(40));
+        assert_eq!(
+            health.attempt_order(value),
+            vec!["https://b.test", "https://c.test", "https://a.test"]
+        );
+
+        health.record_success("https://a.test", Duration::from_millis(90));
+        assert_eq!(health.attempt_order(value)[0], "https://a.test");
+        assert!(!health.get("https://a.test").unwrap().is_failing());
+        assert!(RpcHealth::default().get("https://a.test").is_none());
+    }
+}
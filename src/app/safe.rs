@@ -0,0 +1,336 @@
+use super::{chains::resolve_chain, stats::ApiStats};
+use alloy::primitives::{Address, B256, Bytes, U256};
+use serde::Deserialize;
+use serde_json::Value;
+use std::time::Duration;
+
+/// Queued transactions fetched per request; the service pages beyond this.
+const PAGE_LIMIT: usize = 100;
+
+/// A Safe's signing policy and the multisig transactions waiting on it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SafeQueue {
+    pub threshold: u64,
+    pub owners: Vec<Address>,
+    /// Nonce of the next transaction the Safe will execute.
+    pub nonce: u64,
+    /// Unexecuted transactions from `nonce` on, lowest nonce first.
+    pub transactions: Vec<QueuedTransaction>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedTransaction {
+    pub safe_tx_hash: B256,
+    pub nonce: u64,
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
+    pub delegate_call: bool,
+    pub confirmations: Vec<Address>,
+    pub confirmations_required: u64,
+    pub submitted: String,
+    /// The call decoded by the service, one line per call (a MultiSend batch
+    /// lists its inner calls after the outer one).
+    pub decoded: Vec<String>,
+}
+
+impl QueuedTransaction {
+    pub fn is_executable(&self) -> bool {
+        self.confirmations.len() as u64 >= self.confirmations_required
+    }
+}
+
+impl SafeQueue {
+    /// Owners that have not confirmed `transaction` yet.
+    pub fn missing_owners(&self, transaction: &QueuedTransaction) -> Vec<Address> {
+        self.owners
+            .iter()
+            .filter(|owner| !transaction.confirmations.contains(owner))
+            .copied()
+            .collect()
+    }
+
+    /// Whether another queued transaction competes for the same nonce;
+    /// only one of them can ever execute.
+    pub fn has_conflict(&self, transaction: &QueuedTransaction) -> bool {
+        self.transactions
+            .iter()
+            .filter(|other| other.nonce == transaction.nonce)
+            .count()
+            > 1
+    }
+}
+
+#[derive(Deserialize)]
+struct SafeInfo {
+    nonce: Value,
+    threshold: Value,
+    owners: Vec<Address>,
+}
+
+#[derive(Deserialize)]
+struct Page {
+    results: Vec<ServiceTransaction>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ServiceTransaction {
+    safe_tx_hash: B256,
+    nonce: Value,
+    to: Address,
+    #[serde(default)]
+    value: Option<String>,
+    #[serde(default)]
+    data: Option<Bytes>,
+    #[serde(default)]
+    operation: u8,
+    #[serde(default)]
+    confirmations_required: Option<u64>,
+    #[serde(default)]
+    confirmations: Option<Vec<Confirmation>>,
+    #[serde(default)]
+    submission_date: Option<String>,
+    #[serde(default)]
+    data_decoded: Option<DataDecoded>,
+}
+
+#[derive(Deserialize)]
+struct Confirmation {
+    owner: Address,
+}
+
+#[derive(Deserialize)]
+struct DataDecoded {
+    method: String,
+    #[serde(default)]
+    parameters: Option<Vec<Parameter>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Parameter {
+    name: String,
+    value: Value,
+    /// Inner calls of a MultiSend `transactions` parameter.
+    #[serde(default)]
+    value_decoded: Option<Value>,
+}
+
+/// The service writes numbers either as JSON numbers or as strings.
+fn number(value: &Value) -> Option<u64> {
+    value
+        .as_u64()
+        .or_else(|| value.as_str().and_then(|text| text.parse().ok()))
+}
+
+fn plain(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// `method(name: value, …)`, followed by one line per MultiSend inner call.
+fn describe(decoded: &DataDecoded) -> Vec<String> {
+    let parameters = decoded.parameters.as_deref().unwrap_or_default();
+    let args: Vec<String> = parameters
+        .iter()
+        .map(|param| match &param.value_decoded {
+            Some(Value::Array(calls)) => format!("{}: {} calls", param.name, calls.len()),
+            _ => format!("{}: {}", param.name, plain(&param.value)),
+        })
+        .collect();
+    let mut lines = vec![format!("{}({})", decoded.method, args.join(", "))];
+    let inner = parameters
+        .iter()
+        .filter_map(|param| param.value_decoded.as_ref()?.as_array())
+        .flatten();
+    for (index, call) in inner.enumerate() {
+        let to = call.get("to").map(plain).unwrap_or_default();
+        let call_line = call
+            .get("dataDecoded")
+            .cloned()
+            .and_then(|decoded| serde_json::from_value::<DataDecoded>(decoded).ok())
+            .map(|decoded| describe(&decoded).join(" "))
+            .unwrap_or_else(|| {
+                let value = call.get("value").map(plain).unwrap_or_default();
+                format!("value {value} wei")
+            });
+        lines.push(format!("{}. {to} {call_line}", index + 1));
+    }
+    lines
+}
+
+fn queued(transaction: ServiceTransaction, threshold: u64) -> QueuedTransaction {
+    QueuedTransaction {
+        safe_tx_hash: transaction.safe_tx_hash,
+        nonce: number(&transaction.nonce).unwrap_or_default(),
+        to: transaction.to,
+        value: transaction
+            .value
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default(),
+        data: transaction.data.unwrap_or_default(),
+        delegate_call: transaction.operation == 1,
+        confirmations: transaction
+            .confirmations
+            .unwrap_or_default()
+            .into_iter()
+            .map(|confirmation| confirmation.owner)
+            .collect(),
+        confirmations_required: transaction.confirmations_required.unwrap_or(threshold),
+        submitted: transaction.submission_date.unwrap_or_default(),
+        decoded: transaction
+            .data_decoded
+            .as_ref()
+            .map(describe)
+            .unwrap_or_default(),
+    }
+}
+
+fn parse_queue(info: &str, page: &str) -> Result<SafeQueue, String> {
+    let info: SafeInfo = serde_json::from_str(info)
+        .map_err(|err| format!("unexpected Safe info response: {err}"))?;
+    let page: Page = serde_json::from_str(page)
+        .map_err(|err| format!("unexpected multisig transactions response: {err}"))?;
+    let threshold = number(&info.threshold).unwrap_or_default();
+    let nonce = number(&info.nonce).unwrap_or_default();
+    let mut transactions: Vec<QueuedTransaction> = page
+        .results
+        .into_iter()
+        .map(|transaction| queued(transaction, threshold))
+        .filter(|transaction| transaction.nonce >= nonce)
+        .collect();
+    transactions.sort_by_key(|transaction| transaction.nonce);
+    Ok(SafeQueue {
+        threshold,
+        owners: info.owners,
+        nonce,
+        transactions,
+    })
+}
+
+async fn get(stats: &ApiStats, client: &reqwest::Client, url: &str) -> Result<String, String> {
+    stats.record_api_call("safe");
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|err| format!("Safe Transaction Service request failed: {err}"))?;
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Err("not a Safe known to the Safe Transaction Service".into());
+    }
+    if !status.is_success() {
+        return Err(format!("Safe Transaction Service returned {status}"));
+    }
+    response
+        .text()
+        .await
+        .map_err(|err| format!("Safe Transaction Service response unreadable: {err}"))
+}
+
+/// Load the Safe's owners, threshold and nonce, then its unexecuted
+/// multisig transactions from that nonce on.
+pub async fn fetch_queue(
+    stats: &ApiStats,
+    chain: String,
+    safe: Address,
+) -> Result<SafeQueue, String> {
+    let base = resolve_chain(&chain)
+        .and_then(|info| info.safe_service)
+        .ok_or_else(|| format!("No Safe Transaction Service for {chain}"))?;
+    let client = reqwest::Client::builder()
+        .user_agent("evm-tui/0.1.0")
+        .timeout(Duration::from_secs(15))
+        .build()
+        .map_err(|err| err.to_string())?;
+    let safe = safe.to_checksum(None);
+    let info = get(stats, &client, &format!("{base}/api/v1/safes/{safe}/")).await?;
+    let nonce = serde_json::from_str::<SafeInfo>(&info)
+        .ok()
+        .and_then(|info| number(&info.nonce))
+        .unwrap_or_default();
+    let page = get(
+        stats,
+        &client,
+        &format!(
+            "{base}/api/v1/safes/{safe}/multisig-transactions/\
+             ?executed=false&nonce__gte={nonce}&ordering=nonce&limit={PAGE_LIMIT}"
+        ),
+    )
+    .await?;
+    parse_queue(&info, &page)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_queue_and_decodes_calls() {
+        let info = r#"{"address": "0x0000000000000000000000000000000000005afe",
+            "nonce": "7", "threshold": 2,
+            "owners": ["0x0000000000000000000000000000000000000001",
+                       "0x0000000000000000000000000000000000000002",
+                       "0x0000000000000000000000000000000000000003"]}"#;
+        let page = r#"{"count": 3, "results": [
+            {"safeTxHash": "0x1111111111111111111111111111111111111111111111111111111111111111",
+             "nonce": 8, "to": "0x0000000000000000000000000000000000000010", "value": "0",
+             "data": null, "operation": 0, "confirmationsRequired": 2, "confirmations": [],
+             "dataDecoded": null},
+            {"safeTxHash": "0x2222222222222222222222222222222222222222222222222222222222222222",
+             "nonce": 7, "to": "0x0000000000000000000000000000000000000020", "value": "0",
+             "data": "0xa9059cbb", "operation": 0, "confirmationsRequired": 2,
+             "confirmations": [{"owner": "0x0000000000000000000000000000000000000002"}],
+             "submissionDate": "2024-03-13T13:55:00Z",
+             "dataDecoded": {"method": "transfer", "parameters": [
+                {"name": "to", "type": "address", "value": "0x0000000000000000000000000000000000000009"},
+                {"name": "value", "type": "uint256", "value": "1000"}]}},
+            {"safeTxHash": "0x3333333333333333333333333333333333333333333333333333333333333333",
+             "nonce": 8, "to": "0x0000000000000000000000000000000000000030", "value": "0",
+             "operation": 1, "confirmations": [
+                {"owner": "0x0000000000000000000000000000000000000001"},
+                {"owner": "0x0000000000000000000000000000000000000003"}],
+             "dataDecoded": {"method": "multiSend", "parameters": [
+                {"name": "transactions", "type": "bytes", "value": "0x00",
+                 "valueDecoded": [
+                    {"operation": 0, "to": "0x0000000000000000000000000000000000000040",
+                     "value": "5", "data": null, "dataDecoded": null},
+                    {"operation": 0, "to": "0x0000000000000000000000000000000000000050",
+                     "value": "0", "dataDecoded": {"method": "approve", "parameters": [
+                        {"name": "spender", "type": "address", "value": "0x0000000000000000000000000000000000000060"}]}}
+                 ]}]}}
+        ]}"#;
+        let queue = parse_queue(info, page).unwrap();
+        assert_eq!((queue.nonce, queue.threshold), (7, 2));
+        let nonces: Vec<u64> = queue.transactions.iter().map(|tx| tx.nonce).collect();
+        assert_eq!(nonces, [7, 8, 8]);
+
+        let transfer = &queue.transactions[0];
+        assert_eq!(
+            transfer.decoded,
+            ["transfer(to: 0x0000000000000000000000000000000000000009, value: 1000)"]
+        );
+        assert!(!transfer.is_executable());
+        assert_eq!(queue.missing_owners(transfer).len(), 2);
+        assert!(!queue.has_conflict(transfer));
+
+        let batch = queue
+            .transactions
+            .iter()
+            .find(|tx| tx.delegate_call)
+            .unwrap();
+        assert!(batch.is_executable());
+        assert!(queue.has_conflict(batch));
+        assert_eq!(
+            batch.decoded,
+            [
+                "multiSend(transactions: 2 calls)",
+                "1. 0x0000000000000000000000000000000000000040 value 5 wei",
+                "2. 0x0000000000000000000000000000000000000050 approve(spender: 0x0000000000000000000000000000000000000060)",
+            ]
+        );
+    }
+}
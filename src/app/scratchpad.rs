@@ -0,0 +1,116 @@
+use crate::{storage::ScratchNote, ui::util::format_timestamp};
+use color_eyre::{Result, eyre::WrapErr};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Investigation pins go to until another is picked with `:scratchpad <name>`.
+pub const DEFAULT_INVESTIGATION: &str = "default";
+const MAX_NAME_LEN: usize = 40;
+
+/// Checked investigation name: letters, digits, `-`, `_` and `.`, so it can
+/// sit in a storage key and a file name as is.
+pub fn investigation_name(input: &str) -> Result<String, String> {
+    let name = input.trim();
+    if name.is_empty() || name.len() > MAX_NAME_LEN {
+        return Err(format!(
+            "Investigation names are 1 to {MAX_NAME_LEN} characters"
+        ));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err("Investigation names use letters, digits, '-', '_' and '.'".to_string());
+    }
+    Ok(name.to_string())
+}
+
+/// A note for `text` pinned now from `source`.
+pub fn note(investigation: &str, text: &str, source: String) -> ScratchNote {
+    let pinned_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default();
+    ScratchNote {
+        investigation: investigation.to_string(),
+        pinned_at,
+        text: text.trim_end().to_string(),
+        source,
+    }
+}
+
+/// `exports/scratchpad_<investigation>.md`, used when no path is given.
+pub fn default_path(investigation: &str) -> PathBuf {
+    Path::new("exports").join(format!("scratchpad_{investigation}.md"))
+}
+
+/// The notes as a Markdown list, each with where and when it was pinned.
+/// Multi-line pins become fenced blocks.
+pub fn to_markdown(investigation: &str, notes: &[ScratchNote]) -> String {
+    let mut out = format!("# Scratchpad: {investigation}\n\n");
+    for note in notes {
+        let when = format_timestamp(note.pinned_at / 1000);
+        if note.text.contains('\n') {
+            out.push_str(&format!("- {} ({when})\n\n  ```\n", note.source));
+            for line in note.text.lines() {
+                out.push_str(&format!("  {line}\n"));
+            }
+            out.push_str("  ```\n");
+        } else {
+            out.push_str(&format!("- `{}` — {} ({when})\n", note.text, note.source));
+        }
+    }
+    out
+}
+
+/// Write the notes as Markdown to `path`, creating parent directories as needed.
+pub fn write_markdown(path: &Path, investigation: &str, notes: &[ScratchNote]) -> Result<()> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent).wrap_err("failed to create export directory")?;
+    }
+    fs::write(path, to_markdown(investigation, notes))
+        .wrap_err_with(|| format!("failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_checked_and_notes_render_as_markdown() {
+        assert_eq!(investigation_name(" exploit-1 ").unwrap(), "exploit-1");
+        assert!(investigation_name("a::b").is_err());
+        assert!(investigation_name("").is_err());
+
+        let notes = [
+            ScratchNote {
+                investigation: "exploit".into(),
+                pinned_at: 1_663_224_179_000,
+                text: "0xbeef".into(),
+                source: "Mainnet 0xdead…beef • Transactions".into(),
+            },
+            ScratchNote {
+                investigation: "exploit".into(),
+                pinned_at: 1_663_224_179_500,
+                text: "slot 0x0\nslot 0x1".into(),
+                source: "Mainnet • Storage".into(),
+            },
+        ];
+        assert_eq!(
+            to_markdown("exploit", &notes),
+            "# Scratchpad: exploit\n\n\
+             - `0xbeef` — Mainnet 0xdead…beef • Transactions (2022-09-15 06:42 UTC)\n\
+             - Mainnet • Storage (2022-09-15 06:42 UTC)\n\n  ```\n  slot 0x0\n  slot 0x1\n  ```\n"
+        );
+        assert_eq!(
+            default_path("exploit"),
+            Path::new("exports/scratchpad_exploit.md")
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use super::watchlist::unix_now;
+use color_eyre::{Result, eyre::WrapErr};
+use ratatui::{
+    buffer::{Buffer, Cell},
+    style::{Color, Modifier},
+    text::Span,
+};
+use std::{
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+
+const EXPORT_DIR: &str = "exports";
+
+/// How a screenshot is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// The characters only.
+    Text,
+    /// The characters with their colors and modifiers as ANSI escapes, for
+    /// `cat` in a terminal or pasting into tools that render them.
+    Ansi,
+}
+
+impl Format {
+    /// ANSI for `.ans` files, plain text otherwise.
+    pub fn for_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("ans") => Format::Ansi,
+            _ => Format::Text,
+        }
+    }
+
+    /// `exports/screen_<unix time>.txt` or `.ans`.
+    pub fn default_path(self) -> PathBuf {
+        let secs = unix_now();
+        let ext = match self {
+            Format::Text => "txt",
+            Format::Ansi => "ans",
+        };
+        Path::new(EXPORT_DIR).join(format!("screen_{secs}.{ext}"))
+    }
+}
+
+/// Each row's symbols, skipping the cells a wide character covers.
+fn rows(buffer: &Buffer) -> impl Iterator<Item = Vec<&Cell>> {
+    let area = buffer.area;
+    (area.top()..area.bottom()).map(move |y| {
+        let mut cells = Vec::new();
+        let mut x = area.left();
+        while x < area.right() {
+            let cell = &buffer[(x, y)];
+            cells.push(cell);
+            x += (Span::raw(cell.symbol()).width() as u16).max(1);
+        }
+        cells
+    })
+}
+
+pub fn to_text(buffer: &Buffer) -> String {
+    let mut text = String::new();
+    for row in rows(buffer) {
+        let line: String = row.iter().map(|cell| cell.symbol()).collect();
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+fn color_code(color: Color, background: bool) -> Option<String> {
+    let base = match color {
+        Color::Reset => return None,
+        Color::Black => 30,
+        Color::Red => 31,
+        Color::Green => 32,
+        Color::Yellow => 33,
+        Color::Blue => 34,
+        Color::Magenta => 35,
+        Color::Cyan => 36,
+        Color::Gray => 37,
+        Color::DarkGray => 90,
+        Color::LightRed => 91,
+        Color::LightGreen => 92,
+        Color::LightYellow => 93,
+        Color::LightBlue => 94,
+        Color::LightMagenta => 95,
+        Color::LightCyan => 96,
+        Color::White => 97,
+        Color::Indexed(index) => {
+            return Some(format!("{};5;{index}", if background { 48 } else { 38 }));
+        }
+        Color::Rgb(r, g, b) => {
+            return Some(format!(
+                "{};2;{r};{g};{b}",
+                if background { 48 } else { 38 }
+            ));
+        }
+    };
+    Some((if background { base + 10 } else { base }).to_string())
+}
+
+/// SGR parameters that set `cell`'s style from a reset.
+fn style_codes(cell: &Cell) -> Vec<String> {
+    const MODIFIERS: [(Modifier, u8); 9] = [
+        (Modifier::BOLD, 1),
+        (Modifier::DIM, 2),
+        (Modifier::ITALIC, 3),
+        (Modifier::UNDERLINED, 4),
+        (Modifier::SLOW_BLINK, 5),
+        (Modifier::RAPID_BLINK, 6),
+        (Modifier::REVERSED, 7),
+        (Modifier::HIDDEN, 8),
+        (Modifier::CROSSED_OUT, 9),
+    ];
+    let mut codes: Vec<String> = MODIFIERS
+        .iter()
+        .filter(|(modifier, _)| cell.modifier.contains(*modifier))
+        .map(|(_, code)| code.to_string())
+        .collect();
+    codes.extend(color_code(cell.fg, false));
+    codes.extend(color_code(cell.bg, true));
+    codes
+}
+
+/// The buffer with an escape sequence wherever the style changes, reset at
+/// the end of every row.
+pub fn to_ansi(buffer: &Buffer) -> String {
+    let mut text = String::new();
+    for row in rows(buffer) {
+        let mut current = Vec::new();
+        for cell in row {
+            let codes = style_codes(cell);
+            if codes != current {
+                text.push_str("\x1b[0m");
+                if !codes.is_empty() {
+                    let _ = write!(text, "\x1b[{}m", codes.join(";"));
+                }
+                current = codes;
+            }
+            text.push_str(cell.symbol());
+        }
+        text.push_str("\x1b[0m\n");
+    }
+    text
+}
+
+/// Write `buffer` to `path` in `format`, creating parent directories as
+/// needed.
+pub fn write(buffer: &Buffer, path: &Path, format: Format) -> Result<()> {
+    let contents = match format {
+        Format::Text => to_text(buffer),
+        Format::Ansi => to_ansi(buffer),
+    };
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent).wrap_err("failed to create export directory")?;
+    }
+    fs::write(path, contents).wrap_err_with(|| format!("failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{layout::Rect, style::Style};
+
+    #[test]
+    fn writes_text_and_ansi() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 6, 2));
+        buffer.set_string(0, 0, "ok", Style::default().fg(Color::Green));
+        buffer.set_string(0, 1, "日本", Style::default().add_modifier(Modifier::BOLD));
+
+        assert_eq!(to_text(&buffer), "ok\n日本\n");
+        assert_eq!(
+            to_ansi(&buffer),
+            "\x1b[0m\x1b[32mok\x1b[0m    \x1b[0m\n\x1b[0m\x1b[1m日本\x1b[0m  \x1b[0m\n"
+        );
+        assert_eq!(Format::for_path(Path::new("bug.ANS")), Format::Ansi);
+        assert_eq!(Format::for_path(Path::new("bug.txt")), Format::Text);
+    }
+}
@@ -0,0 +1,233 @@
+use super::{
+    AddressRef, Message, SecretsState, SelectedEntity, TransactionRef,
+    blocks::{self, HashLookup},
+    ens,
+    explorer::{ExplorerBackend, TransactionFetchError},
+    providers::Providers,
+};
+use crate::ui::util::short_hex;
+use alloy::{eips::BlockId, primitives::Address};
+use tokio::time::{Duration, sleep};
+
+/// Chain searches resolve on; queries carry no chain of their own.
+pub const SEARCH_CHAIN: &str = "Mainnet";
+
+/// Pause before a search's first network request, so one superseded within
+/// this window is aborted before it costs a call.
+const DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// What a search query names, before anything is looked up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Query {
+    Address(AddressRef),
+    /// A 32-byte hash: a transaction, or possibly a block.
+    Hash(TransactionRef),
+    Block(BlockId),
+    Ens(String),
+}
+
+/// Parse user-typed hex as an address. All-lowercase or all-uppercase input
+/// carries no checksum; mixed case must be a valid EIP-55 checksum, since a
+/// mismatch usually means a mistyped character.
+pub fn parse_address_input(value: &str) -> Result<Address, String> {
+    let value = value.trim();
+    let digits = value.strip_prefix("0x").unwrap_or(value);
+    let has_lower = digits.chars().any(|c| c.is_ascii_lowercase());
+    let has_upper = digits.chars().any(|c| c.is_ascii_uppercase());
+    let prefixed = format!("0x{digits}");
+    if has_lower && has_upper {
+        Address::parse_checksummed(&prefixed, None).map_err(|_| {
+            "Address checksum (EIP-55) does not match; check for a typo or paste it all lowercase"
+                .to_string()
+        })
+    } else {
+        prefixed
+            .parse()
+            .map_err(|_| "Input is not a valid address".to_string())
+    }
+}
+
+/// Classify `input` without touching the network.
+pub fn parse_query(input: &str) -> Result<Query, String> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err("Empty query".into());
+    }
+    if let Some(block) = blocks::parse_block_query(trimmed) {
+        return Ok(Query::Block(block));
+    }
+    let hex = trimmed.strip_prefix("0x").unwrap_or(trimmed);
+    let is_hex = hex.chars().all(|c| c.is_ascii_hexdigit());
+    if hex.len() == 40 && is_hex {
+        let address = parse_address_input(hex)?.to_checksum(None);
+        return Ok(Query::Address(AddressRef {
+            label: format!("Address {}", short_hex(&address)),
+            address,
+            chain: SEARCH_CHAIN.into(),
+        }));
+    }
+    if hex.len() == 64 && is_hex {
+        let hash = format!("0x{hex}");
+        return Ok(Query::Hash(TransactionRef {
+            label: format!("Txn {}", short_hex(&hash)),
+            hash,
+            chain: SEARCH_CHAIN.into(),
+        }));
+    }
+    if ens::is_ens_name(trimmed) {
+        return Ok(Query::Ens(trimmed.to_lowercase()));
+    }
+    Err("Input could not be decoded as an address, transaction, block or ENS name".into())
+}
+
+fn completed(query: String, entity: SelectedEntity) -> Message {
+    Message::SearchCompleted { query, entity }
+}
+
+fn failed(query: String, error: String) -> Message {
+    Message::SearchFailed { query, error }
+}
+
+/// Resolve a submitted query: ENS names through the registry, blocks and
+/// hashes against the chain's RPC, and hashes against the explorer when no
+/// RPC is configured. Errors name the stage that failed.
+pub async fn resolve(providers: Providers, query: String, secrets: SecretsState) -> Message {
+    let parsed = match parse_query(&query) {
+        Ok(parsed) => parsed,
+        Err(error) => return failed(query, error),
+    };
+    if !matches!(parsed, Query::Address(_)) {
+        sleep(DEBOUNCE).await;
+    }
+    let rpc_url = secrets.rpc_url(SEARCH_CHAIN).map(str::to_string);
+    match parsed {
+        Query::Address(addr) => completed(query, SelectedEntity::Address(addr)),
+        Query::Ens(name) => {
+            let Some(rpc_url) = rpc_url else {
+                return failed(
+                    query,
+                    format!("ENS: configure an RPC endpoint for {SEARCH_CHAIN} to resolve names"),
+                );
+            };
+            match ens::resolve(&providers.rpc, &rpc_url, &name).await {
+                Ok(Some(address)) => {
+                    let entity = SelectedEntity::Address(AddressRef {
+                        label: name,
+                        address: address.to_checksum(None),
+                        chain: SEARCH_CHAIN.into(),
+                    });
+                    completed(query, entity)
+                }
+                Ok(None) => failed(query, format!("ENS: {name} does not resolve to an address")),
+                Err(err) => failed(query, format!("ENS: {err:#}")),
+            }
+        }
+        Query::Block(block) => {
+            let Some(rpc_url) = rpc_url else {
+                return failed(
+                    query,
+                    format!("RPC: configure an endpoint for {SEARCH_CHAIN} to open blocks"),
+                );
+            };
+            match blocks::load_block(providers.rpc.clone(), rpc_url, block).await {
+                Ok(Some(summary)) => Message::BlockFound {
+                    chain: SEARCH_CHAIN.into(),
+                    block: Box::new(summary),
+                },
+                Ok(None) => failed(query, format!("RPC: block {block} not found")),
+                Err(error) => failed(query, format!("RPC: {error}")),
+            }
+        }
+        Query::Hash(tx) => match rpc_url
+            .as_ref()
+            .and_then(|url| Some((url, tx.hash.parse().ok()?)))
+        {
+            // A 32-byte hash may name a block; ask the node which.
+            Some((rpc_url, hash)) => {
+                match blocks::lookup_hash(providers.rpc.clone(), rpc_url.clone(), hash).await {
+                    HashLookup::Transaction => completed(query, SelectedEntity::Transaction(tx)),
+                    HashLookup::Block(block) => Message::BlockFound {
+                        chain: tx.chain,
+                        block,
+                    },
+                    HashLookup::Ambiguous(reason) => Message::HashAmbiguous { tx, reason },
+                }
+            }
+            None => check_with_explorer(&providers, query, tx, &secrets).await,
+        },
+    }
+}
+
+/// Without an RPC, ask the chain's explorer whether the transaction exists.
+/// When the explorer cannot answer (no key, unsupported lookup) the hash
+/// opens unverified, as the transaction view reports its own load errors.
+async fn check_with_explorer(
+    providers: &Providers,
+    query: String,
+    tx: TransactionRef,
+    secrets: &SecretsState,
+) -> Message {
+    let Ok(explorer) = providers
+        .explorer
+        .explorer(&tx.chain, secrets.explorer_api_key(&tx.chain))
+    else {
+        return completed(query, SelectedEntity::Transaction(tx));
+    };
+    match explorer.transaction_exists(&tx.hash).await {
+        Ok(true) => completed(query, SelectedEntity::Transaction(tx)),
+        Ok(false) => failed(
+            query,
+            format!(
+                "Explorer: {} has no transaction {}",
+                explorer.source().label,
+                short_hex(&tx.hash)
+            ),
+        ),
+        Err(err @ (TransactionFetchError::Http(_) | TransactionFetchError::Parse(_))) => {
+            failed(query, format!("Explorer: {err}"))
+        }
+        Err(_) => completed(query, SelectedEntity::Transaction(tx)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::eips::BlockNumberOrTag;
+
+    #[test]
+    fn mixed_case_addresses_must_be_checksummed() {
+        let checksummed = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+        assert_eq!(
+            parse_address_input(&checksummed.to_ascii_lowercase())
+                .unwrap()
+                .to_checksum(None),
+            checksummed
+        );
+        assert!(parse_address_input(checksummed).is_ok());
+        assert!(parse_address_input("0xF39Fd6e51aad88F6F4ce6aB8827279cffFb92266").is_err());
+    }
+
+    #[test]
+    fn classifies_queries_before_any_lookup() {
+        let address = "0xd8da6bf26964af9d7eed9e10e34f5d4e3c2b1a0f";
+        assert!(matches!(
+            parse_query(address),
+            Ok(Query::Address(addr)) if addr.address.eq_ignore_ascii_case(address)
+        ));
+        assert!(matches!(
+            parse_query(&format!("0x{}", "ab".repeat(32))),
+            Ok(Query::Hash(tx)) if tx.chain == "Mainnet"
+        ));
+        assert_eq!(
+            parse_query("safe"),
+            Ok(Query::Block(BlockId::Number(BlockNumberOrTag::Safe)))
+        );
+        assert_eq!(
+            parse_query(" Vitalik.eth "),
+            Ok(Query::Ens("vitalik.eth".into()))
+        );
+        assert!(parse_query("treasury").is_err());
+        assert!(parse_query("0x1234").is_err());
+    }
+}
@@ -0,0 +1,151 @@
+use super::{
+    anvil::fetch_chain_id,
+    chains::resolve_chain,
+    etherscan::check_api_key,
+    providers::{Providers, Rpc},
+    rpc_health::endpoints,
+};
+use crate::storage::SecretKey;
+use std::future::Future;
+use tokio::{
+    task::JoinSet,
+    time::{Duration, timeout},
+};
+
+const CHECK_TIMEOUT: Duration = Duration::from_secs(8);
+
+/// Outcome of probing one secret: a short success detail or the failure reason.
+pub type SecretCheck = Result<String, String>;
+
+/// Probe every entry concurrently. Keys without a cheap check are skipped.
+pub async fn check_secrets(
+    providers: Providers,
+    entries: Vec<(SecretKey, String)>,
+) -> Vec<(SecretKey, SecretCheck)> {
+    let mut tasks = JoinSet::new();
+    for (key, value) in entries {
+        let providers = providers.clone();
+        tasks.spawn(async move {
+            let outcome = match timeout(CHECK_TIMEOUT, check_secret(&providers, &key, &value)).await
+            {
+                Ok(outcome) => outcome,
+                Err(_) => Some(Err("timed out".to_string())),
+            };
+            outcome.map(|outcome| (key, outcome))
+        });
+    }
+    let mut results = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok(Some(result)) = joined {
+            results.push(result);
+        }
+    }
+    results
+}
+
+async fn check_secret(providers: &Providers, key: &SecretKey, value: &str) -> Option<SecretCheck> {
+    let rpc = &providers.rpc;
+    let outcome = match key {
+        SecretKey::EtherscanApiKey => check_api_key(&providers.session, 1, value)
+            .await
+            .map(|_| "key accepted".to_string())
+            .map_err(|err| err.to_string()),
+        SecretKey::ExplorerApiKey(chain_id) => check_api_key(&providers.session, *chain_id, value)
+            .await
+            .map(|_| "key accepted".to_string())
+            .map_err(|err| err.to_string()),
+        SecretKey::AnvilRpcUrl => check_endpoints(rpc, value, None).await,
+        SecretKey::RpcUrl(chain) => {
+            check_endpoints(rpc, value, resolve_chain(chain).map(|info| info.chain_id)).await
+        }
+        SecretKey::ProviderCredential { .. } => return None,
+    };
+    Some(outcome)
+}
+
+/// Every configured fallback must answer and agree on the chain, not just the first.
+async fn check_endpoints(rpc: &Rpc, value: &str, expected: Option<u64>) -> SecretCheck {
+    verify_endpoints(value, expected, |url| async move {
+        fetch_chain_id(rpc, &url)
+            .await
+            .map_err(|err| format!("{err:#}"))
+    })
+    .await
+}
+
+/// [`check_endpoints`] with the chain id lookup passed in.
+async fn verify_endpoints<F, Fut>(value: &str, expected: Option<u64>, chain_id: F) -> SecretCheck
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<u64, String>>,
+{
+    let urls = endpoints(value);
+    let mut reported = None;
+    for url in &urls {
+        let actual = chain_id(url.clone())
+            .await
+            .map_err(|err| format!("{url}: {err}"))?;
+        if let Some(expected) = expected
+            && actual != expected
+        {
+            return Err(format!(
+                "{url} reports chain id {actual}, expected {expected}"
+            ));
+        }
+        if reported.is_some_and(|first| first != actual) {
+            return Err(format!("{url} serves chain id {actual}, unlike the others"));
+        }
+        reported = Some(actual);
+    }
+    match (reported, urls.len()) {
+        (Some(chain_id), 1) => Ok(format!("chain id {chain_id}")),
+        (Some(chain_id), count) => Ok(format!("chain id {chain_id} • {count} endpoints")),
+        (None, _) => Err("no URL entered".into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn fixed(url: String) -> Result<u64, String> {
+        match url.as_str() {
+            "https://eth.example" | "https://eth-backup.example" => Ok(1),
+            "https://base.example" => Ok(8453),
+            _ => Err("connection refused".into()),
+        }
+    }
+
+    #[tokio::test]
+    async fn checks_every_fallback_against_the_chain() {
+        assert_eq!(
+            verify_endpoints("https://eth.example", Some(1), fixed).await,
+            Ok("chain id 1".into())
+        );
+        assert_eq!(
+            verify_endpoints(
+                "https://eth.example, https://eth-backup.example",
+                Some(1),
+                fixed
+            )
+            .await,
+            Ok("chain id 1 • 2 endpoints".into())
+        );
+        assert_eq!(
+            verify_endpoints("https://base.example", Some(1), fixed).await,
+            Err("https://base.example reports chain id 8453, expected 1".into())
+        );
+        assert_eq!(
+            verify_endpoints("https://eth.example,https://base.example", None, fixed).await,
+            Err("https://base.example serves chain id 8453, unlike the others".into())
+        );
+        assert_eq!(
+            verify_endpoints("https://eth.example,https://down.example", None, fixed).await,
+            Err("https://down.example: connection refused".into())
+        );
+        assert_eq!(
+            verify_endpoints(" ", None, fixed).await,
+            Err("no URL entered".into())
+        );
+    }
+}
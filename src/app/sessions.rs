@@ -0,0 +1,111 @@
+use super::{
+    MainViewTab, SelectedEntity, log_alerts::LogAlertRule, thresholds::ThresholdAlert,
+    watchlist::WatchlistEntry,
+};
+use serde::{Deserialize, Serialize};
+
+/// One investigation's workspace: what was open and what was watched. Its
+/// notes are the scratchpad of the investigation with the same name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Session {
+    pub name: String,
+    /// Unix seconds of the last snapshot.
+    pub saved_at: u64,
+    #[serde(default)]
+    pub selected: Option<SelectedEntity>,
+    #[serde(default)]
+    pub tab: MainViewTab,
+    #[serde(default)]
+    pub watchlist: Vec<WatchlistEntry>,
+    #[serde(default)]
+    pub log_alerts: Vec<LogAlertRule>,
+    #[serde(default)]
+    pub thresholds: Vec<ThresholdAlert>,
+}
+
+impl Session {
+    /// A fresh workspace: nothing open, nothing watched.
+    pub fn empty(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            saved_at: 0,
+            selected: None,
+            tab: MainViewTab::default(),
+            watchlist: Vec::new(),
+            log_alerts: Vec::new(),
+            thresholds: Vec::new(),
+        }
+    }
+
+    /// Watchlist entries, log rules and thresholds together.
+    pub fn watch_count(&self) -> usize {
+        self.watchlist.len() + self.log_alerts.len() + self.thresholds.len()
+    }
+}
+
+/// Sessions stashed while another one is open, stored under the
+/// `sessions.saved` setting. The open session lives in the app state itself.
+#[derive(Debug, Default)]
+pub struct SessionState {
+    pub saved: Vec<Session>,
+}
+
+impl SessionState {
+    pub fn new(saved: Vec<Session>) -> Self {
+        Self { saved }
+    }
+
+    /// Stash `session`, replacing an earlier snapshot of the same name.
+    pub fn stash(&mut self, session: Session) {
+        self.saved.retain(|saved| saved.name != session.name);
+        self.saved.push(session);
+        self.saved.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    /// Take `name`'s snapshot out to open it, or a fresh one for a new name.
+    pub fn take(&mut self, name: &str) -> Session {
+        match self.saved.iter().position(|saved| saved.name == name) {
+            Some(index) => self.saved.remove(index),
+            None => Session::empty(name),
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Session> {
+        let index = self.saved.iter().position(|saved| saved.name == name)?;
+        Some(self.saved.remove(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::AddressRef;
+
+    #[test]
+    fn stashes_and_takes_sessions_by_name() {
+        let mut sessions = SessionState::default();
+        let mut incident = Session::empty("bridge-exploit");
+        incident.selected = Some(SelectedEntity::Address(AddressRef {
+            label: "0xab".into(),
+            address: "0xAB".into(),
+            chain: "Mainnet".into(),
+        }));
+        incident.tab = MainViewTab::AddressEvents;
+        sessions.stash(incident.clone());
+        sessions.stash(Session::empty("audit"));
+        incident.saved_at = 10;
+        sessions.stash(incident.clone());
+        let names: Vec<_> = sessions.saved.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["audit", "bridge-exploit"]);
+
+        let json = serde_json::to_string(&sessions.saved).unwrap();
+        let restored: Vec<Session> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, sessions.saved);
+
+        assert_eq!(sessions.take("bridge-exploit"), incident);
+        assert_eq!(sessions.take("new"), Session::empty("new"));
+        assert_eq!(sessions.saved.len(), 1);
+        assert!(sessions.remove("audit").is_some());
+        assert!(sessions.remove("audit").is_none());
+    }
+}
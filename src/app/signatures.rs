@@ -0,0 +1,328 @@
+use super::{etherscan::build_client, fetch_limits::FetchLimits, stats::ApiStats};
+use crate::storage::{SignatureKind, SignatureRepository};
+use alloy::primitives::keccak256;
+use color_eyre::Result;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+const BUNDLED: &str = include_str!("signatures.txt");
+
+/// Bump whenever `signatures.txt` changes so existing stores load it again.
+const BUNDLE_VERSION: u32 = 1;
+
+const OPENCHAIN_LOOKUP: &str = "https://api.openchain.xyz/signature-database/v1/lookup";
+
+/// Selectors per OpenChain request, to keep URLs within common limits.
+const SYNC_BATCH: usize = 100;
+
+/// Selector of a text signature: the first four bytes of its hash for a
+/// function, the whole hash (topic0) for an event.
+pub fn selector(kind: SignatureKind, signature: &str) -> String {
+    let hash = keccak256(signature.as_bytes());
+    match kind {
+        SignatureKind::Function => format!("0x{}", alloy::hex::encode(&hash[..4])),
+        SignatureKind::Event => format!("{hash:#x}"),
+    }
+}
+
+/// `transfer` from `transfer(address,uint256)`.
+pub fn method_name(signature: &str) -> &str {
+    signature.split('(').next().unwrap_or(signature)
+}
+
+fn parse_bundle(text: &str) -> impl Iterator<Item = (SignatureKind, &str)> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| match line.strip_prefix("event ") {
+            Some(event) => (SignatureKind::Event, event.trim()),
+            None => (SignatureKind::Function, line),
+        })
+}
+
+/// Load the packaged database unless this version already is in the store.
+/// Returns how many signatures were new.
+pub fn seed(repo: &SignatureRepository) -> Result<usize> {
+    if repo.bundle_version()? >= BUNDLE_VERSION {
+        return Ok(0);
+    }
+    let mut added = 0;
+    for (kind, signature) in parse_bundle(BUNDLED) {
+        if repo.add(kind, &selector(kind, signature), signature)? {
+            added += 1;
+        }
+    }
+    repo.set_bundle_version(BUNDLE_VERSION)?;
+    Ok(added)
+}
+
+/// Signatures looked up this session, readable while rendering. `None`
+/// marks selectors the store had no match for.
+#[derive(Debug, Clone, Default)]
+pub struct SignatureBook {
+    known: HashMap<(SignatureKind, String), Option<String>>,
+    /// Selectors handed to [`lookup`] whose answer has not arrived yet.
+    pending: HashSet<(SignatureKind, String)>,
+}
+
+impl SignatureBook {
+    /// `0x`-prefixed, lowercase selector of `calldata`; `None` when it is
+    /// shorter than four bytes.
+    pub fn function_selector(calldata: &str) -> Option<String> {
+        let hex = calldata.strip_prefix("0x").unwrap_or(calldata);
+        let selector = hex.get(..8)?;
+        selector
+            .chars()
+            .all(|c| c.is_ascii_hexdigit())
+            .then(|| format!("0x{}", selector.to_ascii_lowercase()))
+    }
+
+    pub fn get(&self, kind: SignatureKind, selector: &str) -> Option<&str> {
+        self.known
+            .get(&(kind, selector.to_ascii_lowercase()))?
+            .as_deref()
+    }
+
+    /// The text signature `calldata` calls, when known.
+    pub fn method(&self, calldata: &str) -> Option<&str> {
+        self.get(SignatureKind::Function, &Self::function_selector(calldata)?)
+    }
+
+    /// Selectors neither in the book nor already being looked up, marked as
+    /// pending until [`insert`](Self::insert) brings their answer.
+    pub fn unresolved(
+        &mut self,
+        kind: SignatureKind,
+        selectors: impl IntoIterator<Item = String>,
+    ) -> Vec<String> {
+        let mut unresolved = Vec::new();
+        for selector in selectors {
+            let key = (kind, selector.to_ascii_lowercase());
+            if self.known.contains_key(&key) || self.pending.contains(&key) {
+                continue;
+            }
+            unresolved.push(key.1.clone());
+            self.pending.insert(key);
+        }
+        unresolved
+    }
+
+    /// Merge the answers of a [`lookup`]. Answers for selectors no longer
+    /// pending, because a sync dropped them meanwhile, are ignored.
+    pub fn insert(&mut self, kind: SignatureKind, found: Resolved) {
+        for (selector, signature) in found {
+            let key = (kind, selector);
+            if self.pending.remove(&key) {
+                self.known.insert(key, signature);
+            }
+        }
+    }
+
+    /// Forget the pending selectors after a failed lookup so they are tried
+    /// again.
+    pub fn clear_pending(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Drop unmatched selectors so they are looked up again after a sync.
+    pub fn forget_misses(&mut self) {
+        self.known.retain(|_, signature| signature.is_some());
+        self.pending.clear();
+    }
+}
+
+/// Selectors with their stored signature, `None` for a miss.
+pub type Resolved = Vec<(String, Option<String>)>;
+
+/// Look up `selectors` in the store, on the storage thread. The first stored
+/// signature wins; selectors without one are recorded for the next sync.
+pub fn lookup(
+    repo: &SignatureRepository,
+    kind: SignatureKind,
+    selectors: Vec<String>,
+) -> Result<Resolved> {
+    let mut found = Vec::with_capacity(selectors.len());
+    for selector in selectors {
+        let signature = repo.get(kind, &selector)?.into_iter().next();
+        if signature.is_none() {
+            repo.record_miss(kind, &selector)?;
+        }
+        found.push((selector, signature));
+    }
+    Ok(found)
+}
+
+/// A signature OpenChain knows for a selector that was missing locally.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncedSignature {
+    pub kind: SignatureKind,
+    pub selector: String,
+    pub signature: String,
+}
+
+/// Ask OpenChain for the recorded misses, in batches. Spam-filtered results
+/// only; selectors it does not know stay recorded.
+pub async fn sync(
+    stats: &ApiStats,
+    misses: Vec<(SignatureKind, String)>,
+) -> Result<Vec<SyncedSignature>, String> {
+    let client =
+        build_client(FetchLimits::default().explorer_timeout()).map_err(|err| err.to_string())?;
+    let mut found = Vec::new();
+    for batch in misses.chunks(SYNC_BATCH) {
+        let join = |kind: SignatureKind| {
+            batch
+                .iter()
+                .filter(|(entry_kind, _)| *entry_kind == kind)
+                .map(|(_, selector)| selector.as_str())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        stats.record_api_call("openchain");
+        let payload: serde_json::Value = client
+            .get(OPENCHAIN_LOOKUP)
+            .query(&[
+                ("function", join(SignatureKind::Function)),
+                ("event", join(SignatureKind::Event)),
+                ("filter", "true".to_string()),
+            ])
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|err| format!("OpenChain request failed: {err}"))?
+            .json()
+            .await
+            .map_err(|err| format!("OpenChain response unreadable: {err}"))?;
+        found.extend(parse_lookup(payload)?);
+    }
+    Ok(found)
+}
+
+fn parse_lookup(payload: serde_json::Value) -> Result<Vec<SyncedSignature>, String> {
+    let response = serde_json::from_value::<RawLookup>(payload)
+        .map_err(|err| format!("OpenChain response unreadable: {err}"))?;
+    if !response.ok {
+        return Err(response
+            .error
+            .unwrap_or_else(|| "OpenChain lookup failed".into()));
+    }
+    let result = response.result.unwrap_or_default();
+    let entries = [
+        (SignatureKind::Function, result.function),
+        (SignatureKind::Event, result.event),
+    ];
+    let mut found = Vec::new();
+    for (kind, selectors) in entries {
+        for (selector, matches) in selectors {
+            found.extend(
+                matches
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|entry| !entry.filtered)
+                    .map(|entry| SyncedSignature {
+                        kind,
+                        selector: selector.to_ascii_lowercase(),
+                        signature: entry.name,
+                    }),
+            );
+        }
+    }
+    Ok(found)
+}
+
+#[derive(Debug, Deserialize)]
+struct RawLookup {
+    ok: bool,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    result: Option<RawLookupResult>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RawLookupResult {
+    #[serde(default)]
+    function: BTreeMap<String, Option<Vec<RawSignature>>>,
+    #[serde(default)]
+    event: BTreeMap<String, Option<Vec<RawSignature>>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawSignature {
+    name: String,
+    #[serde(default)]
+    filtered: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fjall::Config;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    #[test]
+    fn seeds_bundle_and_resolves_selectors() -> Result<()> {
+        assert_eq!(
+            selector(SignatureKind::Function, "transfer(address,uint256)"),
+            "0xa9059cbb"
+        );
+        assert_eq!(
+            selector(SignatureKind::Event, "Transfer(address,address,uint256)"),
+            "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+        );
+
+        let temp = tempdir()?;
+        let keyspace = Config::new(temp.path()).open()?;
+        let repo = SignatureRepository::new(keyspace.open_partition("sigs", Default::default())?);
+        assert!(seed(&repo)? > 100);
+        assert_eq!(seed(&repo)?, 0);
+
+        let mut book = SignatureBook::default();
+        let selectors = ["0xA9059CBB".to_string(), "0xdeadbeef".to_string()];
+        let unresolved = book.unresolved(SignatureKind::Function, selectors.clone());
+        assert_eq!(unresolved, ["0xa9059cbb", "0xdeadbeef"]);
+        // Already pending, so a second pass asks for nothing.
+        assert!(
+            book.unresolved(SignatureKind::Function, selectors)
+                .is_empty()
+        );
+        let found = lookup(&repo, SignatureKind::Function, unresolved)?;
+        book.insert(SignatureKind::Function, found);
+        let method = book.method("0xa9059cbb000000000000").unwrap();
+        assert_eq!(method_name(method), "transfer");
+        assert_eq!(book.method("0xdeadbeef"), None);
+        assert_eq!(book.method("0x"), None);
+        assert_eq!(
+            repo.misses()?,
+            vec![(SignatureKind::Function, "0xdeadbeef".to_string())]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parses_openchain_lookups() {
+        let payload = json!({
+            "ok": true,
+            "result": {
+                "function": {
+                    "0xdeadbeef": [
+                        {"name": "spam_1337()", "filtered": true},
+                        {"name": "kill()", "filtered": false}
+                    ],
+                    "0x12345678": null
+                },
+                "event": {}
+            }
+        });
+        assert_eq!(
+            parse_lookup(payload).unwrap(),
+            vec![SyncedSignature {
+                kind: SignatureKind::Function,
+                selector: "0xdeadbeef".into(),
+                signature: "kill()".into(),
+            }]
+        );
+        assert!(parse_lookup(json!({"ok": false, "error": "bad selector"})).is_err());
+    }
+}
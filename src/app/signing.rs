@@ -0,0 +1,397 @@
+use super::{
+    anvil::normalize_url,
+    csv_export::expand_path,
+    ledger::{self, ClearSigning, LedgerWallet},
+    nonces::ReplacementKind,
+    pending::{self, PendingTransaction},
+    providers::Rpc,
+};
+use alloy::{
+    consensus::{SignableTransaction, TxEip1559, TxEnvelope},
+    eips::Encodable2718,
+    network::{TransactionBuilder, TxSigner},
+    primitives::{Address, B256, Bytes, Signature, U256},
+    providers::{DynProvider, Provider},
+    rpc::types::TransactionRequest,
+    signers::{ledger::HDPath, local::PrivateKeySigner},
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Where the write flow gets its signatures from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SignerSource {
+    /// Encrypted JSON keystore, unlocked with a passphrase per transaction.
+    Keystore(PathBuf),
+    /// Ledger Ethereum app account at this Ledger Live index. Calls Ledger
+    /// publishes metadata for are clear-signed; others are blind-signed
+    /// against the hash the confirmation screen shows.
+    Ledger(usize),
+}
+
+impl SignerSource {
+    pub fn describe(&self) -> String {
+        match self {
+            SignerSource::Keystore(path) => format!("keystore {}", path.display()),
+            SignerSource::Ledger(index) => format!("Ledger {}", HDPath::LedgerLive(*index)),
+        }
+    }
+}
+
+/// A keystore given by name lives where `cast wallet import` puts it;
+/// anything with a path separator is a file path (`~/` expands).
+pub fn keystore_path(input: &str) -> PathBuf {
+    let input = input.trim();
+    if input.contains(['/', '\\']) {
+        return expand_path(input);
+    }
+    dirs::home_dir()
+        .unwrap_or_default()
+        .join(".foundry")
+        .join("keystores")
+        .join(input)
+}
+
+/// An unlocked signer. Ledger keys never leave the device; it is asked to
+/// sign and shows the transaction for approval.
+#[derive(Debug, Clone)]
+pub enum Wallet {
+    Keystore(PrivateKeySigner),
+    Ledger(LedgerWallet),
+}
+
+impl Wallet {
+    pub fn address(&self) -> Address {
+        match self {
+            Wallet::Keystore(signer) => signer.address(),
+            Wallet::Ledger(ledger) => ledger.address(),
+        }
+    }
+
+    async fn sign(
+        &self,
+        tx: &mut TxEip1559,
+        clear_signing: &ClearSigning,
+    ) -> Result<Signature, String> {
+        match self {
+            Wallet::Keystore(signer) => signer
+                .sign_transaction(tx)
+                .await
+                .map_err(|err| format!("signing failed: {err}")),
+            Wallet::Ledger(ledger) => ledger.sign(tx, clear_signing).await,
+        }
+    }
+}
+
+/// An EIP-1559 transaction with every field the signature covers filled
+/// in, held with its unlocked signer until the user confirms or cancels.
+#[derive(Debug, Clone)]
+pub struct PreparedTransaction {
+    pub wallet: Wallet,
+    pub tx: TxEip1559,
+    /// Whether the sender address was confirmed on the Ledger's screen.
+    pub verified_on_device: bool,
+    /// How a Ledger presents the transaction; `None` for a keystore.
+    pub clear_signing: Option<ClearSigning>,
+}
+
+impl PreparedTransaction {
+    pub fn from(&self) -> Address {
+        self.wallet.address()
+    }
+
+    /// Most the transaction can cost in gas fees.
+    pub fn max_fee(&self) -> U256 {
+        U256::from(self.tx.gas_limit) * U256::from(self.tx.max_fee_per_gas)
+    }
+
+    /// Hash the signer signs, which a Ledger shows when it blind-signs:
+    /// approving there is only safe when the two match.
+    pub fn signing_hash(&self) -> B256 {
+        self.tx.signature_hash()
+    }
+}
+
+/// Decrypt the keystore (scrypt is slow, so off the async runtime).
+async fn unlock_keystore(path: PathBuf, passphrase: String) -> Result<PrivateKeySigner, String> {
+    tokio::task::spawn_blocking(move || {
+        PrivateKeySigner::decrypt_keystore(&path, passphrase)
+            .map_err(|err| format!("cannot unlock keystore {}: {err}", path.display()))
+    })
+    .await
+    .map_err(|err| format!("keystore unlock failed: {err}"))?
+}
+
+async fn connect(rpc: &Rpc, rpc_url: &str) -> Result<(DynProvider, u64), String> {
+    let provider = rpc
+        .connect(&normalize_url(rpc, rpc_url))
+        .await
+        .map_err(|err| format!("{err:#}"))?;
+    rpc.stats().record_api_call("rpc");
+    let chain_id = provider
+        .get_chain_id()
+        .await
+        .map_err(|err| format!("eth_chainId failed: {err}"))?;
+    Ok((provider, chain_id))
+}
+
+/// Hands [`prepare`] and [`replace`] the wallet to sign with once the RPC
+/// endpoint answered, along with whether its address was confirmed on a
+/// device.
+pub trait Unlock: Send {
+    fn unlock(self) -> impl Future<Output = Result<(Wallet, bool), String>> + Send;
+}
+
+/// The configured signer, with the passphrase a keystore is unlocked with.
+#[derive(Debug, Clone)]
+pub struct Credentials {
+    pub source: SignerSource,
+    pub passphrase: String,
+}
+
+impl Unlock for Credentials {
+    async fn unlock(self) -> Result<(Wallet, bool), String> {
+        match self.source {
+            SignerSource::Keystore(path) => Ok((
+                Wallet::Keystore(unlock_keystore(path, self.passphrase).await?),
+                false,
+            )),
+            SignerSource::Ledger(index) => {
+                Ok((Wallet::Ledger(LedgerWallet::connect(index).await?), true))
+            }
+        }
+    }
+}
+
+/// The call a transaction makes: its target, calldata and attached value.
+#[derive(Debug, Clone)]
+pub struct TransactionCall {
+    pub to: Address,
+    pub input: Bytes,
+    pub value: U256,
+}
+
+/// Unlock the signer and fill chain id, nonce, gas and EIP-1559 fees for
+/// `call`. Gas estimation runs the call, so a reverting transaction
+/// fails here rather than on chain. A Ledger first shows its address for
+/// confirmation on the device. The nonce skips past any still in `queue`.
+pub async fn prepare(
+    rpc: Rpc,
+    rpc_url: String,
+    signer: impl Unlock,
+    call: TransactionCall,
+    queue: Vec<PendingTransaction>,
+) -> Result<PreparedTransaction, String> {
+    let TransactionCall { to, input, value } = call;
+    let (provider, chain_id) = connect(&rpc, &rpc_url).await?;
+    let (wallet, verified_on_device) = signer.unlock().await?;
+    let from = wallet.address();
+    let request = TransactionRequest::default()
+        .with_from(from)
+        .with_to(to)
+        .with_input(input)
+        .with_value(value);
+
+    rpc.stats().record_api_call("rpc");
+    let nonce = provider
+        .get_transaction_count(from)
+        .pending()
+        .await
+        .map_err(|err| format!("failed to read the sender's nonce: {err}"))?;
+    let nonce = pending::next_nonce(nonce, &queue, chain_id, from);
+    rpc.stats().record_api_call("rpc");
+    let gas = provider
+        .estimate_gas(request.clone())
+        .await
+        .map_err(|err| format!("gas estimation failed (the call would revert?): {err}"))?;
+    rpc.stats().record_api_call("rpc");
+    let fees = provider
+        .estimate_eip1559_fees()
+        .await
+        .map_err(|err| format!("fee estimation failed: {err}"))?;
+
+    let tx = request
+        .with_chain_id(chain_id)
+        .with_nonce(nonce)
+        .with_gas_limit(gas)
+        .with_max_fee_per_gas(fees.max_fee_per_gas)
+        .with_max_priority_fee_per_gas(fees.max_priority_fee_per_gas)
+        .build_1559()
+        .map_err(|err| format!("cannot build the transaction: {err}"))?;
+    let clear_signing = clear_signing(&rpc, &wallet, &tx).await;
+    Ok(PreparedTransaction {
+        wallet,
+        tx,
+        verified_on_device,
+        clear_signing,
+    })
+}
+
+/// Unlock the signer that sent `original` and re-price it at the same nonce
+/// as a speed-up or a cancellation.
+pub async fn replace(
+    rpc: Rpc,
+    rpc_url: String,
+    signer: impl Unlock,
+    original: PendingTransaction,
+    kind: ReplacementKind,
+) -> Result<PreparedTransaction, String> {
+    let (provider, chain_id) = connect(&rpc, &rpc_url).await?;
+    if chain_id != original.chain_id {
+        return Err(format!(
+            "the RPC endpoint is on chain {chain_id}, the transaction on {}",
+            original.chain_id
+        ));
+    }
+    let (wallet, verified_on_device) = signer.unlock().await?;
+    if wallet.address() != original.from {
+        return Err(format!(
+            "the signer is {}, but the transaction was sent by {}",
+            wallet.address(),
+            original.from
+        ));
+    }
+    rpc.stats().record_api_call("rpc");
+    let fees = provider
+        .estimate_eip1559_fees()
+        .await
+        .map_err(|err| format!("fee estimation failed: {err}"))?;
+    let tx = original.replacement_tx(kind, fees.max_fee_per_gas, fees.max_priority_fee_per_gas);
+    let clear_signing = clear_signing(&rpc, &wallet, &tx).await;
+    Ok(PreparedTransaction {
+        wallet,
+        tx,
+        verified_on_device,
+        clear_signing,
+    })
+}
+
+/// The metadata a Ledger needs to clear-sign `tx`, looked up before the
+/// confirmation screen so it can say what the device will show.
+async fn clear_signing(rpc: &Rpc, wallet: &Wallet, tx: &TxEip1559) -> Option<ClearSigning> {
+    match wallet {
+        Wallet::Keystore(_) => None,
+        Wallet::Ledger(_) => Some(ledger::clear_signing(rpc.stats(), tx).await),
+    }
+}
+
+/// Sign the prepared transaction. A Ledger waits here until the transaction
+/// is approved on the device.
+async fn sign(prepared: PreparedTransaction) -> Result<TxEnvelope, String> {
+    let mut tx = prepared.tx;
+    let clear_signing = prepared.clear_signing.unwrap_or(ClearSigning::Blind);
+    let signature = prepared.wallet.sign(&mut tx, &clear_signing).await?;
+    Ok(TxEnvelope::from(tx.into_signed(signature)))
+}
+
+/// Sign the prepared transaction and send it, returning its hash.
+pub async fn broadcast(
+    rpc: Rpc,
+    rpc_url: String,
+    prepared: PreparedTransaction,
+) -> Result<String, String> {
+    let envelope = sign(prepared).await?;
+    let provider = rpc
+        .connect(&normalize_url(&rpc, &rpc_url))
+        .await
+        .map_err(|err| format!("{err:#}"))?;
+    rpc.stats().record_api_call("rpc");
+    let sent = provider
+        .send_raw_transaction(&envelope.encoded_2718())
+        .await
+        .map_err(|err| format!("broadcast failed: {err}"))?;
+    Ok(format!("{:#x}", sent.tx_hash()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::providers::{BoxFuture, RpcConnector};
+    use alloy::{primitives::U64, providers::ProviderBuilder, transports::mock::Asserter};
+    use std::sync::Arc;
+
+    #[test]
+    fn describes_ledger_accounts_by_path() {
+        assert_eq!(
+            SignerSource::Ledger(0).describe(),
+            "Ledger m/44'/60'/0'/0/0"
+        );
+    }
+
+    impl Unlock for Wallet {
+        async fn unlock(self) -> Result<(Wallet, bool), String> {
+            Ok((self, false))
+        }
+    }
+
+    /// Connects every URL to the same mocked provider.
+    struct Mocked(DynProvider);
+
+    impl RpcConnector for Mocked {
+        fn connect<'a>(
+            &'a self,
+            _rpc_url: &'a str,
+        ) -> BoxFuture<'a, color_eyre::Result<DynProvider>> {
+            let provider = self.0.clone();
+            Box::pin(async move { Ok(provider) })
+        }
+    }
+
+    #[tokio::test]
+    async fn prepares_and_signs_with_an_injected_signer() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new()
+            .connect_mocked_client(asserter.clone())
+            .erased();
+        let rpc = Rpc::new(Arc::new(Mocked(provider)));
+        asserter.push_success(&U64::from(10));
+        asserter.push_success(&U64::from(7));
+        asserter.push_success(&U64::from(60_000));
+        asserter.push_success(&serde_json::json!({
+            "oldestBlock": "0x1",
+            "baseFeePerGas": ["0x3b9aca00", "0x3b9aca00"],
+            "gasUsedRatio": [0.5],
+            "reward": [["0x77359400"]],
+        }));
+
+        let key = PrivateKeySigner::random();
+        let from = key.address();
+        let to = Address::repeat_byte(0x11);
+        let call = TransactionCall {
+            to,
+            input: Bytes::from_static(&[0xa9, 0x05, 0x9c, 0xbb]),
+            value: U256::from(5),
+        };
+        let prepared = prepare(
+            rpc.clone(),
+            "http://node.invalid".into(),
+            Wallet::Keystore(key),
+            call,
+            Vec::new(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(prepared.from(), from);
+        assert!(!prepared.verified_on_device);
+        assert_eq!(prepared.clear_signing, None);
+        assert_eq!(prepared.tx.chain_id, 10);
+        assert_eq!(prepared.tx.nonce, 7);
+        assert_eq!(prepared.tx.gas_limit, 60_000);
+        assert_eq!(prepared.tx.to, to.into());
+        assert!(prepared.tx.max_fee_per_gas > prepared.tx.max_priority_fee_per_gas);
+
+        let signing_hash = prepared.signing_hash();
+        let envelope = sign(prepared.clone()).await.unwrap();
+        let TxEnvelope::Eip1559(signed) = &envelope else {
+            panic!("expected an EIP-1559 transaction");
+        };
+        assert_eq!(signed.signature_hash(), signing_hash);
+        assert_eq!(signed.recover_signer().unwrap(), from);
+
+        asserter.push_success(envelope.tx_hash());
+        let sent = broadcast(rpc, "http://node.invalid".into(), prepared)
+            .await
+            .unwrap();
+        assert_eq!(sent, format!("{:#x}", envelope.tx_hash()));
+    }
+}
@@ -0,0 +1,332 @@
+use super::{
+    anvil::fetch_storage_slots,
+    permissions::EIP1967_ADMIN_SLOT,
+    providers::Rpc,
+    scheduler::{Priority, Scheduler, host_key},
+    storage_layout::{SlotVariable, StorageLayout},
+    upgrade_impact::EIP1967_IMPLEMENTATION_SLOT,
+};
+use crate::storage::AlertOutputs;
+use alloy::primitives::{Address, B256, U256};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    time::Duration,
+};
+use tokio::{task::JoinSet, time::timeout};
+
+/// How often pinned slots are re-read; roughly once per mainnet block.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(12);
+/// Changes kept per slot for the session.
+const MAX_SAMPLES: usize = 64;
+
+/// A storage slot pinned from the Storage tab, persisted across sessions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PinnedSlot {
+    pub address: String,
+    pub chain: String,
+    pub slot: B256,
+    pub label: String,
+    /// Variables the slot holds per the contract's storage layout, when known.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variables: Vec<SlotVariable>,
+    /// Where value changes are sent.
+    #[serde(flatten)]
+    pub alerts: AlertOutputs,
+}
+
+impl PinnedSlot {
+    pub fn key(&self) -> String {
+        format!(
+            "{}:{}:{:#x}",
+            self.chain.to_ascii_lowercase(),
+            self.address.to_ascii_lowercase(),
+            self.slot
+        )
+    }
+
+    /// `value` decoded per variable when the layout was known, otherwise
+    /// read by [`describe_value`].
+    pub fn describe(&self, value: &B256) -> String {
+        if self.variables.is_empty() {
+            return describe_value(value);
+        }
+        self.variables
+            .iter()
+            .map(|variable| variable.describe(value))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+/// One observed value, recorded when it differs from the previous one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlotSample {
+    pub block: u64,
+    pub value: B256,
+}
+
+/// Pinned slots and the values seen for each this session.
+#[derive(Debug, Default)]
+pub struct SlotWatch {
+    pub pins: Vec<PinnedSlot>,
+    history: HashMap<String, VecDeque<SlotSample>>,
+    errors: HashMap<String, String>,
+    /// Slot being typed in the pin prompt.
+    pub editing: Option<String>,
+    pub selected_index: usize,
+}
+
+impl SlotWatch {
+    pub fn new(pins: Vec<PinnedSlot>) -> Self {
+        Self {
+            pins,
+            ..Self::default()
+        }
+    }
+
+    /// Add `pin` unless it is already pinned; returns whether it was added.
+    pub fn pin(&mut self, pin: PinnedSlot) -> bool {
+        let key = pin.key();
+        if self.pins.iter().any(|existing| existing.key() == key) {
+            return false;
+        }
+        self.pins.push(pin);
+        true
+    }
+
+    /// Move `pin` to the next [`AlertOutputs`]; returns the new setting.
+    pub fn cycle_alerts(&mut self, pin: &PinnedSlot) -> Option<AlertOutputs> {
+        let key = pin.key();
+        let pin = self
+            .pins
+            .iter_mut()
+            .find(|existing| existing.key() == key)?;
+        pin.alerts = pin.alerts.next();
+        Some(pin.alerts)
+    }
+
+    pub fn unpin(&mut self, pin: &PinnedSlot) {
+        let key = pin.key();
+        self.pins.retain(|existing| existing.key() != key);
+        self.history.remove(&key);
+        self.errors.remove(&key);
+    }
+
+    pub fn pins_for(&self, address: &str) -> Vec<&PinnedSlot> {
+        self.pins
+            .iter()
+            .filter(|pin| pin.address.eq_ignore_ascii_case(address))
+            .collect()
+    }
+
+    /// Oldest first.
+    pub fn history(&self, pin: &PinnedSlot) -> Option<&VecDeque<SlotSample>> {
+        self.history.get(&pin.key())
+    }
+
+    pub fn error(&self, pin: &PinnedSlot) -> Option<&str> {
+        self.errors.get(&pin.key()).map(String::as_str)
+    }
+
+    /// Apply one poll; returns the pins whose value changed since last seen.
+    pub fn apply_poll(
+        &mut self,
+        results: Vec<(String, Result<SlotSample, String>)>,
+    ) -> Vec<PinnedSlot> {
+        let mut changed = Vec::new();
+        for (key, outcome) in results {
+            match outcome {
+                Ok(sample) => {
+                    self.errors.remove(&key);
+                    if self.record(key.clone(), sample)
+                        && let Some(pin) = self.pins.iter().find(|pin| pin.key() == key)
+                    {
+                        changed.push(pin.clone());
+                    }
+                }
+                Err(err) => {
+                    self.errors.insert(key, err);
+                }
+            }
+        }
+        changed
+    }
+
+    /// Record a reading; returns true when it changed a previously seen value.
+    fn record(&mut self, key: String, sample: SlotSample) -> bool {
+        let history = self.history.entry(key).or_default();
+        match history.back() {
+            Some(last) if last.value == sample.value => false,
+            last => {
+                let changed = last.is_some();
+                history.push_back(sample);
+                if history.len() > MAX_SAMPLES {
+                    history.pop_front();
+                }
+                changed
+            }
+        }
+    }
+
+    pub fn move_selection(&mut self, down: bool, len: usize) {
+        self.selected_index = if down {
+            (self.selected_index + 1).min(len.saturating_sub(1))
+        } else {
+            self.selected_index.saturating_sub(1)
+        };
+    }
+}
+
+/// Parse a slot typed in the pin prompt: a decimal or `0x` index, one of
+/// the EIP-1967 names `implementation` / `admin`, or with a storage layout
+/// a variable path like `balances[0xabc…]`. Returns the slot, a label and
+/// the variables the layout places in it.
+pub fn parse_slot(
+    input: &str,
+    layout: Option<&StorageLayout>,
+) -> Result<(B256, String, Vec<SlotVariable>), String> {
+    let input = input.trim();
+    match input.to_ascii_lowercase().as_str() {
+        "implementation" | "impl" => {
+            return Ok((
+                EIP1967_IMPLEMENTATION_SLOT,
+                "EIP-1967 implementation".into(),
+                Vec::new(),
+            ));
+        }
+        "admin" => return Ok((EIP1967_ADMIN_SLOT, "EIP-1967 admin".into(), Vec::new())),
+        _ => {}
+    }
+    if let Some(layout) = layout
+        && input.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+    {
+        let (slot, variables) = layout.resolve(input)?;
+        return Ok((slot, input.to_string(), variables));
+    }
+    let value = match input.strip_prefix("0x") {
+        Some(hex) => U256::from_str_radix(hex, 16),
+        None => U256::from_str_radix(input, 10),
+    }
+    .map_err(|_| match layout {
+        Some(_) => format!("{input:?} is not a slot number, 0x slot, or state variable"),
+        None => format!("{input:?} is not a slot number, 0x slot, implementation, or admin"),
+    })?;
+    let slot = B256::from(value);
+    let variables = layout.map_or_else(Vec::new, |layout| layout.variables_at(slot));
+    let label = match variables.as_slice() {
+        [variable] => variable.label.clone(),
+        _ => format!("slot {input}"),
+    };
+    Ok((slot, label, variables))
+}
+
+/// Human reading of a slot value: small integers in decimal, address-shaped
+/// words as addresses, anything else as hex.
+pub fn describe_value(value: &B256) -> String {
+    let number = U256::from_be_bytes(value.0);
+    if value.0[..12].iter().all(|byte| *byte == 0) && value.0[12..16].iter().any(|b| *b != 0) {
+        Address::from_word(*value).to_string()
+    } else if number < U256::from(u64::MAX) {
+        number.to_string()
+    } else {
+        format!("{value:#x}")
+    }
+}
+
+/// Read every pin, one batch per endpoint so each chain's values come from
+/// the same block. Results are keyed by [`PinnedSlot::key`].
+pub async fn poll_pins(
+    rpc: Rpc,
+    scheduler: Scheduler,
+    pins: Vec<(PinnedSlot, Option<String>)>,
+) -> Vec<(String, Result<SlotSample, String>)> {
+    let mut batches: BTreeMap<Option<String>, Vec<PinnedSlot>> = BTreeMap::new();
+    let mut results = Vec::new();
+    for (pin, rpc_url) in pins {
+        if pin.address.parse::<Address>().is_err() {
+            results.push((pin.key(), Err("invalid address".to_string())));
+            continue;
+        }
+        batches.entry(rpc_url).or_default().push(pin);
+    }
+    let mut tasks = JoinSet::new();
+    for (rpc_url, pins) in batches {
+        let Some(url) = rpc_url else {
+            results.extend(
+                pins.iter()
+                    .map(|pin| (pin.key(), Err(format!("no RPC endpoint for {}", pin.chain)))),
+            );
+            continue;
+        };
+        let rpc = rpc.clone();
+        let scheduler = scheduler.clone();
+        tasks.spawn(async move {
+            let targets = pins
+                .iter()
+                .filter_map(|pin| {
+                    let address = pin.address.parse::<Address>().ok()?;
+                    Some((address, U256::from_be_bytes(pin.slot.0)))
+                })
+                .collect();
+            let host = host_key(Some(&url), &pins[0].chain);
+            let _permit = scheduler.acquire(&host, Priority::Poll).await;
+            let outcome = match timeout(
+                Duration::from_secs(10),
+                fetch_storage_slots(&rpc, &url, targets),
+            )
+            .await
+            {
+                Ok(Ok(outcome)) => Ok(outcome),
+                Ok(Err(err)) => Err(format!("{err:#}")),
+                Err(_) => Err("timed out".to_string()),
+            };
+            match outcome {
+                Ok((block, values)) => pins
+                    .iter()
+                    .zip(values)
+                    .map(|(pin, value)| (pin.key(), Ok(SlotSample { block, value })))
+                    .collect::<Vec<_>>(),
+                Err(err) => pins
+                    .iter()
+                    .map(|pin| (pin.key(), Err(err.clone())))
+                    .collect(),
+            }
+        });
+    }
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok(batch) = joined {
+            results.extend(batch);
+        }
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_changes_are_recorded() {
+        let (slot, label, variables) = parse_slot("0x3", None).unwrap();
+        assert_eq!(slot, B256::from(U256::from(3)));
+        assert_eq!(label, "slot 0x3");
+        assert!(variables.is_empty());
+        assert_eq!(
+            parse_slot("implementation", None).unwrap().0,
+            EIP1967_IMPLEMENTATION_SLOT
+        );
+        assert!(parse_slot("paused", None).is_err());
+
+        let mut watch = SlotWatch::default();
+        let sample = |block, value: u64| SlotSample {
+            block,
+            value: U256::from(value).into(),
+        };
+        assert!(!watch.record("k".into(), sample(1, 7)));
+        assert!(!watch.record("k".into(), sample(2, 7)));
+        assert!(watch.record("k".into(), sample(3, 8)));
+        assert_eq!(watch.history.get("k").map(VecDeque::len), Some(2));
+        assert_eq!(describe_value(&sample(0, 8).value), "8");
+    }
+}
@@ -0,0 +1,114 @@
+use super::{
+    etherscan::build_client,
+    explorer::{ContractSource, SourceMatch, TransactionFetchError},
+    stats::ApiStats,
+    storage_layout::StorageLayout,
+};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::{collections::BTreeMap, time::Duration};
+
+const SOURCIFY_V2_BASE: &str = "https://sourcify.dev/server/v2/contract";
+
+/// Look up a contract on Sourcify; `Ok(None)` when it has no full or partial match.
+pub async fn fetch_verified_source(
+    stats: &ApiStats,
+    timeout: Duration,
+    chain_id: u64,
+    address: &str,
+) -> Result<Option<ContractSource>, TransactionFetchError> {
+    let client = build_client(timeout)?;
+    stats.record_api_call("sourcify");
+    let response = client
+        .get(format!("{SOURCIFY_V2_BASE}/{chain_id}/{address}"))
+        .query(&[("fields", "abi,compilation,sources,storageLayout")])
+        .send()
+        .await?;
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let payload: serde_json::Value = response.error_for_status()?.json().await?;
+    parse_contract(payload)
+}
+
+fn parse_contract(
+    payload: serde_json::Value,
+) -> Result<Option<ContractSource>, TransactionFetchError> {
+    let raw = serde_json::from_value::<RawContract>(payload)?;
+    let match_type = match raw.match_type.as_deref() {
+        Some("exact_match") | Some("perfect") => SourceMatch::FullMatch,
+        Some("match") | Some("partial") => SourceMatch::PartialMatch,
+        _ => return Ok(None),
+    };
+    let Some(abi) = raw.abi else {
+        return Ok(None);
+    };
+    let compilation = raw.compilation.unwrap_or_default();
+    let source = raw
+        .sources
+        .iter()
+        .map(|(path, file)| format!("// File: {path}\n{}", file.content))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    Ok(Some(ContractSource {
+        name: compilation.name,
+        compiler: compilation.compiler_version,
+        abi: abi.to_string(),
+        source,
+        source_label: "Sourcify",
+        match_type,
+        storage_layout: raw.storage_layout,
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+struct RawContract {
+    #[serde(rename = "match", default)]
+    match_type: Option<String>,
+    #[serde(default)]
+    abi: Option<serde_json::Value>,
+    #[serde(default)]
+    compilation: Option<RawCompilation>,
+    #[serde(default)]
+    sources: BTreeMap<String, RawSourceFile>,
+    #[serde(default, rename = "storageLayout")]
+    storage_layout: Option<StorageLayout>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawCompilation {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    compiler_version: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawSourceFile {
+    #[serde(default)]
+    content: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_partial_match_and_rejects_unverified() {
+        let payload = json!({
+            "match": "match",
+            "abi": [{"type": "fallback", "stateMutability": "payable"}],
+            "compilation": { "name": "Vault", "compilerVersion": "0.8.20+commit.a1b79de6" },
+            "sources": { "src/Vault.sol": { "content": "contract Vault {}" } }
+        });
+        let source = parse_contract(payload).unwrap().expect("verified contract");
+        assert_eq!(source.name, "Vault");
+        assert_eq!(source.match_type, SourceMatch::PartialMatch);
+        assert!(source.source.starts_with("// File: src/Vault.sol"));
+
+        let unverified = json!({ "match": null, "abi": null });
+        assert!(parse_contract(unverified).unwrap().is_none());
+    }
+}
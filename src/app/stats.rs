@@ -0,0 +1,189 @@
+use crate::storage::{SettingKey, SettingsRepository, Storage};
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// Hydration timings kept for percentile estimates; older samples are dropped.
+const HYDRATION_SAMPLE_LIMIT: usize = 256;
+
+/// Counters background tasks bump, shared by every clone. API calls are
+/// folded into [`UsageStats`] on the UI thread.
+#[derive(Debug, Clone, Default)]
+pub struct ApiStats {
+    pending_calls: Arc<Mutex<BTreeMap<&'static str, u64>>>,
+    /// Cache lookups this session as `(hits, misses)`; not persisted.
+    cache_lookups: Arc<Mutex<BTreeMap<&'static str, (u64, u64)>>>,
+}
+
+impl ApiStats {
+    /// Count an outbound request. Safe to call from any async task.
+    pub fn record_api_call(&self, endpoint: &'static str) {
+        if let Ok(mut pending) = self.pending_calls.lock() {
+            *pending.entry(endpoint).or_default() += 1;
+        }
+    }
+
+    pub fn record_cache_lookup(&self, cache: &'static str, hit: bool) {
+        if let Ok(mut lookups) = self.cache_lookups.lock() {
+            let (hits, misses) = lookups.entry(cache).or_default();
+            if hit {
+                *hits += 1;
+            } else {
+                *misses += 1;
+            }
+        }
+    }
+
+    pub fn cache_lookups(&self) -> Vec<(&'static str, u64, u64)> {
+        self.cache_lookups
+            .lock()
+            .map(|lookups| {
+                lookups
+                    .iter()
+                    .map(|(cache, (hits, misses))| (*cache, *hits, *misses))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Local-only usage counters. Nothing here leaves the machine; the settings
+/// partition is the only place it is written.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsageStats {
+    features: BTreeMap<String, u64>,
+    api_calls: BTreeMap<String, u64>,
+    hydration_ms: VecDeque<u64>,
+    #[serde(skip)]
+    dirty: bool,
+}
+
+impl UsageStats {
+    pub fn load(settings: &SettingsRepository) -> Result<Self> {
+        Ok(settings
+            .get_json(SettingKey::UsageStats)?
+            .unwrap_or_default())
+    }
+
+    /// Persist if anything changed since the last save.
+    pub fn save(&mut self, settings: &SettingsRepository) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        settings.set_json(SettingKey::UsageStats, self)?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Like [`save`](Self::save), but on the storage writer thread.
+    pub fn queue_save(&mut self, storage: &Storage) {
+        if !std::mem::take(&mut self.dirty) {
+            return;
+        }
+        let snapshot = self.clone();
+        storage.write("usage stats", move |storage| {
+            storage
+                .settings()
+                .set_json(SettingKey::UsageStats, &snapshot)
+        });
+    }
+
+    pub fn record_feature(&mut self, feature: &str) {
+        *self.features.entry(feature.to_string()).or_default() += 1;
+        self.dirty = true;
+    }
+
+    pub fn record_hydration(&mut self, elapsed: Duration) {
+        if self.hydration_ms.len() == HYDRATION_SAMPLE_LIMIT {
+            self.hydration_ms.pop_front();
+        }
+        self.hydration_ms.push_back(elapsed.as_millis() as u64);
+        self.dirty = true;
+    }
+
+    pub fn absorb_pending_api_calls(&mut self, stats: &ApiStats) {
+        let Ok(mut pending) = stats.pending_calls.lock() else {
+            return;
+        };
+        for (endpoint, count) in std::mem::take(&mut *pending) {
+            *self.api_calls.entry(endpoint.to_string()).or_default() += count;
+            self.dirty = true;
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self {
+            dirty: true,
+            ..Self::default()
+        };
+    }
+
+    /// Features sorted by use, most used first.
+    pub fn top_features(&self) -> Vec<(&str, u64)> {
+        let mut features: Vec<_> = self
+            .features
+            .iter()
+            .map(|(name, count)| (name.as_str(), *count))
+            .collect();
+        features.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        features
+    }
+
+    pub fn api_calls(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.api_calls
+            .iter()
+            .map(|(name, count)| (name.as_str(), *count))
+    }
+
+    pub fn hydration_samples(&self) -> usize {
+        self.hydration_ms.len()
+    }
+
+    /// Nearest-rank percentile of recorded hydration times, in milliseconds.
+    pub fn hydration_percentile(&self, percentile: f64) -> Option<u64> {
+        if self.hydration_ms.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<u64> = self.hydration_ms.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = ((percentile / 100.0) * sorted.len() as f64).ceil() as usize;
+        Some(sorted[rank.clamp(1, sorted.len()) - 1])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        let mut stats = UsageStats::default();
+        assert_eq!(stats.hydration_percentile(50.0), None);
+        for ms in 1..=100 {
+            stats.record_hydration(Duration::from_millis(ms));
+        }
+        assert_eq!(stats.hydration_percentile(50.0), Some(50));
+        assert_eq!(stats.hydration_percentile(90.0), Some(90));
+        assert_eq!(stats.hydration_percentile(99.0), Some(99));
+
+        stats.record_feature("search");
+        stats.record_feature("search");
+        stats.record_feature("favorite");
+        assert_eq!(stats.top_features(), vec![("search", 2), ("favorite", 1)]);
+
+        let api = ApiStats::default();
+        api.record_api_call("rpc");
+        api.record_api_call("rpc");
+        api.record_cache_lookup("chain id", false);
+        api.record_cache_lookup("chain id", true);
+        stats.absorb_pending_api_calls(&api);
+        stats.absorb_pending_api_calls(&api);
+        assert_eq!(stats.api_calls().collect::<Vec<_>>(), vec![("rpc", 2)]);
+        assert_eq!(api.cache_lookups(), vec![("chain id", 1, 1)]);
+        assert!(ApiStats::default().cache_lookups().is_empty());
+    }
+}
@@ -0,0 +1,417 @@
+use alloy::primitives::{Address, B256, I256, U256, keccak256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A contract's storage layout, as solc emits it in `storageLayout`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageLayout {
+    pub storage: Vec<StorageEntry>,
+    #[serde(default)]
+    pub types: BTreeMap<String, StorageType>,
+}
+
+/// A state variable or struct member. `slot` is relative to the enclosing
+/// struct for members, and decimal like everything solc reports here.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageEntry {
+    pub label: String,
+    pub slot: String,
+    #[serde(default)]
+    pub offset: usize,
+    #[serde(rename = "type")]
+    pub type_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageType {
+    /// `inplace`, `mapping`, `dynamic_array` or `bytes`.
+    pub encoding: String,
+    pub label: String,
+    pub number_of_bytes: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub members: Option<Vec<StorageEntry>>,
+}
+
+impl StorageType {
+    fn size(&self) -> usize {
+        self.number_of_bytes.parse().unwrap_or(32)
+    }
+}
+
+/// What a storage word holds for one variable, enough to decode it without
+/// the layout. Pins keep these so mapping entries survive restarts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlotVariable {
+    pub label: String,
+    pub type_label: String,
+    pub encoding: String,
+    pub offset: usize,
+    pub bytes: usize,
+}
+
+impl SlotVariable {
+    fn new(label: String, offset: usize, ty: &StorageType) -> Self {
+        Self {
+            label,
+            type_label: ty.label.clone(),
+            encoding: ty.encoding.clone(),
+            offset,
+            bytes: ty.size().min(32),
+        }
+    }
+
+    /// `owner (address) = 0x…`.
+    pub fn describe(&self, word: &B256) -> String {
+        format!(
+            "{} ({}) = {}",
+            self.label,
+            self.type_label,
+            self.decode(word)
+        )
+    }
+
+    fn decode(&self, word: &B256) -> String {
+        match self.encoding.as_str() {
+            "mapping" => "(mapping, entries hashed elsewhere)".into(),
+            "dynamic_array" => format!("length {}", U256::from_be_bytes(word.0)),
+            "bytes" => decode_short_bytes(word, self.type_label == "string"),
+            _ => self.decode_inplace(word),
+        }
+    }
+
+    fn decode_inplace(&self, word: &B256) -> String {
+        let end = 32usize.saturating_sub(self.offset);
+        let start = end.saturating_sub(self.bytes);
+        let bytes = &word.0[start..end];
+        let mut padded = [0u8; 32];
+        padded[32 - bytes.len()..].copy_from_slice(bytes);
+        let label = self.type_label.as_str();
+        if label.starts_with("address") || label.starts_with("contract ") {
+            Address::from_slice(&padded[12..]).to_checksum(None)
+        } else if label == "bool" {
+            (padded[31] != 0).to_string()
+        } else if label.starts_with("uint") || label.starts_with("enum ") {
+            format_number(U256::from_be_bytes(padded))
+        } else if label.starts_with("int") {
+            // Sign-extend from the variable's width.
+            if bytes.first().is_some_and(|byte| byte & 0x80 != 0) {
+                padded[..32 - bytes.len()].fill(0xff);
+            }
+            let value = I256::from_be_bytes(padded);
+            match value.is_negative() {
+                true => format!("-{}", format_number(value.unsigned_abs())),
+                false => format_number(value.unsigned_abs()),
+            }
+        } else {
+            format!("0x{}", alloy::hex::encode(bytes))
+        }
+    }
+}
+
+/// Inline `string`/`bytes` up to 31 bytes; longer values only report length.
+fn decode_short_bytes(word: &B256, text: bool) -> String {
+    let last = word.0[31];
+    if last & 1 == 1 {
+        let length = (U256::from_be_bytes(word.0) - U256::from(1)) / U256::from(2);
+        return format!("({length} bytes, stored separately)");
+    }
+    let content = &word.0[..(last / 2).min(31) as usize];
+    match text {
+        true => format!("{:?}", String::from_utf8_lossy(content)),
+        false => format!("0x{}", alloy::hex::encode(content)),
+    }
+}
+
+/// Decimal, with long runs of trailing zeros folded into an exponent that
+/// is a multiple of three: `100e18` rather than twenty zeros.
+fn format_number(value: U256) -> String {
+    let digits = value.to_string();
+    let zeros = digits.len() - digits.trim_end_matches('0').len();
+    if value.is_zero() || zeros < 6 {
+        return digits;
+    }
+    let exponent = zeros - zeros % 3;
+    format!("{}e{exponent}", &digits[..digits.len() - exponent])
+}
+
+fn slot_number(slot: &str) -> U256 {
+    U256::from_str_radix(slot, 10).unwrap_or_default()
+}
+
+impl StorageLayout {
+    /// Variables stored in `slot`, flattening structs held in place.
+    pub fn variables_at(&self, slot: B256) -> Vec<SlotVariable> {
+        let target = U256::from_be_bytes(slot.0);
+        let mut found = Vec::new();
+        self.collect(&self.storage, U256::ZERO, "", target, &mut found);
+        found
+    }
+
+    fn collect(
+        &self,
+        entries: &[StorageEntry],
+        base: U256,
+        prefix: &str,
+        target: U256,
+        found: &mut Vec<SlotVariable>,
+    ) {
+        for entry in entries {
+            let Some(ty) = self.types.get(&entry.type_id) else {
+                continue;
+            };
+            let slot = base + slot_number(&entry.slot);
+            let label = format!("{prefix}{}", entry.label);
+            match ty.members.as_ref() {
+                Some(members) => self.collect(members, slot, &format!("{label}."), target, found),
+                None if slot == target => found.push(SlotVariable::new(label, entry.offset, ty)),
+                None => {}
+            }
+        }
+    }
+
+    /// Resolve a variable path such as `owner`, `balances[0xabc…]`,
+    /// `allowance[0x1…][0x2…]`, `queue[3]` or `config.fee` to its slot and
+    /// the variables that slot holds.
+    pub fn resolve(&self, path: &str) -> Result<(B256, Vec<SlotVariable>), String> {
+        let path = path.trim();
+        let name_end = path.find(['[', '.']).unwrap_or(path.len());
+        let name = &path[..name_end];
+        let entry = self
+            .storage
+            .iter()
+            .find(|entry| entry.label == name)
+            .ok_or_else(|| format!("No state variable named {name}"))?;
+        let mut slot = slot_number(&entry.slot);
+        let mut offset = entry.offset;
+        let mut type_id = entry.type_id.clone();
+        let mut rest = &path[name_end..];
+        while !rest.is_empty() {
+            let ty = self.type_of(&type_id)?;
+            if let Some(after) = rest.strip_prefix('[') {
+                let close = after
+                    .find(']')
+                    .ok_or_else(|| format!("Missing ] in {path}"))?;
+                let index = after[..close].trim();
+                rest = &after[close + 1..];
+                (slot, offset, type_id) = self.index(ty, slot, index)?;
+            } else if let Some(after) = rest.strip_prefix('.') {
+                let end = after.find(['[', '.']).unwrap_or(after.len());
+                let member_name = &after[..end];
+                rest = &after[end..];
+                let member = ty
+                    .members
+                    .iter()
+                    .flatten()
+                    .find(|member| member.label == member_name)
+                    .ok_or_else(|| format!("{} has no member {member_name}", ty.label))?;
+                slot += slot_number(&member.slot);
+                offset = member.offset;
+                type_id = member.type_id.clone();
+            } else {
+                return Err(format!("Cannot read {rest} in {path}"));
+            }
+        }
+        let ty = self.type_of(&type_id)?;
+        let variables = match ty.members.as_ref() {
+            // A pin reads one word: show the struct members in its first slot.
+            Some(members) => members
+                .iter()
+                .filter(|member| slot_number(&member.slot).is_zero())
+                .filter_map(|member| {
+                    let member_ty = self.types.get(&member.type_id)?;
+                    Some(SlotVariable::new(
+                        format!("{path}.{}", member.label),
+                        member.offset,
+                        member_ty,
+                    ))
+                })
+                .collect(),
+            None => vec![SlotVariable::new(path.to_string(), offset, ty)],
+        };
+        Ok((slot.into(), variables))
+    }
+
+    fn type_of(&self, type_id: &str) -> Result<&StorageType, String> {
+        self.types
+            .get(type_id)
+            .ok_or_else(|| format!("Storage layout lacks type {type_id}"))
+    }
+
+    /// Step into a mapping entry or array element.
+    fn index(
+        &self,
+        ty: &StorageType,
+        slot: U256,
+        index: &str,
+    ) -> Result<(U256, usize, String), String> {
+        if ty.encoding == "mapping" {
+            let (Some(key), Some(value)) = (ty.key.as_ref(), ty.value.as_ref()) else {
+                return Err(format!("{} lacks key or value types", ty.label));
+            };
+            let key_ty = self.type_of(key)?;
+            let mut preimage = encode_key(&key_ty.label, index)?;
+            preimage.extend_from_slice(&slot.to_be_bytes::<32>());
+            let slot = U256::from_be_bytes(keccak256(preimage).0);
+            return Ok((slot, 0, value.clone()));
+        }
+        let Some(base) = ty.base.as_ref() else {
+            return Err(format!("{} cannot be indexed", ty.label));
+        };
+        let index: usize = index
+            .parse()
+            .map_err(|_| format!("{index} is not an array index"))?;
+        let start = match ty.encoding.as_str() {
+            "dynamic_array" => U256::from_be_bytes(keccak256(slot.to_be_bytes::<32>()).0),
+            _ => slot,
+        };
+        let size = self.type_of(base)?.size();
+        let (slot, offset) = if size < 32 {
+            let per_slot = 32 / size;
+            (
+                start + U256::from(index / per_slot),
+                (index % per_slot) * size,
+            )
+        } else {
+            (start + U256::from(index * size.div_ceil(32)), 0)
+        };
+        Ok((slot, offset, base.clone()))
+    }
+}
+
+/// Mapping key as hashed with the slot: value types padded to a word,
+/// `string`/`bytes` keys as their raw bytes.
+fn encode_key(type_label: &str, key: &str) -> Result<Vec<u8>, String> {
+    let invalid = || format!("{key} is not a valid {type_label} key");
+    let parse_u256 = |text: &str| match text.strip_prefix("0x") {
+        Some(hex) => U256::from_str_radix(hex, 16),
+        None => U256::from_str_radix(text, 10),
+    };
+    if type_label == "string" {
+        return Ok(key.trim_matches('"').as_bytes().to_vec());
+    }
+    if type_label == "bytes" {
+        return alloy::hex::decode(key).map_err(|_| invalid());
+    }
+    let word: [u8; 32] = if type_label.starts_with("address") || type_label.starts_with("contract ")
+    {
+        key.parse::<Address>().map_err(|_| invalid())?.into_word().0
+    } else if type_label == "bool" {
+        match key {
+            "true" => U256::from(1),
+            "false" => U256::ZERO,
+            _ => return Err(invalid()),
+        }
+        .to_be_bytes()
+    } else if type_label.starts_with("uint") || type_label.starts_with("enum ") {
+        parse_u256(key).map_err(|_| invalid())?.to_be_bytes()
+    } else if type_label.starts_with("int") {
+        let negative = key.starts_with('-');
+        let magnitude = parse_u256(key.trim_start_matches('-')).map_err(|_| invalid())?;
+        let value = I256::try_from(magnitude).map_err(|_| invalid())?;
+        match negative {
+            true => -value,
+            false => value,
+        }
+        .to_be_bytes()
+    } else if type_label.starts_with("bytes") {
+        // bytesN keys are left-aligned.
+        let bytes = alloy::hex::decode(key).map_err(|_| invalid())?;
+        if bytes.len() > 32 {
+            return Err(invalid());
+        }
+        let mut word = [0u8; 32];
+        word[..bytes.len()].copy_from_slice(&bytes);
+        word
+    } else {
+        return Err(invalid());
+    };
+    Ok(word.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn token_layout() -> StorageLayout {
+        serde_json::from_value(json!({
+            "storage": [
+                {"label": "owner", "slot": "0", "offset": 0, "type": "t_address"},
+                {"label": "paused", "slot": "0", "offset": 20, "type": "t_bool"},
+                {"label": "balances", "slot": "1", "offset": 0, "type": "t_mapping(t_address,t_uint256)"},
+                {"label": "name", "slot": "2", "offset": 0, "type": "t_string_storage"},
+                {"label": "fees", "slot": "3", "offset": 0, "type": "t_array(t_uint64)dyn_storage"}
+            ],
+            "types": {
+                "t_address": {"encoding": "inplace", "label": "address", "numberOfBytes": "20"},
+                "t_bool": {"encoding": "inplace", "label": "bool", "numberOfBytes": "1"},
+                "t_uint256": {"encoding": "inplace", "label": "uint256", "numberOfBytes": "32"},
+                "t_uint64": {"encoding": "inplace", "label": "uint64", "numberOfBytes": "8"},
+                "t_string_storage": {"encoding": "bytes", "label": "string", "numberOfBytes": "32"},
+                "t_mapping(t_address,t_uint256)": {
+                    "encoding": "mapping", "label": "mapping(address => uint256)", "numberOfBytes": "32",
+                    "key": "t_address", "value": "t_uint256"
+                },
+                "t_array(t_uint64)dyn_storage": {
+                    "encoding": "dynamic_array", "label": "uint64[]", "numberOfBytes": "32", "base": "t_uint64"
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn decodes_packed_slots_and_resolves_mapping_entries() {
+        let layout = token_layout();
+        let mut word = [0u8; 32];
+        word[11] = 1;
+        word[12..].copy_from_slice(&[0xab; 20]);
+        let described: Vec<String> = layout
+            .variables_at(B256::ZERO)
+            .iter()
+            .map(|variable| variable.describe(&B256::from(word)))
+            .collect();
+        assert_eq!(
+            described,
+            vec![
+                format!("owner (address) = {}", Address::repeat_byte(0xab)),
+                "paused (bool) = true".to_string(),
+            ]
+        );
+
+        // keccak256(abi.encode(holder, 1)), as solc lays out `balances[holder]`.
+        let holder = Address::repeat_byte(0x11);
+        let mut preimage = holder.into_word().to_vec();
+        preimage.extend_from_slice(&U256::from(1).to_be_bytes::<32>());
+        let (slot, variables) = layout.resolve(&format!("balances[{holder}]")).unwrap();
+        assert_eq!(slot, keccak256(preimage));
+        let amount = B256::from(U256::from(100) * U256::from(10).pow(U256::from(18)));
+        assert_eq!(
+            variables[0].describe(&amount),
+            format!("balances[{holder}] (uint256) = 100e18")
+        );
+
+        let (slot, variables) = layout.resolve("fees[5]").unwrap();
+        let start = U256::from_be_bytes(keccak256(U256::from(3).to_be_bytes::<32>()).0);
+        assert_eq!(slot, B256::from(start + U256::from(1)));
+        assert_eq!(variables[0].offset, 8);
+
+        let mut short = [0u8; 32];
+        short[..3].copy_from_slice(b"USD");
+        short[31] = 6;
+        let (_, variables) = layout.resolve("name").unwrap();
+        assert_eq!(
+            variables[0].describe(&B256::from(short)),
+            "name (string) = \"USD\""
+        );
+        assert!(layout.resolve("supply").is_err());
+        assert!(layout.resolve("balances[nope]").is_err());
+    }
+}
@@ -0,0 +1,291 @@
+use super::{AddressRef, SelectedEntity, TransactionRef, labels::UserLabel};
+use crate::{storage::FavoriteRecord, ui::util::short_hex};
+
+/// Most suggestions shown under the search prompt.
+pub const MAX_SUGGESTIONS: usize = 6;
+
+/// Recent queries kept for suggestions.
+pub const HISTORY_LEN: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuggestionSource {
+    Favorite,
+    Label,
+    /// The bundled registry of well-known addresses.
+    Known,
+    Recent,
+}
+
+impl SuggestionSource {
+    pub fn label(self) -> &'static str {
+        match self {
+            SuggestionSource::Favorite => "favorite",
+            SuggestionSource::Label => "label",
+            SuggestionSource::Known => "known",
+            SuggestionSource::Recent => "recent",
+        }
+    }
+}
+
+/// One entry of the search dropdown. Entries with an entity open it
+/// directly; the rest submit `query` like a typed search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub title: String,
+    pub query: String,
+    pub source: SuggestionSource,
+    pub entity: Option<SelectedEntity>,
+}
+
+impl Suggestion {
+    pub fn favorite_address(record: &FavoriteRecord) -> Self {
+        let label = record
+            .label
+            .clone()
+            .unwrap_or_else(|| format!("Address {}", short_hex(&record.identifier)));
+        Self {
+            title: format!("{label} [{}]", record.chain),
+            query: record.identifier.clone(),
+            source: SuggestionSource::Favorite,
+            entity: Some(SelectedEntity::Address(AddressRef {
+                label,
+                address: record.identifier.clone(),
+                chain: record.chain.clone(),
+            })),
+        }
+    }
+
+    pub fn favorite_transaction(record: &FavoriteRecord) -> Self {
+        let label = record
+            .label
+            .clone()
+            .unwrap_or_else(|| format!("Txn {}", short_hex(&record.identifier)));
+        Self {
+            title: format!("{label} [{}]", record.chain),
+            query: record.identifier.clone(),
+            source: SuggestionSource::Favorite,
+            entity: Some(SelectedEntity::Transaction(TransactionRef {
+                label,
+                hash: record.identifier.clone(),
+                chain: record.chain.clone(),
+            })),
+        }
+    }
+
+    /// A user label, or a bundled one when `source` is [`SuggestionSource::Known`].
+    pub fn label(label: &UserLabel, source: SuggestionSource) -> Self {
+        let address = label.address.to_checksum(None);
+        let mut title = format!("{} [{}]", label.name, label.chain);
+        for tag in &label.tags {
+            title.push_str(&format!(" #{tag}"));
+        }
+        Self {
+            title,
+            query: address.clone(),
+            source,
+            entity: Some(SelectedEntity::Address(AddressRef {
+                label: label.name.clone(),
+                address,
+                chain: label.chain.clone(),
+            })),
+        }
+    }
+
+    pub fn recent(query: &str) -> Self {
+        Self {
+            title: query.to_string(),
+            query: query.to_string(),
+            source: SuggestionSource::Recent,
+            entity: None,
+        }
+    }
+
+    /// 0 when the title or query starts with `needle`, 1 when either
+    /// contains it, `None` otherwise. `needle` is lowercase.
+    fn rank(&self, needle: &str) -> Option<u8> {
+        let title = self.title.to_lowercase();
+        let query = self.query.to_lowercase();
+        let query = query.strip_prefix("0x").unwrap_or(&query);
+        let bare = needle.strip_prefix("0x").unwrap_or(needle);
+        if title.starts_with(needle) || (!bare.is_empty() && query.starts_with(bare)) {
+            Some(0)
+        } else if title.contains(needle) || (!bare.is_empty() && query.contains(bare)) {
+            Some(1)
+        } else {
+            None
+        }
+    }
+}
+
+/// Score `needle` as a subsequence of `haystack`, both lowercase. Each
+/// matched character scores 1, plus 2 when it follows the previous match
+/// and 3 when it starts a word.
+fn fuzzy_score(haystack: &str, needle: &str) -> Option<u32> {
+    let mut needle_chars = needle.chars().filter(|c| !c.is_whitespace()).peekable();
+    needle_chars.peek()?;
+    let mut score = 0;
+    let mut previous: Option<char> = None;
+    let mut previous_matched = false;
+    for c in haystack.chars() {
+        let Some(&wanted) = needle_chars.peek() else {
+            break;
+        };
+        let matched = c == wanted;
+        if matched {
+            needle_chars.next();
+            score += 1;
+            if previous_matched {
+                score += 2;
+            }
+            if previous.is_none_or(|p| !p.is_alphanumeric()) {
+                score += 3;
+            }
+        }
+        previous_matched = matched;
+        previous = Some(c);
+    }
+    needle_chars.peek().is_none().then_some(score)
+}
+
+/// Keep the first suggestion per query, up to [`MAX_SUGGESTIONS`].
+fn unique<'a>(ranked: impl IntoIterator<Item = &'a Suggestion>) -> Vec<Suggestion> {
+    let mut seen: Vec<String> = Vec::new();
+    let mut matches = Vec::new();
+    for candidate in ranked {
+        let key = candidate.query.to_lowercase();
+        if seen.contains(&key) {
+            continue;
+        }
+        seen.push(key);
+        matches.push(candidate.clone());
+        if matches.len() == MAX_SUGGESTIONS {
+            break;
+        }
+    }
+    matches
+}
+
+/// Candidates whose title or query contains the letters of `input` in
+/// order, best first. Consecutive letters and word starts score higher,
+/// so "treas" prefers "Treasury" over "The Real Estate Asset Store".
+pub fn fuzzy(candidates: &[Suggestion], input: &str) -> Vec<Suggestion> {
+    let needle = input.trim().to_lowercase();
+    let mut scored: Vec<(u32, &Suggestion)> = candidates
+        .iter()
+        .filter_map(|candidate| {
+            let haystack = format!("{} {}", candidate.title, candidate.query).to_lowercase();
+            fuzzy_score(&haystack, &needle).map(|score| (score, candidate))
+        })
+        .collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    unique(scored.into_iter().map(|(_, candidate)| candidate))
+}
+
+/// Move `query` to the front of the recent searches, dropping duplicates
+/// and anything past [`HISTORY_LEN`].
+pub fn push_history(history: &mut Vec<String>, query: &str) {
+    let query = query.trim();
+    if query.is_empty() {
+        return;
+    }
+    history.retain(|entry| !entry.eq_ignore_ascii_case(query));
+    history.insert(0, query.to_string());
+    history.truncate(HISTORY_LEN);
+}
+
+/// Candidates matching `input`, prefix matches first and otherwise in
+/// candidate order. An empty input lists only recent searches. Later
+/// candidates repeating an earlier query are dropped.
+pub fn matching(candidates: &[Suggestion], input: &str) -> Vec<Suggestion> {
+    let needle = input.trim().to_lowercase();
+    let mut ranked: Vec<(u8, &Suggestion)> = candidates
+        .iter()
+        .filter_map(|candidate| {
+            if needle.is_empty() {
+                (candidate.source == SuggestionSource::Recent).then_some((0, candidate))
+            } else {
+                candidate.rank(&needle).map(|rank| (rank, candidate))
+            }
+        })
+        .collect();
+    ranked.sort_by_key(|(rank, _)| *rank);
+    unique(ranked.into_iter().map(|(_, candidate)| candidate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::Address;
+
+    #[test]
+    fn ranks_prefix_matches_first_and_dedupes_queries() {
+        let treasury = UserLabel {
+            chain: "Mainnet".into(),
+            address: Address::repeat_byte(0xab),
+            name: "DAO treasury".into(),
+            tags: vec!["multisig".into()],
+        };
+        let favorite = FavoriteRecord {
+            label: Some("Treasury".into()),
+            identifier: Address::repeat_byte(0xab).to_checksum(None),
+            chain: "Mainnet".into(),
+            order: 0,
+        };
+        let candidates = vec![
+            Suggestion::label(&treasury, SuggestionSource::Label),
+            Suggestion::favorite_address(&favorite),
+            Suggestion::recent("latest"),
+        ];
+
+        // Both name the same address; the favorite's prefix match wins.
+        let matches = matching(&candidates, "treas");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].source, SuggestionSource::Favorite);
+
+        let matches = matching(&candidates, "0xABAB");
+        assert_eq!(matches.len(), 1);
+        assert!(matches!(
+            matches[0].entity,
+            Some(SelectedEntity::Address(ref addr)) if addr.label == "DAO treasury"
+        ));
+
+        assert_eq!(
+            matching(&candidates, "#multisig")[0].title,
+            "DAO treasury [Mainnet] #multisig"
+        );
+        assert_eq!(matching(&candidates, "")[0].query, "latest");
+
+        let mut history = vec!["latest".to_string(), "0x01".to_string()];
+        push_history(&mut history, " 0x01 ");
+        assert_eq!(history, vec!["0x01".to_string(), "latest".to_string()]);
+    }
+
+    #[test]
+    fn fuzzy_search_prefers_word_starts_and_runs() {
+        let label = |name: &str, byte: u8| {
+            Suggestion::label(
+                &UserLabel {
+                    chain: "Mainnet".into(),
+                    address: Address::repeat_byte(byte),
+                    name: name.into(),
+                    tags: Vec::new(),
+                },
+                SuggestionSource::Known,
+            )
+        };
+        let candidates = vec![
+            label("The Real Estate Asset Store", 1),
+            label("DAO Treasury", 2),
+            label("Uniswap V3: Router", 3),
+        ];
+        let matches = fuzzy(&candidates, "treas");
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].title, "DAO Treasury [Mainnet]");
+        assert_eq!(
+            fuzzy(&candidates, "uni v3")[0].title,
+            "Uniswap V3: Router [Mainnet]"
+        );
+        assert!(fuzzy(&candidates, "zzz").is_empty());
+        assert!(fuzzy(&candidates, "  ").is_empty());
+    }
+}
@@ -0,0 +1,292 @@
+use super::{SecretsState, chains::resolve_chain, stats::ApiStats};
+use crate::storage::SecretKey;
+use alloy::{
+    consensus::Transaction as ConsensusTransaction,
+    network::TransactionResponse,
+    primitives::{Address, Bytes, U256},
+    rpc::types::{
+        Transaction,
+        trace::geth::{AccountState, CallFrame, DiffMode},
+    },
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{collections::BTreeMap, time::Duration};
+
+/// Provider name of the Tenderly credentials in the secrets store.
+pub const PROVIDER: &str = "tenderly";
+
+/// Credential fields with their titles in the secrets form.
+pub const FIELDS: [(&str, &str); 3] = [
+    ("account", "Tenderly Account Slug"),
+    ("project", "Tenderly Project Slug"),
+    ("access_key", "Tenderly Access Key"),
+];
+
+const API_URL: &str = "https://api.tenderly.co/api/v1";
+
+/// Where transaction traces and replays come from on a chain.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TraceBackend {
+    /// `debug_traceTransaction` on the chain's own RPC (needs a debug
+    /// namespace, and an archive node for old blocks).
+    #[default]
+    Rpc,
+    /// Tenderly's simulation API, replaying the transaction at its position.
+    Tenderly,
+}
+
+impl TraceBackend {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.to_ascii_lowercase().as_str() {
+            "rpc" | "node" => Some(TraceBackend::Rpc),
+            "tenderly" => Some(TraceBackend::Tenderly),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TraceBackend::Rpc => "RPC debug_traceTransaction",
+            TraceBackend::Tenderly => "Tenderly",
+        }
+    }
+}
+
+/// Tenderly project credentials bound to the chain being traced.
+#[derive(Debug, Clone)]
+pub struct Tenderly {
+    account: String,
+    project: String,
+    access_key: String,
+    chain_id: u64,
+}
+
+/// What a Tenderly replay reports, in the shapes the RPC tracers return.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Simulation {
+    /// Call tree as `callTracer` would report it.
+    pub call_trace: Option<CallFrame>,
+    /// ETH balances before and after, as the `prestateTracer` diff mode.
+    pub balances: DiffMode,
+}
+
+impl Tenderly {
+    /// Credentials from the secrets store for a chain Tenderly can know;
+    /// `None` when any field is missing or the chain has no public id.
+    pub fn for_chain(secrets: &SecretsState, chain: &str) -> Option<Self> {
+        let field = |field: &str| {
+            secrets
+                .get(&SecretKey::ProviderCredential {
+                    provider: PROVIDER.into(),
+                    field: field.into(),
+                })
+                .map(str::to_string)
+        };
+        Some(Self {
+            account: field("account")?,
+            project: field("project")?,
+            access_key: field("access_key")?,
+            chain_id: resolve_chain(chain)?.chain_id,
+        })
+    }
+
+    /// Replay a mined transaction at its index in its block, without saving
+    /// the simulation to the project.
+    pub async fn replay(
+        &self,
+        stats: &ApiStats,
+        transaction: &Transaction,
+        gas_price: u128,
+    ) -> Result<Simulation, String> {
+        let block = transaction
+            .block_number
+            .ok_or("Tenderly can only replay mined transactions")?;
+        let body = json!({
+            "network_id": self.chain_id.to_string(),
+            "block_number": block,
+            "transaction_index": transaction.transaction_index,
+            "from": transaction.from(),
+            "to": transaction.to(),
+            "input": transaction.input(),
+            "gas": transaction.gas_limit(),
+            "gas_price": gas_price.to_string(),
+            "value": transaction.value().to_string(),
+            "save": false,
+            "save_if_fails": false,
+            "simulation_type": "full",
+        });
+        let client = reqwest::Client::builder()
+            .user_agent("evm-tui/0.1.0")
+            .timeout(Duration::from_secs(30))
+            .build()
+            .map_err(|err| err.to_string())?;
+        stats.record_api_call("tenderly");
+        let response = client
+            .post(format!(
+                "{API_URL}/account/{}/project/{}/simulate",
+                self.account, self.project
+            ))
+            .header("X-Access-Key", &self.access_key)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|err| format!("Tenderly request failed: {err}"))?;
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|err| format!("Tenderly response unreadable: {err}"))?;
+        if !status.is_success() {
+            let message = serde_json::from_str::<ErrorResponse>(&text)
+                .map(|error| error.error.message)
+                .unwrap_or(text);
+            return Err(format!("Tenderly returned {status}: {message}"));
+        }
+        parse_simulation(&text)
+    }
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: ErrorBody,
+}
+
+#[derive(Deserialize)]
+struct ErrorBody {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct SimulateResponse {
+    transaction: SimulatedTransaction,
+}
+
+#[derive(Deserialize)]
+struct SimulatedTransaction {
+    transaction_info: TransactionInfo,
+}
+
+#[derive(Deserialize)]
+struct TransactionInfo {
+    call_trace: Option<TenderlyCall>,
+    #[serde(default)]
+    balance_diff: Option<Vec<BalanceChange>>,
+}
+
+#[derive(Deserialize)]
+struct TenderlyCall {
+    #[serde(default)]
+    call_type: String,
+    from: Address,
+    to: Option<Address>,
+    #[serde(default)]
+    input: Option<Bytes>,
+    #[serde(default)]
+    value: Option<String>,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    calls: Option<Vec<TenderlyCall>>,
+}
+
+#[derive(Deserialize)]
+struct BalanceChange {
+    address: Address,
+    original: String,
+    dirty: String,
+}
+
+/// Tenderly writes amounts as decimal or `0x` hex strings.
+fn amount(value: &str) -> Option<U256> {
+    value.parse().ok()
+}
+
+fn call_frame(call: TenderlyCall) -> CallFrame {
+    CallFrame {
+        from: call.from,
+        to: call.to,
+        input: call.input.unwrap_or_default(),
+        value: call.value.as_deref().and_then(amount),
+        error: call.error.filter(|error| !error.is_empty()),
+        typ: call.call_type,
+        calls: call
+            .calls
+            .unwrap_or_default()
+            .into_iter()
+            .map(call_frame)
+            .collect(),
+        ..CallFrame::default()
+    }
+}
+
+fn parse_simulation(body: &str) -> Result<Simulation, String> {
+    let response: SimulateResponse =
+        serde_json::from_str(body).map_err(|err| format!("unexpected Tenderly response: {err}"))?;
+    let info = response.transaction.transaction_info;
+    let mut balances = DiffMode {
+        pre: BTreeMap::new(),
+        post: BTreeMap::new(),
+    };
+    for change in info.balance_diff.unwrap_or_default() {
+        let state = |balance: &str| AccountState {
+            balance: amount(balance),
+            ..AccountState::default()
+        };
+        balances.pre.insert(change.address, state(&change.original));
+        balances.post.insert(change.address, state(&change.dirty));
+    }
+    Ok(Simulation {
+        call_trace: info.call_trace.map(call_frame),
+        balances,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_tenderly_traces_to_tracer_shapes() {
+        let body = r#"{"transaction": {"transaction_info": {
+            "call_trace": {
+                "call_type": "CALL",
+                "from": "0x0000000000000000000000000000000000000001",
+                "to": "0x0000000000000000000000000000000000000002",
+                "input": "0x",
+                "value": "0xde0b6b3a7640000",
+                "calls": [{
+                    "call_type": "DELEGATECALL",
+                    "from": "0x0000000000000000000000000000000000000002",
+                    "to": "0x0000000000000000000000000000000000000003",
+                    "value": "5",
+                    "error": "execution reverted",
+                    "calls": null
+                }]
+            },
+            "balance_diff": [{
+                "address": "0x0000000000000000000000000000000000000001",
+                "original": "2000000000000000000",
+                "dirty": "1000000000000000000",
+                "is_miner": false
+            }]
+        }}}"#;
+        let simulation = parse_simulation(body).unwrap();
+        let frame = simulation.call_trace.unwrap();
+        assert_eq!(frame.typ, "CALL");
+        assert_eq!(frame.value, Some(U256::from(1_000_000_000_000_000_000u128)));
+        assert_eq!(frame.calls[0].typ, "DELEGATECALL");
+        assert_eq!(frame.calls[0].value, Some(U256::from(5)));
+        assert!(frame.calls[0].error.is_some());
+        let alice = Address::with_last_byte(1);
+        assert_eq!(
+            simulation.balances.post[&alice].balance,
+            Some(U256::from(1_000_000_000_000_000_000u128))
+        );
+        assert_eq!(
+            TraceBackend::parse("Tenderly"),
+            Some(TraceBackend::Tenderly)
+        );
+        assert!(parse_simulation("{}").is_err());
+    }
+}
@@ -0,0 +1,577 @@
+use super::{
+    chains::resolve_chain,
+    notify::WatchEvent,
+    providers::Rpc,
+    scheduler::{Priority, Scheduler, host_key},
+};
+use crate::{
+    storage::{AlertOutputs, GroupMember},
+    ui::util::short_hex,
+};
+use alloy::{
+    eips::BlockNumberOrTag,
+    primitives::{
+        Address, U256,
+        utils::{format_units, parse_units},
+    },
+    providers::Provider,
+    sol,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    time::Duration,
+};
+use tokio::{task::JoinSet, time::timeout};
+
+sol! {
+    #[sol(rpc)]
+    interface IERC20Balance {
+        function balanceOf(address owner) external view returns (uint256);
+        function symbol() external view returns (string);
+        function decimals() external view returns (uint8);
+    }
+}
+
+/// How often thresholds are re-evaluated; about one mainnet block.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// What a threshold alert watches.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Condition {
+    /// The latest block's base fee below `wei` (or above it, with `above`).
+    BaseFee {
+        #[serde(default)]
+        above: bool,
+        wei: u64,
+    },
+    /// Any change of `holder`'s balance of the ERC-20 `token`.
+    TokenBalance { token: String, holder: String },
+}
+
+impl Condition {
+    /// Read `base fee < 10` (gwei, `>` for above) or
+    /// `balance <token> <holder>`.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        let lower = input.to_ascii_lowercase();
+        if let Some(rest) = lower
+            .strip_prefix("base fee")
+            .or_else(|| lower.strip_prefix("basefee"))
+        {
+            let rest = rest.trim_start();
+            let (above, amount) = match rest.chars().next() {
+                Some('<') => (false, &rest[1..]),
+                Some('>') => (true, &rest[1..]),
+                _ => return Err("base fee takes < or > and a gwei amount".into()),
+            };
+            let amount = amount.trim();
+            let amount = amount.strip_suffix("gwei").unwrap_or(amount).trim();
+            let wei = parse_units(amount, "gwei")
+                .ok()
+                .and_then(|wei| u64::try_from(wei.get_absolute()).ok())
+                .ok_or_else(|| format!("{amount} is not a gwei amount"))?;
+            return Ok(Condition::BaseFee { above, wei });
+        }
+        let words: Vec<&str> = input.split_whitespace().collect();
+        match words.as_slice() {
+            [keyword, token, holder] if keyword.eq_ignore_ascii_case("balance") => {
+                for (what, value) in [("token", token), ("holder", holder)] {
+                    if value.parse::<Address>().is_err() {
+                        return Err(format!("{what} must be a 0x address"));
+                    }
+                }
+                Ok(Condition::TokenBalance {
+                    token: token.to_string(),
+                    holder: holder.to_string(),
+                })
+            }
+            _ => Err("expected `base fee < 10` or `balance <token> <holder>`".into()),
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Condition::BaseFee { above, wei } => {
+                let sign = if *above { '>' } else { '<' };
+                write!(f, "base fee {sign} {} gwei", amount(U256::from(*wei), 9))
+            }
+            Condition::TokenBalance { token, holder } => {
+                write!(f, "balance {token} {holder}")
+            }
+        }
+    }
+}
+
+/// A threshold alert, stored under the `alerts.thresholds` setting.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThresholdAlert {
+    pub chain: String,
+    #[serde(flatten)]
+    pub condition: Condition,
+    #[serde(flatten)]
+    pub alerts: AlertOutputs,
+}
+
+impl ThresholdAlert {
+    pub fn key(&self) -> String {
+        format!(
+            "{}:{}",
+            self.chain.to_ascii_lowercase(),
+            self.condition.to_string().to_ascii_lowercase()
+        )
+    }
+
+    /// Check the chain; the condition was checked when parsed.
+    pub fn validate(&self) -> Result<(), String> {
+        match resolve_chain(&self.chain) {
+            Some(_) => Ok(()),
+            None => Err(format!("unknown chain {}", self.chain)),
+        }
+    }
+
+    /// Short description for lists and alerts, e.g. `Base fee < 10 gwei` or
+    /// `0x1234...abcd balance of 0xa0b8...eb48`.
+    pub fn title(&self) -> String {
+        match &self.condition {
+            Condition::BaseFee { .. } => {
+                let mut title = self.condition.to_string();
+                title.replace_range(..1, "B");
+                title
+            }
+            Condition::TokenBalance { token, holder } => {
+                format!("{} balance of {}", short_hex(holder), short_hex(token))
+            }
+        }
+    }
+}
+
+/// One chain's latest base fee and the readings of its balance alerts.
+#[derive(Debug, Clone)]
+pub struct ChainReading {
+    pub chain: String,
+    pub outcome: Result<Readings, String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Readings {
+    pub base_fee: Option<u64>,
+    /// Balance per [`ThresholdAlert::key`], or why it could not be read.
+    pub balances: Vec<(String, Result<TokenBalance, String>)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenBalance {
+    pub value: U256,
+    /// Fetched with the first reading only.
+    pub symbol: Option<String>,
+    pub decimals: Option<u8>,
+}
+
+/// The last value seen for an alert.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Observed {
+    BaseFee { wei: u64, met: bool },
+    Balance(TokenBalance),
+}
+
+/// Threshold alerts with the last value each was evaluated against.
+#[derive(Debug, Default)]
+pub struct ThresholdState {
+    pub alerts: Vec<ThresholdAlert>,
+    observed: HashMap<String, Observed>,
+    errors: HashMap<String, String>,
+}
+
+impl ThresholdState {
+    pub fn new(alerts: Vec<ThresholdAlert>) -> Self {
+        Self {
+            alerts,
+            ..Self::default()
+        }
+    }
+
+    /// Add `alert` unless the same one exists; returns whether it was added.
+    pub fn add(&mut self, alert: ThresholdAlert) -> bool {
+        let key = alert.key();
+        if self.alerts.iter().any(|existing| existing.key() == key) {
+            return false;
+        }
+        self.alerts.push(alert);
+        true
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<ThresholdAlert> {
+        if index >= self.alerts.len() {
+            return None;
+        }
+        let alert = self.alerts.remove(index);
+        let key = alert.key();
+        self.observed.remove(&key);
+        self.errors.remove(&key);
+        Some(alert)
+    }
+
+    /// Move the alert at `index` to the next [`AlertOutputs`].
+    pub fn cycle_alerts(&mut self, index: usize) -> Option<AlertOutputs> {
+        let alert = self.alerts.get_mut(index)?;
+        alert.alerts = alert.alerts.next();
+        Some(alert.alerts)
+    }
+
+    /// The value `alert` was last evaluated against, e.g. `12.5 gwei`.
+    pub fn current(&self, alert: &ThresholdAlert) -> Option<String> {
+        match self.observed.get(&alert.key())? {
+            Observed::BaseFee { wei, .. } => Some(format!("{} gwei", amount(U256::from(*wei), 9))),
+            Observed::Balance(balance) => Some(balance.display()),
+        }
+    }
+
+    pub fn error(&self, alert: &ThresholdAlert) -> Option<&str> {
+        self.errors.get(&alert.key()).map(String::as_str)
+    }
+
+    /// Alerts grouped per chain, each flagged when its token's symbol and
+    /// decimals are still unknown.
+    pub fn batches(&self) -> Vec<(String, Vec<(ThresholdAlert, bool)>)> {
+        let mut batches: BTreeMap<String, Vec<(ThresholdAlert, bool)>> = BTreeMap::new();
+        for alert in &self.alerts {
+            let unknown = !matches!(self.observed.get(&alert.key()), Some(Observed::Balance(_)));
+            batches
+                .entry(alert.chain.to_ascii_lowercase())
+                .or_default()
+                .push((alert.clone(), unknown));
+        }
+        batches
+            .into_values()
+            .map(|alerts| (alerts[0].0.chain.clone(), alerts))
+            .collect()
+    }
+
+    /// Evaluate a poll's readings and return one event per alert that
+    /// fired: a base fee crossing into its threshold (or already past it on
+    /// the first reading), or a balance differing from the last reading.
+    pub fn apply_poll(&mut self, readings: Vec<ChainReading>) -> Vec<(AlertOutputs, WatchEvent)> {
+        let mut raised = Vec::new();
+        for reading in readings {
+            let alerts = self
+                .alerts
+                .iter()
+                .filter(|alert| alert.chain.eq_ignore_ascii_case(&reading.chain));
+            let readings = match reading.outcome {
+                Ok(readings) => readings,
+                Err(err) => {
+                    for alert in alerts {
+                        self.errors.insert(alert.key(), err.clone());
+                    }
+                    continue;
+                }
+            };
+            let mut balances: HashMap<_, _> = readings.balances.into_iter().collect();
+            for alert in alerts {
+                let key = alert.key();
+                let message = match &alert.condition {
+                    Condition::BaseFee { above, wei } => {
+                        let Some(base_fee) = readings.base_fee else {
+                            self.errors
+                                .insert(key, "latest block has no base fee".into());
+                            continue;
+                        };
+                        let met = if *above {
+                            base_fee > *wei
+                        } else {
+                            base_fee < *wei
+                        };
+                        let was_met = matches!(
+                            self.observed.get(&key),
+                            Some(Observed::BaseFee { met: true, .. })
+                        );
+                        self.observed
+                            .insert(key.clone(), Observed::BaseFee { wei: base_fee, met });
+                        self.errors.remove(&key);
+                        if !met || was_met {
+                            continue;
+                        }
+                        format!("now {} gwei", amount(U256::from(base_fee), 9))
+                    }
+                    Condition::TokenBalance { .. } => {
+                        let balance = match balances.remove(&key) {
+                            Some(Ok(balance)) => balance,
+                            Some(Err(err)) => {
+                                self.errors.insert(key, err);
+                                continue;
+                            }
+                            None => continue,
+                        };
+                        self.errors.remove(&key);
+                        let previous = match self.observed.get(&key) {
+                            Some(Observed::Balance(previous)) => previous.clone(),
+                            _ => {
+                                self.observed.insert(key, Observed::Balance(balance));
+                                continue;
+                            }
+                        };
+                        // Metadata is only fetched once; keep it across readings.
+                        let balance = TokenBalance {
+                            symbol: balance.symbol.or_else(|| previous.symbol.clone()),
+                            decimals: balance.decimals.or(previous.decimals),
+                            ..balance
+                        };
+                        let changed = balance.value != previous.value;
+                        let message = format!(
+                            "{} → {}",
+                            TokenBalance {
+                                symbol: None,
+                                ..previous
+                            }
+                            .display(),
+                            balance.display()
+                        );
+                        self.observed.insert(key, Observed::Balance(balance));
+                        if !changed {
+                            continue;
+                        }
+                        message
+                    }
+                };
+                let subject = match &alert.condition {
+                    Condition::TokenBalance { holder, .. } => Some(GroupMember {
+                        address: holder.clone(),
+                        chain: alert.chain.clone(),
+                    }),
+                    Condition::BaseFee { .. } => None,
+                };
+                raised.push((
+                    alert.alerts,
+                    WatchEvent {
+                        source: "threshold",
+                        title: format!("{} ({})", alert.title(), alert.chain),
+                        message,
+                        subject,
+                        transaction: None,
+                    },
+                ));
+            }
+        }
+        raised
+    }
+}
+
+impl TokenBalance {
+    fn display(&self) -> String {
+        let value = match self.decimals {
+            Some(decimals) => amount(self.value, decimals),
+            None => self.value.to_string(),
+        };
+        match &self.symbol {
+            Some(symbol) => format!("{value} {symbol}"),
+            None => value,
+        }
+    }
+}
+
+/// `value` scaled down by `decimals`, without trailing zeros.
+fn amount(value: U256, decimals: u8) -> String {
+    let Ok(mut formatted) = format_units(value, decimals) else {
+        return value.to_string();
+    };
+    if formatted.contains('.') {
+        let trimmed = formatted.trim_end_matches('0').trim_end_matches('.').len();
+        formatted.truncate(trimmed);
+    }
+    formatted
+}
+
+/// A chain, its RPC endpoint and its alerts, flagged as in
+/// [`ThresholdState::batches`].
+pub type AlertBatch = (String, Option<String>, Vec<(ThresholdAlert, bool)>);
+
+/// Read each chain's base fee and watched balances.
+pub async fn poll_alerts(
+    rpc: Rpc,
+    scheduler: Scheduler,
+    batches: Vec<AlertBatch>,
+) -> Vec<ChainReading> {
+    let mut tasks = JoinSet::new();
+    let mut readings = Vec::new();
+    for (chain, rpc_url, alerts) in batches {
+        let Some(url) = rpc_url else {
+            readings.push(ChainReading {
+                outcome: Err(format!("no RPC endpoint for {chain}")),
+                chain,
+            });
+            continue;
+        };
+        let rpc = rpc.clone();
+        let scheduler = scheduler.clone();
+        tasks.spawn(async move {
+            let host = host_key(Some(&url), &chain);
+            let _permit = scheduler.acquire(&host, Priority::Poll).await;
+            let outcome = match timeout(Duration::from_secs(20), read(&rpc, &url, &alerts)).await {
+                Ok(outcome) => outcome,
+                Err(_) => Err("timed out".to_string()),
+            };
+            ChainReading { chain, outcome }
+        });
+    }
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok(reading) = joined {
+            readings.push(reading);
+        }
+    }
+    readings
+}
+
+async fn read(
+    rpc: &Rpc,
+    rpc_url: &str,
+    alerts: &[(ThresholdAlert, bool)],
+) -> Result<Readings, String> {
+    let provider = rpc
+        .connect(rpc_url)
+        .await
+        .map_err(|err| format!("{err:#}"))?;
+    let mut readings = Readings::default();
+    if alerts
+        .iter()
+        .any(|(alert, _)| matches!(alert.condition, Condition::BaseFee { .. }))
+    {
+        rpc.stats().record_api_call("rpc");
+        readings.base_fee = provider
+            .get_block_by_number(BlockNumberOrTag::Latest)
+            .await
+            .map_err(|err| format!("failed to load latest block: {err}"))?
+            .and_then(|block| block.header.base_fee_per_gas);
+    }
+    for (alert, fetch_metadata) in alerts {
+        let Condition::TokenBalance { token, holder } = &alert.condition else {
+            continue;
+        };
+        let (Ok(token), Ok(holder)) = (token.parse::<Address>(), holder.parse::<Address>()) else {
+            readings
+                .balances
+                .push((alert.key(), Err("invalid address".into())));
+            continue;
+        };
+        let contract = IERC20Balance::new(token, &provider);
+        rpc.stats().record_api_call("rpc");
+        let balance = match contract.balanceOf(holder).call().await {
+            Ok(value) => {
+                let (mut symbol, mut decimals) = (None, None);
+                if *fetch_metadata {
+                    rpc.stats().record_api_call("rpc");
+                    symbol = contract.symbol().call().await.ok();
+                    rpc.stats().record_api_call("rpc");
+                    decimals = contract.decimals().call().await.ok();
+                }
+                Ok(TokenBalance {
+                    value,
+                    symbol,
+                    decimals,
+                })
+            }
+            Err(err) => Err(format!("balanceOf failed: {err}")),
+        };
+        readings.balances.push((alert.key(), balance));
+    }
+    Ok(readings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+    const HOLDER: &str = "0x00000000000000000000000000000000000000aa";
+
+    fn alert(condition: &str) -> ThresholdAlert {
+        ThresholdAlert {
+            chain: "Mainnet".into(),
+            condition: Condition::parse(condition).unwrap(),
+            alerts: AlertOutputs::default(),
+        }
+    }
+
+    fn reading(
+        base_fee: Option<u64>,
+        balances: Vec<(String, Result<TokenBalance, String>)>,
+    ) -> ChainReading {
+        ChainReading {
+            chain: "Mainnet".into(),
+            outcome: Ok(Readings { base_fee, balances }),
+        }
+    }
+
+    #[test]
+    fn thresholds_fire_on_crossing_and_balance_change() {
+        let gwei = 1_000_000_000;
+        let cheap = alert("base fee < 10.5 gwei");
+        assert_eq!(
+            cheap.condition,
+            Condition::BaseFee {
+                above: false,
+                wei: 10 * gwei + gwei / 2
+            }
+        );
+        assert_eq!(cheap.title(), "Base fee < 10.5 gwei");
+        assert!(Condition::parse("base fee = 3").is_err());
+        assert!(Condition::parse("balance 0x01 0x02").is_err());
+        let stored = serde_json::to_string(&cheap).unwrap();
+        assert_eq!(
+            serde_json::from_str::<ThresholdAlert>(&stored).unwrap(),
+            cheap
+        );
+
+        let watched = alert(&format!("balance {TOKEN} {HOLDER}"));
+        let mut state = ThresholdState::new(vec![cheap.clone()]);
+        assert!(state.add(watched.clone()));
+        assert!(!state.add(alert("basefee<10.5")));
+        assert!(state.batches()[0].1.iter().all(|(_, unknown)| *unknown));
+
+        let balance = |value: u64, decimals: Option<u8>| {
+            vec![(
+                watched.key(),
+                Ok(TokenBalance {
+                    value: U256::from(value),
+                    symbol: decimals.map(|_| "USDC".to_string()),
+                    decimals,
+                }),
+            )]
+        };
+        // Already cheap on the first reading: fires; the balance is a baseline.
+        let raised = state.apply_poll(vec![reading(Some(8 * gwei), balance(1_500_000, Some(6)))]);
+        assert_eq!(raised.len(), 1);
+        assert_eq!(raised[0].1.message, "now 8 gwei");
+        assert_eq!(state.current(&watched).as_deref(), Some("1.5 USDC"));
+        assert!(!state.batches()[0].1[1].1);
+
+        // Still cheap and unchanged: quiet.
+        assert!(
+            state
+                .apply_poll(vec![reading(Some(9 * gwei), balance(1_500_000, None))])
+                .is_empty()
+        );
+        // Fee rises, balance moves.
+        let raised = state.apply_poll(vec![reading(Some(20 * gwei), balance(2_000_000, None))]);
+        assert_eq!(raised.len(), 1);
+        assert_eq!(raised[0].1.message, "1.5 → 2 USDC");
+        assert_eq!(raised[0].1.subject.as_ref().unwrap().address, HOLDER);
+        // Dropping back under re-arms the fee alert.
+        assert_eq!(
+            state
+                .apply_poll(vec![reading(Some(10 * gwei), balance(2_000_000, None))])
+                .len(),
+            1
+        );
+
+        state.apply_poll(vec![ChainReading {
+            chain: "mainnet".into(),
+            outcome: Err("timed out".into()),
+        }]);
+        assert_eq!(state.error(&watched), Some("timed out"));
+    }
+}
@@ -0,0 +1,398 @@
+use super::{
+    anvil::normalize_url, chains::resolve_chain, providers::Rpc, stats::ApiStats,
+    tenderly::Tenderly,
+};
+use crate::storage::{Storage, TokenRecord};
+use alloy::{
+    consensus::Transaction as ConsensusTransaction,
+    network::TransactionResponse,
+    primitives::{Address, B256, LogData, U256},
+    providers::{Provider, ext::DebugApi},
+    rpc::types::trace::geth::{CallConfig, CallFrame, GethDebugTracingOptions},
+    sol,
+    sol_types::SolEvent,
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+sol! {
+    interface IERC20 {
+        event Transfer(address indexed from, address indexed to, uint256 value);
+    }
+
+    interface IERC721 {
+        event Transfer(address indexed from, address indexed to, uint256 indexed tokenId);
+    }
+
+    interface IERC1155 {
+        event TransferSingle(address indexed operator, address indexed from, address indexed to, uint256 id, uint256 value);
+        event TransferBatch(address indexed operator, address indexed from, address indexed to, uint256[] ids, uint256[] values);
+    }
+
+    #[sol(rpc)]
+    interface IERC20Metadata {
+        function symbol() external view returns (string);
+        function name() external view returns (string);
+        function decimals() external view returns (uint8);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum Asset {
+    Native,
+    Erc20(Address),
+    Erc721 { token: Address, id: U256 },
+    Erc1155 { token: Address, id: U256 },
+}
+
+/// One movement of value: `amount` is 1 for ERC-721.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Transfer {
+    pub from: Address,
+    pub to: Address,
+    pub asset: Asset,
+    pub amount: U256,
+}
+
+/// Every asset movement of one transaction: ETH in call order, then tokens in
+/// log order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct TransferSummary {
+    pub transfers: Vec<Transfer>,
+    pub tokens: BTreeMap<Address, TokenRecord>,
+    /// Set when internal ETH transfers could not be traced.
+    pub trace_note: Option<String>,
+}
+
+/// Token metadata persisted for one chain, so it is read from the contract
+/// once rather than on every hydration. New entries are queued on the
+/// storage writer with every other write.
+#[derive(Clone)]
+pub struct TokenCache {
+    chain_id: u64,
+    storage: Storage,
+}
+
+impl TokenCache {
+    /// `None` for chains without a known id, such as a local node whose
+    /// contracts change between runs.
+    pub fn for_chain(storage: &Storage, chain: &str) -> Option<Self> {
+        Some(Self {
+            chain_id: resolve_chain(chain)?.chain_id,
+            storage: storage.clone(),
+        })
+    }
+
+    fn get(&self, address: &str) -> Option<TokenRecord> {
+        self.storage
+            .tokens()
+            .get(self.chain_id, address)
+            .ok()
+            .flatten()
+    }
+
+    fn put(&self, address: String, record: TokenRecord) {
+        let chain_id = self.chain_id;
+        self.storage.write("token metadata", move |storage| {
+            storage.tokens().put(chain_id, &address, &record)
+        });
+    }
+}
+
+/// State of the Summary tab's Transfers section.
+#[derive(Debug, Default)]
+pub struct TransfersView {
+    /// Transaction the section was loaded for.
+    pub hash: Option<String>,
+    pub loading: bool,
+    pub result: Option<Result<TransferSummary, String>>,
+}
+
+impl TransfersView {
+    pub fn needs_load(&self, hash: &str) -> bool {
+        self.hash.as_deref() != Some(hash)
+    }
+}
+
+/// Token movements announced by one log. ERC-20 and ERC-721 share the
+/// `Transfer` topic and differ in how many arguments are indexed.
+pub(super) fn transfers_from_log(token: Address, data: &LogData) -> Vec<Transfer> {
+    let Some(topic0) = data.topics().first() else {
+        return Vec::new();
+    };
+    let transfer = |from, to, asset, amount| Transfer {
+        from,
+        to,
+        asset,
+        amount,
+    };
+    match *topic0 {
+        IERC20::Transfer::SIGNATURE_HASH if data.topics().len() == 4 => {
+            IERC721::Transfer::decode_log_data(data)
+                .map(|event| {
+                    let asset = Asset::Erc721 {
+                        token,
+                        id: event.tokenId,
+                    };
+                    vec![transfer(event.from, event.to, asset, U256::from(1))]
+                })
+                .unwrap_or_default()
+        }
+        IERC20::Transfer::SIGNATURE_HASH => IERC20::Transfer::decode_log_data(data)
+            .map(|event| {
+                vec![transfer(
+                    event.from,
+                    event.to,
+                    Asset::Erc20(token),
+                    event.value,
+                )]
+            })
+            .unwrap_or_default(),
+        IERC1155::TransferSingle::SIGNATURE_HASH => IERC1155::TransferSingle::decode_log_data(data)
+            .map(|event| {
+                let asset = Asset::Erc1155 {
+                    token,
+                    id: event.id,
+                };
+                vec![transfer(event.from, event.to, asset, event.value)]
+            })
+            .unwrap_or_default(),
+        IERC1155::TransferBatch::SIGNATURE_HASH => IERC1155::TransferBatch::decode_log_data(data)
+            .map(|event| {
+                event
+                    .ids
+                    .iter()
+                    .zip(&event.values)
+                    .map(|(id, value)| {
+                        let asset = Asset::Erc1155 { token, id: *id };
+                        transfer(event.from, event.to, asset, *value)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+/// ETH moved by successful call frames, depth first. Reverted frames and
+/// everything beneath them moved nothing.
+fn native_transfers(frame: &CallFrame, out: &mut Vec<Transfer>) {
+    if frame.error.is_some() {
+        return;
+    }
+    if let (Some(value), Some(to)) = (frame.value, frame.to)
+        && !value.is_zero()
+        && frame.typ != "DELEGATECALL"
+    {
+        out.push(Transfer {
+            from: frame.from,
+            to,
+            asset: Asset::Native,
+            amount: value,
+        });
+    }
+    for call in &frame.calls {
+        native_transfers(call, out);
+    }
+}
+
+/// Collect ETH transfers from a `callTracer` trace, or a Tenderly replay when
+/// given one (just the top-level value when neither can trace), and token
+/// transfers from the receipt.
+pub async fn fetch_transfers(
+    rpc: Rpc,
+    rpc_url: String,
+    hash: String,
+    cache: Option<TokenCache>,
+    tenderly: Option<Tenderly>,
+) -> Result<TransferSummary, String> {
+    let tx_hash: B256 = hash
+        .parse()
+        .map_err(|_| "invalid transaction hash".to_string())?;
+    let provider = rpc
+        .connect(&normalize_url(&rpc, &rpc_url))
+        .await
+        .map_err(|err| format!("{err:#}"))?;
+
+    rpc.stats().record_api_call("rpc");
+    let transaction = provider
+        .get_transaction_by_hash(tx_hash)
+        .await
+        .map_err(|err| format!("failed to load transaction: {err}"))?
+        .ok_or("transaction not found on this RPC")?;
+    rpc.stats().record_api_call("rpc");
+    let receipt = provider
+        .get_transaction_receipt(tx_hash)
+        .await
+        .map_err(|err| format!("failed to load receipt: {err}"))?
+        .ok_or("transaction is still pending")?;
+    if !receipt.status() {
+        return Ok(TransferSummary {
+            trace_note: Some("Reverted: nothing was transferred".into()),
+            ..TransferSummary::default()
+        });
+    }
+
+    let mut summary = TransferSummary::default();
+    let trace = match &tenderly {
+        Some(tenderly) => tenderly
+            .replay(rpc.stats(), &transaction, receipt.effective_gas_price)
+            .await
+            .and_then(|simulation| {
+                simulation
+                    .call_trace
+                    .ok_or_else(|| "no call trace in the simulation".to_string())
+            })
+            .map_err(|err| ("Tenderly", err)),
+        None => {
+            rpc.stats().record_api_call("rpc");
+            let options = GethDebugTracingOptions::call_tracer(CallConfig::default());
+            provider
+                .debug_trace_transaction(tx_hash, options)
+                .await
+                .map_err(|err| err.to_string())
+                .and_then(|trace| trace.try_into_call_frame().map_err(|err| err.to_string()))
+                .map_err(|err| ("debug_traceTransaction", err))
+        }
+    };
+    match trace {
+        Ok(frame) => native_transfers(&frame, &mut summary.transfers),
+        Err((source, err)) => {
+            summary.trace_note = Some(format!(
+                "Internal ETH transfers unavailable ({source}: {err})"
+            ));
+            if let Some(to) = transaction.to()
+                && !transaction.value().is_zero()
+            {
+                summary.transfers.push(Transfer {
+                    from: transaction.from(),
+                    to,
+                    asset: Asset::Native,
+                    amount: transaction.value(),
+                });
+            }
+        }
+    }
+    for log in receipt.inner.logs() {
+        summary
+            .transfers
+            .extend(transfers_from_log(log.address(), log.data()));
+    }
+
+    summary.tokens =
+        token_metadata(rpc.stats(), &provider, &summary.transfers, cache.as_ref()).await;
+    Ok(summary)
+}
+
+/// Symbol, name and decimals of every ERC-20 in `transfers`; tokens that do
+/// not implement the metadata extension get empty entries. Cached entries
+/// skip the calls, and tokens that report decimals are added to the cache.
+pub(super) async fn token_metadata(
+    stats: &ApiStats,
+    provider: &impl Provider,
+    transfers: &[Transfer],
+    cache: Option<&TokenCache>,
+) -> BTreeMap<Address, TokenRecord> {
+    let mut tokens = BTreeMap::new();
+    for transfer in transfers {
+        let Asset::Erc20(token) = transfer.asset else {
+            continue;
+        };
+        if tokens.contains_key(&token) {
+            continue;
+        }
+        let address = token.to_string();
+        if let Some(cached) = cache.and_then(|cache| cache.get(&address)) {
+            tokens.insert(token, cached);
+            continue;
+        }
+        let contract = IERC20Metadata::new(token, provider);
+        stats.record_api_call("rpc");
+        let symbol = contract.symbol().call().await.ok();
+        stats.record_api_call("rpc");
+        let name = contract.name().call().await.ok();
+        stats.record_api_call("rpc");
+        let decimals = contract.decimals().call().await.ok();
+        let record = TokenRecord {
+            symbol,
+            name,
+            decimals,
+        };
+        // A failed `decimals` call may be transient; only cache usable entries.
+        if let Some(cache) = cache
+            && record.decimals.is_some()
+        {
+            cache.put(address, record.clone());
+        }
+        tokens.insert(token, record);
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_token_standards_and_skips_reverted_calls() {
+        let token = Address::repeat_byte(0x70);
+        let (from, to) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let erc20 = IERC20::Transfer {
+            from,
+            to,
+            value: U256::from(5),
+        }
+        .encode_log_data();
+        assert_eq!(
+            transfers_from_log(token, &erc20),
+            vec![Transfer {
+                from,
+                to,
+                asset: Asset::Erc20(token),
+                amount: U256::from(5),
+            }]
+        );
+        let nft = IERC721::Transfer {
+            from,
+            to,
+            tokenId: U256::from(9),
+        }
+        .encode_log_data();
+        assert_eq!(
+            transfers_from_log(token, &nft)[0].asset,
+            Asset::Erc721 {
+                token,
+                id: U256::from(9)
+            }
+        );
+        let batch = IERC1155::TransferBatch {
+            operator: from,
+            from,
+            to,
+            ids: vec![U256::from(1), U256::from(2)],
+            values: vec![U256::from(10), U256::from(20)],
+        }
+        .encode_log_data();
+        assert_eq!(transfers_from_log(token, &batch).len(), 2);
+
+        let frame = CallFrame {
+            from,
+            to: Some(to),
+            value: Some(U256::from(3)),
+            typ: "CALL".into(),
+            calls: vec![CallFrame {
+                from: to,
+                to: Some(from),
+                value: Some(U256::from(1)),
+                typ: "CALL".into(),
+                error: Some("execution reverted".into()),
+                ..CallFrame::default()
+            }],
+            ..CallFrame::default()
+        };
+        let mut native = Vec::new();
+        native_transfers(&frame, &mut native);
+        assert_eq!(native.len(), 1);
+        assert_eq!(native[0].amount, U256::from(3));
+    }
+}
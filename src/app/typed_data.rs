@@ -0,0 +1,176 @@
+use alloy::{
+    dyn_abi::TypedData,
+    hex,
+    primitives::{Address, B256, Signature, U256, eip191_hash_message},
+};
+
+/// Upper bound for a canonical `s` (secp256k1 order / 2); signatures above
+/// it are malleable and rejected by OpenZeppelin's `ECDSA.recover`.
+const HALF_ORDER: U256 = U256::from_be_slice(&[
+    0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+]);
+
+/// Digest of a pasted payload and, when a signature is given, its signer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inspection {
+    /// Labelled lines describing the payload, its hashes and the signature.
+    pub rows: Vec<(&'static str, String)>,
+    pub digest: B256,
+    pub signer: Option<Address>,
+}
+
+/// Hash `payload` the way a wallet would sign it: a JSON object as EIP-712
+/// typed data, anything else as an EIP-191 `personal_sign` message (`0x`
+/// hex as raw bytes, otherwise the text). A non-empty `signature` (65 bytes,
+/// or 64 in ERC-2098 compact form) is checked against the digest.
+pub fn inspect(payload: &str, signature: &str) -> Result<Inspection, String> {
+    let mut rows = Vec::new();
+    let digest = if payload.trim_start().starts_with('{') {
+        let typed: TypedData = serde_json::from_str(payload)
+            .map_err(|err| format!("not EIP-712 typed data: {err}"))?;
+        rows.push(("kind", format!("EIP-712 {}", typed.primary_type)));
+        let domain = &typed.domain;
+        let mut fields = Vec::new();
+        if let Some(name) = &domain.name {
+            fields.push(format!("name {name:?}"));
+        }
+        if let Some(version) = &domain.version {
+            fields.push(format!("version {version:?}"));
+        }
+        if let Some(chain_id) = domain.chain_id {
+            fields.push(format!("chain {chain_id}"));
+        }
+        if let Some(contract) = domain.verifying_contract {
+            fields.push(format!("contract {}", contract.to_checksum(None)));
+        }
+        if let Some(salt) = domain.salt {
+            fields.push(format!("salt {salt:#x}"));
+        }
+        rows.push(("domain", fields.join(", ")));
+        rows.push(("domain separator", format!("{:#x}", domain.separator())));
+        let struct_hash = typed
+            .hash_struct()
+            .map_err(|err| format!("cannot hash {}: {err}", typed.primary_type))?;
+        rows.push(("struct hash", format!("{struct_hash:#x}")));
+        typed
+            .eip712_signing_hash()
+            .map_err(|err| format!("cannot hash typed data: {err}"))?
+    } else {
+        let message = match payload.trim().strip_prefix("0x").map(hex::decode) {
+            Some(Ok(bytes)) => bytes,
+            _ => payload.as_bytes().to_vec(),
+        };
+        rows.push(("kind", format!("personal_sign, {} bytes", message.len())));
+        eip191_hash_message(message)
+    };
+    rows.push(("digest", format!("{digest:#x}")));
+
+    let signature = signature.trim();
+    if signature.is_empty() {
+        return Ok(Inspection {
+            rows,
+            digest,
+            signer: None,
+        });
+    }
+    let signature = parse_signature(signature)?;
+    rows.push((
+        "v / r / s",
+        format!(
+            "{} / {:#x} / {:#x}",
+            27 + u8::from(signature.v()),
+            signature.r(),
+            signature.s()
+        ),
+    ));
+    let signer = signature
+        .recover_address_from_prehash(&digest)
+        .map_err(|err| format!("cannot recover the signer: {err}"))?;
+    rows.push(("signer", signer.to_checksum(None)));
+    if signature.s() > HALF_ORDER {
+        rows.push((
+            "warning",
+            "high s: malleable, rejected by OpenZeppelin ECDSA".into(),
+        ));
+    }
+    Ok(Inspection {
+        rows,
+        digest,
+        signer: Some(signer),
+    })
+}
+
+fn parse_signature(input: &str) -> Result<Signature, String> {
+    let bytes = hex::decode(input).map_err(|_| "signature is not hex".to_string())?;
+    match bytes.len() {
+        65 => Signature::from_raw(&bytes).map_err(|err| format!("invalid signature: {err}")),
+        64 => Ok(Signature::from_erc2098(&bytes)),
+        len => Err(format!("signature is {len} bytes; expected 65 or 64")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{address, b256};
+
+    #[test]
+    fn hashes_typed_data_and_recovers_signers() {
+        // The `Mail` example from EIP-712.
+        let mail = r#"{
+            "types": {
+                "EIP712Domain": [
+                    {"name": "name", "type": "string"},
+                    {"name": "version", "type": "string"},
+                    {"name": "chainId", "type": "uint256"},
+                    {"name": "verifyingContract", "type": "address"}
+                ],
+                "Person": [
+                    {"name": "name", "type": "string"},
+                    {"name": "wallet", "type": "address"}
+                ],
+                "Mail": [
+                    {"name": "from", "type": "Person"},
+                    {"name": "to", "type": "Person"},
+                    {"name": "contents", "type": "string"}
+                ]
+            },
+            "primaryType": "Mail",
+            "domain": {
+                "name": "Ether Mail",
+                "version": "1",
+                "chainId": 1,
+                "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+            },
+            "message": {
+                "from": {"name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"},
+                "to": {"name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"},
+                "contents": "Hello, Bob!"
+            }
+        }"#;
+        let inspection = inspect(
+            mail,
+            "0x4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d\
+             07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b915621c",
+        )
+        .unwrap();
+        assert_eq!(
+            inspection.digest,
+            b256!("0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2")
+        );
+        assert_eq!(
+            inspection.signer,
+            Some(address!("0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"))
+        );
+
+        let message = inspect("hello", "").unwrap();
+        assert_eq!(
+            message.digest,
+            b256!("0x50b2c43fd39106bafbba0da34fc430e1f91e3c96ea2acee2bc34119f92b37750")
+        );
+        assert_eq!(message.signer, None);
+        assert!(inspect("{\"types\": 1}", "").is_err());
+        assert!(inspect("hello", "0x1234").is_err());
+    }
+}
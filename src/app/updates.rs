@@ -0,0 +1,121 @@
+use super::{stats::ApiStats, watchlist::unix_now};
+use crate::storage::{SettingKey, SettingsRepository};
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Set to `1`/`true` to opt into the startup release check.
+pub const OPT_IN_ENV: &str = "EVM_TUI_CHECK_UPDATES";
+
+const RELEASES_URL: &str = "https://api.github.com/repos/carlosgj94/evm-tui/releases/latest";
+const CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseInfo {
+    pub version: String,
+    pub changelog: String,
+    pub url: String,
+}
+
+impl ReleaseInfo {
+    pub fn is_newer_than_current(&self) -> bool {
+        is_newer(&self.version, CURRENT_VERSION)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedCheck {
+    checked_at: u64,
+    latest: ReleaseInfo,
+}
+
+pub fn is_enabled() -> bool {
+    std::env::var(OPT_IN_ENV)
+        .map(|value| matches!(value.trim(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
+
+/// Latest release from a check made within the cache TTL, if any.
+pub fn cached_release(settings: &SettingsRepository) -> Result<Option<ReleaseInfo>> {
+    let Some(cached) = settings.get_json::<CachedCheck>(SettingKey::UpdateCheck)? else {
+        return Ok(None);
+    };
+    let age = unix_now().saturating_sub(cached.checked_at);
+    Ok((age < CACHE_TTL.as_secs()).then_some(cached.latest))
+}
+
+pub fn store_release(settings: &SettingsRepository, latest: &ReleaseInfo) -> Result<()> {
+    let cached = CachedCheck {
+        checked_at: unix_now(),
+        latest: latest.clone(),
+    };
+    settings.set_json(SettingKey::UpdateCheck, &cached)
+}
+
+pub async fn fetch_latest_release(stats: &ApiStats) -> std::result::Result<ReleaseInfo, String> {
+    let client = reqwest::Client::builder()
+        .user_agent(concat!("evm-tui/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|err| err.to_string())?;
+    stats.record_api_call("release check");
+    let release: RawRelease = client
+        .get(RELEASES_URL)
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|err| format!("release check failed: {err}"))?
+        .json()
+        .await
+        .map_err(|err| format!("release response parse error: {err}"))?;
+    Ok(ReleaseInfo {
+        version: release.tag_name.trim_start_matches('v').to_string(),
+        changelog: release.body.unwrap_or_default(),
+        url: release.html_url,
+    })
+}
+
+fn is_newer(candidate: &str, current: &str) -> bool {
+    match (parse_version(candidate), parse_version(current)) {
+        (Some(candidate), Some(current)) => candidate > current,
+        _ => false,
+    }
+}
+
+fn parse_version(value: &str) -> Option<(u64, u64, u64)> {
+    let core = value
+        .trim()
+        .trim_start_matches('v')
+        .split(['-', '+'])
+        .next()?;
+    let mut parts = core.split('.').map(|part| part.parse::<u64>().ok());
+    let major = parts.next()??;
+    let minor = parts.next().unwrap_or(Some(0))?;
+    let patch = parts.next().unwrap_or(Some(0))?;
+    Some((major, minor, patch))
+}
+
+#[derive(Debug, Deserialize)]
+struct RawRelease {
+    tag_name: String,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    html_url: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_semantic_versions() {
+        assert!(is_newer("v0.2.0", "0.1.9"));
+        assert!(is_newer("1.0", "0.9.9"));
+        assert!(!is_newer("0.1.0", "0.1.0"));
+        assert!(!is_newer("0.1.0-rc.1", "0.1.0"));
+        assert!(!is_newer("nightly", "0.1.0"));
+    }
+}
@@ -0,0 +1,297 @@
+use super::{AddressRef, anvil::normalize_url, providers::Providers};
+use alloy::{
+    dyn_abi::{DynSolValue, FunctionExt},
+    json_abi::{Function, JsonAbi, StateMutability},
+    network::TransactionBuilder,
+    primitives::{Address, B256, U256, b256, hex},
+    providers::Provider,
+    rpc::types::{TransactionRequest, state::StateOverridesBuilder},
+};
+use std::collections::BTreeSet;
+
+/// `bytes32(uint256(keccak256("eip1967.proxy.implementation")) - 1)`.
+pub(super) const EIP1967_IMPLEMENTATION_SLOT: B256 =
+    b256!("0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc");
+/// Upper bound on view functions probed per report, to keep one preview cheap.
+const MAX_PROBED_VIEWS: usize = 48;
+
+/// One zero-argument view function read through the proxy before and after the swap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ViewDiff {
+    pub signature: String,
+    pub before: String,
+    pub after: String,
+}
+
+impl ViewDiff {
+    pub fn changed(&self) -> bool {
+        self.before != self.after
+    }
+}
+
+/// What changes for callers of a proxy if it were pointed at `new_implementation`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpgradeReport {
+    pub proxy: Address,
+    pub current_implementation: Address,
+    pub new_implementation: Address,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub views: Vec<ViewDiff>,
+    pub notes: Vec<String>,
+}
+
+impl UpgradeReport {
+    pub fn changed_views(&self) -> usize {
+        self.views.iter().filter(|view| view.changed()).count()
+    }
+}
+
+/// Functions and events present in only one of the two ABIs, as
+/// `(added, removed)`. Output type changes show up as a removal plus an addition.
+pub fn abi_diff(old: &JsonAbi, new: &JsonAbi) -> (Vec<String>, Vec<String>) {
+    let entries = |abi: &JsonAbi| -> BTreeSet<String> {
+        abi.functions()
+            .map(|function| format!("function {}", function.signature_with_outputs()))
+            .chain(
+                abi.events()
+                    .map(|event| format!("event {}", event.signature())),
+            )
+            .collect()
+    };
+    let (old, new) = (entries(old), entries(new));
+    (
+        new.difference(&old).cloned().collect(),
+        old.difference(&new).cloned().collect(),
+    )
+}
+
+/// Simulate pointing `proxy` at `new_implementation`: every zero-argument view
+/// is `eth_call`ed at the latest block as-is and again with the EIP-1967
+/// implementation slot overridden, so nothing is sent on-chain.
+pub async fn simulate_upgrade(
+    providers: &Providers,
+    rpc_url: &str,
+    api_key: Option<String>,
+    chain: String,
+    proxy: Address,
+    new_implementation: Address,
+) -> Result<UpgradeReport, String> {
+    let url = normalize_url(&providers.rpc, rpc_url);
+    let provider = providers
+        .rpc
+        .connect(&url)
+        .await
+        .map_err(|err| format!("{err:#}"))?;
+
+    providers.rpc.stats().record_api_call("rpc");
+    let slot = provider
+        .get_storage_at(proxy, U256::from_be_bytes(EIP1967_IMPLEMENTATION_SLOT.0))
+        .await
+        .map_err(|err| format!("failed to read implementation slot: {err}"))?;
+    let current_implementation = Address::from_word(B256::from(slot));
+    if current_implementation == Address::ZERO {
+        return Err(format!("{proxy} is not an EIP-1967 proxy"));
+    }
+
+    let mut notes = Vec::new();
+    providers.rpc.stats().record_api_call("rpc");
+    let code = provider
+        .get_code_at(new_implementation)
+        .await
+        .map_err(|err| format!("failed to load code for {new_implementation}: {err}"))?;
+    if code.is_empty() {
+        notes.push(format!("{new_implementation} has no code on {chain}"));
+    }
+    if new_implementation == current_implementation {
+        notes.push("New implementation is already the current one".into());
+    }
+
+    let old_abi = load_abi(
+        providers,
+        &chain,
+        current_implementation,
+        api_key.as_deref(),
+    )
+    .await;
+    let new_abi = load_abi(providers, &chain, new_implementation, api_key.as_deref()).await;
+    let (added, removed) = match (&old_abi, &new_abi) {
+        (Ok(old), Ok(new)) => abi_diff(old, new),
+        _ => (Vec::new(), Vec::new()),
+    };
+    for (which, abi) in [("Current", &old_abi), ("New", &new_abi)] {
+        if let Err(err) = abi {
+            notes.push(format!("{which} implementation ABI unavailable: {err}"));
+        }
+    }
+
+    let mut probed = BTreeSet::new();
+    let views: Vec<Function> = [old_abi.as_ref().ok(), new_abi.as_ref().ok()]
+        .into_iter()
+        .flatten()
+        .flat_map(|abi| abi.functions())
+        .filter(|function| {
+            function.inputs.is_empty()
+                && matches!(
+                    function.state_mutability,
+                    StateMutability::View | StateMutability::Pure
+                )
+        })
+        .filter(|function| probed.insert(function.signature()))
+        .cloned()
+        .collect();
+    if views.is_empty() {
+        return Err("no verified ABI with view functions for either implementation".into());
+    }
+    if views.len() > MAX_PROBED_VIEWS {
+        notes.push(format!(
+            "Probed the first {MAX_PROBED_VIEWS} of {} view functions",
+            views.len()
+        ));
+    }
+
+    let overrides = StateOverridesBuilder::default()
+        .with_state_diff(
+            proxy,
+            [(EIP1967_IMPLEMENTATION_SLOT, new_implementation.into_word())],
+        )
+        .build();
+    let mut diffs = Vec::new();
+    for function in views.iter().take(MAX_PROBED_VIEWS) {
+        let request = TransactionRequest::default()
+            .with_to(proxy)
+            .with_input(function.selector().to_vec());
+        providers.rpc.stats().record_api_call("rpc");
+        let before = provider.call(request.clone()).await;
+        providers.rpc.stats().record_api_call("rpc");
+        let after = provider.call(request).overrides(overrides.clone()).await;
+        diffs.push(ViewDiff {
+            signature: function.signature(),
+            before: describe_output(function, before.map_err(|err| err.to_string())),
+            after: describe_output(function, after.map_err(|err| err.to_string())),
+        });
+    }
+
+    Ok(UpgradeReport {
+        proxy,
+        current_implementation,
+        new_implementation,
+        added,
+        removed,
+        views: diffs,
+        notes,
+    })
+}
+
+async fn load_abi(
+    providers: &Providers,
+    chain: &str,
+    address: Address,
+    api_key: Option<&str>,
+) -> Result<JsonAbi, String> {
+    let target = AddressRef {
+        label: String::new(),
+        address: address.to_string(),
+        chain: chain.to_string(),
+    };
+    match providers.explorer.contract_source(&target, api_key).await {
+        Ok(Some(source)) => {
+            serde_json::from_str(&source.abi).map_err(|err| format!("ABI is not valid JSON: {err}"))
+        }
+        Ok(None) => Err("not verified".into()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+fn describe_output<T: AsRef<[u8]>>(function: &Function, output: Result<T, String>) -> String {
+    match output {
+        Ok(data) => match function.abi_decode_output(data.as_ref()) {
+            Ok(values) => values
+                .iter()
+                .map(format_value)
+                .collect::<Vec<_>>()
+                .join(", "),
+            Err(_) => format!("0x{}", hex::encode(data.as_ref())),
+        },
+        Err(err) => format!("reverted: {err}"),
+    }
+}
+
+pub(super) fn format_value(value: &DynSolValue) -> String {
+    match value {
+        DynSolValue::Bool(flag) => flag.to_string(),
+        DynSolValue::Int(int, _) => int.to_string(),
+        DynSolValue::Uint(uint, _) => uint.to_string(),
+        DynSolValue::Address(address) => address.to_string(),
+        DynSolValue::FixedBytes(word, size) => format!("0x{}", hex::encode(&word[..*size])),
+        DynSolValue::Bytes(bytes) => format!("0x{}", hex::encode(bytes)),
+        DynSolValue::String(text) => format!("{text:?}"),
+        DynSolValue::Array(items) | DynSolValue::FixedArray(items) => {
+            format!(
+                "[{}]",
+                items
+                    .iter()
+                    .map(format_value)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        }
+        DynSolValue::Tuple(items) => {
+            format!(
+                "({})",
+                items
+                    .iter()
+                    .map(format_value)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        }
+        other => format!("{other:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diffs_abi_signatures_and_decodes_outputs() {
+        let old = JsonAbi::parse([
+            "function version() view returns (uint256)",
+            "function owner() view returns (address)",
+            "event Paused(address)",
+        ])
+        .unwrap();
+        let new = JsonAbi::parse([
+            "function version() view returns (string)",
+            "function owner() view returns (address)",
+            "function pause()",
+        ])
+        .unwrap();
+
+        let (added, removed) = abi_diff(&old, &new);
+        assert_eq!(
+            added,
+            vec![
+                "function pause()()".to_string(),
+                "function version()(string)".to_string()
+            ]
+        );
+        assert_eq!(
+            removed,
+            vec![
+                "event Paused(address)".to_string(),
+                "function version()(uint256)".to_string()
+            ]
+        );
+
+        let version = new.function("version").unwrap()[0].clone();
+        let encoded =
+            DynSolValue::Tuple(vec![DynSolValue::String("2.0.0".into())]).abi_encode_params();
+        assert_eq!(describe_output(&version, Ok(encoded)), "\"2.0.0\"");
+        assert_eq!(
+            describe_output::<Vec<u8>>(&version, Err("execution reverted".into())),
+            "reverted: execution reverted"
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use super::{anvil::normalize_url, providers::Rpc, upgrade_impact::EIP1967_IMPLEMENTATION_SLOT};
+use alloy::{
+    primitives::{Address, B256, U256, keccak256},
+    providers::Provider,
+    rpc::types::Filter,
+};
+
+/// Most `eth_getLogs` calls one scan makes before settling for the blocks
+/// it covered; upgrades are rare, so the newest ones come first.
+const MAX_QUERIES: usize = 64;
+const MIN_CHUNK: u64 = 1_000;
+
+/// One `Upgraded(address)` event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Upgrade {
+    pub block_number: u64,
+    pub transaction_hash: Option<String>,
+    pub implementation: Address,
+}
+
+/// Implementation changes of an EIP-1967 proxy, newest first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpgradeHistory {
+    /// Current implementation; `None` when the slot is empty (not a proxy).
+    pub implementation: Option<Address>,
+    pub upgrades: Vec<Upgrade>,
+    pub from_block: u64,
+    pub to_block: u64,
+    /// Whether the scan stopped before reaching `floor`.
+    pub truncated: bool,
+}
+
+/// Upgrade timeline shown on the Permissions tab of the selected address.
+#[derive(Debug, Default)]
+pub struct UpgradesView {
+    /// Address whose scan is running or last finished.
+    pub address: Option<String>,
+    pub history: Option<UpgradeHistory>,
+    pub loading: bool,
+    pub error: Option<String>,
+}
+
+impl UpgradesView {
+    pub fn needs_load(&self, address: &str) -> bool {
+        self.address.as_deref() != Some(address)
+    }
+}
+
+/// Read the proxy's implementation slot and, when set, walk `Upgraded`
+/// logs back from the head to `floor` (the deployment block when known).
+/// The chunk starts as the whole range and halves whenever a provider
+/// rejects it.
+pub async fn fetch_upgrades(
+    rpc: Rpc,
+    rpc_url: String,
+    address: String,
+    floor: Option<u64>,
+) -> Result<UpgradeHistory, String> {
+    let target: Address = address.parse().map_err(|_| "invalid address".to_string())?;
+    let provider = rpc
+        .connect(&normalize_url(&rpc, &rpc_url))
+        .await
+        .map_err(|err| format!("{err:#}"))?;
+    rpc.stats().record_api_call("rpc");
+    let slot = provider
+        .get_storage_at(target, U256::from_be_bytes(EIP1967_IMPLEMENTATION_SLOT.0))
+        .await
+        .map_err(|err| format!("failed to read the implementation slot: {err}"))?;
+    rpc.stats().record_api_call("rpc");
+    let head = provider
+        .get_block_number()
+        .await
+        .map_err(|err| format!("failed to query latest block number: {err}"))?;
+    let implementation = implementation_from_slot(B256::from(slot));
+    if implementation.is_none() {
+        return Ok(UpgradeHistory {
+            implementation,
+            upgrades: Vec::new(),
+            from_block: head,
+            to_block: head,
+            truncated: false,
+        });
+    }
+
+    let floor = floor.unwrap_or(0).min(head);
+    let topic = keccak256("Upgraded(address)");
+    let mut upgrades = Vec::new();
+    let mut chunk = head - floor + 1;
+    let mut to = head;
+    let mut from = head;
+    let mut queries = 0;
+    let truncated = loop {
+        if queries == MAX_QUERIES {
+            break true;
+        }
+        queries += 1;
+        from = to.saturating_sub(chunk - 1).max(floor);
+        let query = Filter::new()
+            .address(target)
+            .event_signature(topic)
+            .from_block(from)
+            .to_block(to);
+        rpc.stats().record_api_call("rpc");
+        match provider.get_logs(&query).await {
+            Ok(logs) => {
+                upgrades.extend(logs.iter().rev().filter_map(|log| {
+                    Some(Upgrade {
+                        block_number: log.block_number?,
+                        transaction_hash: log.transaction_hash.map(|hash| format!("{hash:#x}")),
+                        implementation: Address::from_word(*log.topics().get(1)?),
+                    })
+                }));
+                if from == floor {
+                    break false;
+                }
+                to = from - 1;
+            }
+            Err(_) if chunk > MIN_CHUNK => chunk /= 2,
+            Err(err) => return Err(format!("eth_getLogs failed: {err}")),
+        }
+    };
+    Ok(UpgradeHistory {
+        implementation,
+        upgrades,
+        from_block: from,
+        to_block: head,
+        truncated,
+    })
+}
+
+fn implementation_from_slot(word: B256) -> Option<Address> {
+    let address = Address::from_word(word);
+    (!address.is_zero()).then_some(address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_implementation_slot_is_not_a_proxy() {
+        assert_eq!(implementation_from_slot(B256::ZERO), None);
+        let implementation = Address::repeat_byte(0x42);
+        assert_eq!(
+            implementation_from_slot(implementation.into_word()),
+            Some(implementation)
+        );
+    }
+}
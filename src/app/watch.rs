@@ -0,0 +1,245 @@
+use super::{
+    anvil::fetch_account_overview,
+    chains,
+    providers::Rpc,
+    scheduler::{Priority, Scheduler, host_key},
+};
+use crate::{
+    storage::{AlertRule, GroupMember, WatchGroupRecord},
+    ui::util::short_hex,
+};
+use alloy::primitives::{Address, U256, utils::format_units};
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    time::Duration,
+};
+use tokio::{task::JoinSet, time::timeout};
+
+/// How often members of groups with alert rules are re-polled.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(60);
+const MAX_ALERTS: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemberSnapshot {
+    pub balance_wei: U256,
+    pub nonce: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchAlert {
+    pub group: String,
+    pub message: String,
+}
+
+/// Watch groups plus the latest observation of each member and recent alerts.
+#[derive(Debug, Default)]
+pub struct WatchState {
+    pub groups: Vec<WatchGroupRecord>,
+    snapshots: HashMap<String, MemberSnapshot>,
+    alerts: VecDeque<WatchAlert>,
+}
+
+pub fn member_key(member: &GroupMember) -> String {
+    format!(
+        "{}:{}",
+        member.chain.to_ascii_lowercase(),
+        member.address.to_ascii_lowercase()
+    )
+}
+
+impl WatchState {
+    pub fn new(groups: Vec<WatchGroupRecord>) -> Self {
+        Self {
+            groups,
+            ..Self::default()
+        }
+    }
+
+    /// Distinct members of every group that has at least one alert rule.
+    pub fn polled_members(&self) -> Vec<GroupMember> {
+        let mut seen = HashMap::new();
+        for group in self.groups.iter().filter(|group| !group.rules.is_empty()) {
+            for member in &group.members {
+                seen.entry(member_key(member))
+                    .or_insert_with(|| member.clone());
+            }
+        }
+        seen.into_values().collect()
+    }
+
+    pub fn snapshot(&self, member: &GroupMember) -> Option<&MemberSnapshot> {
+        self.snapshots.get(&member_key(member))
+    }
+
+    /// Sum of the last known balances per native currency, since members
+    /// can sit on chains with different ones, and how many members
+    /// contributed.
+    pub fn group_balance(&self, group: &WatchGroupRecord) -> (BTreeMap<&'static str, U256>, usize) {
+        let mut totals = BTreeMap::new();
+        let mut known = 0;
+        for member in &group.members {
+            if let Some(snapshot) = self.snapshot(member) {
+                *totals
+                    .entry(chains::currency(&member.chain))
+                    .or_insert(U256::ZERO) += snapshot.balance_wei;
+                known += 1;
+            }
+        }
+        (totals, known)
+    }
+
+    pub fn latest_alert(&self, group: &str) -> Option<&WatchAlert> {
+        self.alerts.iter().rev().find(|alert| alert.group == group)
+    }
+
+    /// Store fresh snapshots and return alerts for every rule they trip. The
+    /// first observation of a member only establishes a baseline.
+    pub fn apply_snapshots(&mut self, results: Vec<(String, MemberSnapshot)>) -> Vec<WatchAlert> {
+        let mut raised = Vec::new();
+        for (key, current) in results {
+            let previous = self.snapshots.insert(key.clone(), current);
+            let Some(previous) = previous else {
+                continue;
+            };
+            for group in &self.groups {
+                let Some(member) = group
+                    .members
+                    .iter()
+                    .find(|member| member_key(member) == key)
+                else {
+                    continue;
+                };
+                for rule in &group.rules {
+                    if let Some(message) = describe_change(*rule, member, &previous, &current) {
+                        raised.push(WatchAlert {
+                            group: group.name.clone(),
+                            message,
+                        });
+                    }
+                }
+            }
+        }
+        for alert in &raised {
+            if self.alerts.len() == MAX_ALERTS {
+                self.alerts.pop_front();
+            }
+            self.alerts.push_back(alert.clone());
+        }
+        raised
+    }
+}
+
+fn describe_change(
+    rule: AlertRule,
+    member: &GroupMember,
+    previous: &MemberSnapshot,
+    current: &MemberSnapshot,
+) -> Option<String> {
+    let who = short_hex(&member.address);
+    match rule {
+        AlertRule::BalanceChanged if previous.balance_wei != current.balance_wei => Some(format!(
+            "{who} balance {} → {} {}",
+            format_eth(previous.balance_wei),
+            format_eth(current.balance_wei),
+            chains::currency(&member.chain)
+        )),
+        AlertRule::MemberTransacted if current.nonce > previous.nonce => Some(format!(
+            "{who} sent {} transaction(s)",
+            current.nonce - previous.nonce
+        )),
+        _ => None,
+    }
+}
+
+pub fn format_eth(value: U256) -> String {
+    format_units(value, "ether").unwrap_or_else(|_| value.to_string())
+}
+
+/// Fetch balance and nonce for each member from its chain's RPC endpoint.
+/// Members are polled concurrently, within each host's scheduler limit.
+pub async fn poll_members(
+    rpc: Rpc,
+    scheduler: Scheduler,
+    members: Vec<(GroupMember, Option<String>)>,
+) -> Vec<(String, Result<MemberSnapshot, String>)> {
+    let mut tasks = JoinSet::new();
+    for (member, rpc_url) in members {
+        let rpc = rpc.clone();
+        let scheduler = scheduler.clone();
+        tasks.spawn(async move {
+            let key = member_key(&member);
+            let outcome = match (rpc_url, member.address.parse::<Address>()) {
+                (None, _) => Err(format!("no RPC endpoint for {}", member.chain)),
+                (_, Err(_)) => Err("invalid address".to_string()),
+                (Some(url), Ok(address)) => {
+                    let host = host_key(Some(&url), &member.chain);
+                    let _permit = scheduler.acquire(&host, Priority::Poll).await;
+                    match timeout(
+                        Duration::from_secs(10),
+                        fetch_account_overview(&rpc, &url, address),
+                    )
+                    .await
+                    {
+                        Ok(Ok(overview)) => Ok(MemberSnapshot {
+                            balance_wei: overview.balance_wei,
+                            nonce: overview.transaction_count,
+                        }),
+                        Ok(Err(err)) => Err(format!("{err:#}")),
+                        Err(_) => Err("timed out".to_string()),
+                    }
+                }
+            };
+            (key, outcome)
+        });
+    }
+    let mut results = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok(result) = joined {
+            results.push(result);
+        }
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::AlertOutputs;
+
+    #[test]
+    fn group_rules_fire_after_baseline() {
+        let member = GroupMember {
+            address: "0x00000000000000000000000000000000000000aa".into(),
+            chain: "Mainnet".into(),
+        };
+        let mut watch = WatchState::new(vec![WatchGroupRecord {
+            name: "multisigs".into(),
+            members: vec![member.clone()],
+            rules: vec![AlertRule::MemberTransacted],
+            alerts: AlertOutputs::default(),
+        }]);
+        let key = member_key(&member);
+        let snapshot = |balance: u64, nonce: u64| MemberSnapshot {
+            balance_wei: U256::from(balance),
+            nonce,
+        };
+
+        assert!(
+            watch
+                .apply_snapshots(vec![(key.clone(), snapshot(10, 1))])
+                .is_empty()
+        );
+        // Balance-only change does not trip a transact rule.
+        assert!(
+            watch
+                .apply_snapshots(vec![(key.clone(), snapshot(5, 1))])
+                .is_empty()
+        );
+        let alerts = watch.apply_snapshots(vec![(key, snapshot(5, 3))]);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].group, "multisigs");
+        assert!(alerts[0].message.ends_with("sent 2 transaction(s)"));
+        let (totals, known) = watch.group_balance(&watch.groups[0]);
+        assert_eq!((totals.get("ETH"), known), (Some(&U256::from(5)), 1));
+    }
+}
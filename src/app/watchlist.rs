@@ -0,0 +1,415 @@
+use super::{
+    HydratedAddress, chains,
+    notify::WatchEvent,
+    prefetch::age_label,
+    safe::SafeQueue,
+    watch::{MemberSnapshot, format_eth, member_key},
+};
+use crate::{
+    storage::{AlertOutputs, GroupMember},
+    ui::util::short_hex,
+};
+use alloy::primitives::B256;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// How often watchlist balances are re-polled, when nothing in the
+/// foreground is loading.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(30);
+/// How often the Safe queues of entries with alerts on are checked.
+pub const SAFE_POLL_INTERVAL: Duration = Duration::from_secs(120);
+
+/// An address on the Watchlist tab.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchlistEntry {
+    #[serde(flatten)]
+    pub member: GroupMember,
+    /// Where its transactions and, for a Safe, new multisig proposals are
+    /// sent.
+    #[serde(flatten)]
+    pub alerts: AlertOutputs,
+}
+
+/// Addresses on the sidebar's Watchlist tab, stored under the `watchlist`
+/// setting, with their latest balance and when they were last seen active.
+#[derive(Debug, Default)]
+pub struct WatchlistState {
+    pub entries: Vec<WatchlistEntry>,
+    snapshots: HashMap<String, MemberSnapshot>,
+    /// Unix seconds of the latest activity seen, keyed by [`member_key`].
+    last_active: HashMap<String, u64>,
+    errors: HashMap<String, String>,
+    /// Queued Safe transactions already seen per entry with alerts on.
+    proposals: HashMap<String, HashSet<B256>>,
+    /// Entries the Safe service does not know; not asked again.
+    not_safes: HashSet<String>,
+}
+
+impl WatchlistState {
+    pub fn new(entries: Vec<WatchlistEntry>) -> Self {
+        Self {
+            entries,
+            ..Self::default()
+        }
+    }
+
+    fn position(&self, member: &GroupMember) -> Option<usize> {
+        let key = member_key(member);
+        self.entries
+            .iter()
+            .position(|entry| member_key(&entry.member) == key)
+    }
+
+    /// Add `member`, or remove it when already listed. Returns whether it
+    /// is on the watchlist afterwards.
+    pub fn toggle(&mut self, member: GroupMember) -> bool {
+        let key = member_key(&member);
+        if let Some(index) = self.position(&member) {
+            self.entries.remove(index);
+            self.snapshots.remove(&key);
+            self.last_active.remove(&key);
+            self.errors.remove(&key);
+            self.proposals.remove(&key);
+            false
+        } else {
+            self.entries.push(WatchlistEntry {
+                member,
+                alerts: AlertOutputs::default(),
+            });
+            true
+        }
+    }
+
+    /// Move `member` to the next [`AlertOutputs`]; `None` when it is not
+    /// listed.
+    pub fn cycle_alerts(&mut self, member: &GroupMember) -> Option<AlertOutputs> {
+        let index = self.position(member)?;
+        let entry = &mut self.entries[index];
+        entry.alerts = entry.alerts.next();
+        if !entry.alerts.any() {
+            self.proposals.remove(&member_key(member));
+        }
+        Some(entry.alerts)
+    }
+
+    pub fn contains(&self, member: &GroupMember) -> bool {
+        self.position(member).is_some()
+    }
+
+    pub fn alerts(&self, member: &GroupMember) -> AlertOutputs {
+        self.position(member)
+            .map(|index| self.entries[index].alerts)
+            .unwrap_or_default()
+    }
+
+    pub fn snapshot(&self, member: &GroupMember) -> Option<&MemberSnapshot> {
+        self.snapshots.get(&member_key(member))
+    }
+
+    pub fn error(&self, member: &GroupMember) -> Option<&str> {
+        self.errors.get(&member_key(member)).map(String::as_str)
+    }
+
+    /// Time since the latest activity seen, e.g. `3m`, as of `now` (Unix seconds).
+    pub fn activity_age(&self, member: &GroupMember, now: u64) -> Option<String> {
+        let at = self.last_active.get(&member_key(member))?;
+        Some(age_label(Duration::from_secs(now.saturating_sub(*at))))
+    }
+
+    fn mark_active(&mut self, key: &str, at: u64) {
+        let latest = self.last_active.entry(key.to_string()).or_default();
+        *latest = (*latest).max(at);
+    }
+
+    fn alerting_entry(&self, key: &str) -> Option<&WatchlistEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.alerts.any() && member_key(&entry.member) == key)
+    }
+
+    /// Store a poll's results. A nonce or balance that moved since the last
+    /// poll counts as activity at `now`. Returns the events of entries with
+    /// alerts on.
+    pub fn apply_poll(
+        &mut self,
+        results: Vec<(String, Result<MemberSnapshot, String>)>,
+        now: u64,
+    ) -> Vec<(AlertOutputs, WatchEvent)> {
+        let mut notices = Vec::new();
+        for (key, outcome) in results {
+            match outcome {
+                Ok(current) => {
+                    self.errors.remove(&key);
+                    let Some(previous) = self.snapshots.insert(key.clone(), current) else {
+                        continue;
+                    };
+                    if previous == current {
+                        continue;
+                    }
+                    self.mark_active(&key, now);
+                    if let Some(entry) = self.alerting_entry(&key) {
+                        notices.push((
+                            entry.alerts,
+                            WatchEvent {
+                                source: "watchlist",
+                                title: "Watchlist".into(),
+                                message: describe_activity(&entry.member, &previous, &current),
+                                transaction: None,
+                                subject: Some(entry.member.clone()),
+                            },
+                        ));
+                    }
+                }
+                Err(err) => {
+                    self.errors.insert(key, err);
+                }
+            }
+        }
+        notices
+    }
+
+    /// Entries with alerts on whose Safe queue should be checked.
+    pub fn safe_candidates(&self) -> Vec<GroupMember> {
+        self.entries
+            .iter()
+            .filter(|entry| {
+                entry.alerts.any() && !self.not_safes.contains(&member_key(&entry.member))
+            })
+            .map(|entry| entry.member.clone())
+            .collect()
+    }
+
+    /// Store fetched Safe queues and return an event per proposal not seen
+    /// before. The first fetch of a Safe only records what is queued.
+    pub fn apply_safe_queues(
+        &mut self,
+        results: Vec<(String, Result<SafeQueue, String>)>,
+    ) -> Vec<(AlertOutputs, WatchEvent)> {
+        let mut notices = Vec::new();
+        for (key, outcome) in results {
+            let queue = match outcome {
+                Ok(queue) => queue,
+                // Any address that is not a Safe gets a 404 from the service.
+                Err(err) if err.starts_with("not a Safe") || err.starts_with("No Safe") => {
+                    self.not_safes.insert(key);
+                    continue;
+                }
+                Err(_) => continue,
+            };
+            let Some(WatchlistEntry { member, alerts }) = self.alerting_entry(&key).cloned() else {
+                continue;
+            };
+            let baseline = !self.proposals.contains_key(&key);
+            let seen = self.proposals.entry(key).or_default();
+            for transaction in &queue.transactions {
+                if seen.insert(transaction.safe_tx_hash) && !baseline {
+                    let call = transaction.decoded.first().cloned().unwrap_or_else(|| {
+                        format!("call to {}", short_hex(&transaction.to.to_string()))
+                    });
+                    let message = format!(
+                        "Safe {} ({}): new proposal at nonce {}: {call}, {}/{} confirmations",
+                        short_hex(&member.address),
+                        member.chain,
+                        transaction.nonce,
+                        transaction.confirmations.len(),
+                        transaction.confirmations_required
+                    );
+                    notices.push((
+                        alerts,
+                        WatchEvent {
+                            source: "safe_queue",
+                            title: "Safe queue".into(),
+                            message,
+                            transaction: None,
+                            subject: Some(member.clone()),
+                        },
+                    ));
+                }
+            }
+        }
+        notices
+    }
+
+    /// Take the balance, nonce and newest transaction time from a hydrated
+    /// address, so entries show an age before the poller sees them move.
+    pub fn observe(&mut self, chain: &str, data: &HydratedAddress) {
+        let member = GroupMember {
+            address: data.identifier.clone(),
+            chain: chain.to_string(),
+        };
+        if !self.contains(&member) {
+            return;
+        }
+        let key = member_key(&member);
+        if let Some(overview) = data.overview.as_ref() {
+            self.snapshots.insert(
+                key.clone(),
+                MemberSnapshot {
+                    balance_wei: overview.balance_wei,
+                    nonce: overview.transaction_count,
+                },
+            );
+        }
+        if let Some(newest) = data
+            .transactions_table
+            .as_ref()
+            .and_then(|table| table.rows.iter().filter_map(|row| row.timestamp).max())
+        {
+            self.mark_active(&key, newest);
+        }
+    }
+}
+
+/// A sent transaction when the nonce moved, otherwise the balance change
+/// (an incoming transfer).
+fn describe_activity(
+    member: &GroupMember,
+    previous: &MemberSnapshot,
+    current: &MemberSnapshot,
+) -> String {
+    let who = format!("{} ({})", short_hex(&member.address), member.chain);
+    if current.nonce > previous.nonce {
+        format!(
+            "{who} sent {} transaction(s)",
+            current.nonce - previous.nonce
+        )
+    } else {
+        format!(
+            "{who} balance {} → {} {}",
+            format_eth(previous.balance_wei),
+            format_eth(current.balance_wei),
+            chains::currency(&member.chain)
+        )
+    }
+}
+
+/// Seconds since the Unix epoch, shared by everything that stamps or ages
+/// records.
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::safe::QueuedTransaction;
+    use alloy::primitives::{Address, Bytes, U256};
+
+    fn member() -> GroupMember {
+        GroupMember {
+            address: "0x00000000000000000000000000000000000000AA".into(),
+            chain: "Mainnet".into(),
+        }
+    }
+
+    #[test]
+    fn polls_record_balances_and_activity() {
+        let member = member();
+        let mut watchlist = WatchlistState::default();
+        assert!(watchlist.toggle(member.clone()));
+        let key = member_key(&member);
+        let snapshot = |balance: u64, nonce: u64| MemberSnapshot {
+            balance_wei: U256::from(balance),
+            nonce,
+        };
+
+        // The first poll is a baseline, not activity.
+        watchlist.apply_poll(vec![(key.clone(), Ok(snapshot(10, 1)))], 1_000);
+        assert_eq!(watchlist.activity_age(&member, 1_000), None);
+        watchlist.apply_poll(vec![(key.clone(), Ok(snapshot(10, 1)))], 1_030);
+        assert_eq!(watchlist.activity_age(&member, 1_030), None);
+        let notices = watchlist.apply_poll(vec![(key.clone(), Ok(snapshot(15, 1)))], 1_060);
+        assert!(notices.is_empty());
+        assert_eq!(
+            watchlist.activity_age(&member, 1_240).as_deref(),
+            Some("3m")
+        );
+        assert_eq!(watchlist.snapshot(&member), Some(&snapshot(15, 1)));
+
+        assert_eq!(
+            watchlist.cycle_alerts(&member).map(|a| a.notify),
+            Some(true)
+        );
+        let notices = watchlist.apply_poll(vec![(key.clone(), Ok(snapshot(5, 3)))], 1_090);
+        assert_eq!(notices.len(), 1);
+        assert!(notices[0].0.notify && !notices[0].0.webhook);
+        assert_eq!(
+            notices[0].1.message,
+            "0x0000...00AA (Mainnet) sent 2 transaction(s)"
+        );
+
+        watchlist.apply_poll(vec![(key.clone(), Err("timed out".into()))], 1_120);
+        assert_eq!(watchlist.error(&member), Some("timed out"));
+        assert_eq!(watchlist.snapshot(&member), Some(&snapshot(5, 3)));
+
+        let lowercase = GroupMember {
+            address: member.address.to_ascii_lowercase(),
+            chain: "mainnet".into(),
+        };
+        assert!(!watchlist.toggle(lowercase));
+        assert!(watchlist.entries.is_empty());
+        assert_eq!(watchlist.snapshot(&member), None);
+        assert_eq!(watchlist.cycle_alerts(&member), None);
+
+        // Entries saved before alerts existed, or before webhooks, still load.
+        let stored: Vec<WatchlistEntry> = serde_json::from_str(
+            r#"[{"address":"0xaa","chain":"Base"},{"address":"0xbb","chain":"Base","notify":true}]"#,
+        )
+        .unwrap();
+        assert!(!stored[0].alerts.any());
+        assert!(stored[1].alerts.notify && !stored[1].alerts.webhook);
+    }
+
+    #[test]
+    fn new_safe_proposals_are_reported_after_the_first_fetch() {
+        let member = member();
+        let key = member_key(&member);
+        let mut watchlist = WatchlistState::default();
+        watchlist.toggle(member.clone());
+        watchlist.cycle_alerts(&member);
+        let proposal = |byte: u8| QueuedTransaction {
+            safe_tx_hash: B256::repeat_byte(byte),
+            nonce: 7,
+            to: Address::repeat_byte(0x10),
+            value: U256::ZERO,
+            data: Bytes::new(),
+            delegate_call: false,
+            confirmations: Vec::new(),
+            confirmations_required: 2,
+            submitted: String::new(),
+            decoded: vec!["transfer(to, value)".into()],
+        };
+        let queue = |transactions| SafeQueue {
+            threshold: 2,
+            owners: Vec::new(),
+            nonce: 7,
+            transactions,
+        };
+
+        let first = watchlist.apply_safe_queues(vec![(key.clone(), Ok(queue(vec![proposal(1)])))]);
+        assert!(first.is_empty());
+        let next = watchlist.apply_safe_queues(vec![(
+            key.clone(),
+            Ok(queue(vec![proposal(1), proposal(2)])),
+        )]);
+        assert_eq!(next.len(), 1);
+        assert!(
+            next[0]
+                .1
+                .message
+                .contains("nonce 7: transfer(to, value), 0/2")
+        );
+
+        watchlist.apply_safe_queues(vec![(
+            key,
+            Err("not a Safe known to the Safe Transaction Service".into()),
+        )]);
+        assert!(watchlist.safe_candidates().is_empty());
+    }
+}
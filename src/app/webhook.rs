@@ -0,0 +1,92 @@
+use super::{SecretsState, notify::WatchEvent, stats::ApiStats};
+use crate::storage::SecretKey;
+use serde_json::{Value, json};
+use std::time::Duration;
+
+/// Provider name of the webhook URL in the secrets store.
+pub const PROVIDER: &str = "webhook";
+pub const FIELD: &str = "url";
+
+pub fn key() -> SecretKey {
+    SecretKey::ProviderCredential {
+        provider: PROVIDER.into(),
+        field: FIELD.into(),
+    }
+}
+
+pub fn url(secrets: &SecretsState) -> Option<&str> {
+    secrets.get(&key()).filter(|url| !url.is_empty())
+}
+
+/// JSON body for `event` raised at `timestamp` (Unix seconds). Slack reads
+/// `text` and Discord `content`; other receivers get the fields apart.
+pub fn payload(event: &WatchEvent, timestamp: u64) -> Value {
+    let summary = format!("{}: {}", event.title, event.message);
+    json!({
+        "text": summary,
+        "content": summary,
+        "source": event.source,
+        "title": event.title,
+        "message": event.message,
+        "chain": event.subject.as_ref().map(|member| member.chain.as_str()),
+        "address": event.subject.as_ref().map(|member| member.address.as_str()),
+        "transaction": event.transaction,
+        "timestamp": timestamp,
+    })
+}
+
+pub async fn post(stats: &ApiStats, url: String, payload: Value) -> Result<(), String> {
+    stats.record_api_call("webhook");
+    let client = reqwest::Client::builder()
+        .user_agent("evm-tui/0.1.0")
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|err| err.to_string())?;
+    let response = client
+        .post(&url)
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|err| format!("Webhook request failed: {err}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("Webhook returned {status}"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::GroupMember;
+
+    #[test]
+    fn payload_reads_in_chat_apps_and_carries_the_subject() {
+        let event = WatchEvent {
+            source: "watchlist",
+            title: "Watchlist".into(),
+            message: "0x00…aa (Mainnet) sent 1 transaction(s)".into(),
+            transaction: None,
+            subject: Some(GroupMember {
+                address: "0x00000000000000000000000000000000000000aa".into(),
+                chain: "Mainnet".into(),
+            }),
+        };
+        let body = payload(&event, 1_700_000_000);
+        assert_eq!(
+            body["text"],
+            "Watchlist: 0x00…aa (Mainnet) sent 1 transaction(s)"
+        );
+        assert_eq!(body["content"], body["text"]);
+        assert_eq!(body["chain"], "Mainnet");
+        assert_eq!(body["timestamp"], 1_700_000_000);
+
+        let group = WatchEvent {
+            subject: None,
+            transaction: Some("0x01".into()),
+            ..event
+        };
+        assert!(payload(&group, 0)["address"].is_null());
+        assert_eq!(payload(&group, 0)["transaction"], "0x01");
+    }
+}
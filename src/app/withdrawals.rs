@@ -0,0 +1,106 @@
+use super::{explorer::BeaconWithdrawal, format_eth_value};
+use crate::ui::util::format_timestamp;
+use alloy::primitives::U256;
+use std::collections::BTreeSet;
+
+/// Withdrawals fetched per address; totals cover only these.
+pub const WITHDRAWAL_FETCH_LIMIT: usize = 100;
+
+/// Most recent withdrawals listed one per line in the Info tab.
+const LISTED: usize = 5;
+
+/// Validator indices named before the rest are summarized as a count.
+const NAMED_VALIDATORS: usize = 4;
+
+/// Info tab lines for the consensus-layer withdrawals of an address, newest
+/// first: the total, the validators paying it, and the latest few.
+pub fn info_lines(withdrawals: &[BeaconWithdrawal]) -> Vec<String> {
+    let Some(latest) = withdrawals.first() else {
+        return Vec::new();
+    };
+    let total = withdrawals.iter().fold(U256::ZERO, |total, withdrawal| {
+        total + withdrawal.amount_wei
+    });
+    let validators: BTreeSet<u64> = withdrawals
+        .iter()
+        .map(|withdrawal| withdrawal.validator_index)
+        .collect();
+    let mut named: Vec<String> = validators
+        .iter()
+        .take(NAMED_VALIDATORS)
+        .map(u64::to_string)
+        .collect();
+    if validators.len() > NAMED_VALIDATORS {
+        named.push(format!("+{} more", validators.len() - NAMED_VALIDATORS));
+    }
+    let scope = if withdrawals.len() >= WITHDRAWAL_FETCH_LIMIT {
+        format!("latest {}", withdrawals.len())
+    } else {
+        "all".into()
+    };
+    let mut lines = vec![
+        format!(
+            "Beacon withdrawals: {} withdrawn over {scope} {} (since block {})",
+            format_eth_value(&total),
+            if withdrawals.len() == 1 {
+                "withdrawal".to_string()
+            } else {
+                format!("{} withdrawals", withdrawals.len())
+            },
+            withdrawals
+                .last()
+                .map_or(latest.block_number, |oldest| oldest.block_number)
+        ),
+        format!(
+            "Validator{}: {}",
+            if validators.len() == 1 { "" } else { "s" },
+            named.join(", ")
+        ),
+    ];
+    lines.extend(withdrawals.iter().take(LISTED).map(|withdrawal| {
+        let when = withdrawal
+            .timestamp
+            .map(|timestamp| format!(" • {}", format_timestamp(timestamp)))
+            .unwrap_or_default();
+        format!(
+            "  {} from validator {} • block {}{when}",
+            format_eth_value(&withdrawal.amount_wei),
+            withdrawal.validator_index,
+            withdrawal.block_number
+        )
+    }));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::etherscan::parse_beacon_withdrawals;
+    use serde_json::json;
+
+    #[test]
+    fn totals_withdrawals_in_ether() {
+        let result = json!([
+            {"withdrawalIndex": "20", "validatorIndex": "117823",
+             "address": "0xb9d7934878b5fb9610b3fe8a5e441e8fad7e293f",
+             "amount": "16511250", "blockNumber": "17100000", "timestamp": "1681338599"},
+            {"withdrawalIndex": "13", "validatorIndex": "117824",
+             "address": "0xb9d7934878b5fb9610b3fe8a5e441e8fad7e293f",
+             "amount": "32000000000", "blockNumber": "17034877", "timestamp": "1681130000"}
+        ]);
+        let withdrawals = parse_beacon_withdrawals(result).unwrap();
+        assert_eq!(
+            withdrawals[0].amount_wei,
+            U256::from(16_511_250_000_000_000u64)
+        );
+
+        let lines = info_lines(&withdrawals);
+        assert_eq!(
+            lines[0],
+            "Beacon withdrawals: 32.01651125 ETH withdrawn over all 2 withdrawals (since block 17034877)"
+        );
+        assert_eq!(lines[1], "Validators: 117823, 117824");
+        assert!(lines[2].starts_with("  0.01651125 ETH from validator 117823 • block 17100000"));
+        assert!(info_lines(&[]).is_empty());
+    }
+}
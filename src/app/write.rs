@@ -0,0 +1,167 @@
+use super::{convert::parse_amount, upgrade_impact::format_value};
+use alloy::{
+    dyn_abi::{DynSolType, JsonAbiExt},
+    json_abi::{Function, JsonAbi, StateMutability},
+    primitives::{Bytes, U256},
+};
+
+/// State-changing functions of a contract's ABI, sorted by signature.
+pub fn writable_functions(abi: &str) -> Result<Vec<Function>, String> {
+    let abi: JsonAbi =
+        serde_json::from_str(abi).map_err(|err| format!("ABI is not valid JSON: {err}"))?;
+    let mut functions: Vec<Function> = abi
+        .functions()
+        .filter(|function| {
+            matches!(
+                function.state_mutability,
+                StateMutability::NonPayable | StateMutability::Payable
+            )
+        })
+        .cloned()
+        .collect();
+    functions.sort_by_key(Function::signature);
+    Ok(functions)
+}
+
+/// Value to send: wei, or an amount with a unit (`0.1 ether`); empty is zero.
+pub fn parse_value(input: &str) -> Result<U256, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(U256::ZERO);
+    }
+    parse_amount(input)
+        .map(|(value, _)| value)
+        .ok_or_else(|| format!("Value: {input:?} is not an amount"))
+}
+
+/// `name(type name, …)` for the argument prompt.
+pub fn describe_inputs(function: &Function) -> String {
+    let inputs: Vec<String> = function
+        .inputs
+        .iter()
+        .map(|param| match param.name.as_str() {
+            "" => param.selector_type().into_owned(),
+            name => format!("{} {name}", param.selector_type()),
+        })
+        .collect();
+    format!("{}({})", function.name, inputs.join(", "))
+}
+
+/// Split comma-separated arguments, keeping commas inside brackets,
+/// parentheses and quotes with their argument.
+fn split_args(input: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    let mut quoted = false;
+    for c in input.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            '[' | '(' if !quoted => depth += 1,
+            ']' | ')' if !quoted => depth = depth.saturating_sub(1),
+            ',' if !quoted && depth == 0 => {
+                args.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    if !current.trim().is_empty() || !args.is_empty() {
+        args.push(current.trim().to_string());
+    }
+    args
+}
+
+/// Calldata for `function` with `args` typed as `cast send` takes them:
+/// comma-separated, arrays as `[1,2]`, tuples as `(a,b)`.
+pub fn encode_call(function: &Function, args: &str) -> Result<Bytes, String> {
+    let args = split_args(args);
+    if args.len() != function.inputs.len() {
+        return Err(format!(
+            "{} takes {} argument(s), got {}",
+            function.name,
+            function.inputs.len(),
+            args.len()
+        ));
+    }
+    let values = function
+        .inputs
+        .iter()
+        .zip(&args)
+        .map(|(param, arg)| {
+            let ty = DynSolType::parse(&param.selector_type())
+                .map_err(|err| format!("unsupported type {}: {err}", param.ty))?;
+            ty.coerce_str(arg.trim_matches('"'))
+                .map_err(|err| format!("{}: {err}", param.name))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    function
+        .abi_encode_input(&values)
+        .map(Bytes::from)
+        .map_err(|err| format!("cannot encode {}: {err}", function.name))
+}
+
+/// `name(param: value, …)` decoded back from calldata, so the confirmation
+/// shows what will actually be signed rather than what was typed.
+pub fn decode_call(function: &Function, calldata: &[u8]) -> Result<String, String> {
+    let values = function
+        .abi_decode_input(calldata.get(4..).unwrap_or_default())
+        .map_err(|err| format!("calldata does not decode: {err}"))?;
+    let args: Vec<String> = function
+        .inputs
+        .iter()
+        .zip(&values)
+        .map(|(param, value)| match param.name.as_str() {
+            "" => format_value(value),
+            name => format!("{name}: {}", format_value(value)),
+        })
+        .collect();
+    Ok(format!("{}({})", function.name, args.join(", ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::hex;
+
+    #[test]
+    fn encodes_and_decodes_typed_arguments() {
+        let abi = r#"[
+            {"type": "function", "name": "balanceOf", "stateMutability": "view",
+             "inputs": [{"name": "owner", "type": "address"}], "outputs": []},
+            {"type": "function", "name": "transfer", "stateMutability": "nonpayable",
+             "inputs": [{"name": "to", "type": "address"}, {"name": "amount", "type": "uint256"}],
+             "outputs": []},
+            {"type": "function", "name": "batch", "stateMutability": "payable",
+             "inputs": [{"name": "ids", "type": "uint256[]"}, {"name": "note", "type": "string"}],
+             "outputs": []}
+        ]"#;
+        let functions = writable_functions(abi).unwrap();
+        let names: Vec<&str> = functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["batch", "transfer"]);
+        let transfer = &functions[1];
+        assert_eq!(
+            describe_inputs(transfer),
+            "transfer(address to, uint256 amount)"
+        );
+
+        let calldata =
+            encode_call(transfer, "0x00000000000000000000000000000000000000aa, 1000").unwrap();
+        assert_eq!(hex::encode(&calldata[..4]), "a9059cbb");
+        assert_eq!(
+            decode_call(transfer, &calldata).unwrap(),
+            "transfer(to: 0x00000000000000000000000000000000000000AA, amount: 1000)"
+        );
+
+        let batch = &functions[0];
+        let calldata = encode_call(batch, "[1, 2, 3], \"a, b\"").unwrap();
+        assert_eq!(
+            decode_call(batch, &calldata).unwrap(),
+            "batch(ids: [1, 2, 3], note: \"a, b\")"
+        );
+        assert!(encode_call(transfer, "0x00000000000000000000000000000000000000aa").is_err());
+        assert!(encode_call(transfer, "nope, 1").is_err());
+    }
+}
@@ -0,0 +1,266 @@
+use super::Message;
+use alloy::{
+    consensus::BlockHeader,
+    primitives::Address,
+    providers::{DynProvider, Provider, ProviderBuilder, WsConnect},
+    pubsub::Subscription,
+    rpc::types::Filter,
+};
+use color_eyre::{
+    Result,
+    eyre::{WrapErr, eyre},
+};
+use serde::de::DeserializeOwned;
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex, mpsc},
+    time::{Duration, Instant},
+};
+use tokio::{runtime::Handle, sync::broadcast::error::RecvError, task::JoinHandle, time::sleep};
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// State of the shared connection to one WebSocket endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionState {
+    Connecting,
+    Connected,
+    /// A subscription lost the connection and retries at `retry_at`.
+    Reconnecting {
+        attempt: u32,
+        retry_at: Instant,
+        error: String,
+    },
+    /// A request failed; the next one reconnects.
+    Disconnected(String),
+}
+
+/// Open connections keyed by URL, shared by requests and subscriptions so a
+/// `wss://` endpoint costs one socket for the whole session. Clones share
+/// the connections.
+#[derive(Clone, Default)]
+pub struct WsConnections {
+    providers: Arc<Mutex<BTreeMap<String, DynProvider>>>,
+    states: Arc<Mutex<BTreeMap<String, ConnectionState>>>,
+}
+
+pub fn is_ws(url: &str) -> bool {
+    url.starts_with("ws://") || url.starts_with("wss://")
+}
+
+/// Delay before reconnect attempt `attempt` (1-based): doubling from 1s up
+/// to 30s.
+pub fn backoff(attempt: u32) -> Duration {
+    INITIAL_BACKOFF
+        .saturating_mul(1 << attempt.saturating_sub(1).min(5))
+        .min(MAX_BACKOFF)
+}
+
+impl WsConnections {
+    pub fn state(&self, url: &str) -> Option<ConnectionState> {
+        self.states.lock().ok()?.get(url).cloned()
+    }
+
+    fn set_state(&self, url: &str, state: ConnectionState) {
+        if let Ok(mut states) = self.states.lock() {
+            states.insert(url.to_string(), state);
+        }
+    }
+
+    /// The shared connection to `url`, opened on first use. alloy's own
+    /// retry loop is disabled so reconnects go through [`backoff`] and show
+    /// up in [`WsConnections::state`].
+    pub async fn provider(&self, url: &str) -> Result<DynProvider> {
+        let cached = self
+            .providers
+            .lock()
+            .ok()
+            .and_then(|providers| providers.get(url).cloned());
+        if let Some(provider) = cached {
+            return Ok(provider);
+        }
+        self.set_state(url, ConnectionState::Connecting);
+        let provider = match ProviderBuilder::new()
+            .connect_ws(WsConnect::new(url).with_max_retries(0))
+            .await
+        {
+            Ok(provider) => provider.erased(),
+            Err(err) => {
+                let err = eyre!(err).wrap_err(format!("failed to connect to WebSocket at {url}"));
+                self.set_state(url, ConnectionState::Disconnected(format!("{err:#}")));
+                return Err(err);
+            }
+        };
+        if let Ok(mut providers) = self.providers.lock() {
+            providers.insert(url.to_string(), provider.clone());
+        }
+        self.set_state(url, ConnectionState::Connected);
+        Ok(provider)
+    }
+
+    /// Drop the shared connection after a failure so the next use
+    /// reconnects.
+    pub fn invalidate(&self, url: &str, error: String) {
+        if let Ok(mut providers) = self.providers.lock() {
+            providers.remove(url);
+        }
+        self.set_state(url, ConnectionState::Disconnected(error));
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubscriptionKind {
+    NewHeads,
+    /// Logs emitted by one contract.
+    Logs(Address),
+    PendingTransactions,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubscriptionEvent {
+    NewHead(u64),
+    Log {
+        block_number: Option<u64>,
+        transaction_hash: Option<String>,
+    },
+    PendingTransaction(String),
+}
+
+/// A running subscription; dropping it unsubscribes.
+#[derive(Debug)]
+pub struct SubscriptionHandle(JoinHandle<()>);
+
+impl Drop for SubscriptionHandle {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Stream `kind` from `url` into the app's message queue until the handle is
+/// dropped, reconnecting with [`backoff`] whenever the socket goes away.
+pub fn subscribe(
+    runtime: &Handle,
+    sender: mpsc::Sender<Message>,
+    connections: WsConnections,
+    url: String,
+    kind: SubscriptionKind,
+) -> SubscriptionHandle {
+    SubscriptionHandle(runtime.spawn(async move {
+        let mut attempt = 0;
+        loop {
+            let Err(err) = stream_events(&connections, &sender, &url, &kind, &mut attempt).await
+            else {
+                // The app stopped listening.
+                return;
+            };
+            attempt += 1;
+            let delay = backoff(attempt);
+            connections.invalidate(&url, format!("{err:#}"));
+            connections.set_state(
+                &url,
+                ConnectionState::Reconnecting {
+                    attempt,
+                    retry_at: Instant::now() + delay,
+                    error: format!("{err:#}"),
+                },
+            );
+            sleep(delay).await;
+        }
+    }))
+}
+
+async fn stream_events(
+    connections: &WsConnections,
+    sender: &mpsc::Sender<Message>,
+    url: &str,
+    kind: &SubscriptionKind,
+    attempt: &mut u32,
+) -> Result<()> {
+    let provider = connections.provider(url).await?;
+    match kind {
+        SubscriptionKind::NewHeads => {
+            let subscription = provider
+                .subscribe_blocks()
+                .await
+                .wrap_err("newHeads subscription failed")?;
+            *attempt = 0;
+            forward(subscription, sender, |header| {
+                SubscriptionEvent::NewHead(header.number())
+            })
+            .await
+        }
+        SubscriptionKind::Logs(address) => {
+            let subscription = provider
+                .subscribe_logs(&Filter::new().address(*address))
+                .await
+                .wrap_err("logs subscription failed")?;
+            *attempt = 0;
+            forward(subscription, sender, |log| SubscriptionEvent::Log {
+                block_number: log.block_number,
+                transaction_hash: log.transaction_hash.map(|hash| format!("{hash:#x}")),
+            })
+            .await
+        }
+        SubscriptionKind::PendingTransactions => {
+            let subscription = provider
+                .subscribe_pending_transactions()
+                .await
+                .wrap_err("pendingTransactions subscription failed")?;
+            *attempt = 0;
+            forward(subscription, sender, |hash| {
+                SubscriptionEvent::PendingTransaction(format!("{hash:#x}"))
+            })
+            .await
+        }
+    }
+}
+
+/// Relay items until the subscription closes (an error, so the caller
+/// reconnects) or the app hangs up (`Ok`).
+async fn forward<T, F>(
+    mut subscription: Subscription<T>,
+    sender: &mpsc::Sender<Message>,
+    event: F,
+) -> Result<()>
+where
+    T: DeserializeOwned,
+    F: Fn(T) -> SubscriptionEvent,
+{
+    loop {
+        match subscription.recv().await {
+            Ok(item) => {
+                if sender.send(Message::Subscription(event(item))).is_err() {
+                    return Ok(());
+                }
+            }
+            // Missed items while the UI was busy; later ones still arrive.
+            Err(RecvError::Lagged(_)) => continue,
+            Err(RecvError::Closed) => return Err(eyre!("WebSocket connection closed")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_cap() {
+        let delays: Vec<_> = (1..=7).map(|attempt| backoff(attempt).as_secs()).collect();
+        assert_eq!(delays, vec![1, 2, 4, 8, 16, 30, 30]);
+        assert!(is_ws("wss://mainnet.example/ws"));
+        assert!(!is_ws("https://mainnet.example"));
+
+        let connections = WsConnections::default();
+        connections.invalidate("wss://mainnet.example/ws", "closed".into());
+        assert_eq!(
+            connections.state("wss://mainnet.example/ws"),
+            Some(ConnectionState::Disconnected("closed".into()))
+        );
+        assert_eq!(
+            WsConnections::default().state("wss://mainnet.example/ws"),
+            None
+        );
+    }
+}
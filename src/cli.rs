@@ -0,0 +1,93 @@
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+use color_eyre::{Result, eyre::WrapErr};
+use std::{fs, io, path::PathBuf};
+
+/// Terminal explorer for EVM addresses and transactions.
+#[derive(Debug, Parser)]
+#[command(name = "evm-tui", version)]
+pub struct Cli {
+    /// Open this view, e.g. `evmtui://mainnet/address/0x…?tab=transactions`
+    /// (`:link` copies the link to the current one).
+    pub link: Option<String>,
+    /// Explore built-in fixture data instead of live explorers and RPC
+    /// endpoints: no keys needed, and nothing is saved.
+    #[arg(long)]
+    pub demo: bool,
+    #[command(subcommand)]
+    pub command: Option<CliCommand>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CliCommand {
+    /// Print a shell completion script to stdout.
+    Completions {
+        /// Target shell.
+        shell: Shell,
+    },
+    /// Generate the roff man page (stdout unless --out-dir is given).
+    Man {
+        /// Directory to write `evm-tui.1` into.
+        #[arg(long)]
+        out_dir: Option<PathBuf>,
+    },
+}
+
+impl CliCommand {
+    pub fn run(self) -> Result<()> {
+        match self {
+            CliCommand::Completions { shell } => {
+                let mut command = Cli::command();
+                clap_complete::generate(shell, &mut command, "evm-tui", &mut io::stdout());
+                Ok(())
+            }
+            CliCommand::Man { out_dir } => {
+                let man = clap_mangen::Man::new(Cli::command());
+                match out_dir {
+                    Some(dir) => {
+                        fs::create_dir_all(&dir).wrap_err("failed to create man page directory")?;
+                        let path = dir.join("evm-tui.1");
+                        let mut file =
+                            fs::File::create(&path).wrap_err("failed to create man page file")?;
+                        man.render(&mut file)
+                            .wrap_err("failed to render man page")?;
+                        println!("{}", path.display());
+                        Ok(())
+                    }
+                    None => man
+                        .render(&mut io::stdout())
+                        .wrap_err("failed to render man page"),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cli_definition_is_consistent() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn parses_completions_subcommand() {
+        let cli = Cli::try_parse_from(["evm-tui", "completions", "zsh"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(CliCommand::Completions { shell: Shell::Zsh })
+        ));
+    }
+
+    #[test]
+    fn parses_a_link_argument() {
+        let link = "evmtui://mainnet/tx/0xab?tab=fees";
+        let cli = Cli::try_parse_from(["evm-tui", link]).unwrap();
+        assert_eq!(cli.link.as_deref(), Some(link));
+        assert!(cli.command.is_none());
+        assert!(!cli.demo);
+        assert!(Cli::try_parse_from(["evm-tui", "--demo"]).unwrap().demo);
+    }
+}
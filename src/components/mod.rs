@@ -2,6 +2,19 @@ use crate::app::{Action, AppContext, AppResult, AppView};
 use ratatui::Frame;
 use ratatui::layout::Rect;
 
+/// One shortcut shown in the bottom bar, e.g. `s` / `Sort`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyHint {
+    pub key: &'static str,
+    pub label: &'static str,
+}
+
+impl KeyHint {
+    pub const fn new(key: &'static str, label: &'static str) -> Self {
+        Self { key, label }
+    }
+}
+
 /// Trait implemented by all UI components (panes, modals, etc.).
 pub trait Component {
     /// Component-local action type. Returned actions will be lifted into the global [`Action`].
@@ -22,4 +35,10 @@ pub trait Component {
 
     /// Called on every tick to perform periodic work (e.g., animation, polling).
     fn tick(&mut self, ctx: &mut AppContext<'_>) -> AppResult<Option<Action>>;
+
+    /// Shortcuts that apply while this component has focus, most specific
+    /// first; the bottom bar shows them ahead of the global ones.
+    fn key_hints(&self, _ctx: &AppView<'_>) -> Vec<KeyHint> {
+        Vec::new()
+    }
 }
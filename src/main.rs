@@ -1,14 +1,36 @@
 mod app;
+mod cli;
 mod components;
 mod storage;
 mod ui;
 
-use color_eyre::Result;
+use clap::Parser;
+use color_eyre::{Result, eyre::eyre};
 
 fn main() -> Result<()> {
     color_eyre::install()?;
+    let cli = cli::Cli::parse();
+    if let Some(command) = cli.command {
+        return command.run();
+    }
+    // Checked before the terminal is taken over, so a bad link is reported
+    // plainly.
+    let link = cli
+        .link
+        .as_deref()
+        .map(app::deep_link::parse)
+        .transpose()
+        .map_err(|err| eyre!(err))?;
+    let app = if cli.demo {
+        app::App::demo(link, app::demo::providers())?
+    } else {
+        app::App::new(link, app::providers::Providers::default())?
+    };
     let terminal = ratatui::init();
-    let result = app::App::new()?.run(terminal);
+    let result = app.run(terminal);
     ratatui::restore();
-    result
+    for json in result? {
+        println!("{json}");
+    }
+    Ok(())
 }
@@ -0,0 +1,238 @@
+use super::{FavoriteRecord, FavoritesTable, Storage, TrashRecord, unix_now};
+use color_eyre::{Result, eyre::WrapErr};
+use fjall::{Batch, PartitionHandle, PersistMode};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum FavoriteOp {
+    Upsert {
+        table: FavoritesTable,
+        record: FavoriteRecord,
+    },
+    /// Remove from favorites and keep a copy in the trash partition.
+    Trash(TrashRecord),
+    /// Move a trashed favorite back.
+    Restore {
+        table: FavoritesTable,
+        record: FavoriteRecord,
+    },
+}
+
+/// A group of favorites mutations applied all-or-nothing.
+///
+/// The ops are first written to the `journal` partition and synced; the
+/// favorites partitions are then updated in one fjall batch that also clears
+/// the journal entry. A crash between the two steps leaves the entry behind
+/// and [`Storage::open`] replays it, so every op is idempotent.
+#[must_use = "a favorites batch does nothing until committed"]
+pub struct FavoritesBatch<'a> {
+    storage: &'a Storage,
+    ops: Vec<FavoriteOp>,
+}
+
+impl<'a> FavoritesBatch<'a> {
+    pub(super) fn new(storage: &'a Storage) -> Self {
+        Self {
+            storage,
+            ops: Vec::new(),
+        }
+    }
+
+    pub fn upsert(mut self, table: FavoritesTable, record: FavoriteRecord) -> Self {
+        self.ops.push(FavoriteOp::Upsert { table, record });
+        self
+    }
+
+    pub fn trash(mut self, table: FavoritesTable, record: FavoriteRecord) -> Self {
+        self.ops.push(FavoriteOp::Trash(TrashRecord {
+            table,
+            record,
+            deleted_at: unix_now(),
+        }));
+        self
+    }
+
+    pub fn restore(mut self, entry: &TrashRecord) -> Self {
+        self.ops.push(FavoriteOp::Restore {
+            table: entry.table,
+            record: entry.record.clone(),
+        });
+        self
+    }
+
+    pub fn commit(self) -> Result<()> {
+        if self.ops.is_empty() {
+            return Ok(());
+        }
+        let journal = &self.storage.journal;
+        let sequence = match journal.last_key_value()? {
+            Some((key, _)) => decode_sequence(&key).map_or(0, |seq| seq + 1),
+            None => 0,
+        };
+        let entry = serde_json::to_vec(&self.ops).wrap_err("failed to serialize journal entry")?;
+        journal
+            .insert(sequence.to_be_bytes(), entry)
+            .wrap_err("failed to write favorites journal")?;
+        self.storage.keyspace.persist(PersistMode::SyncAll)?;
+
+        apply(self.storage, &self.ops, sequence)
+    }
+}
+
+/// Replay journal entries left behind by an interrupted commit. Returns how many
+/// entries were recovered.
+pub(super) fn reconcile(storage: &Storage) -> Result<usize> {
+    let mut pending = Vec::new();
+    for entry in storage.journal.iter() {
+        let (key, value) = entry?;
+        pending.push((key, value));
+    }
+    let mut recovered = 0;
+    for (key, value) in pending {
+        let Some(sequence) = decode_sequence(&key) else {
+            storage.journal.remove(key)?;
+            continue;
+        };
+        match serde_json::from_slice::<Vec<FavoriteOp>>(&value) {
+            Ok(ops) => {
+                apply(storage, &ops, sequence)?;
+                recovered += 1;
+            }
+            Err(_) => storage.journal.remove(key)?,
+        }
+    }
+    Ok(recovered)
+}
+
+fn apply(storage: &Storage, ops: &[FavoriteOp], sequence: u64) -> Result<()> {
+    let mut batch: Batch = storage.keyspace.batch();
+    for op in ops {
+        match op {
+            FavoriteOp::Upsert { table, record } => {
+                let bytes =
+                    serde_json::to_vec(record).wrap_err("failed to serialize favorite record")?;
+                batch.insert(
+                    partition(storage, *table),
+                    record.identifier.as_str(),
+                    bytes,
+                );
+            }
+            FavoriteOp::Trash(entry) => {
+                let key = TrashRecord::storage_key(entry.table, &entry.record.identifier);
+                let bytes =
+                    serde_json::to_vec(entry).wrap_err("failed to serialize trash record")?;
+                batch.remove(
+                    partition(storage, entry.table),
+                    entry.record.identifier.as_str(),
+                );
+                batch.insert(storage.trash.handle(), key, bytes);
+            }
+            FavoriteOp::Restore { table, record } => {
+                let key = TrashRecord::storage_key(*table, &record.identifier);
+                let bytes =
+                    serde_json::to_vec(record).wrap_err("failed to serialize favorite record")?;
+                batch.insert(
+                    partition(storage, *table),
+                    record.identifier.as_str(),
+                    bytes,
+                );
+                batch.remove(storage.trash.handle(), key);
+            }
+        }
+    }
+    batch.remove(&storage.journal, sequence.to_be_bytes());
+    batch.commit().wrap_err("failed to apply favorites batch")?;
+    storage.keyspace.persist(PersistMode::SyncAll)?;
+    Ok(())
+}
+
+fn partition(storage: &Storage, table: FavoritesTable) -> &PartitionHandle {
+    match table {
+        FavoritesTable::Addresses => storage.favorites_addresses.handle(),
+        FavoritesTable::Transactions => storage.favorites_transactions.handle(),
+    }
+}
+
+fn decode_sequence(key: &[u8]) -> Option<u64> {
+    key.try_into().ok().map(u64::from_be_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn record(identifier: &str) -> FavoriteRecord {
+        FavoriteRecord {
+            label: Some("label".into()),
+            identifier: identifier.into(),
+            chain: "Mainnet".into(),
+            order: 0,
+        }
+    }
+
+    #[test]
+    fn interrupted_batch_is_replayed_on_open() -> Result<()> {
+        let temp = tempdir().unwrap();
+        {
+            let storage = Storage::open(temp.path())?;
+            storage
+                .favorites_batch()
+                .upsert(FavoritesTable::Addresses, record("0xaa"))
+                .commit()?;
+
+            // Simulate a crash after the journal write but before the batch applied.
+            let ops = vec![
+                FavoriteOp::Trash(TrashRecord {
+                    table: FavoritesTable::Addresses,
+                    record: record("0xaa"),
+                    deleted_at: unix_now(),
+                }),
+                FavoriteOp::Upsert {
+                    table: FavoritesTable::Transactions,
+                    record: record("0xbb"),
+                },
+            ];
+            storage
+                .journal
+                .insert(7u64.to_be_bytes(), serde_json::to_vec(&ops)?)?;
+        }
+
+        let storage = Storage::open(temp.path())?;
+        assert_eq!(storage.recovered_journal_entries(), 1);
+        assert!(storage.favorites_addresses().list()?.is_empty());
+        assert_eq!(storage.trash().list()?.len(), 1);
+        assert_eq!(storage.favorites_transactions().list()?.len(), 1);
+        assert!(storage.journal.is_empty()?);
+        Ok(())
+    }
+
+    #[test]
+    fn trashed_favorites_restore_and_expire() -> Result<()> {
+        let temp = tempdir().unwrap();
+        let storage = Storage::open(temp.path())?;
+        storage
+            .favorites_batch()
+            .upsert(FavoritesTable::Addresses, record("0xaa"))
+            .commit()?;
+        storage
+            .favorites_batch()
+            .trash(FavoritesTable::Addresses, record("0xaa"))
+            .commit()?;
+        assert!(storage.favorites_addresses().list()?.is_empty());
+
+        let trashed = storage.trash().list()?;
+        assert_eq!(trashed.len(), 1);
+        storage.favorites_batch().restore(&trashed[0]).commit()?;
+        assert_eq!(storage.favorites_addresses().list()?.len(), 1);
+        assert!(storage.trash().list()?.is_empty());
+
+        storage
+            .favorites_batch()
+            .trash(FavoritesTable::Addresses, record("0xaa"))
+            .commit()?;
+        assert_eq!(storage.trash().purge_older_than(u64::MAX)?, 1);
+        assert!(storage.trash().list()?.is_empty());
+        Ok(())
+    }
+}
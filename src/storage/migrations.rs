@@ -0,0 +1,241 @@
+use super::{FavoriteRecord, FavoritesTable, Storage, TrashRecord};
+use alloy::primitives::Address;
+use color_eyre::{
+    Result,
+    eyre::{WrapErr, eyre},
+};
+use fjall::{PartitionHandle, PersistMode};
+use std::collections::BTreeMap;
+
+const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+struct Migration {
+    description: &'static str,
+    run: fn(&Storage) -> Result<()>,
+}
+
+/// Ordered schema migrations; entry `n` upgrades version `n` to `n + 1`.
+/// Append only: a shipped migration must never be edited or reordered. Each
+/// one must be idempotent, since a crash can interrupt it before the new
+/// version is recorded.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        description: "move settings to versioned keys",
+        run: |storage| storage.settings.rekey_legacy(),
+    },
+    Migration {
+        description: "backfill favorites order",
+        run: |storage| {
+            backfill_order(storage.favorites_addresses.handle())?;
+            backfill_order(storage.favorites_transactions.handle())
+        },
+    },
+    Migration {
+        description: "checksum favorite addresses",
+        run: checksum_favorite_addresses,
+    },
+];
+
+/// Version a store is at once every migration has run.
+pub const CURRENT_SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// Bring the store up to [`CURRENT_SCHEMA_VERSION`], recording the version
+/// after every step. Returns how many migrations ran.
+pub(super) fn migrate(storage: &Storage) -> Result<usize> {
+    let stored = schema_version(&storage.meta)?;
+    if stored > CURRENT_SCHEMA_VERSION {
+        return Err(eyre!(
+            "data directory uses schema v{stored}, newer than this build (v{CURRENT_SCHEMA_VERSION}); \
+             upgrade evm-tui instead of opening it with an older release"
+        ));
+    }
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(stored as usize) {
+        (migration.run)(storage)
+            .wrap_err_with(|| format!("migration {}: {}", index + 1, migration.description))?;
+        storage
+            .meta
+            .insert(SCHEMA_VERSION_KEY, (index as u32 + 1).to_be_bytes())
+            .wrap_err("failed to record schema version")?;
+        storage.keyspace.persist(PersistMode::SyncAll)?;
+    }
+    Ok(CURRENT_SCHEMA_VERSION.saturating_sub(stored) as usize)
+}
+
+pub(super) fn schema_version(meta: &PartitionHandle) -> Result<u32> {
+    let Some(raw) = meta
+        .get(SCHEMA_VERSION_KEY)
+        .wrap_err("failed to read schema version")?
+    else {
+        return Ok(0);
+    };
+    let bytes: [u8; 4] = raw
+        .as_ref()
+        .try_into()
+        .map_err(|_| eyre!("schema version is corrupt"))?;
+    Ok(u32::from_be_bytes(bytes))
+}
+
+/// Number favorites in their current (key) order so later additions append.
+fn backfill_order(handle: &PartitionHandle) -> Result<()> {
+    let mut records = Vec::new();
+    for entry in handle.iter() {
+        let (key, value) = entry?;
+        let record: FavoriteRecord = serde_json::from_slice(value.as_ref())
+            .wrap_err("failed to deserialize favorite record")?;
+        records.push((key, record));
+    }
+    if records.iter().any(|(_, record)| record.order != 0) {
+        return Ok(());
+    }
+    for (order, (key, mut record)) in records.into_iter().enumerate() {
+        record.order = order as u64;
+        let bytes = serde_json::to_vec(&record).wrap_err("failed to serialize favorite record")?;
+        handle.insert(key, bytes)?;
+    }
+    Ok(())
+}
+
+/// Re-key address favorites and their trash entries under the EIP-55 form,
+/// merging favorites that only differed by case into the earliest one.
+fn checksum_favorite_addresses(storage: &Storage) -> Result<()> {
+    let handle = storage.favorites_addresses.handle();
+    let mut merged: BTreeMap<String, FavoriteRecord> = BTreeMap::new();
+    let mut stale = Vec::new();
+    for record in storage.favorites_addresses.list()? {
+        let Ok(address) = record.identifier.parse::<Address>() else {
+            continue;
+        };
+        let checksummed = address.to_checksum(None);
+        if record.identifier != checksummed {
+            stale.push(record.identifier.clone());
+        }
+        // `list` is sorted by order, so the first record seen wins.
+        let kept = merged.entry(checksummed.clone()).or_insert(FavoriteRecord {
+            identifier: checksummed,
+            ..record.clone()
+        });
+        if kept.label.is_none() {
+            kept.label = record.label;
+        }
+    }
+
+    let mut batch = storage.keyspace.batch();
+    for identifier in &stale {
+        batch.remove(handle, identifier.as_str());
+    }
+    for (identifier, record) in &merged {
+        let bytes = serde_json::to_vec(record).wrap_err("failed to serialize favorite record")?;
+        batch.insert(handle, identifier.as_str(), bytes);
+    }
+    for mut entry in storage.trash.list()? {
+        if entry.table != FavoritesTable::Addresses {
+            continue;
+        }
+        let Ok(address) = entry.record.identifier.parse::<Address>() else {
+            continue;
+        };
+        let checksummed = address.to_checksum(None);
+        if entry.record.identifier == checksummed {
+            continue;
+        }
+        batch.remove(
+            storage.trash.handle(),
+            TrashRecord::storage_key(entry.table, &entry.record.identifier),
+        );
+        entry.record.identifier = checksummed;
+        let bytes = serde_json::to_vec(&entry).wrap_err("failed to serialize trash record")?;
+        batch.insert(
+            storage.trash.handle(),
+            TrashRecord::storage_key(entry.table, &entry.record.identifier),
+            bytes,
+        );
+    }
+    batch
+        .commit()
+        .wrap_err("failed to re-key favorite addresses")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::SettingKey;
+    use tempfile::tempdir;
+
+    #[test]
+    fn legacy_store_is_migrated_once() -> Result<()> {
+        let temp = tempdir().unwrap();
+        {
+            // A store written before migrations existed: flat setting keys and
+            // favorites without an order.
+            let keyspace = fjall::Config::new(temp.path()).open()?;
+            let settings = keyspace.open_partition("settings", Default::default())?;
+            settings.insert("top:last_query", "vitalik.eth")?;
+            let favorites = keyspace.open_partition("favorites_addresses", Default::default())?;
+            for id in ["0xbb", "0xaa"] {
+                favorites.insert(id, br#"{"label":null,"identifier":"","chain":"Mainnet"}"#)?;
+            }
+            keyspace.persist(PersistMode::SyncAll)?;
+        }
+
+        let storage = Storage::open(temp.path())?;
+        assert_eq!(schema_version(&storage.meta)?, CURRENT_SCHEMA_VERSION);
+        assert_eq!(
+            storage
+                .settings()
+                .get_json::<String>(SettingKey::LastQuery)?,
+            Some("vitalik.eth".to_string())
+        );
+        let orders: Vec<_> = storage
+            .favorites_addresses()
+            .list()?
+            .into_iter()
+            .map(|record| (record.identifier, record.order))
+            .collect();
+        assert_eq!(orders, vec![("0xaa".into(), 0), ("0xbb".into(), 1)]);
+        assert_eq!(migrate(&storage)?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn favorites_differing_by_case_are_merged() -> Result<()> {
+        let temp = tempdir().unwrap();
+        let storage = Storage::open(temp.path())?;
+        let handle = storage.favorites_addresses.handle();
+        let lower = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+        let upper = "0xF39FD6E51AAD88F6F4CE6AB8827279CFFFB92266";
+        for (identifier, label, order) in [(lower, None, 0), (upper, Some("deployer"), 1)] {
+            let record = FavoriteRecord {
+                label: label.map(str::to_string),
+                identifier: identifier.into(),
+                chain: "Mainnet".into(),
+                order,
+            };
+            handle.insert(identifier, serde_json::to_vec(&record)?)?;
+        }
+
+        checksum_favorite_addresses(&storage)?;
+        let favorites = storage.favorites_addresses().list()?;
+        assert_eq!(favorites.len(), 1);
+        assert_eq!(
+            favorites[0].identifier,
+            "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"
+        );
+        assert_eq!(favorites[0].label.as_deref(), Some("deployer"));
+        assert_eq!(favorites[0].order, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn newer_schema_is_refused() -> Result<()> {
+        let temp = tempdir().unwrap();
+        {
+            let storage = Storage::open(temp.path())?;
+            storage.meta.insert(
+                SCHEMA_VERSION_KEY,
+                (CURRENT_SCHEMA_VERSION + 1).to_be_bytes(),
+            )?;
+        }
+        assert!(Storage::open(temp.path()).is_err());
+        Ok(())
+    }
+}
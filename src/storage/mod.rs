@@ -1,23 +1,54 @@
-use color_eyre::Result;
-use fjall::{Config, Keyspace, PartitionCreateOptions};
+use crate::app::watchlist::unix_now;
+use color_eyre::{Report, Result};
+use fjall::{Config, Keyspace, PartitionCreateOptions, PartitionHandle};
 use std::{
     fs,
     path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
 };
 
+mod journal;
+mod migrations;
 mod repositories;
+mod writer;
 
+pub use journal::FavoritesBatch;
 pub use repositories::{
-    FavoriteRecord, FavoritesRepository, SecretKey, SecretsRepository, SettingsRepository,
+    AlertOutputs, AlertRule, CachedResponse, FavoriteRecord, FavoritesRepository, FavoritesTable,
+    GroupMember, GroupsRepository, ResponseCacheRepository, ScratchNote, ScratchpadRepository,
+    SecretKey, SecretsRepository, SettingKey, SettingsRepository, SignatureKind,
+    SignatureRepository, TokenRecord, TokenRepository, TrashRecord, TrashRepository,
+    WatchGroupRecord,
 };
 
+/// How long removed favorites stay restorable before being purged on startup.
+pub const TRASH_RETENTION: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// How long cached explorer answers are kept at most; older ones are purged
+/// on startup whatever their TTL.
+pub const RESPONSE_CACHE_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+#[derive(Clone)]
 pub struct Storage {
-    #[allow(dead_code)]
     keyspace: Keyspace,
+    journal: PartitionHandle,
+    meta: PartitionHandle,
     favorites_addresses: FavoritesRepository,
     favorites_transactions: FavoritesRepository,
     settings: SettingsRepository,
     secrets: SecretsRepository,
+    trash: TrashRepository,
+    groups: GroupsRepository,
+    tokens: TokenRepository,
+    signatures: SignatureRepository,
+    scratchpad: ScratchpadRepository,
+    responses: ResponseCacheRepository,
+    recovered_journal_entries: usize,
+    /// Background thread for [`Storage::write`]; writes run inline without it.
+    writer: Option<writer::StorageWriter>,
+    /// Inline write failures kept for the writer's `on_error` once it starts.
+    unreported: Arc<Mutex<Vec<(&'static str, Report)>>>,
 }
 
 impl Storage {
@@ -37,14 +68,46 @@ impl Storage {
             keyspace.open_partition("favorites_transactions", PartitionCreateOptions::default())?;
         let settings = keyspace.open_partition("settings", PartitionCreateOptions::default())?;
         let secrets = keyspace.open_partition("secrets", PartitionCreateOptions::default())?;
+        let trash = keyspace.open_partition("trash", PartitionCreateOptions::default())?;
+        let groups = keyspace.open_partition("watch_groups", PartitionCreateOptions::default())?;
+        let tokens = keyspace.open_partition("tokens", PartitionCreateOptions::default())?;
+        let signatures =
+            keyspace.open_partition("signatures", PartitionCreateOptions::default())?;
+        let scratchpad =
+            keyspace.open_partition("scratchpad", PartitionCreateOptions::default())?;
+        let responses =
+            keyspace.open_partition("explorer_cache", PartitionCreateOptions::default())?;
+        let journal = keyspace.open_partition("journal", PartitionCreateOptions::default())?;
+        let meta = keyspace.open_partition("meta", PartitionCreateOptions::default())?;
 
-        Ok(Self {
+        let mut storage = Self {
             favorites_addresses: FavoritesRepository::new(favorites_addresses),
             favorites_transactions: FavoritesRepository::new(favorites_transactions),
             settings: SettingsRepository::new(settings),
             secrets: SecretsRepository::new(secrets),
+            trash: TrashRepository::new(trash),
+            groups: GroupsRepository::new(groups),
+            tokens: TokenRepository::new(tokens),
+            signatures: SignatureRepository::new(signatures),
+            scratchpad: ScratchpadRepository::new(scratchpad),
+            responses: ResponseCacheRepository::new(responses),
             keyspace,
-        })
+            journal,
+            meta,
+            recovered_journal_entries: 0,
+            writer: None,
+            unreported: Arc::default(),
+        };
+        // Replay first so interrupted edits written in the old format are migrated too.
+        storage.recovered_journal_entries = journal::reconcile(&storage)?;
+        migrations::migrate(&storage)?;
+        storage
+            .trash
+            .purge_older_than(unix_now().saturating_sub(TRASH_RETENTION.as_secs()))?;
+        storage
+            .responses
+            .purge_older_than(unix_now().saturating_sub(RESPONSE_CACHE_RETENTION.as_secs()))?;
+        Ok(storage)
     }
 
     pub fn favorites_addresses(&self) -> &FavoritesRepository {
@@ -62,6 +125,113 @@ impl Storage {
     pub fn secrets(&self) -> &SecretsRepository {
         &self.secrets
     }
+
+    pub fn trash(&self) -> &TrashRepository {
+        &self.trash
+    }
+
+    pub fn groups(&self) -> &GroupsRepository {
+        &self.groups
+    }
+
+    pub fn tokens(&self) -> &TokenRepository {
+        &self.tokens
+    }
+
+    pub fn signatures(&self) -> &SignatureRepository {
+        &self.signatures
+    }
+
+    pub fn scratchpad(&self) -> &ScratchpadRepository {
+        &self.scratchpad
+    }
+
+    pub fn responses(&self) -> &ResponseCacheRepository {
+        &self.responses
+    }
+
+    /// Start an atomic, journaled group of favorites edits.
+    pub fn favorites_batch(&self) -> FavoritesBatch<'_> {
+        FavoritesBatch::new(self)
+    }
+
+    /// Interrupted favorites batches replayed while opening the store.
+    pub fn recovered_journal_entries(&self) -> usize {
+        self.recovered_journal_entries
+    }
+
+    /// Move [`Storage::write`] and [`Storage::read`] calls to a dedicated
+    /// thread. Failures are reported to `on_error` with the label the write
+    /// was queued under, starting with any inline write that failed before.
+    pub fn start_writer(
+        &mut self,
+        on_error: impl Fn(&'static str, Report) + Send + Sync + 'static,
+    ) -> Result<()> {
+        let unreported = self
+            .unreported
+            .lock()
+            .map(|mut failures| std::mem::take(&mut *failures))
+            .unwrap_or_default();
+        for (what, err) in unreported {
+            on_error(what, err);
+        }
+        self.writer = Some(writer::StorageWriter::spawn(self.clone(), on_error)?);
+        Ok(())
+    }
+
+    /// Run `write` after every write queued before it, off the calling thread
+    /// once a writer is started. Without one it runs inline and failures wait
+    /// for [`Storage::start_writer`].
+    pub fn write(
+        &self,
+        what: &'static str,
+        write: impl FnOnce(&Storage) -> Result<()> + Send + 'static,
+    ) {
+        let write = match &self.writer {
+            Some(writer) => match writer.write(what, Box::new(write)) {
+                None => return,
+                Some(write) => write,
+            },
+            None => Box::new(write),
+        };
+        if let Err(err) = write(self) {
+            match &self.writer {
+                Some(writer) => writer.report(what, err),
+                None => {
+                    if let Ok(mut failures) = self.unreported.lock() {
+                        failures.push((what, err));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Run `read` on the writer thread once every write queued before it is
+    /// done, and hand its result to `done` there. Runs both inline without a
+    /// writer.
+    pub fn read<T: Send + 'static>(
+        &self,
+        read: impl FnOnce(&Storage) -> Result<T> + Send + 'static,
+        done: impl FnOnce(Result<T>) + Send + 'static,
+    ) {
+        let job: writer::ReadFn = Box::new(move |storage| done(read(storage)));
+        let job = match &self.writer {
+            Some(writer) => match writer.read(job) {
+                None => return,
+                Some(job) => job,
+            },
+            None => job,
+        };
+        job(self);
+    }
+
+    /// Block until every queued write is on disk, before reading data they
+    /// may touch.
+    pub fn flush(&self) {
+        if let Some(writer) = &self.writer {
+            writer.flush();
+        }
+    }
 }
 
 fn default_data_dir() -> Result<PathBuf> {
@@ -1,7 +1,7 @@
 use color_eyre::{Result, eyre::WrapErr};
 use fjall::PartitionHandle;
-use serde::{Deserialize, Serialize};
-use serde_json;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::collections::BTreeSet;
 
 #[derive(Clone)]
 pub struct FavoritesRepository {
@@ -13,6 +13,10 @@ impl FavoritesRepository {
         Self { handle }
     }
 
+    pub(super) fn handle(&self) -> &PartitionHandle {
+        &self.handle
+    }
+
     pub fn list(&self) -> Result<Vec<FavoriteRecord>> {
         let mut items = Vec::new();
         for entry in self.handle.iter() {
@@ -23,28 +27,655 @@ impl FavoritesRepository {
                 String::from_utf8(key.to_vec()).wrap_err("favorite key is not valid UTF-8")?;
             items.push(record);
         }
+        items.sort_by_key(|record| record.order);
         Ok(items)
     }
 
-    pub fn upsert(&self, record: &FavoriteRecord) -> Result<()> {
-        let stored = serde_json::to_vec(record).wrap_err("failed to serialize favorite record")?;
-        self.handle
-            .insert(record.identifier.as_bytes(), stored)
-            .wrap_err("failed to insert favorite")
-    }
-
-    pub fn remove(&self, identifier: &str) -> Result<()> {
-        self.handle
-            .remove(identifier.as_bytes())
-            .wrap_err("failed to remove favorite")
+    /// Order value that places a new favorite after every existing one.
+    pub fn next_order(&self) -> Result<u64> {
+        Ok(self.list()?.last().map_or(0, |record| record.order + 1))
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct FavoriteRecord {
     pub label: Option<String>,
     pub identifier: String,
     pub chain: String,
+    /// Position in the sidebar; new favorites take [`FavoritesRepository::next_order`].
+    #[serde(default)]
+    pub order: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FavoritesTable {
+    Addresses,
+    Transactions,
+}
+
+/// A favorite removed by the user, kept until restored or purged.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrashRecord {
+    pub table: FavoritesTable,
+    pub record: FavoriteRecord,
+    /// Unix seconds at which the favorite was removed.
+    pub deleted_at: u64,
+}
+
+impl TrashRecord {
+    pub(super) fn storage_key(table: FavoritesTable, identifier: &str) -> String {
+        let prefix = match table {
+            FavoritesTable::Addresses => "address",
+            FavoritesTable::Transactions => "transaction",
+        };
+        format!("{prefix}:{identifier}")
+    }
+}
+
+#[derive(Clone)]
+pub struct TrashRepository {
+    handle: PartitionHandle,
+}
+
+impl TrashRepository {
+    pub(crate) fn new(handle: PartitionHandle) -> Self {
+        Self { handle }
+    }
+
+    pub(super) fn handle(&self) -> &PartitionHandle {
+        &self.handle
+    }
+
+    /// Trashed favorites, most recently removed first.
+    pub fn list(&self) -> Result<Vec<TrashRecord>> {
+        let mut items = Vec::new();
+        for entry in self.handle.iter() {
+            let (_, value) = entry?;
+            let record: TrashRecord = serde_json::from_slice(value.as_ref())
+                .wrap_err("failed to deserialize trash record")?;
+            items.push(record);
+        }
+        items.sort_by_key(|item| std::cmp::Reverse(item.deleted_at));
+        Ok(items)
+    }
+
+    /// Drop entries removed before `cutoff` (unix seconds). Returns how many were purged.
+    pub fn purge_older_than(&self, cutoff: u64) -> Result<usize> {
+        let mut purged = 0;
+        for item in self.list()? {
+            if item.deleted_at < cutoff {
+                let key = TrashRecord::storage_key(item.table, &item.record.identifier);
+                self.handle
+                    .remove(key.as_bytes())
+                    .wrap_err("failed to purge trash entry")?;
+                purged += 1;
+            }
+        }
+        Ok(purged)
+    }
+}
+
+/// Condition evaluated across every member of a watch group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AlertRule {
+    /// Any member's native balance differs from the previous poll.
+    BalanceChanged,
+    /// Any member's nonce advanced, i.e. it sent a transaction.
+    MemberTransacted,
+}
+
+impl AlertRule {
+    pub fn label(self) -> &'static str {
+        match self {
+            AlertRule::BalanceChanged => "balance changes",
+            AlertRule::MemberTransacted => "member transacts",
+        }
+    }
+}
+
+/// Where a watch's alerts go besides the status bar. Flattened into the
+/// watch's record, so the fields sit next to its own.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlertOutputs {
+    /// Desktop notification.
+    pub notify: bool,
+    /// JSON POST to the webhook URL from the secrets form.
+    pub webhook: bool,
+}
+
+impl AlertOutputs {
+    /// Off, desktop, webhook, both, and round again.
+    pub fn next(self) -> Self {
+        match (self.notify, self.webhook) {
+            (false, false) => Self {
+                notify: true,
+                webhook: false,
+            },
+            (true, false) => Self {
+                notify: false,
+                webhook: true,
+            },
+            (false, true) => Self {
+                notify: true,
+                webhook: true,
+            },
+            (true, true) => Self::default(),
+        }
+    }
+
+    pub fn any(self) -> bool {
+        self.notify || self.webhook
+    }
+
+    pub fn label(self) -> &'static str {
+        match (self.notify, self.webhook) {
+            (false, false) => "off",
+            (true, false) => "desktop",
+            (false, true) => "webhook",
+            (true, true) => "desktop + webhook",
+        }
+    }
+
+    /// Suffix marking a watch in lists: `🔔` desktop, `↗` webhook.
+    pub fn marker(self) -> &'static str {
+        match (self.notify, self.webhook) {
+            (false, false) => "",
+            (true, false) => " 🔔",
+            (false, true) => " ↗",
+            (true, true) => " 🔔↗",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupMember {
+    pub address: String,
+    pub chain: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchGroupRecord {
+    pub name: String,
+    #[serde(default)]
+    pub members: Vec<GroupMember>,
+    #[serde(default)]
+    pub rules: Vec<AlertRule>,
+    #[serde(flatten)]
+    pub alerts: AlertOutputs,
+}
+
+#[derive(Clone)]
+pub struct GroupsRepository {
+    handle: PartitionHandle,
+}
+
+impl GroupsRepository {
+    pub(crate) fn new(handle: PartitionHandle) -> Self {
+        Self { handle }
+    }
+
+    pub fn list(&self) -> Result<Vec<WatchGroupRecord>> {
+        let mut items = Vec::new();
+        for entry in self.handle.iter() {
+            let (_, value) = entry?;
+            let record: WatchGroupRecord = serde_json::from_slice(value.as_ref())
+                .wrap_err("failed to deserialize watch group")?;
+            items.push(record);
+        }
+        Ok(items)
+    }
+
+    pub fn upsert(&self, record: &WatchGroupRecord) -> Result<()> {
+        let stored = serde_json::to_vec(record).wrap_err("failed to serialize watch group")?;
+        self.handle
+            .insert(record.name.as_bytes(), stored)
+            .wrap_err("failed to write watch group")
+    }
+
+    pub fn remove(&self, name: &str) -> Result<()> {
+        self.handle
+            .remove(name.as_bytes())
+            .wrap_err("failed to remove watch group")
+    }
+}
+
+/// ERC-20 metadata read from the token contract. Fields are `None` when the
+/// contract does not implement them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenRecord {
+    pub symbol: Option<String>,
+    pub name: Option<String>,
+    pub decimals: Option<u8>,
+}
+
+/// Token metadata keyed by `v1::token::<chain_id>::<address>`.
+#[derive(Clone)]
+pub struct TokenRepository {
+    handle: PartitionHandle,
+}
+
+impl TokenRepository {
+    pub(crate) fn new(handle: PartitionHandle) -> Self {
+        Self { handle }
+    }
+
+    fn storage_key(chain_id: u64, address: &str) -> String {
+        format!("v1::token::{chain_id}::{}", address.to_ascii_lowercase())
+    }
+
+    pub fn get(&self, chain_id: u64, address: &str) -> Result<Option<TokenRecord>> {
+        let raw = self
+            .handle
+            .get(Self::storage_key(chain_id, address).as_bytes())
+            .wrap_err("failed to read token metadata")?;
+        raw.map(|raw| serde_json::from_slice(&raw).wrap_err("failed to deserialize token metadata"))
+            .transpose()
+    }
+
+    pub fn put(&self, chain_id: u64, address: &str, record: &TokenRecord) -> Result<()> {
+        let stored = serde_json::to_vec(record).wrap_err("failed to serialize token metadata")?;
+        self.handle
+            .insert(Self::storage_key(chain_id, address).as_bytes(), stored)
+            .wrap_err("failed to write token metadata")
+    }
+}
+
+/// An explorer API answer kept so repeated requests skip the network.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedResponse {
+    /// Unix seconds at which it was fetched.
+    pub fetched_at: u64,
+    pub result: serde_json::Value,
+}
+
+const RESPONSE_PREFIX: &str = "v1::response::";
+
+/// Explorer answers keyed by `v1::response::<request>`, where the request is
+/// the endpoint and its query parameters without the API key.
+#[derive(Clone)]
+pub struct ResponseCacheRepository {
+    handle: PartitionHandle,
+}
+
+impl ResponseCacheRepository {
+    pub(crate) fn new(handle: PartitionHandle) -> Self {
+        Self { handle }
+    }
+
+    fn storage_key(request: &str) -> String {
+        format!("{RESPONSE_PREFIX}{request}")
+    }
+
+    pub fn get(&self, request: &str) -> Result<Option<CachedResponse>> {
+        let raw = self
+            .handle
+            .get(Self::storage_key(request).as_bytes())
+            .wrap_err("failed to read cached response")?;
+        // An entry that no longer decodes is treated as a miss and refetched.
+        Ok(raw.and_then(|raw| serde_json::from_slice(&raw).ok()))
+    }
+
+    pub fn put(&self, request: &str, response: &CachedResponse) -> Result<()> {
+        let stored = serde_json::to_vec(response).wrap_err("failed to serialize response")?;
+        self.handle
+            .insert(Self::storage_key(request).as_bytes(), stored)
+            .wrap_err("failed to write cached response")
+    }
+
+    /// Number of cached answers.
+    pub fn len(&self) -> Result<usize> {
+        let mut count = 0;
+        for entry in self.handle.prefix(RESPONSE_PREFIX) {
+            entry?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Drop answers fetched before `cutoff` (Unix seconds), or every answer
+    /// with `u64::MAX`. Returns how many were removed.
+    pub fn purge_older_than(&self, cutoff: u64) -> Result<usize> {
+        let mut stale = Vec::new();
+        for entry in self.handle.prefix(RESPONSE_PREFIX) {
+            let (key, value) = entry?;
+            let fetched_at = serde_json::from_slice::<CachedResponse>(&value)
+                .map(|response| response.fetched_at)
+                .unwrap_or_default();
+            if fetched_at < cutoff {
+                stale.push(key);
+            }
+        }
+        for key in &stale {
+            self.handle
+                .remove(key.as_ref())
+                .wrap_err("failed to purge cached response")?;
+        }
+        Ok(stale.len())
+    }
+}
+
+/// A line pinned to an investigation's scratchpad.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScratchNote {
+    pub investigation: String,
+    /// Unix milliseconds at which it was pinned; notes are listed in this order.
+    pub pinned_at: u64,
+    pub text: String,
+    /// Where it was pinned from, e.g. `Mainnet 0x12ab…cdef • Storage`.
+    pub source: String,
+}
+
+impl ScratchNote {
+    fn storage_key(investigation: &str, pinned_at: u64) -> String {
+        format!("{SCRATCH_PREFIX}{investigation}::{pinned_at:020}")
+    }
+}
+
+const SCRATCH_PREFIX: &str = "v1::note::";
+
+/// Scratchpad notes keyed by `v1::note::<investigation>::<pinned_at>`.
+#[derive(Clone)]
+pub struct ScratchpadRepository {
+    handle: PartitionHandle,
+}
+
+impl ScratchpadRepository {
+    pub(crate) fn new(handle: PartitionHandle) -> Self {
+        Self { handle }
+    }
+
+    /// Notes of `investigation`, oldest first.
+    pub fn list(&self, investigation: &str) -> Result<Vec<ScratchNote>> {
+        let prefix = format!("{SCRATCH_PREFIX}{investigation}::");
+        let mut items = Vec::new();
+        for entry in self.handle.prefix(prefix.as_bytes()) {
+            let (_, value) = entry?;
+            let note: ScratchNote = serde_json::from_slice(value.as_ref())
+                .wrap_err("failed to deserialize scratchpad note")?;
+            items.push(note);
+        }
+        Ok(items)
+    }
+
+    /// Every investigation with at least one note, sorted by name.
+    pub fn investigations(&self) -> Result<Vec<String>> {
+        let mut names = BTreeSet::new();
+        for entry in self.handle.prefix(SCRATCH_PREFIX.as_bytes()) {
+            let (key, _) = entry?;
+            let key = String::from_utf8_lossy(key.as_ref());
+            if let Some((name, _)) = key
+                .strip_prefix(SCRATCH_PREFIX)
+                .and_then(|rest| rest.rsplit_once("::"))
+            {
+                names.insert(name.to_string());
+            }
+        }
+        Ok(names.into_iter().collect())
+    }
+
+    pub fn add(&self, note: &ScratchNote) -> Result<()> {
+        let stored = serde_json::to_vec(note).wrap_err("failed to serialize scratchpad note")?;
+        self.handle
+            .insert(
+                ScratchNote::storage_key(&note.investigation, note.pinned_at).as_bytes(),
+                stored,
+            )
+            .wrap_err("failed to write scratchpad note")
+    }
+
+    pub fn remove(&self, note: &ScratchNote) -> Result<()> {
+        self.handle
+            .remove(ScratchNote::storage_key(&note.investigation, note.pinned_at).as_bytes())
+            .wrap_err("failed to remove scratchpad note")
+    }
+}
+
+/// Whether a signature belongs to a function (4-byte selector) or an event
+/// (32-byte topic0).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SignatureKind {
+    Function,
+    Event,
+}
+
+impl SignatureKind {
+    fn name(self) -> &'static str {
+        match self {
+            SignatureKind::Function => "fn",
+            SignatureKind::Event => "event",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "fn" => Some(SignatureKind::Function),
+            "event" => Some(SignatureKind::Event),
+            _ => None,
+        }
+    }
+}
+
+const SIGNATURE_PREFIX: &str = "v1::sig::";
+const SIGNATURE_MISS_PREFIX: &str = "v1::sig_miss::";
+const SIGNATURE_BUNDLE_KEY: &str = "v1::sig_bundle";
+
+/// Text signatures keyed by `v1::sig::<fn|event>::<selector>`, plus the
+/// selectors seen without a match (`v1::sig_miss::…`) for the next sync.
+#[derive(Clone)]
+pub struct SignatureRepository {
+    handle: PartitionHandle,
+}
+
+impl SignatureRepository {
+    pub(crate) fn new(handle: PartitionHandle) -> Self {
+        Self { handle }
+    }
+
+    fn storage_key(prefix: &str, kind: SignatureKind, selector: &str) -> String {
+        format!("{prefix}{}::{}", kind.name(), selector.to_ascii_lowercase())
+    }
+
+    /// Known signatures for `selector`, in the order they were added.
+    pub fn get(&self, kind: SignatureKind, selector: &str) -> Result<Vec<String>> {
+        let raw = self
+            .handle
+            .get(Self::storage_key(SIGNATURE_PREFIX, kind, selector).as_bytes())
+            .wrap_err("failed to read signature")?;
+        raw.map(|raw| serde_json::from_slice(&raw).wrap_err("failed to deserialize signatures"))
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Add `signature` after any already known for `selector` and forget a
+    /// recorded miss. Returns whether it was new.
+    pub fn add(&self, kind: SignatureKind, selector: &str, signature: &str) -> Result<bool> {
+        let mut known = self.get(kind, selector)?;
+        self.handle
+            .remove(Self::storage_key(SIGNATURE_MISS_PREFIX, kind, selector).as_bytes())
+            .wrap_err("failed to clear signature miss")?;
+        if known.iter().any(|existing| existing == signature) {
+            return Ok(false);
+        }
+        known.push(signature.to_string());
+        let stored = serde_json::to_vec(&known).wrap_err("failed to serialize signatures")?;
+        self.handle
+            .insert(
+                Self::storage_key(SIGNATURE_PREFIX, kind, selector).as_bytes(),
+                stored,
+            )
+            .wrap_err("failed to write signature")?;
+        Ok(true)
+    }
+
+    /// Remember that `selector` was seen but not known.
+    pub fn record_miss(&self, kind: SignatureKind, selector: &str) -> Result<()> {
+        self.handle
+            .insert(
+                Self::storage_key(SIGNATURE_MISS_PREFIX, kind, selector).as_bytes(),
+                [],
+            )
+            .wrap_err("failed to record signature miss")
+    }
+
+    /// Selectors recorded by [`record_miss`](Self::record_miss) and not since added.
+    pub fn misses(&self) -> Result<Vec<(SignatureKind, String)>> {
+        let mut items = Vec::new();
+        for entry in self.handle.prefix(SIGNATURE_MISS_PREFIX.as_bytes()) {
+            let (key, _) = entry?;
+            let Some((kind, selector)) = std::str::from_utf8(&key)
+                .ok()
+                .and_then(|key| key.strip_prefix(SIGNATURE_MISS_PREFIX))
+                .and_then(|rest| rest.split_once("::"))
+            else {
+                continue;
+            };
+            if let Some(kind) = SignatureKind::from_name(kind) {
+                items.push((kind, selector.to_string()));
+            }
+        }
+        Ok(items)
+    }
+
+    /// Version of the packaged database last loaded; 0 when never seeded.
+    pub fn bundle_version(&self) -> Result<u32> {
+        let raw = self
+            .handle
+            .get(SIGNATURE_BUNDLE_KEY.as_bytes())
+            .wrap_err("failed to read signature bundle version")?;
+        Ok(raw
+            .and_then(|raw| <[u8; 4]>::try_from(raw.as_ref()).ok())
+            .map_or(0, u32::from_be_bytes))
+    }
+
+    pub fn set_bundle_version(&self, version: u32) -> Result<()> {
+        self.handle
+            .insert(SIGNATURE_BUNDLE_KEY.as_bytes(), version.to_be_bytes())
+            .wrap_err("failed to record signature bundle version")
+    }
+}
+
+const SETTING_PREFIX: &str = "v1::setting::";
+
+/// Every persisted setting. Values are JSON stored under `v1::setting::<name>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingKey {
+    /// Last query submitted from the top bar search.
+    LastQuery,
+    /// Local usage counters shown on the diagnostics screen.
+    UsageStats,
+    /// Most recent release check and when it ran.
+    UpdateCheck,
+    /// Seconds between background refreshes of the selection; 0 is off.
+    AutoRefresh,
+    /// Storage slots pinned on the Storage tab.
+    PinnedSlots,
+    /// Address labels added by the user on top of the bundled registry.
+    AddressLabels,
+    /// Recent successful searches, newest first.
+    SearchHistory,
+    /// Foundry project whose artifacts are matched against contract code.
+    FoundryProject,
+    /// Keystore or Ledger account that signs transactions from the write flow.
+    Signer,
+    /// Transactions sent from the write flow that are not mined yet.
+    PendingTransactions,
+    /// Per-chain choice of trace backend (RPC or Tenderly).
+    TraceBackends,
+    /// Per-chain transaction depth and timeouts for address hydration.
+    FetchLimits,
+    /// Per-chain choice of node API for account data (JSON-RPC or GraphQL).
+    NodeBackends,
+    /// How long cached explorer answers stay fresh, by kind.
+    ExplorerCacheTtls,
+    /// Built-in chains left out of the secrets form and the Chains tab.
+    HiddenChains,
+    /// Sidebar width and whether it is hidden.
+    Layout,
+    /// Investigation whose scratchpad new pins go to.
+    Investigation,
+    /// Addresses on the sidebar's Watchlist tab.
+    Watchlist,
+    /// Contract events that raise alerts.
+    LogAlerts,
+    /// Base fee and token balance thresholds that raise alerts.
+    Thresholds,
+    /// External plugin executables.
+    Plugins,
+    /// Main view tabs filled by scripts or plugins.
+    CustomTabs,
+    /// Investigation sessions stashed while another one is open.
+    Sessions,
+}
+
+impl SettingKey {
+    /// Settings that predate versioned keys.
+    const LEGACY: [SettingKey; 3] = [
+        SettingKey::LastQuery,
+        SettingKey::UsageStats,
+        SettingKey::UpdateCheck,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            SettingKey::LastQuery => "top.last_query",
+            SettingKey::UsageStats => "stats.usage",
+            SettingKey::UpdateCheck => "updates.last_check",
+            SettingKey::AutoRefresh => "view.auto_refresh_secs",
+            SettingKey::PinnedSlots => "storage.pinned_slots",
+            SettingKey::AddressLabels => "labels.user",
+            SettingKey::SearchHistory => "top.search_history",
+            SettingKey::FoundryProject => "artifacts.foundry_project",
+            SettingKey::Signer => "signing.signer",
+            SettingKey::PendingTransactions => "signing.pending",
+            SettingKey::TraceBackends => "simulation.trace_backends",
+            SettingKey::FetchLimits => "hydration.fetch_limits",
+            SettingKey::NodeBackends => "hydration.node_backends",
+            SettingKey::ExplorerCacheTtls => "explorer.cache_ttls",
+            SettingKey::HiddenChains => "chains.hidden",
+            SettingKey::Layout => "view.layout",
+            SettingKey::Investigation => "scratchpad.investigation",
+            SettingKey::Watchlist => "sidebar.watchlist",
+            SettingKey::LogAlerts => "alerts.log_rules",
+            SettingKey::Thresholds => "alerts.thresholds",
+            SettingKey::Plugins => "plugins.list",
+            SettingKey::CustomTabs => "view.custom_tabs",
+            SettingKey::Sessions => "sessions.saved",
+        }
+    }
+
+    /// Flat key the setting was written under before keys were versioned.
+    fn legacy_key(self) -> Option<&'static str> {
+        match self {
+            SettingKey::LastQuery => Some("top:last_query"),
+            SettingKey::UsageStats => Some("stats:usage"),
+            SettingKey::UpdateCheck => Some("updates:last_check"),
+            SettingKey::AutoRefresh
+            | SettingKey::PinnedSlots
+            | SettingKey::AddressLabels
+            | SettingKey::SearchHistory
+            | SettingKey::FoundryProject
+            | SettingKey::Signer
+            | SettingKey::PendingTransactions
+            | SettingKey::TraceBackends
+            | SettingKey::FetchLimits
+            | SettingKey::NodeBackends
+            | SettingKey::ExplorerCacheTtls
+            | SettingKey::HiddenChains
+            | SettingKey::Layout
+            | SettingKey::Investigation
+            | SettingKey::Watchlist
+            | SettingKey::LogAlerts
+            | SettingKey::Thresholds
+            | SettingKey::Plugins
+            | SettingKey::CustomTabs
+            | SettingKey::Sessions => None,
+        }
+    }
+
+    fn storage_key(self) -> String {
+        format!("{SETTING_PREFIX}{}", self.name())
+    }
 }
 
 #[derive(Clone)]
@@ -57,39 +688,116 @@ impl SettingsRepository {
         Self { handle }
     }
 
-    pub fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
-        Ok(self
+    /// Read a setting. Values that no longer decode as `T` read as unset, so a
+    /// format change falls back to defaults instead of failing startup.
+    pub fn get_json<T: DeserializeOwned>(&self, key: SettingKey) -> Result<Option<T>> {
+        let raw = self
             .handle
-            .get(key.as_bytes())
-            .wrap_err("failed to read setting")?
-            .map(|v| v.to_vec()))
+            .get(key.storage_key().as_bytes())
+            .wrap_err("failed to read setting")?;
+        Ok(raw.and_then(|raw| serde_json::from_slice(&raw).ok()))
     }
 
-    pub fn put(&self, key: &str, value: &[u8]) -> Result<()> {
+    pub fn set_json<T: Serialize>(&self, key: SettingKey, value: &T) -> Result<()> {
+        let bytes = serde_json::to_vec(value).wrap_err("failed to serialize setting")?;
         self.handle
-            .insert(key.as_bytes(), value)
+            .insert(key.storage_key().as_bytes(), bytes)
             .wrap_err("failed to write setting")
     }
+
+    /// Move values written under the old flat keys to their versioned keys.
+    pub(super) fn rekey_legacy(&self) -> Result<()> {
+        for key in SettingKey::LEGACY {
+            let Some(legacy_key) = key.legacy_key() else {
+                continue;
+            };
+            let Some(raw) = self
+                .handle
+                .get(legacy_key.as_bytes())
+                .wrap_err("failed to read setting")?
+            else {
+                continue;
+            };
+            // The last query was stored as bare UTF-8 rather than JSON.
+            let value = match key {
+                SettingKey::LastQuery => serde_json::to_vec(&String::from_utf8_lossy(&raw))
+                    .wrap_err("failed to serialize setting")?,
+                _ => raw.to_vec(),
+            };
+            if self.handle.get(key.storage_key().as_bytes())?.is_none() {
+                self.handle
+                    .insert(key.storage_key().as_bytes(), value)
+                    .wrap_err("failed to write setting")?;
+            }
+            self.handle
+                .remove(legacy_key.as_bytes())
+                .wrap_err("failed to remove legacy setting")?;
+        }
+        Ok(())
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+const SECRET_PREFIX: &str = "v1::secret::";
+
+/// Namespaced secret identifier. Stored as `v1::secret::<namespace>[::<scope>]::<name>`;
+/// the two original keys keep their flat names so existing installs still resolve.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum SecretKey {
+    /// Shared Etherscan v2 key used for every chain without its own override.
     EtherscanApiKey,
     AnvilRpcUrl,
+    /// Explorer API key scoped to a single chain id.
+    ExplorerApiKey(u64),
+    /// RPC endpoint for a named chain (lowercase chain name).
+    RpcUrl(String),
+    /// Credential for a third-party provider, e.g. `("tenderly", "access_key")`.
+    ProviderCredential {
+        provider: String,
+        field: String,
+    },
 }
 
 impl SecretKey {
-    fn storage_key(self) -> &'static str {
+    fn storage_key(&self) -> String {
         match self {
-            SecretKey::EtherscanApiKey => "v1::secret::etherscan_api_key",
-            SecretKey::AnvilRpcUrl => "v1::secret::anvil_rpc_url",
+            SecretKey::EtherscanApiKey => format!("{SECRET_PREFIX}etherscan_api_key"),
+            SecretKey::AnvilRpcUrl => format!("{SECRET_PREFIX}anvil_rpc_url"),
+            SecretKey::ExplorerApiKey(chain_id) => {
+                format!("{SECRET_PREFIX}explorer::{chain_id}::api_key")
+            }
+            SecretKey::RpcUrl(chain) => format!("{SECRET_PREFIX}rpc::{chain}::url"),
+            SecretKey::ProviderCredential { provider, field } => {
+                format!("{SECRET_PREFIX}provider::{provider}::{field}")
+            }
+        }
+    }
+
+    fn from_storage_key(raw: &str) -> Option<Self> {
+        let rest = raw.strip_prefix(SECRET_PREFIX)?;
+        let parts: Vec<&str> = rest.split("::").collect();
+        match parts.as_slice() {
+            ["etherscan_api_key"] => Some(SecretKey::EtherscanApiKey),
+            ["anvil_rpc_url"] => Some(SecretKey::AnvilRpcUrl),
+            ["explorer", chain_id, "api_key"] => {
+                chain_id.parse().ok().map(SecretKey::ExplorerApiKey)
+            }
+            ["rpc", chain, "url"] => Some(SecretKey::RpcUrl((*chain).to_string())),
+            ["provider", provider, field] => Some(SecretKey::ProviderCredential {
+                provider: (*provider).to_string(),
+                field: (*field).to_string(),
+            }),
+            _ => None,
         }
     }
 
-    pub fn env_var(self) -> &'static str {
+    /// Environment variable that overrides the stored value, if the key has one.
+    pub fn env_var(&self) -> Option<String> {
         match self {
-            SecretKey::EtherscanApiKey => "ETHERSCAN_API_KEY",
-            SecretKey::AnvilRpcUrl => "ANVIL_RPC_URL",
+            SecretKey::EtherscanApiKey => Some("ETHERSCAN_API_KEY".into()),
+            SecretKey::AnvilRpcUrl => Some("ANVIL_RPC_URL".into()),
+            SecretKey::ExplorerApiKey(chain_id) => Some(format!("ETHERSCAN_API_KEY_{chain_id}")),
+            SecretKey::RpcUrl(chain) => Some(format!("RPC_URL_{}", chain.to_ascii_uppercase())),
+            SecretKey::ProviderCredential { .. } => None,
         }
     }
 }
@@ -104,28 +812,45 @@ impl SecretsRepository {
         Self { handle }
     }
 
-    pub fn get(&self, key: SecretKey) -> Result<Option<String>> {
-        Ok(self
-            .handle
+    pub fn get(&self, key: &SecretKey) -> Result<Option<String>> {
+        self.handle
             .get(key.storage_key().as_bytes())
             .wrap_err("failed to read secret")?
             .map(|bytes| {
                 String::from_utf8(bytes.to_vec()).wrap_err("secret value is not valid UTF-8")
             })
-            .transpose()?)
+            .transpose()
     }
 
-    pub fn set(&self, key: SecretKey, value: &str) -> Result<()> {
+    pub fn set(&self, key: &SecretKey, value: &str) -> Result<()> {
         self.handle
             .insert(key.storage_key().as_bytes(), value.as_bytes())
             .wrap_err("failed to write secret")
     }
 
-    pub fn remove(&self, key: SecretKey) -> Result<()> {
+    pub fn remove(&self, key: &SecretKey) -> Result<()> {
         self.handle
             .remove(key.storage_key().as_bytes())
             .wrap_err("failed to remove secret")
     }
+
+    /// Every stored secret whose key matches the namespaced scheme.
+    pub fn list(&self) -> Result<Vec<(SecretKey, String)>> {
+        let mut items = Vec::new();
+        for entry in self.handle.prefix(SECRET_PREFIX.as_bytes()) {
+            let (key, value) = entry?;
+            let Some(key) = std::str::from_utf8(&key)
+                .ok()
+                .and_then(SecretKey::from_storage_key)
+            else {
+                continue;
+            };
+            let value =
+                String::from_utf8(value.to_vec()).wrap_err("secret value is not valid UTF-8")?;
+            items.push((key, value));
+        }
+        Ok(items)
+    }
 }
 
 #[cfg(test)]
@@ -141,15 +866,194 @@ mod tests {
         let handle = keyspace.open_partition("secrets_test", Default::default())?;
         let secrets = SecretsRepository::new(handle);
 
-        assert!(secrets.get(SecretKey::EtherscanApiKey)?.is_none());
-        secrets.set(SecretKey::EtherscanApiKey, "secret-value")?;
+        assert!(secrets.get(&SecretKey::EtherscanApiKey)?.is_none());
+        secrets.set(&SecretKey::EtherscanApiKey, "secret-value")?;
         assert_eq!(
-            secrets.get(SecretKey::EtherscanApiKey)?,
+            secrets.get(&SecretKey::EtherscanApiKey)?,
             Some("secret-value".to_string())
         );
-        secrets.remove(SecretKey::EtherscanApiKey)?;
-        assert!(secrets.get(SecretKey::EtherscanApiKey)?.is_none());
+        secrets.remove(&SecretKey::EtherscanApiKey)?;
+        assert!(secrets.get(&SecretKey::EtherscanApiKey)?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn settings_move_off_legacy_keys() -> Result<()> {
+        let temp = tempdir().unwrap();
+        let keyspace = Config::new(temp.path()).open()?;
+        let handle = keyspace.open_partition("settings_test", Default::default())?;
+        handle.insert("top:last_query", "0xabc")?;
+        let settings = SettingsRepository::new(handle.clone());
+
+        settings.rekey_legacy()?;
+        assert!(handle.get("top:last_query")?.is_none());
+        assert_eq!(
+            settings.get_json::<String>(SettingKey::LastQuery)?,
+            Some("0xabc".to_string())
+        );
+        settings.set_json(SettingKey::UsageStats, &[1, 2, 3])?;
+        assert_eq!(settings.get_json::<String>(SettingKey::UsageStats)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn signatures_accumulate_and_clear_misses() -> Result<()> {
+        let temp = tempdir().unwrap();
+        let keyspace = Config::new(temp.path()).open()?;
+        let handle = keyspace.open_partition("signatures_test", Default::default())?;
+        let signatures = SignatureRepository::new(handle);
+
+        signatures.record_miss(SignatureKind::Function, "0xA9059CBB")?;
+        signatures.record_miss(SignatureKind::Event, "0x01")?;
+        assert_eq!(signatures.misses()?.len(), 2);
+        assert!(signatures.add(
+            SignatureKind::Function,
+            "0xa9059cbb",
+            "transfer(address,uint256)"
+        )?);
+        assert!(!signatures.add(
+            SignatureKind::Function,
+            "0xa9059cbb",
+            "transfer(address,uint256)"
+        )?);
+        assert_eq!(
+            signatures.get(SignatureKind::Function, "0xA9059CBB")?,
+            vec!["transfer(address,uint256)".to_string()]
+        );
+        assert!(
+            signatures
+                .get(SignatureKind::Event, "0xa9059cbb")?
+                .is_empty()
+        );
+        assert_eq!(
+            signatures.misses()?,
+            vec![(SignatureKind::Event, "0x01".to_string())]
+        );
+
+        assert_eq!(signatures.bundle_version()?, 0);
+        signatures.set_bundle_version(3)?;
+        assert_eq!(signatures.bundle_version()?, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn scratchpad_notes_are_per_investigation() -> Result<()> {
+        let temp = tempdir().unwrap();
+        let keyspace = Config::new(temp.path()).open()?;
+        let handle = keyspace.open_partition("scratchpad_test", Default::default())?;
+        let scratchpad = ScratchpadRepository::new(handle);
+
+        let note = |investigation: &str, pinned_at: u64, text: &str| ScratchNote {
+            investigation: investigation.into(),
+            pinned_at,
+            text: text.into(),
+            source: "Mainnet".into(),
+        };
+        scratchpad.add(&note("exploit", 20, "0xbeef"))?;
+        scratchpad.add(&note("exploit", 3, "slot 0x05"))?;
+        scratchpad.add(&note("exploit-2", 1, "0xdead"))?;
 
+        let notes = scratchpad.list("exploit")?;
+        assert_eq!(
+            notes.iter().map(|n| n.text.as_str()).collect::<Vec<_>>(),
+            ["slot 0x05", "0xbeef"]
+        );
+        assert_eq!(scratchpad.investigations()?, ["exploit", "exploit-2"]);
+
+        scratchpad.remove(&notes[0])?;
+        assert_eq!(scratchpad.list("exploit")?.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn cached_responses_expire_by_fetch_time() -> Result<()> {
+        let temp = tempdir().unwrap();
+        let keyspace = Config::new(temp.path()).open()?;
+        let handle = keyspace.open_partition("responses_test", Default::default())?;
+        let responses = ResponseCacheRepository::new(handle);
+
+        let response = |fetched_at: u64| CachedResponse {
+            fetched_at,
+            result: serde_json::json!([{ "hash": "0x01" }]),
+        };
+        responses.put(
+            "https://api.etherscan.io/v2/api?action=txlist",
+            &response(100),
+        )?;
+        responses.put(
+            "https://api.etherscan.io/v2/api?action=getsourcecode",
+            &response(200),
+        )?;
+        assert_eq!(
+            responses.get("https://api.etherscan.io/v2/api?action=txlist")?,
+            Some(response(100))
+        );
+        assert_eq!(responses.len()?, 2);
+
+        assert_eq!(responses.purge_older_than(150)?, 1);
+        assert!(
+            responses
+                .get("https://api.etherscan.io/v2/api?action=txlist")?
+                .is_none()
+        );
+        assert_eq!(responses.purge_older_than(u64::MAX)?, 1);
+        assert_eq!(responses.len()?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn token_metadata_is_per_chain() -> Result<()> {
+        let temp = tempdir().unwrap();
+        let keyspace = Config::new(temp.path()).open()?;
+        let handle = keyspace.open_partition("tokens_test", Default::default())?;
+        let tokens = TokenRepository::new(handle);
+
+        let usdc = TokenRecord {
+            symbol: Some("USDC".into()),
+            name: Some("USD Coin".into()),
+            decimals: Some(6),
+        };
+        tokens.put(1, "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", &usdc)?;
+        assert_eq!(
+            tokens.get(1, "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48")?,
+            Some(usdc)
+        );
+        assert_eq!(
+            tokens.get(8453, "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48")?,
+            None
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn namespaced_secrets_list_back() -> Result<()> {
+        let temp = tempdir().unwrap();
+        let keyspace = Config::new(temp.path()).open()?;
+        let handle = keyspace.open_partition("secrets_test", Default::default())?;
+        let secrets = SecretsRepository::new(handle);
+
+        let tenderly = SecretKey::ProviderCredential {
+            provider: "tenderly".into(),
+            field: "access_key".into(),
+        };
+        secrets.set(&SecretKey::ExplorerApiKey(8453), "base-key")?;
+        secrets.set(&SecretKey::RpcUrl("base".into()), "https://base.example")?;
+        secrets.set(&tenderly, "tdly")?;
+
+        let mut listed = secrets.list()?;
+        listed.sort();
+        assert_eq!(
+            listed,
+            vec![
+                (SecretKey::ExplorerApiKey(8453), "base-key".to_string()),
+                (
+                    SecretKey::RpcUrl("base".into()),
+                    "https://base.example".to_string()
+                ),
+                (tenderly, "tdly".to_string()),
+            ]
+        );
         Ok(())
     }
 }
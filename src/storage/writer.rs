@@ -0,0 +1,155 @@
+use super::Storage;
+use color_eyre::{Report, Result};
+use std::{
+    sync::{Arc, mpsc},
+    thread,
+};
+
+type WriteFn = Box<dyn FnOnce(&Storage) -> Result<()> + Send>;
+type ErrorFn = Arc<dyn Fn(&'static str, Report) + Send + Sync>;
+pub(super) type ReadFn = Box<dyn FnOnce(&Storage) + Send>;
+
+enum Job {
+    Write {
+        what: &'static str,
+        run: WriteFn,
+    },
+    /// Runs after every write queued before it and hands its result on.
+    Read(ReadFn),
+    /// Answered once every write queued before it is done.
+    Barrier(mpsc::Sender<()>),
+}
+
+/// Runs storage writes and reads on a dedicated thread, in the order they
+/// were queued, so slow disks (journal syncs, large settings values) never
+/// stall input.
+#[derive(Clone)]
+pub(super) struct StorageWriter {
+    jobs: mpsc::Sender<Job>,
+    on_error: ErrorFn,
+}
+
+impl StorageWriter {
+    /// Start the thread over its own handle on the store. Failed writes are
+    /// reported to `on_error` with the `what` they were queued under.
+    pub(super) fn spawn(
+        storage: Storage,
+        on_error: impl Fn(&'static str, Report) + Send + Sync + 'static,
+    ) -> Result<Self> {
+        let on_error: ErrorFn = Arc::new(on_error);
+        let report = on_error.clone();
+        let (jobs, queue) = mpsc::channel();
+        thread::Builder::new()
+            .name("storage-writer".into())
+            .spawn(move || {
+                for job in queue {
+                    match job {
+                        Job::Write { what, run } => {
+                            if let Err(err) = run(&storage) {
+                                report(what, err);
+                            }
+                        }
+                        Job::Read(read) => read(&storage),
+                        Job::Barrier(done) => {
+                            let _ = done.send(());
+                        }
+                    }
+                }
+            })?;
+        Ok(Self { jobs, on_error })
+    }
+
+    /// Report a write that had to run inline because the thread is gone.
+    pub(super) fn report(&self, what: &'static str, err: Report) {
+        (self.on_error)(what, err);
+    }
+
+    /// Queue `run`; returns it back when the thread is gone.
+    pub(super) fn write(&self, what: &'static str, run: WriteFn) -> Option<WriteFn> {
+        match self.jobs.send(Job::Write { what, run }) {
+            Ok(()) => None,
+            Err(mpsc::SendError(Job::Write { run, .. })) => Some(run),
+            Err(_) => None,
+        }
+    }
+
+    /// Queue `read`; returns it back when the thread is gone.
+    pub(super) fn read(&self, read: ReadFn) -> Option<ReadFn> {
+        match self.jobs.send(Job::Read(read)) {
+            Ok(()) => None,
+            Err(mpsc::SendError(Job::Read(read))) => Some(read),
+            Err(_) => None,
+        }
+    }
+
+    pub(super) fn flush(&self) {
+        let (done, wait) = mpsc::channel();
+        if self.jobs.send(Job::Barrier(done)).is_ok() {
+            let _ = wait.recv();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::{SettingKey, Storage};
+    use std::sync::{Arc, Mutex, mpsc};
+    use tempfile::tempdir;
+
+    #[test]
+    fn queued_writes_land_in_order_and_report_failures() -> color_eyre::Result<()> {
+        let temp = tempdir()?;
+        let mut storage = Storage::open(temp.path())?;
+        let failures = Arc::new(Mutex::new(Vec::new()));
+        let seen = failures.clone();
+        storage
+            .start_writer(move |what, err| seen.lock().unwrap().push(format!("{what}: {err}")))?;
+
+        for query in ["first", "second"] {
+            storage.write("last query", move |storage| {
+                storage.settings().set_json(SettingKey::LastQuery, &query)
+            });
+        }
+        storage.write("nothing", |_| Err(color_eyre::eyre::eyre!("disk full")));
+        storage.flush();
+
+        let last: Option<String> = storage.settings().get_json(SettingKey::LastQuery)?;
+        assert_eq!(last.as_deref(), Some("second"));
+        assert_eq!(*failures.lock().unwrap(), vec!["nothing: disk full"]);
+        Ok(())
+    }
+
+    #[test]
+    fn reads_see_the_writes_queued_before_them() -> color_eyre::Result<()> {
+        let temp = tempdir()?;
+        let mut storage = Storage::open(temp.path())?;
+        storage.start_writer(|_, _| {})?;
+        storage.write("last query", |storage| {
+            storage
+                .settings()
+                .set_json(SettingKey::LastQuery, &"queued")
+        });
+        let (done, answer) = mpsc::channel();
+        storage.read(
+            |storage| storage.settings().get_json::<String>(SettingKey::LastQuery),
+            move |last| {
+                let _ = done.send(last.ok().flatten());
+            },
+        );
+        assert_eq!(answer.recv()?.as_deref(), Some("queued"));
+        Ok(())
+    }
+
+    #[test]
+    fn failures_before_the_writer_starts_are_reported_to_it() -> color_eyre::Result<()> {
+        let temp = tempdir()?;
+        let mut storage = Storage::open(temp.path())?;
+        storage.write("nothing", |_| Err(color_eyre::eyre::eyre!("disk full")));
+        let failures = Arc::new(Mutex::new(Vec::new()));
+        let seen = failures.clone();
+        storage
+            .start_writer(move |what, err| seen.lock().unwrap().push(format!("{what}: {err}")))?;
+        assert_eq!(*failures.lock().unwrap(), vec!["nothing: disk full"]);
+        Ok(())
+    }
+}
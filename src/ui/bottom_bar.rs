@@ -1,17 +1,108 @@
 use crate::{
-    app::{Action, AppContext, AppResult, AppView, FocusedPane},
-    components::Component,
+    app::{
+        Action, AppContext, AppResult, AppView, FocusedPane, SelectedEntity, auto_refresh,
+        rpc_health::{self, EndpointHealth},
+    },
+    components::{Component, KeyHint},
 };
 use ratatui::{
     Frame,
     layout::Rect,
     style::{Color, Modifier, Style},
-    text::Line,
+    text::{Line, Span},
     widgets::{Block, Paragraph},
 };
 
 #[derive(Debug, Default)]
-pub struct BottomBar;
+pub struct BottomBar {
+    /// Hints of the focused pane, refreshed before every draw.
+    hints: Vec<KeyHint>,
+}
+
+/// Shortcuts that work from any pane, shown after the focused pane's.
+const GLOBAL_HINTS: [KeyHint; 4] = [
+    KeyHint::new("q", "Quit"),
+    KeyHint::new("1..4", "Focus"),
+    KeyHint::new("/", "Search"),
+    KeyHint::new(":", "Command"),
+];
+
+impl BottomBar {
+    pub fn set_hints(&mut self, hints: Vec<KeyHint>) {
+        self.hints = hints;
+    }
+
+    /// `key Label • …` with keys highlighted; everything is dimmed while a
+    /// modal, which lists its own keys, has focus.
+    fn hint_line(&self, dimmed: bool) -> Line<'static> {
+        let (key_style, label_style) = if dimmed {
+            (
+                Style::default().fg(Color::DarkGray),
+                Style::default().fg(Color::DarkGray),
+            )
+        } else {
+            (
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+                Style::default(),
+            )
+        };
+        let mut spans = Vec::new();
+        let mut shown = Vec::new();
+        for hint in self.hints.iter().chain(GLOBAL_HINTS.iter()) {
+            // A pane hint may already cover a global key, e.g. `/` in the top bar.
+            if shown.contains(&hint.key) {
+                continue;
+            }
+            shown.push(hint.key);
+            if !spans.is_empty() {
+                spans.push(Span::styled(" • ", Style::default().fg(Color::DarkGray)));
+            }
+            spans.push(Span::styled(hint.key, key_style));
+            spans.push(Span::styled(format!(" {}", hint.label), label_style));
+        }
+        Line::from(spans)
+    }
+}
+
+/// One dot per configured RPC endpoint of the selected chain, in failover
+/// order: green with latency when healthy, red when its last request failed.
+fn rpc_indicator(ctx: &AppView<'_>) -> Option<Line<'static>> {
+    let chain = match ctx.state.selected.as_ref()? {
+        SelectedEntity::Address(addr) => addr.chain.as_str(),
+        SelectedEntity::Transaction(tx) => tx.chain.as_str(),
+    };
+    let urls = rpc_health::endpoints(ctx.state.secrets.rpc_url(chain)?);
+    let mut spans = vec![Span::raw(" RPC ")];
+    for url in &urls {
+        let (dot, color, detail) = match ctx.providers.rpc.health().get(url) {
+            Some(
+                health @ EndpointHealth {
+                    latency: Some(latency),
+                    ..
+                },
+            ) if !health.is_failing() => ("●", Color::Green, format!("{}ms", latency.as_millis())),
+            Some(health) if health.is_failing() => ("●", Color::Red, "down".to_string()),
+            _ => ("○", Color::Gray, String::new()),
+        };
+        spans.push(Span::styled(dot, Style::default().fg(color)));
+        if !detail.is_empty() {
+            spans.push(Span::styled(
+                format!(" {detail}"),
+                Style::default().fg(Color::Gray),
+            ));
+        }
+        spans.push(Span::raw(" "));
+    }
+    if let Some(interval) = ctx.state.auto_refresh {
+        spans.push(Span::styled(
+            format!("⟳ {} ", auto_refresh::label(interval)),
+            Style::default().fg(Color::Cyan),
+        ));
+    }
+    Some(Line::from(spans))
+}
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -44,10 +135,31 @@ impl Component for BottomBar {
         } else {
             Style::default().add_modifier(Modifier::BOLD)
         };
-        let widget = Paragraph::new(Line::from(
-            "q Quit • [ Prev Tab • ] Next Tab • h j k l Move • Enter Open • 1..9 Focus • [F] Favorite/Remove",
-        ))
-        .block(Block::bordered().title(Line::from("[4] Keymap").style(style)));
+        let mut block = Block::bordered().title(Line::from("[4] Keymap").style(style));
+        if let Some(register) = ctx.state.macros.recording() {
+            block = block.title(Span::styled(
+                format!(" ● recording @{register} "),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ));
+        }
+        if let Some(indicator) = rpc_indicator(ctx) {
+            block = block.title(indicator.right_aligned());
+        }
+        let line = match ctx.state.command_line.as_deref() {
+            Some(text) => Line::from(vec![
+                Span::styled(":", Style::default().fg(Color::Yellow)),
+                Span::raw(format!("{text}_")),
+                Span::styled(
+                    "  (export csv [dir] • export json [file|-] • Enter run • Esc cancel)",
+                    Style::default().fg(Color::Gray),
+                ),
+            ]),
+            None => self.hint_line(matches!(
+                ctx.state.navigation.focused_pane,
+                FocusedPane::Modal
+            )),
+        };
+        let widget = Paragraph::new(line).block(block);
         frame.render_widget(widget, area);
     }
 
@@ -55,3 +167,37 @@ impl Component for BottomBar {
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(line: &Line<'_>) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn pane_hints_come_before_global_ones() {
+        let mut bar = BottomBar::default();
+        bar.set_hints(vec![
+            KeyHint::new("Enter", "Open"),
+            KeyHint::new("L", "Follow"),
+        ]);
+        assert_eq!(
+            text(&bar.hint_line(false)),
+            "Enter Open • L Follow • q Quit • 1..4 Focus • / Search • : Command"
+        );
+    }
+
+    #[test]
+    fn pane_hint_replaces_global_hint_with_same_key() {
+        let mut bar = BottomBar::default();
+        bar.set_hints(vec![KeyHint::new("/", "Filter")]);
+        let line = text(&bar.hint_line(true));
+        assert!(line.starts_with("/ Filter • q Quit"));
+        assert!(!line.contains("Search"));
+    }
+}
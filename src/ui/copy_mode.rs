@@ -0,0 +1,456 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyCommand {
+    Left,
+    Right,
+    Up,
+    Down,
+    WordForward,
+    WordBackward,
+    LineStart,
+    LineEnd,
+    Top,
+    Bottom,
+    /// `v`: start or drop a character-wise selection.
+    Visual,
+    /// `V`: start or drop a line-wise selection.
+    VisualLine,
+    Yank,
+    /// `m`: like `Yank`, but into the scratchpad.
+    Pin,
+    Exit,
+    /// `/`: type a query; hits are highlighted as it is typed.
+    Search,
+    SearchInput(char),
+    SearchBackspace,
+    SearchSubmit,
+    SearchCancel,
+    NextMatch,
+    PreviousMatch,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Position {
+    row: usize,
+    col: usize,
+}
+
+/// Vim-like cursor over the text a pane last rendered, for copying what
+/// terminal selection cannot: it would run across the pane borders.
+#[derive(Debug, Default)]
+pub struct CopyMode {
+    /// Cell symbols per row; a wide character's trailing cell is empty.
+    rows: Vec<Vec<String>>,
+    cursor: Position,
+    anchor: Option<Position>,
+    linewise: bool,
+    /// Query being typed after `/`.
+    prompt: Option<String>,
+    /// Last submitted query, what `n`/`N` look for.
+    query: String,
+}
+
+impl CopyMode {
+    pub fn command_from_key(&self, event: KeyEvent) -> Option<CopyCommand> {
+        if self.prompt.is_some() {
+            return match event.code {
+                KeyCode::Esc => Some(CopyCommand::SearchCancel),
+                KeyCode::Enter => Some(CopyCommand::SearchSubmit),
+                KeyCode::Backspace => Some(CopyCommand::SearchBackspace),
+                KeyCode::Char(c) if !event.modifiers.contains(KeyModifiers::CONTROL) => {
+                    Some(CopyCommand::SearchInput(c))
+                }
+                _ => None,
+            };
+        }
+        let shift = event.modifiers.contains(KeyModifiers::SHIFT);
+        Some(match event.code {
+            KeyCode::Char('/') => CopyCommand::Search,
+            KeyCode::Char('n') => CopyCommand::NextMatch,
+            KeyCode::Char('N') => CopyCommand::PreviousMatch,
+            KeyCode::Char('h') | KeyCode::Left => CopyCommand::Left,
+            KeyCode::Char('l') | KeyCode::Right => CopyCommand::Right,
+            KeyCode::Char('k') | KeyCode::Up => CopyCommand::Up,
+            KeyCode::Char('j') | KeyCode::Down => CopyCommand::Down,
+            KeyCode::Char('w') => CopyCommand::WordForward,
+            KeyCode::Char('b') => CopyCommand::WordBackward,
+            KeyCode::Char('0') | KeyCode::Home => CopyCommand::LineStart,
+            KeyCode::Char('$') | KeyCode::End => CopyCommand::LineEnd,
+            KeyCode::Char('g') => CopyCommand::Top,
+            KeyCode::Char('G') => CopyCommand::Bottom,
+            KeyCode::Char('V') => CopyCommand::VisualLine,
+            KeyCode::Char('v') if shift => CopyCommand::VisualLine,
+            KeyCode::Char('v') => CopyCommand::Visual,
+            KeyCode::Char('y') | KeyCode::Enter => CopyCommand::Yank,
+            KeyCode::Char('m') => CopyCommand::Pin,
+            KeyCode::Esc | KeyCode::Char('q') => CopyCommand::Exit,
+            _ => return None,
+        })
+    }
+
+    /// Label for the pane border: `COPY`, `VISUAL` or `V-LINE`.
+    pub fn label(&self) -> &'static str {
+        match (self.anchor, self.linewise) {
+            (None, _) => "COPY",
+            (Some(_), false) => "VISUAL",
+            (Some(_), true) => "V-LINE",
+        }
+    }
+
+    /// The label plus the search prompt, or the position among the hits.
+    pub fn status(&self) -> String {
+        let label = self.label();
+        if let Some(prompt) = self.prompt.as_ref() {
+            return format!("{label} /{prompt}_");
+        }
+        if self.query.is_empty() {
+            return label.to_string();
+        }
+        let hits = self.matches(&self.query);
+        match hits.iter().position(|(start, _)| *start == self.cursor) {
+            Some(index) => format!("{label} /{} {}/{}", self.query, index + 1, hits.len()),
+            None if hits.is_empty() => format!("{label} /{} no match", self.query),
+            None => format!("{label} /{} {} hits", self.query, hits.len()),
+        }
+    }
+
+    /// Move or change the selection. `Yank` and `Pin` return the selected
+    /// text, or the cursor's line when nothing is selected.
+    pub fn apply(&mut self, command: CopyCommand) -> Option<String> {
+        let last_row = self.rows.len().saturating_sub(1);
+        match command {
+            CopyCommand::Left => self.cursor.col = self.cursor.col.saturating_sub(1),
+            CopyCommand::Right => self.cursor.col += 1,
+            CopyCommand::Up => self.cursor.row = self.cursor.row.saturating_sub(1),
+            CopyCommand::Down => self.cursor.row = (self.cursor.row + 1).min(last_row),
+            CopyCommand::WordForward => self.cursor.col = self.next_word(),
+            CopyCommand::WordBackward => self.cursor.col = self.previous_word(),
+            CopyCommand::LineStart => self.cursor.col = 0,
+            CopyCommand::LineEnd => self.cursor.col = self.line_end(self.cursor.row),
+            CopyCommand::Top => self.cursor = Position::default(),
+            CopyCommand::Bottom => self.cursor.row = last_row,
+            CopyCommand::Visual | CopyCommand::VisualLine => {
+                let linewise = command == CopyCommand::VisualLine;
+                if self.anchor.is_some() && self.linewise == linewise {
+                    self.anchor = None;
+                } else {
+                    self.anchor.get_or_insert(self.cursor);
+                    self.linewise = linewise;
+                }
+            }
+            CopyCommand::Yank | CopyCommand::Pin => return Some(self.selected_text()),
+            CopyCommand::Exit => {}
+            CopyCommand::Search => self.prompt = Some(String::new()),
+            CopyCommand::SearchInput(c) => {
+                if let Some(prompt) = self.prompt.as_mut() {
+                    prompt.push(c);
+                }
+            }
+            CopyCommand::SearchBackspace => {
+                if let Some(prompt) = self.prompt.as_mut() {
+                    prompt.pop();
+                }
+            }
+            CopyCommand::SearchCancel => self.prompt = None,
+            CopyCommand::SearchSubmit => {
+                self.query = self.prompt.take().unwrap_or_default();
+                self.jump(true, true);
+            }
+            CopyCommand::NextMatch => self.jump(true, false),
+            CopyCommand::PreviousMatch => self.jump(false, false),
+        }
+        self.clamp();
+        None
+    }
+
+    /// Take the text `area` of `buffer` now shows, then highlight the
+    /// selection and cursor over it.
+    pub fn capture_and_highlight(&mut self, buffer: &mut Buffer, area: Rect) {
+        self.rows = (area.top()..area.bottom())
+            .map(|y| {
+                (area.left()..area.right())
+                    .map(|x| buffer[(x, y)].symbol().to_string())
+                    .collect()
+            })
+            .collect();
+        self.clamp();
+        let query = self.prompt.as_deref().unwrap_or(&self.query);
+        let hits = self.matches(query);
+        for (row, y) in (area.top()..area.bottom()).enumerate() {
+            for (col, x) in (area.left()..area.right()).enumerate() {
+                let position = Position { row, col };
+                let is_hit = hits
+                    .iter()
+                    .any(|(start, end)| start.row == row && (start.col..*end).contains(&col));
+                let cell = &mut buffer[(x, y)];
+                if position == self.cursor {
+                    cell.set_style(Style::default().add_modifier(Modifier::REVERSED));
+                } else if self.is_selected(position) {
+                    cell.set_style(Style::default().fg(Color::White).bg(Color::Blue));
+                } else if is_hit {
+                    cell.set_style(Style::default().fg(Color::Black).bg(Color::Yellow));
+                }
+            }
+        }
+    }
+
+    /// Where `query` occurs: start and end column (exclusive) per hit, in
+    /// reading order. Case-insensitive unless the query has capitals.
+    fn matches(&self, query: &str) -> Vec<(Position, usize)> {
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let fold = !query.chars().any(|c| c.is_uppercase());
+        let query = if fold {
+            query.to_ascii_lowercase()
+        } else {
+            query.to_string()
+        };
+        let mut hits = Vec::new();
+        for (row, cells) in self.rows.iter().enumerate() {
+            // Byte offset in the row's text where each cell starts.
+            let mut text = String::new();
+            let mut starts = Vec::with_capacity(cells.len() + 1);
+            for cell in cells {
+                starts.push(text.len());
+                text.push_str(cell);
+            }
+            starts.push(text.len());
+            if fold {
+                text.make_ascii_lowercase();
+            }
+            let column = |offset: usize| starts.partition_point(|start| *start < offset);
+            for (offset, found) in text.match_indices(&query) {
+                let start = column(offset);
+                let end = column(offset + found.len()).max(start + 1);
+                hits.push((Position { row, col: start }, end));
+            }
+        }
+        hits
+    }
+
+    /// Move the cursor to the next (`forward`) or previous hit of the query,
+    /// wrapping around; `here` counts a hit under the cursor as next.
+    fn jump(&mut self, forward: bool, here: bool) {
+        let hits: Vec<Position> = self
+            .matches(&self.query)
+            .into_iter()
+            .map(|(start, _)| start)
+            .collect();
+        let cursor = self.cursor;
+        let target = if forward {
+            hits.iter()
+                .find(|hit| **hit > cursor || (here && **hit == cursor))
+                .or(hits.first())
+        } else {
+            hits.iter().rev().find(|hit| **hit < cursor).or(hits.last())
+        };
+        if let Some(target) = target {
+            self.cursor = *target;
+        }
+    }
+
+    fn clamp(&mut self) {
+        self.cursor.row = self.cursor.row.min(self.rows.len().saturating_sub(1));
+        let width = self.rows.get(self.cursor.row).map_or(0, Vec::len);
+        self.cursor.col = self.cursor.col.min(width.saturating_sub(1));
+    }
+
+    fn cell(&self, row: usize, col: usize) -> &str {
+        self.rows
+            .get(row)
+            .and_then(|cells| cells.get(col))
+            .map_or("", String::as_str)
+    }
+
+    fn is_blank(&self, row: usize, col: usize) -> bool {
+        self.cell(row, col).trim().is_empty()
+    }
+
+    fn line_end(&self, row: usize) -> usize {
+        let cells = self.rows.get(row).map_or(0, Vec::len);
+        (0..cells)
+            .rev()
+            .find(|col| !self.is_blank(row, *col))
+            .unwrap_or(0)
+    }
+
+    fn next_word(&self) -> usize {
+        let Position { row, mut col } = self.cursor;
+        let width = self.rows.get(row).map_or(0, Vec::len);
+        while col < width && !self.is_blank(row, col) {
+            col += 1;
+        }
+        while col < width && self.is_blank(row, col) {
+            col += 1;
+        }
+        if col >= width { self.cursor.col } else { col }
+    }
+
+    fn previous_word(&self) -> usize {
+        let Position { row, mut col } = self.cursor;
+        while col > 0 && self.is_blank(row, col - 1) {
+            col -= 1;
+        }
+        while col > 0 && !self.is_blank(row, col - 1) {
+            col -= 1;
+        }
+        col
+    }
+
+    /// Start and end of the selection, inclusive and in reading order.
+    fn bounds(&self) -> Option<(Position, Position)> {
+        let anchor = self.anchor?;
+        Some((anchor.min(self.cursor), anchor.max(self.cursor)))
+    }
+
+    fn is_selected(&self, position: Position) -> bool {
+        match self.bounds() {
+            Some((start, end)) if self.linewise => (start.row..=end.row).contains(&position.row),
+            Some((start, end)) => (start..=end).contains(&position),
+            None => false,
+        }
+    }
+
+    fn selected_text(&self) -> String {
+        let (start, end) = match self.bounds() {
+            Some(bounds) => bounds,
+            None => {
+                let row = self.cursor.row;
+                (
+                    Position { row, col: 0 },
+                    Position {
+                        row,
+                        col: usize::MAX,
+                    },
+                )
+            }
+        };
+        let linewise = self.linewise || self.anchor.is_none();
+        (start.row..=end.row)
+            .map(|row| {
+                let cells = self.rows.get(row).map_or(&[][..], Vec::as_slice);
+                let from = if linewise || row > start.row {
+                    0
+                } else {
+                    start.col
+                };
+                let to = if linewise || row < end.row {
+                    cells.len()
+                } else {
+                    (end.col + 1).min(cells.len())
+                };
+                let text: String = cells[from.min(to)..to].concat();
+                text.trim_end().to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Text of the table row `area` of `buffer` shows with the `▸ ` selection
+/// marker, columns separated by single spaces.
+pub fn highlighted_row(buffer: &Buffer, area: Rect) -> Option<String> {
+    (area.top()..area.bottom()).find_map(|y| {
+        let row: String = (area.left()..area.right())
+            .map(|x| buffer[(x, y)].symbol())
+            .collect();
+        let (_, rest) = row.split_once("▸ ")?;
+        let text = rest.split_whitespace().collect::<Vec<_>>().join(" ");
+        (!text.is_empty()).then_some(text)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::text::Line;
+    use ratatui::widgets::{Paragraph, Widget};
+
+    fn copy_mode(text: &str, width: u16) -> CopyMode {
+        let lines: Vec<Line> = text.lines().map(Line::from).collect();
+        let area = Rect::new(0, 0, width, lines.len() as u16);
+        let mut buffer = Buffer::empty(area);
+        Paragraph::new(lines).render(area, &mut buffer);
+        let mut mode = CopyMode::default();
+        mode.capture_and_highlight(&mut buffer, area);
+        mode
+    }
+
+    #[test]
+    fn yanks_charwise_and_linewise_selections() {
+        let mut mode = copy_mode("Balance: 1.5 ETH\nNonce: 42\nCode: none", 20);
+        assert_eq!(mode.apply(CopyCommand::Yank).unwrap(), "Balance: 1.5 ETH");
+
+        mode.apply(CopyCommand::WordForward);
+        mode.apply(CopyCommand::Visual);
+        mode.apply(CopyCommand::Down);
+        assert_eq!(mode.label(), "VISUAL");
+        assert_eq!(mode.apply(CopyCommand::Yank).unwrap(), "1.5 ETH\nNonce: 42");
+
+        mode.apply(CopyCommand::VisualLine);
+        mode.apply(CopyCommand::Bottom);
+        assert_eq!(mode.label(), "V-LINE");
+        assert_eq!(
+            mode.apply(CopyCommand::Yank).unwrap(),
+            "Balance: 1.5 ETH\nNonce: 42\nCode: none"
+        );
+
+        mode.apply(CopyCommand::VisualLine);
+        assert_eq!(mode.label(), "COPY");
+        mode.apply(CopyCommand::LineEnd);
+        mode.apply(CopyCommand::WordBackward);
+        assert_eq!(mode.cursor, Position { row: 2, col: 6 });
+        assert_eq!(mode.apply(CopyCommand::Pin).unwrap(), "Code: none");
+    }
+
+    #[test]
+    fn finds_the_marked_table_row() {
+        let area = Rect::new(0, 0, 30, 3);
+        let mut buffer = Buffer::empty(area);
+        Paragraph::new(vec![
+            Line::from("  0x01   Transfer"),
+            Line::from("▸ 0x02   Approval   42"),
+            Line::from("  0x03   Transfer"),
+        ])
+        .render(area, &mut buffer);
+        assert_eq!(
+            highlighted_row(&buffer, area).as_deref(),
+            Some("0x02 Approval 42")
+        );
+        assert_eq!(highlighted_row(&buffer, Rect::new(0, 0, 30, 1)), None);
+    }
+
+    #[test]
+    fn search_jumps_between_hits() {
+        let mut mode = copy_mode("CALL 0xabc\n  call 0xdef\nSTOP", 20);
+        mode.apply(CopyCommand::Search);
+        for c in "call".chars() {
+            mode.apply(CopyCommand::SearchInput(c));
+        }
+        assert_eq!(mode.status(), "COPY /call_");
+        mode.apply(CopyCommand::SearchSubmit);
+        assert_eq!(mode.cursor, Position { row: 0, col: 0 });
+        assert_eq!(mode.status(), "COPY /call 1/2");
+        mode.apply(CopyCommand::NextMatch);
+        assert_eq!(mode.cursor, Position { row: 1, col: 2 });
+        mode.apply(CopyCommand::NextMatch);
+        assert_eq!(mode.cursor, Position { row: 0, col: 0 });
+        mode.apply(CopyCommand::PreviousMatch);
+        assert_eq!(mode.cursor, Position { row: 1, col: 2 });
+
+        // A capital makes the search case-sensitive.
+        mode.apply(CopyCommand::Search);
+        mode.apply(CopyCommand::SearchInput('C'));
+        mode.apply(CopyCommand::SearchInput('A'));
+        mode.apply(CopyCommand::SearchSubmit);
+        assert_eq!(mode.status(), "COPY /CA 1/1");
+        assert_eq!(mode.cursor, Position { row: 0, col: 0 });
+    }
+}
@@ -1,22 +1,62 @@
-use super::util::short_hex;
+use super::copy_mode::{CopyCommand, CopyMode, highlighted_row};
+use super::util::{
+    TableScrolls, compact_eth, format_timestamp, loading_title, short_hex, truncate_line,
+};
 use crate::{
     app::{
-        Action, AppContext, AppResult, AppView, FocusedPane, HydratedTransaction, MainViewMode,
-        MainViewTab, SelectedEntity, TransactionDirection, TransactionRef, TransactionStatus,
+        Action, AddressRef, AppContext, AppResult, AppView, BlobDetails, FocusedPane,
+        HydratedAddress, HydratedTransaction, InfoLink, MainViewMode, MainViewTab, SelectedEntity,
+        TransactionDirection, TransactionRef, TransactionStatus,
+        access_list::{AccessEntries, AccessListView},
+        activity::{self, Activity},
+        balance_diff::BalanceDiff,
+        chains::{self, Rollup},
+        custom_tabs::{self, CustomTab, CustomTabState, TabContent},
+        fees::FeesView,
+        format_eth_value, format_native_value,
+        l2::{self, L2Context, Origin, TicketStatus, WithdrawalStatus},
+        labels::LabelRegistry,
+        line_mode::LineMode,
+        nonces::NonceHistory,
+        plugins::PluginState,
+        raw_tx::{RawSource, RawView},
+        signatures::{self, SignatureBook},
+        slots,
+        transfers::{Asset, Transfer, TransferSummary, TransfersView},
+        upgrades::UpgradesView,
     },
-    components::Component,
+    components::{Component, KeyHint},
+    storage::SignatureKind,
 };
+use alloy::primitives::{Address, B256, I256, U256, utils::format_units};
+use crossterm::event::KeyEvent;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    text::Line,
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState, Tabs},
+    text::{Line, Span},
+    widgets::{
+        Bar, BarChart, BarGroup, Block, Borders, Cell, Paragraph, Row, Sparkline, Table,
+        TableState, Tabs, Wrap,
+    },
 };
 
+/// Rows taken by the activity chart above the transactions table.
+const ACTIVITY_HEIGHT: u16 = 6;
+/// Below this width the transactions table drops its Direction and Block
+/// columns and shortens values, so hashes and counterparties stay readable.
+const COMPACT_TABLE_WIDTH: u16 = 90;
+
 #[derive(Debug, Default)]
 pub struct MainView {
     placeholder: String,
+    /// Scroll positions of the tables that can grow to thousands of rows,
+    /// per table and address.
+    scrolls: TableScrolls,
+    /// Set while the pane is in copy mode (`v`).
+    copy: Option<CopyMode>,
+    /// Table row marked `▸` in the last frame, what `m` pins.
+    highlighted: Option<String>,
 }
 
 #[allow(dead_code)]
@@ -31,214 +71,86 @@ pub enum MainViewCommand {
     ActivateSelection,
     HydrationStarted,
     HydrationFinished,
+    EnterCopyMode,
+    Copy(CopyCommand),
+    /// `w`: next of truncate, wrap and scroll for the current tab.
+    CycleLineMode,
+    ScrollLeft,
+    ScrollRight,
+    /// `m`: pin the highlighted row, or the selection, to the scratchpad.
+    PinHighlighted,
 }
 
 impl MainView {
-    fn tab_titles(mode: MainViewMode) -> &'static [(&'static str, MainViewTab)] {
-        match mode {
-            MainViewMode::Address => &[
-                ("Info", MainViewTab::AddressInfo),
-                ("Transactions", MainViewTab::AddressTransactions),
-                ("Internal", MainViewTab::AddressInternal),
-                ("Balances", MainViewTab::AddressBalances),
-                ("Permissions", MainViewTab::AddressPermissions),
-            ],
-            MainViewMode::Transaction => &[
-                ("Summary", MainViewTab::TransactionSummary),
-                ("Debug", MainViewTab::TransactionDebug),
-                ("Storage Diff", MainViewTab::TransactionStorageDiff),
-            ],
-        }
+    pub fn is_copying(&self) -> bool {
+        self.copy.is_some()
     }
 
-    fn tab_index(mode: MainViewMode, tab: MainViewTab) -> usize {
-        Self::tab_titles(mode)
-            .iter()
-            .position(|(_, t)| *t == tab.normalize(mode))
-            .unwrap_or(0)
+    /// What `key` does in copy mode, `None` outside it.
+    pub fn copy_command(&self, key: KeyEvent) -> Option<CopyCommand> {
+        self.copy.as_ref()?.command_from_key(key)
     }
 
-    fn content_for(tab: MainViewTab) -> &'static str {
-        match tab {
-            MainViewTab::AddressTransactions => "Address transactions overview (placeholder)",
-            MainViewTab::AddressInternal => "Address internal calls (placeholder)",
-            MainViewTab::AddressBalances => "Address balances summary (placeholder)",
-            MainViewTab::AddressPermissions => "Address permissions matrix (placeholder)",
-            MainViewTab::AddressInfo => "Address overview (placeholder)",
-            MainViewTab::TransactionSummary => "Transaction summary (placeholder)",
-            MainViewTab::TransactionDebug => "Transaction debugger (placeholder)",
-            MainViewTab::TransactionStorageDiff => "Transaction storage diff (placeholder)",
+    /// Where a pin comes from: chain, selection and tab.
+    fn pin_source(ctx: &AppContext<'_>) -> String {
+        let mode = ctx.state.navigation.main_view_mode;
+        let tabs = Self::tab_titles(mode, &ctx.state.custom_tabs);
+        let tab = tabs[Self::tab_index(&tabs, ctx.state.navigation.main_view_tab)].0;
+        match ctx.state.selected.as_ref() {
+            Some(SelectedEntity::Address(addr)) => {
+                format!("{} {} • {tab}", addr.chain, short_hex(&addr.address))
+            }
+            Some(SelectedEntity::Transaction(tx)) => {
+                format!("{} {} • {tab}", tx.chain, short_hex(&tx.hash))
+            }
+            None => tab.to_string(),
         }
     }
 
-    fn transaction_summary_text(data: &HydratedTransaction) -> String {
-        let status = data
-            .status
-            .map(TransactionStatus::label)
-            .unwrap_or("Not cached");
-        let from = data
-            .from
-            .as_ref()
-            .map(|addr| short_hex(addr))
-            .unwrap_or_else(|| "Not cached".into());
-        let to = match (data.to.as_ref(), data.status) {
-            (Some(addr), _) => short_hex(addr),
-            (None, Some(_)) => "Contract creation".into(),
-            (None, None) => "Not cached".into(),
-        };
-        let value = data
-            .value_formatted
-            .clone()
-            .unwrap_or_else(|| "Not cached".into());
-        let block = data
-            .block_number
-            .map(|n| n.to_string())
-            .unwrap_or_else(|| "Not cached".into());
-        let calldata_raw = data.calldata.clone();
-        let calldata_display = calldata_raw
-            .as_ref()
-            .map(|value| {
-                if value.len() > 66 {
-                    format!("{}…", &value[..66])
-                } else {
-                    value.clone()
-                }
-            })
-            .unwrap_or_else(|| "Not cached".into());
-
-        let mut lines = Vec::new();
-        lines.push(format!("Hash: {}", short_hex(&data.identifier)));
-        lines.push(format!("Status: {status}"));
-        lines.push(format!("From: {from}"));
-        lines.push(format!("To: {to}"));
-        lines.push(format!("Value: {value}"));
-        lines.push(format!("Block: {block}"));
-        lines.push(format!("Calldata: {calldata_display}"));
-
-        lines.join("\n")
-    }
-}
-
-impl Component for MainView {
-    type Command = MainViewCommand;
-
-    fn init(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<()> {
-        self.placeholder = "Select a favorite to begin".into();
-        Ok(())
-    }
-
-    fn update(
-        &mut self,
-        command: &Self::Command,
-        ctx: &mut AppContext<'_>,
-    ) -> AppResult<Option<Action>> {
-        match command {
-            MainViewCommand::SetMode(mode) => {
-                ctx.state.navigation.main_view_mode = *mode;
-                ctx.state.navigation.main_view_tab =
-                    ctx.state.navigation.main_view_tab.normalize(*mode);
-            }
-            MainViewCommand::SwitchTab(tab) => {
-                ctx.state.navigation.main_view_tab =
-                    tab.normalize(ctx.state.navigation.main_view_mode);
-            }
-            MainViewCommand::NextTab => {
-                ctx.state.navigation.next_main_view_tab();
-            }
-            MainViewCommand::PreviousTab => {
-                ctx.state.navigation.previous_main_view_tab();
-            }
-            MainViewCommand::MoveSelectionUp => {
-                if ctx.state.navigation.main_view_mode == MainViewMode::Address
-                    && !ctx.state.loading.main_view.is_loading
-                {
-                    let tab = ctx
-                        .state
-                        .navigation
-                        .main_view_tab
-                        .normalize(MainViewMode::Address);
-                    if matches!(tab, MainViewTab::AddressTransactions) {
-                        if let Some(address) = ctx.state.current_address.as_ref() {
-                            if let Some(table) = address.transactions_table.as_ref() {
-                                ctx.state.address_transactions_view.clamp(table.rows.len());
-                                if !table.rows.is_empty() {
-                                    if ctx.state.address_transactions_view.selected_index > 0 {
-                                        ctx.state.address_transactions_view.selected_index -= 1;
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-            MainViewCommand::MoveSelectionDown => {
-                if ctx.state.navigation.main_view_mode == MainViewMode::Address
-                    && !ctx.state.loading.main_view.is_loading
-                {
-                    let tab = ctx
-                        .state
-                        .navigation
-                        .main_view_tab
-                        .normalize(MainViewMode::Address);
-                    if matches!(tab, MainViewTab::AddressTransactions) {
-                        if let Some(address) = ctx.state.current_address.as_ref() {
-                            if let Some(table) = address.transactions_table.as_ref() {
-                                ctx.state.address_transactions_view.clamp(table.rows.len());
-                                if !table.rows.is_empty() {
-                                    let last = table.rows.len().saturating_sub(1);
-                                    let index =
-                                        &mut ctx.state.address_transactions_view.selected_index;
-                                    if *index < last {
-                                        *index += 1;
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-            MainViewCommand::ActivateSelection => {
-                if ctx.state.navigation.main_view_mode == MainViewMode::Address
-                    && !ctx.state.loading.main_view.is_loading
-                {
-                    let tab = ctx
-                        .state
-                        .navigation
-                        .main_view_tab
-                        .normalize(MainViewMode::Address);
-                    if matches!(tab, MainViewTab::AddressTransactions) {
-                        if let (Some(SelectedEntity::Address(addr)), Some(address)) = (
-                            ctx.state.selected.as_ref(),
-                            ctx.state.current_address.as_ref(),
-                        ) {
-                            if let Some(table) = address.transactions_table.as_ref() {
-                                ctx.state.address_transactions_view.clamp(table.rows.len());
-                                if !table.rows.is_empty() {
-                                    let index = ctx.state.address_transactions_view.selected_index;
-                                    let row = &table.rows[index];
-                                    ctx.state.pending_transaction_preview = Some(row.clone());
-                                    return Ok(Some(Action::SelectionChanged(
-                                        SelectedEntity::Transaction(TransactionRef {
-                                            label: short_hex(&row.hash),
-                                            hash: row.hash.clone(),
-                                            chain: addr.chain.clone(),
-                                        }),
-                                    )));
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-            MainViewCommand::HydrationStarted | MainViewCommand::HydrationFinished => {}
+    /// Text `m` pins: the full transaction on the Transactions tab, the
+    /// highlighted row of other tables, and the selection itself otherwise.
+    fn pin_text(&self, ctx: &AppContext<'_>) -> Option<String> {
+        let mode = ctx.state.navigation.main_view_mode;
+        let tab = ctx.state.navigation.main_view_tab.normalize(mode);
+        if tab == MainViewTab::AddressTransactions
+            && let Some(row) = ctx
+                .state
+                .current_address
+                .as_ref()
+                .and_then(|address| address.transactions_table.as_ref())
+                .and_then(|table| {
+                    table
+                        .rows
+                        .get(ctx.state.address_transactions_view.selected_index)
+                })
+        {
+            let to = row.to.as_deref().unwrap_or("contract creation");
+            let block = row
+                .block_number
+                .map(|block| format!(" • block {block}"))
+                .unwrap_or_default();
+            return Some(format!(
+                "{} {} → {to} {}{block}",
+                row.hash, row.from, row.value_display
+            ));
+        }
+        if let Some(row) = self.highlighted.clone() {
+            return Some(row);
+        }
+        match ctx.state.selected.as_ref()? {
+            SelectedEntity::Address(addr) => Some(addr.address.clone()),
+            SelectedEntity::Transaction(tx) => Some(tx.hash.clone()),
         }
-        Ok(None)
     }
 
-    fn render(&mut self, frame: &mut Frame<'_>, area: Rect, ctx: &AppView<'_>) {
+    /// The pane as it looks outside copy mode.
+    fn render_pane(&mut self, frame: &mut Frame<'_>, area: Rect, ctx: &AppView<'_>) {
         let is_focused = matches!(ctx.state.navigation.focused_pane, FocusedPane::MainView);
         let mode = ctx.state.navigation.main_view_mode;
-        let tab = ctx.state.navigation.main_view_tab.normalize(mode);
+        let tab = ctx
+            .state
+            .custom_tabs
+            .resolve(ctx.state.navigation.main_view_tab, mode);
 
         let border_style = if is_focused {
             Style::default()
@@ -255,9 +167,12 @@ impl Component for MainView {
         };
         let title = format!("[3] Main View · {mode_label}");
 
-        let block = Block::default()
+        let mut block = Block::default()
             .borders(Borders::ALL)
             .title(Line::from(title).style(border_style));
+        if let Some(badge) = loading_title(&ctx.state.loading.main_view) {
+            block = block.title(badge);
+        }
         let inner = block.inner(area);
         frame.render_widget(block, area);
 
@@ -273,16 +188,22 @@ impl Component for MainView {
             return;
         }
 
-        let tab_titles: Vec<Line> = Self::tab_titles(mode)
-            .iter()
-            .map(|(label, _)| Line::from(*label))
-            .collect();
-        let tabs = Tabs::new(tab_titles)
-            .select(Self::tab_index(mode, tab))
+        let titles = Self::tab_titles(mode, &ctx.state.custom_tabs);
+        let tab_index = Self::tab_index(&titles, tab);
+        let tab_label = titles[tab_index].0;
+        let tabs = Tabs::new(titles.iter().map(|(label, _)| Line::from(*label)))
+            .select(tab_index)
             .highlight_style(Style::default().fg(Color::Cyan));
         frame.render_widget(tabs, layout[0]);
 
-        let tab_label = Self::tab_titles(mode)[Self::tab_index(mode, tab)].0;
+        let custom_tab = match tab {
+            MainViewTab::Custom(index) => ctx.state.custom_tabs.tabs.get(index),
+            _ => None,
+        };
+        let rerun_hint = match custom_tab {
+            Some(custom) if !custom.command.is_empty() => "[r] Rerun • ",
+            _ => "",
+        };
         let selection_text = match (&ctx.state.selected, mode) {
             (Some(entity @ SelectedEntity::Address(addr)), MainViewMode::Address) => {
                 let fav_marker = if ctx.state.is_favorite(entity) {
@@ -290,16 +211,66 @@ impl Component for MainView {
                 } else {
                     ""
                 };
+                let label = match ctx.state.labels.lookup(&addr.chain, &addr.address) {
+                    Some(label) => {
+                        let tags = label.tags.joined();
+                        let tags = if tags.is_empty() {
+                            String::new()
+                        } else {
+                            format!(" [{tags}]")
+                        };
+                        let source = if label.user { " (your label)" } else { "" };
+                        format!("\nLabel: {}{tags}{source}", label.name)
+                    }
+                    None => String::new(),
+                };
                 let base = format!(
-                    "{} on {}{fav_marker}\nTab: {}",
+                    "{} on {}{fav_marker}{label}\nTab: {}",
                     short_hex(&addr.address),
                     addr.chain,
                     tab_label
                 );
-                if matches!(tab, MainViewTab::AddressTransactions) {
-                    format!("{base}\n[Enter] Open transaction • [F] Favorite/Remove")
+                let bindings_hint = match &ctx.state.current_address {
+                    Some(data)
+                        if data.identifier == addr.address && data.contract_source.is_some() =>
+                    {
+                        " • [b] Export sol! bindings"
+                    }
+                    _ => "",
+                };
+                if let Some(text) = &ctx.state.labels.editing {
+                    format!(
+                        "{base}\nLabel › {text}_ (name #tag… • Enter save • empty clears • Esc cancel)"
+                    )
+                } else if matches!(tab, MainViewTab::AddressEvents) {
+                    format!(
+                        "{base}\n[e] Filter (e.g. Transfer to=0x…) • [Enter] Open transaction • [a] Alert on it"
+                    )
+                } else if matches!(tab, MainViewTab::AddressInfo)
+                    && ctx.state.current_address.as_ref().is_some_and(|data| {
+                        data.identifier == addr.address && !data.info_links().is_empty()
+                    })
+                {
+                    format!(
+                        "{base}\n[j/k] Select link • [Enter] Open • [F] Favorite/Remove • [N] Label{bindings_hint}"
+                    )
+                } else if matches!(tab, MainViewTab::AddressChains) {
+                    format!("{base}\n[j/k] Select chain • [Enter] Open on that chain")
+                } else if matches!(tab, MainViewTab::AddressStorage) {
+                    format!(
+                        "{base}\n[p] Pin slot (number, 0x…, implementation, admin) • [n] Alerts • [x] Unpin"
+                    )
+                } else if matches!(tab, MainViewTab::AddressTransactions) {
+                    let follow_hint = if ctx.state.follow.is_following(&addr.address) {
+                        "[L] Stop following ● live"
+                    } else {
+                        "[L] Follow new blocks"
+                    };
+                    format!(
+                        "{base}\n[Enter] Open transaction • {follow_hint} • [F] Favorite/Remove • [N] Label{bindings_hint}"
+                    )
                 } else {
-                    format!("{base}\n[F] Favorite/Remove")
+                    format!("{base}\n{rerun_hint}[F] Favorite/Remove • [N] Label{bindings_hint}")
                 }
             }
             (Some(entity @ SelectedEntity::Transaction(tx)), MainViewMode::Transaction) => {
@@ -309,7 +280,7 @@ impl Component for MainView {
                     ""
                 };
                 format!(
-                    "{} on {}{fav_marker}\nTab: {}\n[F] Favorite/Remove",
+                    "{} on {}{fav_marker}\nTab: {}\n{rerun_hint}[p/n] Previous/next in block • [F] Favorite/Remove",
                     short_hex(&tx.hash),
                     tx.chain,
                     tab_label
@@ -328,12 +299,38 @@ impl Component for MainView {
         };
         let transaction_data = match (&ctx.state.selected, &ctx.state.current_transaction) {
             (Some(SelectedEntity::Transaction(tx)), Some(data)) if data.identifier == tx.hash => {
-                Some(data)
+                Some((tx, data))
             }
             _ => None,
         };
 
-        let tab_summary = if ctx.state.loading.main_view.is_loading {
+        // Sections of this tab that failed to load sit above what did load.
+        let banners: Vec<Line> = match (mode, address_data) {
+            (MainViewMode::Address, Some(data)) if !ctx.state.loading.main_view.is_loading => data
+                .failures
+                .iter()
+                .filter(|failure| failure.section.tab() == tab)
+                .map(|failure| Line::from(failure.banner()))
+                .collect(),
+            _ => Vec::new(),
+        };
+        let content = if banners.is_empty() {
+            layout[1]
+        } else {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(banners.len() as u16), Constraint::Min(1)])
+                .split(layout[1]);
+            let banner_widget = Paragraph::new(banners)
+                .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD));
+            frame.render_widget(banner_widget, chunks[0]);
+            chunks[1]
+        };
+
+        // Custom tabs do not wait on the selection's hydration.
+        let tab_summary = if let Some(custom) = custom_tab {
+            Self::custom_tab_text(custom, ctx)
+        } else if ctx.state.loading.main_view.is_loading {
             "Loading…".to_string()
         } else {
             match mode {
@@ -344,7 +341,7 @@ impl Component for MainView {
                             MainViewTab::AddressTransactions => data.transactions.join("\n"),
                             MainViewTab::AddressInternal => data.internal.join("\n"),
                             MainViewTab::AddressBalances => data.balances.join("\n"),
-                            MainViewTab::AddressPermissions => data.permissions.join("\n"),
+                            MainViewTab::AddressPermissions => Self::permissions_text(data, ctx),
                             _ => Self::content_for(tab).to_string(),
                         }
                     } else {
@@ -352,11 +349,24 @@ impl Component for MainView {
                     }
                 }
                 MainViewMode::Transaction => {
-                    if let Some(data) = transaction_data {
+                    if let Some((tx, data)) = transaction_data {
                         match tab {
-                            MainViewTab::TransactionSummary => Self::transaction_summary_text(data),
+                            MainViewTab::TransactionSummary => Self::transaction_summary_text(
+                                data,
+                                &ctx.state.transfers,
+                                &tx.chain,
+                                &ctx.state.labels,
+                                &ctx.state.signatures,
+                            ),
+                            MainViewTab::TransactionFees => {
+                                Self::fees_text(&ctx.state.fees, chains::currency(&tx.chain))
+                            }
+                            MainViewTab::TransactionAccessList => {
+                                Self::access_list_text(&ctx.state.access_list)
+                            }
                             MainViewTab::TransactionDebug => data.debug.join("\n"),
                             MainViewTab::TransactionStorageDiff => data.storage_diff.join("\n"),
+                            MainViewTab::TransactionRaw => Self::raw_text(&ctx.state.raw),
                             _ => Self::content_for(tab).to_string(),
                         }
                     } else {
@@ -372,138 +382,1947 @@ impl Component for MainView {
             format!("{selection_text}\n\n{tab_summary}")
         };
 
+        if mode == MainViewMode::Address
+            && matches!(tab, MainViewTab::AddressEvents)
+            && !ctx.state.loading.main_view.is_loading
+        {
+            self.render_events(frame, content, &selection_text, ctx);
+            return;
+        }
+
+        if mode == MainViewMode::Address && matches!(tab, MainViewTab::AddressChains) {
+            Self::render_chains(frame, content, &selection_text, ctx);
+            return;
+        }
+
+        if mode == MainViewMode::Transaction
+            && matches!(tab, MainViewTab::TransactionBalanceDiff)
+            && !ctx.state.loading.main_view.is_loading
+        {
+            Self::render_balance_diff(frame, content, &selection_text, ctx);
+            return;
+        }
+
+        if mode == MainViewMode::Address
+            && matches!(tab, MainViewTab::AddressStorage)
+            && let Some(SelectedEntity::Address(addr)) = ctx.state.selected.as_ref()
+        {
+            Self::render_storage(frame, content, &selection_text, &addr.address, ctx);
+            return;
+        }
+
         if mode == MainViewMode::Address
             && matches!(tab, MainViewTab::AddressTransactions)
             && !ctx.state.loading.main_view.is_loading
+            && let Some(SelectedEntity::Address(addr)) = ctx.state.selected.as_ref()
+            && let Some(address) = address_data
+            && let Some(table) = address.transactions_table.as_ref()
+            && !table.rows.is_empty()
+            && content.height >= 4
         {
-            if let Some(address) = address_data {
-                if let Some(table) = address.transactions_table.as_ref() {
-                    if !table.rows.is_empty() && layout[1].height >= 4 {
-                        let available_height = layout[1].height;
-                        let mut summary_height = summary_content.lines().count() as u16;
-                        if summary_height == 0 {
-                            summary_height = 1;
-                        }
-                        summary_height =
-                            summary_height.min(available_height.saturating_sub(2).max(2));
-
-                        let content_chunks = Layout::default()
-                            .direction(Direction::Vertical)
-                            .constraints([Constraint::Length(summary_height), Constraint::Min(2)])
-                            .split(layout[1]);
-
-                        let summary_widget = Paragraph::new(summary_content.clone())
-                            .style(Style::default().fg(Color::Gray));
-                        frame.render_widget(summary_widget, content_chunks[0]);
-
-                        let rows: Vec<Row<'_>> = table
-                            .rows
-                            .iter()
-                            .map(|row| {
-                                let status_style = match row.status {
-                                    TransactionStatus::Failed => {
-                                        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
-                                    }
-                                    TransactionStatus::Success => Style::default().fg(Color::Green),
-                                };
-                                let direction_style = match row.direction {
-                                    TransactionDirection::Incoming => {
-                                        Style::default().fg(Color::Green)
-                                    }
-                                    TransactionDirection::Outgoing => {
-                                        Style::default().fg(Color::Red)
-                                    }
-                                    TransactionDirection::SelfTransfer => {
-                                        Style::default().fg(Color::Yellow)
-                                    }
-                                    TransactionDirection::Interaction => Style::default(),
-                                };
-                                let value_style = match row.direction {
-                                    TransactionDirection::Incoming => {
-                                        Style::default().fg(Color::Green)
-                                    }
-                                    TransactionDirection::Outgoing => {
-                                        Style::default().fg(Color::Red)
-                                    }
-                                    _ => Style::default(),
-                                };
-                                let status_cell =
-                                    Cell::from(row.status.label()).style(status_style);
-                                let hash_cell = Cell::from(short_hex(&row.hash));
-                                let direction_cell =
-                                    Cell::from(row.direction.label()).style(direction_style);
-                                let spacer_cell = Cell::from("");
-                                let counterparty_cell = Cell::from(row.counterparty.as_str());
-                                let value_cell =
-                                    Cell::from(row.value_display.as_str()).style(value_style);
-                                let block_cell = Cell::from(
-                                    row.block_number
-                                        .map(|n| n.to_string())
-                                        .unwrap_or_else(|| "?".into()),
-                                );
-                                Row::new(vec![
-                                    status_cell,
-                                    hash_cell,
-                                    direction_cell,
-                                    spacer_cell,
-                                    counterparty_cell,
-                                    value_cell,
-                                    block_cell,
-                                ])
-                            })
-                            .collect();
-
-                        let header = Row::new(vec![
-                            "Status",
-                            "Tx Hash",
-                            "Direction",
-                            "",
-                            "Counterparty",
-                            "Value",
-                            "Block",
-                        ])
-                        .style(Style::default().add_modifier(Modifier::BOLD));
+            let available_height = content.height;
+            let mut summary_height = summary_content.lines().count() as u16;
+            if summary_height == 0 {
+                summary_height = 1;
+            }
+            summary_height = summary_height.min(available_height.saturating_sub(2).max(2));
 
-                        let mut state = TableState::default();
-                        let selected = ctx
-                            .state
-                            .address_transactions_view
-                            .selected_index
-                            .min(table.rows.len().saturating_sub(1));
-                        state.select(Some(selected));
-
-                        let widths = [
-                            Constraint::Length(7),
-                            Constraint::Length(14),
-                            Constraint::Length(11),
-                            Constraint::Length(2),
-                            Constraint::Fill(1),
-                            Constraint::Length(15),
-                            Constraint::Length(8),
-                        ];
-
-                        let table_widget = Table::new(rows, widths)
-                            .header(header)
-                            .column_spacing(1)
-                            .highlight_symbol("▸ ")
-                            .row_highlight_style(
-                                Style::default()
-                                    .fg(Color::Cyan)
-                                    .add_modifier(Modifier::BOLD),
-                            );
-
-                        frame.render_stateful_widget(table_widget, content_chunks[1], &mut state);
-                        return;
+            // The chart only shows when the table keeps room for a few rows.
+            let activity = activity::bucket_activity(&table.rows)
+                .filter(|_| available_height >= summary_height + ACTIVITY_HEIGHT + 4);
+            let chart_height = if activity.is_some() {
+                ACTIVITY_HEIGHT
+            } else {
+                0
+            };
+            let content_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(summary_height),
+                    Constraint::Length(chart_height),
+                    Constraint::Min(2),
+                ])
+                .split(content);
+
+            let summary_widget =
+                Paragraph::new(summary_content.clone()).style(Style::default().fg(Color::Gray));
+            frame.render_widget(summary_widget, content_chunks[0]);
+            if let Some(activity) = activity.as_ref() {
+                let currency = chains::currency(&addr.chain);
+                Self::render_activity(frame, content_chunks[1], activity, currency);
+            }
+
+            let selected = ctx
+                .state
+                .address_transactions_view
+                .selected_index
+                .min(table.rows.len().saturating_sub(1));
+            // Only the rows in view are built; the header takes one line.
+            let window = self
+                .scrolls
+                .of(MainViewTab::AddressTransactions, &addr.address)
+                .window(
+                    selected,
+                    table.rows.len(),
+                    content_chunks[2].height.saturating_sub(1) as usize,
+                );
+            let compact = content_chunks[2].width < COMPACT_TABLE_WIDTH;
+            let rows: Vec<Row<'_>> = table.rows[window.clone()]
+                .iter()
+                .map(|row| {
+                    let status_style = match row.status {
+                        TransactionStatus::Failed => {
+                            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+                        }
+                        TransactionStatus::Success => Style::default().fg(Color::Green),
+                    };
+                    let direction_style = match row.direction {
+                        TransactionDirection::Incoming => Style::default().fg(Color::Green),
+                        TransactionDirection::Outgoing => Style::default().fg(Color::Red),
+                        TransactionDirection::SelfTransfer => Style::default().fg(Color::Yellow),
+                        TransactionDirection::Interaction => Style::default(),
+                    };
+                    let value_style = match row.direction {
+                        TransactionDirection::Incoming => Style::default().fg(Color::Green),
+                        TransactionDirection::Outgoing => Style::default().fg(Color::Red),
+                        _ => Style::default(),
+                    };
+                    let status_cell = Cell::from(row.status.label()).style(status_style);
+                    let hash_cell = Cell::from(short_hex(&row.hash));
+                    let direction_cell = Cell::from(row.direction.label()).style(direction_style);
+                    let method_cell = Cell::from(
+                        row.calldata
+                            .as_deref()
+                            .and_then(|calldata| ctx.state.signatures.method(calldata))
+                            .map(signatures::method_name)
+                            .unwrap_or_default(),
+                    )
+                    .style(Style::default().fg(Color::Yellow));
+                    let counterparty = row
+                        .counterparty_address()
+                        .and_then(|address| ctx.state.labels.lookup(&addr.chain, address))
+                        .map(|label| label.name.to_string())
+                        .unwrap_or_else(|| row.counterparty.clone());
+                    let counterparty_cell = Cell::from(counterparty);
+                    let value_cell = if compact {
+                        let sign = match row.direction {
+                            _ if row.value_wei.is_zero() => "",
+                            TransactionDirection::Incoming => "+",
+                            TransactionDirection::Outgoing => "-",
+                            _ => "",
+                        };
+                        Cell::from(format!("{sign}{}", compact_eth(&row.value_wei)))
+                    } else {
+                        Cell::from(row.value_display.as_str())
                     }
-                }
+                    .style(value_style);
+                    let block_cell = Cell::from(
+                        row.block_number
+                            .map(|n| n.to_string())
+                            .unwrap_or_else(|| "?".into()),
+                    );
+                    // Rows that just landed in follow mode flash, then fade out.
+                    let level = ctx.state.follow.highlight_level(&row.hash);
+                    let row_style = if level > 0.5 {
+                        Style::default().bg(Color::Yellow).fg(Color::Black)
+                    } else if level > 0.0 {
+                        Style::default().bg(Color::DarkGray)
+                    } else {
+                        Style::default()
+                    };
+                    let cells = if compact {
+                        vec![
+                            status_cell,
+                            hash_cell,
+                            method_cell,
+                            counterparty_cell,
+                            value_cell,
+                        ]
+                    } else {
+                        vec![
+                            status_cell,
+                            hash_cell,
+                            direction_cell,
+                            method_cell,
+                            counterparty_cell,
+                            value_cell,
+                            block_cell,
+                        ]
+                    };
+                    Row::new(cells).style(row_style)
+                })
+                .collect();
+
+            let header = if compact {
+                Row::new(vec![
+                    "Status",
+                    "Tx Hash",
+                    "Method",
+                    "Counterparty",
+                    chains::currency(&addr.chain),
+                ])
+            } else {
+                Row::new(vec![
+                    "Status",
+                    "Tx Hash",
+                    "Direction",
+                    "Method",
+                    "Counterparty",
+                    "Value",
+                    "Block",
+                ])
             }
+            .style(Style::default().add_modifier(Modifier::BOLD));
+
+            let mut state = TableState::default();
+            state.select(Some(selected - window.start));
+
+            let widths = if compact {
+                vec![
+                    Constraint::Length(7),
+                    Constraint::Length(13),
+                    Constraint::Length(10),
+                    Constraint::Fill(1),
+                    Constraint::Length(10),
+                ]
+            } else {
+                vec![
+                    Constraint::Length(7),
+                    Constraint::Length(14),
+                    Constraint::Length(11),
+                    Constraint::Length(14),
+                    Constraint::Fill(1),
+                    Constraint::Length(15),
+                    Constraint::Length(8),
+                ]
+            };
+
+            let table_widget = Table::new(rows, widths)
+                .header(header)
+                .column_spacing(1)
+                .highlight_symbol("▸ ")
+                .row_highlight_style(
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                );
+
+            frame.render_stateful_widget(table_widget, content_chunks[2], &mut state);
+            return;
+        }
+
+        // Warnings (e.g. an RPC serving the wrong chain) must stand out from the gray body.
+        let lines: Vec<Line> = summary_content
+            .lines()
+            .map(|line| {
+                if line.starts_with('⚠') {
+                    Line::from(line.to_string())
+                        .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+                } else {
+                    Line::from(line.to_string())
+                }
+            })
+            .collect();
+        let mut lines = lines;
+        if mode == MainViewMode::Address
+            && matches!(tab, MainViewTab::AddressInfo)
+            && !ctx.state.loading.main_view.is_loading
+            && let Some(data) = address_data
+        {
+            lines.extend(Self::info_link_lines(data, ctx.state.info_link_index));
         }
+        if matches!(
+            tab,
+            MainViewTab::AddressInfo | MainViewTab::TransactionSummary | MainViewTab::Custom(_)
+        ) && let Some(entity) = ctx.state.selected.as_ref()
+        {
+            let subject = custom_tabs::subject(entity);
+            let title = custom_tab.map(|custom| custom.title.as_str());
+            lines.extend(Self::plugin_panel_lines(
+                &ctx.state.plugins,
+                &subject,
+                title,
+            ));
+        }
+        let line_mode = ctx.state.line_modes.mode(tab);
+        let body = match line_mode {
+            LineMode::Truncate => {
+                let width = content.width as usize;
+                let lines: Vec<Line> = lines
+                    .into_iter()
+                    .map(|line| truncate_line(line, width))
+                    .collect();
+                Paragraph::new(lines)
+            }
+            LineMode::Wrap => Paragraph::new(lines).wrap(Wrap { trim: false }),
+            LineMode::Scroll => {
+                let offset = ctx.state.line_modes.offset(tab).min(u16::MAX as usize) as u16;
+                Paragraph::new(lines).scroll((0, offset))
+            }
+        };
+        frame.render_widget(body.style(Style::default().fg(Color::Gray)), content);
 
-        let body = Paragraph::new(summary_content).style(Style::default().fg(Color::Gray));
-        frame.render_widget(body, layout[1]);
+        let indicator = match line_mode {
+            LineMode::Scroll => format!(
+                " w {} +{} ",
+                line_mode.label(),
+                ctx.state.line_modes.offset(tab)
+            ),
+            _ => format!(" w {} ", line_mode.label()),
+        };
+        let x = area
+            .right()
+            .saturating_sub(indicator.chars().count() as u16 + 1)
+            .max(area.x);
+        frame.buffer_mut().set_string(
+            x,
+            area.bottom().saturating_sub(1),
+            indicator,
+            Style::default().fg(Color::DarkGray),
+        );
     }
 
-    fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
-        Ok(None)
+    /// The mode's built-in tabs followed by its custom ones.
+    fn tab_titles(mode: MainViewMode, custom: &CustomTabState) -> Vec<(&str, MainViewTab)> {
+        let builtin: &[(&str, MainViewTab)] = match mode {
+            MainViewMode::Address => &[
+                ("Info", MainViewTab::AddressInfo),
+                ("Transactions", MainViewTab::AddressTransactions),
+                ("Internal", MainViewTab::AddressInternal),
+                ("Events", MainViewTab::AddressEvents),
+                ("Storage", MainViewTab::AddressStorage),
+                ("Balances", MainViewTab::AddressBalances),
+                ("Permissions", MainViewTab::AddressPermissions),
+                ("Chains", MainViewTab::AddressChains),
+            ],
+            MainViewMode::Transaction => &[
+                ("Summary", MainViewTab::TransactionSummary),
+                ("Fees", MainViewTab::TransactionFees),
+                ("Balance Diff", MainViewTab::TransactionBalanceDiff),
+                ("Access List", MainViewTab::TransactionAccessList),
+                ("Debug", MainViewTab::TransactionDebug),
+                ("Storage Diff", MainViewTab::TransactionStorageDiff),
+                ("Raw", MainViewTab::TransactionRaw),
+            ],
+        };
+        let mut tabs = builtin.to_vec();
+        tabs.extend(custom.for_mode(mode).into_iter().map(|index| {
+            (
+                custom.tabs[index].title.as_str(),
+                MainViewTab::Custom(index),
+            )
+        }));
+        tabs
+    }
+
+    fn tab_index(tabs: &[(&str, MainViewTab)], tab: MainViewTab) -> usize {
+        tabs.iter().position(|(_, t)| *t == tab).unwrap_or(0)
+    }
+
+    /// A custom tab's script output for the selection, or where it stands.
+    fn custom_tab_text(tab: &CustomTab, ctx: &AppView<'_>) -> String {
+        let Some(entity) = ctx.state.selected.as_ref() else {
+            return String::new();
+        };
+        if tab.command.is_empty() {
+            let subject = custom_tabs::subject(entity);
+            return match ctx
+                .state
+                .plugins
+                .panels_for(&subject, Some(&tab.title))
+                .next()
+            {
+                Some(_) => String::new(),
+                None => format!(
+                    "Nothing from plugins yet • they fill this tab with show_panel and \"tab\": \"{}\"",
+                    tab.title
+                ),
+            };
+        }
+        match ctx
+            .state
+            .custom_tabs
+            .content(tab, &custom_tabs::subject(entity))
+        {
+            None | Some(TabContent::Loading) => format!("Running {}…", tab.command),
+            Some(TabContent::Ready(lines)) if lines.is_empty() => {
+                format!("{} printed nothing", tab.command)
+            }
+            Some(TabContent::Ready(lines)) => lines.join("\n"),
+            Some(TabContent::Failed(err)) => format!("⚠ {err}"),
+        }
+    }
+
+    fn content_for(tab: MainViewTab) -> &'static str {
+        match tab {
+            MainViewTab::AddressTransactions => "Address transactions overview (placeholder)",
+            MainViewTab::AddressInternal => "Address internal calls (placeholder)",
+            MainViewTab::AddressEvents => "Contract event logs",
+            MainViewTab::AddressStorage => "Pinned storage slots",
+            MainViewTab::AddressBalances => "Address balances summary (placeholder)",
+            MainViewTab::AddressPermissions => "Address permissions matrix (placeholder)",
+            MainViewTab::AddressInfo => "Address overview (placeholder)",
+            MainViewTab::AddressChains => "The address on every configured chain",
+            MainViewTab::TransactionSummary => "Transaction summary (placeholder)",
+            MainViewTab::TransactionFees => "Gas fee breakdown",
+            MainViewTab::TransactionBalanceDiff => "Balance changes",
+            MainViewTab::TransactionAccessList => "EIP-2930 access list",
+            MainViewTab::TransactionDebug => "Transaction debugger (placeholder)",
+            MainViewTab::TransactionStorageDiff => "Transaction storage diff (placeholder)",
+            MainViewTab::TransactionRaw => "Signed RLP encoding",
+            MainViewTab::Custom(_) => "Custom tab",
+        }
+    }
+
+    fn permissions_text(data: &HydratedAddress, ctx: &AppView<'_>) -> String {
+        let mut lines = data.permissions.clone();
+        if let Some(SelectedEntity::Address(addr)) = ctx.state.selected.as_ref() {
+            lines.push(String::new());
+            lines.extend(Self::upgrade_lines(
+                &ctx.state.upgrades,
+                &addr.chain,
+                &ctx.state.labels,
+            ));
+        }
+        lines.join("\n")
+    }
+
+    fn upgrade_lines(view: &UpgradesView, chain: &str, labels: &LabelRegistry) -> Vec<String> {
+        let mut lines = vec!["Upgrade history (EIP-1967)".to_string()];
+        if view.loading {
+            lines.push("  Scanning Upgraded events…".into());
+            return lines;
+        }
+        if let Some(error) = view.error.as_ref() {
+            lines.push(format!("  {error}"));
+            return lines;
+        }
+        let Some(history) = view.history.as_ref() else {
+            lines.push("  Not a contract".into());
+            return lines;
+        };
+        let Some(implementation) = history.implementation else {
+            lines.push("  Not an EIP-1967 proxy (implementation slot is empty)".into());
+            return lines;
+        };
+        let name = |address: &Address| labels.display(chain, &address.to_checksum(None));
+        lines.push(format!(
+            "  Current implementation: {}",
+            name(&implementation)
+        ));
+        let range = format!("blocks {}–{}", history.from_block, history.to_block);
+        if history.upgrades.is_empty() {
+            lines.push(format!("  No Upgraded events in {range}"));
+        } else {
+            lines.push(format!(
+                "  {} upgrade(s) in {range}, newest first:",
+                history.upgrades.len()
+            ));
+        }
+        lines.extend(history.upgrades.iter().map(|upgrade| {
+            format!(
+                "  block {:<10} → {}  tx {}",
+                upgrade.block_number,
+                name(&upgrade.implementation),
+                upgrade
+                    .transaction_hash
+                    .as_deref()
+                    .map(short_hex)
+                    .unwrap_or_else(|| "?".into())
+            )
+        }));
+        if history.truncated {
+            lines.push(format!(
+                "  Older blocks (before {}) were not scanned",
+                history.from_block
+            ));
+        }
+        lines
+    }
+
+    fn transaction_summary_text(
+        data: &HydratedTransaction,
+        transfers: &TransfersView,
+        chain: &str,
+        labels: &LabelRegistry,
+        signatures: &SignatureBook,
+    ) -> String {
+        let status = data
+            .status
+            .map(TransactionStatus::label)
+            .unwrap_or("Not cached");
+        let from = data
+            .from
+            .as_ref()
+            .map(|addr| labels.display(chain, addr))
+            .unwrap_or_else(|| "Not cached".into());
+        let to = match (data.to.as_ref(), data.status) {
+            (Some(addr), _) => labels.display(chain, addr),
+            (None, Some(_)) => "Contract creation".into(),
+            (None, None) => "Not cached".into(),
+        };
+        let value = data
+            .value_formatted
+            .clone()
+            .unwrap_or_else(|| "Not cached".into());
+        let block = data
+            .block_number
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| "Not cached".into());
+        let calldata_raw = data.calldata.clone();
+        let calldata_display = calldata_raw
+            .as_ref()
+            .map(|value| {
+                if value.len() > 66 {
+                    format!("{}…", &value[..66])
+                } else {
+                    value.clone()
+                }
+            })
+            .unwrap_or_else(|| "Not cached".into());
+
+        let mut lines = Vec::new();
+        lines.push(format!("Hash: {}", short_hex(&data.identifier)));
+        lines.push(format!("Status: {status}"));
+        lines.push(format!("From: {from}"));
+        lines.push(format!("To: {to}"));
+        lines.push(format!("Value: {value}"));
+        lines.push(format!("Block: {block}"));
+        if let Some(context) = data.block_context.as_ref() {
+            lines.push(format!(
+                "Position: {} of {} in block {} • {}",
+                context.index + 1,
+                context.hashes.len(),
+                context.block_number,
+                format_timestamp(context.timestamp)
+            ));
+        }
+        lines.push(format!("Calldata: {calldata_display}"));
+        if let Some(method) = calldata_raw
+            .as_deref()
+            .and_then(|calldata| signatures.method(calldata))
+        {
+            lines.push(format!("Method: {method}"));
+        }
+        if let Some(blobs) = data.blobs.as_ref() {
+            lines.extend(Self::blob_lines(blobs, chains::currency(chain)));
+        }
+        if let Some(context) = data.l2.as_ref() {
+            lines.extend(Self::l2_lines(context, data.from.as_deref(), chain, labels));
+        }
+        if let Some(history) = data.nonce_history.as_ref() {
+            lines.extend(Self::nonce_lines(&data.identifier, history));
+        }
+        lines.extend(Self::transfer_lines(transfers, chain, labels));
+
+        lines.join("\n")
+    }
+
+    fn access_entry_lines(entries: &AccessEntries) -> Vec<String> {
+        if entries.is_empty() {
+            return vec!["  (empty)".into()];
+        }
+        let mut lines = Vec::new();
+        for (address, keys) in entries {
+            lines.push(format!("  {address} • {} slot(s)", keys.len()));
+            lines.extend(keys.iter().map(|key| format!("    {key:#x}")));
+        }
+        lines
+    }
+
+    fn access_list_text(view: &AccessListView) -> String {
+        let mut lines = vec!["Declared access list".to_string()];
+        match (&view.declared, view.loading) {
+            (_, true) => lines.push("  Loading transaction…".into()),
+            (Some(Ok(declared)), _) => match declared.entries.as_ref() {
+                Some(entries) => lines.extend(Self::access_entry_lines(entries)),
+                None => lines.push(format!(
+                    "  None: type {} transactions cannot carry an access list",
+                    declared.transaction_type
+                )),
+            },
+            (Some(Err(err)), _) => lines.push(format!("  {err}")),
+            (None, _) => lines.push("  No data yet".into()),
+        }
+
+        lines.push(String::new());
+        lines.push("Generated with eth_createAccessList".to_string());
+        match (&view.generated, view.generating) {
+            (_, true) => lines.push("  Replaying the call…".into()),
+            (Some(Ok(generated)), _) => {
+                lines.push(format!(
+                    "  Replayed at block {} (parent of the inclusion block)",
+                    generated
+                        .block
+                        .as_u64()
+                        .map(|n| n.to_string())
+                        .unwrap_or_else(|| "latest".into())
+                ));
+                lines.extend(Self::access_entry_lines(&generated.entries));
+                lines.push(format!("  Gas with list: {}", generated.gas_with));
+                match &generated.gas_without {
+                    Ok(gas) => lines.push(format!("  Gas without list: {gas}")),
+                    Err(err) => lines.push(format!("  Gas without list: {err}")),
+                }
+                match generated.savings() {
+                    Some(saved) if saved > 0 => lines.push(format!("  The list saves {saved} gas")),
+                    Some(saved) => lines.push(format!(
+                        "  The list costs {} more gas than it saves",
+                        -saved
+                    )),
+                    None => {}
+                }
+            }
+            (Some(Err(err)), _) => lines.push(format!("  {err}")),
+            (None, _) => {
+                lines.push("  Press [a] to replay this transaction's call and compare gas".into())
+            }
+        }
+        lines.join("\n")
+    }
+
+    fn raw_text(view: &RawView) -> String {
+        let raw = match (&view.result, view.loading) {
+            (_, true) => return "Loading signed transaction…".into(),
+            (Some(Ok(raw)), _) => raw,
+            (Some(Err(err)), _) => return err.clone(),
+            (None, _) => return "No data yet".into(),
+        };
+        let mut lines = vec![match raw.source {
+            RawSource::Node => {
+                format!("{} bytes from eth_getRawTransactionByHash", raw.bytes.len())
+            }
+            RawSource::Reencoded => format!(
+                "{} bytes re-encoded from the RPC transaction (node lacks eth_getRawTransactionByHash)",
+                raw.bytes.len()
+            ),
+        }];
+        lines.push(String::new());
+        let width = raw
+            .fields
+            .iter()
+            .map(|(name, _)| name.len())
+            .max()
+            .unwrap_or(0);
+        lines.extend(
+            raw.fields
+                .iter()
+                .map(|(name, value)| format!("{name:<width$}  {value}")),
+        );
+        lines.push(String::new());
+        match (&raw.signer, raw.signer_matches()) {
+            (Ok(signer), Some(true)) => {
+                lines.push(format!("Recovered signer: {signer} ✓ matches from"))
+            }
+            (Ok(signer), Some(false)) => lines.push(format!(
+                "Recovered signer: {signer} ✗ RPC reports from {}",
+                raw.reported_from
+                    .map(|from| from.to_string())
+                    .unwrap_or_default()
+            )),
+            (Ok(signer), None) => lines.push(format!("Recovered signer: {signer}")),
+            (Err(err), _) => lines.push(err.clone()),
+        }
+        lines.push(String::new());
+        lines.push(format!("0x{}", alloy::primitives::hex::encode(&raw.bytes)));
+        lines.join("\n")
+    }
+
+    fn fees_text(view: &FeesView, currency: &str) -> String {
+        let fees = match (&view.result, view.loading) {
+            (_, true) => return "Loading receipt and block…".into(),
+            (Some(Ok(fees)), _) => fees,
+            (Some(Err(err)), _) => return err.clone(),
+            (None, _) => return "No data yet".into(),
+        };
+        let gwei = |wei: u128| {
+            format_units(U256::from(wei), "gwei")
+                .map(|value| format!("{value} gwei"))
+                .unwrap_or_else(|_| format!("{wei} wei"))
+        };
+        let eth = |wei: U256| {
+            let mut text = format!(
+                "{} {currency}",
+                format_units(wei, "ether").unwrap_or_else(|_| wei.to_string())
+            );
+            if let Some(usd) = fees.usd(wei) {
+                text.push_str(&format!(" (${usd:.2})"));
+            }
+            text
+        };
+
+        let mut lines = vec![
+            format!(
+                "Type {} transaction in block {}",
+                fees.transaction_type, fees.block_number
+            ),
+            format!(
+                "Gas used: {} of {} limit ({}%)",
+                fees.gas_used,
+                fees.gas_limit,
+                fees.gas_used * 100 / fees.gas_limit.max(1)
+            ),
+            String::new(),
+        ];
+        match fees.max_priority_fee_per_gas {
+            Some(priority) => {
+                lines.push(format!("Max fee: {}", gwei(fees.max_fee_per_gas)));
+                lines.push(format!("Max priority fee: {}", gwei(priority)));
+            }
+            None => lines.push(format!("Gas price: {}", gwei(fees.max_fee_per_gas))),
+        }
+        lines.push(format!(
+            "Effective gas price: {}",
+            gwei(fees.effective_gas_price)
+        ));
+        match fees.base_fee_per_gas {
+            Some(base) => {
+                lines.push(format!("Base fee at inclusion: {}", gwei(base.into())));
+                lines.push(format!("Priority fee paid: {}", gwei(fees.tip_per_gas())));
+            }
+            None => lines.push("Base fee at inclusion: none (pre-London block)".into()),
+        }
+        lines.push(String::new());
+        lines.push(format!("Total fee: {}", eth(fees.total_fee_wei())));
+        if let Some(burned) = fees.burned_wei() {
+            lines.push(format!("  Burned: {}", eth(burned)));
+            lines.push(format!(
+                "  Tip: {}",
+                eth(fees.total_fee_wei().saturating_sub(burned))
+            ));
+        }
+        match (fees.overpaid_tip_per_gas(), fees.overpaid_wei()) {
+            (Some(per_gas), Some(total)) => lines.push(format!(
+                "Overpaid priority: {} per gas above the lowest tip in the block ({})",
+                gwei(per_gas),
+                eth(total)
+            )),
+            _ => lines.push("Overpaid priority: no other transactions in the block".into()),
+        }
+        if fees.native_usd.is_none() {
+            lines.push(String::new());
+            lines
+                .push("Fiat values need an Etherscan API key on an Etherscan-backed chain.".into());
+        }
+        lines.join("\n")
+    }
+
+    fn describe_transfer(
+        summary: &TransferSummary,
+        transfer: &Transfer,
+        chain: &str,
+        labels: &LabelRegistry,
+    ) -> String {
+        let token_label = |token: Address| {
+            summary
+                .tokens
+                .get(&token)
+                .and_then(|meta| meta.symbol.clone().or_else(|| meta.name.clone()))
+                .unwrap_or_else(|| short_hex(&token.to_string()))
+        };
+        let amount = match transfer.asset {
+            Asset::Native => format!(
+                "{} {}",
+                format_units(transfer.amount, "ether")
+                    .unwrap_or_else(|_| transfer.amount.to_string()),
+                chains::currency(chain)
+            ),
+            Asset::Erc20(token) => {
+                let decimals = summary.tokens.get(&token).and_then(|meta| meta.decimals);
+                let value = match decimals {
+                    Some(decimals) => format_units(transfer.amount, decimals)
+                        .unwrap_or_else(|_| transfer.amount.to_string()),
+                    None => transfer.amount.to_string(),
+                };
+                format!("{value} {}", token_label(token))
+            }
+            Asset::Erc721 { token, id } => format!("{} #{id}", token_label(token)),
+            Asset::Erc1155 { token, id } => {
+                format!("{} × {} #{id}", transfer.amount, token_label(token))
+            }
+        };
+        format!(
+            "  {} → {} • {amount}",
+            labels.display(chain, &transfer.from.to_string()),
+            labels.display(chain, &transfer.to.to_string())
+        )
+    }
+
+    fn transfer_lines(view: &TransfersView, chain: &str, labels: &LabelRegistry) -> Vec<String> {
+        let mut lines = vec![String::new(), "Transfers".to_string()];
+        match (&view.result, view.loading) {
+            (_, true) => lines.push("  Tracing…".into()),
+            (Some(Ok(summary)), _) => {
+                if summary.transfers.is_empty() {
+                    lines.push(format!(
+                        "  No {} or token movements",
+                        chains::currency(chain)
+                    ));
+                }
+                lines.extend(
+                    summary
+                        .transfers
+                        .iter()
+                        .map(|transfer| Self::describe_transfer(summary, transfer, chain, labels)),
+                );
+                if let Some(note) = summary.trace_note.as_ref() {
+                    lines.push(format!("  {note}"));
+                }
+            }
+            (Some(Err(err)), _) => lines.push(format!("  {err}")),
+            (None, _) => lines.push("  No data yet".into()),
+        }
+        lines
+    }
+
+    fn nonce_lines(hash: &str, history: &NonceHistory) -> Vec<String> {
+        let mut lines = vec![format!("Nonce: {}", history.nonce)];
+        if hash.parse().is_ok_and(|hash| history.replaced(hash)) {
+            lines.push("Replaced: another transaction was mined at this nonce".into());
+        }
+        if !history.siblings.is_empty() {
+            lines.push(String::new());
+            lines.push(format!(
+                "Same sender and nonce ({} other)",
+                history.siblings.len()
+            ));
+        }
+        for sibling in &history.siblings {
+            lines.push(format!(
+                "  {} {} • max fee {} wei{}",
+                short_hex(&format!("{:#x}", sibling.hash)),
+                sibling.kind.label(),
+                sibling.max_fee_per_gas,
+                if sibling.mined {
+                    " • mined"
+                } else {
+                    " • pending"
+                }
+            ));
+        }
+        lines
+    }
+
+    fn l2_lines(
+        context: &L2Context,
+        from: Option<&str>,
+        chain: &str,
+        labels: &LabelRegistry,
+    ) -> Vec<String> {
+        let address = |address: &Address| labels.display(chain, &address.to_checksum(None));
+        let mut lines = vec![
+            String::new(),
+            format!("Rollup ({})", context.rollup.label()),
+            format!("Origin: {}", context.origin.label()),
+        ];
+        match &context.origin {
+            Origin::L2 | Origin::ArbitrumSystem { .. } => {}
+            Origin::OpDeposit {
+                source_hash, mint, ..
+            } => {
+                lines.push(format!("Source hash: {source_hash:#x}"));
+                lines.push(format!("Minted on L2: {}", format_eth_value(mint)));
+                if let Some(sender) = from.and_then(|from| from.parse::<Address>().ok()) {
+                    lines.push(format!(
+                        "L1 sender if a contract (unaliased): {}",
+                        address(&l2::unalias(sender))
+                    ));
+                }
+            }
+            Origin::ArbitrumDeposit { request_id } => {
+                if let Some(id) = request_id {
+                    lines.push(format!("Request id: {id:#x}"));
+                }
+            }
+            Origin::RetryableSubmission {
+                request_id,
+                retry_to,
+                beneficiary,
+                deposit_value,
+                max_submission_fee,
+                ticket,
+            } => {
+                if let Some(id) = request_id {
+                    lines.push(format!("Request id: {id:#x}"));
+                }
+                lines.push("Ticket id: this transaction's hash".into());
+                if let Some(to) = retry_to {
+                    lines.push(format!("Retry target: {}", address(to)));
+                }
+                if let Some(beneficiary) = beneficiary {
+                    lines.push(format!("Beneficiary: {}", address(beneficiary)));
+                }
+                lines.push(format!("Deposit: {}", format_eth_value(deposit_value)));
+                lines.push(format!(
+                    "Max submission fee: {}",
+                    format_eth_value(max_submission_fee)
+                ));
+                lines.push(match ticket {
+                    TicketStatus::Live { timeout } => format!(
+                        "Ticket: not redeemed, expires {}",
+                        format_timestamp(*timeout)
+                    ),
+                    TicketStatus::Gone => "Ticket: redeemed or expired".into(),
+                    TicketStatus::Unknown(err) => format!("Ticket: status unknown ({err})"),
+                });
+            }
+            Origin::RetryableRedeem { ticket_id } => {
+                if let Some(id) = ticket_id {
+                    lines.push(format!("Ticket id: {id:#x}"));
+                }
+            }
+        }
+        if let Some(fee) = context.l1_fee.as_ref() {
+            lines.push(format!("L1 data fee: {}", format_eth_value(fee)));
+        }
+        if let Some(gas) = context.l1_gas_used {
+            lines.push(format!("L1 gas used: {gas}"));
+        }
+        if let Some(block) = context.l1_block {
+            lines.push(format!("L1 block: {block}"));
+        }
+        if !context.withdrawals.is_empty() {
+            lines.push(format!("L2→L1 withdrawals ({})", context.withdrawals.len()));
+        }
+        for withdrawal in &context.withdrawals {
+            let (id, done) = match context.rollup {
+                Rollup::Arbitrum { .. } => (
+                    format!("outbox position {}", withdrawal.id),
+                    "executed on L1",
+                ),
+                Rollup::OpStack { .. } => (
+                    format!("withdrawal hash {:#x}", B256::from(withdrawal.id)),
+                    "finalized on L1",
+                ),
+            };
+            let status = match &withdrawal.status {
+                WithdrawalStatus::Executed => done.to_string(),
+                WithdrawalStatus::Waiting => {
+                    "not yet on L1 (7-day challenge period, then a claim)".into()
+                }
+                WithdrawalStatus::Unknown(err) => format!("L1 status unknown ({err})"),
+            };
+            lines.push(format!(
+                "  {} to {} • {status}",
+                format_eth_value(&withdrawal.value),
+                address(&withdrawal.target)
+            ));
+            lines.push(format!("    {id}"));
+        }
+        lines
+    }
+
+    fn blob_lines(blobs: &BlobDetails, currency: &str) -> Vec<String> {
+        let gwei = |wei: u128| {
+            format_units(U256::from(wei), "gwei")
+                .map(|value| format!("{value} gwei"))
+                .unwrap_or_else(|_| format!("{wei} wei"))
+        };
+        let pending = || "Pending".to_string();
+        let mut lines = vec![
+            String::new(),
+            "Blobs (EIP-4844)".to_string(),
+            format!("Blob count: {}", blobs.versioned_hashes.len()),
+            format!(
+                "Blob gas used: {}",
+                blobs
+                    .blob_gas_used
+                    .map(|used| used.to_string())
+                    .unwrap_or_else(pending)
+            ),
+            format!(
+                "Blob base fee: {}",
+                blobs.blob_gas_price.map(gwei).unwrap_or_else(pending)
+            ),
+        ];
+        if let Some(max) = blobs.max_fee_per_blob_gas {
+            lines.push(format!("Max fee per blob gas: {}", gwei(max)));
+        }
+        if let Some(fee) = blobs.fee_wei() {
+            lines.push(format!(
+                "Blob fee paid: {} {currency}",
+                format_units(fee, "ether").unwrap_or_else(|_| fee.to_string())
+            ));
+        }
+        lines.push("Versioned hashes:".to_string());
+        lines.extend(
+            blobs
+                .versioned_hashes
+                .iter()
+                .map(|hash| format!("  {hash:#x}")),
+        );
+        lines
+    }
+
+    /// What plugins showed for `subject` on the custom `tab`, or on Info and
+    /// Summary, each under its title.
+    fn plugin_panel_lines(
+        plugins: &PluginState,
+        subject: &str,
+        tab: Option<&str>,
+    ) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        for panel in plugins.panels_for(subject, tab) {
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                Span::styled(
+                    panel.title.clone(),
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!(" · plugin {}", panel.plugin),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+            lines.extend(
+                panel
+                    .lines
+                    .iter()
+                    .map(|line| Line::from(format!("  {line}"))),
+            );
+        }
+        lines
+    }
+
+    /// Delegation and deployment rows; the highlighted one is what `Enter` opens.
+    fn info_link_lines(data: &HydratedAddress, selected: usize) -> Vec<Line<'static>> {
+        let links = data.info_links();
+        let selected = links.get(selected.min(links.len().saturating_sub(1)));
+        let row = |link: InfoLink, text: String| {
+            if selected == Some(&link) {
+                Line::from(format!("▸ {text}")).style(
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                Line::from(format!("  {text}"))
+            }
+        };
+        let heading = |text: &'static str| {
+            Line::from(Span::styled(
+                text,
+                Style::default().add_modifier(Modifier::BOLD),
+            ))
+        };
+
+        let mut lines = Vec::new();
+        if let Some(delegate) = data
+            .overview
+            .as_ref()
+            .and_then(|overview| overview.delegate)
+        {
+            lines.push(Line::from(""));
+            lines.push(heading("EIP-7702 delegation"));
+            lines.push(row(InfoLink::Delegate, format!("Delegates to: {delegate}")));
+        }
+        if let Some(creation) = data.creation.as_ref() {
+            let when = match (creation.block_number, creation.timestamp) {
+                (Some(block), Some(timestamp)) => {
+                    format!(" • block {block} • {}", format_timestamp(timestamp))
+                }
+                (Some(block), None) => format!(" • block {block}"),
+                _ => String::new(),
+            };
+            lines.push(Line::from(""));
+            lines.push(heading("Deployment"));
+            lines.push(row(
+                InfoLink::Deployer,
+                format!("Deployer: {}", creation.creator),
+            ));
+            lines.push(row(
+                InfoLink::CreationTransaction,
+                format!("Creation tx: {}{when}", creation.transaction_hash),
+            ));
+        }
+        lines
+    }
+
+    fn render_storage(
+        frame: &mut Frame<'_>,
+        area: Rect,
+        selection_text: &str,
+        address: &str,
+        ctx: &AppView<'_>,
+    ) {
+        let watch = &ctx.state.slots;
+        let pins = watch.pins_for(address);
+        let mut header = selection_text.to_string();
+        if let Some(text) = watch.editing.as_ref() {
+            header.push_str(&format!("\nPin slot › {text}_  (Enter pin • Esc cancel)"));
+        }
+        header.push_str(&format!(
+            "\n{} pinned slot(s) • re-read every {}s, changes kept this session",
+            pins.len(),
+            slots::POLL_INTERVAL.as_secs()
+        ));
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(header.lines().count() as u16),
+                Constraint::Length(pins.len().min(8) as u16 + 1),
+                Constraint::Min(1),
+            ])
+            .split(area);
+        frame.render_widget(
+            Paragraph::new(header).style(Style::default().fg(Color::Gray)),
+            chunks[0],
+        );
+        if pins.is_empty() {
+            return;
+        }
+
+        let table_rows: Vec<Row<'_>> = pins
+            .iter()
+            .map(|pin| {
+                let history = watch.history(pin);
+                let (value, style) = match (history.and_then(|h| h.back()), watch.error(pin)) {
+                    (_, Some(error)) => (error.to_string(), Style::default().fg(Color::Red)),
+                    (Some(sample), None) => (pin.describe(&sample.value), Style::default()),
+                    (None, None) => ("Reading…".to_string(), Style::default().fg(Color::DarkGray)),
+                };
+                let changes = history.map_or(0, |h| h.len().saturating_sub(1));
+                Row::new(vec![
+                    Cell::from(format!("{}{}", pin.label, pin.alerts.marker()))
+                        .style(Style::default().fg(Color::Yellow)),
+                    Cell::from(short_hex(&format!("{:#x}", pin.slot))),
+                    Cell::from(value).style(style),
+                    Cell::from(changes.to_string()),
+                ])
+            })
+            .collect();
+        let selected = watch.selected_index.min(pins.len() - 1);
+        let mut state = TableState::default();
+        state.select(Some(selected));
+        let table = Table::new(
+            table_rows,
+            [
+                Constraint::Length(24),
+                Constraint::Length(14),
+                Constraint::Fill(1),
+                Constraint::Length(7),
+            ],
+        )
+        .header(
+            Row::new(vec!["Label", "Slot", "Value", "Changes"])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+        )
+        .column_spacing(1)
+        .highlight_symbol("▸ ")
+        .row_highlight_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        );
+        frame.render_stateful_widget(table, chunks[1], &mut state);
+
+        let pin = pins[selected];
+        let mut lines = vec![Line::from(Span::styled(
+            format!("History of {} ({:#x})", pin.label, pin.slot),
+            Style::default().add_modifier(Modifier::BOLD),
+        ))];
+        match watch.history(pin) {
+            Some(history) => lines.extend(history.iter().rev().map(|sample| {
+                Line::from(format!(
+                    "  block {:<10} {}",
+                    sample.block,
+                    pin.describe(&sample.value)
+                ))
+            })),
+            None => lines.push(Line::from("  No readings yet")),
+        }
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[2]);
+    }
+
+    fn asset_label(diff: &BalanceDiff, asset: Asset, currency: &str) -> String {
+        let token_label = |token: Address| {
+            diff.tokens
+                .get(&token)
+                .and_then(|meta| meta.symbol.clone().or_else(|| meta.name.clone()))
+                .unwrap_or_else(|| short_hex(&token.to_string()))
+        };
+        match asset {
+            Asset::Native => currency.into(),
+            Asset::Erc20(token) => token_label(token),
+            Asset::Erc721 { token, id } | Asset::Erc1155 { token, id } => {
+                format!("{} #{id}", short_hex(&token.to_string()))
+            }
+        }
+    }
+
+    fn format_delta(diff: &BalanceDiff, asset: Asset, delta: I256) -> String {
+        let decimals = match asset {
+            Asset::Native => Some(18),
+            Asset::Erc20(token) => diff.tokens.get(&token).and_then(|meta| meta.decimals),
+            _ => None,
+        };
+        let magnitude = delta.unsigned_abs();
+        let amount = decimals
+            .and_then(|decimals| format_units(magnitude, decimals).ok())
+            .unwrap_or_else(|| magnitude.to_string());
+        let sign = if delta.is_negative() { "-" } else { "+" };
+        format!("{sign}{amount}")
+    }
+
+    fn render_balance_diff(
+        frame: &mut Frame<'_>,
+        area: Rect,
+        selection_text: &str,
+        ctx: &AppView<'_>,
+    ) {
+        let view = &ctx.state.balance_diff;
+        let chain = match ctx.state.selected.as_ref() {
+            Some(SelectedEntity::Transaction(tx)) => tx.chain.as_str(),
+            _ => "",
+        };
+        let currency = chains::currency(chain);
+        let mut header = selection_text.to_string();
+        let diff = match (&view.result, view.loading) {
+            (_, true) => {
+                header.push_str("\n\nReplaying with the prestate tracer…");
+                None
+            }
+            (Some(Ok(diff)), _) => {
+                header.push_str(&format!(
+                    "\n\n{} balance change(s) • sender's {currency} includes the gas fee",
+                    diff.deltas.len()
+                ));
+                if let Some(note) = diff.note.as_ref() {
+                    header.push_str(&format!("\n{note}"));
+                }
+                Some(diff)
+            }
+            (Some(Err(err)), _) => {
+                header.push_str(&format!("\n\n{err}"));
+                None
+            }
+            (None, _) => {
+                header.push_str("\n\nNo data yet");
+                None
+            }
+        };
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(header.lines().count() as u16),
+                Constraint::Min(1),
+            ])
+            .split(area);
+        frame.render_widget(
+            Paragraph::new(header).style(Style::default().fg(Color::Gray)),
+            chunks[0],
+        );
+        let Some(diff) = diff.filter(|diff| !diff.deltas.is_empty()) else {
+            return;
+        };
+
+        let mut previous = None;
+        let rows: Vec<Row<'_>> = diff
+            .deltas
+            .iter()
+            .map(|delta| {
+                // Show each account once; its assets follow on the next rows.
+                let account = if previous == Some(delta.account) {
+                    String::new()
+                } else {
+                    ctx.state.labels.display(chain, &delta.account.to_string())
+                };
+                previous = Some(delta.account);
+                let color = if delta.delta.is_negative() {
+                    Color::Red
+                } else {
+                    Color::Green
+                };
+                Row::new(vec![
+                    Cell::from(account).style(Style::default().fg(Color::Yellow)),
+                    Cell::from(Self::asset_label(diff, delta.asset, currency)),
+                    Cell::from(Self::format_delta(diff, delta.asset, delta.delta))
+                        .style(Style::default().fg(color)),
+                ])
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(22),
+                Constraint::Length(20),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(
+            Row::new(vec!["Account", "Asset", "Change"])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+        )
+        .column_spacing(1);
+        frame.render_widget(table, chunks[1]);
+    }
+
+    /// Transactions per bucket on the left, net native currency flow on the
+    /// right (green in, red out), both from the fetched history.
+    fn render_activity(frame: &mut Frame<'_>, area: Rect, activity: &Activity, currency: &str) {
+        let halves = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(area);
+        let per = activity.size.label();
+        let total: u64 = activity
+            .buckets
+            .iter()
+            .map(|bucket| bucket.transactions)
+            .sum();
+        let mut count_title = format!("{total} tx(s) per {per}");
+        if activity.undated > 0 {
+            count_title.push_str(&format!(" • {} undated", activity.undated));
+        }
+        let counts: Vec<u64> = activity
+            .buckets
+            .iter()
+            .map(|bucket| bucket.transactions)
+            .collect();
+        frame.render_widget(
+            Sparkline::default()
+                .block(Block::default().title(count_title))
+                .data(&counts)
+                .style(Style::default().fg(Color::Cyan)),
+            halves[0],
+        );
+
+        let net: I256 = activity.buckets.iter().map(|bucket| bucket.net_wei).sum();
+        let sign = if net.is_negative() { "-" } else { "+" };
+        let flow_title = format!(
+            "Net {currency} per {per} • {sign}{}",
+            format_native_value(&net.unsigned_abs(), currency)
+        );
+        let bars: Vec<Bar<'_>> = activity
+            .buckets
+            .iter()
+            .map(|bucket| {
+                // Scaled to gwei so the bar heights fit in a u64.
+                let gwei = bucket.net_wei.unsigned_abs() / U256::from(1_000_000_000u64);
+                let color = if bucket.net_wei.is_negative() {
+                    Color::Red
+                } else {
+                    Color::Green
+                };
+                Bar::default()
+                    .value(gwei.saturating_to())
+                    .text_value(String::new())
+                    .style(Style::default().fg(color))
+            })
+            .collect();
+        frame.render_widget(
+            BarChart::default()
+                .block(Block::default().title(flow_title))
+                .bar_width(1)
+                .bar_gap(0)
+                .data(BarGroup::default().bars(&bars)),
+            halves[1],
+        );
+    }
+
+    fn render_chains(frame: &mut Frame<'_>, area: Rect, selection_text: &str, ctx: &AppView<'_>) {
+        let view = &ctx.state.multichain;
+        let loading = view.rows.iter().filter(|row| row.result.is_none()).count();
+        let status = if view.rows.is_empty() {
+            "No chain has its own RPC endpoint; add one in the settings modal.".to_string()
+        } else if loading > 0 {
+            format!("Loading {loading} of {} chain(s)…", view.rows.len())
+        } else {
+            let used = view
+                .rows
+                .iter()
+                .filter(|row| matches!(&row.result, Some(Ok(summary)) if !summary.is_unused()))
+                .count();
+            format!("Used on {used} of {} chain(s)", view.rows.len())
+        };
+        let header = format!("{selection_text}\n{status}");
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(header.lines().count() as u16),
+                Constraint::Min(1),
+            ])
+            .split(area);
+        frame.render_widget(
+            Paragraph::new(header).style(Style::default().fg(Color::Gray)),
+            chunks[0],
+        );
+        if view.rows.is_empty() {
+            return;
+        }
+
+        let rows: Vec<Row<'_>> = view
+            .rows
+            .iter()
+            .map(|row| {
+                let chain =
+                    Cell::from(row.chain.as_str()).style(Style::default().fg(Color::Yellow));
+                match &row.result {
+                    None => Row::new(vec![chain, Cell::from("Loading…")]),
+                    // Errors go in the wide last column.
+                    Some(Err(err)) => Row::new(vec![
+                        chain,
+                        Cell::from("—"),
+                        Cell::from(""),
+                        Cell::from(""),
+                        Cell::from(err.as_str()).style(Style::default().fg(Color::Red)),
+                    ]),
+                    Some(Ok(summary)) => {
+                        let activity = match &summary.last_activity {
+                            Ok(Some(activity)) => format!(
+                                "{} • block {}{}",
+                                short_hex(&activity.hash),
+                                activity.block_number,
+                                activity
+                                    .timestamp
+                                    .map(|ts| format!(" • {}", format_timestamp(ts)))
+                                    .unwrap_or_default()
+                            ),
+                            Ok(None) if summary.is_unused() => String::new(),
+                            Ok(None) => "None found".into(),
+                            Err(err) => format!("Unavailable ({err})"),
+                        };
+                        let style = if summary.is_unused() {
+                            Style::default().fg(Color::DarkGray)
+                        } else {
+                            Style::default()
+                        };
+                        Row::new(vec![
+                            chain,
+                            Cell::from(format_native_value(
+                                &summary.overview.balance_wei,
+                                chains::currency(&row.chain),
+                            )),
+                            Cell::from(summary.overview.transaction_count.to_string()),
+                            Cell::from(summary.kind()),
+                            Cell::from(activity),
+                        ])
+                        .style(style)
+                    }
+                }
+            })
+            .collect();
+        let mut state = TableState::default();
+        state.select(Some(view.selected_index.min(view.rows.len() - 1)));
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(10),
+                Constraint::Length(22),
+                Constraint::Length(7),
+                Constraint::Length(11),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(
+            Row::new(vec!["Chain", "Balance", "Nonce", "Type", "Last activity"])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+        )
+        .column_spacing(1)
+        .highlight_symbol("▸ ")
+        .row_highlight_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        );
+        frame.render_stateful_widget(table, chunks[1], &mut state);
+    }
+
+    fn render_events(
+        &mut self,
+        frame: &mut Frame<'_>,
+        area: Rect,
+        selection_text: &str,
+        ctx: &AppView<'_>,
+    ) {
+        let view = &ctx.state.events;
+        let mut header = selection_text.to_string();
+        match view.editing.as_ref() {
+            Some(text) => {
+                header.push_str(&format!("\nFilter › {text}_  (Enter apply • Esc cancel)"))
+            }
+            None if view.filter.is_empty() => header.push_str("\nFilter: none"),
+            None => header.push_str(&format!("\nFilter: {}", view.filter)),
+        }
+        let status = if view.loading {
+            "Scanning logs…".to_string()
+        } else if let Some(error) = view.error.as_ref() {
+            error.clone()
+        } else if let Some(page) = view.page.as_ref() {
+            format!(
+                "{} event(s) in blocks {}–{} via eth_getLogs • newest first",
+                page.events.len(),
+                page.from_block,
+                page.to_block
+            )
+        } else {
+            "Waiting for address data…".to_string()
+        };
+        header.push('\n');
+        header.push_str(&status);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(header.lines().count() as u16),
+                Constraint::Min(1),
+            ])
+            .split(area);
+        let header_style = if view.error.is_some() {
+            Style::default().fg(Color::Red)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        frame.render_widget(Paragraph::new(header).style(header_style), chunks[0]);
+
+        let rows = view.rows();
+        if rows.is_empty() {
+            return;
+        }
+        let selected = view.selected_index.min(rows.len() - 1);
+        let window = self
+            .scrolls
+            .of(
+                MainViewTab::AddressEvents,
+                view.address.as_deref().unwrap_or_default(),
+            )
+            .window(
+                selected,
+                rows.len(),
+                chunks[1].height.saturating_sub(1) as usize,
+            );
+        let table_rows: Vec<Row<'_>> = rows[window.clone()]
+            .iter()
+            .map(|event| {
+                Row::new(vec![
+                    Cell::from(
+                        event
+                            .block_number
+                            .map(|n| n.to_string())
+                            .unwrap_or_else(|| "?".into()),
+                    ),
+                    Cell::from(
+                        event
+                            .transaction_hash
+                            .as_deref()
+                            .map(short_hex)
+                            .unwrap_or_default(),
+                    ),
+                    Cell::from(
+                        event
+                            .unknown_topic
+                            .as_deref()
+                            .and_then(|topic| ctx.state.signatures.get(SignatureKind::Event, topic))
+                            .map(signatures::method_name)
+                            .unwrap_or(&event.name),
+                    )
+                    .style(Style::default().fg(Color::Yellow)),
+                    Cell::from(event.arguments()),
+                ])
+            })
+            .collect();
+        let mut state = TableState::default();
+        state.select(Some(selected - window.start));
+        let table = Table::new(
+            table_rows,
+            [
+                Constraint::Length(9),
+                Constraint::Length(14),
+                Constraint::Length(18),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(
+            Row::new(vec!["Block", "Tx Hash", "Event", "Arguments"])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+        )
+        .column_spacing(1)
+        .highlight_symbol("▸ ")
+        .row_highlight_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        );
+        frame.render_stateful_widget(table, chunks[1], &mut state);
+    }
+}
+
+impl Component for MainView {
+    type Command = MainViewCommand;
+
+    fn init(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<()> {
+        self.placeholder = "Select a favorite to begin".into();
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        command: &Self::Command,
+        ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        match command {
+            MainViewCommand::SetMode(mode) => {
+                ctx.state.navigation.main_view_mode = *mode;
+                ctx.state.navigation.main_view_tab =
+                    ctx.state.navigation.main_view_tab.normalize(*mode);
+            }
+            MainViewCommand::SwitchTab(tab) => {
+                ctx.state.navigation.main_view_tab =
+                    tab.normalize(ctx.state.navigation.main_view_mode);
+            }
+            MainViewCommand::NextTab => {
+                let custom = ctx
+                    .state
+                    .custom_tabs
+                    .for_mode(ctx.state.navigation.main_view_mode);
+                ctx.state.navigation.next_main_view_tab(&custom);
+            }
+            MainViewCommand::PreviousTab => {
+                let custom = ctx
+                    .state
+                    .custom_tabs
+                    .for_mode(ctx.state.navigation.main_view_mode);
+                ctx.state.navigation.previous_main_view_tab(&custom);
+            }
+            MainViewCommand::MoveSelectionUp => {
+                if ctx.state.navigation.main_view_mode == MainViewMode::Address
+                    && !ctx.state.loading.main_view.is_loading
+                {
+                    let tab = ctx
+                        .state
+                        .navigation
+                        .main_view_tab
+                        .normalize(MainViewMode::Address);
+                    if matches!(tab, MainViewTab::AddressEvents) {
+                        ctx.state.events.move_selection(false);
+                    } else if matches!(tab, MainViewTab::AddressStorage) {
+                        ctx.state.slots.move_selection(false, 0);
+                    } else if matches!(tab, MainViewTab::AddressChains) {
+                        ctx.state.multichain.move_selection(false);
+                    } else if matches!(tab, MainViewTab::AddressInfo) {
+                        ctx.state.info_link_index = ctx.state.info_link_index.saturating_sub(1);
+                    } else if matches!(tab, MainViewTab::AddressTransactions)
+                        && let Some(address) = ctx.state.current_address.as_ref()
+                        && let Some(table) = address.transactions_table.as_ref()
+                    {
+                        ctx.state.address_transactions_view.clamp(table.rows.len());
+                        if !table.rows.is_empty()
+                            && ctx.state.address_transactions_view.selected_index > 0
+                        {
+                            ctx.state.address_transactions_view.selected_index -= 1;
+                        }
+                    }
+                }
+            }
+            MainViewCommand::MoveSelectionDown => {
+                if ctx.state.navigation.main_view_mode == MainViewMode::Address
+                    && !ctx.state.loading.main_view.is_loading
+                {
+                    let tab = ctx
+                        .state
+                        .navigation
+                        .main_view_tab
+                        .normalize(MainViewMode::Address);
+                    if matches!(tab, MainViewTab::AddressEvents) {
+                        ctx.state.events.move_selection(true);
+                    } else if matches!(tab, MainViewTab::AddressChains) {
+                        ctx.state.multichain.move_selection(true);
+                    } else if matches!(tab, MainViewTab::AddressStorage)
+                        && let Some(SelectedEntity::Address(addr)) = ctx.state.selected.as_ref()
+                    {
+                        let len = ctx.state.slots.pins_for(&addr.address).len();
+                        ctx.state.slots.move_selection(true, len);
+                    } else if matches!(tab, MainViewTab::AddressInfo)
+                        && let Some(address) = ctx.state.current_address.as_ref()
+                    {
+                        let last = address.info_links().len().saturating_sub(1);
+                        ctx.state.info_link_index = (ctx.state.info_link_index + 1).min(last);
+                    } else if matches!(tab, MainViewTab::AddressTransactions)
+                        && let Some(address) = ctx.state.current_address.as_ref()
+                        && let Some(table) = address.transactions_table.as_ref()
+                    {
+                        ctx.state.address_transactions_view.clamp(table.rows.len());
+                        if !table.rows.is_empty() {
+                            let last = table.rows.len().saturating_sub(1);
+                            let index = &mut ctx.state.address_transactions_view.selected_index;
+                            if *index < last {
+                                *index += 1;
+                            }
+                        }
+                    }
+                }
+            }
+            MainViewCommand::ActivateSelection => {
+                if ctx.state.navigation.main_view_mode == MainViewMode::Address
+                    && !ctx.state.loading.main_view.is_loading
+                {
+                    let tab = ctx
+                        .state
+                        .navigation
+                        .main_view_tab
+                        .normalize(MainViewMode::Address);
+                    if matches!(tab, MainViewTab::AddressInfo)
+                        && let (Some(SelectedEntity::Address(addr)), Some(address)) = (
+                            ctx.state.selected.as_ref(),
+                            ctx.state.current_address.as_ref(),
+                        )
+                        && address.identifier == addr.address
+                    {
+                        let links = address.info_links();
+                        let link = links
+                            .get(ctx.state.info_link_index)
+                            .or(links.last())
+                            .copied();
+                        let creation = address.creation.as_ref();
+                        let delegate = address
+                            .overview
+                            .as_ref()
+                            .and_then(|overview| overview.delegate);
+                        let entity = match (link, creation, delegate) {
+                            (Some(InfoLink::Delegate), _, Some(delegate)) => {
+                                Some(SelectedEntity::Address(AddressRef {
+                                    label: format!("Delegate {}", short_hex(&delegate.to_string())),
+                                    address: delegate.to_string(),
+                                    chain: addr.chain.clone(),
+                                }))
+                            }
+                            (Some(InfoLink::Deployer), Some(creation), _) => {
+                                Some(SelectedEntity::Address(AddressRef {
+                                    label: format!("Deployer {}", short_hex(&creation.creator)),
+                                    address: creation.creator.clone(),
+                                    chain: addr.chain.clone(),
+                                }))
+                            }
+                            (Some(InfoLink::CreationTransaction), Some(creation), _) => {
+                                Some(SelectedEntity::Transaction(TransactionRef {
+                                    label: short_hex(&creation.transaction_hash),
+                                    hash: creation.transaction_hash.clone(),
+                                    chain: addr.chain.clone(),
+                                }))
+                            }
+                            _ => None,
+                        };
+                        if let Some(entity) = entity {
+                            return Ok(Some(Action::SelectionChanged(entity)));
+                        }
+                    }
+                    if matches!(tab, MainViewTab::AddressChains)
+                        && let Some(SelectedEntity::Address(addr)) = ctx.state.selected.as_ref()
+                        && let Some(row) = ctx.state.multichain.selected()
+                        && row.chain != addr.chain
+                    {
+                        return Ok(Some(Action::SelectionChanged(SelectedEntity::Address(
+                            AddressRef {
+                                label: addr.label.clone(),
+                                address: addr.address.clone(),
+                                chain: row.chain.clone(),
+                            },
+                        ))));
+                    }
+                    if matches!(tab, MainViewTab::AddressEvents)
+                        && let Some(SelectedEntity::Address(addr)) = ctx.state.selected.as_ref()
+                        && let Some(hash) = ctx
+                            .state
+                            .events
+                            .selected()
+                            .and_then(|event| event.transaction_hash.clone())
+                    {
+                        return Ok(Some(Action::SelectionChanged(SelectedEntity::Transaction(
+                            TransactionRef {
+                                label: short_hex(&hash),
+                                hash,
+                                chain: addr.chain.clone(),
+                            },
+                        ))));
+                    }
+                    if matches!(tab, MainViewTab::AddressTransactions)
+                        && let (Some(SelectedEntity::Address(addr)), Some(address)) = (
+                            ctx.state.selected.as_ref(),
+                            ctx.state.current_address.as_ref(),
+                        )
+                        && let Some(table) = address.transactions_table.as_ref()
+                    {
+                        ctx.state.address_transactions_view.clamp(table.rows.len());
+                        if !table.rows.is_empty() {
+                            let index = ctx.state.address_transactions_view.selected_index;
+                            let row = &table.rows[index];
+                            ctx.state.pending_transaction_preview = Some(row.clone());
+                            return Ok(Some(Action::SelectionChanged(
+                                SelectedEntity::Transaction(TransactionRef {
+                                    label: short_hex(&row.hash),
+                                    hash: row.hash.clone(),
+                                    chain: addr.chain.clone(),
+                                }),
+                            )));
+                        }
+                    }
+                }
+            }
+            MainViewCommand::HydrationStarted | MainViewCommand::HydrationFinished => {}
+            MainViewCommand::EnterCopyMode => self.copy = Some(CopyMode::default()),
+            MainViewCommand::CycleLineMode => {
+                let mode = ctx.state.navigation.main_view_mode;
+                let tab = ctx.state.navigation.main_view_tab.normalize(mode);
+                ctx.state.line_modes.cycle(tab);
+            }
+            MainViewCommand::ScrollLeft | MainViewCommand::ScrollRight => {
+                let mode = ctx.state.navigation.main_view_mode;
+                let tab = ctx.state.navigation.main_view_tab.normalize(mode);
+                ctx.state
+                    .line_modes
+                    .scroll(tab, matches!(command, MainViewCommand::ScrollRight));
+            }
+            MainViewCommand::Copy(command) => {
+                let Some(copy) = self.copy.as_mut() else {
+                    return Ok(None);
+                };
+                let yanked = copy.apply(*command);
+                if yanked.is_some() || *command == CopyCommand::Exit {
+                    self.copy = None;
+                }
+                if *command == CopyCommand::Pin {
+                    return Ok(yanked.map(|text| Action::PinNote {
+                        text,
+                        source: Self::pin_source(ctx),
+                    }));
+                }
+                return Ok(yanked.map(Action::CopyToClipboard));
+            }
+            MainViewCommand::PinHighlighted => {
+                return Ok(self.pin_text(ctx).map(|text| Action::PinNote {
+                    text,
+                    source: Self::pin_source(ctx),
+                }));
+            }
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, frame: &mut Frame<'_>, area: Rect, ctx: &AppView<'_>) {
+        self.render_pane(frame, area, ctx);
+        let inner = Block::default().borders(Borders::ALL).inner(area);
+        self.highlighted = highlighted_row(frame.buffer_mut(), inner);
+        if let Some(copy) = self.copy.as_mut() {
+            copy.capture_and_highlight(frame.buffer_mut(), inner);
+            let label = format!(" {} ", copy.status());
+            frame.buffer_mut().set_string(
+                inner.x + 1,
+                area.bottom().saturating_sub(1),
+                label,
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            );
+        }
+    }
+
+    fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        Ok(None)
+    }
+    fn key_hints(&self, ctx: &AppView<'_>) -> Vec<KeyHint> {
+        if self.copy.is_some() {
+            return vec![
+                KeyHint::new("h j k l", "Move"),
+                KeyHint::new("w/b", "Word"),
+                KeyHint::new("0/$", "Line start/end"),
+                KeyHint::new("v", "Select"),
+                KeyHint::new("V", "Select lines"),
+                KeyHint::new("y", "Yank"),
+                KeyHint::new("m", "Pin"),
+                KeyHint::new("/", "Find"),
+                KeyHint::new("n/N", "Next/Prev hit"),
+                KeyHint::new("Esc", "Exit"),
+            ];
+        }
+        let mode = ctx.state.navigation.main_view_mode;
+        let tab = ctx
+            .state
+            .custom_tabs
+            .resolve(ctx.state.navigation.main_view_tab, mode);
+        let mut hints = match tab {
+            MainViewTab::AddressInfo => vec![KeyHint::new("Enter", "Open link")],
+            MainViewTab::AddressTransactions => {
+                let following = match ctx.state.selected.as_ref() {
+                    Some(SelectedEntity::Address(addr)) => {
+                        ctx.state.follow.is_following(&addr.address)
+                    }
+                    _ => false,
+                };
+                vec![
+                    KeyHint::new("Enter", "Open"),
+                    KeyHint::new("L", if following { "Unfollow" } else { "Follow" }),
+                    KeyHint::new("E", "Export CSV"),
+                ]
+            }
+            MainViewTab::AddressEvents => {
+                vec![
+                    KeyHint::new("Enter", "Open"),
+                    KeyHint::new("e", "Filter"),
+                    KeyHint::new("a", "Alert"),
+                ]
+            }
+            MainViewTab::AddressStorage => {
+                vec![
+                    KeyHint::new("p", "Pin slot"),
+                    KeyHint::new("n", "Alerts"),
+                    KeyHint::new("x", "Unpin"),
+                ]
+            }
+            MainViewTab::AddressChains => vec![KeyHint::new("Enter", "Switch chain")],
+            MainViewTab::TransactionAccessList => vec![KeyHint::new("a", "Generate")],
+            MainViewTab::Custom(index)
+                if ctx
+                    .state
+                    .custom_tabs
+                    .tabs
+                    .get(index)
+                    .is_some_and(|custom| !custom.command.is_empty()) =>
+            {
+                vec![KeyHint::new("r", "Rerun")]
+            }
+            _ => Vec::new(),
+        };
+        match mode {
+            MainViewMode::Address => {
+                let failed = ctx
+                    .state
+                    .current_address
+                    .as_ref()
+                    .is_some_and(|data| !data.failures.is_empty());
+                if failed && !matches!(tab, MainViewTab::Custom(_)) {
+                    hints.push(KeyHint::new("r", "Retry"));
+                    hints.push(KeyHint::new("!", "Details"));
+                }
+                hints.extend([
+                    KeyHint::new("f", "Favorite"),
+                    KeyHint::new("+", "Watch"),
+                    KeyHint::new("N", "Label"),
+                    KeyHint::new("O", "QR"),
+                    KeyHint::new("A", "Gas"),
+                    KeyHint::new("S", "Safe"),
+                    KeyHint::new("W", "Write"),
+                ]);
+            }
+            MainViewMode::Transaction => hints.extend([
+                KeyHint::new("p/n", "Prev/Next in block"),
+                KeyHint::new("f", "Favorite"),
+            ]),
+        }
+        hints.extend([
+            KeyHint::new("v", "Copy mode"),
+            KeyHint::new("m", "Pin"),
+            KeyHint::new("w", "Wrap/Truncate/Scroll"),
+            KeyHint::new("J", "Export JSON"),
+            KeyHint::new("b", "Bindings"),
+            KeyHint::new("j/k", "Move"),
+            KeyHint::new("[ ]", "Tab"),
+        ]);
+        hints
     }
 }
@@ -1,4 +1,5 @@
 pub mod bottom_bar;
+pub mod copy_mode;
 pub mod main_view;
 pub mod modal;
 pub mod sidebar;
@@ -0,0 +1,374 @@
+use crate::{
+    app::{
+        Action, AppContext, AppResult, AppView, BlockSummary, Message, SelectedEntity,
+        TransactionRef, blocks,
+        scheduler::{Priority, host_key},
+    },
+    components::Component,
+    ui::util::{centered_rect, format_timestamp, short_hex},
+};
+use alloy::eips::BlockId;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+#[derive(Debug, Clone)]
+pub enum BlockCommand {
+    Next,
+    Previous,
+    /// Open the highlighted choice or transaction.
+    Open,
+    OpenTransaction,
+    OpenBlock,
+    /// Load the previous (`false`) or next (`true`) block.
+    Step(bool),
+    Close,
+    Loaded(Result<Option<BlockSummary>, String>),
+}
+
+#[derive(Debug)]
+enum Stage {
+    /// A hash search that could be a transaction or a block.
+    Choose {
+        hash: String,
+        reason: String,
+    },
+    Loading(BlockId),
+    Done(Result<BlockSummary, String>),
+}
+
+/// A block's header and transactions, reached from search. Starts with a
+/// transaction-or-block chooser when a hash search was inconclusive.
+#[derive(Debug)]
+pub struct BlockModal {
+    chain: String,
+    stage: Stage,
+    selected: usize,
+}
+
+impl BlockModal {
+    pub fn loaded(chain: String, block: BlockSummary) -> Self {
+        Self {
+            chain,
+            stage: Stage::Done(Ok(block)),
+            selected: 0,
+        }
+    }
+
+    pub fn choose(tx: TransactionRef, reason: String) -> Self {
+        Self {
+            chain: tx.chain,
+            stage: Stage::Choose {
+                hash: tx.hash,
+                reason,
+            },
+            selected: 0,
+        }
+    }
+
+    pub fn command_from_key(&self, event: KeyEvent) -> Option<BlockCommand> {
+        match event.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(BlockCommand::Close),
+            KeyCode::Char('j') | KeyCode::Down => Some(BlockCommand::Next),
+            KeyCode::Char('k') | KeyCode::Up => Some(BlockCommand::Previous),
+            KeyCode::Enter => Some(BlockCommand::Open),
+            KeyCode::Char('t') if matches!(self.stage, Stage::Choose { .. }) => {
+                Some(BlockCommand::OpenTransaction)
+            }
+            KeyCode::Char('b') if matches!(self.stage, Stage::Choose { .. }) => {
+                Some(BlockCommand::OpenBlock)
+            }
+            KeyCode::Char(key @ ('p' | 'n')) if matches!(self.stage, Stage::Done(Ok(_))) => {
+                Some(BlockCommand::Step(key == 'n'))
+            }
+            _ => None,
+        }
+    }
+
+    fn row_count(&self) -> usize {
+        match &self.stage {
+            Stage::Choose { .. } => 2,
+            Stage::Loading(_) | Stage::Done(Err(_)) => 0,
+            Stage::Done(Ok(block)) => block.transactions.len(),
+        }
+    }
+
+    fn open_transaction(&self, hash: String) -> Action {
+        Action::SelectionChanged(SelectedEntity::Transaction(TransactionRef {
+            label: format!("Txn {}", short_hex(&hash)),
+            hash,
+            chain: self.chain.clone(),
+        }))
+    }
+
+    fn load(&mut self, block: BlockId, ctx: &mut AppContext<'_>) {
+        let Some(rpc_url) = ctx.state.secrets.rpc_url(&self.chain).map(str::to_string) else {
+            self.stage = Stage::Done(Err(format!(
+                "Configure an RPC endpoint for {} first",
+                self.chain
+            )));
+            return;
+        };
+        self.stage = Stage::Loading(block);
+        self.selected = 0;
+        let rpc = ctx.commands.providers().rpc.clone();
+        let host = host_key(Some(&rpc_url), &self.chain);
+        ctx.commands
+            .spawn_scheduled(host, Priority::Foreground, move || async move {
+                Message::BlockLoaded(blocks::load_block(rpc, rpc_url, block).await)
+            });
+    }
+
+    fn open(&mut self, ctx: &mut AppContext<'_>) -> Option<Action> {
+        match &self.stage {
+            Stage::Choose { hash, .. } if self.selected == 0 => {
+                Some(self.open_transaction(hash.clone()))
+            }
+            Stage::Choose { hash, .. } => {
+                if let Ok(hash) = hash.parse() {
+                    self.load(BlockId::hash(hash), ctx);
+                }
+                None
+            }
+            Stage::Done(Ok(block)) => block
+                .transactions
+                .get(self.selected)
+                .map(|hash| self.open_transaction(format!("{hash:#x}"))),
+            Stage::Loading(_) | Stage::Done(Err(_)) => None,
+        }
+    }
+
+    fn block_lines(&self, block: &BlockSummary) -> Vec<Line<'static>> {
+        let label = Style::default().fg(Color::Gray);
+        let field = |name: &str, value: String| {
+            Line::from(vec![
+                Span::styled(format!("{name:<11}"), label),
+                Span::raw(value),
+            ])
+        };
+        let mut lines = vec![
+            field("Hash", format!("{:#x}", block.hash)),
+            field("Parent", format!("{:#x}", block.parent_hash)),
+            field("Time", format_timestamp(block.timestamp)),
+            field("Miner", block.miner.to_checksum(None)),
+            field(
+                "Gas used",
+                format!(
+                    "{} / {} ({:.1}%)",
+                    block.gas_used,
+                    block.gas_limit,
+                    block.gas_used as f64 * 100.0 / block.gas_limit.max(1) as f64
+                ),
+            ),
+        ];
+        if let Some(base_fee) = block.base_fee_per_gas {
+            lines.push(field(
+                "Base fee",
+                format!("{:.3} gwei", base_fee as f64 / 1e9),
+            ));
+        }
+        lines.push(field("Txs", block.transactions.len().to_string()));
+        lines
+    }
+}
+
+impl Component for BlockModal {
+    type Command = BlockCommand;
+
+    fn init(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<()> {
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        command: &Self::Command,
+        ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        match command {
+            BlockCommand::Next => {
+                if self.selected + 1 < self.row_count() {
+                    self.selected += 1;
+                }
+                Ok(None)
+            }
+            BlockCommand::Previous => {
+                self.selected = self.selected.saturating_sub(1);
+                Ok(None)
+            }
+            BlockCommand::Open => Ok(self.open(ctx)),
+            BlockCommand::OpenTransaction => {
+                self.selected = 0;
+                Ok(self.open(ctx))
+            }
+            BlockCommand::OpenBlock => {
+                self.selected = 1;
+                Ok(self.open(ctx))
+            }
+            BlockCommand::Step(forward) => {
+                if let Stage::Done(Ok(block)) = &self.stage {
+                    let number = if *forward {
+                        block.number.checked_add(1)
+                    } else {
+                        block.number.checked_sub(1)
+                    };
+                    if let Some(number) = number {
+                        self.load(BlockId::number(number), ctx);
+                    }
+                }
+                Ok(None)
+            }
+            BlockCommand::Close => Ok(Some(Action::CloseModal)),
+            BlockCommand::Loaded(result) => {
+                if let Stage::Loading(block) = self.stage {
+                    self.stage = Stage::Done(match result {
+                        Ok(Some(summary)) => Ok(summary.clone()),
+                        Ok(None) => Err(format!("Block {block} not found on this RPC")),
+                        Err(err) => Err(err.clone()),
+                    });
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame<'_>, area: Rect, _ctx: &AppView<'_>) {
+        let title = match &self.stage {
+            Stage::Choose { .. } => "Transaction or block?".to_string(),
+            Stage::Done(Ok(block)) => format!("Block {} • {}", block.number, self.chain),
+            Stage::Loading(_) | Stage::Done(Err(_)) => format!("Block • {}", self.chain),
+        };
+        let modal_area = centered_rect(96, 28, area);
+        frame.render_widget(Clear, modal_area);
+        let frame_block = Block::default()
+            .title(Span::styled(
+                title,
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = frame_block.inner(modal_area);
+        frame.render_widget(frame_block, modal_area);
+
+        let highlight = Style::default()
+            .fg(Color::White)
+            .bg(Color::Blue)
+            .add_modifier(Modifier::BOLD);
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        let footer = match &self.stage {
+            Stage::Choose { hash, reason } => {
+                let chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Length(3),
+                        Constraint::Min(1),
+                        Constraint::Length(1),
+                    ])
+                    .split(inner);
+                frame.render_widget(
+                    Paragraph::new(vec![
+                        Line::from(hash.clone()),
+                        Line::from(Span::styled(
+                            reason.clone(),
+                            Style::default().fg(Color::Yellow),
+                        )),
+                    ]),
+                    chunks[0],
+                );
+                let items = vec![
+                    ListItem::new("[t] Open as transaction"),
+                    ListItem::new("[b] Open as block"),
+                ];
+                frame.render_stateful_widget(
+                    List::new(items).highlight_style(highlight),
+                    chunks[1],
+                    &mut state,
+                );
+                (chunks[2], "j/k Move • Enter Open • Esc Close")
+            }
+            Stage::Loading(block) => {
+                let chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Min(1), Constraint::Length(1)])
+                    .split(inner);
+                frame.render_widget(
+                    Paragraph::new(format!("Loading block {block}…"))
+                        .style(Style::default().fg(Color::Gray)),
+                    chunks[0],
+                );
+                (chunks[1], "Esc Close")
+            }
+            Stage::Done(Err(err)) => {
+                let chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Min(1), Constraint::Length(1)])
+                    .split(inner);
+                frame.render_widget(
+                    Paragraph::new(err.clone()).style(Style::default().fg(Color::Red)),
+                    chunks[0],
+                );
+                (chunks[1], "Esc Close")
+            }
+            Stage::Done(Ok(block)) => {
+                let header = self.block_lines(block);
+                let chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Length(header.len() as u16 + 1),
+                        Constraint::Min(1),
+                        Constraint::Length(1),
+                    ])
+                    .split(inner);
+                frame.render_widget(Paragraph::new(header), chunks[0]);
+                if block.transactions.is_empty() {
+                    frame.render_widget(
+                        Paragraph::new("No transactions in this block")
+                            .style(Style::default().fg(Color::DarkGray)),
+                        chunks[1],
+                    );
+                } else {
+                    let items: Vec<ListItem> = block
+                        .transactions
+                        .iter()
+                        .enumerate()
+                        .map(|(index, hash)| {
+                            ListItem::new(Line::from(vec![
+                                Span::styled(
+                                    format!("{index:>4}  "),
+                                    Style::default().fg(Color::Gray),
+                                ),
+                                Span::raw(format!("{hash:#x}")),
+                            ]))
+                        })
+                        .collect();
+                    frame.render_stateful_widget(
+                        List::new(items).highlight_style(highlight),
+                        chunks[1],
+                        &mut state,
+                    );
+                }
+                (
+                    chunks[2],
+                    "j/k Move • Enter Open transaction • p/n Previous/next block • Esc Close",
+                )
+            }
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                footer.1,
+                Style::default().fg(Color::Gray),
+            ))),
+            footer.0,
+        );
+    }
+
+    fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{
+    app::{Action, AppContext, AppResult, AppView, convert::convert},
+    components::Component,
+    ui::util::centered_rect,
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+#[derive(Debug, Clone)]
+pub enum ConvertCommand {
+    InputChar(char),
+    Backspace,
+    Clear,
+    Close,
+}
+
+/// Unit, base, date and hash conversions of whatever is typed, recomputed
+/// on every keystroke.
+#[derive(Debug, Default)]
+pub struct ConvertModal {
+    input: String,
+}
+
+impl ConvertModal {
+    pub fn new(input: String) -> Self {
+        Self { input }
+    }
+
+    pub fn command_from_key(event: KeyEvent) -> Option<ConvertCommand> {
+        match event.code {
+            KeyCode::Esc | KeyCode::Enter => Some(ConvertCommand::Close),
+            KeyCode::Backspace => Some(ConvertCommand::Backspace),
+            KeyCode::Char('u') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(ConvertCommand::Clear)
+            }
+            KeyCode::Char(c) if !event.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(ConvertCommand::InputChar(c))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn insert_text(&mut self, text: &str) {
+        self.input
+            .extend(text.chars().filter(|c| !matches!(c, '\r' | '\n')));
+    }
+}
+
+impl Component for ConvertModal {
+    type Command = ConvertCommand;
+
+    fn init(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<()> {
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        command: &Self::Command,
+        _ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        match command {
+            ConvertCommand::InputChar(c) => self.input.push(*c),
+            ConvertCommand::Backspace => {
+                self.input.pop();
+            }
+            ConvertCommand::Clear => self.input.clear(),
+            ConvertCommand::Close => return Ok(Some(Action::CloseModal)),
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, frame: &mut Frame<'_>, area: Rect, _ctx: &AppView<'_>) {
+        let modal_area = centered_rect(90, 16, area);
+        frame.render_widget(Clear, modal_area);
+
+        let block = Block::default()
+            .title(Span::styled(
+                "Convert",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled("› ", Style::default().fg(Color::Gray)),
+                Span::styled(
+                    format!("{}_", self.input),
+                    Style::default().fg(Color::Yellow),
+                ),
+            ]),
+            Line::from(""),
+        ];
+        let rows = convert(&self.input);
+        if rows.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "Type an amount (1.5 ether, 20 gwei, 0x2386f26fc10000), a Unix time, \
+                 a UTC date (2024-03-13 13:55), or text to hash.",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        lines.extend(rows.into_iter().map(|(label, value)| {
+            Line::from(vec![
+                Span::styled(format!("{label:<18}"), Style::default().fg(Color::Gray)),
+                Span::raw(value),
+            ])
+        }));
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[0]);
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "Ctrl+U Clear • Enter/Esc Close",
+                Style::default().fg(Color::Gray),
+            ))),
+            chunks[1],
+        );
+    }
+
+    fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,284 @@
+use crate::{
+    app::{
+        Action, AppContext, AppResult, AppView, Message,
+        deploy_address::{DeployInputs, check_deployed},
+        scheduler::{Priority, host_key},
+    },
+    components::Component,
+    ui::util::centered_rect,
+};
+use alloy::primitives::Address;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+#[derive(Debug, Clone)]
+pub enum DeployAddressCommand {
+    InputChar(char),
+    Backspace,
+    NextField,
+    PreviousField,
+    Check,
+    Close,
+    Checked(Result<Vec<(Address, usize)>, String>),
+}
+
+const FIELDS: [&str; 4] = [
+    "Deployer",
+    "Nonce (CREATE)",
+    "Salt (CREATE2)",
+    "Init code or its hash",
+];
+
+#[derive(Debug)]
+enum CodeCheck {
+    Running,
+    Done(Result<Vec<(Address, usize)>, String>),
+}
+
+/// Compute CREATE and CREATE2 addresses as the inputs are typed, and check
+/// on demand whether they already hold code on `chain`.
+#[derive(Debug)]
+pub struct DeployAddressModal {
+    chain: String,
+    inputs: DeployInputs,
+    focused: usize,
+    check: Option<CodeCheck>,
+    message: Option<String>,
+}
+
+impl DeployAddressModal {
+    pub fn new(chain: String) -> Self {
+        Self {
+            chain,
+            inputs: DeployInputs::default(),
+            focused: 0,
+            check: None,
+            message: None,
+        }
+    }
+
+    pub fn command_from_key(event: KeyEvent) -> Option<DeployAddressCommand> {
+        match event.code {
+            KeyCode::Esc => Some(DeployAddressCommand::Close),
+            KeyCode::Enter => Some(DeployAddressCommand::Check),
+            KeyCode::Tab | KeyCode::Down => Some(DeployAddressCommand::NextField),
+            KeyCode::BackTab | KeyCode::Up => Some(DeployAddressCommand::PreviousField),
+            KeyCode::Backspace => Some(DeployAddressCommand::Backspace),
+            KeyCode::Char(c) if !event.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(DeployAddressCommand::InputChar(c))
+            }
+            _ => None,
+        }
+    }
+
+    fn field_mut(&mut self, index: usize) -> &mut String {
+        match index {
+            0 => &mut self.inputs.deployer,
+            1 => &mut self.inputs.nonce,
+            2 => &mut self.inputs.salt,
+            _ => &mut self.inputs.init_code,
+        }
+    }
+
+    fn field(&self, index: usize) -> &str {
+        match index {
+            0 => &self.inputs.deployer,
+            1 => &self.inputs.nonce,
+            2 => &self.inputs.salt,
+            _ => &self.inputs.init_code,
+        }
+    }
+
+    pub fn insert_text(&mut self, text: &str) {
+        let focused = self.focused;
+        self.field_mut(focused)
+            .extend(text.chars().filter(|c| !c.is_whitespace()));
+        self.check = None;
+    }
+
+    fn check(&mut self, ctx: &mut AppContext<'_>) {
+        let addresses: Vec<Address> = self
+            .inputs
+            .compute()
+            .into_iter()
+            .filter_map(|(_, address)| address.ok())
+            .collect();
+        if addresses.is_empty() {
+            self.message = Some("Fill in the deployer and a nonce or salt first".into());
+            return;
+        }
+        let Some(rpc_url) = ctx.state.secrets.rpc_url(&self.chain).map(str::to_string) else {
+            self.message = Some(format!(
+                "Configure an RPC endpoint for {} first",
+                self.chain
+            ));
+            return;
+        };
+        self.message = None;
+        self.check = Some(CodeCheck::Running);
+        let rpc = ctx.commands.providers().rpc.clone();
+        let host = host_key(Some(&rpc_url), &self.chain);
+        ctx.commands
+            .spawn_scheduled(host, Priority::Foreground, move || async move {
+                Message::DeployedCodeChecked(check_deployed(rpc, rpc_url, addresses).await)
+            });
+    }
+
+    fn code_note(&self, address: Address) -> Option<Span<'static>> {
+        match self.check.as_ref()? {
+            CodeCheck::Running => Some(Span::styled(
+                "  checking…",
+                Style::default().fg(Color::DarkGray),
+            )),
+            CodeCheck::Done(Ok(sizes)) => {
+                let (_, size) = sizes.iter().find(|(checked, _)| *checked == address)?;
+                Some(match size {
+                    0 => Span::styled("  no code yet", Style::default().fg(Color::Green)),
+                    size => Span::styled(
+                        format!("  already deployed ({size} bytes)"),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                })
+            }
+            CodeCheck::Done(Err(_)) => None,
+        }
+    }
+}
+
+impl Component for DeployAddressModal {
+    type Command = DeployAddressCommand;
+
+    fn init(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<()> {
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        command: &Self::Command,
+        ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        match command {
+            DeployAddressCommand::InputChar(c) => {
+                let focused = self.focused;
+                self.field_mut(focused).push(*c);
+                self.check = None;
+            }
+            DeployAddressCommand::Backspace => {
+                let focused = self.focused;
+                self.field_mut(focused).pop();
+                self.check = None;
+            }
+            DeployAddressCommand::NextField => self.focused = (self.focused + 1) % FIELDS.len(),
+            DeployAddressCommand::PreviousField => {
+                self.focused = (self.focused + FIELDS.len() - 1) % FIELDS.len();
+            }
+            DeployAddressCommand::Check => self.check(ctx),
+            DeployAddressCommand::Checked(result) => {
+                if matches!(self.check, Some(CodeCheck::Running)) {
+                    self.check = Some(CodeCheck::Done(result.clone()));
+                }
+            }
+            DeployAddressCommand::Close => return Ok(Some(Action::CloseModal)),
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, frame: &mut Frame<'_>, area: Rect, _ctx: &AppView<'_>) {
+        let modal_area = centered_rect(80, 20, area);
+        frame.render_widget(Clear, modal_area);
+
+        let block = Block::default()
+            .title(Span::styled(
+                format!("Deployment address • {}", self.chain),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+
+        let mut lines: Vec<Line<'_>> = FIELDS
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let focused = index == self.focused;
+                let value = self.field(index);
+                Line::from(vec![
+                    Span::styled(
+                        format!("{} {name:<22}", if focused { "›" } else { " " }),
+                        Style::default().fg(Color::Gray),
+                    ),
+                    Span::styled(
+                        if focused {
+                            format!("{value}_")
+                        } else {
+                            value.to_string()
+                        },
+                        Style::default().fg(if focused { Color::Yellow } else { Color::White }),
+                    ),
+                ])
+            })
+            .collect();
+        lines.push(Line::from(""));
+
+        let computed = self.inputs.compute();
+        if computed.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "CREATE needs the deployer and nonce; CREATE2 the deployer, salt and init code \
+                 (or its keccak256 hash).",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        for (scheme, result) in computed {
+            let mut spans = vec![Span::styled(
+                format!("{:<8}", scheme.label()),
+                Style::default().add_modifier(Modifier::BOLD),
+            )];
+            match result {
+                Ok(address) => {
+                    spans.push(Span::raw(address.to_checksum(None)));
+                    spans.extend(self.code_note(address));
+                }
+                Err(err) => spans.push(Span::styled(err, Style::default().fg(Color::Red))),
+            }
+            lines.push(Line::from(spans));
+        }
+        if let Some(CodeCheck::Done(Err(err))) = self.check.as_ref() {
+            lines.push(Line::from(Span::styled(
+                format!("Code check failed: {err}"),
+                Style::default().fg(Color::Red),
+            )));
+        }
+        if let Some(message) = &self.message {
+            lines.push(Line::from(Span::styled(
+                message.clone(),
+                Style::default().fg(Color::Red),
+            )));
+        }
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[0]);
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "Tab/↑/↓ Field • Enter Check for code • Esc Close",
+                Style::default().fg(Color::Gray),
+            ))),
+            chunks[1],
+        );
+    }
+
+    fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{
+    app::{Action, AppContext, AppResult, AppView, updates::CURRENT_VERSION},
+    components::Component,
+    ui::util::centered_rect,
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+#[derive(Debug, Clone)]
+pub enum DiagnosticsCommand {
+    Reset,
+    Close,
+}
+
+/// Local usage statistics: feature counts, API calls, and hydration latency,
+/// plus the Etherscan quota left today and its last error with guidance.
+#[derive(Debug, Default)]
+pub struct DiagnosticsModal;
+
+impl DiagnosticsModal {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn command_from_key(event: KeyEvent) -> Option<DiagnosticsCommand> {
+        match event.code {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => Some(DiagnosticsCommand::Close),
+            KeyCode::Char('r') => Some(DiagnosticsCommand::Reset),
+            _ => None,
+        }
+    }
+}
+
+fn heading(text: &str) -> Line<'static> {
+    Line::from(Span::styled(
+        text.to_string(),
+        Style::default()
+            .fg(Color::Gray)
+            .add_modifier(Modifier::BOLD),
+    ))
+}
+
+impl Component for DiagnosticsModal {
+    type Command = DiagnosticsCommand;
+
+    fn init(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<()> {
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        command: &Self::Command,
+        ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        match command {
+            DiagnosticsCommand::Reset => {
+                ctx.state.usage.reset();
+                ctx.state.usage.queue_save(ctx.storage);
+                Ok(None)
+            }
+            DiagnosticsCommand::Close => Ok(Some(Action::CloseModal)),
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame<'_>, area: Rect, ctx: &AppView<'_>) {
+        let modal_area = centered_rect(64, 28, area);
+        frame.render_widget(Clear, modal_area);
+
+        let block = Block::default()
+            .title(Span::styled(
+                format!("Diagnostics • evm-tui v{CURRENT_VERSION}"),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+
+        let usage = &ctx.state.usage;
+        let mut lines = vec![heading("Hydration time")];
+        match (
+            usage.hydration_percentile(50.0),
+            usage.hydration_percentile(90.0),
+            usage.hydration_percentile(99.0),
+        ) {
+            (Some(p50), Some(p90), Some(p99)) => lines.push(Line::from(format!(
+                "  p50 {p50} ms • p90 {p90} ms • p99 {p99} ms ({} samples)",
+                usage.hydration_samples()
+            ))),
+            _ => lines.push(Line::from("  No hydrations recorded yet")),
+        }
+
+        lines.push(Line::from(""));
+        lines.push(heading("Most used features"));
+        let features = usage.top_features();
+        if features.is_empty() {
+            lines.push(Line::from("  Nothing recorded yet"));
+        }
+        for (name, count) in features.into_iter().take(6) {
+            lines.push(Line::from(format!("  {name:<24} {count:>6}")));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(heading("Etherscan API"));
+        let quota = ctx.providers.session.quota.usage();
+        let (remaining, limit, reported) = quota.remaining_today();
+        lines.push(Line::from(if reported {
+            format!("  {remaining}/{limit} calls left today (reported by Etherscan)")
+        } else {
+            format!("  ~{remaining}/{limit} calls left today (free tier, counted this session)")
+        }));
+        match quota.last_failure {
+            Some((ago, message, guidance)) => {
+                lines.push(Line::from(Span::styled(
+                    format!("  {}s ago: {message}", ago.as_secs()),
+                    Style::default().fg(Color::Red),
+                )));
+                if let Some(guidance) = guidance {
+                    lines.push(Line::from(Span::styled(
+                        format!("  {guidance}"),
+                        Style::default().fg(Color::Yellow),
+                    )));
+                }
+            }
+            None => lines.push(Line::from("  No errors this session")),
+        }
+
+        lines.push(Line::from(""));
+        lines.push(heading("API calls"));
+        let mut any_calls = false;
+        for (endpoint, count) in usage.api_calls() {
+            any_calls = true;
+            lines.push(Line::from(format!("  {endpoint:<24} {count:>6}")));
+        }
+        if !any_calls {
+            lines.push(Line::from("  No API calls recorded yet"));
+        }
+
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[0]);
+
+        let footer = Paragraph::new(Line::from(Span::styled(
+            "Stored locally only • r Reset • Esc Close",
+            Style::default().fg(Color::Gray),
+        )));
+        frame.render_widget(footer, chunks[1]);
+    }
+
+    fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,197 @@
+use crate::{
+    app::{
+        Action, AppContext, AppResult, AppView, failures::SectionFailure, updates::CURRENT_VERSION,
+    },
+    components::Component,
+    ui::util::centered_rect,
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+#[derive(Debug, Clone)]
+pub enum ErrorsCommand {
+    Next,
+    Previous,
+    Copy,
+    Close,
+}
+
+/// Full detail of the sections that failed to load for the selected address:
+/// source, HTTP status, cause chain and a suggestion, with a copyable report.
+#[derive(Debug)]
+pub struct ErrorsModal {
+    /// `<address> on <chain>`, heading the copied report.
+    subject: String,
+    failures: Vec<SectionFailure>,
+    selected: usize,
+}
+
+impl ErrorsModal {
+    pub fn new(subject: String, failures: Vec<SectionFailure>) -> Self {
+        Self {
+            subject,
+            failures,
+            selected: 0,
+        }
+    }
+
+    pub fn command_from_key(event: KeyEvent) -> Option<ErrorsCommand> {
+        match event.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(ErrorsCommand::Close),
+            KeyCode::Char('j') | KeyCode::Down => Some(ErrorsCommand::Next),
+            KeyCode::Char('k') | KeyCode::Up => Some(ErrorsCommand::Previous),
+            KeyCode::Char('c') | KeyCode::Char('y') => Some(ErrorsCommand::Copy),
+            _ => None,
+        }
+    }
+
+    fn report(&self, failure: &SectionFailure) -> String {
+        format!(
+            "evm-tui v{CURRENT_VERSION} error report\n{}\n\n{}",
+            self.subject,
+            failure.report()
+        )
+    }
+}
+
+fn field(name: &str, value: String) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(format!("{name:<12}"), Style::default().fg(Color::Gray)),
+        Span::raw(value),
+    ])
+}
+
+impl Component for ErrorsModal {
+    type Command = ErrorsCommand;
+
+    fn init(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<()> {
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        command: &Self::Command,
+        _ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        match command {
+            ErrorsCommand::Next => {
+                if self.selected + 1 < self.failures.len() {
+                    self.selected += 1;
+                }
+                Ok(None)
+            }
+            ErrorsCommand::Previous => {
+                self.selected = self.selected.saturating_sub(1);
+                Ok(None)
+            }
+            ErrorsCommand::Copy => Ok(self
+                .failures
+                .get(self.selected)
+                .map(|failure| Action::CopyToClipboard(self.report(failure)))),
+            ErrorsCommand::Close => Ok(Some(Action::CloseModal)),
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame<'_>, area: Rect, _ctx: &AppView<'_>) {
+        let modal_area = centered_rect(84, 24, area);
+        frame.render_widget(Clear, modal_area);
+
+        let block = Block::default()
+            .title(Span::styled(
+                format!("Load errors • {}", self.subject),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let list_height = (self.failures.len() as u16).clamp(1, 5);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(list_height),
+                Constraint::Length(1),
+                Constraint::Min(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let Some(failure) = self.failures.get(self.selected) else {
+            frame.render_widget(
+                Paragraph::new("Every section loaded").style(Style::default().fg(Color::DarkGray)),
+                chunks[0],
+            );
+            return;
+        };
+
+        let items: Vec<ListItem> = self
+            .failures
+            .iter()
+            .map(|failure| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{:<20}", failure.section.label()),
+                        Style::default().fg(Color::Red),
+                    ),
+                    Span::raw(failure.detail.message.clone()),
+                ]))
+            })
+            .collect();
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .fg(Color::White)
+                .bg(Color::Blue)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(list, chunks[0], &mut state);
+
+        let detail = &failure.detail;
+        let mut lines = vec![
+            field("Source", detail.source.clone()),
+            field(
+                "HTTP status",
+                detail
+                    .status
+                    .map_or_else(|| "none".to_string(), |status| status.to_string()),
+            ),
+            field("Error", detail.message.clone()),
+        ];
+        for (depth, cause) in detail.causes.iter().enumerate() {
+            lines.push(field(
+                if depth == 0 { "Caused by" } else { "" },
+                format!("{}{cause}", "  ".repeat(depth)),
+            ));
+        }
+        if failure.kept_earlier {
+            lines.push(field("Showing", "results of an earlier load".into()));
+        }
+        if let Some(suggestion) = detail.suggestion.as_ref() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                suggestion.clone(),
+                Style::default().fg(Color::Yellow),
+            )));
+        }
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[2]);
+
+        let footer = Paragraph::new(Line::from(Span::styled(
+            "j/k Move • c Copy error report • Esc Close",
+            Style::default().fg(Color::Gray),
+        )));
+        frame.render_widget(footer, chunks[3]);
+    }
+
+    fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,341 @@
+use crate::{
+    app::{
+        Action, AddressRef, AppContext, AppResult, AppView, Message,
+        gas_analytics::{self, ANALYTICS_FETCH_LIMIT, GasReport, SelectorStats},
+        scheduler::{Priority, host_key},
+    },
+    components::Component,
+    ui::util::{centered_rect, short_hex},
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState, Wrap},
+};
+
+/// Failure rate above which an entry point is flagged: callers keep paying
+/// for calls that revert, a sign of griefing or a broken integration.
+const FAILURE_WARNING: f64 = 0.2;
+
+#[derive(Debug, Clone)]
+pub enum GasAnalyticsCommand {
+    Next,
+    Previous,
+    Refresh,
+    Close,
+    Loaded(Result<GasReport, String>),
+}
+
+/// Gas used per function selector over a contract's latest transactions.
+#[derive(Debug)]
+pub struct GasAnalyticsModal {
+    address: AddressRef,
+    report: Option<Result<GasReport, String>>,
+    selected: usize,
+}
+
+impl GasAnalyticsModal {
+    pub fn new(address: AddressRef) -> Self {
+        Self {
+            address,
+            report: None,
+            selected: 0,
+        }
+    }
+
+    pub fn command_from_key(event: KeyEvent) -> Option<GasAnalyticsCommand> {
+        match event.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(GasAnalyticsCommand::Close),
+            KeyCode::Char('j') | KeyCode::Down => Some(GasAnalyticsCommand::Next),
+            KeyCode::Char('k') | KeyCode::Up => Some(GasAnalyticsCommand::Previous),
+            KeyCode::Char('r') => Some(GasAnalyticsCommand::Refresh),
+            _ => None,
+        }
+    }
+
+    /// Fetch the history again, past the explorer cache, and summarize it in
+    /// the background.
+    fn reload(&mut self, ctx: &mut AppContext<'_>) {
+        self.report = None;
+        let address = self.address.clone();
+        let secrets = &ctx.state.secrets;
+        let api_key = secrets.explorer_api_key(&address.chain).map(str::to_string);
+        let rpc_url = secrets.rpc_url(&address.chain).map(str::to_string);
+        let providers = ctx.commands.providers().fresh_history();
+        let host = host_key(rpc_url.as_deref(), &address.chain);
+        ctx.commands
+            .spawn_scheduled(host, Priority::Foreground, move || async move {
+                Message::GasAnalyticsLoaded(
+                    gas_analytics::fetch_gas_report(providers, address, api_key, rpc_url).await,
+                )
+            });
+    }
+
+    fn selectors(&self) -> &[SelectorStats] {
+        match &self.report {
+            Some(Ok(report)) => &report.selectors,
+            _ => &[],
+        }
+    }
+
+    fn function_name(view: &AppView<'_>, stats: &SelectorStats) -> String {
+        match stats.selector.as_deref() {
+            Some(selector) => view
+                .state
+                .signatures
+                .method(selector)
+                .map(str::to_string)
+                .unwrap_or_else(|| selector.to_string()),
+            None => "(no calldata: receive/fallback)".into(),
+        }
+    }
+
+    fn detail_lines(
+        view: &AppView<'_>,
+        report: &GasReport,
+        stats: &SelectorStats,
+    ) -> Vec<Line<'static>> {
+        let gray = Style::default().fg(Color::Gray);
+        let share = stats.total_gas as f64 / report.total_gas.max(1) as f64 * 100.0;
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled(
+                    Self::function_name(view, stats),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    stats
+                        .selector
+                        .as_deref()
+                        .map(|selector| format!("  {selector}"))
+                        .unwrap_or_default(),
+                    gray,
+                ),
+            ]),
+            Line::from(format!(
+                "{} calls • {} gas in total ({share:.1}% of the contract's) • max {}",
+                stats.calls,
+                grouped(stats.total_gas),
+                grouped(stats.max_gas)
+            )),
+        ];
+        if stats.measured < stats.calls {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "Gas figures cover the {} calls the source reported gas for.",
+                    stats.measured
+                ),
+                gray,
+            )));
+        }
+        if stats.failed > 0 {
+            let style = if stats.failure_rate() > FAILURE_WARNING {
+                Style::default().fg(Color::Yellow)
+            } else {
+                gray
+            };
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "{} failed calls burned {} gas{}",
+                    stats.failed,
+                    grouped(stats.failed_gas),
+                    if stats.failure_rate() > FAILURE_WARNING {
+                        ": callers keep paying for reverts (griefing or a broken integration?)"
+                    } else {
+                        ""
+                    }
+                ),
+                style,
+            )));
+        }
+        lines
+    }
+}
+
+/// `1234567` as `1,234,567`.
+fn grouped(value: u64) -> String {
+    let digits = value.to_string();
+    let mut out = String::new();
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(digit);
+    }
+    out
+}
+
+impl Component for GasAnalyticsModal {
+    type Command = GasAnalyticsCommand;
+
+    fn init(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<()> {
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        command: &Self::Command,
+        ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        match command {
+            GasAnalyticsCommand::Next => {
+                if self.selected + 1 < self.selectors().len() {
+                    self.selected += 1;
+                }
+            }
+            GasAnalyticsCommand::Previous => self.selected = self.selected.saturating_sub(1),
+            GasAnalyticsCommand::Refresh => self.reload(ctx),
+            GasAnalyticsCommand::Close => return Ok(Some(Action::CloseModal)),
+            GasAnalyticsCommand::Loaded(result) => {
+                self.report = Some(result.clone());
+                self.selected = self.selected.min(self.selectors().len().saturating_sub(1));
+            }
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, frame: &mut Frame<'_>, area: Rect, ctx: &AppView<'_>) {
+        let modal_area = centered_rect(100, 26, area);
+        frame.render_widget(Clear, modal_area);
+
+        let mut title = format!(
+            "Gas analytics • {} • {}",
+            ctx.state
+                .labels
+                .display(&self.address.chain, &self.address.address),
+            self.address.chain
+        );
+        if let Some(Ok(report)) = &self.report {
+            title.push_str(&format!(
+                " • {} calls in the latest {} transactions via {}",
+                report.calls, report.scanned, report.source
+            ));
+        }
+        let block = Block::default()
+            .title(Span::styled(
+                title,
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),
+                Constraint::Length(5),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "j/k Select • r Reload • Esc Close",
+                Style::default().fg(Color::Gray),
+            ))),
+            chunks[2],
+        );
+
+        let muted = Style::default().fg(Color::DarkGray);
+        let report = match &self.report {
+            None => {
+                frame.render_widget(
+                    Paragraph::new(Span::styled(
+                        format!(
+                            "Loading up to {ANALYTICS_FETCH_LIMIT} transactions of {}…",
+                            short_hex(&self.address.address)
+                        ),
+                        muted,
+                    )),
+                    chunks[0],
+                );
+                return;
+            }
+            Some(Err(err)) => {
+                frame.render_widget(
+                    Paragraph::new(Span::styled(
+                        format!("Failed to load transactions: {err}"),
+                        Style::default().fg(Color::Red),
+                    ))
+                    .wrap(Wrap { trim: false }),
+                    chunks[0],
+                );
+                return;
+            }
+            Some(Ok(report)) => report,
+        };
+        if report.selectors.is_empty() {
+            frame.render_widget(
+                Paragraph::new(Span::styled(
+                    "No calls into this contract in the fetched history.",
+                    muted,
+                )),
+                chunks[0],
+            );
+            return;
+        }
+
+        let rows: Vec<Row<'_>> = report
+            .selectors
+            .iter()
+            .map(|stats| {
+                let failure = stats.failure_rate();
+                let failed_style = if failure > FAILURE_WARNING {
+                    Style::default().fg(Color::Yellow)
+                } else {
+                    Style::default()
+                };
+                Row::new(vec![
+                    Cell::from(Self::function_name(ctx, stats)),
+                    Cell::from(stats.calls.to_string()),
+                    Cell::from(format!("{:.1}%", failure * 100.0)).style(failed_style),
+                    Cell::from(grouped(stats.average_gas)),
+                    Cell::from(grouped(stats.median_gas)),
+                    Cell::from(format!(
+                        "{:.1}%",
+                        stats.total_gas as f64 / report.total_gas.max(1) as f64 * 100.0
+                    )),
+                ])
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Min(24),
+                Constraint::Length(7),
+                Constraint::Length(8),
+                Constraint::Length(11),
+                Constraint::Length(11),
+                Constraint::Length(8),
+            ],
+        )
+        .header(
+            Row::new(vec![
+                "Function", "Calls", "Failed", "Avg gas", "Median", "Share",
+            ])
+            .style(Style::default().fg(Color::Gray)),
+        )
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state = TableState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(table, chunks[0], &mut state);
+
+        if let Some(stats) = report.selectors.get(self.selected) {
+            frame.render_widget(
+                Paragraph::new(Self::detail_lines(ctx, report, stats))
+                    .wrap(Wrap { trim: false })
+                    .block(Block::default().borders(Borders::TOP)),
+                chunks[1],
+            );
+        }
+    }
+
+    fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,313 @@
+use crate::{
+    app::{
+        Action, AppContext, AppResult, AppView, SecretsState,
+        failures::redact_urls,
+        providers::Rpc,
+        quota::{DAILY_LIMIT, PER_SECOND_LIMIT},
+        rpc_health,
+        ws::SubscriptionHandle,
+    },
+    components::Component,
+    storage::SecretKey,
+    ui::util::{centered_rect, ws_status},
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone)]
+pub enum HealthCommand {
+    ScrollDown,
+    ScrollUp,
+    /// A pendingTransactions notification arrived.
+    PendingSeen,
+    Close,
+}
+
+/// Window the mempool rate is counted over.
+const MEMPOOL_WINDOW: Duration = Duration::from_secs(10);
+
+/// Live view of the dependencies hydration waits on: RPC endpoints, the
+/// Etherscan quota, and in-memory cache hit rates. With a WebSocket endpoint
+/// it also subscribes to pending transactions while open.
+#[derive(Debug, Default)]
+pub struct HealthModal {
+    scroll: u16,
+    mempool: Option<(String, SubscriptionHandle)>,
+    pending: VecDeque<Instant>,
+}
+
+impl HealthModal {
+    pub fn new(mempool: Option<(String, SubscriptionHandle)>) -> Self {
+        Self {
+            mempool,
+            ..Self::default()
+        }
+    }
+
+    pub fn command_from_key(event: KeyEvent) -> Option<HealthCommand> {
+        match event.code {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => Some(HealthCommand::Close),
+            KeyCode::Char('j') | KeyCode::Down => Some(HealthCommand::ScrollDown),
+            KeyCode::Char('k') | KeyCode::Up => Some(HealthCommand::ScrollUp),
+            _ => None,
+        }
+    }
+}
+
+fn heading(text: &str) -> Line<'static> {
+    Line::from(Span::styled(
+        text.to_string(),
+        Style::default()
+            .fg(Color::Gray)
+            .add_modifier(Modifier::BOLD),
+    ))
+}
+
+/// Every configured RPC value with a display name, Anvil first.
+fn configured_rpcs(secrets: &SecretsState) -> Vec<(String, String)> {
+    let mut rpcs = Vec::new();
+    if let Some(url) = secrets.anvil_rpc_url.as_ref() {
+        rpcs.push(("Anvil".to_string(), url.clone()));
+    }
+    for (key, value) in &secrets.scoped {
+        if let SecretKey::RpcUrl(chain) = key {
+            rpcs.push((chain.clone(), value.clone()));
+        }
+    }
+    rpcs
+}
+
+fn rpc_lines(rpc: &Rpc, name: &str, value: &str) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(format!("  {name}"))];
+    let health = rpc.health();
+    let current = health.attempt_order(value).into_iter().next();
+    for url in rpc_health::endpoints(value) {
+        let marker = if current.as_deref() == Some(url.as_str()) {
+            "▶"
+        } else {
+            " "
+        };
+        let (status, style) = match health.get(&url) {
+            Some(health) if health.is_failing() => (
+                format!(
+                    "down ×{} • {}",
+                    health.consecutive_failures,
+                    redact_urls(&health.last_error.unwrap_or_default())
+                ),
+                Style::default().fg(Color::Red),
+            ),
+            Some(health) => (
+                health
+                    .latency
+                    .map(|latency| format!("{} ms", latency.as_millis()))
+                    .unwrap_or_default(),
+                Style::default().fg(Color::Green),
+            ),
+            None => ("not used yet".into(), Style::default().fg(Color::DarkGray)),
+        };
+        let mut spans = vec![
+            // Keyed providers (Alchemy, Infura) carry the key in the path.
+            Span::raw(format!("    {marker} {} ", redact_urls(&url))),
+            Span::styled(status, style),
+        ];
+        if let Some((socket, color)) = ws_status(rpc.ws(), &url) {
+            spans.push(Span::styled(
+                format!(" • ws {socket}"),
+                Style::default().fg(color),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+impl Component for HealthModal {
+    type Command = HealthCommand;
+
+    fn init(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<()> {
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        command: &Self::Command,
+        _ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        match command {
+            HealthCommand::ScrollDown => self.scroll = self.scroll.saturating_add(1),
+            HealthCommand::ScrollUp => self.scroll = self.scroll.saturating_sub(1),
+            HealthCommand::PendingSeen => {
+                let now = Instant::now();
+                self.pending.push_back(now);
+                while self
+                    .pending
+                    .front()
+                    .is_some_and(|seen| now.duration_since(*seen) > MEMPOOL_WINDOW)
+                {
+                    self.pending.pop_front();
+                }
+            }
+            HealthCommand::Close => return Ok(Some(Action::CloseModal)),
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, frame: &mut Frame<'_>, area: Rect, ctx: &AppView<'_>) {
+        let modal_area = centered_rect(84, 24, area);
+        frame.render_widget(Clear, modal_area);
+
+        let block = Block::default()
+            .title(Span::styled(
+                "Dependency health",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+
+        let mut lines = vec![heading("RPC endpoints (▶ = used next)")];
+        let rpcs = configured_rpcs(&ctx.state.secrets);
+        if rpcs.is_empty() {
+            lines.push(Line::from("  No RPC endpoint configured"));
+        }
+        for (name, value) in &rpcs {
+            lines.extend(rpc_lines(&ctx.providers.rpc, name, value));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(heading("Etherscan quota (this session)"));
+        let usage = ctx.providers.session.quota.usage();
+        let burst_style = if usage.last_second >= PER_SECOND_LIMIT {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(
+                    "  {}/{PER_SECOND_LIMIT} calls in the last second",
+                    usage.last_second
+                ),
+                burst_style,
+            ),
+            Span::raw(format!(
+                " • {}/{DAILY_LIMIT} in the last 24h",
+                usage.last_day
+            )),
+        ]));
+        match usage.last_throttle {
+            Some((ago, message)) => lines.push(Line::from(Span::styled(
+                format!("  Rate limited {}s ago: {message}", ago.as_secs()),
+                Style::default().fg(Color::Red),
+            ))),
+            None => lines.push(Line::from("  Not rate limited")),
+        }
+
+        lines.push(Line::from(""));
+        lines.push(heading("Mempool (pendingTransactions)"));
+        match self.mempool.as_ref() {
+            Some((url, _)) => {
+                let recent = self
+                    .pending
+                    .iter()
+                    .filter(|seen| seen.elapsed() <= MEMPOOL_WINDOW)
+                    .count();
+                lines.push(Line::from(format!(
+                    "  {recent} pending tx in the last {}s via {}",
+                    MEMPOOL_WINDOW.as_secs(),
+                    redact_urls(url)
+                )));
+            }
+            None => lines.push(Line::from(
+                "  Needs a ws:// or wss:// RPC endpoint on the selected chain",
+            )),
+        }
+
+        lines.push(Line::from(""));
+        lines.push(heading("Cache hit rate (this session)"));
+        let caches = ctx.providers.rpc.stats().cache_lookups();
+        if caches.is_empty() {
+            lines.push(Line::from("  No cache lookups yet"));
+        }
+        for (cache, hits, misses) in caches {
+            let total = hits + misses;
+            lines.push(Line::from(format!(
+                "  {cache:<24} {:>3}% ({hits}/{total})",
+                hits * 100 / total.max(1)
+            )));
+        }
+        let previews = &ctx.state.transaction_preview_cache;
+        lines.push(Line::from(format!(
+            "  {:<24} {} of {} rows held",
+            "transaction preview",
+            previews.len(),
+            previews.capacity()
+        )));
+
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .scroll((self.scroll, 0)),
+            chunks[0],
+        );
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "Updates live • j/k Scroll • Esc Close",
+                Style::default().fg(Color::Gray),
+            ))),
+            chunks[1],
+        );
+    }
+
+    fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rpc_lines_never_show_the_key_in_the_url() {
+        let key = "s3cr3tK3yValue";
+        let url = format!("https://eth-mainnet.g.alchemy.com/v2/{key}");
+        let rpc = Rpc::default();
+        rpc.health()
+            .record_failure(&url, format!("error sending request for url ({url})"));
+
+        let text: Vec<String> = rpc_lines(&rpc, "Mainnet", &url)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect();
+        assert!(
+            text.iter()
+                .any(|line| line.contains("eth-mainnet.g.alchemy.com/…")),
+            "{text:?}"
+        );
+        assert!(text.iter().all(|line| !line.contains(key)), "{text:?}");
+    }
+}
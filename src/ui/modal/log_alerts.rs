@@ -0,0 +1,397 @@
+use crate::{
+    app::{
+        Action, AppContext, AppResult, AppView, SelectedEntity, TransactionRef,
+        log_alerts::LogAlertRule,
+    },
+    components::Component,
+    storage::{AlertOutputs, SettingKey},
+    ui::util::{centered_rect, short_hex},
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+#[derive(Debug, Clone)]
+pub enum LogAlertsCommand {
+    Next,
+    Previous,
+    NewRule,
+    NextField,
+    PreviousField,
+    InputChar(char),
+    Backspace,
+    ConfirmInput,
+    CancelInput,
+    CycleAlerts,
+    DeleteRule,
+    /// Open the transaction of the highlighted rule's latest match.
+    OpenLatest,
+    Close,
+}
+
+const FIELD_TITLES: [&str; 4] = ["Chain", "Contract", "Event", "Filter"];
+const FIELD_PLACEHOLDERS: [&str; 4] = [
+    "Mainnet",
+    "0x… contract address",
+    "Transfer(address indexed from, address indexed to, uint256 value)",
+    "optional argument=value pairs, e.g. to=0x…",
+];
+
+/// The rule being built: one text value per [`FIELD_TITLES`] entry.
+#[derive(Debug, Clone, Default)]
+struct RuleForm {
+    values: [String; 4],
+    focused: usize,
+}
+
+impl RuleForm {
+    fn from_rule(rule: &LogAlertRule) -> Self {
+        Self {
+            values: [
+                rule.chain.clone(),
+                rule.address.clone(),
+                rule.event.clone(),
+                rule.filter.clone(),
+            ],
+            // Straight to what is usually left to fill in.
+            focused: if rule.event.is_empty() { 2 } else { 3 },
+        }
+    }
+
+    fn rule(&self) -> LogAlertRule {
+        let [chain, address, event, filter] =
+            self.values.clone().map(|value| value.trim().to_string());
+        LogAlertRule {
+            chain,
+            address,
+            event,
+            filter,
+            alerts: AlertOutputs {
+                notify: true,
+                webhook: false,
+            },
+        }
+    }
+}
+
+/// Log alert rules with their latest matches, and the form that adds one.
+#[derive(Debug, Default)]
+pub struct LogAlertsModal {
+    selected: usize,
+    form: Option<RuleForm>,
+    message: Option<String>,
+}
+
+impl LogAlertsModal {
+    /// Opens on rule `selected`, or on the form when a `draft` is given.
+    pub fn new(draft: Option<LogAlertRule>, selected: usize) -> Self {
+        Self {
+            selected,
+            form: draft.as_ref().map(RuleForm::from_rule),
+            message: None,
+        }
+    }
+
+    pub fn command_from_key(&self, event: KeyEvent) -> Option<LogAlertsCommand> {
+        if self.form.is_some() {
+            return match event.code {
+                KeyCode::Esc => Some(LogAlertsCommand::CancelInput),
+                KeyCode::Enter => Some(LogAlertsCommand::ConfirmInput),
+                KeyCode::Backspace => Some(LogAlertsCommand::Backspace),
+                KeyCode::Tab | KeyCode::Down => Some(LogAlertsCommand::NextField),
+                KeyCode::BackTab | KeyCode::Up => Some(LogAlertsCommand::PreviousField),
+                KeyCode::Char(c) if !event.modifiers.contains(KeyModifiers::CONTROL) => {
+                    Some(LogAlertsCommand::InputChar(c))
+                }
+                _ => None,
+            };
+        }
+        match event.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(LogAlertsCommand::Close),
+            KeyCode::Char('j') | KeyCode::Down => Some(LogAlertsCommand::Next),
+            KeyCode::Char('k') | KeyCode::Up => Some(LogAlertsCommand::Previous),
+            KeyCode::Char('n') => Some(LogAlertsCommand::NewRule),
+            KeyCode::Char('o') => Some(LogAlertsCommand::CycleAlerts),
+            KeyCode::Char('d') => Some(LogAlertsCommand::DeleteRule),
+            KeyCode::Enter => Some(LogAlertsCommand::OpenLatest),
+            _ => None,
+        }
+    }
+
+    fn save(ctx: &mut AppContext<'_>) {
+        let rules = ctx.state.log_alerts.rules.clone();
+        ctx.storage.write("log alerts", move |storage| {
+            storage.settings().set_json(SettingKey::LogAlerts, &rules)
+        });
+    }
+
+    /// A blank rule on the selected address, the contract most alerts are about.
+    fn blank_form(ctx: &AppContext<'_>) -> RuleForm {
+        let (chain, address) = match ctx.state.selected.as_ref() {
+            Some(SelectedEntity::Address(addr)) => (addr.chain.clone(), addr.address.clone()),
+            Some(SelectedEntity::Transaction(tx)) => (tx.chain.clone(), String::new()),
+            None => ("Mainnet".to_string(), String::new()),
+        };
+        RuleForm {
+            values: [chain, address, String::new(), String::new()],
+            focused: 2,
+        }
+    }
+}
+
+impl Component for LogAlertsModal {
+    type Command = LogAlertsCommand;
+
+    fn init(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<()> {
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        command: &Self::Command,
+        ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        match command {
+            LogAlertsCommand::Next => {
+                if self.selected + 1 < ctx.state.log_alerts.rules.len() {
+                    self.selected += 1;
+                }
+            }
+            LogAlertsCommand::Previous => self.selected = self.selected.saturating_sub(1),
+            LogAlertsCommand::NewRule => {
+                self.message = None;
+                self.form = Some(Self::blank_form(ctx));
+            }
+            LogAlertsCommand::NextField | LogAlertsCommand::PreviousField => {
+                if let Some(form) = self.form.as_mut() {
+                    let forward = matches!(command, LogAlertsCommand::NextField);
+                    let count = FIELD_TITLES.len();
+                    form.focused = (form.focused + if forward { 1 } else { count - 1 }) % count;
+                }
+            }
+            LogAlertsCommand::InputChar(c) => {
+                if let Some(form) = self.form.as_mut() {
+                    form.values[form.focused].push(*c);
+                }
+            }
+            LogAlertsCommand::Backspace => {
+                if let Some(form) = self.form.as_mut() {
+                    form.values[form.focused].pop();
+                }
+            }
+            LogAlertsCommand::CancelInput => {
+                self.form = None;
+                self.message = None;
+            }
+            LogAlertsCommand::ConfirmInput => {
+                let Some(rule) = self.form.as_ref().map(RuleForm::rule) else {
+                    return Ok(None);
+                };
+                if let Err(err) = rule.validate() {
+                    self.message = Some(err);
+                    return Ok(None);
+                }
+                let title = rule.title();
+                if !ctx.state.log_alerts.add(rule) {
+                    self.message = Some("That rule already exists".into());
+                    return Ok(None);
+                }
+                Self::save(ctx);
+                ctx.state.usage.record_feature("log alert");
+                self.form = None;
+                self.selected = ctx.state.log_alerts.rules.len() - 1;
+                self.message = Some(format!("Watching {title} • matches from the next block on"));
+            }
+            LogAlertsCommand::CycleAlerts => {
+                if let Some(outputs) = ctx.state.log_alerts.cycle_alerts(self.selected) {
+                    Self::save(ctx);
+                    self.message = Some(format!("Alerts: {}", outputs.label()));
+                }
+            }
+            LogAlertsCommand::DeleteRule => {
+                if let Some(rule) = ctx.state.log_alerts.remove(self.selected) {
+                    Self::save(ctx);
+                    self.selected = self.selected.saturating_sub(1);
+                    self.message = Some(format!("Deleted {}", rule.title()));
+                }
+            }
+            LogAlertsCommand::OpenLatest => {
+                let alerts = &ctx.state.log_alerts;
+                let Some(rule) = alerts.rules.get(self.selected) else {
+                    return Ok(None);
+                };
+                let Some(hit) = alerts.hits(rule).next() else {
+                    self.message = Some("No match yet".into());
+                    return Ok(None);
+                };
+                return Ok(Some(Action::SelectionChanged(SelectedEntity::Transaction(
+                    TransactionRef {
+                        label: format!("Txn {}", short_hex(&hit.transaction_hash)),
+                        hash: hit.transaction_hash.clone(),
+                        chain: rule.chain.clone(),
+                    },
+                ))));
+            }
+            LogAlertsCommand::Close => return Ok(Some(Action::CloseModal)),
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, frame: &mut Frame<'_>, area: Rect, ctx: &AppView<'_>) {
+        let modal_area = centered_rect(96, 26, area);
+        frame.render_widget(Clear, modal_area);
+
+        let block = Block::default()
+            .title(Span::styled(
+                "Log alerts",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage(45),
+                Constraint::Min(5),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let alerts = &ctx.state.log_alerts;
+        if alerts.rules.is_empty() {
+            frame.render_widget(
+                Paragraph::new(
+                    "No log alert rules yet • press n to add one, or a on an Events tab filter",
+                )
+                .style(Style::default().fg(Color::DarkGray)),
+                chunks[0],
+            );
+        } else {
+            let items: Vec<ListItem> = alerts
+                .rules
+                .iter()
+                .map(|rule| {
+                    let filter = if rule.filter.is_empty() {
+                        "any arguments"
+                    } else {
+                        rule.filter.as_str()
+                    };
+                    let state = match alerts.error(rule) {
+                        Some(err) => {
+                            Span::styled(format!(" • ⚠ {err}"), Style::default().fg(Color::Yellow))
+                        }
+                        None => Span::raw(format!(" • {} match(es)", alerts.hits(rule).count())),
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(rule.title(), Style::default().add_modifier(Modifier::BOLD)),
+                        Span::raw(format!(
+                            " ({}){} • {filter}",
+                            rule.chain,
+                            rule.alerts.marker()
+                        )),
+                        state,
+                    ]))
+                })
+                .collect();
+            let list = List::new(items).highlight_style(
+                Style::default()
+                    .bg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            );
+            let mut state = ListState::default().with_selected(Some(self.selected));
+            frame.render_stateful_widget(list, chunks[0], &mut state);
+        }
+
+        let lower: Vec<Line> = match &self.form {
+            Some(form) => FIELD_TITLES
+                .iter()
+                .zip(FIELD_PLACEHOLDERS)
+                .enumerate()
+                .map(|(index, (title, placeholder))| {
+                    let value = &form.values[index];
+                    let focused = index == form.focused;
+                    let text = match (value.is_empty(), focused) {
+                        (true, false) => {
+                            Span::styled(placeholder, Style::default().fg(Color::DarkGray))
+                        }
+                        (_, true) => {
+                            Span::styled(format!("{value}_"), Style::default().fg(Color::Yellow))
+                        }
+                        (false, false) => Span::raw(value.clone()),
+                    };
+                    Line::from(vec![
+                        Span::styled(
+                            format!("{} {title:<9}", if focused { "▸" } else { " " }),
+                            Style::default().add_modifier(Modifier::BOLD),
+                        ),
+                        text,
+                    ])
+                })
+                .collect(),
+            None => alerts
+                .rules
+                .get(self.selected)
+                .map(|rule| {
+                    alerts
+                        .hits(rule)
+                        .map(|hit| {
+                            Line::from(format!(
+                                "  block {} • {} • {}",
+                                hit.block,
+                                short_hex(&hit.transaction_hash),
+                                hit.arguments
+                            ))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        };
+        let title = if self.form.is_some() {
+            "New rule"
+        } else {
+            "Latest matches"
+        };
+        frame.render_widget(
+            Paragraph::new(lower).block(Block::default().borders(Borders::TOP).title(title)),
+            chunks[1],
+        );
+
+        if let Some(message) = &self.message {
+            frame.render_widget(
+                Paragraph::new(Span::styled(
+                    message.clone(),
+                    Style::default().fg(Color::Yellow),
+                )),
+                chunks[2],
+            );
+        }
+
+        let footer = if self.form.is_some() {
+            "Tab/↑↓ Field • Enter Save • Esc Cancel"
+        } else {
+            "n New • Enter Open latest match • o Alerts • d Delete • Esc Close"
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                footer,
+                Style::default().fg(Color::Gray),
+            ))),
+            chunks[3],
+        );
+    }
+
+    fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        Ok(None)
+    }
+}
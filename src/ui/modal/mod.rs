@@ -1,3 +1,47 @@
+pub mod block;
+pub mod convert;
+pub mod deploy_address;
+pub mod diagnostics;
+pub mod errors;
+pub mod gas_analytics;
+pub mod health;
+pub mod log_alerts;
+pub mod pending;
+pub mod permissions;
+pub mod pipe;
+pub mod portfolio;
+pub mod qr;
+pub mod safe_queue;
+pub mod scratchpad;
 pub mod secrets;
+pub mod sessions;
+pub mod thresholds;
+pub mod trash;
+pub mod typed_data;
+pub mod update;
+pub mod upgrade_impact;
+pub mod write;
 
+pub use block::BlockModal;
+pub use convert::ConvertModal;
+pub use deploy_address::DeployAddressModal;
+pub use diagnostics::DiagnosticsModal;
+pub use errors::ErrorsModal;
+pub use gas_analytics::GasAnalyticsModal;
+pub use health::HealthModal;
+pub use log_alerts::LogAlertsModal;
+pub use pending::PendingModal;
+pub use permissions::PermissionsModal;
+pub use pipe::PipeModal;
+pub use portfolio::PortfolioModal;
+pub use qr::QrModal;
+pub use safe_queue::SafeQueueModal;
+pub use scratchpad::ScratchpadModal;
 pub use secrets::SecretsModal;
+pub use sessions::SessionsModal;
+pub use thresholds::ThresholdsModal;
+pub use trash::TrashModal;
+pub use typed_data::TypedDataModal;
+pub use update::UpdateModal;
+pub use upgrade_impact::UpgradeImpactModal;
+pub use write::WriteModal;
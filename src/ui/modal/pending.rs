@@ -0,0 +1,774 @@
+use crate::{
+    app::{
+        Action, AppContext, AppResult, AppView, Message, SelectedEntity, TransactionRef, chains,
+        nonces::ReplacementKind,
+        pending::{self, AccountCheck, PendingTransaction},
+        signing::{self, Credentials, PreparedTransaction, SignerSource},
+        watchlist::unix_now,
+    },
+    components::Component,
+    ui::util::{centered_rect, short_hex},
+};
+use alloy::primitives::{Address, U256, utils::format_units};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState, Wrap},
+};
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant},
+};
+
+/// How often the queue is re-checked against the chain while open.
+const CHECK_INTERVAL: Duration = Duration::from_secs(12);
+
+#[derive(Debug, Clone)]
+pub enum PendingCommand {
+    Next,
+    Previous,
+    SpeedUp,
+    Cancel,
+    Refresh,
+    Open,
+    InputChar(char),
+    Backspace,
+    Submit,
+    Confirm,
+    Back,
+    Checked(Result<AccountCheck, String>),
+    Prepared(Result<Box<PreparedTransaction>, String>),
+    Replaced(Result<String, String>),
+}
+
+#[derive(Debug)]
+enum Stage {
+    List,
+    /// Unlocking the signer for a speed-up or cancellation of `hash`.
+    Replace {
+        kind: ReplacementKind,
+        hash: String,
+        passphrase: String,
+    },
+    /// Unlocking the signer and pricing the replacement.
+    Preparing {
+        kind: ReplacementKind,
+        hash: String,
+    },
+    /// The re-priced transaction, waiting for `y`.
+    Confirm {
+        kind: ReplacementKind,
+        hash: String,
+        prepared: Box<PreparedTransaction>,
+    },
+    Sending(ReplacementKind),
+}
+
+/// Transactions sent from the write flow that are not mined yet, with the
+/// account's managed nonce, stuck detection and speed-up/cancel actions.
+#[derive(Debug)]
+pub struct PendingModal {
+    selected: usize,
+    stage: Stage,
+    /// Latest base fee per chain, from the last account checks.
+    base_fees: HashMap<String, u128>,
+    /// Mined nonce per chain and account.
+    mined: HashMap<(String, Address), u64>,
+    last_check: Option<Instant>,
+    checking: usize,
+    status: Option<Result<String, String>>,
+}
+
+impl PendingModal {
+    pub fn new() -> Self {
+        Self {
+            selected: 0,
+            stage: Stage::List,
+            base_fees: HashMap::new(),
+            mined: HashMap::new(),
+            last_check: None,
+            checking: 0,
+            status: None,
+        }
+    }
+
+    pub fn command_from_key(&self, event: KeyEvent) -> Option<PendingCommand> {
+        if let Stage::Replace { .. } = self.stage {
+            return match event.code {
+                KeyCode::Esc => Some(PendingCommand::Back),
+                KeyCode::Enter => Some(PendingCommand::Submit),
+                KeyCode::Backspace => Some(PendingCommand::Backspace),
+                KeyCode::Char(c) if !event.modifiers.contains(KeyModifiers::CONTROL) => {
+                    Some(PendingCommand::InputChar(c))
+                }
+                _ => None,
+            };
+        }
+        if let Stage::Confirm { .. } = self.stage {
+            return match event.code {
+                KeyCode::Char('y') => Some(PendingCommand::Confirm),
+                KeyCode::Char('n') | KeyCode::Esc => Some(PendingCommand::Back),
+                _ => None,
+            };
+        }
+        match event.code {
+            KeyCode::Esc => Some(PendingCommand::Back),
+            KeyCode::Enter => Some(PendingCommand::Open),
+            KeyCode::Char('j') | KeyCode::Down => Some(PendingCommand::Next),
+            KeyCode::Char('k') | KeyCode::Up => Some(PendingCommand::Previous),
+            KeyCode::Char('s') => Some(PendingCommand::SpeedUp),
+            KeyCode::Char('c') => Some(PendingCommand::Cancel),
+            KeyCode::Char('r') => Some(PendingCommand::Refresh),
+            _ => None,
+        }
+    }
+
+    pub fn insert_text(&mut self, text: &str) {
+        if let Stage::Replace { passphrase, .. } = &mut self.stage {
+            passphrase.extend(text.chars().filter(|c| !matches!(c, '\r' | '\n')));
+        }
+    }
+
+    /// Check every account with queued transactions in the background.
+    fn refresh(&mut self, ctx: &mut AppContext<'_>) {
+        self.last_check = Some(Instant::now());
+        let accounts: HashSet<(String, Address)> = ctx
+            .state
+            .pending_transactions
+            .iter()
+            .map(|tx| (tx.chain.clone(), tx.from))
+            .collect();
+        for (chain, from) in accounts {
+            let Some(rpc_url) = ctx.state.secrets.rpc_url(&chain).map(str::to_string) else {
+                continue;
+            };
+            self.checking += 1;
+            let rpc = ctx.commands.providers().rpc.clone();
+            ctx.commands.spawn_async(move || async move {
+                Message::PendingChecked(pending::check_account(rpc, rpc_url, chain, from).await)
+            });
+        }
+    }
+
+    fn start_replace(&mut self, kind: ReplacementKind, ctx: &AppContext<'_>) {
+        let Some(tx) = ctx.state.pending_transactions.get(self.selected) else {
+            return;
+        };
+        if let Some(err) = self.not_replaceable(tx, unix_now()) {
+            self.status = Some(Err(err));
+            return;
+        }
+        if ctx.state.signer.is_none() {
+            self.status = Some(Err(
+                "Set a signer first with :keystore <name|path> or :ledger [index]".into(),
+            ));
+            return;
+        }
+        self.stage = Stage::Replace {
+            kind,
+            hash: tx.hash.clone(),
+            passphrase: String::new(),
+        };
+    }
+
+    /// Unlock the signer and re-price the transaction in the background.
+    fn submit(&mut self, ctx: &mut AppContext<'_>) {
+        let Stage::Replace {
+            kind,
+            hash,
+            passphrase,
+        } = &mut self.stage
+        else {
+            return;
+        };
+        let (kind, passphrase) = (*kind, std::mem::take(passphrase));
+        let original = ctx
+            .state
+            .pending_transactions
+            .iter()
+            .find(|tx| tx.hash == *hash)
+            .cloned();
+        let result = (|| {
+            let original = original.ok_or("The transaction is no longer pending")?;
+            let source = ctx.state.signer.clone().ok_or("No signer configured")?;
+            let rpc_url = ctx
+                .state
+                .secrets
+                .rpc_url(&original.chain)
+                .map(str::to_string)
+                .ok_or_else(|| format!("Configure an RPC endpoint for {} first", original.chain))?;
+            Ok::<_, String>((original, source, rpc_url))
+        })();
+        let (original, source, rpc_url) = match result {
+            Ok(parts) => parts,
+            Err(err) => {
+                self.status = Some(Err(err));
+                self.stage = Stage::List;
+                return;
+            }
+        };
+        self.stage = Stage::Preparing {
+            kind,
+            hash: original.hash.clone(),
+        };
+        let credentials = Credentials { source, passphrase };
+        let rpc = ctx.commands.providers().rpc.clone();
+        ctx.commands.spawn_async(move || async move {
+            Message::PendingPrepared(
+                signing::replace(rpc, rpc_url, credentials, original, kind)
+                    .await
+                    .map(Box::new),
+            )
+        });
+    }
+
+    /// Sign the confirmed replacement and broadcast it.
+    fn confirm(&mut self, ctx: &mut AppContext<'_>) {
+        let Stage::Confirm {
+            kind,
+            hash,
+            prepared,
+        } = &self.stage
+        else {
+            return;
+        };
+        let (kind, hash, prepared) = (*kind, hash.clone(), (**prepared).clone());
+        let Some(chain) = ctx
+            .state
+            .pending_transactions
+            .iter()
+            .find(|tx| tx.hash == hash)
+            .map(|tx| tx.chain.clone())
+        else {
+            self.status = Some(Err("The transaction is no longer pending".into()));
+            self.stage = Stage::List;
+            return;
+        };
+        let Some(rpc_url) = ctx.state.secrets.rpc_url(&chain).map(str::to_string) else {
+            self.status = Some(Err(format!("Configure an RPC endpoint for {chain} first")));
+            self.stage = Stage::List;
+            return;
+        };
+        self.stage = Stage::Sending(kind);
+        let rpc = ctx.commands.providers().rpc.clone();
+        ctx.commands.spawn_async(move || async move {
+            let result = signing::broadcast(rpc, rpc_url, prepared.clone())
+                .await
+                .map(|sent| Box::new(PendingTransaction::sent(chain, &prepared, sent)));
+            Message::PendingReplaced { hash, kind, result }
+        });
+    }
+
+    fn open(&self, ctx: &AppContext<'_>) -> Option<Action> {
+        let tx = ctx.state.pending_transactions.get(self.selected)?;
+        Some(Action::SelectionChanged(SelectedEntity::Transaction(
+            TransactionRef {
+                label: format!("Txn {}", short_hex(&tx.hash)),
+                hash: tx.hash.clone(),
+                chain: tx.chain.clone(),
+            },
+        )))
+    }
+
+    /// Why `tx` cannot be sped up or cancelled: only a stuck transaction is
+    /// worth replacing, and not one that is mined or waits on an earlier
+    /// nonce, whose replacement would wait just the same.
+    fn not_replaceable(&self, tx: &PendingTransaction, now: u64) -> Option<String> {
+        let mined = self.mined.get(&(tx.chain.clone(), tx.from)).copied();
+        match mined {
+            Some(mined) if tx.nonce < mined => {
+                return Some(format!("Nonce {} is already mined", tx.nonce));
+            }
+            Some(mined) if tx.nonce > mined => {
+                return Some(format!(
+                    "Nonce {} is waiting on nonce {mined}; replace that one instead",
+                    tx.nonce
+                ));
+            }
+            _ => {}
+        }
+        match tx.stuck_reason(now, self.base_fees.get(&tx.chain).copied()) {
+            Some(_) => None,
+            None => Some(format!(
+                "Nonce {} is not stuck; it can be replaced once its fee cap is below the base \
+                 fee or it has waited 3 minutes",
+                tx.nonce
+            )),
+        }
+    }
+
+    fn state_cell(&self, tx: &PendingTransaction, now: u64) -> Cell<'static> {
+        let mined = self.mined.get(&(tx.chain.clone(), tx.from)).copied();
+        if mined.is_some_and(|mined| tx.nonce > mined) {
+            return Cell::from(Span::styled(
+                format!("waiting on nonce {}", mined.unwrap_or_default()),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        match tx.stuck_reason(now, self.base_fees.get(&tx.chain).copied()) {
+            Some(reason) => Cell::from(Span::styled(
+                format!("stuck: {reason}"),
+                Style::default().fg(Color::Red),
+            )),
+            None => Cell::from(Span::styled("pending", Style::default().fg(Color::Green))),
+        }
+    }
+}
+
+impl Default for PendingModal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn gwei(wei: u128) -> String {
+    format_units(U256::from(wei), "gwei").unwrap_or_else(|_| wei.to_string())
+}
+
+fn age(secs: u64) -> String {
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m", secs / 60),
+        _ => format!("{}h", secs / 3600),
+    }
+}
+
+impl Component for PendingModal {
+    type Command = PendingCommand;
+
+    fn init(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<()> {
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        command: &Self::Command,
+        ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        let count = ctx.state.pending_transactions.len();
+        match command {
+            PendingCommand::Next => {
+                if self.selected + 1 < count {
+                    self.selected += 1;
+                }
+            }
+            PendingCommand::Previous => self.selected = self.selected.saturating_sub(1),
+            PendingCommand::SpeedUp => self.start_replace(ReplacementKind::SpeedUp, ctx),
+            PendingCommand::Cancel => self.start_replace(ReplacementKind::Cancel, ctx),
+            PendingCommand::Refresh => {
+                self.status = None;
+                self.refresh(ctx);
+            }
+            PendingCommand::Open => return Ok(self.open(ctx)),
+            PendingCommand::InputChar(c) => {
+                if let Stage::Replace { passphrase, .. } = &mut self.stage {
+                    passphrase.push(*c);
+                }
+            }
+            PendingCommand::Backspace => {
+                if let Stage::Replace { passphrase, .. } = &mut self.stage {
+                    passphrase.pop();
+                }
+            }
+            PendingCommand::Submit => self.submit(ctx),
+            PendingCommand::Confirm => self.confirm(ctx),
+            PendingCommand::Back => match self.stage {
+                Stage::List => return Ok(Some(Action::CloseModal)),
+                Stage::Replace { .. } | Stage::Confirm { .. } => self.stage = Stage::List,
+                // The signer or the node is already at work; wait for its result.
+                Stage::Preparing { .. } | Stage::Sending(_) => {}
+            },
+            PendingCommand::Checked(result) => {
+                self.checking = self.checking.saturating_sub(1);
+                match result {
+                    Ok(check) => {
+                        if let Some(base_fee) = check.base_fee {
+                            self.base_fees.insert(check.chain.clone(), base_fee);
+                        }
+                        self.mined
+                            .insert((check.chain.clone(), check.from), check.mined_nonce);
+                    }
+                    Err(err) => self.status = Some(Err(err.clone())),
+                }
+            }
+            PendingCommand::Prepared(result) => {
+                if let Stage::Preparing { kind, hash } = &self.stage {
+                    self.stage = match result {
+                        Ok(prepared) => Stage::Confirm {
+                            kind: *kind,
+                            hash: hash.clone(),
+                            prepared: prepared.clone(),
+                        },
+                        Err(err) => {
+                            self.status = Some(Err(err.clone()));
+                            Stage::List
+                        }
+                    };
+                }
+            }
+            PendingCommand::Replaced(result) => {
+                if let Stage::Sending(kind) = self.stage {
+                    self.stage = Stage::List;
+                    self.status = Some(
+                        result
+                            .as_ref()
+                            .map(|hash| format!("Sent {} {}", kind.label(), short_hex(hash)))
+                            .map_err(Clone::clone),
+                    );
+                }
+            }
+        }
+        self.selected = self
+            .selected
+            .min(ctx.state.pending_transactions.len().saturating_sub(1));
+        Ok(None)
+    }
+
+    fn render(&mut self, frame: &mut Frame<'_>, area: Rect, ctx: &AppView<'_>) {
+        let modal_area = centered_rect(100, 22, area);
+        frame.render_widget(Clear, modal_area);
+
+        let queue = &ctx.state.pending_transactions;
+        let block = Block::default()
+            .title(Span::styled(
+                format!(
+                    "Pending transactions • {}{}",
+                    queue.len(),
+                    if self.checking > 0 {
+                        " • checking…"
+                    } else {
+                        ""
+                    }
+                ),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(3),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let muted = Style::default().fg(Color::DarkGray);
+        if queue.is_empty() {
+            frame.render_widget(
+                Paragraph::new(Span::styled(
+                    "Nothing pending. Transactions sent with W are tracked here until mined.",
+                    muted,
+                )),
+                chunks[0],
+            );
+        } else {
+            let now = unix_now();
+            let rows: Vec<Row<'_>> = queue
+                .iter()
+                .map(|tx| {
+                    let kind = tx
+                        .replacement
+                        .map(|kind| format!(" ({})", kind.label()))
+                        .unwrap_or_default();
+                    Row::new(vec![
+                        Cell::from(tx.chain.clone()),
+                        Cell::from(short_hex(&tx.from.to_checksum(None))),
+                        Cell::from(tx.nonce.to_string()),
+                        Cell::from(format!("{}{kind}", short_hex(&tx.hash))),
+                        Cell::from(format!(
+                            "{} / {}",
+                            gwei(tx.max_fee_per_gas),
+                            gwei(tx.max_priority_fee_per_gas)
+                        )),
+                        Cell::from(age(now.saturating_sub(tx.sent_at))),
+                        self.state_cell(tx, now),
+                    ])
+                })
+                .collect();
+            let table = Table::new(
+                rows,
+                [
+                    Constraint::Length(10),
+                    Constraint::Length(13),
+                    Constraint::Length(6),
+                    Constraint::Length(24),
+                    Constraint::Length(22),
+                    Constraint::Length(5),
+                    Constraint::Min(10),
+                ],
+            )
+            .header(
+                Row::new(vec![
+                    "Chain",
+                    "From",
+                    "Nonce",
+                    "Hash",
+                    "Fee cap / tip gwei",
+                    "Age",
+                    "State",
+                ])
+                .style(Style::default().fg(Color::Gray)),
+            )
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+            let mut state = TableState::default().with_selected(Some(self.selected));
+            frame.render_stateful_widget(table, chunks[0], &mut state);
+        }
+
+        let (lines, hint) = match &self.stage {
+            Stage::List => {
+                let line = match &self.status {
+                    Some(Ok(status)) => Line::from(Span::styled(
+                        status.clone(),
+                        Style::default().fg(Color::Green),
+                    )),
+                    Some(Err(err)) => {
+                        Line::from(Span::styled(err.clone(), Style::default().fg(Color::Red)))
+                    }
+                    None => Line::from(Span::styled(
+                        "New writes take the next nonce after the ones queued here.",
+                        muted,
+                    )),
+                };
+                (
+                    vec![line],
+                    "j/k Select • s Speed up • c Cancel • r Refresh • Enter Open • Esc Close",
+                )
+            }
+            Stage::Replace {
+                kind, passphrase, ..
+            } => {
+                let pending = queue.get(self.selected);
+                let nonce = pending.map(|tx| tx.nonce);
+                let currency = pending.map_or("ETH", |tx| chains::currency(&tx.chain));
+                let action = match kind {
+                    ReplacementKind::Cancel => "Cancel",
+                    _ => "Speed up",
+                };
+                let mut lines = vec![Line::from(Span::styled(
+                    format!(
+                        "{action} nonce {}: re-send it with fees raised at least 12.5%{}",
+                        nonce.unwrap_or_default(),
+                        if *kind == ReplacementKind::Cancel {
+                            format!(" as a 0 {currency} transfer to yourself")
+                        } else {
+                            String::new()
+                        }
+                    ),
+                    Style::default().fg(Color::Yellow),
+                ))];
+                let hint = match ctx.state.signer {
+                    Some(SignerSource::Ledger(_)) => {
+                        lines.push(Line::from(Span::styled(
+                            "Confirm the address on the Ledger; what it will show to sign comes next.",
+                            muted,
+                        )));
+                        "Enter Connect and price • Esc Back"
+                    }
+                    _ => {
+                        lines.push(Line::from(vec![
+                            Span::styled("Password  ", Style::default().fg(Color::Gray)),
+                            Span::styled(
+                                format!("{}_", "•".repeat(passphrase.chars().count())),
+                                Style::default().fg(Color::Yellow),
+                            ),
+                        ]));
+                        "Enter Unlock and price • Esc Back"
+                    }
+                };
+                (lines, hint)
+            }
+            Stage::Preparing { kind, .. } => (
+                vec![Line::from(Span::styled(
+                    format!("Unlocking the signer and pricing the {}…", kind.label()),
+                    muted,
+                ))],
+                "Please wait",
+            ),
+            Stage::Confirm { hash, prepared, .. } => {
+                let currency = queue
+                    .iter()
+                    .find(|tx| tx.hash == *hash)
+                    .map_or("ETH", |tx| chains::currency(&tx.chain));
+                let mut lines = vec![Line::from(format!(
+                    "Nonce {} • fee cap {} gwei • tip {} gwei • up to {} {currency} in fees",
+                    prepared.tx.nonce,
+                    gwei(prepared.tx.max_fee_per_gas),
+                    gwei(prepared.tx.max_priority_fee_per_gas),
+                    format_units(prepared.max_fee(), "ether").unwrap_or_default(),
+                ))];
+                if let Some(clear_signing) = &prepared.clear_signing {
+                    lines.push(Line::from(Span::styled(clear_signing.describe(), muted)));
+                    if clear_signing.is_blind() {
+                        lines.push(Line::from(format!(
+                            "Sign hash {:#x} (approve only if the Ledger shows it)",
+                            prepared.signing_hash()
+                        )));
+                    }
+                }
+                (lines, "y Sign and send • n/Esc Back")
+            }
+            Stage::Sending(kind) => (
+                vec![Line::from(Span::styled(
+                    format!("Sending {}…", kind.label()),
+                    muted,
+                ))],
+                "Please wait",
+            ),
+        };
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[1]);
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                hint,
+                Style::default().fg(Color::Gray),
+            ))),
+            chunks[2],
+        );
+    }
+
+    fn tick(&mut self, ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        if self.checking == 0
+            && self
+                .last_check
+                .is_none_or(|checked| checked.elapsed() >= CHECK_INTERVAL)
+        {
+            self.refresh(ctx);
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{App, SecretsState, demo};
+    use alloy::primitives::{B256, Bytes};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    /// A demo app without RPC endpoints, so nothing here reaches a node.
+    fn app() -> App {
+        let mut app = App::demo(None, demo::providers()).unwrap();
+        app.state.secrets = SecretsState::default();
+        app.state.signer = Some(SignerSource::Keystore("deployer.json".into()));
+        app
+    }
+
+    fn queued(nonce: u64, sent_at: u64) -> PendingTransaction {
+        PendingTransaction {
+            chain: "Ethereum".into(),
+            chain_id: 1,
+            hash: format!("{:#x}", B256::with_last_byte(nonce as u8)),
+            from: Address::repeat_byte(0xaa),
+            nonce,
+            to: Address::repeat_byte(0xbb),
+            input: Bytes::new(),
+            value: U256::ZERO,
+            gas_limit: 21_000,
+            max_fee_per_gas: 40,
+            max_priority_fee_per_gas: 8,
+            sent_at,
+            replacement: None,
+            replaced: Vec::new(),
+        }
+    }
+
+    fn press(modal: &mut PendingModal, app: &mut App, code: KeyCode) {
+        if let Some(command) = modal.command_from_key(KeyEvent::new(code, KeyModifiers::NONE)) {
+            modal.update(&command, &mut app.context()).unwrap();
+        }
+    }
+
+    fn screen(modal: &mut PendingModal, app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+        terminal
+            .draw(|frame| modal.render(frame, frame.area(), &app.view()))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn replacing(modal: &PendingModal) -> Option<(ReplacementKind, &str)> {
+        match &modal.stage {
+            Stage::Replace { kind, hash, .. } => Some((*kind, hash)),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn replacement_keys_act_on_the_selected_nonce() {
+        let mut app = app();
+        let stale = unix_now() - 600;
+        app.state.pending_transactions = vec![queued(7, stale), queued(8, stale)];
+        let mut modal = PendingModal::new();
+
+        press(&mut modal, &mut app, KeyCode::Char('j'));
+        press(&mut modal, &mut app, KeyCode::Char('c'));
+        let second = app.state.pending_transactions[1].hash.clone();
+        assert_eq!(
+            replacing(&modal),
+            Some((ReplacementKind::Cancel, second.as_str()))
+        );
+        let text = screen(&mut modal, &app);
+        assert!(text.contains("Cancel nonce 8"), "{text}");
+
+        press(&mut modal, &mut app, KeyCode::Esc);
+        press(&mut modal, &mut app, KeyCode::Char('k'));
+        press(&mut modal, &mut app, KeyCode::Char('s'));
+        let first = app.state.pending_transactions[0].hash.clone();
+        assert_eq!(
+            replacing(&modal),
+            Some((ReplacementKind::SpeedUp, first.as_str()))
+        );
+        let text = screen(&mut modal, &app);
+        assert!(text.contains("Speed up nonce 7"), "{text}");
+    }
+
+    #[test]
+    fn mined_and_unstuck_transactions_are_not_replaced() {
+        let mut app = app();
+        let stale = unix_now() - 600;
+        app.state.pending_transactions = vec![queued(7, stale), queued(8, unix_now())];
+        let mut modal = PendingModal::new();
+
+        // Sent just now, under the base fee cap: not stuck yet.
+        press(&mut modal, &mut app, KeyCode::Char('j'));
+        press(&mut modal, &mut app, KeyCode::Char('s'));
+        assert_eq!(replacing(&modal), None);
+        assert!(
+            matches!(&modal.status, Some(Err(err)) if err.contains("Nonce 8 is not stuck")),
+            "{:?}",
+            modal.status
+        );
+
+        // Nonce 7 is mined, though the queue has not been pruned yet.
+        let check = AccountCheck {
+            chain: "Ethereum".into(),
+            from: Address::repeat_byte(0xaa),
+            mined_nonce: 8,
+            base_fee: Some(10),
+        };
+        modal
+            .update(&PendingCommand::Checked(Ok(check)), &mut app.context())
+            .unwrap();
+        press(&mut modal, &mut app, KeyCode::Char('k'));
+        press(&mut modal, &mut app, KeyCode::Char('c'));
+        assert_eq!(replacing(&modal), None);
+        let text = screen(&mut modal, &app);
+        assert!(text.contains("Nonce 7 is already mined"), "{text}");
+    }
+}
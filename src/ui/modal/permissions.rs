@@ -0,0 +1,258 @@
+use crate::{
+    app::{
+        Action, AppContext, AppResult, AppView,
+        permissions::{PermissionGraph, PermissionRole},
+    },
+    components::Component,
+    ui::util::{centered_rect, short_hex},
+};
+use alloy::primitives::Address;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+use std::collections::{BTreeSet, HashMap};
+
+#[derive(Debug, Clone)]
+pub enum PermissionsCommand {
+    Next,
+    Previous,
+    Toggle,
+    Close,
+    Loaded(Result<PermissionGraph, String>),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Row {
+    Holder(Address),
+    Edge {
+        target: Address,
+        role: PermissionRole,
+    },
+}
+
+/// Navigable holder → contract tree of privileged roles across favorited contracts.
+#[derive(Debug)]
+pub struct PermissionsModal {
+    chain: String,
+    labels: HashMap<Address, String>,
+    graph: Option<Result<PermissionGraph, String>>,
+    expanded: BTreeSet<Address>,
+    selected: usize,
+}
+
+impl PermissionsModal {
+    pub fn new(chain: String, labels: HashMap<Address, String>) -> Self {
+        Self {
+            chain,
+            labels,
+            graph: None,
+            expanded: BTreeSet::new(),
+            selected: 0,
+        }
+    }
+
+    pub fn command_from_key(event: KeyEvent) -> Option<PermissionsCommand> {
+        match event.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(PermissionsCommand::Close),
+            KeyCode::Char('j') | KeyCode::Down => Some(PermissionsCommand::Next),
+            KeyCode::Char('k') | KeyCode::Up => Some(PermissionsCommand::Previous),
+            KeyCode::Enter | KeyCode::Char('l') | KeyCode::Char('h') => {
+                Some(PermissionsCommand::Toggle)
+            }
+            _ => None,
+        }
+    }
+
+    fn rows(&self) -> Vec<Row> {
+        let Some(Ok(graph)) = self.graph.as_ref() else {
+            return Vec::new();
+        };
+        let mut rows = Vec::new();
+        for (holder, edges) in graph.by_holder() {
+            rows.push(Row::Holder(holder));
+            if self.expanded.contains(&holder) {
+                rows.extend(edges.iter().map(|edge| Row::Edge {
+                    target: edge.target,
+                    role: edge.role,
+                }));
+            }
+        }
+        rows
+    }
+
+    fn describe(&self, address: &Address) -> String {
+        match self.labels.get(address) {
+            Some(label) => format!("{label} ({})", short_hex(&address.to_string())),
+            None => short_hex(&address.to_string()),
+        }
+    }
+}
+
+impl Component for PermissionsModal {
+    type Command = PermissionsCommand;
+
+    fn init(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<()> {
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        command: &Self::Command,
+        _ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        let rows = self.rows();
+        match command {
+            PermissionsCommand::Next => {
+                if self.selected + 1 < rows.len() {
+                    self.selected += 1;
+                }
+            }
+            PermissionsCommand::Previous => self.selected = self.selected.saturating_sub(1),
+            PermissionsCommand::Toggle => {
+                // Toggling from a child row collapses its parent holder.
+                let holder = rows[..rows.len().min(self.selected + 1)]
+                    .iter()
+                    .rev()
+                    .find_map(|row| match row {
+                        Row::Holder(holder) => Some(*holder),
+                        Row::Edge { .. } => None,
+                    });
+                if let Some(holder) = holder {
+                    if !self.expanded.remove(&holder) {
+                        self.expanded.insert(holder);
+                    }
+                    self.selected = self
+                        .rows()
+                        .iter()
+                        .position(|row| matches!(row, Row::Holder(h) if *h == holder))
+                        .unwrap_or(0);
+                }
+            }
+            PermissionsCommand::Loaded(result) => {
+                self.graph = Some(result.clone());
+                self.selected = 0;
+            }
+            PermissionsCommand::Close => return Ok(Some(Action::CloseModal)),
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, frame: &mut Frame<'_>, area: Rect, _ctx: &AppView<'_>) {
+        let modal_area = centered_rect(88, 26, area);
+        frame.render_widget(Clear, modal_area);
+
+        let block = Block::default()
+            .title(Span::styled(
+                format!("Permissions • favorited contracts on {}", self.chain),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Min(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let graph = match self.graph.as_ref() {
+            None => {
+                frame.render_widget(Paragraph::new("Resolving owners and admins…"), chunks[0]);
+                return;
+            }
+            Some(Err(err)) => {
+                frame.render_widget(
+                    Paragraph::new(format!("Failed to build permission graph: {err}"))
+                        .style(Style::default().fg(Color::Red)),
+                    chunks[0],
+                );
+                return;
+            }
+            Some(Ok(graph)) => graph,
+        };
+
+        let risks = graph.single_points_of_failure();
+        let mut summary = vec![Line::from(format!(
+            "{} contracts • {} privileged holders",
+            graph.targets.len(),
+            graph.by_holder().len()
+        ))];
+        summary.push(match risks.first() {
+            Some((holder, count)) => Line::from(Span::styled(
+                format!(
+                    "⚠ {} single point(s) of failure • EOA {} controls {count} contracts",
+                    risks.len(),
+                    self.describe(holder)
+                ),
+                Style::default().fg(Color::Yellow),
+            )),
+            None => Line::from(Span::styled(
+                "No EOA controls more than one contract",
+                Style::default().fg(Color::Green),
+            )),
+        });
+        frame.render_widget(Paragraph::new(summary), chunks[0]);
+
+        let risky: BTreeSet<Address> = risks.iter().map(|(holder, _)| *holder).collect();
+        let items: Vec<ListItem> = self
+            .rows()
+            .into_iter()
+            .map(|row| match row {
+                Row::Holder(holder) => {
+                    let marker = if self.expanded.contains(&holder) {
+                        "▾"
+                    } else {
+                        "▸"
+                    };
+                    let style = if risky.contains(&holder) {
+                        Style::default().fg(Color::Yellow)
+                    } else {
+                        Style::default()
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!("{marker} {}", self.describe(&holder)), style),
+                        Span::styled(
+                            format!(" [{}]", graph.kind(&holder).label()),
+                            Style::default().fg(Color::Gray),
+                        ),
+                    ]))
+                }
+                Row::Edge { target, role } => ListItem::new(Line::from(Span::styled(
+                    format!("    {} of {}", role.label(), self.describe(&target)),
+                    Style::default().fg(Color::Gray),
+                ))),
+            })
+            .collect();
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .bg(Color::Blue)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(list, chunks[1], &mut state);
+
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "j/k Move • Enter Expand/collapse • Esc Close",
+                Style::default().fg(Color::Gray),
+            ))),
+            chunks[2],
+        );
+    }
+
+    fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,170 @@
+use crate::{
+    app::{Action, AppContext, AppResult, AppView, pipe::PipeOutput},
+    components::Component,
+    ui::util::centered_rect,
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+#[derive(Debug, Clone)]
+pub enum PipeCommand {
+    ScrollDown,
+    ScrollUp,
+    PageDown,
+    PageUp,
+    Top,
+    Bottom,
+    Copy,
+    Close,
+}
+
+/// What a `:pipe` command printed for the current tab's JSON.
+#[derive(Debug)]
+pub struct PipeModal {
+    output: PipeOutput,
+    scroll: usize,
+    /// Body height at the last render, for paging.
+    page: usize,
+}
+
+impl PipeModal {
+    pub fn new(output: PipeOutput) -> Self {
+        Self {
+            output,
+            scroll: 0,
+            page: 10,
+        }
+    }
+
+    pub fn command_from_key(event: KeyEvent) -> Option<PipeCommand> {
+        match event.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => Some(PipeCommand::Close),
+            KeyCode::Char('j') | KeyCode::Down => Some(PipeCommand::ScrollDown),
+            KeyCode::Char('k') | KeyCode::Up => Some(PipeCommand::ScrollUp),
+            KeyCode::PageDown | KeyCode::Char(' ') => Some(PipeCommand::PageDown),
+            KeyCode::PageUp => Some(PipeCommand::PageUp),
+            KeyCode::Char('g') | KeyCode::Home => Some(PipeCommand::Top),
+            KeyCode::Char('G') | KeyCode::End => Some(PipeCommand::Bottom),
+            KeyCode::Char('y') | KeyCode::Char('c') => Some(PipeCommand::Copy),
+            _ => None,
+        }
+    }
+
+    fn line_count(&self) -> usize {
+        self.output.stdout.len() + self.output.stderr.len()
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.line_count().saturating_sub(self.page)
+    }
+}
+
+impl Component for PipeModal {
+    type Command = PipeCommand;
+
+    fn init(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<()> {
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        command: &Self::Command,
+        _ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        match command {
+            PipeCommand::ScrollDown => self.scroll = (self.scroll + 1).min(self.max_scroll()),
+            PipeCommand::ScrollUp => self.scroll = self.scroll.saturating_sub(1),
+            PipeCommand::PageDown => self.scroll = (self.scroll + self.page).min(self.max_scroll()),
+            PipeCommand::PageUp => self.scroll = self.scroll.saturating_sub(self.page),
+            PipeCommand::Top => self.scroll = 0,
+            PipeCommand::Bottom => self.scroll = self.max_scroll(),
+            PipeCommand::Copy => {
+                return Ok(Some(Action::CopyToClipboard(self.output.stdout.join("\n"))));
+            }
+            PipeCommand::Close => return Ok(Some(Action::CloseModal)),
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, frame: &mut Frame<'_>, area: Rect, _ctx: &AppView<'_>) {
+        let modal_area = centered_rect(90, 30, area);
+        frame.render_widget(Clear, modal_area);
+
+        let block = Block::default()
+            .title(Span::styled(
+                format!("Pipe • {}", self.output.command),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+        self.page = (chunks[0].height as usize).max(1);
+        self.scroll = self.scroll.min(self.max_scroll());
+
+        let output = &self.output;
+        if self.line_count() == 0 {
+            frame.render_widget(
+                Paragraph::new("No output").style(Style::default().fg(Color::DarkGray)),
+                chunks[0],
+            );
+        } else {
+            let lines: Vec<Line> =
+                output
+                    .stdout
+                    .iter()
+                    .map(|line| Line::from(line.as_str()))
+                    .chain(output.stderr.iter().map(|line| {
+                        Line::from(line.as_str()).style(Style::default().fg(Color::Red))
+                    }))
+                    .skip(self.scroll)
+                    .take(self.page)
+                    .collect();
+            frame.render_widget(Paragraph::new(lines), chunks[0]);
+        }
+
+        let mut status = Vec::new();
+        if let Some(failure) = &output.failure {
+            status.push(Span::styled(
+                format!("⚠ {failure} "),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        if output.hidden > 0 {
+            status.push(Span::styled(
+                format!("{} more lines not shown", output.hidden),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        frame.render_widget(Paragraph::new(Line::from(status)), chunks[1]);
+
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "j/k Scroll • g/G Top/Bottom • y Copy output • Esc Close",
+                Style::default().fg(Color::Gray),
+            ))),
+            chunks[2],
+        );
+    }
+
+    fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,371 @@
+use crate::{
+    app::{
+        Action, AppContext, AppResult, AppView, SelectedEntity, chains,
+        watch::{WatchState, format_eth},
+    },
+    components::Component,
+    storage::{AlertOutputs, AlertRule, GroupMember, WatchGroupRecord},
+    ui::util::{centered_rect, short_hex},
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+#[derive(Debug, Clone)]
+pub enum PortfolioCommand {
+    Next,
+    Previous,
+    StartNewGroup,
+    InputChar(char),
+    Backspace,
+    ConfirmInput,
+    CancelInput,
+    AddSelected,
+    RemoveSelected,
+    ToggleRule(AlertRule),
+    CycleAlerts,
+    DeleteGroup,
+    Close,
+}
+
+/// Watch groups with aggregate balances, alert rules, and the latest alert.
+#[derive(Debug, Default)]
+pub struct PortfolioModal {
+    selected: usize,
+    /// Name being typed for a new group, when in input mode.
+    new_group: Option<String>,
+    message: Option<String>,
+}
+
+impl PortfolioModal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn command_from_key(&self, event: KeyEvent) -> Option<PortfolioCommand> {
+        if self.new_group.is_some() {
+            return match event.code {
+                KeyCode::Esc => Some(PortfolioCommand::CancelInput),
+                KeyCode::Enter => Some(PortfolioCommand::ConfirmInput),
+                KeyCode::Backspace => Some(PortfolioCommand::Backspace),
+                KeyCode::Char(c) if !event.modifiers.contains(KeyModifiers::CONTROL) => {
+                    Some(PortfolioCommand::InputChar(c))
+                }
+                _ => None,
+            };
+        }
+        match event.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(PortfolioCommand::Close),
+            KeyCode::Char('j') | KeyCode::Down => Some(PortfolioCommand::Next),
+            KeyCode::Char('k') | KeyCode::Up => Some(PortfolioCommand::Previous),
+            KeyCode::Char('n') => Some(PortfolioCommand::StartNewGroup),
+            KeyCode::Char('a') => Some(PortfolioCommand::AddSelected),
+            KeyCode::Char('x') => Some(PortfolioCommand::RemoveSelected),
+            KeyCode::Char('b') => Some(PortfolioCommand::ToggleRule(AlertRule::BalanceChanged)),
+            KeyCode::Char('t') => Some(PortfolioCommand::ToggleRule(AlertRule::MemberTransacted)),
+            KeyCode::Char('o') => Some(PortfolioCommand::CycleAlerts),
+            KeyCode::Char('d') => Some(PortfolioCommand::DeleteGroup),
+            _ => None,
+        }
+    }
+
+    fn selected_member(ctx: &AppContext<'_>) -> Option<GroupMember> {
+        match ctx.state.selected.as_ref() {
+            Some(SelectedEntity::Address(addr)) => Some(GroupMember {
+                address: addr.address.clone(),
+                chain: addr.chain.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Apply `edit` to the highlighted group and persist it.
+    fn edit_group(
+        &mut self,
+        ctx: &mut AppContext<'_>,
+        edit: impl FnOnce(&mut WatchGroupRecord) -> Option<String>,
+    ) -> AppResult<()> {
+        let Some(group) = ctx.state.watch.groups.get_mut(self.selected) else {
+            self.message = Some("Create a group first with n".into());
+            return Ok(());
+        };
+        self.message = edit(group);
+        let group = group.clone();
+        ctx.storage.write("watch group", move |storage| {
+            storage.groups().upsert(&group)
+        });
+        Ok(())
+    }
+}
+
+impl Component for PortfolioModal {
+    type Command = PortfolioCommand;
+
+    fn init(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<()> {
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        command: &Self::Command,
+        ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        match command {
+            PortfolioCommand::Next => {
+                if self.selected + 1 < ctx.state.watch.groups.len() {
+                    self.selected += 1;
+                }
+            }
+            PortfolioCommand::Previous => self.selected = self.selected.saturating_sub(1),
+            PortfolioCommand::StartNewGroup => {
+                self.message = None;
+                self.new_group = Some(String::new());
+            }
+            PortfolioCommand::InputChar(c) => {
+                if let Some(name) = self.new_group.as_mut() {
+                    name.push(*c);
+                }
+            }
+            PortfolioCommand::Backspace => {
+                if let Some(name) = self.new_group.as_mut() {
+                    name.pop();
+                }
+            }
+            PortfolioCommand::CancelInput => self.new_group = None,
+            PortfolioCommand::ConfirmInput => {
+                let name = self.new_group.take().unwrap_or_default();
+                let name = name.trim();
+                if name.is_empty() {
+                    return Ok(None);
+                }
+                if ctx
+                    .state
+                    .watch
+                    .groups
+                    .iter()
+                    .any(|group| group.name == name)
+                {
+                    self.message = Some(format!("Group \"{name}\" already exists"));
+                    return Ok(None);
+                }
+                let group = WatchGroupRecord {
+                    name: name.to_string(),
+                    members: Vec::new(),
+                    rules: Vec::new(),
+                    alerts: AlertOutputs::default(),
+                };
+                let saved = group.clone();
+                ctx.storage.write("watch group", move |storage| {
+                    storage.groups().upsert(&saved)
+                });
+                ctx.state.watch.groups.push(group);
+                self.selected = ctx.state.watch.groups.len() - 1;
+            }
+            PortfolioCommand::AddSelected => {
+                let Some(member) = Self::selected_member(ctx) else {
+                    self.message = Some("Select an address first".into());
+                    return Ok(None);
+                };
+                self.edit_group(ctx, |group| {
+                    if group.members.contains(&member) {
+                        return Some("Already a member".into());
+                    }
+                    let message = format!("Added {} to {}", short_hex(&member.address), group.name);
+                    group.members.push(member);
+                    Some(message)
+                })?;
+            }
+            PortfolioCommand::RemoveSelected => {
+                let Some(member) = Self::selected_member(ctx) else {
+                    self.message = Some("Select an address first".into());
+                    return Ok(None);
+                };
+                self.edit_group(ctx, |group| {
+                    let before = group.members.len();
+                    group.members.retain(|existing| existing != &member);
+                    (group.members.len() == before).then(|| "Not a member".into())
+                })?;
+            }
+            PortfolioCommand::ToggleRule(rule) => {
+                self.edit_group(ctx, |group| {
+                    if let Some(index) = group.rules.iter().position(|r| r == rule) {
+                        group.rules.remove(index);
+                    } else {
+                        group.rules.push(*rule);
+                    }
+                    None
+                })?;
+            }
+            PortfolioCommand::CycleAlerts => {
+                self.edit_group(ctx, |group| {
+                    group.alerts = group.alerts.next();
+                    Some(format!("Alerts: {}", group.alerts.label()))
+                })?;
+            }
+            PortfolioCommand::DeleteGroup => {
+                if self.selected < ctx.state.watch.groups.len() {
+                    let group = ctx.state.watch.groups.remove(self.selected);
+                    let name = group.name.clone();
+                    ctx.storage
+                        .write("watch group", move |storage| storage.groups().remove(&name));
+                    self.selected = self.selected.saturating_sub(1);
+                    self.message = Some(format!("Deleted {}", group.name));
+                }
+            }
+            PortfolioCommand::Close => return Ok(Some(Action::CloseModal)),
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, frame: &mut Frame<'_>, area: Rect, ctx: &AppView<'_>) {
+        let modal_area = centered_rect(84, 24, area);
+        frame.render_widget(Clear, modal_area);
+
+        let block = Block::default()
+            .title(Span::styled(
+                "Portfolio • Watch groups",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage(50),
+                Constraint::Min(3),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let watch = &ctx.state.watch;
+        if watch.groups.is_empty() {
+            frame.render_widget(
+                Paragraph::new("No watch groups yet • press n to create one")
+                    .style(Style::default().fg(Color::DarkGray)),
+                chunks[0],
+            );
+        } else {
+            let items: Vec<ListItem> = watch
+                .groups
+                .iter()
+                .map(|group| group_summary(watch, group))
+                .collect();
+            let list = List::new(items).highlight_style(
+                Style::default()
+                    .bg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            );
+            let mut state = ListState::default().with_selected(Some(self.selected));
+            frame.render_stateful_widget(list, chunks[0], &mut state);
+        }
+
+        let members: Vec<Line> = watch
+            .groups
+            .get(self.selected)
+            .map(|group| {
+                group
+                    .members
+                    .iter()
+                    .map(|member| {
+                        let balance = watch
+                            .snapshot(member)
+                            .map(|snapshot| {
+                                format!(
+                                    "{} {}",
+                                    format_eth(snapshot.balance_wei),
+                                    chains::currency(&member.chain)
+                                )
+                            })
+                            .unwrap_or_else(|| "not polled yet".into());
+                        Line::from(format!(
+                            "  {} ({}) • {balance}",
+                            short_hex(&member.address),
+                            member.chain
+                        ))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        frame.render_widget(
+            Paragraph::new(members).block(Block::default().borders(Borders::TOP).title("Members")),
+            chunks[1],
+        );
+
+        let status = match (&self.new_group, &self.message) {
+            (Some(name), _) => Line::from(Span::styled(
+                format!("New group name › {name}_"),
+                Style::default().fg(Color::Yellow),
+            )),
+            (None, Some(message)) => Line::from(Span::styled(
+                message.clone(),
+                Style::default().fg(Color::Yellow),
+            )),
+            (None, None) => Line::from(""),
+        };
+        frame.render_widget(Paragraph::new(status), chunks[2]);
+
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "n New • a/x Add/remove selected address • b/t Toggle rules • o Alerts • d Delete • Esc Close",
+                Style::default().fg(Color::Gray),
+            ))),
+            chunks[3],
+        );
+    }
+
+    fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        Ok(None)
+    }
+}
+
+fn group_summary<'a>(watch: &WatchState, group: &'a WatchGroupRecord) -> ListItem<'a> {
+    let (totals, known) = watch.group_balance(group);
+    let total = if totals.is_empty() {
+        "0 ETH".to_string()
+    } else {
+        totals
+            .iter()
+            .map(|(currency, total)| format!("{} {currency}", format_eth(*total)))
+            .collect::<Vec<_>>()
+            .join(" + ")
+    };
+    let rules = if group.rules.is_empty() {
+        "no alerts".to_string()
+    } else {
+        group
+            .rules
+            .iter()
+            .map(|rule| rule.label())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            group.name.as_str(),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(format!(
+            " • {} members • {total} ({known} polled) • {rules}{}",
+            group.members.len(),
+            group.alerts.marker()
+        )),
+    ])];
+    if let Some(alert) = watch.latest_alert(&group.name) {
+        lines.push(Line::from(Span::styled(
+            format!("    ⚠ {}", alert.message),
+            Style::default().fg(Color::Yellow),
+        )));
+    }
+    ListItem::new(lines)
+}
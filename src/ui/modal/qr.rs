@@ -0,0 +1,149 @@
+use crate::{
+    app::{Action, AppContext, AppResult, AppView, qr::QrMatrix},
+    components::Component,
+    ui::util::centered_rect,
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+#[derive(Debug, Clone)]
+pub enum QrCommand {
+    Copy,
+    Close,
+}
+
+/// The selected address as a QR code for a mobile wallet to scan. Drawn with
+/// half blocks; the app lays a sharper image over them on terminals with
+/// an image protocol, see [`QrModal::image_area`].
+#[derive(Debug)]
+pub struct QrModal {
+    address: String,
+    chain: String,
+    matrix: QrMatrix,
+    lines: Vec<String>,
+    image_area: Option<Rect>,
+}
+
+impl QrModal {
+    pub fn new(address: String, chain: String) -> Result<Self, String> {
+        let matrix = QrMatrix::encode(&address)?;
+        let lines = matrix.half_block_lines();
+        Ok(Self {
+            address,
+            chain,
+            matrix,
+            lines,
+            image_area: None,
+        })
+    }
+
+    pub fn command_from_key(event: KeyEvent) -> Option<QrCommand> {
+        match event.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => Some(QrCommand::Close),
+            KeyCode::Char('c') | KeyCode::Char('y') => Some(QrCommand::Copy),
+            _ => None,
+        }
+    }
+
+    pub fn matrix(&self) -> &QrMatrix {
+        &self.matrix
+    }
+
+    /// Cells the code took in the last frame, `None` when it did not fit.
+    pub fn image_area(&self) -> Option<Rect> {
+        self.image_area
+    }
+}
+
+impl Component for QrModal {
+    type Command = QrCommand;
+
+    fn init(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<()> {
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        command: &Self::Command,
+        _ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        Ok(Some(match command {
+            QrCommand::Copy => Action::CopyToClipboard(self.address.clone()),
+            QrCommand::Close => Action::CloseModal,
+        }))
+    }
+
+    fn render(&mut self, frame: &mut Frame<'_>, area: Rect, _ctx: &AppView<'_>) {
+        let code_width = self.matrix.size as u16;
+        let code_height = self.lines.len() as u16;
+        let width = code_width.max(self.address.len() as u16) + 4;
+        let modal_area = centered_rect(width, code_height + 6, area);
+        frame.render_widget(Clear, modal_area);
+
+        let block = Block::default()
+            .title(Span::styled(
+                format!("Address QR • {}", self.chain),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(code_height),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let fits = chunks[0].width >= code_width && chunks[0].height >= code_height;
+        if fits {
+            let code_area = Rect {
+                x: chunks[0].x + (chunks[0].width - code_width) / 2,
+                width: code_width,
+                ..chunks[0]
+            };
+            let code_style = Style::default().fg(Color::Black).bg(Color::White);
+            let lines: Vec<Line> = self
+                .lines
+                .iter()
+                .map(|line| Line::from(Span::styled(line.clone(), code_style)))
+                .collect();
+            frame.render_widget(Paragraph::new(lines), code_area);
+            self.image_area = Some(code_area);
+        } else {
+            frame.render_widget(
+                Paragraph::new("Enlarge the terminal to show the QR code")
+                    .style(Style::default().fg(Color::Yellow))
+                    .centered(),
+                chunks[0],
+            );
+            self.image_area = None;
+        }
+
+        frame.render_widget(Paragraph::new(self.address.as_str()).centered(), chunks[2]);
+        let footer = Paragraph::new(Line::from(Span::styled(
+            "c Copy address • Esc Close",
+            Style::default().fg(Color::Gray),
+        )))
+        .centered();
+        frame.render_widget(footer, chunks[4]);
+    }
+
+    fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,365 @@
+use crate::{
+    app::{
+        Action, AddressRef, AppContext, AppResult, AppView, Message, SelectedEntity, chains,
+        safe::{self, QueuedTransaction, SafeQueue},
+        scheduler::{Priority, host_key},
+    },
+    components::Component,
+    ui::util::{centered_rect, short_hex},
+};
+use alloy::primitives::{Address, U256, utils::format_units};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+#[derive(Debug, Clone)]
+pub enum SafeQueueCommand {
+    Next,
+    Previous,
+    OpenTarget,
+    Refresh,
+    Close,
+    Loaded(Result<SafeQueue, String>),
+}
+
+/// Unexecuted multisig transactions of a Safe from the Safe Transaction
+/// Service, with collected confirmations and the decoded calls.
+#[derive(Debug)]
+pub struct SafeQueueModal {
+    chain: String,
+    safe: Address,
+    queue: Option<Result<SafeQueue, String>>,
+    selected: usize,
+}
+
+impl SafeQueueModal {
+    pub fn new(chain: String, safe: Address) -> Self {
+        Self {
+            chain,
+            safe,
+            queue: None,
+            selected: 0,
+        }
+    }
+
+    pub fn command_from_key(event: KeyEvent) -> Option<SafeQueueCommand> {
+        match event.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(SafeQueueCommand::Close),
+            KeyCode::Char('j') | KeyCode::Down => Some(SafeQueueCommand::Next),
+            KeyCode::Char('k') | KeyCode::Up => Some(SafeQueueCommand::Previous),
+            KeyCode::Enter => Some(SafeQueueCommand::OpenTarget),
+            KeyCode::Char('r') => Some(SafeQueueCommand::Refresh),
+            _ => None,
+        }
+    }
+
+    fn transactions(&self) -> &[QueuedTransaction] {
+        match &self.queue {
+            Some(Ok(queue)) => &queue.transactions,
+            _ => &[],
+        }
+    }
+
+    fn name(&self, view: &AppView<'_>, address: &Address) -> String {
+        let address = address.to_checksum(None);
+        match view.state.labels.lookup(&self.chain, &address) {
+            Some(label) => format!("{} ({})", label.name, short_hex(&address)),
+            None => address,
+        }
+    }
+
+    fn detail_lines(
+        &self,
+        view: &AppView<'_>,
+        queue: &SafeQueue,
+        tx: &QueuedTransaction,
+    ) -> Vec<Line<'static>> {
+        let gray = Style::default().fg(Color::Gray);
+        let field = |label: &str, value: String| {
+            Line::from(vec![
+                Span::styled(format!("{label:<14}"), gray),
+                Span::raw(value),
+            ])
+        };
+        let mut lines = vec![
+            field("Safe tx hash", format!("{:#x}", tx.safe_tx_hash)),
+            field("To", self.name(view, &tx.to)),
+            field("Value", native(tx.value, &self.chain)),
+        ];
+        if tx.delegate_call {
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:<14}", "Operation"), gray),
+                Span::styled(
+                    "DELEGATECALL: the target runs with the Safe's storage and funds",
+                    Style::default().fg(Color::Red),
+                ),
+            ]));
+        }
+        if !tx.submitted.is_empty() {
+            lines.push(field("Proposed", tx.submitted.clone()));
+        }
+        if queue.has_conflict(tx) {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "Nonce {} has several proposals; executing one voids the others.",
+                    tx.nonce
+                ),
+                Style::default().fg(Color::Yellow),
+            )));
+        }
+        lines.push(Line::from(""));
+        match tx.decoded.split_first() {
+            Some((call, inner)) => {
+                lines.push(field("Call", call.clone()));
+                lines.extend(inner.iter().map(|call| Line::from(format!("    {call}"))));
+            }
+            None if tx.data.is_empty() => lines.push(field(
+                "Call",
+                format!("plain {} transfer", chains::currency(&self.chain)),
+            )),
+            None => lines.push(field(
+                "Calldata",
+                format!(
+                    "{} (not decoded by the service)",
+                    short_hex(&tx.data.to_string())
+                ),
+            )),
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(
+                "Confirmations {}/{}",
+                tx.confirmations.len(),
+                tx.confirmations_required
+            ),
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+        let missing = queue.missing_owners(tx);
+        for owner in &queue.owners {
+            let signed = !missing.contains(owner);
+            lines.push(Line::from(vec![
+                Span::styled(
+                    if signed { "  ✓ " } else { "  · " },
+                    Style::default().fg(if signed {
+                        Color::Green
+                    } else {
+                        Color::DarkGray
+                    }),
+                ),
+                Span::raw(self.name(view, owner)),
+            ]));
+        }
+        lines
+    }
+}
+
+/// A wei amount in the native currency of `chain`.
+fn native(wei: U256, chain: &str) -> String {
+    format!(
+        "{} {}",
+        format_units(wei, "ether").unwrap_or_else(|_| wei.to_string()),
+        chains::currency(chain)
+    )
+}
+
+impl Component for SafeQueueModal {
+    type Command = SafeQueueCommand;
+
+    fn init(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<()> {
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        command: &Self::Command,
+        ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        match command {
+            SafeQueueCommand::Next => {
+                if self.selected + 1 < self.transactions().len() {
+                    self.selected += 1;
+                }
+            }
+            SafeQueueCommand::Previous => self.selected = self.selected.saturating_sub(1),
+            SafeQueueCommand::OpenTarget => {
+                if let Some(tx) = self.transactions().get(self.selected) {
+                    let address = tx.to.to_checksum(None);
+                    return Ok(Some(Action::SelectionChanged(SelectedEntity::Address(
+                        AddressRef {
+                            label: format!("Safe target {}", short_hex(&address)),
+                            address,
+                            chain: self.chain.clone(),
+                        },
+                    ))));
+                }
+            }
+            SafeQueueCommand::Refresh => {
+                self.queue = None;
+                let (chain, safe) = (self.chain.clone(), self.safe);
+                let stats = ctx.commands.providers().rpc.stats().clone();
+                let service = chains::resolve_chain(&chain).and_then(|info| info.safe_service);
+                let host = host_key(service, &chain);
+                ctx.commands
+                    .spawn_scheduled(host, Priority::Foreground, move || async move {
+                        Message::SafeQueueLoaded(safe::fetch_queue(&stats, chain, safe).await)
+                    });
+            }
+            SafeQueueCommand::Close => return Ok(Some(Action::CloseModal)),
+            SafeQueueCommand::Loaded(result) => {
+                self.queue = Some(result.clone());
+                self.selected = self
+                    .selected
+                    .min(self.transactions().len().saturating_sub(1));
+            }
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, frame: &mut Frame<'_>, area: Rect, ctx: &AppView<'_>) {
+        let modal_area = centered_rect(100, 30, area);
+        frame.render_widget(Clear, modal_area);
+
+        let mut title = format!(
+            "Safe queue • {} • {}",
+            short_hex(&self.safe.to_checksum(None)),
+            self.chain
+        );
+        if let Some(Ok(queue)) = &self.queue {
+            title.push_str(&format!(
+                " • {} of {} owners • next nonce {}",
+                queue.threshold,
+                queue.owners.len(),
+                queue.nonce
+            ));
+        }
+        let block = Block::default()
+            .title(Span::styled(
+                title,
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(8),
+                Constraint::Min(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "j/k Select • Enter Open target • r Refresh • Esc Close",
+                Style::default().fg(Color::Gray),
+            ))),
+            chunks[2],
+        );
+
+        let queue = match &self.queue {
+            None => {
+                frame.render_widget(
+                    Paragraph::new(
+                        "Loading queued transactions from the Safe Transaction Service…",
+                    ),
+                    chunks[0],
+                );
+                return;
+            }
+            Some(Err(err)) => {
+                frame.render_widget(
+                    Paragraph::new(Span::styled(err.clone(), Style::default().fg(Color::Red)))
+                        .wrap(Wrap { trim: false }),
+                    chunks[0],
+                );
+                return;
+            }
+            Some(Ok(queue)) => queue,
+        };
+        if queue.transactions.is_empty() {
+            frame.render_widget(
+                Paragraph::new("No queued transactions: nothing is waiting for signatures."),
+                chunks[0],
+            );
+            return;
+        }
+
+        let items: Vec<ListItem<'_>> = queue
+            .transactions
+            .iter()
+            .map(|tx| {
+                let (status, color) = if tx.is_executable() {
+                    ("ready", Color::Green)
+                } else {
+                    ("needs signatures", Color::Yellow)
+                };
+                let call =
+                    tx.decoded
+                        .first()
+                        .cloned()
+                        .unwrap_or_else(|| match tx.data.is_empty() {
+                            true => format!("send {}", native(tx.value, &self.chain)),
+                            false => "undecoded call".into(),
+                        });
+                let mut spans = vec![
+                    Span::raw(format!("#{:<5}", tx.nonce)),
+                    Span::styled(
+                        format!(
+                            "{}/{} {status:<16}",
+                            tx.confirmations.len(),
+                            tx.confirmations_required
+                        ),
+                        Style::default().fg(color),
+                    ),
+                    Span::raw(format!(
+                        "{} → {call}",
+                        ctx.state
+                            .labels
+                            .display(&self.chain, &tx.to.to_checksum(None))
+                    )),
+                ];
+                if tx.delegate_call {
+                    spans.push(Span::styled(
+                        "  delegatecall",
+                        Style::default().fg(Color::Red),
+                    ));
+                }
+                if queue.has_conflict(tx) {
+                    spans.push(Span::styled(
+                        "  conflicting nonce",
+                        Style::default().fg(Color::Yellow),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+        let list = List::new(items)
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("› ");
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(list, chunks[0], &mut state);
+
+        if let Some(tx) = queue.transactions.get(self.selected) {
+            let lines = self.detail_lines(ctx, queue, tx);
+            frame.render_widget(
+                Paragraph::new(lines)
+                    .wrap(Wrap { trim: false })
+                    .block(Block::default().borders(Borders::TOP)),
+                chunks[1],
+            );
+        }
+    }
+
+    fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,219 @@
+use crate::{
+    app::{Action, AppContext, AppResult, AppView, scratchpad},
+    components::Component,
+    storage::ScratchNote,
+    ui::util::{centered_rect, format_timestamp},
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+#[derive(Debug, Clone)]
+pub enum ScratchpadCommand {
+    Next,
+    Previous,
+    Copy,
+    Delete,
+    Export,
+    Close,
+}
+
+/// Lines pinned with `m` to the current investigation, with copy, delete
+/// and Markdown export.
+#[derive(Debug)]
+pub struct ScratchpadModal {
+    investigation: String,
+    notes: Vec<ScratchNote>,
+    /// Other investigations with notes, to switch to with `:scratchpad <name>`.
+    others: Vec<String>,
+    selected: usize,
+    /// Outcome of the last export.
+    message: Option<String>,
+}
+
+impl ScratchpadModal {
+    pub fn new(investigation: String, notes: Vec<ScratchNote>, others: Vec<String>) -> Self {
+        let selected = notes.len().saturating_sub(1);
+        Self {
+            investigation,
+            notes,
+            others,
+            selected,
+            message: None,
+        }
+    }
+
+    pub fn command_from_key(event: KeyEvent) -> Option<ScratchpadCommand> {
+        match event.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(ScratchpadCommand::Close),
+            KeyCode::Char('j') | KeyCode::Down => Some(ScratchpadCommand::Next),
+            KeyCode::Char('k') | KeyCode::Up => Some(ScratchpadCommand::Previous),
+            KeyCode::Enter | KeyCode::Char('c') | KeyCode::Char('y') => {
+                Some(ScratchpadCommand::Copy)
+            }
+            KeyCode::Char('d') | KeyCode::Delete => Some(ScratchpadCommand::Delete),
+            KeyCode::Char('e') => Some(ScratchpadCommand::Export),
+            _ => None,
+        }
+    }
+
+    fn delete_selected(&mut self, ctx: &mut AppContext<'_>) {
+        if self.selected >= self.notes.len() {
+            return;
+        }
+        let note = self.notes.remove(self.selected);
+        ctx.storage.write("scratchpad", move |storage| {
+            storage.scratchpad().remove(&note)
+        });
+        self.selected = self.selected.min(self.notes.len().saturating_sub(1));
+    }
+
+    fn export(&mut self) {
+        let path = scratchpad::default_path(&self.investigation);
+        self.message = Some(
+            match scratchpad::write_markdown(&path, &self.investigation, &self.notes) {
+                Ok(()) => format!("Wrote {}", path.display()),
+                Err(err) => format!("Export failed: {err}"),
+            },
+        );
+    }
+}
+
+impl Component for ScratchpadModal {
+    type Command = ScratchpadCommand;
+
+    fn init(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<()> {
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        command: &Self::Command,
+        ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        match command {
+            ScratchpadCommand::Next => {
+                if self.selected + 1 < self.notes.len() {
+                    self.selected += 1;
+                }
+            }
+            ScratchpadCommand::Previous => self.selected = self.selected.saturating_sub(1),
+            ScratchpadCommand::Copy => {
+                return Ok(self
+                    .notes
+                    .get(self.selected)
+                    .map(|note| Action::CopyToClipboard(note.text.clone())));
+            }
+            ScratchpadCommand::Delete => self.delete_selected(ctx),
+            ScratchpadCommand::Export => self.export(),
+            ScratchpadCommand::Close => return Ok(Some(Action::CloseModal)),
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, frame: &mut Frame<'_>, area: Rect, _ctx: &AppView<'_>) {
+        let modal_area = centered_rect(96, 24, area);
+        frame.render_widget(Clear, modal_area);
+
+        let block = Block::default()
+            .title(Span::styled(
+                format!("Scratchpad • {} ({})", self.investigation, self.notes.len()),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(4),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        if self.notes.is_empty() {
+            frame.render_widget(
+                Paragraph::new("Nothing pinned yet: press m on a row of the main view")
+                    .style(Style::default().fg(Color::DarkGray)),
+                chunks[0],
+            );
+        } else {
+            let items: Vec<ListItem> = self
+                .notes
+                .iter()
+                .map(|note| {
+                    let first = note.text.lines().next().unwrap_or_default();
+                    let more = if note.text.lines().count() > 1 {
+                        " …"
+                    } else {
+                        ""
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::raw(format!("{first}{more}  ")),
+                        Span::styled(note.source.clone(), Style::default().fg(Color::DarkGray)),
+                    ]))
+                })
+                .collect();
+            let list = List::new(items).highlight_style(
+                Style::default()
+                    .fg(Color::White)
+                    .bg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            );
+            let mut state = ListState::default().with_selected(Some(self.selected));
+            frame.render_stateful_widget(list, chunks[0], &mut state);
+        }
+
+        // The whole of the selected note, which the list may cut short.
+        if let Some(note) = self.notes.get(self.selected) {
+            let detail = Paragraph::new(vec![
+                Line::from(Span::styled(
+                    format!(
+                        "{} • {}",
+                        note.source,
+                        format_timestamp(note.pinned_at / 1000)
+                    ),
+                    Style::default().fg(Color::Gray),
+                )),
+                Line::from(note.text.as_str()),
+            ])
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::TOP));
+            frame.render_widget(detail, chunks[1]);
+        }
+
+        let status = match (&self.message, self.others.is_empty()) {
+            (Some(message), _) => Span::styled(message.clone(), Style::default().fg(Color::Green)),
+            (None, false) => Span::styled(
+                format!(
+                    "Also: {} • :scratchpad <name> to switch",
+                    self.others.join(", ")
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+            (None, true) => Span::raw(""),
+        };
+        frame.render_widget(Paragraph::new(Line::from(status)), chunks[2]);
+
+        let footer = Paragraph::new(Line::from(Span::styled(
+            "j/k Move • c Copy • d Delete • e Export Markdown • Esc Close",
+            Style::default().fg(Color::Gray),
+        )));
+        frame.render_widget(footer, chunks[3]);
+    }
+
+    fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        Ok(None)
+    }
+}
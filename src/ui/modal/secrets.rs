@@ -1,7 +1,13 @@
 use crate::{
-    app::{Action, AppContext, AppResult, AppView},
+    app::{
+        Action, AppContext, AppResult, AppView, Message,
+        chains::{self, ExplorerKind},
+        secrets_check::{SecretCheck, check_secrets},
+        tenderly, webhook,
+    },
     components::Component,
     storage::SecretKey,
+    ui::util::{centered_rect, mask_secret},
 };
 use crossterm::event::KeyEvent;
 use ratatui::{
@@ -11,17 +17,51 @@ use ratatui::{
     text::{Line, Span, Text},
     widgets::{Block, Borders, Clear, Paragraph},
 };
-use std::cmp::min;
+use std::collections::BTreeSet;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum SecretsField {
-    Etherscan,
-    Anvil,
+/// Result of probing a field's value against its service.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+enum FieldCheck {
+    #[default]
+    Unchecked,
+    Pending,
+    Passed(String),
+    Failed(String),
 }
 
-impl Default for SecretsField {
-    fn default() -> Self {
-        SecretsField::Etherscan
+/// One editable row of the settings form.
+#[derive(Debug, Clone)]
+struct SecretsField {
+    key: SecretKey,
+    title: String,
+    /// Shown when the value is empty; required fields block saving.
+    placeholder: &'static str,
+    required: bool,
+    value: String,
+    check: FieldCheck,
+}
+
+impl SecretsField {
+    fn required(key: SecretKey, title: impl Into<String>) -> Self {
+        Self {
+            key,
+            title: title.into(),
+            placeholder: "<required>",
+            required: true,
+            value: String::new(),
+            check: FieldCheck::Unchecked,
+        }
+    }
+
+    fn optional(key: SecretKey, title: impl Into<String>, placeholder: &'static str) -> Self {
+        Self {
+            key,
+            title: title.into(),
+            placeholder,
+            required: false,
+            value: String::new(),
+            check: FieldCheck::Unchecked,
+        }
     }
 }
 
@@ -35,19 +75,81 @@ pub enum SecretsFormCommand {
     Submit,
     Cancel,
     ClearField,
+    ToggleReveal,
+    ValidationFinished(Vec<(SecretKey, SecretCheck)>),
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct SecretsModal {
-    etherscan_value: String,
-    anvil_value: String,
-    focused_field: SecretsField,
+    fields: Vec<SecretsField>,
+    focused: usize,
+    scroll: usize,
     message: Option<String>,
+    validating: bool,
+    /// Show the focused field in clear text; every other value stays masked.
+    reveal: bool,
+    /// Values that last failed validation; submitting them unchanged saves anyway.
+    rejected_values: Option<Vec<String>>,
+}
+
+impl Default for SecretsModal {
+    fn default() -> Self {
+        Self::new(&BTreeSet::new())
+    }
 }
 
 impl SecretsModal {
-    pub fn new() -> Self {
-        Self::default()
+    pub fn new(hidden_chains: &BTreeSet<String>) -> Self {
+        Self {
+            fields: Self::catalog(hidden_chains),
+            focused: 0,
+            scroll: 0,
+            message: None,
+            validating: false,
+            reveal: false,
+            rejected_values: None,
+        }
+    }
+
+    /// The shared credentials first, then explorer keys and RPC URLs for the
+    /// chains not hidden, then the Tenderly project and the alert webhook.
+    fn catalog(hidden_chains: &BTreeSet<String>) -> Vec<SecretsField> {
+        let mut fields = vec![
+            SecretsField::required(SecretKey::EtherscanApiKey, "Etherscan API Key"),
+            SecretsField::required(SecretKey::AnvilRpcUrl, "Anvil RPC URL"),
+        ];
+        for chain in chains::visible(hidden_chains) {
+            if let ExplorerKind::EtherscanV2 { label } = chain.explorer {
+                fields.push(SecretsField::optional(
+                    SecretKey::ExplorerApiKey(chain.chain_id),
+                    format!("{label} API Key"),
+                    "<shared Etherscan key>",
+                ));
+            }
+        }
+        for chain in chains::visible(hidden_chains) {
+            fields.push(SecretsField::optional(
+                SecretKey::RpcUrl(chain.name.to_ascii_lowercase()),
+                format!("{} RPC URLs", chain.name),
+                "<Anvil RPC URL> • comma-separate fallbacks",
+            ));
+        }
+        for (field, title) in tenderly::FIELDS {
+            fields.push(SecretsField::optional(
+                SecretKey::ProviderCredential {
+                    provider: tenderly::PROVIDER.into(),
+                    field: field.into(),
+                },
+                title,
+                "<only for :tracer tenderly>",
+            ));
+        }
+        fields.push(SecretsField::optional(
+            webhook::key(),
+            "Webhook URL",
+            "<POSTs alerts of watches set to webhook>",
+        ));
+        fields
     }
 
     pub fn command_from_key(event: KeyEvent) -> Option<SecretsFormCommand> {
@@ -57,12 +159,14 @@ impl SecretsModal {
             (KeyModifiers::NONE, KeyCode::Tab) | (KeyModifiers::NONE, KeyCode::Down) => {
                 Some(SecretsFormCommand::FocusNextField)
             }
-            (KeyModifiers::SHIFT, KeyCode::Tab) | (KeyModifiers::NONE, KeyCode::Up) => {
-                Some(SecretsFormCommand::FocusPreviousField)
-            }
+            (KeyModifiers::SHIFT, KeyCode::Tab)
+            | (KeyModifiers::SHIFT, KeyCode::BackTab)
+            | (KeyModifiers::NONE, KeyCode::BackTab)
+            | (KeyModifiers::NONE, KeyCode::Up) => Some(SecretsFormCommand::FocusPreviousField),
             (_, KeyCode::Enter) => Some(SecretsFormCommand::Submit),
             (_, KeyCode::Backspace) => Some(SecretsFormCommand::Backspace),
             (KeyModifiers::CONTROL, KeyCode::Char('u')) => Some(SecretsFormCommand::ClearField),
+            (KeyModifiers::CONTROL, KeyCode::Char('r')) => Some(SecretsFormCommand::ToggleReveal),
             (modifiers, KeyCode::Char(c)) if !modifiers.contains(KeyModifiers::CONTROL) => {
                 Some(SecretsFormCommand::InputChar(c))
             }
@@ -71,59 +175,123 @@ impl SecretsModal {
     }
 
     fn selected_value(&mut self) -> &mut String {
-        match self.focused_field {
-            SecretsField::Etherscan => &mut self.etherscan_value,
-            SecretsField::Anvil => &mut self.anvil_value,
-        }
-    }
-
-    fn field_title(field: SecretsField) -> &'static str {
-        match field {
-            SecretsField::Etherscan => "Etherscan API Key",
-            SecretsField::Anvil => "Anvil RPC URL",
-        }
+        let field = &mut self.fields[self.focused];
+        field.check = FieldCheck::Unchecked;
+        &mut field.value
     }
 
     fn cycle_field(&mut self, forward: bool) {
-        self.focused_field = if forward {
-            match self.focused_field {
-                SecretsField::Etherscan => SecretsField::Anvil,
-                SecretsField::Anvil => SecretsField::Etherscan,
-            }
+        let len = self.fields.len();
+        self.focused = if forward {
+            (self.focused + 1) % len
         } else {
-            match self.focused_field {
-                SecretsField::Etherscan => SecretsField::Anvil,
-                SecretsField::Anvil => SecretsField::Etherscan,
-            }
+            (self.focused + len - 1) % len
         };
     }
 
-    fn validate(&self) -> Result<(), &'static str> {
-        if self.etherscan_value.trim().is_empty() {
-            return Err("Etherscan API key is required");
-        }
-        if self.anvil_value.trim().is_empty() {
-            return Err("Anvil RPC URL is required");
+    fn validate(&self) -> Result<(), String> {
+        match self
+            .fields
+            .iter()
+            .find(|field| field.required && field.value.trim().is_empty())
+        {
+            Some(field) => Err(format!("{} is required", field.title)),
+            None => Ok(()),
         }
-        Ok(())
     }
 
-    fn save(&mut self, ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+    fn values(&self) -> Vec<String> {
+        self.fields
+            .iter()
+            .map(|field| field.value.trim().to_string())
+            .collect()
+    }
+
+    /// Probe the filled-in fields in the background; the result arrives as
+    /// [`SecretsFormCommand::ValidationFinished`].
+    fn submit(&mut self, ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        if self.validating {
+            return Ok(None);
+        }
         if let Err(message) = self.validate() {
-            self.message = Some(message.to_string());
+            self.message = Some(message);
             return Ok(None);
         }
+        if self.rejected_values.as_ref() == Some(&self.values()) {
+            return self.save(ctx);
+        }
 
-        let etherscan = self.etherscan_value.trim();
-        let anvil = self.anvil_value.trim();
+        let mut entries = Vec::new();
+        for field in &mut self.fields {
+            let value = field.value.trim();
+            if value.is_empty() {
+                field.check = FieldCheck::Unchecked;
+            } else {
+                field.check = FieldCheck::Pending;
+                entries.push((field.key.clone(), value.to_string()));
+            }
+        }
+        self.validating = true;
+        self.message = Some("Checking credentials…".into());
+        let providers = ctx.commands.providers().clone();
+        ctx.commands.spawn_async(move || async move {
+            Message::SecretsValidated(check_secrets(providers, entries).await)
+        });
+        Ok(None)
+    }
 
-        ctx.storage
-            .secrets()
-            .set(SecretKey::EtherscanApiKey, etherscan)?;
-        ctx.storage.secrets().set(SecretKey::AnvilRpcUrl, anvil)?;
+    fn finish_validation(
+        &mut self,
+        results: &[(SecretKey, SecretCheck)],
+        ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        if !self.validating {
+            return Ok(None);
+        }
+        self.validating = false;
+        for field in &mut self.fields {
+            if let Some((_, outcome)) = results.iter().find(|(key, _)| *key == field.key) {
+                field.check = match outcome {
+                    Ok(detail) => FieldCheck::Passed(detail.clone()),
+                    Err(reason) => FieldCheck::Failed(reason.clone()),
+                };
+            } else if field.check == FieldCheck::Pending {
+                field.check = FieldCheck::Unchecked;
+            }
+        }
+        let failed = self
+            .fields
+            .iter()
+            .position(|field| matches!(field.check, FieldCheck::Failed(_)));
+        match failed {
+            Some(index) => {
+                self.focused = index;
+                self.rejected_values = Some(self.values());
+                self.message = Some(
+                    "Some checks failed • fix them or press Enter again to save anyway".into(),
+                );
+                Ok(None)
+            }
+            None => self.save(ctx),
+        }
+    }
 
-        ctx.state.secrets.etherscan_api_key = Some(etherscan.to_string());
-        ctx.state.secrets.anvil_rpc_url = Some(anvil.to_string());
+    fn save(&mut self, ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        for field in &self.fields {
+            let value = field.value.trim();
+            let key = field.key.clone();
+            if value.is_empty() {
+                ctx.storage
+                    .write("secrets", move |storage| storage.secrets().remove(&key));
+                ctx.state.secrets.set(&field.key, None);
+            } else {
+                let stored = value.to_string();
+                ctx.storage.write("secrets", move |storage| {
+                    storage.secrets().set(&key, &stored)
+                });
+                ctx.state.secrets.set(&field.key, Some(value.to_string()));
+            }
+        }
         self.message = Some("Configuration saved".into());
         Ok(Some(Action::SecretsSaved))
     }
@@ -132,18 +300,31 @@ impl SecretsModal {
         self.selected_value().clear();
     }
 
+    /// Keep the focused row inside a window of `visible` rows.
+    fn scroll_into_view(&mut self, visible: usize) {
+        let visible = visible.max(1);
+        if self.focused < self.scroll {
+            self.scroll = self.focused;
+        } else if self.focused >= self.scroll + visible {
+            self.scroll = self.focused + 1 - visible;
+        }
+    }
+
     fn apply_command(
         &mut self,
         command: &SecretsFormCommand,
         ctx: &mut AppContext<'_>,
     ) -> AppResult<Option<Action>> {
         match command {
+            SecretsFormCommand::ToggleReveal => self.reveal = !self.reveal,
             SecretsFormCommand::FocusNextField => {
                 self.message = None;
+                self.reveal = false;
                 self.cycle_field(true);
             }
             SecretsFormCommand::FocusPreviousField => {
                 self.message = None;
+                self.reveal = false;
                 self.cycle_field(false);
             }
             SecretsFormCommand::InputChar(c) => {
@@ -166,35 +347,28 @@ impl SecretsModal {
                 self.message = None;
                 self.clear_field();
             }
-            SecretsFormCommand::Submit => return self.save(ctx),
+            SecretsFormCommand::Submit => return self.submit(ctx),
+            SecretsFormCommand::ValidationFinished(results) => {
+                return self.finish_validation(results, ctx);
+            }
             SecretsFormCommand::Cancel => return Ok(Some(Action::CloseModal)),
         }
         Ok(None)
     }
-
-    fn centered_rect(&self, width: u16, height: u16, area: Rect) -> Rect {
-        let width = min(width, area.width);
-        let height = min(height, area.height);
-        Rect {
-            x: area.x + (area.width.saturating_sub(width)) / 2,
-            y: area.y + (area.height.saturating_sub(height)) / 2,
-            width,
-            height,
-        }
-    }
 }
 
 impl Component for SecretsModal {
     type Command = SecretsFormCommand;
 
     fn init(&mut self, ctx: &mut AppContext<'_>) -> AppResult<()> {
-        self.etherscan_value = ctx
-            .state
-            .secrets
-            .etherscan_api_key
-            .clone()
-            .unwrap_or_default();
-        self.anvil_value = ctx.state.secrets.anvil_rpc_url.clone().unwrap_or_default();
+        for field in &mut self.fields {
+            field.value = ctx
+                .state
+                .secrets
+                .get(&field.key)
+                .unwrap_or_default()
+                .to_string();
+        }
         Ok(())
     }
 
@@ -207,7 +381,7 @@ impl Component for SecretsModal {
     }
 
     fn render(&mut self, frame: &mut Frame<'_>, area: Rect, ctx: &AppView<'_>) {
-        let modal_area = self.centered_rect(72, 15, area);
+        let modal_area = centered_rect(80, 20, area);
         frame.render_widget(Clear, modal_area);
 
         let title = if ctx.state.secrets.etherscan_api_key.is_some()
@@ -236,8 +410,7 @@ impl Component for SecretsModal {
             .constraints(
                 [
                     Constraint::Length(2),
-                    Constraint::Length(3),
-                    Constraint::Length(3),
+                    Constraint::Min(1),
                     Constraint::Length(1),
                     Constraint::Length(2),
                 ]
@@ -246,71 +419,89 @@ impl Component for SecretsModal {
             .split(inner);
 
         let intro = Paragraph::new(Text::raw(
-            "Enter credentials to enable contract lookups and local RPC calls.",
+            "Enter credentials to enable contract lookups and RPC calls. Per-chain entries are optional.",
         ))
         .alignment(Alignment::Center);
         frame.render_widget(intro, chunks[0]);
 
-        for (idx, (field, target_area)) in [
-            (SecretsField::Etherscan, chunks[1]),
-            (SecretsField::Anvil, chunks[2]),
-        ]
-        .into_iter()
-        .enumerate()
-        {
-            let value = match field {
-                SecretsField::Etherscan => &self.etherscan_value,
-                SecretsField::Anvil => &self.anvil_value,
-            };
-            let placeholder = if value.trim().is_empty() {
-                "<required>"
-            } else {
-                value
-            };
-            let is_focused = self.focused_field == field;
-            let mut spans = Vec::new();
-            spans.push(Span::styled(
-                format!("{}: ", SecretsModal::field_title(field)),
-                Style::default()
-                    .fg(Color::Gray)
-                    .add_modifier(Modifier::BOLD),
-            ));
-            spans.push(Span::styled(
-                placeholder.to_string(),
-                if is_focused {
-                    Style::default()
-                        .fg(Color::White)
-                        .bg(Color::Blue)
-                        .add_modifier(Modifier::BOLD)
-                } else if value.trim().is_empty() {
-                    Style::default().fg(Color::DarkGray)
+        let visible = chunks[1].height as usize;
+        self.scroll_into_view(visible);
+        let lines: Vec<Line> = self
+            .fields
+            .iter()
+            .enumerate()
+            .skip(self.scroll)
+            .take(visible)
+            .map(|(idx, field)| {
+                let is_focused = idx == self.focused;
+                let is_empty = field.value.trim().is_empty();
+                let shown = if is_empty {
+                    field.placeholder.to_string()
+                } else if is_focused && self.reveal {
+                    field.value.clone()
                 } else {
-                    Style::default().fg(Color::White)
-                },
-            ));
-            if is_focused {
-                spans.push(Span::styled(
-                    " ▌",
-                    Style::default()
-                        .fg(Color::LightCyan)
-                        .add_modifier(Modifier::BOLD),
-                ));
-            }
+                    mask_secret(&field.value)
+                };
+                let mut spans = vec![
+                    Span::styled(
+                        format!("{}: ", field.title),
+                        Style::default()
+                            .fg(Color::Gray)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        shown,
+                        if is_focused {
+                            Style::default()
+                                .fg(Color::White)
+                                .bg(Color::Blue)
+                                .add_modifier(Modifier::BOLD)
+                        } else if is_empty {
+                            Style::default().fg(Color::DarkGray)
+                        } else {
+                            Style::default().fg(Color::White)
+                        },
+                    ),
+                ];
+                if is_focused {
+                    spans.push(Span::styled(
+                        " ▌",
+                        Style::default()
+                            .fg(Color::LightCyan)
+                            .add_modifier(Modifier::BOLD),
+                    ));
+                }
+                match &field.check {
+                    FieldCheck::Unchecked => {}
+                    FieldCheck::Pending => spans.push(Span::styled(
+                        "  … checking",
+                        Style::default().fg(Color::Gray),
+                    )),
+                    FieldCheck::Passed(detail) => spans.push(Span::styled(
+                        format!("  ✓ {detail}"),
+                        Style::default().fg(Color::Green),
+                    )),
+                    FieldCheck::Failed(reason) => spans.push(Span::styled(
+                        format!("  ✗ {reason}"),
+                        Style::default().fg(Color::Red),
+                    )),
+                }
+                Line::from(spans)
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines).alignment(Alignment::Left), chunks[1]);
 
-            let paragraph = Paragraph::new(Line::from(spans))
-                .block(Block::default().borders(Borders::NONE))
-                .alignment(Alignment::Left);
-            frame.render_widget(paragraph, target_area);
-
-            if idx == 0 {
-                let hint = Paragraph::new(Line::from(Span::styled(
-                    "Rotate fields with Tab • Clear with Ctrl+U",
-                    Style::default().fg(Color::Gray),
-                )))
-                .alignment(Alignment::Left);
-                frame.render_widget(hint, chunks[3]);
-            }
-        }
+        let hint = Paragraph::new(Line::from(Span::styled(
+            format!(
+                "Rotate fields with Tab • Clear with Ctrl+U • {} with Ctrl+R • Field {}/{}",
+                if self.reveal { "Hide" } else { "Reveal" },
+                self.focused + 1,
+                self.fields.len()
+            ),
+            Style::default().fg(Color::Gray),
+        )))
+        .alignment(Alignment::Left);
+        frame.render_widget(hint, chunks[2]);
 
         let status_line = if let Some(message) = self.message.as_ref() {
             Paragraph::new(Span::styled(
@@ -323,7 +514,7 @@ impl Component for SecretsModal {
                 Style::default().fg(Color::Gray),
             ))
         };
-        frame.render_widget(status_line, chunks[4]);
+        frame.render_widget(status_line, chunks[3]);
     }
 
     fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
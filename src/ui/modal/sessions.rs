@@ -0,0 +1,270 @@
+use crate::{
+    app::{Action, AppContext, AppResult, AppView, SelectedEntity, scratchpad},
+    components::Component,
+    storage::SettingKey,
+    ui::util::{centered_rect, format_timestamp, short_hex},
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+#[derive(Debug, Clone)]
+pub enum SessionsCommand {
+    Next,
+    Previous,
+    Open,
+    NewSession,
+    InputChar(char),
+    Backspace,
+    CancelInput,
+    Delete,
+    Close,
+}
+
+/// The open investigation session and the stashed ones, to switch between.
+#[derive(Debug, Default)]
+pub struct SessionsModal {
+    /// 0 is the open session, then the stashed ones in order.
+    selected: usize,
+    /// Name of the session being created.
+    input: Option<String>,
+    message: Option<String>,
+}
+
+fn entity_label(entity: Option<&SelectedEntity>) -> String {
+    match entity {
+        Some(SelectedEntity::Address(addr)) => {
+            format!("{} {}", addr.chain, short_hex(&addr.address))
+        }
+        Some(SelectedEntity::Transaction(tx)) => format!("{} tx {}", tx.chain, short_hex(&tx.hash)),
+        None => "nothing open".to_string(),
+    }
+}
+
+impl SessionsModal {
+    pub fn command_from_key(&self, event: KeyEvent) -> Option<SessionsCommand> {
+        if self.input.is_some() {
+            return match event.code {
+                KeyCode::Esc => Some(SessionsCommand::CancelInput),
+                KeyCode::Enter => Some(SessionsCommand::Open),
+                KeyCode::Backspace => Some(SessionsCommand::Backspace),
+                KeyCode::Char(c) if !event.modifiers.contains(KeyModifiers::CONTROL) => {
+                    Some(SessionsCommand::InputChar(c))
+                }
+                _ => None,
+            };
+        }
+        match event.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(SessionsCommand::Close),
+            KeyCode::Char('j') | KeyCode::Down => Some(SessionsCommand::Next),
+            KeyCode::Char('k') | KeyCode::Up => Some(SessionsCommand::Previous),
+            KeyCode::Enter => Some(SessionsCommand::Open),
+            KeyCode::Char('n') => Some(SessionsCommand::NewSession),
+            KeyCode::Char('d') | KeyCode::Delete => Some(SessionsCommand::Delete),
+            _ => None,
+        }
+    }
+}
+
+impl Component for SessionsModal {
+    type Command = SessionsCommand;
+
+    fn init(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<()> {
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        command: &Self::Command,
+        ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        match command {
+            SessionsCommand::Next => {
+                if self.selected < ctx.state.sessions.saved.len() {
+                    self.selected += 1;
+                }
+            }
+            SessionsCommand::Previous => self.selected = self.selected.saturating_sub(1),
+            SessionsCommand::NewSession => {
+                self.message = None;
+                self.input = Some(String::new());
+            }
+            SessionsCommand::InputChar(c) => {
+                if let Some(input) = self.input.as_mut() {
+                    input.push(*c);
+                }
+            }
+            SessionsCommand::Backspace => {
+                if let Some(input) = self.input.as_mut() {
+                    input.pop();
+                }
+            }
+            SessionsCommand::CancelInput => {
+                self.input = None;
+                self.message = None;
+            }
+            SessionsCommand::Open => {
+                if let Some(input) = self.input.as_ref() {
+                    return match scratchpad::investigation_name(input) {
+                        Ok(name) if ctx.state.sessions.saved.iter().any(|s| s.name == name) => {
+                            self.message = Some(format!("{name} exists • Enter on it to open"));
+                            Ok(None)
+                        }
+                        Ok(name) => Ok(Some(Action::SwitchSession(name))),
+                        Err(err) => {
+                            self.message = Some(err);
+                            Ok(None)
+                        }
+                    };
+                }
+                return Ok(match self.selected {
+                    0 => Some(Action::CloseModal),
+                    index => ctx
+                        .state
+                        .sessions
+                        .saved
+                        .get(index - 1)
+                        .map(|session| Action::SwitchSession(session.name.clone())),
+                });
+            }
+            SessionsCommand::Delete => {
+                if self.selected == 0 {
+                    self.message = Some("Switch to another session to delete this one".into());
+                    return Ok(None);
+                }
+                let Some(name) = ctx
+                    .state
+                    .sessions
+                    .saved
+                    .get(self.selected - 1)
+                    .map(|session| session.name.clone())
+                else {
+                    return Ok(None);
+                };
+                ctx.state.sessions.remove(&name);
+                let saved = ctx.state.sessions.saved.clone();
+                ctx.storage.write("sessions", move |storage| {
+                    storage.settings().set_json(SettingKey::Sessions, &saved)
+                });
+                self.selected = self.selected.min(ctx.state.sessions.saved.len());
+                self.message = Some(format!(
+                    "Deleted session {name} • its notes stay in :scratchpad {name}"
+                ));
+            }
+            SessionsCommand::Close => return Ok(Some(Action::CloseModal)),
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, frame: &mut Frame<'_>, area: Rect, ctx: &AppView<'_>) {
+        let modal_area = centered_rect(80, 20, area);
+        frame.render_widget(Clear, modal_area);
+
+        let block = Block::default()
+            .title(Span::styled(
+                "Sessions",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let state = ctx.state;
+        let open_watches = state.watchlist.entries.len()
+            + state.log_alerts.rules.len()
+            + state.thresholds.alerts.len();
+        let mut items = vec![ListItem::new(Line::from(vec![
+            Span::styled("● ", Style::default().fg(Color::Green)),
+            Span::styled(
+                state.investigation.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!(
+                " • {} • {open_watches} watches • open",
+                entity_label(state.selected.as_ref())
+            )),
+        ]))];
+        items.extend(state.sessions.saved.iter().map(|session| {
+            ListItem::new(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(
+                    session.name.clone(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!(
+                    " • {} • {} watches",
+                    entity_label(session.selected.as_ref()),
+                    session.watch_count()
+                )),
+                Span::styled(
+                    format!(" • saved {}", format_timestamp(session.saved_at)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        }));
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .bg(Color::Blue)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut list_state = ListState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(list, chunks[0], &mut list_state);
+
+        if let Some(input) = &self.input {
+            frame.render_widget(
+                Paragraph::new(Line::from(vec![
+                    Span::styled(
+                        "New session › ",
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(format!("{input}_"), Style::default().fg(Color::Yellow)),
+                ])),
+                chunks[1],
+            );
+        }
+        if let Some(message) = &self.message {
+            frame.render_widget(
+                Paragraph::new(Span::styled(
+                    message.clone(),
+                    Style::default().fg(Color::Yellow),
+                )),
+                chunks[2],
+            );
+        }
+
+        let footer = if self.input.is_some() {
+            "Enter Create and switch • Esc Cancel"
+        } else {
+            "Enter Switch • n New • d Delete • Esc Close"
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                footer,
+                Style::default().fg(Color::Gray),
+            ))),
+            chunks[3],
+        );
+    }
+
+    fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        Ok(None)
+    }
+}
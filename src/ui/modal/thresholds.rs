@@ -0,0 +1,328 @@
+use crate::{
+    app::{
+        Action, AppContext, AppResult, AppView, SelectedEntity,
+        thresholds::{Condition, ThresholdAlert},
+    },
+    components::Component,
+    storage::{AlertOutputs, SettingKey},
+    ui::util::centered_rect,
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+#[derive(Debug, Clone)]
+pub enum ThresholdsCommand {
+    Next,
+    Previous,
+    NewAlert,
+    NextField,
+    InputChar(char),
+    Backspace,
+    ConfirmInput,
+    CancelInput,
+    CycleAlerts,
+    DeleteAlert,
+    Close,
+}
+
+const FIELD_TITLES: [&str; 2] = ["Chain", "Condition"];
+const FIELD_PLACEHOLDERS: [&str; 2] = ["Mainnet", "base fee < 10, or balance <token> <holder>"];
+
+/// The alert being built: chain and condition text.
+#[derive(Debug, Clone, Default)]
+struct AlertForm {
+    values: [String; 2],
+    focused: usize,
+}
+
+/// Active threshold alerts with their last readings, and the form that adds
+/// one.
+#[derive(Debug, Default)]
+pub struct ThresholdsModal {
+    selected: usize,
+    form: Option<AlertForm>,
+    message: Option<String>,
+}
+
+impl ThresholdsModal {
+    pub fn command_from_key(&self, event: KeyEvent) -> Option<ThresholdsCommand> {
+        if self.form.is_some() {
+            return match event.code {
+                KeyCode::Esc => Some(ThresholdsCommand::CancelInput),
+                KeyCode::Enter => Some(ThresholdsCommand::ConfirmInput),
+                KeyCode::Backspace => Some(ThresholdsCommand::Backspace),
+                KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                    Some(ThresholdsCommand::NextField)
+                }
+                KeyCode::Char(c) if !event.modifiers.contains(KeyModifiers::CONTROL) => {
+                    Some(ThresholdsCommand::InputChar(c))
+                }
+                _ => None,
+            };
+        }
+        match event.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(ThresholdsCommand::Close),
+            KeyCode::Char('j') | KeyCode::Down => Some(ThresholdsCommand::Next),
+            KeyCode::Char('k') | KeyCode::Up => Some(ThresholdsCommand::Previous),
+            KeyCode::Char('n') => Some(ThresholdsCommand::NewAlert),
+            KeyCode::Char('o') => Some(ThresholdsCommand::CycleAlerts),
+            KeyCode::Char('d') => Some(ThresholdsCommand::DeleteAlert),
+            _ => None,
+        }
+    }
+
+    fn save(ctx: &mut AppContext<'_>) {
+        let alerts = ctx.state.thresholds.alerts.clone();
+        ctx.storage.write("threshold alerts", move |storage| {
+            storage.settings().set_json(SettingKey::Thresholds, &alerts)
+        });
+    }
+
+    /// A blank alert on the selected entity's chain.
+    fn blank_form(ctx: &AppContext<'_>) -> AlertForm {
+        let chain = match ctx.state.selected.as_ref() {
+            Some(SelectedEntity::Address(addr)) => addr.chain.clone(),
+            Some(SelectedEntity::Transaction(tx)) => tx.chain.clone(),
+            None => "Mainnet".to_string(),
+        };
+        AlertForm {
+            values: [chain, String::new()],
+            focused: 1,
+        }
+    }
+
+    fn alert(form: &AlertForm) -> Result<ThresholdAlert, String> {
+        let alert = ThresholdAlert {
+            chain: form.values[0].trim().to_string(),
+            condition: Condition::parse(&form.values[1])?,
+            alerts: AlertOutputs {
+                notify: true,
+                webhook: false,
+            },
+        };
+        alert.validate()?;
+        Ok(alert)
+    }
+}
+
+impl Component for ThresholdsModal {
+    type Command = ThresholdsCommand;
+
+    fn init(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<()> {
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        command: &Self::Command,
+        ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        match command {
+            ThresholdsCommand::Next => {
+                if self.selected + 1 < ctx.state.thresholds.alerts.len() {
+                    self.selected += 1;
+                }
+            }
+            ThresholdsCommand::Previous => self.selected = self.selected.saturating_sub(1),
+            ThresholdsCommand::NewAlert => {
+                self.message = None;
+                self.form = Some(Self::blank_form(ctx));
+            }
+            ThresholdsCommand::NextField => {
+                if let Some(form) = self.form.as_mut() {
+                    form.focused = (form.focused + 1) % FIELD_TITLES.len();
+                }
+            }
+            ThresholdsCommand::InputChar(c) => {
+                if let Some(form) = self.form.as_mut() {
+                    form.values[form.focused].push(*c);
+                }
+            }
+            ThresholdsCommand::Backspace => {
+                if let Some(form) = self.form.as_mut() {
+                    form.values[form.focused].pop();
+                }
+            }
+            ThresholdsCommand::CancelInput => {
+                self.form = None;
+                self.message = None;
+            }
+            ThresholdsCommand::ConfirmInput => {
+                let Some(form) = self.form.as_ref() else {
+                    return Ok(None);
+                };
+                let alert = match Self::alert(form) {
+                    Ok(alert) => alert,
+                    Err(err) => {
+                        self.message = Some(err);
+                        return Ok(None);
+                    }
+                };
+                let title = alert.title();
+                if !ctx.state.thresholds.add(alert) {
+                    self.message = Some("That alert already exists".into());
+                    return Ok(None);
+                }
+                Self::save(ctx);
+                ctx.state.usage.record_feature("threshold alert");
+                self.form = None;
+                self.selected = ctx.state.thresholds.alerts.len() - 1;
+                self.message = Some(format!("Watching {title}"));
+            }
+            ThresholdsCommand::CycleAlerts => {
+                if let Some(outputs) = ctx.state.thresholds.cycle_alerts(self.selected) {
+                    Self::save(ctx);
+                    self.message = Some(format!("Alerts: {}", outputs.label()));
+                }
+            }
+            ThresholdsCommand::DeleteAlert => {
+                if let Some(alert) = ctx.state.thresholds.remove(self.selected) {
+                    Self::save(ctx);
+                    self.selected = self.selected.saturating_sub(1);
+                    self.message = Some(format!("Deleted {}", alert.title()));
+                }
+            }
+            ThresholdsCommand::Close => return Ok(Some(Action::CloseModal)),
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, frame: &mut Frame<'_>, area: Rect, ctx: &AppView<'_>) {
+        let modal_area = centered_rect(90, 22, area);
+        frame.render_widget(Clear, modal_area);
+
+        let block = Block::default()
+            .title(Span::styled(
+                "Threshold alerts",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),
+                Constraint::Length(4),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let thresholds = &ctx.state.thresholds;
+        if thresholds.alerts.is_empty() {
+            frame.render_widget(
+                Paragraph::new("No threshold alerts yet • press n to add one")
+                    .style(Style::default().fg(Color::DarkGray)),
+                chunks[0],
+            );
+        } else {
+            let items: Vec<ListItem> = thresholds
+                .alerts
+                .iter()
+                .map(|alert| {
+                    let state = match (thresholds.error(alert), thresholds.current(alert)) {
+                        (Some(err), _) => {
+                            Span::styled(format!(" • ⚠ {err}"), Style::default().fg(Color::Yellow))
+                        }
+                        (None, Some(current)) => Span::raw(format!(" • now {current}")),
+                        (None, None) => {
+                            Span::styled(" • waiting", Style::default().fg(Color::DarkGray))
+                        }
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(alert.title(), Style::default().add_modifier(Modifier::BOLD)),
+                        Span::raw(format!(" ({}){}", alert.chain, alert.alerts.marker())),
+                        state,
+                    ]))
+                })
+                .collect();
+            let list = List::new(items).highlight_style(
+                Style::default()
+                    .bg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            );
+            let mut state = ListState::default().with_selected(Some(self.selected));
+            frame.render_stateful_widget(list, chunks[0], &mut state);
+        }
+
+        let lower: Vec<Line> = match &self.form {
+            Some(form) => FIELD_TITLES
+                .iter()
+                .zip(FIELD_PLACEHOLDERS)
+                .enumerate()
+                .map(|(index, (title, placeholder))| {
+                    let value = &form.values[index];
+                    let focused = index == form.focused;
+                    let text = match (value.is_empty(), focused) {
+                        (true, false) => {
+                            Span::styled(placeholder, Style::default().fg(Color::DarkGray))
+                        }
+                        (_, true) => {
+                            Span::styled(format!("{value}_"), Style::default().fg(Color::Yellow))
+                        }
+                        (false, false) => Span::raw(value.clone()),
+                    };
+                    Line::from(vec![
+                        Span::styled(
+                            format!("{} {title:<10}", if focused { "▸" } else { " " }),
+                            Style::default().add_modifier(Modifier::BOLD),
+                        ),
+                        text,
+                    ])
+                })
+                .collect(),
+            None => vec![
+                Line::from("  base fee < 10       alerts once the base fee drops under 10 gwei"),
+                Line::from("  base fee > 50       alerts once it rises over 50 gwei"),
+                Line::from("  balance <token> <holder>   alerts when the ERC-20 balance changes"),
+            ],
+        };
+        let title = if self.form.is_some() {
+            "New alert"
+        } else {
+            "Conditions"
+        };
+        frame.render_widget(
+            Paragraph::new(lower).block(Block::default().borders(Borders::TOP).title(title)),
+            chunks[1],
+        );
+
+        if let Some(message) = &self.message {
+            frame.render_widget(
+                Paragraph::new(Span::styled(
+                    message.clone(),
+                    Style::default().fg(Color::Yellow),
+                )),
+                chunks[2],
+            );
+        }
+
+        let footer = if self.form.is_some() {
+            "Tab Field • Enter Save • Esc Cancel"
+        } else {
+            "n New • o Alerts • d Delete • Esc Close"
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                footer,
+                Style::default().fg(Color::Gray),
+            ))),
+            chunks[3],
+        );
+    }
+
+    fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,175 @@
+use crate::{
+    app::{Action, AppContext, AppResult, AppView, watchlist::unix_now},
+    components::Component,
+    storage::{FavoritesTable, TRASH_RETENTION, TrashRecord},
+    ui::util::{centered_rect, short_hex},
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+#[derive(Debug, Clone)]
+pub enum TrashCommand {
+    Next,
+    Previous,
+    Restore,
+    Close,
+}
+
+/// Recently removed favorites with a one-key restore.
+#[derive(Debug)]
+pub struct TrashModal {
+    entries: Vec<TrashRecord>,
+    selected: usize,
+}
+
+impl TrashModal {
+    pub fn new(entries: Vec<TrashRecord>) -> Self {
+        Self {
+            entries,
+            selected: 0,
+        }
+    }
+
+    pub fn command_from_key(event: KeyEvent) -> Option<TrashCommand> {
+        match event.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(TrashCommand::Close),
+            KeyCode::Char('j') | KeyCode::Down => Some(TrashCommand::Next),
+            KeyCode::Char('k') | KeyCode::Up => Some(TrashCommand::Previous),
+            KeyCode::Enter | KeyCode::Char('r') => Some(TrashCommand::Restore),
+            _ => None,
+        }
+    }
+
+    fn restore_selected(&mut self, ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        if self.selected >= self.entries.len() {
+            return Ok(None);
+        }
+        let entry = self.entries.remove(self.selected);
+        let mut restored = entry.clone();
+        ctx.storage.write("favorites", move |storage| {
+            // Restored favorites go to the end of the list, like a fresh favorite.
+            restored.record.order = match restored.table {
+                FavoritesTable::Addresses => storage.favorites_addresses().next_order()?,
+                FavoritesTable::Transactions => storage.favorites_transactions().next_order()?,
+            };
+            storage.favorites_batch().restore(&restored).commit()
+        });
+        self.selected = self.selected.min(self.entries.len().saturating_sub(1));
+        Ok(Some(Action::FavoriteRestored(entry)))
+    }
+}
+
+fn days_left(deleted_at: u64) -> u64 {
+    let expires = deleted_at + TRASH_RETENTION.as_secs();
+    expires.saturating_sub(unix_now()).div_ceil(24 * 60 * 60)
+}
+
+impl Component for TrashModal {
+    type Command = TrashCommand;
+
+    fn init(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<()> {
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        command: &Self::Command,
+        ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        match command {
+            TrashCommand::Next => {
+                if self.selected + 1 < self.entries.len() {
+                    self.selected += 1;
+                }
+                Ok(None)
+            }
+            TrashCommand::Previous => {
+                self.selected = self.selected.saturating_sub(1);
+                Ok(None)
+            }
+            TrashCommand::Restore => self.restore_selected(ctx),
+            TrashCommand::Close => Ok(Some(Action::CloseModal)),
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame<'_>, area: Rect, _ctx: &AppView<'_>) {
+        let modal_area = centered_rect(64, 18, area);
+        frame.render_widget(Clear, modal_area);
+
+        let block = Block::default()
+            .title(Span::styled(
+                "Trash",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+
+        if self.entries.is_empty() {
+            frame.render_widget(
+                Paragraph::new("Trash is empty").style(Style::default().fg(Color::DarkGray)),
+                chunks[0],
+            );
+        } else {
+            let items: Vec<ListItem> = self
+                .entries
+                .iter()
+                .map(|entry| {
+                    let kind = match entry.table {
+                        FavoritesTable::Addresses => "address",
+                        FavoritesTable::Transactions => "tx",
+                    };
+                    let label = entry
+                        .record
+                        .label
+                        .clone()
+                        .unwrap_or_else(|| short_hex(&entry.record.identifier));
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!("{kind:<8}"), Style::default().fg(Color::Gray)),
+                        Span::raw(format!("{label:<28} ")),
+                        Span::styled(
+                            format!(
+                                "{} • {}d left",
+                                entry.record.chain,
+                                days_left(entry.deleted_at)
+                            ),
+                            Style::default().fg(Color::DarkGray),
+                        ),
+                    ]))
+                })
+                .collect();
+            let list = List::new(items).highlight_style(
+                Style::default()
+                    .fg(Color::White)
+                    .bg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            );
+            let mut state = ListState::default().with_selected(Some(self.selected));
+            frame.render_stateful_widget(list, chunks[0], &mut state);
+        }
+
+        let footer = Paragraph::new(Line::from(Span::styled(
+            "j/k Move • Enter Restore • Esc Close",
+            Style::default().fg(Color::Gray),
+        )));
+        frame.render_widget(footer, chunks[1]);
+    }
+
+    fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,235 @@
+use crate::{
+    app::{
+        Action, AddressRef, AppContext, AppResult, AppView, SelectedEntity,
+        typed_data::{Inspection, inspect},
+    },
+    components::Component,
+    ui::util::{centered_rect, short_hex},
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+/// Payload lines kept on screen; earlier ones scroll out of view.
+const PAYLOAD_LINES: usize = 6;
+
+#[derive(Debug, Clone)]
+pub enum TypedDataCommand {
+    InputChar(char),
+    Backspace,
+    ClearField,
+    SwitchField,
+    OpenSigner,
+    Close,
+}
+
+/// Digest and signer recovery for pasted EIP-712 typed data or
+/// `personal_sign` messages, recomputed as the fields change.
+#[derive(Debug)]
+pub struct TypedDataModal {
+    chain: String,
+    payload: String,
+    signature: String,
+    editing_signature: bool,
+    result: Option<Result<Inspection, String>>,
+}
+
+impl TypedDataModal {
+    pub fn new(chain: String) -> Self {
+        Self {
+            chain,
+            payload: String::new(),
+            signature: String::new(),
+            editing_signature: false,
+            result: None,
+        }
+    }
+
+    pub fn command_from_key(event: KeyEvent) -> Option<TypedDataCommand> {
+        match event.code {
+            KeyCode::Esc => Some(TypedDataCommand::Close),
+            KeyCode::Enter => Some(TypedDataCommand::OpenSigner),
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                Some(TypedDataCommand::SwitchField)
+            }
+            KeyCode::Backspace => Some(TypedDataCommand::Backspace),
+            KeyCode::Char('u') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(TypedDataCommand::ClearField)
+            }
+            KeyCode::Char(c) if !event.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(TypedDataCommand::InputChar(c))
+            }
+            _ => None,
+        }
+    }
+
+    fn field_mut(&mut self) -> &mut String {
+        if self.editing_signature {
+            &mut self.signature
+        } else {
+            &mut self.payload
+        }
+    }
+
+    /// Pasted messages keep their line breaks, which are part of what
+    /// gets signed; signatures drop all whitespace.
+    pub fn insert_text(&mut self, text: &str) {
+        let text = text.replace("\r\n", "\n");
+        if self.editing_signature {
+            self.signature
+                .extend(text.chars().filter(|c| !c.is_whitespace()));
+        } else {
+            self.payload.push_str(&text);
+        }
+        self.recompute();
+    }
+
+    fn recompute(&mut self) {
+        self.result = (!self.payload.is_empty()).then(|| inspect(&self.payload, &self.signature));
+    }
+
+    fn signer(&self) -> Option<Action> {
+        let signer = self.result.as_ref()?.as_ref().ok()?.signer?;
+        let address = signer.to_checksum(None);
+        Some(Action::SelectionChanged(SelectedEntity::Address(
+            AddressRef {
+                label: format!("Signer {}", short_hex(&address)),
+                address,
+                chain: self.chain.clone(),
+            },
+        )))
+    }
+
+    fn field_title(&self, name: &str, focused: bool) -> Line<'static> {
+        Line::from(Span::styled(
+            format!("{} {name}", if focused { "›" } else { " " }),
+            Style::default().fg(if focused { Color::Yellow } else { Color::Gray }),
+        ))
+    }
+}
+
+impl Component for TypedDataModal {
+    type Command = TypedDataCommand;
+
+    fn init(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<()> {
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        command: &Self::Command,
+        _ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        match command {
+            TypedDataCommand::InputChar(c) => {
+                self.field_mut().push(*c);
+                self.recompute();
+            }
+            TypedDataCommand::Backspace => {
+                self.field_mut().pop();
+                self.recompute();
+            }
+            TypedDataCommand::ClearField => {
+                self.field_mut().clear();
+                self.recompute();
+            }
+            TypedDataCommand::SwitchField => self.editing_signature = !self.editing_signature,
+            TypedDataCommand::OpenSigner => return Ok(self.signer()),
+            TypedDataCommand::Close => return Ok(Some(Action::CloseModal)),
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, frame: &mut Frame<'_>, area: Rect, _ctx: &AppView<'_>) {
+        let modal_area = centered_rect(100, 26, area);
+        frame.render_widget(Clear, modal_area);
+
+        let block = Block::default()
+            .title(Span::styled(
+                "Signature inspector",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+
+        let mut lines = vec![self.field_title(
+            "EIP-712 JSON or personal_sign message",
+            !self.editing_signature,
+        )];
+        let payload: Vec<&str> = self.payload.split('\n').collect();
+        if payload.len() > PAYLOAD_LINES {
+            lines.push(Line::from(Span::styled(
+                format!("    … {} earlier line(s)", payload.len() - PAYLOAD_LINES),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        let shown = &payload[payload.len().saturating_sub(PAYLOAD_LINES)..];
+        for (index, line) in shown.iter().enumerate() {
+            let cursor = !self.editing_signature && index + 1 == shown.len();
+            lines.push(Line::from(format!(
+                "    {line}{}",
+                if cursor { "_" } else { "" }
+            )));
+        }
+        lines.push(self.field_title(
+            "Signature (65 bytes, or 64 compact)",
+            self.editing_signature,
+        ));
+        lines.push(Line::from(format!(
+            "    {}{}",
+            self.signature,
+            if self.editing_signature { "_" } else { "" }
+        )));
+        lines.push(Line::from(""));
+
+        match &self.result {
+            None => lines.push(Line::from(Span::styled(
+                "Paste typed data from eth_signTypedData_v4 or a message, then its signature.",
+                Style::default().fg(Color::DarkGray),
+            ))),
+            Some(Err(err)) => lines.push(Line::from(Span::styled(
+                err.clone(),
+                Style::default().fg(Color::Red),
+            ))),
+            Some(Ok(inspection)) => lines.extend(inspection.rows.iter().map(|(label, value)| {
+                let style = match *label {
+                    "signer" => Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                    "warning" => Style::default().fg(Color::Yellow),
+                    _ => Style::default(),
+                };
+                Line::from(vec![
+                    Span::styled(format!("{label:<18}"), Style::default().fg(Color::Gray)),
+                    Span::styled(value.clone(), style),
+                ])
+            })),
+        }
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[0]);
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "Tab Field • Ctrl+U Clear field • Enter Open signer • Esc Close",
+                Style::default().fg(Color::Gray),
+            ))),
+            chunks[1],
+        );
+    }
+
+    fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{
+    app::{Action, AppContext, AppResult, AppView, ReleaseInfo, updates::CURRENT_VERSION},
+    components::Component,
+    ui::util::centered_rect,
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+#[derive(Debug, Clone)]
+pub enum UpdateModalCommand {
+    ScrollUp,
+    ScrollDown,
+    Close,
+}
+
+/// Shows the changelog of a newer release found by the startup update check.
+#[derive(Debug)]
+pub struct UpdateModal {
+    release: ReleaseInfo,
+    scroll: u16,
+}
+
+impl UpdateModal {
+    pub fn new(release: ReleaseInfo) -> Self {
+        Self { release, scroll: 0 }
+    }
+
+    pub fn command_from_key(event: KeyEvent) -> Option<UpdateModalCommand> {
+        match event.code {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => Some(UpdateModalCommand::Close),
+            KeyCode::Char('j') | KeyCode::Down => Some(UpdateModalCommand::ScrollDown),
+            KeyCode::Char('k') | KeyCode::Up => Some(UpdateModalCommand::ScrollUp),
+            _ => None,
+        }
+    }
+}
+
+impl Component for UpdateModal {
+    type Command = UpdateModalCommand;
+
+    fn init(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<()> {
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        command: &Self::Command,
+        _ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        match command {
+            UpdateModalCommand::ScrollUp => self.scroll = self.scroll.saturating_sub(1),
+            UpdateModalCommand::ScrollDown => {
+                let max = self.release.changelog.lines().count() as u16;
+                self.scroll = (self.scroll + 1).min(max);
+            }
+            UpdateModalCommand::Close => return Ok(Some(Action::CloseModal)),
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, frame: &mut Frame<'_>, area: Rect, _ctx: &AppView<'_>) {
+        let modal_area = centered_rect(80, 24, area);
+        frame.render_widget(Clear, modal_area);
+
+        let block = Block::default()
+            .title(Span::styled(
+                format!(
+                    "Update available: v{} (installed v{CURRENT_VERSION})",
+                    self.release.version
+                ),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(2)])
+            .split(inner);
+
+        let changelog = if self.release.changelog.trim().is_empty() {
+            "No changelog published for this release.".to_string()
+        } else {
+            self.release.changelog.clone()
+        };
+        let body = Paragraph::new(changelog)
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll, 0));
+        frame.render_widget(body, chunks[0]);
+
+        let footer = Paragraph::new(vec![
+            Line::from(Span::styled(
+                self.release.url.clone(),
+                Style::default().fg(Color::Blue),
+            )),
+            Line::from(Span::styled(
+                "j/k Scroll • Esc Close",
+                Style::default().fg(Color::Gray),
+            )),
+        ]);
+        frame.render_widget(footer, chunks[1]);
+    }
+
+    fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,316 @@
+use crate::{
+    app::{
+        Action, AddressRef, AppContext, AppResult, AppView, Message,
+        scheduler::{Priority, host_key},
+        search::parse_address_input,
+        upgrade_impact::{UpgradeReport, simulate_upgrade},
+    },
+    components::Component,
+    ui::util::{centered_rect, short_hex},
+};
+use alloy::primitives::Address;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+#[derive(Debug, Clone)]
+pub enum UpgradeImpactCommand {
+    InputChar(char),
+    Backspace,
+    Submit,
+    Edit,
+    ScrollDown,
+    ScrollUp,
+    Close,
+    Loaded(Result<UpgradeReport, String>),
+}
+
+#[derive(Debug)]
+enum Stage {
+    Input,
+    Running,
+    Done(Result<UpgradeReport, String>),
+}
+
+/// Prompt for a new implementation and show the simulated before/after of the proxy.
+#[derive(Debug)]
+pub struct UpgradeImpactModal {
+    proxy: AddressRef,
+    input: String,
+    stage: Stage,
+    message: Option<String>,
+    scroll: u16,
+}
+
+impl UpgradeImpactModal {
+    pub fn new(proxy: AddressRef) -> Self {
+        Self {
+            proxy,
+            input: String::new(),
+            stage: Stage::Input,
+            message: None,
+            scroll: 0,
+        }
+    }
+
+    pub fn command_from_key(&self, event: KeyEvent) -> Option<UpgradeImpactCommand> {
+        if matches!(self.stage, Stage::Input) {
+            return match event.code {
+                KeyCode::Esc => Some(UpgradeImpactCommand::Close),
+                KeyCode::Enter => Some(UpgradeImpactCommand::Submit),
+                KeyCode::Backspace => Some(UpgradeImpactCommand::Backspace),
+                KeyCode::Char(c) if !event.modifiers.contains(KeyModifiers::CONTROL) => {
+                    Some(UpgradeImpactCommand::InputChar(c))
+                }
+                _ => None,
+            };
+        }
+        match event.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(UpgradeImpactCommand::Close),
+            KeyCode::Char('j') | KeyCode::Down => Some(UpgradeImpactCommand::ScrollDown),
+            KeyCode::Char('k') | KeyCode::Up => Some(UpgradeImpactCommand::ScrollUp),
+            KeyCode::Char('e') => Some(UpgradeImpactCommand::Edit),
+            _ => None,
+        }
+    }
+
+    pub fn insert_text(&mut self, text: &str) {
+        if matches!(self.stage, Stage::Input) {
+            self.input
+                .extend(text.chars().filter(|c| !c.is_whitespace()));
+        }
+    }
+
+    fn submit(&mut self, ctx: &mut AppContext<'_>) {
+        let new_implementation = match parse_address_input(&self.input) {
+            Ok(address) => address,
+            Err(err) => {
+                self.message = Some(err);
+                return;
+            }
+        };
+        let Ok(proxy) = self.proxy.address.parse::<Address>() else {
+            self.message = Some("Selected address is not a valid proxy address".into());
+            return;
+        };
+        let chain = self.proxy.chain.clone();
+        let Some(rpc_url) = ctx.state.secrets.rpc_url(&chain).map(str::to_string) else {
+            self.message = Some(format!("Configure an RPC endpoint for {chain} first"));
+            return;
+        };
+        let api_key = ctx
+            .state
+            .secrets
+            .explorer_api_key(&chain)
+            .map(str::to_string);
+        self.message = None;
+        self.scroll = 0;
+        self.stage = Stage::Running;
+        let providers = ctx.commands.providers().clone();
+        let host = host_key(Some(&rpc_url), &chain);
+        ctx.commands
+            .spawn_scheduled(host, Priority::Foreground, move || async move {
+                Message::UpgradeSimulated(
+                    simulate_upgrade(
+                        &providers,
+                        &rpc_url,
+                        api_key,
+                        chain,
+                        proxy,
+                        new_implementation,
+                    )
+                    .await,
+                )
+            });
+    }
+
+    fn report_lines(report: &UpgradeReport) -> Vec<Line<'static>> {
+        let heading = Style::default().add_modifier(Modifier::BOLD);
+        let mut lines = vec![
+            Line::from(format!(
+                "{} → {}",
+                short_hex(&report.current_implementation.to_string()),
+                short_hex(&report.new_implementation.to_string())
+            )),
+            Line::from(format!(
+                "{} of {} view functions change • {} ABI entries added • {} removed",
+                report.changed_views(),
+                report.views.len(),
+                report.added.len(),
+                report.removed.len()
+            )),
+        ];
+        for note in &report.notes {
+            lines.push(Line::from(Span::styled(
+                format!("⚠ {note}"),
+                Style::default().fg(Color::Yellow),
+            )));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("View outputs", heading)));
+        for view in &report.views {
+            if view.changed() {
+                lines.push(Line::from(Span::styled(
+                    format!("~ {}", view.signature),
+                    Style::default().fg(Color::Yellow),
+                )));
+                lines.push(Line::from(Span::styled(
+                    format!("    before {}", view.before),
+                    Style::default().fg(Color::Red),
+                )));
+                lines.push(Line::from(Span::styled(
+                    format!("    after  {}", view.after),
+                    Style::default().fg(Color::Green),
+                )));
+            } else {
+                lines.push(Line::from(Span::styled(
+                    format!("  {} = {}", view.signature, view.before),
+                    Style::default().fg(Color::Gray),
+                )));
+            }
+        }
+
+        if !report.added.is_empty() || !report.removed.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled("ABI changes", heading)));
+            lines.extend(report.added.iter().map(|entry| {
+                Line::from(Span::styled(
+                    format!("+ {entry}"),
+                    Style::default().fg(Color::Green),
+                ))
+            }));
+            lines.extend(report.removed.iter().map(|entry| {
+                Line::from(Span::styled(
+                    format!("- {entry}"),
+                    Style::default().fg(Color::Red),
+                ))
+            }));
+        }
+        lines
+    }
+}
+
+impl Component for UpgradeImpactModal {
+    type Command = UpgradeImpactCommand;
+
+    fn init(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<()> {
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        command: &Self::Command,
+        ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        match command {
+            UpgradeImpactCommand::InputChar(c) => self.input.push(*c),
+            UpgradeImpactCommand::Backspace => {
+                self.input.pop();
+            }
+            UpgradeImpactCommand::Submit => self.submit(ctx),
+            UpgradeImpactCommand::Edit => {
+                if matches!(self.stage, Stage::Done(_)) {
+                    self.stage = Stage::Input;
+                }
+            }
+            UpgradeImpactCommand::ScrollDown => self.scroll = self.scroll.saturating_add(1),
+            UpgradeImpactCommand::ScrollUp => self.scroll = self.scroll.saturating_sub(1),
+            UpgradeImpactCommand::Loaded(result) => {
+                if matches!(self.stage, Stage::Running) {
+                    self.stage = Stage::Done(result.clone());
+                }
+            }
+            UpgradeImpactCommand::Close => return Ok(Some(Action::CloseModal)),
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, frame: &mut Frame<'_>, area: Rect, _ctx: &AppView<'_>) {
+        let modal_area = centered_rect(90, 28, area);
+        frame.render_widget(Clear, modal_area);
+
+        let block = Block::default()
+            .title(Span::styled(
+                format!(
+                    "Upgrade impact • {} on {}",
+                    short_hex(&self.proxy.address),
+                    self.proxy.chain
+                ),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+
+        let (body, footer) = match &self.stage {
+            Stage::Input => {
+                let mut lines = vec![
+                    Line::from("New implementation address"),
+                    Line::from(Span::styled(
+                        format!("› {}_", self.input),
+                        Style::default().fg(Color::Yellow),
+                    )),
+                    Line::from(""),
+                    Line::from(Span::styled(
+                        "View functions are eth_call'ed through the proxy at the latest block, \
+                         then again with its EIP-1967 implementation slot overridden.",
+                        Style::default().fg(Color::DarkGray),
+                    )),
+                ];
+                if let Some(message) = &self.message {
+                    lines.push(Line::from(Span::styled(
+                        message.clone(),
+                        Style::default().fg(Color::Red),
+                    )));
+                }
+                (lines, "Enter Simulate • Esc Close")
+            }
+            Stage::Running => (
+                vec![Line::from("Simulating upgrade against the latest block…")],
+                "Esc Close",
+            ),
+            Stage::Done(Err(err)) => (
+                vec![Line::from(Span::styled(
+                    format!("Simulation failed: {err}"),
+                    Style::default().fg(Color::Red),
+                ))],
+                "e Edit address • Esc Close",
+            ),
+            Stage::Done(Ok(report)) => (
+                Self::report_lines(report),
+                "j/k Scroll • e Edit address • Esc Close",
+            ),
+        };
+        frame.render_widget(
+            Paragraph::new(body)
+                .wrap(Wrap { trim: false })
+                .scroll((self.scroll, 0)),
+            chunks[0],
+        );
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                footer,
+                Style::default().fg(Color::Gray),
+            ))),
+            chunks[1],
+        );
+    }
+
+    fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,865 @@
+use crate::{
+    app::{
+        Action, AppContext, AppResult, AppView, Message, SelectedEntity, TransactionRef, chains,
+        ledger::ClearSigning,
+        pending::PendingTransaction,
+        signing::{self, Credentials, PreparedTransaction, SignerSource, TransactionCall},
+        write::{decode_call, describe_inputs, encode_call, parse_value},
+    },
+    components::Component,
+    ui::util::{centered_rect, short_hex},
+};
+use alloy::{
+    hex,
+    json_abi::{Function, StateMutability},
+    primitives::{Address, B256, Bytes, U256, utils::format_units},
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+#[derive(Debug, Clone)]
+pub enum WriteCommand {
+    Next,
+    Previous,
+    InputChar(char),
+    Backspace,
+    SwitchField,
+    Submit,
+    /// Sign and broadcast from the confirmation screen.
+    Confirm,
+    Back,
+    Prepared(Result<Box<PreparedTransaction>, String>),
+    Sent(Result<String, String>),
+}
+
+/// The call being built: function, encoded calldata and value.
+#[derive(Debug, Clone)]
+struct Call {
+    function: Function,
+    input: Bytes,
+    value: U256,
+    /// Arguments decoded back from `input`.
+    decoded: String,
+}
+
+#[derive(Debug)]
+enum Stage {
+    Function,
+    Arguments {
+        function: Function,
+        args: String,
+        value: String,
+        editing_value: bool,
+        error: Option<String>,
+    },
+    Passphrase {
+        call: Call,
+        passphrase: String,
+        error: Option<String>,
+    },
+    Preparing(Call),
+    Confirm {
+        call: Call,
+        prepared: Box<PreparedTransaction>,
+    },
+    /// Signing and broadcasting, with the hash a Ledger asks to approve
+    /// when it blind-signs.
+    Sending {
+        call: Call,
+        signing_hash: Option<B256>,
+    },
+    Done(Result<String, String>),
+}
+
+/// Build a state-changing call to a contract from its ABI, unlock a
+/// keystore or connect a Ledger, and sign and broadcast it after an
+/// explicit confirmation.
+#[derive(Debug)]
+pub struct WriteModal {
+    chain: String,
+    contract: Address,
+    name: String,
+    signer: SignerSource,
+    functions: Vec<Function>,
+    selected: usize,
+    stage: Stage,
+}
+
+impl WriteModal {
+    pub fn new(
+        chain: String,
+        contract: Address,
+        name: String,
+        signer: SignerSource,
+        functions: Vec<Function>,
+    ) -> Self {
+        Self {
+            chain,
+            contract,
+            name,
+            signer,
+            functions,
+            selected: 0,
+            stage: Stage::Function,
+        }
+    }
+
+    pub fn command_from_key(&self, event: KeyEvent) -> Option<WriteCommand> {
+        let typing = matches!(
+            self.stage,
+            Stage::Arguments { .. } | Stage::Passphrase { .. }
+        );
+        match event.code {
+            KeyCode::Esc => Some(WriteCommand::Back),
+            KeyCode::Enter => Some(WriteCommand::Submit),
+            KeyCode::Down if !typing => Some(WriteCommand::Next),
+            KeyCode::Up if !typing => Some(WriteCommand::Previous),
+            KeyCode::Tab | KeyCode::BackTab if typing => Some(WriteCommand::SwitchField),
+            KeyCode::Backspace if typing => Some(WriteCommand::Backspace),
+            KeyCode::Char(c) if typing && !event.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(WriteCommand::InputChar(c))
+            }
+            KeyCode::Char('j') => Some(WriteCommand::Next),
+            KeyCode::Char('k') => Some(WriteCommand::Previous),
+            KeyCode::Char('y') if matches!(self.stage, Stage::Confirm { .. }) => {
+                Some(WriteCommand::Confirm)
+            }
+            KeyCode::Char('n') if matches!(self.stage, Stage::Confirm { .. }) => {
+                Some(WriteCommand::Back)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn insert_text(&mut self, text: &str) {
+        for c in text.chars().filter(|c| !matches!(c, '\r' | '\n')) {
+            self.input_char(c);
+        }
+    }
+
+    fn input_char(&mut self, c: char) {
+        match &mut self.stage {
+            Stage::Arguments {
+                args,
+                value,
+                editing_value,
+                error,
+                ..
+            } => {
+                if *editing_value { value } else { args }.push(c);
+                *error = None;
+            }
+            Stage::Passphrase { passphrase, .. }
+                if matches!(self.signer, SignerSource::Keystore(_)) =>
+            {
+                passphrase.push(c)
+            }
+            _ => {}
+        }
+    }
+
+    fn backspace(&mut self) {
+        match &mut self.stage {
+            Stage::Arguments {
+                args,
+                value,
+                editing_value,
+                ..
+            } => {
+                if *editing_value { value } else { args }.pop();
+            }
+            Stage::Passphrase { passphrase, .. } => {
+                passphrase.pop();
+            }
+            _ => {}
+        }
+    }
+
+    fn submit(&mut self, ctx: &mut AppContext<'_>) -> Option<Action> {
+        match &mut self.stage {
+            Stage::Function => {
+                let function = self.functions.get(self.selected)?.clone();
+                self.stage = Stage::Arguments {
+                    function,
+                    args: String::new(),
+                    value: String::new(),
+                    editing_value: false,
+                    error: None,
+                };
+            }
+            Stage::Arguments {
+                function,
+                args,
+                value,
+                error,
+                ..
+            } => {
+                let built = encode_call(function, args).and_then(|input| {
+                    let value = parse_value(value)?;
+                    if !value.is_zero() && function.state_mutability != StateMutability::Payable {
+                        return Err(format!("{} is not payable", function.name));
+                    }
+                    let decoded = decode_call(function, &input)?;
+                    Ok(Call {
+                        function: function.clone(),
+                        input,
+                        value,
+                        decoded,
+                    })
+                });
+                match built {
+                    Ok(call) => {
+                        self.stage = Stage::Passphrase {
+                            call,
+                            passphrase: String::new(),
+                            error: None,
+                        }
+                    }
+                    Err(err) => *error = Some(err),
+                }
+            }
+            Stage::Passphrase {
+                call, passphrase, ..
+            } => {
+                let (call, passphrase) = (call.clone(), std::mem::take(passphrase));
+                if let Err(err) = self.prepare(call, passphrase, ctx)
+                    && let Stage::Passphrase { error, .. } = &mut self.stage
+                {
+                    *error = Some(err);
+                }
+            }
+            Stage::Done(Ok(hash)) => {
+                return Some(Action::SelectionChanged(SelectedEntity::Transaction(
+                    TransactionRef {
+                        label: format!("Txn {}", short_hex(hash)),
+                        hash: hash.clone(),
+                        chain: self.chain.clone(),
+                    },
+                )));
+            }
+            Stage::Preparing(_)
+            | Stage::Confirm { .. }
+            | Stage::Sending { .. }
+            | Stage::Done(_) => {}
+        }
+        None
+    }
+
+    /// Unlock the signer and fill in the transaction in the background.
+    fn prepare(
+        &mut self,
+        call: Call,
+        passphrase: String,
+        ctx: &mut AppContext<'_>,
+    ) -> Result<(), String> {
+        let rpc_url = ctx
+            .state
+            .secrets
+            .rpc_url(&self.chain)
+            .map(str::to_string)
+            .ok_or_else(|| format!("Configure an RPC endpoint for {} first", self.chain))?;
+        let credentials = Credentials {
+            source: self.signer.clone(),
+            passphrase,
+        };
+        let request = TransactionCall {
+            to: self.contract,
+            input: call.input.clone(),
+            value: call.value,
+        };
+        let queue = ctx.state.pending_transactions.clone();
+        self.stage = Stage::Preparing(call);
+        let rpc = ctx.commands.providers().rpc.clone();
+        ctx.commands.spawn_async(move || async move {
+            Message::WritePrepared(
+                signing::prepare(rpc, rpc_url, credentials, request, queue)
+                    .await
+                    .map(Box::new),
+            )
+        });
+        Ok(())
+    }
+
+    fn confirm(&mut self, ctx: &mut AppContext<'_>) {
+        let Stage::Confirm { call, prepared } = &self.stage else {
+            return;
+        };
+        let Some(rpc_url) = ctx.state.secrets.rpc_url(&self.chain).map(str::to_string) else {
+            self.stage = Stage::Done(Err(format!(
+                "Configure an RPC endpoint for {} first",
+                self.chain
+            )));
+            return;
+        };
+        let (chain, prepared) = (self.chain.clone(), (**prepared).clone());
+        self.stage = Stage::Sending {
+            call: call.clone(),
+            signing_hash: prepared
+                .clear_signing
+                .as_ref()
+                .is_some_and(ClearSigning::is_blind)
+                .then(|| prepared.signing_hash()),
+        };
+        let rpc = ctx.commands.providers().rpc.clone();
+        ctx.commands.spawn_async(move || async move {
+            let sent = signing::broadcast(rpc, rpc_url, prepared.clone()).await;
+            Message::WriteSent(
+                sent.map(|hash| Box::new(PendingTransaction::sent(chain, &prepared, hash))),
+            )
+        });
+    }
+
+    /// Step back one stage; the first stage and finished sends close.
+    fn back(&mut self) -> Option<Action> {
+        self.stage = match std::mem::replace(&mut self.stage, Stage::Function) {
+            Stage::Function | Stage::Done(_) => return Some(Action::CloseModal),
+            Stage::Arguments { .. } => Stage::Function,
+            Stage::Passphrase { call, .. } | Stage::Confirm { call, .. } => Stage::Arguments {
+                function: call.function,
+                args: String::new(),
+                value: String::new(),
+                editing_value: false,
+                error: None,
+            },
+            // The request is already out; only its result can be awaited.
+            stage @ (Stage::Preparing(_) | Stage::Sending { .. }) => stage,
+        };
+        None
+    }
+
+    fn render_functions(&self, frame: &mut Frame<'_>, area: Rect) {
+        if self.functions.is_empty() {
+            frame.render_widget(
+                Paragraph::new("The ABI has no state-changing functions."),
+                area,
+            );
+            return;
+        }
+        let items: Vec<ListItem<'_>> = self
+            .functions
+            .iter()
+            .map(|function| {
+                let mut spans = vec![Span::raw(describe_inputs(function))];
+                if function.state_mutability == StateMutability::Payable {
+                    spans.push(Span::styled(
+                        "  payable",
+                        Style::default().fg(Color::Yellow),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+        let list = List::new(items)
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("› ");
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn call_lines(&self, call: &Call) -> Vec<Line<'static>> {
+        let field = |label: &str, value: String| {
+            Line::from(vec![
+                Span::styled(format!("{label:<10}"), Style::default().fg(Color::Gray)),
+                Span::raw(value),
+            ])
+        };
+        vec![
+            field("Chain", self.chain.clone()),
+            field(
+                "To",
+                format!("{} ({})", self.contract.to_checksum(None), self.name),
+            ),
+            field("Call", call.decoded.clone()),
+            field("Value", native(call.value, &self.chain)),
+            field("Calldata", hex::encode_prefixed(&call.input)),
+        ]
+    }
+}
+
+/// A wei amount in the native currency of `chain`.
+fn native(wei: U256, chain: &str) -> String {
+    format!(
+        "{} {}",
+        format_units(wei, "ether").unwrap_or_else(|_| wei.to_string()),
+        chains::currency(chain)
+    )
+}
+
+impl Component for WriteModal {
+    type Command = WriteCommand;
+
+    fn init(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<()> {
+        Ok(())
+    }
+
+    fn update(
+        &mut self,
+        command: &Self::Command,
+        ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        match command {
+            WriteCommand::Next if matches!(self.stage, Stage::Function) => {
+                if self.selected + 1 < self.functions.len() {
+                    self.selected += 1;
+                }
+            }
+            WriteCommand::Previous if matches!(self.stage, Stage::Function) => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            WriteCommand::Next | WriteCommand::Previous => {}
+            WriteCommand::InputChar(c) => self.input_char(*c),
+            WriteCommand::Backspace => self.backspace(),
+            WriteCommand::SwitchField => {
+                if let Stage::Arguments {
+                    function,
+                    editing_value,
+                    ..
+                } = &mut self.stage
+                {
+                    *editing_value =
+                        !*editing_value && function.state_mutability == StateMutability::Payable;
+                }
+            }
+            WriteCommand::Submit => return Ok(self.submit(ctx)),
+            WriteCommand::Confirm => self.confirm(ctx),
+            WriteCommand::Back => return Ok(self.back()),
+            WriteCommand::Prepared(result) => {
+                if let Stage::Preparing(call) = &self.stage {
+                    let call = call.clone();
+                    self.stage = match result {
+                        Ok(prepared) => Stage::Confirm {
+                            call,
+                            prepared: prepared.clone(),
+                        },
+                        Err(err) => Stage::Passphrase {
+                            call,
+                            passphrase: String::new(),
+                            error: Some(err.clone()),
+                        },
+                    };
+                }
+            }
+            WriteCommand::Sent(result) => {
+                if matches!(self.stage, Stage::Sending { .. }) {
+                    self.stage = Stage::Done(result.clone());
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn render(&mut self, frame: &mut Frame<'_>, area: Rect, _ctx: &AppView<'_>) {
+        let modal_area = centered_rect(100, 24, area);
+        frame.render_widget(Clear, modal_area);
+
+        let block = Block::default()
+            .title(Span::styled(
+                format!("Write • {} • {}", self.name, self.chain),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Gray));
+        let inner = block.inner(modal_area);
+        frame.render_widget(block, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+
+        let muted = Style::default().fg(Color::DarkGray);
+        let (lines, hint) = match &self.stage {
+            Stage::Function => {
+                self.render_functions(frame, chunks[0]);
+                (None, "j/k Select • Enter Choose • Esc Close")
+            }
+            Stage::Arguments {
+                function,
+                args,
+                value,
+                editing_value,
+                error,
+            } => {
+                let focused =
+                    |on: bool| Style::default().fg(if on { Color::Yellow } else { Color::White });
+                let cursor = |on: bool| if on { "_" } else { "" };
+                let mut lines = vec![
+                    Line::from(Span::styled(
+                        describe_inputs(function),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    )),
+                    Line::from(Span::styled(
+                        "Comma-separated; arrays as [1,2], tuples as (a,b), strings may be quoted.",
+                        muted,
+                    )),
+                    Line::from(""),
+                    Line::from(vec![
+                        Span::styled("Arguments ", Style::default().fg(Color::Gray)),
+                        Span::styled(
+                            format!("{args}{}", cursor(!*editing_value)),
+                            focused(!*editing_value),
+                        ),
+                    ]),
+                ];
+                if function.state_mutability == StateMutability::Payable {
+                    lines.push(Line::from(vec![
+                        Span::styled("Value     ", Style::default().fg(Color::Gray)),
+                        Span::styled(
+                            format!("{value}{}", cursor(*editing_value)),
+                            focused(*editing_value),
+                        ),
+                        Span::styled("  wei, or with a unit (0.1 ether)", muted),
+                    ]));
+                }
+                if let Some(error) = error {
+                    lines.push(Line::from(""));
+                    lines.push(Line::from(Span::styled(
+                        error.clone(),
+                        Style::default().fg(Color::Red),
+                    )));
+                }
+                (Some(lines), "Tab Field • Enter Continue • Esc Back")
+            }
+            Stage::Passphrase {
+                call,
+                passphrase,
+                error,
+            } => {
+                let mut lines = self.call_lines(call);
+                lines.push(Line::from(""));
+                lines.push(Line::from(vec![
+                    Span::styled("Signer    ", Style::default().fg(Color::Gray)),
+                    Span::raw(self.signer.describe()),
+                ]));
+                let hint = match self.signer {
+                    SignerSource::Keystore(_) => {
+                        lines.push(Line::from(vec![
+                            Span::styled("Password  ", Style::default().fg(Color::Gray)),
+                            Span::styled(
+                                format!("{}_", "•".repeat(passphrase.chars().count())),
+                                Style::default().fg(Color::Yellow),
+                            ),
+                        ]));
+                        "Enter Unlock and estimate • Esc Back"
+                    }
+                    SignerSource::Ledger(_) => {
+                        lines.push(Line::from(Span::styled(
+                            "Connect and unlock the Ledger and open its Ethereum app. It \
+                             will show the sender address for confirmation.",
+                            muted,
+                        )));
+                        "Enter Connect and estimate • Esc Back"
+                    }
+                };
+                if let Some(error) = error {
+                    lines.push(Line::from(Span::styled(
+                        error.clone(),
+                        Style::default().fg(Color::Red),
+                    )));
+                }
+                (Some(lines), hint)
+            }
+            Stage::Preparing(call) => {
+                let mut lines = self.call_lines(call);
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(
+                    match self.signer {
+                        SignerSource::Keystore(_) => "Unlocking keystore and estimating gas…",
+                        SignerSource::Ledger(_) => {
+                            "Confirm the address on the Ledger; gas is estimated after that…"
+                        }
+                    },
+                    muted,
+                )));
+                (Some(lines), "Please wait")
+            }
+            Stage::Confirm { call, prepared } => {
+                let mut lines = vec![Line::from(Span::styled(
+                    "Review before signing. This sends a real transaction.",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ))];
+                lines.push(Line::from(""));
+                lines.extend(self.call_lines(call));
+                let field = |label: &str, value: String| {
+                    Line::from(vec![
+                        Span::styled(format!("{label:<10}"), Style::default().fg(Color::Gray)),
+                        Span::raw(value),
+                    ])
+                };
+                lines.push(field(
+                    "From",
+                    format!(
+                        "{}{}",
+                        prepared.from().to_checksum(None),
+                        if prepared.verified_on_device {
+                            " (confirmed on Ledger)"
+                        } else {
+                            ""
+                        }
+                    ),
+                ));
+                lines.push(field(
+                    "Nonce",
+                    format!("{} (chain id {})", prepared.tx.nonce, prepared.tx.chain_id),
+                ));
+                lines.push(field(
+                    "Gas",
+                    format!(
+                        "{} at most {} gwei, up to {} in fees",
+                        prepared.tx.gas_limit,
+                        format_units(U256::from(prepared.tx.max_fee_per_gas), "gwei")
+                            .unwrap_or_default(),
+                        native(prepared.max_fee(), &self.chain)
+                    ),
+                ));
+                if let Some(clear_signing) = &prepared.clear_signing {
+                    lines.push(Line::from(Span::styled(clear_signing.describe(), muted)));
+                    if clear_signing.is_blind() {
+                        lines.push(field(
+                            "Sign hash",
+                            format!("{:#x}", prepared.signing_hash()),
+                        ));
+                        lines.push(Line::from(Span::styled(
+                            "Approve on the device only if the two hashes match.",
+                            muted,
+                        )));
+                    }
+                }
+                (Some(lines), "y Sign and send • n/Esc Back")
+            }
+            Stage::Sending { call, signing_hash } => {
+                let mut lines = self.call_lines(call);
+                lines.push(Line::from(""));
+                match self.signer {
+                    SignerSource::Keystore(_) => {
+                        lines.push(Line::from(Span::styled("Broadcasting…", muted)));
+                    }
+                    SignerSource::Ledger(_) => match signing_hash {
+                        Some(signing_hash) => {
+                            lines.push(Line::from(Span::styled(
+                                "Approve on the Ledger only if it shows this hash:",
+                                muted,
+                            )));
+                            lines.push(Line::from(format!("{signing_hash:#x}")));
+                        }
+                        None => lines.push(Line::from(Span::styled(
+                            "Check the call on the Ledger's screen and approve it there…",
+                            muted,
+                        ))),
+                    },
+                }
+                (Some(lines), "Please wait")
+            }
+            Stage::Done(Ok(hash)) => (
+                Some(vec![
+                    Line::from(Span::styled(
+                        "Transaction sent",
+                        Style::default()
+                            .fg(Color::Green)
+                            .add_modifier(Modifier::BOLD),
+                    )),
+                    Line::from(hash.clone()),
+                ]),
+                "Enter Open transaction • Esc Close",
+            ),
+            Stage::Done(Err(err)) => (
+                Some(vec![Line::from(Span::styled(
+                    err.clone(),
+                    Style::default().fg(Color::Red),
+                ))]),
+                "Esc Close",
+            ),
+        };
+        if let Some(lines) = lines {
+            frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[0]);
+        }
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                hint,
+                Style::default().fg(Color::Gray),
+            ))),
+            chunks[1],
+        );
+    }
+
+    fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{App, SecretsState, demo, signing::Wallet, write::writable_functions};
+    use alloy::{consensus::TxEip1559, signers::local::PrivateKeySigner};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    const ABI: &str = r#"[
+        {"type": "function", "name": "deposit", "stateMutability": "payable",
+         "inputs": [{"name": "to", "type": "address"}, {"name": "amount", "type": "uint256"}],
+         "outputs": []}
+    ]"#;
+
+    /// A demo app without RPC endpoints, so nothing here reaches a node.
+    fn app() -> App {
+        let mut app = App::demo(None, demo::providers()).unwrap();
+        app.state.secrets = SecretsState::default();
+        app
+    }
+
+    fn modal() -> WriteModal {
+        WriteModal::new(
+            "Ethereum".into(),
+            Address::repeat_byte(0x11),
+            "Vault".into(),
+            SignerSource::Keystore("deployer.json".into()),
+            writable_functions(ABI).unwrap(),
+        )
+    }
+
+    fn screen(modal: &mut WriteModal, app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(140, 30)).unwrap();
+        terminal
+            .draw(|frame| modal.render(frame, frame.area(), &app.view()))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn press(modal: &mut WriteModal, app: &mut App, code: KeyCode) -> Option<Action> {
+        let command = modal.command_from_key(KeyEvent::new(code, KeyModifiers::NONE))?;
+        modal.update(&command, &mut app.context()).unwrap()
+    }
+
+    /// Choose the function and fill in its arguments and value.
+    fn fill_call(modal: &mut WriteModal, app: &mut App) {
+        press(modal, app, KeyCode::Enter);
+        modal.insert_text("0x00000000000000000000000000000000000000aa, 1000");
+        press(modal, app, KeyCode::Tab);
+        modal.insert_text("0.5 ether");
+        press(modal, app, KeyCode::Enter);
+    }
+
+    fn confirming(modal: &mut WriteModal) {
+        let Stage::Passphrase { call, .. } = &modal.stage else {
+            panic!("expected the passphrase stage");
+        };
+        let call = call.clone();
+        let tx = TxEip1559 {
+            chain_id: 1,
+            nonce: 3,
+            gas_limit: 60_000,
+            max_fee_per_gas: 2_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+            to: modal.contract.into(),
+            value: call.value,
+            input: call.input.clone(),
+            ..Default::default()
+        };
+        modal.stage = Stage::Confirm {
+            call,
+            prepared: Box::new(PreparedTransaction {
+                wallet: Wallet::Keystore(PrivateKeySigner::random()),
+                tx,
+                verified_on_device: false,
+                clear_signing: None,
+            }),
+        };
+    }
+
+    #[test]
+    fn confirmation_shows_the_decoded_call_and_value() {
+        let mut app = app();
+        let mut modal = modal();
+        fill_call(&mut modal, &mut app);
+        confirming(&mut modal);
+
+        let text = screen(&mut modal, &app);
+        assert!(
+            text.contains(
+                "Call      deposit(to: 0x00000000000000000000000000000000000000AA, amount: 1000)"
+            ),
+            "{text}"
+        );
+        assert!(
+            text.contains(&format!(
+                "Value     {}",
+                native(U256::from(500_000_000_000_000_000u64), "Ethereum")
+            )),
+            "{text}"
+        );
+        assert!(text.contains("y Sign and send"), "{text}");
+    }
+
+    #[test]
+    fn backing_out_of_the_confirmation_never_signs() {
+        let mut app = app();
+        for code in [KeyCode::Esc, KeyCode::Char('n')] {
+            let mut modal = modal();
+            fill_call(&mut modal, &mut app);
+            confirming(&mut modal);
+            // Enter is not a confirmation either.
+            press(&mut modal, &mut app, KeyCode::Enter);
+            assert!(matches!(modal.stage, Stage::Confirm { .. }));
+
+            assert!(press(&mut modal, &mut app, code).is_none());
+            assert!(matches!(modal.stage, Stage::Arguments { .. }), "{code:?}");
+            press(&mut modal, &mut app, KeyCode::Esc);
+            assert_eq!(
+                press(&mut modal, &mut app, KeyCode::Esc),
+                Some(Action::CloseModal)
+            );
+        }
+
+        // Only `y` moves on to signing, which stops here for want of an RPC
+        // endpoint before anything is signed.
+        let mut modal = modal();
+        fill_call(&mut modal, &mut app);
+        confirming(&mut modal);
+        press(&mut modal, &mut app, KeyCode::Char('y'));
+        assert!(
+            matches!(&modal.stage, Stage::Done(Err(err)) if err.contains("RPC endpoint")),
+            "{:?}",
+            modal.stage
+        );
+    }
+
+    #[test]
+    fn passphrase_is_never_rendered() {
+        let mut app = app();
+        let mut modal = modal();
+        fill_call(&mut modal, &mut app);
+        let passphrase = "correct-horse-battery";
+        modal.insert_text(passphrase);
+
+        let text = screen(&mut modal, &app);
+        assert!(
+            text.contains(&format!("{}_", "•".repeat(passphrase.len()))),
+            "{text}"
+        );
+        assert!(!text.contains(passphrase), "{text}");
+
+        // A failed unlock shows its error, still without the passphrase.
+        press(&mut modal, &mut app, KeyCode::Enter);
+        let text = screen(&mut modal, &app);
+        assert!(text.contains("Configure an RPC endpoint"), "{text}");
+        assert!(!text.contains(passphrase), "{text}");
+    }
+}
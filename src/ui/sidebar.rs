@@ -1,36 +1,32 @@
-use super::util::short_hex;
+use super::util::{compact_eth, loading_title, short_hex};
 use crate::{
+    app::chains,
+    app::prefetch::Freshness,
+    app::watchlist::{WatchlistState, unix_now},
     app::{
         Action, AddressRef, AppContext, AppResult, AppView, FocusedPane, SelectedEntity,
         SidebarTab, TransactionRef,
     },
-    components::Component,
+    components::{Component, KeyHint},
+    storage::GroupMember,
 };
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    text::Line,
+    text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Tabs},
 };
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct Sidebar {
     addresses: Vec<AddressRef>,
     transactions: Vec<TransactionRef>,
+    /// Mirrors [`WatchlistState::entries`]; balances are read from the state.
+    watchlist: Vec<AddressRef>,
     selected_index: usize,
 }
 
-impl Default for Sidebar {
-    fn default() -> Self {
-        Self {
-            addresses: Vec::new(),
-            transactions: Vec::new(),
-            selected_index: 0,
-        }
-    }
-}
-
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum SidebarCommand {
@@ -51,6 +47,7 @@ impl Sidebar {
         match tab {
             SidebarTab::Addresses => self.addresses.len(),
             SidebarTab::Transactions => self.transactions.len(),
+            SidebarTab::Watchlist => self.watchlist.len(),
         }
     }
 
@@ -77,6 +74,28 @@ impl Sidebar {
         }
     }
 
+    pub fn set_watchlist(&mut self, items: Vec<AddressRef>, current_tab: SidebarTab) {
+        self.watchlist = items;
+        if matches!(current_tab, SidebarTab::Watchlist) {
+            self.clamp_selection(SidebarTab::Watchlist);
+        }
+    }
+
+    /// Every favorite, addresses first, in sidebar order.
+    pub fn favorites(&self) -> Vec<SelectedEntity> {
+        self.addresses
+            .iter()
+            .cloned()
+            .map(SelectedEntity::Address)
+            .chain(
+                self.transactions
+                    .iter()
+                    .cloned()
+                    .map(SelectedEntity::Transaction),
+            )
+            .collect()
+    }
+
     fn selected_entity(&self, tab: SidebarTab, index: usize) -> Option<SelectedEntity> {
         match tab {
             SidebarTab::Addresses => self
@@ -87,6 +106,10 @@ impl Sidebar {
                 .transactions
                 .get(index)
                 .map(|tx| SelectedEntity::Transaction(tx.clone())),
+            SidebarTab::Watchlist => self
+                .watchlist
+                .get(index)
+                .map(|addr| SelectedEntity::Address(addr.clone())),
         }
     }
 
@@ -98,10 +121,18 @@ impl Sidebar {
         self.selected_entity(tab, self.selected_index)
     }
 
+    fn addresses_for(&self, tab: SidebarTab) -> &[AddressRef] {
+        if tab == SidebarTab::Watchlist {
+            &self.watchlist
+        } else {
+            &self.addresses
+        }
+    }
+
     fn display_label(&self, tab: SidebarTab, index: usize) -> String {
         match tab {
-            SidebarTab::Addresses => self
-                .addresses
+            SidebarTab::Addresses | SidebarTab::Watchlist => self
+                .addresses_for(tab)
                 .get(index)
                 .map(|addr| format!("{} [{}]", short_hex(&addr.address), addr.chain))
                 .unwrap_or_default(),
@@ -114,6 +145,63 @@ impl Sidebar {
     }
 }
 
+/// Latest polled balance and time since the address was last seen active,
+/// or the poll error; dim until the first poll lands.
+fn watchlist_spans(watchlist: &WatchlistState, addr: &AddressRef) -> Vec<Span<'static>> {
+    let member = GroupMember {
+        address: addr.address.clone(),
+        chain: addr.chain.clone(),
+    };
+    let mut spans = Vec::new();
+    let alerts = watchlist.alerts(&member);
+    if alerts.any() {
+        spans.push(Span::raw(alerts.marker()));
+    }
+    let Some(snapshot) = watchlist.snapshot(&member) else {
+        spans.push(match watchlist.error(&member) {
+            Some(_) => Span::styled(" ⚠ poll failed", Style::default().fg(Color::Yellow)),
+            None => Span::styled(" …", Style::default().fg(Color::DarkGray)),
+        });
+        return spans;
+    };
+    spans.push(Span::styled(
+        format!(
+            " {} {}",
+            compact_eth(&snapshot.balance_wei),
+            chains::currency(&addr.chain)
+        ),
+        Style::default().fg(Color::Green),
+    ));
+    if let Some(age) = watchlist.activity_age(&member, unix_now()) {
+        spans.push(Span::styled(
+            format!(" · {age}"),
+            Style::default().fg(Color::Gray),
+        ));
+    }
+    if watchlist.error(&member).is_some() {
+        spans.push(Span::styled(" ⚠", Style::default().fg(Color::Yellow)));
+    }
+    spans
+}
+
+/// Age of the background-prefetched copy: green while fresh, yellow once due
+/// for a refresh, and a hollow dot until it is first fetched.
+fn freshness_span(freshness: Freshness) -> Span<'static> {
+    let (text, color) = match freshness {
+        Freshness::Missing => (" ○".to_string(), Color::DarkGray),
+        Freshness::Loading => (" ◌".to_string(), Color::DarkGray),
+        Freshness::Fresh(_) => (
+            format!(" ● {}", freshness.age_label().unwrap_or_default()),
+            Color::Green,
+        ),
+        Freshness::Stale(_) => (
+            format!(" ● {}", freshness.age_label().unwrap_or_default()),
+            Color::Yellow,
+        ),
+    };
+    Span::styled(text, Style::default().fg(color))
+}
+
 impl Component for Sidebar {
     type Command = SidebarCommand;
 
@@ -189,11 +277,7 @@ impl Component for Sidebar {
                         }
                     }
                 }
-                if current_tab == SidebarTab::Addresses {
-                    self.clamp_selection(SidebarTab::Addresses);
-                } else {
-                    self.clamp_selection(SidebarTab::Transactions);
-                }
+                self.clamp_selection(current_tab);
             }
             SidebarCommand::RemoveFavorite(entity) => {
                 let current_tab = ctx.state.navigation.sidebar_tab;
@@ -215,19 +299,14 @@ impl Component for Sidebar {
                         }
                     }
                 }
-                if current_tab == SidebarTab::Addresses {
-                    self.clamp_selection(SidebarTab::Addresses);
-                } else {
-                    self.clamp_selection(SidebarTab::Transactions);
-                }
+                self.clamp_selection(current_tab);
             }
         }
-        if selection_changed {
-            if let Some(entity) =
+        if selection_changed
+            && let Some(entity) =
                 self.selected_entity(ctx.state.navigation.sidebar_tab, self.selected_index)
-            {
-                return Ok(Some(Action::SelectionChanged(entity)));
-            }
+        {
+            return Ok(Some(Action::SelectionChanged(entity)));
         }
         Ok(None)
     }
@@ -243,9 +322,12 @@ impl Component for Sidebar {
             Style::default().add_modifier(Modifier::BOLD)
         };
 
-        let block = Block::default()
+        let mut block = Block::default()
             .borders(Borders::ALL)
             .title(Line::from("[2] Favorites").style(border_style));
+        if let Some(badge) = loading_title(&ctx.state.loading.sidebar) {
+            block = block.title(badge);
+        }
         let inner = block.inner(area);
         frame.render_widget(block, area);
 
@@ -254,10 +336,15 @@ impl Component for Sidebar {
             .constraints([Constraint::Length(3), Constraint::Min(1)])
             .split(inner);
 
-        let tab_titles = vec![Line::from("Addresses"), Line::from("Transactions")];
+        let tab_titles = vec![
+            Line::from("Addresses"),
+            Line::from("Transactions"),
+            Line::from("Watchlist"),
+        ];
         let tab_index = match ctx.state.navigation.sidebar_tab {
             SidebarTab::Addresses => 0,
             SidebarTab::Transactions => 1,
+            SidebarTab::Watchlist => 2,
         };
         let tabs = Tabs::new(tab_titles)
             .select(tab_index)
@@ -267,16 +354,27 @@ impl Component for Sidebar {
 
         let len = self.len(ctx.state.navigation.sidebar_tab);
         if len == 0 {
-            let empty = Paragraph::new("No favorites yet. Press `a` to add one.")
-                .style(Style::default().fg(Color::Gray));
+            let hint = match ctx.state.navigation.sidebar_tab {
+                SidebarTab::Watchlist => "Nothing watched yet. Press `+` on an address.",
+                SidebarTab::Addresses | SidebarTab::Transactions => {
+                    "No favorites yet. Press `a` to add one."
+                }
+            };
+            let empty = Paragraph::new(hint).style(Style::default().fg(Color::Gray));
             frame.render_widget(empty, chunks[1]);
             return;
         }
 
+        let tab = ctx.state.navigation.sidebar_tab;
         let list_items: Vec<ListItem> = (0..len)
             .map(|i| {
-                let label = self.display_label(ctx.state.navigation.sidebar_tab, i);
-                ListItem::new(label)
+                let mut spans = vec![Span::raw(self.display_label(tab, i))];
+                if tab == SidebarTab::Watchlist {
+                    spans.extend(watchlist_spans(&ctx.state.watchlist, &self.watchlist[i]));
+                } else if let Some(entity) = self.selected_entity(tab, i) {
+                    spans.push(freshness_span(ctx.state.prefetch.freshness(&entity)));
+                }
+                ListItem::new(Line::from(spans))
             })
             .collect();
         let highlight = Style::default()
@@ -294,4 +392,18 @@ impl Component for Sidebar {
     fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
         Ok(None)
     }
+    fn key_hints(&self, ctx: &AppView<'_>) -> Vec<KeyHint> {
+        let mut hints = vec![KeyHint::new("Enter", "Open")];
+        if ctx.state.navigation.sidebar_tab == SidebarTab::Watchlist {
+            hints.push(KeyHint::new("n", "Alerts"));
+            hints.push(KeyHint::new("x", "Unwatch"));
+        }
+        hints.extend([
+            KeyHint::new("j/k", "Move"),
+            KeyHint::new("[ ]", "Switch tab"),
+            KeyHint::new("Ctrl+←/→", "Resize"),
+            KeyHint::new("Ctrl+B", "Hide"),
+        ]);
+        hints
+    }
 }
@@ -1,19 +1,42 @@
-use super::util::short_hex;
+use super::util::{is_compact, loading_title, short_hex, ws_status};
 use crate::{
     app::{
-        Action, AddressRef, AppContext, AppResult, AppView, FocusedPane, Message, SelectedEntity,
-        TransactionRef,
+        Action, AppContext, AppResult, AppView, FocusedPane, Message, SelectedEntity, labels,
+        rpc_health, search,
+        suggestions::{self, Suggestion, SuggestionSource},
+        ws,
     },
-    components::Component,
+    components::{Component, KeyHint},
+    storage::SettingKey,
 };
 use ratatui::{
     Frame,
     layout::Rect,
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Paragraph},
+    widgets::{Block, Clear, List, ListItem, ListState, Paragraph},
 };
-use tokio::time::{Duration, sleep};
+use tokio::task::AbortHandle;
+
+/// Connection state of the selected chain's WebSocket endpoints, once used.
+fn ws_indicator(ctx: &AppView<'_>) -> Option<Line<'static>> {
+    let chain = match ctx.state.selected.as_ref()? {
+        SelectedEntity::Address(addr) => addr.chain.as_str(),
+        SelectedEntity::Transaction(tx) => tx.chain.as_str(),
+    };
+    let mut spans = Vec::new();
+    for url in rpc_health::endpoints(ctx.state.secrets.rpc_url(chain)?) {
+        if !ws::is_ws(&url) {
+            continue;
+        }
+        let (label, color) = ws_status(ctx.providers.rpc.ws(), &url)
+            .unwrap_or_else(|| ("idle".to_string(), Color::DarkGray));
+        spans.push(Span::raw(" WS "));
+        spans.push(Span::styled(label, Style::default().fg(color)));
+        spans.push(Span::raw(" "));
+    }
+    (!spans.is_empty()).then(|| Line::from(spans))
+}
 
 #[derive(Debug)]
 pub struct TopBar {
@@ -21,7 +44,17 @@ pub struct TopBar {
     search_active: bool,
     search_value: String,
     pending_search: bool,
+    /// The in-flight lookup, aborted when a new search supersedes it.
+    search_task: Option<AbortHandle>,
     status: Option<String>,
+    /// Everything the prompt can suggest, gathered when it opens.
+    candidates: Vec<Suggestion>,
+    /// Saved searches and favorites are being read off the storage thread.
+    awaiting_candidates: bool,
+    /// A saved-data search submitted before the candidates arrived.
+    pending_saved: Option<String>,
+    suggestions: Vec<Suggestion>,
+    highlighted: Option<usize>,
 }
 
 impl Default for TopBar {
@@ -31,7 +64,13 @@ impl Default for TopBar {
             search_active: false,
             search_value: String::new(),
             pending_search: false,
+            search_task: None,
             status: None,
+            candidates: Vec::new(),
+            awaiting_candidates: false,
+            pending_saved: None,
+            suggestions: Vec::new(),
+            highlighted: None,
         }
     }
 }
@@ -41,7 +80,12 @@ pub enum TopCommand {
     ActivateSearch,
     InputChar(char),
     Backspace,
+    NextSuggestion,
+    PreviousSuggestion,
+    /// Submit the highlighted suggestion, or the typed query.
     Submit,
+    /// Search for `query` directly, without opening the prompt.
+    QuickOpen(String),
     Cancel,
     SearchCompleted {
         query: String,
@@ -51,53 +95,185 @@ pub enum TopCommand {
         query: String,
         error: String,
     },
+    /// Recent searches and favorites read for the suggestions.
+    CandidatesLoaded(Result<Vec<Suggestion>, String>),
     ShowStatus(String),
 }
 
 impl TopBar {
-    const LAST_QUERY_KEY: &'static str = "top:last_query";
-
     pub fn is_search_active(&self) -> bool {
         self.search_active
     }
 
-    fn decode_query(query: &str) -> Result<SelectedEntity, String> {
-        let trimmed = query.trim();
-        let lower = trimmed.trim();
-        let (prefix_stripped, has_prefix) = if let Some(rest) = lower.strip_prefix("0x") {
-            (rest, true)
-        } else {
-            (lower, false)
+    /// Whether `query` is something search can open without looking at
+    /// saved data: a hex address or hash, a block, or an ENS name.
+    fn is_direct_query(query: &str) -> bool {
+        search::parse_query(query).is_ok()
+    }
+
+    /// Abort the in-flight lookup; returns whether there was one.
+    fn abort_search(&mut self) -> bool {
+        let aborted = self.search_task.take().is_some_and(|task| {
+            task.abort();
+            true
+        });
+        self.pending_search = false;
+        aborted
+    }
+
+    /// Read recent searches and favorites on the storage thread, after the
+    /// writes still queued; they arrive as [`TopCommand::CandidatesLoaded`].
+    fn request_candidates(&mut self, ctx: &AppContext<'_>) {
+        self.awaiting_candidates = true;
+        ctx.commands.read_storage(
+            ctx.storage,
+            |storage| {
+                let history = storage
+                    .settings()
+                    .get_json::<Vec<String>>(SettingKey::SearchHistory)?
+                    .unwrap_or_default();
+                let mut candidates: Vec<Suggestion> = history
+                    .iter()
+                    .filter(|query| Self::is_direct_query(query))
+                    .map(|query| Suggestion::recent(query))
+                    .collect();
+                for record in storage.favorites_addresses().list()? {
+                    candidates.push(Suggestion::favorite_address(&record));
+                }
+                for record in storage.favorites_transactions().list()? {
+                    candidates.push(Suggestion::favorite_transaction(&record));
+                }
+                Ok(candidates)
+            },
+            Message::SearchCandidatesLoaded,
+        );
+    }
+
+    /// Add user labels and the bundled registry to the `stored` candidates
+    /// for the dropdown and saved-data search.
+    fn with_labels(mut candidates: Vec<Suggestion>, ctx: &AppContext<'_>) -> Vec<Suggestion> {
+        candidates.extend(
+            ctx.state
+                .labels
+                .user
+                .iter()
+                .map(|label| Suggestion::label(label, SuggestionSource::Label)),
+        );
+        candidates.extend(
+            labels::builtin_labels()
+                .iter()
+                .map(|label| Suggestion::label(label, SuggestionSource::Known)),
+        );
+        candidates
+    }
+
+    /// Open a suggestion: its entity directly, or its query as a search.
+    fn open_suggestion(
+        &mut self,
+        suggestion: Suggestion,
+        ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        self.clear_suggestions();
+        self.search_value = suggestion.query.clone();
+        let Some(entity) = suggestion.entity else {
+            return self.update(&TopCommand::Submit, ctx);
         };
-        if prefix_stripped.is_empty() {
-            return Err("Empty query".into());
+        self.pending_search = true;
+        self.status = Some(format!("Opening {}…", suggestion.title));
+        let query = suggestion.query;
+        ctx.commands
+            .spawn_async(move || async move { Message::SearchCompleted { query, entity } });
+        Ok(Some(Action::LoadingStarted(FocusedPane::Top)))
+    }
+
+    /// Fuzzy-match text that is not an address, hash or block against saved
+    /// data. A single match opens; several are listed in the dropdown.
+    fn search_saved(
+        &mut self,
+        query: &str,
+        candidates: Vec<Suggestion>,
+        ctx: &mut AppContext<'_>,
+    ) -> AppResult<Option<Action>> {
+        let mut matches = suggestions::fuzzy(&candidates, query);
+        match matches.len() {
+            0 => {
+                self.status = Some(format!(
+                    "Nothing saved matches \"{query}\"; enter an address, hash or block"
+                ));
+                Ok(None)
+            }
+            1 => self.open_suggestion(matches.remove(0), ctx),
+            count => {
+                self.candidates = candidates;
+                self.suggestions = matches;
+                self.highlighted = Some(0);
+                self.status = Some(format!("{count} saved matches • ↑/↓ select • Enter opens"));
+                Ok(None)
+            }
         }
-        if prefix_stripped.len() == 40 && prefix_stripped.chars().all(|c| c.is_ascii_hexdigit()) {
-            let address = if has_prefix {
-                format!("0x{prefix_stripped}")
-            } else {
-                format!("0x{prefix_stripped}")
-            };
-            let short = short_hex(&address);
-            return Ok(SelectedEntity::Address(AddressRef {
-                label: format!("Address {short}"),
-                address,
-                chain: "Mainnet".into(),
-            }));
+    }
+
+    fn refresh_suggestions(&mut self) {
+        self.suggestions = suggestions::matching(&self.candidates, &self.search_value);
+        self.highlighted = None;
+    }
+
+    fn clear_suggestions(&mut self) {
+        self.candidates.clear();
+        self.awaiting_candidates = false;
+        self.pending_saved = None;
+        self.suggestions.clear();
+        self.highlighted = None;
+    }
+
+    /// Draw the suggestion dropdown just below the bar at `area`. Called
+    /// after the panes so it stays on top of them.
+    pub fn render_suggestions(&self, frame: &mut Frame<'_>, area: Rect) {
+        if !self.search_active || self.pending_search || self.suggestions.is_empty() {
+            return;
         }
-        if prefix_stripped.len() == 64 && prefix_stripped.chars().all(|c| c.is_ascii_hexdigit()) {
-            let hash = if has_prefix {
-                format!("0x{prefix_stripped}")
-            } else {
-                format!("0x{prefix_stripped}")
-            };
-            return Ok(SelectedEntity::Transaction(TransactionRef {
-                label: format!("Txn {}", short_hex(&hash)),
-                hash,
-                chain: "Mainnet".into(),
-            }));
+        let screen = frame.area();
+        let top = area.bottom();
+        if top >= screen.bottom() {
+            return;
         }
-        Err("Input could not be decoded as a valid address or transaction".into())
+        let dropdown = Rect {
+            x: area.x + 1,
+            y: top,
+            width: area.width.saturating_sub(2).min(80),
+            height: (self.suggestions.len() as u16 + 2).min(screen.bottom() - top),
+        };
+        let items: Vec<ListItem> = self
+            .suggestions
+            .iter()
+            .map(|suggestion| {
+                let mut spans = vec![
+                    Span::styled(
+                        format!("{:<9}", suggestion.source.label()),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::raw(suggestion.title.clone()),
+                ];
+                if suggestion.entity.is_some() {
+                    spans.push(Span::styled(
+                        format!("  {}", short_hex(&suggestion.query)),
+                        Style::default().fg(Color::Gray),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(Block::bordered().title("Suggestions • ↑/↓ select"))
+            .highlight_style(
+                Style::default()
+                    .fg(Color::White)
+                    .bg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            );
+        let mut state = ListState::default().with_selected(self.highlighted);
+        frame.render_widget(Clear, dropdown);
+        frame.render_stateful_widget(list, dropdown, &mut state);
     }
 
     fn status_line(&self) -> Option<Line<'_>> {
@@ -111,12 +287,13 @@ impl Component for TopBar {
     type Command = TopCommand;
 
     fn init(&mut self, ctx: &mut AppContext<'_>) -> AppResult<()> {
-        if let Some(raw) = ctx.storage.settings().get(Self::LAST_QUERY_KEY)? {
-            if let Ok(value) = String::from_utf8(raw) {
-                if !value.is_empty() {
-                    self.search_value = value;
-                }
-            }
+        if let Some(value) = ctx
+            .storage
+            .settings()
+            .get_json::<String>(SettingKey::LastQuery)?
+            && !value.is_empty()
+        {
+            self.search_value = value;
         }
         Ok(())
     }
@@ -130,52 +307,82 @@ impl Component for TopBar {
             TopCommand::ActivateSearch => {
                 self.search_active = true;
                 self.pending_search = false;
-                self.status = Some("Type an address or transaction hash".into());
+                self.candidates = Self::with_labels(Vec::new(), ctx);
+                self.refresh_suggestions();
+                self.request_candidates(ctx);
+                self.status = Some(
+                    "Type an address, hash, block number or tag, ENS name, or a saved name".into(),
+                );
             }
             TopCommand::InputChar(c) => {
                 if !self.search_active {
                     self.search_active = true;
                 }
                 self.search_value.push(*c);
+                self.refresh_suggestions();
             }
             TopCommand::Backspace => {
                 self.search_value.pop();
+                self.refresh_suggestions();
+            }
+            TopCommand::NextSuggestion => {
+                if !self.suggestions.is_empty() {
+                    self.highlighted = Some(match self.highlighted {
+                        Some(index) => (index + 1).min(self.suggestions.len() - 1),
+                        None => 0,
+                    });
+                }
+            }
+            TopCommand::PreviousSuggestion => {
+                self.highlighted = self.highlighted.and_then(|index| index.checked_sub(1));
             }
             TopCommand::Submit => {
+                self.abort_search();
+                if let Some(suggestion) = self
+                    .highlighted
+                    .and_then(|index| self.suggestions.get(index).cloned())
+                {
+                    return self.open_suggestion(suggestion, ctx);
+                }
+                self.clear_suggestions();
                 let query = self.search_value.trim().to_string();
                 if query.is_empty() {
                     self.status = Some("Enter a value to search".into());
                     return Ok(None);
                 }
+                if !Self::is_direct_query(&query) {
+                    self.status = Some(format!("Searching saved data for \"{query}\"…"));
+                    self.pending_saved = Some(query);
+                    self.request_candidates(ctx);
+                    return Ok(None);
+                }
                 self.pending_search = true;
-                let commands = ctx.commands.clone();
-                let query_for_task = query.clone();
-                commands.spawn_async(move || {
-                    let query_clone = query_for_task.clone();
-                    async move {
-                        sleep(Duration::from_millis(400)).await;
-                        match TopBar::decode_query(&query_clone) {
-                            Ok(entity) => Message::SearchCompleted {
-                                query: query_clone,
-                                entity,
-                            },
-                            Err(error) => Message::SearchFailed {
-                                query: query_clone,
-                                error,
-                            },
-                        }
-                    }
-                });
+                let secrets = ctx.state.secrets.clone();
+                let task_query = query.clone();
+                let providers = ctx.commands.providers().clone();
+                self.search_task =
+                    Some(ctx.commands.spawn_cancellable(move || {
+                        search::resolve(providers, task_query, secrets)
+                    }));
                 self.status = Some(format!("Searching for {query}…"));
                 return Ok(Some(Action::LoadingStarted(FocusedPane::Top)));
             }
+            TopCommand::QuickOpen(query) => {
+                self.search_value = query.trim().to_string();
+                return self.update(&TopCommand::Submit, ctx);
+            }
             TopCommand::Cancel => {
+                let aborted = self.abort_search();
                 self.search_active = false;
-                self.pending_search = false;
+                self.clear_suggestions();
                 self.status = Some("Search cancelled".into());
+                if aborted {
+                    return Ok(Some(Action::LoadingFinished(FocusedPane::Top)));
+                }
             }
             TopCommand::SearchCompleted { query, entity } => {
                 self.pending_search = false;
+                self.search_task = None;
                 self.status = Some(match entity {
                     SelectedEntity::Address(addr) => {
                         format!("Loaded address {}", short_hex(&addr.address))
@@ -186,18 +393,47 @@ impl Component for TopBar {
                 });
                 self.search_value = query.clone();
                 self.search_active = false;
-                ctx.storage
-                    .settings()
-                    .put(Self::LAST_QUERY_KEY, query.as_bytes())?;
+                let query = query.clone();
+                ctx.storage.write("search history", move |storage| {
+                    let settings = storage.settings();
+                    settings.set_json(SettingKey::LastQuery, &query)?;
+                    let mut history = settings
+                        .get_json::<Vec<String>>(SettingKey::SearchHistory)?
+                        .unwrap_or_default();
+                    suggestions::push_history(&mut history, &query);
+                    settings.set_json(SettingKey::SearchHistory, &history)
+                });
             }
             TopCommand::SearchFailed { query, error } => {
                 self.pending_search = false;
+                self.search_task = None;
                 self.status = Some(format!("Failed to load {}: {}", short_hex(query), error));
             }
+            TopCommand::CandidatesLoaded(result) => {
+                if !std::mem::take(&mut self.awaiting_candidates) {
+                    return Ok(None);
+                }
+                let stored = match result {
+                    Ok(stored) => stored.clone(),
+                    Err(err) => {
+                        self.status = Some(format!("Could not read saved searches: {err}"));
+                        Vec::new()
+                    }
+                };
+                let candidates = Self::with_labels(stored, ctx);
+                if let Some(query) = self.pending_saved.take() {
+                    return self.search_saved(&query, candidates, ctx);
+                }
+                if self.search_active && !self.pending_search {
+                    self.candidates = candidates;
+                    self.refresh_suggestions();
+                }
+            }
             TopCommand::ShowStatus(message) => {
                 self.status = Some(message.clone());
                 self.search_active = false;
                 self.pending_search = false;
+                self.clear_suggestions();
             }
         }
         Ok(None)
@@ -224,7 +460,14 @@ impl Component for TopBar {
             }
             None => "No selection".to_string(),
         };
-        let title = Line::from(format!("[1] {} • {}", self.title, descriptor));
+        // Narrow terminals stack the selection under the title, leaving room
+        // for the connection indicator on the border.
+        let compact = is_compact(area.width);
+        let title = if compact {
+            Line::from(format!("[1] {}", self.title))
+        } else {
+            Line::from(format!("[1] {} • {}", self.title, descriptor))
+        };
         let style = if is_focused {
             Style::default()
                 .fg(Color::Cyan)
@@ -235,6 +478,12 @@ impl Component for TopBar {
         };
 
         let mut lines = Vec::new();
+        if compact {
+            lines.push(Line::from(Span::styled(
+                descriptor,
+                Style::default().fg(Color::White),
+            )));
+        }
         if self.search_active {
             let prompt_style = if self.pending_search {
                 Style::default().fg(Color::Yellow)
@@ -246,9 +495,17 @@ impl Component for TopBar {
                 prompt_style.add_modifier(Modifier::BOLD),
             );
             lines.push(Line::from(vec![hint]));
-            lines.push(Line::from("Enter to submit • Esc to cancel"));
+            lines.push(Line::from(if compact {
+                "Enter submit • ↑/↓ pick • Esc cancel"
+            } else {
+                "Enter to submit • ↑/↓ pick a suggestion • Esc to cancel"
+            }));
         } else {
-            lines.push(Line::from("Press / to search addresses or transactions"));
+            lines.push(Line::from(if compact {
+                "/ search • Ctrl+V clipboard"
+            } else {
+                "Press / to search addresses, transactions or blocks • Ctrl+V opens the clipboard"
+            }));
         }
         if let Some(status) = self.status_line() {
             lines.push(status);
@@ -270,13 +527,34 @@ impl Component for TopBar {
             )));
         }
 
+        let mut block = Block::bordered().title(title.style(style));
+        if let Some(badge) = loading_title(&ctx.state.loading.top) {
+            block = block.title(badge);
+        }
+        if let Some(indicator) = ws_indicator(ctx) {
+            block = block.title(indicator.right_aligned());
+        }
         let widget = Paragraph::new(lines)
             .style(Style::default().fg(Color::Gray))
-            .block(Block::bordered().title(title.style(style)));
+            .block(block);
         frame.render_widget(widget, area);
     }
 
     fn tick(&mut self, _ctx: &mut AppContext<'_>) -> AppResult<Option<Action>> {
         Ok(None)
     }
+    fn key_hints(&self, _ctx: &AppView<'_>) -> Vec<KeyHint> {
+        if self.search_active {
+            vec![
+                KeyHint::new("Enter", "Submit"),
+                KeyHint::new("↑/↓", "Suggestion"),
+                KeyHint::new("Esc", "Cancel"),
+            ]
+        } else {
+            vec![
+                KeyHint::new("/", "Search"),
+                KeyHint::new("Ctrl+V", "Open clipboard"),
+            ]
+        }
+    }
 }
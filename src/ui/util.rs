@@ -1,3 +1,51 @@
+use crate::app::{
+    MainViewTab, PaneLoading,
+    ws::{ConnectionState, WsConnections},
+};
+use alloy::primitives::{Address, U256, utils::format_units};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+};
+use std::{
+    collections::HashMap,
+    ops::Range,
+    time::{Duration, Instant},
+};
+
+/// Terminals narrower than this get the compact layout: a taller top bar
+/// with its lines stacked, so a tmux split stays usable.
+pub const COMPACT_WIDTH: u16 = 100;
+
+pub fn is_compact(width: u16) -> bool {
+    width < COMPACT_WIDTH
+}
+
+/// Ether amount for narrow columns: at most four decimals and no unit, with
+/// `<0.0001` for dust rather than a misleading `0`.
+pub fn compact_eth(value: &U256) -> String {
+    if value.is_zero() {
+        return "0".into();
+    }
+    let Ok(eth) = format_units(*value, "ether") else {
+        return format!("{value} wei");
+    };
+    let (whole, fraction) = eth.split_once('.').unwrap_or((&eth, ""));
+    let fraction = fraction[..fraction.len().min(4)].trim_end_matches('0');
+    match (whole, fraction) {
+        ("0", "") => "<0.0001".into(),
+        (whole, "") => whole.to_string(),
+        (whole, fraction) => format!("{whole}.{fraction}"),
+    }
+}
+
+/// How long each spinner frame shows; panes redraw this often while loading.
+pub const SPINNER_FRAME: Duration = Duration::from_millis(100);
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+/// A fetch running longer than this is flagged in its pane title.
+pub const SLOW_LOADING: Duration = Duration::from_secs(5);
+
 pub fn short_hex(value: &str) -> String {
     let trimmed = value.trim();
     if trimmed.len() <= 10 {
@@ -9,3 +57,245 @@ pub fn short_hex(value: &str) -> String {
     let suffix = &trimmed[trimmed.len() - suffix_len..];
     format!("{}...{}", prefix, suffix)
 }
+
+/// EIP-55 checksummed form of `value`, or `value` unchanged when it is not an address.
+pub fn checksum_address(value: &str) -> String {
+    value
+        .trim()
+        .parse::<Address>()
+        .map(|address| address.to_checksum(None))
+        .unwrap_or_else(|_| value.to_string())
+}
+
+/// Hide all but the last four characters of a credential.
+pub fn mask_secret(value: &str) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    let visible = if chars.len() > 8 { 4 } else { 0 };
+    let hidden = chars.len() - visible;
+    let mut masked = "•".repeat(hidden.min(12));
+    masked.extend(&chars[hidden..]);
+    masked
+}
+
+/// Label and color for a WebSocket endpoint's connection, if it was used
+/// this session.
+pub fn ws_status(connections: &WsConnections, url: &str) -> Option<(String, Color)> {
+    Some(match connections.state(url)? {
+        ConnectionState::Connecting => ("connecting".into(), Color::Yellow),
+        ConnectionState::Connected => ("live".into(), Color::Green),
+        ConnectionState::Reconnecting {
+            attempt, retry_at, ..
+        } => (
+            format!(
+                "reconnect #{attempt} in {}s",
+                retry_at.saturating_duration_since(Instant::now()).as_secs()
+            ),
+            Color::Yellow,
+        ),
+        ConnectionState::Disconnected(_) => ("disconnected".into(), Color::Red),
+    })
+}
+
+/// `YYYY-MM-DD HH:MM UTC` for a Unix timestamp (proleptic Gregorian calendar).
+pub fn format_timestamp(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let minutes = secs % 86_400 / 60;
+    // Civil-from-days, counting eras of 400 years from 0000-03-01.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02} UTC",
+        minutes / 60,
+        minutes % 60
+    )
+}
+
+/// `line` cut to `width` columns, ending in `…` when anything was cut.
+/// Counts one column per character, which holds for the text panes show.
+pub fn truncate_line(line: Line<'static>, width: usize) -> Line<'static> {
+    if line.width() <= width || width == 0 {
+        return line;
+    }
+    let mut left = width - 1;
+    let mut spans = Vec::new();
+    for span in line.spans {
+        if left == 0 {
+            break;
+        }
+        let taken: String = span.content.chars().take(left).collect();
+        left -= taken.chars().count();
+        spans.push(Span::styled(taken, span.style));
+    }
+    spans.push(Span::raw("…"));
+    Line::from(spans).style(line.style)
+}
+
+/// Spinner and elapsed time for a pane title, e.g. `⠹ 1.4s`; turns into a
+/// yellow warning once the fetch passes [`SLOW_LOADING`].
+pub fn loading_badge(elapsed: Duration) -> Span<'static> {
+    let secs = elapsed.as_secs_f64();
+    if elapsed >= SLOW_LOADING {
+        return Span::styled(
+            format!(" ⚠ {secs:.1}s, still loading "),
+            Style::default().fg(Color::Yellow),
+        );
+    }
+    let frame = (elapsed.as_millis() / SPINNER_FRAME.as_millis()) as usize % SPINNER.len();
+    Span::styled(
+        format!(" {} {secs:.1}s ", SPINNER[frame]),
+        Style::default().fg(Color::Cyan),
+    )
+}
+
+/// Title line with the [`loading_badge`] of `pane`, when it is loading.
+pub fn loading_title(pane: &PaneLoading) -> Option<Line<'static>> {
+    pane.elapsed()
+        .map(|elapsed| Line::from(loading_badge(elapsed)))
+}
+
+/// Clamp a `width` × `height` box to `area` and center it; used by modal dialogs.
+pub fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width.saturating_sub(width)) / 2,
+        y: area.y + (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    }
+}
+
+/// First visible row of a scrolling table, kept across frames so only the
+/// rows in view are built.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TableScroll {
+    offset: usize,
+}
+
+impl TableScroll {
+    /// Rows to build for a `viewport` rows tall table that keeps `selected` in
+    /// view, scrolling as little as possible from the previous frame.
+    pub fn window(&mut self, selected: usize, len: usize, viewport: usize) -> Range<usize> {
+        let viewport = viewport.max(1);
+        if selected < self.offset {
+            self.offset = selected;
+        } else if selected >= self.offset + viewport {
+            self.offset = selected + 1 - viewport;
+        }
+        self.offset = self.offset.min(len.saturating_sub(viewport));
+        self.offset..(self.offset + viewport).min(len)
+    }
+}
+
+/// A pane's [`TableScroll`]s, one per table and address, so each table
+/// picks up where it was left for that address.
+#[derive(Debug, Default)]
+pub struct TableScrolls(HashMap<(MainViewTab, String), TableScroll>);
+
+impl TableScrolls {
+    pub fn of(&mut self, tab: MainViewTab, address: &str) -> &mut TableScroll {
+        self.0
+            .entry((tab, address.to_ascii_lowercase()))
+            .or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums_addresses_for_display() {
+        let checksummed = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+        assert_eq!(
+            checksum_address("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266"),
+            checksummed
+        );
+        assert_eq!(checksum_address("Self"), "Self");
+    }
+
+    #[test]
+    fn mask_secret_keeps_only_last_four() {
+        assert_eq!(mask_secret("H43UPPAU7H4KBX99"), "••••••••••••BX99");
+        assert_eq!(mask_secret("short"), "•••••");
+        assert_eq!(mask_secret(""), "");
+    }
+
+    #[test]
+    fn formats_unix_timestamps_as_utc() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00 UTC");
+        // The Merge block (15537393).
+        assert_eq!(format_timestamp(1_663_224_179), "2022-09-15 06:42 UTC");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00 UTC");
+    }
+
+    #[test]
+    fn compact_eth_keeps_four_decimals() {
+        let wei = |value: &str| U256::from_str_radix(value, 10).unwrap();
+        assert_eq!(compact_eth(&U256::ZERO), "0");
+        assert_eq!(compact_eth(&wei("1234567890000000000")), "1.2345");
+        assert_eq!(compact_eth(&wei("5000000000000000000000")), "5000");
+        assert_eq!(compact_eth(&wei("20000000000000")), "<0.0001");
+    }
+
+    #[test]
+    fn truncated_lines_end_in_an_ellipsis() {
+        let line = Line::from(vec![Span::raw("Calldata: "), Span::raw("0xa9059cbb0000")]);
+        assert_eq!(
+            truncate_line(line.clone(), 14).to_string(),
+            "Calldata: 0xa…"
+        );
+        assert_eq!(
+            truncate_line(line.clone(), 24).to_string(),
+            line.to_string()
+        );
+        assert_eq!(truncate_line(Line::from("abc"), 0).to_string(), "abc");
+    }
+
+    #[test]
+    fn loading_badge_spins_then_warns() {
+        assert_eq!(loading_badge(Duration::ZERO).content, " ⠋ 0.0s ");
+        assert_eq!(
+            loading_badge(Duration::from_millis(1_300)).content,
+            " ⠸ 1.3s "
+        );
+        let slow = loading_badge(Duration::from_millis(6_300));
+        assert_eq!(slow.content, " ⚠ 6.3s, still loading ");
+        assert_eq!(slow.style.fg, Some(Color::Yellow));
+    }
+
+    #[test]
+    fn table_window_follows_selection() {
+        let mut scroll = TableScroll::default();
+        assert_eq!(scroll.window(0, 5_000, 10), 0..10);
+        assert_eq!(scroll.window(9, 5_000, 10), 0..10);
+        assert_eq!(scroll.window(10, 5_000, 10), 1..11);
+        assert_eq!(scroll.window(4, 5_000, 10), 1..11);
+        assert_eq!(scroll.window(0, 5_000, 10), 0..10);
+        assert_eq!(scroll.window(4_999, 5_000, 10), 4_990..5_000);
+        assert_eq!(scroll.window(2, 3, 10), 0..3);
+        assert_eq!(scroll.window(0, 0, 10), 0..0);
+    }
+
+    #[test]
+    fn table_scrolls_are_kept_per_table_and_address() {
+        let mut scrolls = TableScrolls::default();
+        let (transactions, events) = (MainViewTab::AddressTransactions, MainViewTab::AddressEvents);
+        assert_eq!(scrolls.of(transactions, "0xAB").window(50, 100, 10), 41..51);
+        assert_eq!(scrolls.of(events, "0xab").window(0, 100, 10), 0..10);
+        assert_eq!(scrolls.of(transactions, "0xcd").window(5, 100, 10), 0..10);
+        assert_eq!(scrolls.of(transactions, "0xab").window(45, 100, 10), 41..51);
+    }
+}